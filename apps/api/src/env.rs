@@ -7,6 +7,11 @@ fn default_port() -> u16 {
     3001
 }
 
+#[cfg(feature = "local-stt")]
+fn default_language_id_concurrency() -> usize {
+    2
+}
+
 #[derive(Deserialize)]
 pub struct Env {
     #[serde(default = "default_port")]
@@ -31,6 +36,9 @@ pub struct Env {
     pub support_database: hypr_api_support::SupportDatabaseEnv,
     #[serde(flatten)]
     pub chatwoot: hypr_api_support::ChatwootEnv,
+    /// SQLite database for `/sync`, e.g. `sqlite:///data/sync.db`. Required in production.
+    #[serde(default, deserialize_with = "hypr_api_env::filter_empty")]
+    pub sync_database_url: Option<String>,
 
     pub exa_api_key: String,
     pub jina_api_key: String,
@@ -42,6 +50,14 @@ pub struct Env {
     pub llm: hypr_llm_proxy::Env,
     #[serde(flatten)]
     pub stt: hypr_transcribe_proxy::Env,
+    /// Whisper ggml model used to identify the spoken language of batch uploads.
+    /// Only read when built with the `local-stt` feature.
+    #[serde(default, deserialize_with = "hypr_api_env::filter_empty")]
+    pub language_id_model_path: Option<String>,
+    /// Detections that may run at once; uploads beyond that keep their declared languages.
+    #[cfg(feature = "local-stt")]
+    #[serde(default = "default_language_id_concurrency")]
+    pub language_id_concurrency: usize,
 }
 
 static ENV: OnceLock<Env> = OnceLock::new();
@@ -3,6 +3,7 @@ mod env;
 mod observability;
 mod openapi;
 mod rate_limit;
+mod self_host;
 
 use std::net::SocketAddr;
 use std::num::NonZeroU32;
@@ -39,6 +40,7 @@ async fn app() -> Router {
     let stt_config = hypr_transcribe_proxy::SttProxyConfig::new(&env.stt, &env.supabase)
         .with_hyprnote_routing(hypr_transcribe_proxy::HyprnoteRoutingConfig::default())
         .with_analytics(analytics.clone());
+    let stt_config = with_language_detector(stt_config, env);
 
     let stt_rate_limit = rate_limit::RateLimitState::builder()
         .pro(
@@ -88,6 +90,14 @@ async fn app() -> Router {
         &env.chatwoot,
         auth_state_support.clone(),
     );
+    let sync_config = hypr_api_sync::SyncConfig::new(
+        &env.supabase.supabase_url,
+        &env.supabase.supabase_anon_key,
+        build_sync_store(env).await,
+    )
+    .with_auth(Arc::new(hypr_supabase_auth::server::SupabaseAuth::new(
+        &env.supabase.supabase_url,
+    )));
     let research_config = hypr_api_research::ResearchConfig {
         exa_api_key: env.exa_api_key.clone(),
         jina_api_key: env.jina_api_key.clone(),
@@ -147,6 +157,10 @@ async fn app() -> Router {
         .nest("/subscription", subscription_router.clone())
         .nest("/rpc", subscription_router.clone())
         .nest("/billing", subscription_router)
+        .nest(
+            "/sync",
+            hypr_api_sync::router(hypr_api_sync::AppState::new(sync_config)),
+        )
         .route_layer(middleware::from_fn(auth::sentry_and_analytics))
         .route_layer(middleware::from_fn_with_state(
             auth_state_basic,
@@ -160,14 +174,24 @@ async fn app() -> Router {
             auth::optional_auth,
         ));
 
-    Router::new()
-        .route("/health", axum::routing::get(version))
-        .route("/openapi.json", axum::routing::get(openapi_json))
+    let router = base_router()
         .merge(support_routes)
         .merge(webhook_routes)
         .merge(pro_routes)
         .merge(integration_routes)
-        .merge(auth_routes)
+        .merge(auth_routes);
+
+    with_layers(router, &env.observability)
+}
+
+fn base_router() -> Router {
+    Router::new()
+        .route("/health", axum::routing::get(version))
+        .route("/openapi.json", axum::routing::get(openapi_json))
+}
+
+fn with_layers(router: Router, observability: &'static observability::Env) -> Router {
+    router
         .layer(
             CorsLayer::new()
                 .allow_origin(cors::Any)
@@ -236,7 +260,7 @@ async fn app() -> Router {
                             hypr_observability::set_remote_parent(&span, request.headers());
                             span
                         })
-                        .on_request(|request: &Request<Body>, span: &tracing::Span| {
+                        .on_request(move |request: &Request<Body>, span: &tracing::Span| {
                             // Skip logging for health checks
                             if request.uri().path() == "/health" {
                                 return;
@@ -248,7 +272,7 @@ async fn app() -> Router {
                             {
                                 span.record("hyprnote.request.id", request_id);
                             }
-                            configure_sentry_trace_scope(span, env, SystemTime::now());
+                            configure_sentry_trace_scope(span, observability, SystemTime::now());
                             tracing::info!(
                                 parent: span,
                                 http.request.method = %request.method(),
@@ -310,6 +334,52 @@ fn build_analytics_client(env: &Env) -> Arc<hypr_analytics::AnalyticsClient> {
     Arc::new(builder.build())
 }
 
+async fn build_sync_store(env: &Env) -> Arc<dyn hypr_api_sync::SyncStore> {
+    match &env.sync_database_url {
+        Some(url) => Arc::new(
+            hypr_api_sync::SqliteStore::connect(url)
+                .await
+                .expect("failed to open SYNC_DATABASE_URL"),
+        ),
+        None if cfg!(debug_assertions) => {
+            tracing::info!("sync: dev mode, keeping records in memory");
+            Arc::new(hypr_api_sync::MemoryStore::new())
+        }
+        None => panic!("SYNC_DATABASE_URL is required in production"),
+    }
+}
+
+#[cfg(feature = "local-stt")]
+fn with_language_detector(
+    config: hypr_transcribe_proxy::SttProxyConfig,
+    env: &Env,
+) -> hypr_transcribe_proxy::SttProxyConfig {
+    let Some(path) = &env.language_id_model_path else {
+        return config;
+    };
+    match hypr_whisper_local::WhisperLanguageDetector::new(
+        path.as_str(),
+        env.language_id_concurrency,
+    ) {
+        Ok(detector) => config.with_language_detector(Arc::new(detector)),
+        Err(e) => {
+            tracing::warn!(error.message = %e, "language_id_model_load_failed");
+            config
+        }
+    }
+}
+
+#[cfg(not(feature = "local-stt"))]
+fn with_language_detector(
+    config: hypr_transcribe_proxy::SttProxyConfig,
+    env: &Env,
+) -> hypr_transcribe_proxy::SttProxyConfig {
+    if env.language_id_model_path.is_some() {
+        tracing::warn!("language_id_model_path_ignored_without_local_stt_feature");
+    }
+    config
+}
+
 fn main() -> std::io::Result<()> {
     rustls::crypto::aws_lc_rs::default_provider()
         .install_default()
@@ -317,10 +387,17 @@ fn main() -> std::io::Result<()> {
 
     let _ = openapi::write_openapi_json();
 
-    let env = env();
+    let self_hosted = self_host::config().map_err(std::io::Error::other)?;
+    let (port, sentry_dsn, observability_env) = match self_hosted {
+        Some(config) => (config.port, &config.sentry_dsn, &config.observability),
+        None => {
+            let env = env();
+            (env.port, &env.sentry_dsn, &env.observability)
+        }
+    };
 
     let _guard = sentry::init(sentry::ClientOptions {
-        dsn: env.sentry_dsn.as_ref().and_then(|s| s.parse().ok()),
+        dsn: sentry_dsn.as_ref().and_then(|s| s.parse().ok()),
         release: option_env!("APP_VERSION").map(|v| format!("hyprnote-api@{}", v).into()),
         environment: Some(
             if cfg!(debug_assertions) {
@@ -345,19 +422,34 @@ fn main() -> std::io::Result<()> {
         scope.set_tag("service.name", "api");
     });
 
-    let observability = observability::init("api", &env.observability);
+    let observability = observability::init("api", observability_env);
 
-    hypr_transcribe_proxy::ApiKeys::from(&env.stt.stt).log_configured_providers();
+    match self_hosted {
+        Some(config) => tracing::info!(
+            stt_providers = ?config.stt.providers.keys().collect::<Vec<_>>(),
+            llm = config.llm.is_some(),
+            "self_hosted_mode"
+        ),
+        None => hypr_transcribe_proxy::ApiKeys::from(&env().stt.stt).log_configured_providers(),
+    }
 
     tokio::runtime::Builder::new_multi_thread()
         .enable_all()
         .build()?
         .block_on(async {
-            let addr = SocketAddr::from(([0, 0, 0, 0], env.port));
+            let app = match self_hosted {
+                Some(config) => with_layers(
+                    base_router().merge(self_host::router(config)),
+                    &config.observability,
+                ),
+                None => app().await,
+            };
+
+            let addr = SocketAddr::from(([0, 0, 0, 0], port));
             tracing::info!(addr = %addr, "server_listening");
 
             let listener = tokio::net::TcpListener::bind(addr).await.unwrap();
-            axum::serve(listener, app().await)
+            axum::serve(listener, app)
                 .with_graceful_shutdown(shutdown_signal())
                 .await
                 .unwrap();
@@ -386,12 +478,17 @@ async fn version() -> &'static str {
     option_env!("APP_VERSION").unwrap_or("unknown")
 }
 
-fn configure_sentry_trace_scope(span: &tracing::Span, env: &Env, request_started_at: SystemTime) {
+fn configure_sentry_trace_scope(
+    span: &tracing::Span,
+    observability: &observability::Env,
+    request_started_at: SystemTime,
+) {
     let Some(trace_identifiers) = hypr_observability::span_identifiers(span) else {
         return;
     };
 
-    let trace_url = build_honeycomb_trace_url(env, &trace_identifiers, request_started_at);
+    let trace_url =
+        build_honeycomb_trace_url(observability, &trace_identifiers, request_started_at);
     sentry::configure_scope(|scope| {
         scope.set_tag(
             "hyprnote.honeycomb.trace_id",
@@ -416,14 +513,13 @@ fn configure_sentry_trace_scope(span: &tracing::Span, env: &Env, request_started
 }
 
 fn build_honeycomb_trace_url(
-    env: &Env,
+    observability: &observability::Env,
     trace_identifiers: &hypr_observability::TraceIdentifiers,
     request_started_at: SystemTime,
 ) -> Option<String> {
-    let team = env.observability.honeycomb_ui_team.as_deref()?;
-    let environment = env.observability.honeycomb_ui_environment.as_deref()?;
-    let base_url = env
-        .observability
+    let team = observability.honeycomb_ui_team.as_deref()?;
+    let environment = observability.honeycomb_ui_environment.as_deref()?;
+    let base_url = observability
         .honeycomb_ui_base_url
         .as_deref()
         .unwrap_or("https://ui.honeycomb.io")
@@ -10,7 +10,7 @@ use opentelemetry_sdk::trace::SdkTracerProvider;
 use serde::Deserialize;
 use tracing_subscriber::prelude::*;
 
-#[derive(Deserialize)]
+#[derive(Default, Deserialize)]
 pub struct Env {
     #[serde(default, deserialize_with = "hypr_api_env::filter_empty")]
     pub otel_service_name: Option<String>,
@@ -26,7 +26,7 @@ pub struct Env {
     pub honeycomb_ui_environment: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Default, Deserialize)]
 struct DirectHoneycombEnv {
     #[serde(default, deserialize_with = "hypr_api_env::filter_empty")]
     honeycomb_api_key: Option<String>,
@@ -36,7 +36,7 @@ struct DirectHoneycombEnv {
     honeycomb_dataset: Option<String>,
 }
 
-#[derive(Deserialize)]
+#[derive(Default, Deserialize)]
 struct OtelCollectorEnv {
     #[serde(default, deserialize_with = "hypr_api_env::filter_empty")]
     otel_exporter_otlp_endpoint: Option<String>,
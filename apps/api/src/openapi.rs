@@ -31,6 +31,7 @@ pub fn openapi() -> utoipa::openapi::OpenApi {
     let nango_doc = with_path_prefix(hypr_api_nango::openapi(), "/nango");
     let subscription_doc = with_path_prefix(hypr_api_subscription::openapi(), "/subscription");
     let support_doc = hypr_api_support::openapi();
+    let sync_doc = with_path_prefix(hypr_api_sync::openapi(), "/sync");
 
     doc.merge(stt_doc);
     doc.merge(llm_doc);
@@ -38,6 +39,7 @@ pub fn openapi() -> utoipa::openapi::OpenApi {
     doc.merge(nango_doc);
     doc.merge(subscription_doc);
     doc.merge(support_doc);
+    doc.merge(sync_doc);
 
     apply_bearer_auth_to_protected_paths(&mut doc);
 
@@ -102,6 +104,7 @@ fn apply_bearer_auth_to_protected_paths(doc: &mut utoipa::openapi::OpenApi) {
         if path.starts_with("/calendar")
             || path.starts_with("/subscription")
             || path.starts_with("/nango")
+            || path.starts_with("/sync")
         {
             set_operation_security(item);
         }
@@ -0,0 +1,364 @@
+//! Self-hosted mode: a single YAML file instead of the hosted environment. Auth is static API
+//! keys and/or an OIDC issuer, there are no entitlement checks or rate limits, and only the STT
+//! and LLM proxies are served. Billing, support and integration routes need Supabase, Stripe
+//! and Nango, so they are left out.
+//!
+//! `${VAR}` references in string values are expanded from the environment, which keeps
+//! provider keys out of the file itself. Comments and keys are left alone.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+
+use axum::{Router, middleware};
+use hypr_api_auth::{ApiKey, OidcAuth};
+use owhisper_client::Provider;
+use serde::Deserialize;
+
+use crate::auth::{self, AuthState};
+use crate::observability;
+
+/// Path of the config file; when set, the server starts in self-hosted mode.
+pub const CONFIG_PATH_ENV: &str = "CHAR_API_CONFIG";
+
+#[derive(Deserialize)]
+pub struct Config {
+    #[serde(default = "default_port")]
+    pub port: u16,
+    #[serde(default)]
+    pub sentry_dsn: Option<String>,
+    #[serde(default)]
+    pub observability: observability::Env,
+    pub auth: AuthConfig,
+    #[serde(default)]
+    pub stt: SttConfig,
+    #[serde(default)]
+    pub llm: Option<LlmConfig>,
+}
+
+#[derive(Deserialize)]
+pub struct AuthConfig {
+    #[serde(default)]
+    pub api_keys: Vec<ApiKeyConfig>,
+    #[serde(default)]
+    pub oidc: Option<OidcConfig>,
+}
+
+#[derive(Deserialize)]
+pub struct ApiKeyConfig {
+    /// Reported as the user id in logs and analytics.
+    pub name: String,
+    pub key: String,
+}
+
+#[derive(Deserialize)]
+pub struct OidcConfig {
+    pub issuer: String,
+    #[serde(default)]
+    pub audience: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+pub struct SttConfig {
+    #[serde(default)]
+    pub default_provider: Option<String>,
+    /// Keyed by provider name, e.g. `deepgram` or `openai`.
+    #[serde(default)]
+    pub providers: BTreeMap<String, SttProviderConfig>,
+}
+
+#[derive(Deserialize)]
+pub struct SttProviderConfig {
+    #[serde(default)]
+    pub api_key: String,
+    /// Overrides the provider's endpoint, e.g. for a local server speaking the same protocol.
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct LlmConfig {
+    /// Any OpenAI-compatible API base, e.g. `http://localhost:11434/v1` (vLLM, Ollama,
+    /// LiteLLM, ...); `/chat/completions` is appended. Without it requests go to OpenRouter.
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Send requests to Amazon Bedrock in this region instead, with a Bedrock API key as
+    /// `api_key`. `url` then overrides the regional endpoint, e.g. for a VPC endpoint.
+    #[serde(default)]
+    pub bedrock_region: Option<String>,
+    #[serde(default)]
+    pub api_key: String,
+    /// Model candidates per key (`default`, `tool_calling`, `audio`, `chat`, `enhance`,
+    /// `title`). Empty keeps whatever model the client asked for.
+    #[serde(default)]
+    pub models: HashMap<String, Vec<String>>,
+}
+
+fn default_port() -> u16 {
+    3000
+}
+
+static CONFIG: OnceLock<Option<Config>> = OnceLock::new();
+
+/// The self-hosted config, or `None` when `CHAR_API_CONFIG` is unset.
+pub fn config() -> Result<Option<&'static Config>, String> {
+    if let Some(config) = CONFIG.get() {
+        return Ok(config.as_ref());
+    }
+
+    let config = match std::env::var_os(CONFIG_PATH_ENV) {
+        Some(path) => {
+            let path = Path::new(&path);
+            Some(load(path).map_err(|e| format!("Failed to load {}: {e}", path.display()))?)
+        }
+        None => None,
+    };
+    Ok(CONFIG.get_or_init(|| config).as_ref())
+}
+
+fn load(path: &Path) -> Result<Config, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    parse(&text)
+}
+
+fn parse(text: &str) -> Result<Config, String> {
+    let mut value: serde_yaml::Value = serde_yaml::from_str(text).map_err(|e| e.to_string())?;
+    expand_env(&mut value)?;
+    let config: Config = serde_yaml::from_value(value).map_err(|e| e.to_string())?;
+    config.validate()?;
+    Ok(config)
+}
+
+fn expand_env(value: &mut serde_yaml::Value) -> Result<(), String> {
+    match value {
+        serde_yaml::Value::String(text) => {
+            *text = shellexpand::env(text)
+                .map_err(|e| e.to_string())?
+                .into_owned();
+        }
+        serde_yaml::Value::Sequence(items) => {
+            for item in items {
+                expand_env(item)?;
+            }
+        }
+        serde_yaml::Value::Mapping(map) => {
+            for (_, item) in map.iter_mut() {
+                expand_env(item)?;
+            }
+        }
+        serde_yaml::Value::Tagged(tagged) => expand_env(&mut tagged.value)?,
+        serde_yaml::Value::Null | serde_yaml::Value::Bool(_) | serde_yaml::Value::Number(_) => {}
+    }
+    Ok(())
+}
+
+impl Config {
+    fn validate(&self) -> Result<(), String> {
+        if self.auth.api_keys.is_empty() && self.auth.oidc.is_none() {
+            return Err("auth needs at least one of `api_keys` or `oidc`".into());
+        }
+        if let Some(key) = self.auth.api_keys.iter().find(|k| k.key.is_empty()) {
+            return Err(format!("api key '{}' is empty", key.name));
+        }
+        self.stt.providers()?;
+        self.stt.default_provider()?;
+        Ok(())
+    }
+}
+
+impl AuthConfig {
+    fn state(&self) -> AuthState {
+        let api_keys = self
+            .api_keys
+            .iter()
+            .map(|k| ApiKey {
+                subject: k.name.clone(),
+                key: k.key.clone(),
+            })
+            .collect();
+
+        match &self.oidc {
+            Some(oidc) => AuthState::oidc(OidcAuth::new(&oidc.issuer, oidc.audience.clone()))
+                .with_api_keys(api_keys),
+            None => AuthState::api_keys(api_keys),
+        }
+    }
+}
+
+impl SttConfig {
+    fn providers(&self) -> Result<Vec<(Provider, &SttProviderConfig)>, String> {
+        self.providers
+            .iter()
+            .map(|(name, config)| {
+                name.parse::<Provider>()
+                    .map(|provider| (provider, config))
+                    .map_err(|_| format!("unknown stt provider '{name}'"))
+            })
+            .collect()
+    }
+
+    fn default_provider(&self) -> Result<Option<Provider>, String> {
+        match &self.default_provider {
+            Some(name) => {
+                let provider = name
+                    .parse::<Provider>()
+                    .map_err(|_| format!("unknown stt provider '{name}'"))?;
+                if !self.providers.contains_key(name) {
+                    return Err(format!("default stt provider '{name}' is not configured"));
+                }
+                Ok(Some(provider))
+            }
+            None if self.providers.len() > 1 => {
+                Err("stt.default_provider is required with more than one provider".into())
+            }
+            None => Ok(self.providers()?.first().map(|(provider, _)| *provider)),
+        }
+    }
+
+    fn proxy_config(&self) -> hypr_transcribe_proxy::SttProxyConfig {
+        let providers = self.providers().expect("validated on load");
+        let api_keys = providers
+            .iter()
+            .map(|(provider, config)| (*provider, config.api_key.clone()))
+            .collect();
+
+        let mut config = hypr_transcribe_proxy::SttProxyConfig::from_api_keys(api_keys);
+        for (provider, provider_config) in providers {
+            if let Some(url) = &provider_config.url {
+                config = config.with_upstream_url(provider, url);
+            }
+        }
+        if let Some(provider) = self.default_provider().expect("validated on load") {
+            config = config.with_default_provider(provider);
+        }
+        config
+    }
+}
+
+impl LlmConfig {
+    fn proxy_config(&self) -> hypr_llm_proxy::LlmProxyConfig {
+        let mut config = hypr_llm_proxy::LlmProxyConfig::new(self.api_key.as_str());
+        match (&self.bedrock_region, &self.url) {
+            (Some(region), url) => {
+                let provider = match url {
+                    Some(url) => hypr_llm_proxy::provider::BedrockProvider::with_base_url(url),
+                    None => hypr_llm_proxy::provider::BedrockProvider::new(region),
+                };
+                config = config.with_provider(Arc::new(provider));
+            }
+            (None, Some(url)) => {
+                config = config.with_provider(Arc::new(
+                    hypr_llm_proxy::provider::OpenAiCompatibleProvider::new(url),
+                ));
+            }
+            (None, None) => {}
+        }
+        // The hosted defaults are OpenRouter model ids, which other providers won't know.
+        if self.url.is_some() || self.bedrock_region.is_some() || !self.models.is_empty() {
+            config = config.with_model_resolver(Arc::new(
+                hypr_llm_proxy::StaticModelResolver::new(self.models.clone()),
+            ));
+        }
+        config
+    }
+}
+
+/// The authenticated STT and LLM routes. Every valid key or token gets full access.
+pub fn router(config: &'static Config) -> Router {
+    let analytics = Arc::new(hypr_analytics::AnalyticsClientBuilder::default().build());
+
+    let stt_config = config.stt.proxy_config().with_analytics(analytics.clone());
+    let mut routes = Router::new()
+        .merge(hypr_transcribe_proxy::listen_router(stt_config.clone()))
+        .nest("/stt", hypr_transcribe_proxy::router(stt_config));
+
+    if let Some(llm) = &config.llm {
+        let llm_config = llm.proxy_config().with_analytics(analytics);
+        routes = routes
+            .merge(hypr_llm_proxy::chat_completions_router(llm_config.clone()))
+            .nest("/llm", hypr_llm_proxy::router(llm_config));
+    }
+
+    routes
+        .route_layer(middleware::from_fn(auth::sentry_and_analytics))
+        .route_layer(middleware::from_fn_with_state(
+            config.auth.state(),
+            auth::require_auth,
+        ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AUTH: &str = "auth:\n  api_keys:\n    - name: desktop\n      key: secret\n";
+
+    fn parse_with(rest: &str) -> Result<Config, String> {
+        parse(&format!("{AUTH}{rest}"))
+    }
+
+    #[test]
+    fn expands_env_in_values_only() {
+        let home = std::env::var("HOME").unwrap();
+        let config = parse_with(
+            "stt:\n  providers:\n    deepgram:\n      api_key: ${HOME}\n      # url: ${CHAR_TEST_UNSET_VAR}\n",
+        )
+        .unwrap();
+
+        assert_eq!(config.stt.providers["deepgram"].api_key, home);
+    }
+
+    #[test]
+    fn unset_env_in_a_value_is_an_error() {
+        let Err(e) = parse_with("llm:\n  api_key: ${CHAR_TEST_UNSET_VAR}\n") else {
+            panic!("expected an error for an unset variable");
+        };
+        assert!(e.contains("CHAR_TEST_UNSET_VAR"));
+    }
+
+    #[test]
+    fn validate() {
+        let cases: &[(&str, &str, Option<&str>)] = &[
+            ("no_auth", "auth: {}\n", Some("auth needs at least one")),
+            (
+                "empty_key",
+                "auth:\n  api_keys:\n    - name: desktop\n      key: \"\"\n",
+                Some("api key 'desktop' is empty"),
+            ),
+            (
+                "unknown_provider",
+                "stt:\n  providers:\n    nope: {}\n",
+                Some("unknown stt provider 'nope'"),
+            ),
+            (
+                "ambiguous_default",
+                "stt:\n  providers:\n    deepgram: {}\n    soniox: {}\n",
+                Some("default_provider is required"),
+            ),
+            (
+                "default_not_configured",
+                "stt:\n  default_provider: soniox\n  providers:\n    deepgram: {}\n",
+                Some("'soniox' is not configured"),
+            ),
+            (
+                "single_provider",
+                "stt:\n  providers:\n    deepgram: {}\n",
+                None,
+            ),
+        ];
+
+        for (name, yaml, expected) in cases {
+            let yaml = if yaml.starts_with("auth:") {
+                yaml.to_string()
+            } else {
+                format!("{AUTH}{yaml}")
+            };
+            match (parse(&yaml), expected) {
+                (Ok(_), None) => {}
+                (Err(e), Some(expected)) => assert!(e.contains(expected), "{name}: {e}"),
+                (Ok(_), Some(expected)) => panic!("{name}: expected error '{expected}'"),
+                (Err(e), None) => panic!("{name}: unexpected error '{e}'"),
+            }
+        }
+    }
+}
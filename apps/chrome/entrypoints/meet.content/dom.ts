@@ -76,6 +76,31 @@ export function getMuteState(root: ParentNode) {
   return false;
 }
 
+function readParticipantTile(participant: HTMLElement) {
+  const selfNameElement =
+    participant.querySelector<HTMLElement>("[data-self-name]");
+  const labelElement = participant.querySelector<HTMLElement>(
+    "[aria-label], span[dir='auto'], span",
+  );
+
+  const rawName =
+    selfNameElement?.getAttribute("data-self-name") ||
+    labelElement?.getAttribute("aria-label") ||
+    labelElement?.textContent ||
+    participant.getAttribute("aria-label") ||
+    "";
+
+  const name = normalizeParticipantName(rawName);
+  if (!name) {
+    return null;
+  }
+
+  return {
+    name,
+    isSelf: Boolean(selfNameElement) || isSelfParticipant(name),
+  };
+}
+
 export function getParticipantNames(root: ParentNode): Participant[] {
   const names = new Map<string, Participant>();
 
@@ -88,25 +113,12 @@ export function getParticipantNames(root: ParentNode): Participant[] {
       .getAttribute("data-participant-id")
       ?.trim();
 
-    const selfNameElement =
-      participant.querySelector<HTMLElement>("[data-self-name]");
-    const labelElement = participant.querySelector<HTMLElement>(
-      "[aria-label], span[dir='auto'], span",
-    );
-
-    const rawName =
-      selfNameElement?.getAttribute("data-self-name") ||
-      labelElement?.getAttribute("aria-label") ||
-      labelElement?.textContent ||
-      participant.getAttribute("aria-label") ||
-      "";
-
-    const name = normalizeParticipantName(rawName);
-    if (!name) {
+    const tile = readParticipantTile(participant);
+    if (!tile) {
       continue;
     }
 
-    const isSelf = Boolean(selfNameElement) || isSelfParticipant(name);
+    const { name, isSelf } = tile;
     const key = participantId || name.toLowerCase();
     const existing = names.get(key);
 
@@ -125,6 +137,35 @@ export function getParticipantNames(root: ParentNode): Participant[] {
   return Array.from(names.values());
 }
 
+export function getActiveSpeakers(root: ParentNode): string[] {
+  const speakers: string[] = [];
+
+  const participantElements = root.querySelectorAll<HTMLElement>(
+    "[data-participant-id]",
+  );
+
+  for (const participant of participantElements) {
+    const speaking =
+      participant.getAttribute("data-is-speaking") === "true" ||
+      Boolean(participant.querySelector("[data-is-speaking='true']"));
+    if (!speaking) {
+      continue;
+    }
+
+    const tile = readParticipantTile(participant);
+    if (!tile || speakers.includes(tile.name)) {
+      continue;
+    }
+
+    speakers.push(tile.name);
+    if (speakers.length >= MAX_PARTICIPANTS) {
+      break;
+    }
+  }
+
+  return speakers;
+}
+
 export function isMeetPageActive(
   location: { hostname: string; pathname: string },
   root: ParentNode,
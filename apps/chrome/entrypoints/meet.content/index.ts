@@ -1,6 +1,7 @@
 import "~/assets/tailwind.css";
 
 import {
+  getActiveSpeakers,
   getMuteState,
   getParticipantNames,
   isMeetPageActive,
@@ -26,7 +27,17 @@ type MeetingEndedMessage = {
   is_active: false;
 };
 
-type HostMessage = MeetingStateMessage | MeetingEndedMessage;
+type ActiveSpeakerMessage = {
+  type: "active_speaker";
+  url: string;
+  timestamp_ms: number;
+  speakers: string[];
+};
+
+type HostMessage =
+  | MeetingStateMessage
+  | MeetingEndedMessage
+  | ActiveSpeakerMessage;
 
 let badgeElement: HTMLDivElement | null = null;
 
@@ -48,6 +59,15 @@ function buildMeetingEndedPayload(): MeetingEndedMessage {
   };
 }
 
+function buildActiveSpeakerPayload(speakers: string[]): ActiveSpeakerMessage {
+  return {
+    type: "active_speaker",
+    url: window.location.href,
+    timestamp_ms: Date.now(),
+    speakers,
+  };
+}
+
 function getBadgeClass(isConnected: boolean) {
   const stateClass = isConnected
     ? "border-[#2c7a5e] bg-[#0f2f26] text-[#8af8c5]"
@@ -108,8 +128,20 @@ export default defineContentScript({
 
     let lastPayloadKey = "";
     let lastSentAt = 0;
+    let lastSpeakersKey = "";
     let mutationTimeout: number | null = null;
 
+    const maybeSendSpeakers = () => {
+      const speakers = getActiveSpeakers(document);
+      const speakersKey = JSON.stringify(speakers);
+      if (speakersKey === lastSpeakersKey) {
+        return;
+      }
+
+      lastSpeakersKey = speakersKey;
+      void sendPayload(buildActiveSpeakerPayload(speakers));
+    };
+
     const maybeSendState = (force = false) => {
       if (!isMeetPageActive(window.location, document)) {
         if (force) {
@@ -126,13 +158,17 @@ export default defineContentScript({
         payloadKey !== lastPayloadKey ||
         now - lastSentAt >= SEND_INTERVAL_MS;
 
-      if (!shouldSend) {
-        return;
+      if (force) {
+        lastSpeakersKey = "";
+      }
+
+      if (shouldSend) {
+        lastPayloadKey = payloadKey;
+        lastSentAt = now;
+        void sendPayload(payload);
       }
 
-      lastPayloadKey = payloadKey;
-      lastSentAt = now;
-      void sendPayload(payload);
+      maybeSendSpeakers();
     };
 
     const observer = new MutationObserver(() => {
@@ -150,7 +186,12 @@ export default defineContentScript({
       childList: true,
       subtree: true,
       attributes: true,
-      attributeFilter: ["aria-label", "data-is-muted", "aria-pressed"],
+      attributeFilter: [
+        "aria-label",
+        "data-is-muted",
+        "aria-pressed",
+        "data-is-speaking",
+      ],
     });
 
     maybeSendState(true);
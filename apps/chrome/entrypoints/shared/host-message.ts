@@ -21,7 +21,17 @@ type MeetingEndedMessage = {
   is_active: false;
 };
 
-export type HostMessage = MeetingStateMessage | MeetingEndedMessage;
+type ActiveSpeakerMessage = {
+  type: "active_speaker";
+  url: string;
+  timestamp_ms: number;
+  speakers: string[];
+};
+
+export type HostMessage =
+  | MeetingStateMessage
+  | MeetingEndedMessage
+  | ActiveSpeakerMessage;
 
 function isRecord(value: unknown): value is Record<string, unknown> {
   return typeof value === "object" && value !== null;
@@ -67,6 +77,15 @@ function parseParticipant(value: unknown): Participant | null {
   };
 }
 
+function parseSpeakerName(value: unknown): string | null {
+  const name = typeof value === "string" ? value.trim() : "";
+  if (!name || name.length > MAX_PARTICIPANT_NAME_LENGTH) {
+    return null;
+  }
+
+  return name;
+}
+
 export function parseIncomingMessage(message: unknown): HostMessage | null {
   if (!isRecord(message) || typeof message.type !== "string") {
     return null;
@@ -117,5 +136,38 @@ export function parseIncomingMessage(message: unknown): HostMessage | null {
     };
   }
 
+  if (message.type === "active_speaker") {
+    const { url, timestamp_ms, speakers: rawSpeakers } = message;
+    if (
+      !isMeetUrl(url) ||
+      typeof timestamp_ms !== "number" ||
+      !Number.isSafeInteger(timestamp_ms) ||
+      timestamp_ms < 0 ||
+      !Array.isArray(rawSpeakers)
+    ) {
+      return null;
+    }
+
+    const speakers: string[] = [];
+    for (const rawSpeaker of rawSpeakers) {
+      const speaker = parseSpeakerName(rawSpeaker);
+      if (!speaker || speakers.includes(speaker)) {
+        continue;
+      }
+
+      speakers.push(speaker);
+      if (speakers.length >= MAX_PARTICIPANTS) {
+        break;
+      }
+    }
+
+    return {
+      type: "active_speaker",
+      url,
+      timestamp_ms,
+      speakers,
+    };
+  }
+
   return null;
 }
@@ -3,6 +3,7 @@ import path from "node:path";
 import { describe, expect, it } from "vitest";
 
 import {
+  getActiveSpeakers,
   getMuteState,
   getParticipantNames,
   isMeetPageActive,
@@ -84,6 +85,18 @@ describe("getParticipantNames", () => {
   });
 });
 
+describe("getActiveSpeakers", () => {
+  it("returns names of speaking tiles", () => {
+    renderFixture("meet-active-speaker.html");
+    expect(getActiveSpeakers(document)).toEqual(["Alice Kim", "Casey Rivera"]);
+  });
+
+  it("returns an empty list when nobody is speaking", () => {
+    renderFixture("meet-active-unmuted.html");
+    expect(getActiveSpeakers(document)).toEqual([]);
+  });
+});
+
 describe("isMeetPageActive", () => {
   it("returns true for a meet code pathname", () => {
     renderFixture("meet-lobby.html");
@@ -46,4 +46,30 @@ describe("parseIncomingMessage", () => {
       is_active: false,
     });
   });
+
+  it("parses active_speaker payload", () => {
+    const message = parseIncomingMessage({
+      type: "active_speaker",
+      url: "https://meet.google.com/abc-defg-hij",
+      timestamp_ms: 1712000000000,
+      speakers: [" Alice ", "", "Alice", "Bob"],
+    });
+
+    expect(message).toEqual({
+      type: "active_speaker",
+      url: "https://meet.google.com/abc-defg-hij",
+      timestamp_ms: 1712000000000,
+      speakers: ["Alice", "Bob"],
+    });
+  });
+
+  it("rejects active_speaker without timestamp", () => {
+    const message = parseIncomingMessage({
+      type: "active_speaker",
+      url: "https://meet.google.com/abc-defg-hij",
+      speakers: ["Alice"],
+    });
+
+    expect(message).toBeNull();
+  });
 });
@@ -0,0 +1,345 @@
+use std::io::{IsTerminal, Write};
+use std::path::PathBuf;
+
+use clap::{Args, Subcommand};
+use comfy_table::{ContentArrangement, Table, presets::UTF8_FULL_CONDENSED};
+use hypr_vault_backup::{BackupKey, Repository, Retention, S3Store, Secret};
+
+use crate::commands::OutputFormat;
+use crate::commands::model::settings;
+use crate::error::{CliError, CliResult};
+
+#[derive(Args, Debug)]
+pub struct RepositoryArgs {
+    /// S3-compatible endpoint, e.g. https://s3.us-east-1.amazonaws.com or http://localhost:9000
+    #[arg(long, env = "CHAR_BACKUP_ENDPOINT")]
+    endpoint: Option<String>,
+    #[arg(long, env = "CHAR_BACKUP_BUCKET")]
+    bucket: Option<String>,
+    #[arg(long, env = "CHAR_BACKUP_REGION")]
+    region: Option<String>,
+    #[arg(long, env = "CHAR_BACKUP_ACCESS_KEY_ID")]
+    access_key_id: Option<String>,
+    #[arg(long, env = "CHAR_BACKUP_SECRET_ACCESS_KEY", hide_env_values = true)]
+    secret_access_key: Option<String>,
+    /// Address the bucket as endpoint/bucket (needed for MinIO and most self-hosted servers)
+    #[arg(long, env = "CHAR_BACKUP_PATH_STYLE")]
+    path_style: bool,
+    /// Folder inside the bucket, so several vaults can share one bucket
+    #[arg(long, env = "CHAR_BACKUP_REPOSITORY", default_value = "vault")]
+    repository: String,
+    /// Base64 key printed by `char backup init`
+    #[arg(long, env = "CHAR_BACKUP_KEY", hide_env_values = true)]
+    key: Option<String>,
+    #[arg(
+        long,
+        env = "CHAR_BACKUP_PASSPHRASE",
+        hide_env_values = true,
+        conflicts_with = "key"
+    )]
+    passphrase: Option<String>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BackupCommands {
+    #[command(about = "Create an encrypted backup repository")]
+    Init {
+        #[command(flatten)]
+        repo: RepositoryArgs,
+    },
+    #[command(about = "Back up the vault")]
+    Run {
+        #[command(flatten)]
+        repo: RepositoryArgs,
+        #[arg(long, value_name = "PATH")]
+        vault: Option<PathBuf>,
+    },
+    #[command(about = "List snapshots")]
+    List {
+        #[command(flatten)]
+        repo: RepositoryArgs,
+        #[arg(long, value_enum, default_value = "pretty")]
+        format: OutputFormat,
+        #[arg(long, hide = true, conflicts_with = "format")]
+        json: bool,
+    },
+    #[command(about = "Restore a snapshot into an empty directory")]
+    Restore {
+        #[command(flatten)]
+        repo: RepositoryArgs,
+        /// Snapshot id, unique id prefix, or `latest`
+        snapshot: String,
+        #[arg(long, value_name = "PATH")]
+        to: PathBuf,
+    },
+    #[command(about = "Forget old snapshots and delete data only they used")]
+    Prune {
+        #[command(flatten)]
+        repo: RepositoryArgs,
+        #[arg(long, default_value_t = 0)]
+        keep_last: usize,
+        #[arg(long, default_value_t = 0)]
+        keep_daily: usize,
+        #[arg(long, default_value_t = 0)]
+        keep_weekly: usize,
+        #[arg(long, default_value_t = 0)]
+        keep_monthly: usize,
+    },
+}
+
+pub async fn run(command: BackupCommands) -> CliResult<()> {
+    match command {
+        BackupCommands::Init { repo } => {
+            let (secret, generated) = match (&repo.key, &repo.passphrase) {
+                (_, Some(passphrase)) => (Secret::Passphrase(passphrase.clone()), None),
+                (Some(key), None) => (Secret::Key(parse_key(key)?), None),
+                (None, None) => {
+                    let key = BackupKey::generate();
+                    (Secret::Key(key.clone()), Some(key))
+                }
+            };
+
+            let store = connect(&repo).await?;
+            Repository::init(store, secret).await.map_err(|e| {
+                CliError::operation_failed("initialize backup repository", e.to_string())
+            })?;
+
+            println!("Initialized backup repository '{}'", repo.repository);
+            if let Some(key) = generated {
+                println!();
+                println!("Backup key: {}", key.to_base64());
+                println!(
+                    "Store it somewhere safe and pass it as --key or CHAR_BACKUP_KEY. \
+                     Without it the backup cannot be decrypted."
+                );
+            }
+            Ok(())
+        }
+        BackupCommands::Run { repo, vault } => {
+            let vault = vault.unwrap_or_else(|| settings::resolve_paths().vault_base);
+            if !vault.is_dir() {
+                return Err(CliError::not_found(
+                    format!("vault '{}'", vault.display()),
+                    Some("Pass the vault directory with --vault.".to_string()),
+                ));
+            }
+
+            let repository = open(&repo).await?;
+            let summary = repository
+                .backup(&vault)
+                .await
+                .map_err(|e| CliError::operation_failed("backup", e.to_string()))?;
+
+            println!(
+                "Snapshot {}: {} files ({} changed), {} new chunks, {} uploaded",
+                summary.snapshot_id,
+                summary.files,
+                summary.changed_files,
+                summary.new_chunks,
+                format_bytes(summary.uploaded_bytes)
+            );
+            Ok(())
+        }
+        BackupCommands::List { repo, format, json } => {
+            let repository = open(&repo).await?;
+            let snapshots = repository
+                .snapshots()
+                .await
+                .map_err(|e| CliError::operation_failed("list snapshots", e.to_string()))?;
+
+            let format = if json { OutputFormat::Json } else { format };
+            match format {
+                OutputFormat::Json => {
+                    #[derive(serde::Serialize)]
+                    struct Item {
+                        id: String,
+                        created_at: String,
+                        source: String,
+                        files: usize,
+                        size: u64,
+                    }
+
+                    let items: Vec<Item> = snapshots
+                        .iter()
+                        .map(|snapshot| Item {
+                            id: snapshot.id.clone(),
+                            created_at: snapshot.created_at.to_rfc3339(),
+                            source: snapshot.source.clone(),
+                            files: snapshot.files.len(),
+                            size: snapshot.total_size(),
+                        })
+                        .collect();
+
+                    let bytes = serde_json::to_vec(&items).map_err(|e| {
+                        CliError::operation_failed("serialize snapshot list", e.to_string())
+                    })?;
+                    let mut stdout = std::io::stdout();
+                    stdout
+                        .write_all(&bytes)
+                        .and_then(|_| stdout.write_all(b"\n"))
+                        .map_err(|e| CliError::operation_failed("write output", e.to_string()))?;
+                }
+                OutputFormat::Pretty if std::io::stdout().is_terminal() => {
+                    let mut table = Table::new();
+                    table
+                        .load_preset(UTF8_FULL_CONDENSED)
+                        .set_content_arrangement(ContentArrangement::Dynamic);
+                    table.set_header(["Snapshot", "Created", "Files", "Size", "Source"]);
+                    for snapshot in &snapshots {
+                        table.add_row([
+                            snapshot.id.clone(),
+                            snapshot.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                            snapshot.files.len().to_string(),
+                            format_bytes(snapshot.total_size()),
+                            snapshot.source.clone(),
+                        ]);
+                    }
+                    println!("{table}");
+                }
+                _ => {
+                    for snapshot in &snapshots {
+                        println!(
+                            "{}\t{}\t{}\t{}\t{}",
+                            snapshot.id,
+                            snapshot.created_at.to_rfc3339(),
+                            snapshot.files.len(),
+                            snapshot.total_size(),
+                            snapshot.source
+                        );
+                    }
+                }
+            }
+            Ok(())
+        }
+        BackupCommands::Restore { repo, snapshot, to } => {
+            let repository = open(&repo).await?;
+            let snapshot = repository
+                .find_snapshot(&snapshot)
+                .await
+                .map_err(|e| match e {
+                    hypr_vault_backup::Error::SnapshotNotFound(id) => CliError::not_found(
+                        format!("snapshot '{id}'"),
+                        Some("Run `char backup list` to see available snapshots.".to_string()),
+                    ),
+                    e => CliError::operation_failed("find snapshot", e.to_string()),
+                })?;
+
+            repository
+                .restore(&snapshot, &to)
+                .await
+                .map_err(|e| CliError::operation_failed("restore", e.to_string()))?;
+
+            println!(
+                "Restored {} files from {} to {}",
+                snapshot.files.len(),
+                snapshot.id,
+                to.display()
+            );
+            Ok(())
+        }
+        BackupCommands::Prune {
+            repo,
+            keep_last,
+            keep_daily,
+            keep_weekly,
+            keep_monthly,
+        } => {
+            let retention = Retention {
+                keep_last,
+                keep_daily,
+                keep_weekly,
+                keep_monthly,
+            };
+            if retention.is_empty() {
+                return Err(CliError::required_argument(
+                    "--keep-last, --keep-daily, --keep-weekly or --keep-monthly",
+                ));
+            }
+
+            let repository = open(&repo).await?;
+            let summary = repository
+                .prune(retention)
+                .await
+                .map_err(|e| CliError::operation_failed("prune", e.to_string()))?;
+
+            for id in &summary.removed_snapshots {
+                println!("removed {id}");
+            }
+            println!(
+                "Removed {} snapshots and {} unused chunks",
+                summary.removed_snapshots.len(),
+                summary.removed_chunks
+            );
+            Ok(())
+        }
+    }
+}
+
+async fn connect(repo: &RepositoryArgs) -> CliResult<S3Store> {
+    let endpoint = repo
+        .endpoint
+        .clone()
+        .ok_or_else(|| CliError::required_argument("--endpoint (or CHAR_BACKUP_ENDPOINT)"))?;
+    let bucket = repo
+        .bucket
+        .clone()
+        .ok_or_else(|| CliError::required_argument("--bucket (or CHAR_BACKUP_BUCKET)"))?;
+    let access_key_id = repo.access_key_id.clone().ok_or_else(|| {
+        CliError::required_argument("--access-key-id (or CHAR_BACKUP_ACCESS_KEY_ID)")
+    })?;
+    let secret_access_key = repo.secret_access_key.clone().ok_or_else(|| {
+        CliError::required_argument("--secret-access-key (or CHAR_BACKUP_SECRET_ACCESS_KEY)")
+    })?;
+
+    let mut builder = hypr_s3::Client::builder()
+        .endpoint_url(endpoint)
+        .bucket(bucket)
+        .credentials(access_key_id, secret_access_key)
+        .force_path_style(repo.path_style);
+    if let Some(region) = &repo.region {
+        builder = builder.region(region);
+    }
+
+    Ok(S3Store::new(builder.build().await, &repo.repository))
+}
+
+async fn open(repo: &RepositoryArgs) -> CliResult<Repository<S3Store>> {
+    let secret = match (&repo.key, &repo.passphrase) {
+        (_, Some(passphrase)) => Secret::Passphrase(passphrase.clone()),
+        (Some(key), None) => Secret::Key(parse_key(key)?),
+        (None, None) => {
+            return Err(CliError::required_argument(
+                "--key (or CHAR_BACKUP_KEY) or --passphrase (or CHAR_BACKUP_PASSPHRASE)",
+            ));
+        }
+    };
+
+    let store = connect(repo).await?;
+    Repository::open(store, secret).await.map_err(|e| match e {
+        hypr_vault_backup::Error::NotInitialized => CliError::not_found(
+            format!("backup repository '{}'", repo.repository),
+            Some("Run `char backup init` first.".to_string()),
+        ),
+        e => CliError::operation_failed("open backup repository", e.to_string()),
+    })
+}
+
+fn parse_key(value: &str) -> CliResult<BackupKey> {
+    BackupKey::from_base64(value)
+        .map_err(|e| CliError::invalid_argument("--key", "<redacted>", e.to_string()))
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
@@ -9,6 +9,7 @@ use tokio::sync::mpsc;
 
 use crate::commands::OutputFormat;
 use crate::commands::cactus_server::resolve_and_spawn_cactus;
+use crate::commands::correct::{LlmArgs, correct_response, emit_report};
 use crate::error::{CliError, CliResult};
 
 mod runtime;
@@ -61,10 +62,15 @@ pub struct Args {
     pub output: Option<PathBuf>,
     pub format: OutputFormat,
     pub quiet: bool,
+    pub correct: Option<LlmArgs>,
+    pub report: Option<PathBuf>,
 }
 
 pub async fn run(args: Args) -> CliResult<()> {
     validate_input_path(&args.input)?;
+    if let Some(llm) = &args.correct {
+        llm.validate()?;
+    }
 
     let languages = vec![
         args.language
@@ -106,6 +112,7 @@ pub async fn run(args: Args) -> CliResult<()> {
         api_key: args.api_key,
         languages,
         keywords: args.keywords,
+        vocabulary: Default::default(),
     };
 
     let quiet = args.quiet;
@@ -194,12 +201,22 @@ pub async fn run(args: Args) -> CliResult<()> {
         progress.finish_and_clear();
     }
 
-    let response = response
+    let mut response = response
         .or_else(|| batch_response_from_streams(streamed_segments))
         .ok_or_else(|| {
             CliError::operation_failed("batch transcription", "completed without a final response")
         })?;
 
+    let report = match &args.correct {
+        Some(llm) => {
+            // The keywords the transcription was biased with are the session's vocabulary,
+            // so the correction should spell them the same way.
+            let glossary = llm.glossary(&args.keywords).await?;
+            Some(correct_response(&mut response, llm, &args.language, &glossary, quiet).await?)
+        }
+        None => None,
+    };
+
     match format {
         OutputFormat::Json => {
             write_json_response(output.as_deref(), &response).await?;
@@ -233,6 +250,10 @@ pub async fn run(args: Args) -> CliResult<()> {
         eprintln!("\x1b[2m{}\x1b[0m", parts.join(", "));
     }
 
+    if let Some(report) = report {
+        emit_report(&report, args.report.as_deref(), quiet).await?;
+    }
+
     Ok(())
 }
 
@@ -328,7 +349,7 @@ fn format_timestamp(secs: f64) -> String {
     format!("{mins:02}:{s:02}.{frac}")
 }
 
-fn format_pretty(response: &owhisper_interface::batch::Response) -> String {
+pub(crate) fn format_pretty(response: &owhisper_interface::batch::Response) -> String {
     use owhisper_interface::batch::Word;
 
     let words: Vec<&Word> = response
@@ -392,7 +413,7 @@ fn format_pretty(response: &owhisper_interface::batch::Response) -> String {
         .join("\n\n")
 }
 
-fn extract_transcript(response: &owhisper_interface::batch::Response) -> String {
+pub(crate) fn extract_transcript(response: &owhisper_interface::batch::Response) -> String {
     response
         .results
         .channels
@@ -404,7 +425,10 @@ fn extract_transcript(response: &owhisper_interface::batch::Response) -> String
         .join("\n")
 }
 
-async fn write_text_response(output: Option<&Path>, transcript: String) -> CliResult<()> {
+pub(crate) async fn write_text_response(
+    output: Option<&Path>,
+    transcript: String,
+) -> CliResult<()> {
     if let Some(path) = output {
         if let Some(parent) = path.parent() {
             tokio::fs::create_dir_all(parent).await.map_err(|e| {
@@ -422,7 +446,7 @@ async fn write_text_response(output: Option<&Path>, transcript: String) -> CliRe
     Ok(())
 }
 
-async fn write_json_response(
+pub(crate) async fn write_json_response(
     output: Option<&Path>,
     response: &owhisper_interface::batch::Response,
 ) -> CliResult<()> {
@@ -0,0 +1,111 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::LlmArgs;
+use crate::error::{CliError, CliResult};
+
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// Minimal client for an OpenAI-compatible `/chat/completions` endpoint, which covers hosted
+/// providers as well as local servers like Ollama, LM Studio and llama.cpp.
+pub(crate) struct LlmClient {
+    http: reqwest::Client,
+    endpoint: String,
+    api_key: String,
+    model: String,
+}
+
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: [ChatMessage<'a>; 2],
+    temperature: f32,
+}
+
+#[derive(Serialize)]
+struct ChatMessage<'a> {
+    role: &'static str,
+    content: &'a str,
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChatChoice {
+    message: ChatChoiceMessage,
+}
+
+#[derive(Deserialize)]
+struct ChatChoiceMessage {
+    content: Option<String>,
+}
+
+impl LlmClient {
+    pub(crate) fn new(args: &LlmArgs) -> CliResult<Self> {
+        let base_url = args.validate()?;
+
+        let http = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|e| CliError::operation_failed("create http client", e.to_string()))?;
+
+        Ok(Self {
+            http,
+            endpoint: format!("{}/chat/completions", base_url.trim_end_matches('/')),
+            api_key: args.api_key.clone(),
+            model: args.model.clone(),
+        })
+    }
+
+    pub(crate) async fn complete(&self, system: &str, user: &str) -> CliResult<String> {
+        let body = ChatRequest {
+            model: &self.model,
+            messages: [
+                ChatMessage {
+                    role: "system",
+                    content: system,
+                },
+                ChatMessage {
+                    role: "user",
+                    content: user,
+                },
+            ],
+            temperature: 0.0,
+        };
+
+        let mut request = self.http.post(&self.endpoint).json(&body);
+        if !self.api_key.is_empty() {
+            request = request.bearer_auth(&self.api_key);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| CliError::external_action_failed("llm request", e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let text = response.text().await.unwrap_or_default();
+            return Err(CliError::external_action_failed(
+                "llm request",
+                format!("{status}: {}", text.trim()),
+            ));
+        }
+
+        let parsed: ChatResponse = response
+            .json()
+            .await
+            .map_err(|e| CliError::external_action_failed("llm request", e.to_string()))?;
+
+        parsed
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.message.content)
+            .ok_or_else(|| CliError::external_action_failed("llm request", "empty response"))
+    }
+}
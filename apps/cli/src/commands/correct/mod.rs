@@ -0,0 +1,532 @@
+use std::io::IsTerminal;
+use std::ops::Range;
+use std::path::PathBuf;
+
+use comfy_table::{ContentArrangement, Table, presets::UTF8_FULL_CONDENSED};
+use hypr_transcript::{FinalizedWord, MeetingContext, TranscriptPostprocessor, WordState};
+use indicatif::{ProgressBar, ProgressStyle};
+use owhisper_interface::batch;
+
+use crate::commands::OutputFormat;
+use crate::commands::batch::{
+    extract_transcript, format_pretty, write_json_response, write_text_response,
+};
+use crate::error::{CliError, CliResult};
+
+mod llm;
+
+pub(crate) use llm::LlmClient;
+
+const DEFAULT_CHUNK_TOKENS: usize = 4000;
+
+/// The prompt is a pretty-printed `{"id","text"}` object per word, so the JSON around each word
+/// costs more than the word itself.
+const WORD_OVERHEAD_CHARS: usize = 48;
+const CHARS_PER_TOKEN: usize = 4;
+
+#[derive(Clone, Debug, clap::Args)]
+pub struct LlmArgs {
+    /// OpenAI-compatible endpoint used for correction, e.g. http://localhost:11434/v1
+    #[arg(long = "llm-base-url", env = "CHAR_LLM_BASE_URL", value_parser = crate::parse_base_url)]
+    pub base_url: Option<String>,
+    #[arg(long = "llm-api-key", env = "CHAR_LLM_API_KEY", default_value = "")]
+    pub api_key: String,
+    #[arg(long = "llm-model", env = "CHAR_LLM_MODEL", default_value = "")]
+    pub model: String,
+    /// Approximate prompt size per request; longer transcripts are split into several requests
+    #[arg(long, value_name = "TOKENS", default_value_t = DEFAULT_CHUNK_TOKENS)]
+    pub max_chunk_tokens: usize,
+    /// Name or term the correction should spell this way; repeat for several
+    #[arg(long, value_name = "TERM")]
+    pub glossary: Vec<String>,
+    /// Meeting whose participants and terms join the glossary: a session's meeting.json or a
+    /// calendar event as JSON
+    #[arg(long, value_name = "FILE")]
+    pub meeting: Option<PathBuf>,
+}
+
+impl LlmArgs {
+    /// Checks the flags a correction run needs, so `batch --correct` can fail before spending
+    /// time on transcription.
+    pub(crate) fn validate(&self) -> CliResult<&str> {
+        let base_url = self
+            .base_url
+            .as_deref()
+            .ok_or_else(|| CliError::required_argument("--llm-base-url (or CHAR_LLM_BASE_URL)"))?;
+        if self.model.is_empty() {
+            return Err(CliError::required_argument(
+                "--llm-model (or CHAR_LLM_MODEL)",
+            ));
+        }
+        Ok(base_url)
+    }
+
+    /// The correction glossary: terms from `--meeting` first, then `keywords` and `--glossary`.
+    pub(crate) async fn glossary(&self, keywords: &[String]) -> CliResult<Vec<String>> {
+        let terms: Vec<String> = keywords
+            .iter()
+            .chain(self.glossary.iter().filter(|term| !keywords.contains(term)))
+            .cloned()
+            .collect();
+
+        let Some(path) = &self.meeting else {
+            return Ok(terms);
+        };
+        let raw = tokio::fs::read(path).await.map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                CliError::not_found(format!("meeting '{}'", path.display()), None)
+            } else {
+                CliError::operation_failed("read meeting", e.to_string())
+            }
+        })?;
+        let meeting = MeetingContext::from_json(&raw).map_err(|e| {
+            CliError::invalid_argument(
+                "--meeting",
+                path.display().to_string(),
+                format!("expected a meeting context or calendar event: {e}"),
+            )
+        })?;
+        Ok(meeting.with_keywords(&terms))
+    }
+}
+
+pub struct Args {
+    pub input: PathBuf,
+    pub llm: LlmArgs,
+    pub language: String,
+    pub output: Option<PathBuf>,
+    pub report: Option<PathBuf>,
+    pub format: OutputFormat,
+    pub quiet: bool,
+}
+
+#[derive(Debug, Default, serde::Serialize)]
+pub(crate) struct CorrectionReport {
+    pub chunks: usize,
+    pub failed_chunks: usize,
+    pub changes: Vec<Change>,
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub(crate) struct Change {
+    pub channel: usize,
+    pub start: f64,
+    pub end: f64,
+    pub before: String,
+    pub after: String,
+}
+
+pub async fn run(args: Args) -> CliResult<()> {
+    let mut response = read_transcript(&args.input).await?;
+    let glossary = args.llm.glossary(&[]).await?;
+
+    let report = correct_response(
+        &mut response,
+        &args.llm,
+        &args.language,
+        &glossary,
+        args.quiet,
+    )
+    .await?;
+
+    match args.format {
+        OutputFormat::Json => write_json_response(args.output.as_deref(), &response).await?,
+        OutputFormat::Text => {
+            write_text_response(args.output.as_deref(), extract_transcript(&response)).await?
+        }
+        OutputFormat::Pretty => {
+            write_text_response(args.output.as_deref(), format_pretty(&response)).await?
+        }
+    }
+
+    emit_report(&report, args.report.as_deref(), args.quiet).await
+}
+
+/// Reads a transcript written by `char batch --format json`.
+pub(crate) async fn read_transcript(path: &std::path::Path) -> CliResult<batch::Response> {
+    let raw = tokio::fs::read(path).await.map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            CliError::not_found(format!("transcript '{}'", path.display()), None)
+        } else {
+            CliError::operation_failed("read transcript", e.to_string())
+        }
+    })?;
+    serde_json::from_slice(&raw).map_err(|e| {
+        CliError::invalid_argument(
+            "<TRANSCRIPT>",
+            path.display().to_string(),
+            format!("expected a `char batch --format json` transcript: {e}"),
+        )
+    })
+}
+
+/// Runs the LLM postprocessor over the first alternative of every channel, chunk by chunk, and
+/// writes accepted corrections back into `response`. A chunk whose request or patch fails keeps
+/// its original words; only a run where every chunk fails is an error.
+pub(crate) async fn correct_response(
+    response: &mut batch::Response,
+    llm: &LlmArgs,
+    language: &str,
+    glossary: &[String],
+    quiet: bool,
+) -> CliResult<CorrectionReport> {
+    let client = LlmClient::new(llm)?;
+    let postprocessor = build_postprocessor(language, glossary);
+
+    let channels: Vec<(usize, Vec<FinalizedWord>, Vec<Range<usize>>)> = response
+        .results
+        .channels
+        .iter()
+        .enumerate()
+        .filter_map(|(index, channel)| {
+            let words = finalized_words(&channel.alternatives.first()?.words, index);
+            let ranges = chunk_ranges(&words, llm.max_chunk_tokens);
+            Some((index, words, ranges))
+        })
+        .collect();
+
+    let total: usize = channels.iter().map(|(_, _, ranges)| ranges.len()).sum();
+    let progress = (!quiet && std::io::stderr().is_terminal()).then(|| {
+        let bar = ProgressBar::new(total as u64);
+        bar.set_style(
+            ProgressStyle::with_template("{spinner} {msg} [{bar:20}] {pos}/{len}")
+                .unwrap()
+                .progress_chars("█▓░"),
+        );
+        bar.set_message("Correcting");
+        bar.enable_steady_tick(std::time::Duration::from_millis(120));
+        bar
+    });
+
+    let mut report = CorrectionReport::default();
+    let mut last_error = None;
+
+    for (index, words, ranges) in channels {
+        let mut corrected = words.clone();
+
+        for range in ranges {
+            report.chunks += 1;
+            let result = postprocessor
+                .process_with(&words[range.clone()], |request| {
+                    let client = &client;
+                    async move {
+                        client
+                            .complete(&request.system_prompt, &request.user_prompt)
+                            .await
+                    }
+                })
+                .await;
+
+            match result {
+                Ok(result) => corrected[range].clone_from_slice(&result.corrected_words),
+                Err(error) => {
+                    report.failed_chunks += 1;
+                    let message = error_chain(&error);
+                    if !quiet {
+                        let line = format!(
+                            "warning: chunk {} of channel {index} left uncorrected: {message}",
+                            report.chunks
+                        );
+                        match &progress {
+                            Some(progress) => progress.println(line),
+                            None => eprintln!("{line}"),
+                        }
+                    }
+                    last_error = Some(message);
+                }
+            }
+
+            if let Some(progress) = &progress {
+                progress.inc(1);
+            }
+        }
+
+        if let Some(alt) = response.results.channels[index].alternatives.first_mut() {
+            report
+                .changes
+                .extend(apply_corrections(alt, &corrected, index));
+        }
+    }
+
+    if let Some(progress) = progress {
+        progress.finish_and_clear();
+    }
+
+    match last_error {
+        Some(error) if report.failed_chunks == report.chunks => {
+            Err(CliError::operation_failed("transcript correction", error))
+        }
+        _ => Ok(report),
+    }
+}
+
+pub(crate) async fn emit_report(
+    report: &CorrectionReport,
+    path: Option<&std::path::Path>,
+    quiet: bool,
+) -> CliResult<()> {
+    if let Some(path) = path {
+        let bytes = serde_json::to_vec_pretty(report)
+            .map_err(|e| CliError::operation_failed("serialize report", e.to_string()))?;
+        tokio::fs::write(path, bytes)
+            .await
+            .map_err(|e| CliError::operation_failed("write report", e.to_string()))?;
+    } else if !quiet && !report.changes.is_empty() {
+        let mut table = Table::new();
+        table
+            .load_preset(UTF8_FULL_CONDENSED)
+            .set_content_arrangement(ContentArrangement::Dynamic);
+        table.set_header(["Time", "Channel", "Before", "After"]);
+        for change in &report.changes {
+            table.add_row([
+                format_time(change.start),
+                change.channel.to_string(),
+                change.before.clone(),
+                change.after.clone(),
+            ]);
+        }
+        eprintln!("{table}");
+    }
+
+    if !quiet {
+        let mut summary = format!(
+            "{} correction{} in {} chunk{}",
+            report.changes.len(),
+            if report.changes.len() == 1 { "" } else { "s" },
+            report.chunks,
+            if report.chunks == 1 { "" } else { "s" },
+        );
+        if report.failed_chunks > 0 {
+            summary.push_str(&format!(", {} failed", report.failed_chunks));
+        }
+        if let Some(path) = path {
+            summary.push_str(&format!(", report -> {}", path.display()));
+        }
+        eprintln!("\x1b[2m{summary}\x1b[0m");
+    }
+
+    Ok(())
+}
+
+fn build_postprocessor(language: &str, glossary: &[String]) -> TranscriptPostprocessor {
+    TranscriptPostprocessor::new()
+        .with_language(language)
+        .with_glossary(glossary.iter().cloned())
+}
+
+fn finalized_words(words: &[batch::Word], channel: usize) -> Vec<FinalizedWord> {
+    words
+        .iter()
+        .enumerate()
+        .map(|(index, word)| FinalizedWord {
+            id: format!("w{index}"),
+            text: display_text(word).to_string(),
+            start_ms: (word.start * 1000.0).round() as i64,
+            end_ms: (word.end * 1000.0).round() as i64,
+            channel: channel as i32,
+            state: WordState::Pending,
+        })
+        .collect()
+}
+
+/// Splits words into consecutive ranges whose estimated prompt size stays within `max_tokens`,
+/// cutting after a sentence end when one falls in the second half of the chunk.
+fn chunk_ranges(words: &[FinalizedWord], max_tokens: usize) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut start = 0;
+    let mut tokens = 0;
+    let mut sentence_end = None;
+
+    for (i, word) in words.iter().enumerate() {
+        let cost = estimate_tokens(word);
+        if tokens + cost > max_tokens && i > start {
+            let end = match sentence_end {
+                Some(end) if end - start >= (i - start) / 2 => end,
+                _ => i,
+            };
+            ranges.push(start..end);
+            start = end;
+            tokens = words[start..i].iter().map(estimate_tokens).sum();
+            sentence_end = (start..i)
+                .rev()
+                .find(|&k| ends_sentence(&words[k]))
+                .map(|k| k + 1);
+        }
+
+        tokens += cost;
+        if ends_sentence(word) {
+            sentence_end = Some(i + 1);
+        }
+    }
+
+    if start < words.len() {
+        ranges.push(start..words.len());
+    }
+    ranges
+}
+
+fn estimate_tokens(word: &FinalizedWord) -> usize {
+    (word.text.len() + WORD_OVERHEAD_CHARS).div_ceil(CHARS_PER_TOKEN)
+}
+
+fn ends_sentence(word: &FinalizedWord) -> bool {
+    word.text.ends_with(['.', '?', '!'])
+}
+
+/// Writes corrected text into the batch words and rebuilds the transcript. Words corrected to
+/// nothing, such as dropped filler, are removed.
+fn apply_corrections(
+    alt: &mut batch::Alternatives,
+    corrected: &[FinalizedWord],
+    channel: usize,
+) -> Vec<Change> {
+    let mut changes = Vec::new();
+
+    for (word, fixed) in alt.words.iter_mut().zip(corrected) {
+        let before = display_text(word).to_string();
+        let after = fixed.text.trim();
+        if after == before {
+            continue;
+        }
+
+        changes.push(Change {
+            channel,
+            start: word.start,
+            end: word.end,
+            before,
+            after: after.to_string(),
+        });
+
+        if word.punctuated_word.is_some() {
+            word.word = after
+                .trim_matches(|c: char| !c.is_alphanumeric())
+                .to_string();
+            word.punctuated_word = Some(after.to_string());
+        } else {
+            word.word = after.to_string();
+        }
+    }
+
+    if !changes.is_empty() {
+        alt.words.retain(|word| !display_text(word).is_empty());
+        alt.transcript = alt
+            .words
+            .iter()
+            .map(display_text)
+            .collect::<Vec<_>>()
+            .join(" ");
+    }
+
+    changes
+}
+
+fn display_text(word: &batch::Word) -> &str {
+    word.punctuated_word.as_deref().unwrap_or(&word.word)
+}
+
+pub(crate) fn error_chain(error: &dyn std::error::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = error.source();
+    while let Some(cause) = source {
+        let cause_message = cause.to_string();
+        if !message.contains(&cause_message) {
+            message.push_str(&format!(": {cause_message}"));
+        }
+        source = cause.source();
+    }
+    message
+}
+
+fn format_time(secs: f64) -> String {
+    let total = secs.max(0.0) as u64;
+    format!("{:02}:{:02}", total / 60, total % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str) -> FinalizedWord {
+        FinalizedWord {
+            id: String::new(),
+            text: text.to_string(),
+            start_ms: 0,
+            end_ms: 0,
+            channel: 0,
+            state: WordState::Pending,
+        }
+    }
+
+    fn batch_word(text: &str, start: f64) -> batch::Word {
+        batch::Word {
+            word: text
+                .trim_matches(|c: char| !c.is_alphanumeric())
+                .to_lowercase(),
+            start,
+            end: start + 0.5,
+            confidence: 0.9,
+            speaker: None,
+            punctuated_word: Some(text.to_string()),
+        }
+    }
+
+    #[test]
+    fn chunks_stay_within_budget_and_prefer_sentence_ends() {
+        let words: Vec<_> = [
+            "One", "two.", "Three", "four", "five.", "Six", "seven", "eight",
+        ]
+        .into_iter()
+        .map(word)
+        .collect();
+        let per_word = estimate_tokens(&words[0]);
+
+        let ranges = chunk_ranges(&words, per_word * 4);
+
+        assert_eq!(ranges, [0..2, 2..5, 5..8]);
+        assert_eq!(chunk_ranges(&words, usize::MAX).len(), 1);
+        assert!(chunk_ranges(&[], 100).is_empty());
+    }
+
+    #[test]
+    fn correction_prompt_lists_the_glossary() {
+        let glossary = vec!["Yujong Lee".to_string(), "Hyprnote".to_string()];
+
+        let request = build_postprocessor("en", &glossary)
+            .build_request(&[word("hyper"), word("note")])
+            .unwrap();
+
+        for term in &glossary {
+            assert!(request.system_prompt.contains(&format!("- {term}")));
+        }
+        assert!(!request.user_prompt.contains("Yujong Lee"));
+    }
+
+    #[test]
+    fn applies_corrections_and_reports_changes() {
+        let mut alt = batch::Alternatives {
+            transcript: "We use hyper note, um, daily.".to_string(),
+            confidence: 0.9,
+            words: vec![
+                batch_word("We", 0.0),
+                batch_word("use", 0.5),
+                batch_word("hyper", 1.0),
+                batch_word("note,", 1.5),
+                batch_word("um,", 2.0),
+                batch_word("daily.", 2.5),
+            ],
+        };
+        let mut corrected = finalized_words(&alt.words, 0);
+        corrected[2].text = "Hyprnote".to_string();
+        corrected[3].text = "".to_string();
+        corrected[4].text = "".to_string();
+
+        let changes = apply_corrections(&mut alt, &corrected, 0);
+
+        assert_eq!(changes.len(), 3);
+        assert_eq!(changes[0].before, "hyper");
+        assert_eq!(changes[0].after, "Hyprnote");
+        assert_eq!(alt.transcript, "We use Hyprnote daily.");
+        assert_eq!(alt.words.len(), 4);
+        assert_eq!(alt.words[2].word, "Hyprnote");
+    }
+}
@@ -1,4 +1,5 @@
 use std::collections::VecDeque;
+use std::time::{Duration, Instant};
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use hypr_listener_core::{
@@ -6,7 +7,10 @@ use hypr_listener_core::{
     SessionProgressEvent, State,
 };
 use hypr_listener2_core::BatchEvent;
-use hypr_transcript::{FinalizedWord, PartialWord, TranscriptDelta, TranscriptProcessor};
+use hypr_transcript::{
+    FinalizedWord, PartialWord, TranscriptDelta, TranscriptProcessor, TranscriptTranslator,
+    TranscriptTranslatorRequest, TranslationBatch,
+};
 use tui_textarea::TextArea;
 
 use super::audio_drop::{AudioDropRequest, looks_like_audio_file, normalize_pasted_path};
@@ -15,6 +19,15 @@ use crate::frame::FrameRequester;
 use crate::textarea_input::textarea_input_from_key_event;
 
 const AUDIO_HISTORY_CAP: usize = 64;
+/// Without new words for this long, the unfinished sentence is translated as is.
+const TRANSLATION_FLUSH_AFTER: Duration = Duration::from_secs(2);
+const TRANSLATION_RETRY_AFTER: Duration = Duration::from_secs(10);
+
+/// Raw LLM output for each target language of a translation batch.
+pub struct TranslationResponse {
+    pub batch: TranslationBatch,
+    pub responses: Vec<(String, Result<String, String>)>,
+}
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 enum Focus {
@@ -51,6 +64,10 @@ pub struct App {
     transcript_max_scroll: u16,
     memo: TextArea<'static>,
     batch_running: bool,
+
+    translator: Option<TranscriptTranslator>,
+    last_words_at: Instant,
+    translation_retry_at: Option<Instant>,
 }
 
 impl App {
@@ -58,7 +75,7 @@ impl App {
         TextArea::default()
     }
 
-    pub fn new(frame_requester: FrameRequester) -> Self {
+    pub fn new(frame_requester: FrameRequester, translator: Option<TranscriptTranslator>) -> Self {
         Self {
             should_quit: false,
             state: State::Inactive,
@@ -82,6 +99,10 @@ impl App {
             transcript_max_scroll: 0,
             memo: Self::init_memo(),
             batch_running: false,
+
+            translator,
+            last_words_at: Instant::now(),
+            translation_retry_at: None,
         }
     }
 
@@ -182,6 +203,85 @@ impl App {
         self.frame_requester.schedule_frame();
     }
 
+    /// The next segments to translate, unless translation is off or backing off after a
+    /// failure.
+    pub fn next_translation_batch(
+        &mut self,
+    ) -> Option<(TranslationBatch, Vec<TranscriptTranslatorRequest>)> {
+        if self
+            .translation_retry_at
+            .is_some_and(|at| at > Instant::now())
+        {
+            return None;
+        }
+
+        let translator = self.translator.as_mut()?;
+        let batch =
+            translator.take_batch(self.last_words_at.elapsed() >= TRANSLATION_FLUSH_AFTER)?;
+        match translator.build_requests(&batch) {
+            Ok(requests) => Some((batch, requests)),
+            Err(e) => {
+                self.errors.push(format!("Translation: {e}"));
+                self.translator = None;
+                None
+            }
+        }
+    }
+
+    pub fn handle_translation(&mut self, response: TranslationResponse) {
+        let Some(translator) = self.translator.as_mut() else {
+            return;
+        };
+
+        let mut failed = false;
+        for (language, raw_response) in response.responses {
+            let result = raw_response.and_then(|raw_response| {
+                translator
+                    .apply_response(&response.batch, &language, &raw_response)
+                    .map_err(|e| e.to_string())
+            });
+            if let Err(e) = result {
+                failed = true;
+                self.errors.push(format!("Translation ({language}): {e}"));
+            }
+        }
+
+        if failed {
+            translator.requeue(response.batch);
+            self.translation_retry_at = Some(Instant::now() + TRANSLATION_RETRY_AFTER);
+        }
+        self.frame_requester.schedule_frame();
+    }
+
+    pub fn is_translating(&self) -> bool {
+        self.translator.is_some()
+    }
+
+    /// Translated segments in transcript order, tagged with their language when there are
+    /// several.
+    pub fn translation_lines(&self) -> Vec<String> {
+        let Some(translator) = &self.translator else {
+            return Vec::new();
+        };
+
+        let languages = translator.target_languages();
+        let mut segments: Vec<_> = languages
+            .iter()
+            .flat_map(|language| translator.track(language))
+            .collect();
+        segments.sort_by_key(|segment| (segment.start_ms, segment.channel));
+        segments
+            .into_iter()
+            .map(|segment| {
+                if languages.len() > 1 {
+                    format!("[{}] {}", segment.language, segment.text)
+                } else {
+                    segment.text
+                }
+            })
+            .collect()
+    }
+
     pub fn can_accept_audio_drop(&self) -> bool {
         self.transcript_focused()
             && self.state == State::Inactive
@@ -391,6 +491,12 @@ impl App {
     }
 
     fn apply_transcript_delta(&mut self, delta: TranscriptDelta) {
+        if let Some(translator) = &mut self.translator {
+            translator.push(&delta);
+        }
+        if !delta.new_words.is_empty() {
+            self.last_words_at = Instant::now();
+        }
         if !delta.replaced_ids.is_empty() {
             self.words.retain(|w| !delta.replaced_ids.contains(&w.id));
         }
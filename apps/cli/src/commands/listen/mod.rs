@@ -2,10 +2,12 @@ use std::sync::Arc;
 
 use hypr_listener_core::actors::{RootActor, RootArgs, RootMsg, SessionParams};
 use hypr_listener2_core::{BatchParams, BatchProvider};
+use hypr_transcript::{TranscriptTranslator, TranscriptTranslatorRequest, TranslationBatch};
 use ractor::Actor;
 use tokio::sync::mpsc;
 
 use crate::commands::cactus_server::resolve_and_spawn_cactus;
+use crate::commands::correct::{LlmArgs, LlmClient};
 use crate::error::{CliError, CliResult};
 use crate::{
     event::{EventHandler, TuiEvent},
@@ -18,7 +20,7 @@ mod audio_drop;
 mod runtime;
 mod ui;
 
-use app::App;
+use app::{App, TranslationResponse};
 use audio_drop::AudioDropRequest;
 use runtime::{ListenBatchRuntime, ListenRuntime};
 
@@ -28,6 +30,13 @@ pub struct Args {
     pub model: String,
     pub language: String,
     pub record: bool,
+    pub translation: Option<TranslationArgs>,
+}
+
+/// Live translated captions next to the transcript.
+pub struct TranslationArgs {
+    pub languages: Vec<String>,
+    pub llm: LlmArgs,
 }
 
 fn spawn_batch_transcription(
@@ -48,6 +57,7 @@ fn spawn_batch_transcription(
         api_key,
         languages: vec![language],
         keywords: vec![],
+        vocabulary: Default::default(),
     };
 
     tokio::spawn(async move {
@@ -55,6 +65,27 @@ fn spawn_batch_transcription(
     });
 }
 
+/// Runs one translation request per target language and sends the raw responses back to the
+/// UI loop, which owns the translator.
+fn spawn_translation(
+    client: Arc<LlmClient>,
+    batch: TranslationBatch,
+    requests: Vec<TranscriptTranslatorRequest>,
+    tx: mpsc::UnboundedSender<TranslationResponse>,
+) {
+    tokio::spawn(async move {
+        let mut responses = Vec::with_capacity(requests.len());
+        for request in requests {
+            let response = client
+                .complete(&request.system_prompt, &request.user_prompt)
+                .await
+                .map_err(|e| e.to_string());
+            responses.push((request.language, response));
+        }
+        let _ = tx.send(TranslationResponse { batch, responses });
+    });
+}
+
 pub async fn run(args: Args) -> CliResult<()> {
     let Args {
         base_url,
@@ -62,8 +93,20 @@ pub async fn run(args: Args) -> CliResult<()> {
         model,
         language: language_code,
         record,
+        translation,
     } = args;
 
+    let (translation_client, translator) = match translation {
+        Some(translation) => (
+            Some(Arc::new(LlmClient::new(&translation.llm)?)),
+            Some(
+                TranscriptTranslator::new(translation.languages)
+                    .with_source_language(&language_code),
+            ),
+        ),
+        None => (None, None),
+    };
+
     let language = language_code
         .parse::<hypr_language::Language>()
         .map_err(|e| {
@@ -114,6 +157,8 @@ pub async fn run(args: Args) -> CliResult<()> {
         base_url: base_url.clone(),
         api_key: api_key.clone(),
         keywords: vec![],
+        vocabulary: Default::default(),
+        meeting: None,
     };
 
     let started = ractor::call!(root_ref, RootMsg::StartSession, params)
@@ -133,7 +178,9 @@ pub async fn run(args: Args) -> CliResult<()> {
         tx: batch_tx.clone(),
     });
     let frame_requester = FrameRequester::new(draw_tx);
-    let mut app = App::new(frame_requester.clone());
+    let (translation_tx, mut translation_rx) = mpsc::unbounded_channel();
+    let mut translating = false;
+    let mut app = App::new(frame_requester.clone(), translator);
     let mut events = EventHandler::new(draw_rx);
     events.resume_events();
 
@@ -171,9 +218,22 @@ pub async fn run(args: Args) -> CliResult<()> {
             Some(batch_event) = batch_rx.recv() => {
                 app.handle_batch_event(batch_event);
             }
+            Some(response) = translation_rx.recv() => {
+                translating = false;
+                app.handle_translation(response);
+            }
             else => break,
         }
 
+        // One batch at a time; segments that complete meanwhile go out together next.
+        if let Some(client) = &translation_client
+            && !translating
+            && let Some((batch, requests)) = app.next_translation_batch()
+        {
+            translating = true;
+            spawn_translation(client.clone(), batch, requests, translation_tx.clone());
+        }
+
         if app.should_quit {
             break;
         }
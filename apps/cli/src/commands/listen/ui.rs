@@ -38,7 +38,16 @@ fn draw_sidebar(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
 
     draw_sidebar_metadata(frame, app, metadata_area, theme);
     draw_sidebar_meters(frame, app, meters_area, theme);
-    draw_transcript(frame, app, transcript_area, theme);
+
+    if app.is_translating() {
+        let [transcript_area, translation_area] =
+            Layout::vertical([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .areas(transcript_area);
+        draw_transcript(frame, app, transcript_area, theme);
+        draw_translation(frame, app, translation_area, theme);
+    } else {
+        draw_transcript(frame, app, transcript_area, theme);
+    }
 }
 
 fn draw_sidebar_metadata(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
@@ -335,6 +344,42 @@ fn draw_transcript(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme)
     );
 }
 
+/// Follows the latest translation, like live captions.
+fn draw_translation(frame: &mut Frame, app: &App, area: Rect, theme: &Theme) {
+    let lines = app.translation_lines();
+    let content_width = area.width.saturating_sub(4) as usize;
+    let visible_lines = area.height.saturating_sub(2) as usize;
+    let line_count: usize = lines
+        .iter()
+        .map(|line| wrapped_line_count(line, content_width))
+        .sum();
+
+    let text: Vec<Line> = if lines.is_empty() {
+        vec![Line::from(Span::styled(
+            "Waiting for a finished sentence...",
+            theme.placeholder,
+        ))]
+    } else {
+        lines.into_iter().map(Line::from).collect()
+    };
+
+    let block = Block::new()
+        .borders(Borders::ALL)
+        .border_style(theme.border)
+        .title(" Translation ")
+        .padding(Padding::new(1, 1, 0, 0));
+
+    let scroll = line_count
+        .saturating_sub(visible_lines)
+        .min(u16::MAX as usize) as u16;
+    let paragraph = Paragraph::new(text)
+        .block(block)
+        .wrap(Wrap { trim: false })
+        .scroll((scroll, 0));
+
+    frame.render_widget(paragraph, area);
+}
+
 fn draw_notepad(frame: &mut Frame, app: &mut App, area: Rect, theme: &Theme) {
     if area.width < 3 || area.height < 3 {
         return;
@@ -1,10 +1,13 @@
 pub mod auth;
+pub mod backup;
 pub mod batch;
 pub mod cactus_server;
+pub mod correct;
 pub mod desktop;
 pub mod entry;
 pub mod listen;
 pub mod model;
+pub mod translate;
 
 use clap::ValueEnum;
 
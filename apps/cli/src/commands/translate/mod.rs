@@ -0,0 +1,174 @@
+use std::path::PathBuf;
+
+use clap::ValueEnum;
+use hypr_transcript::{TranscriptProcessor, TranscriptTranslator, TranslatedSegment};
+
+use crate::commands::batch::write_text_response;
+use crate::commands::correct::{LlmArgs, LlmClient, error_chain, read_transcript};
+use crate::error::{CliError, CliResult};
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum SubtitleFormat {
+    Srt,
+    Vtt,
+    /// Every segment with its source text, for search
+    Json,
+}
+
+pub struct Args {
+    pub input: PathBuf,
+    pub llm: LlmArgs,
+    pub source_language: String,
+    pub target_languages: Vec<String>,
+    pub output: Option<PathBuf>,
+    pub format: SubtitleFormat,
+    pub quiet: bool,
+}
+
+pub async fn run(args: Args) -> CliResult<()> {
+    if !matches!(args.format, SubtitleFormat::Json) && args.target_languages.len() > 1 {
+        return Err(CliError::invalid_argument(
+            "--to",
+            args.target_languages.join(", "),
+            "a subtitle file holds one language; pass --to once or use --format json",
+        ));
+    }
+
+    let response = read_transcript(&args.input).await?;
+    let client = LlmClient::new(&args.llm)?;
+    let client = &client;
+
+    let mut translator = TranscriptTranslator::new(args.target_languages.iter().cloned())
+        .with_source_language(&args.source_language);
+    translator.push(&TranscriptProcessor::process_batch_response(&response));
+
+    let mut requests = 0;
+    let mut last_error = None;
+    let mut failed = 0;
+    while let Some(batch) = translator.take_batch(true) {
+        let outcome = translator
+            .process_with(&batch, move |request| async move {
+                client
+                    .complete(&request.system_prompt, &request.user_prompt)
+                    .await
+            })
+            .await
+            .map_err(|e| CliError::operation_failed("transcript translation", error_chain(&e)))?;
+
+        requests += args.target_languages.len();
+        for (language, error) in outcome.failed {
+            failed += 1;
+            let message = error_chain(&error);
+            if !args.quiet {
+                eprintln!(
+                    "warning: {} segment(s) left untranslated into {language}: {message}",
+                    batch.len()
+                );
+            }
+            last_error = Some(message);
+        }
+    }
+
+    if let Some(error) = last_error
+        && failed == requests
+    {
+        return Err(CliError::operation_failed("transcript translation", error));
+    }
+
+    let mut track: Vec<TranslatedSegment> = args
+        .target_languages
+        .iter()
+        .flat_map(|language| translator.track(language))
+        .collect();
+    track.sort_by_key(|segment| (segment.start_ms, segment.channel));
+
+    let text = match args.format {
+        SubtitleFormat::Srt => format_srt(&track),
+        SubtitleFormat::Vtt => format_vtt(&track),
+        SubtitleFormat::Json => serde_json::to_string_pretty(&track)
+            .map_err(|e| CliError::operation_failed("serialize translations", e.to_string()))?,
+    };
+    write_text_response(args.output.as_deref(), text).await
+}
+
+fn format_srt(track: &[TranslatedSegment]) -> String {
+    track
+        .iter()
+        .enumerate()
+        .map(|(index, segment)| {
+            format!(
+                "{}\n{} --> {}\n{}\n",
+                index + 1,
+                timestamp(segment.start_ms, ','),
+                timestamp(segment.end_ms, ','),
+                segment.text
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn format_vtt(track: &[TranslatedSegment]) -> String {
+    let mut vtt = String::from("WEBVTT\n");
+    for segment in track {
+        vtt.push_str(&format!(
+            "\n{} --> {}\n{}\n",
+            timestamp(segment.start_ms, '.'),
+            timestamp(segment.end_ms, '.'),
+            segment.text
+        ));
+    }
+    vtt
+}
+
+/// `HH:MM:SS<separator>mmm`; SRT separates milliseconds with a comma, WebVTT with a dot.
+fn timestamp(ms: i64, separator: char) -> String {
+    let ms = ms.max(0);
+    format!(
+        "{:02}:{:02}:{:02}{separator}{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        ms % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hypr_transcript::TranslationRange;
+
+    fn segment(start_ms: i64, end_ms: i64, text: &str) -> TranslatedSegment {
+        TranslatedSegment {
+            range: TranslationRange {
+                start_word_id: String::new(),
+                end_word_id: String::new(),
+            },
+            channel: 0,
+            start_ms,
+            end_ms,
+            language: "en".to_string(),
+            source_text: String::new(),
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn writes_srt_and_vtt_cues() {
+        let track = vec![
+            segment(0, 1_500, "Good morning."),
+            segment(3_661_250, 3_662_000, "Bye."),
+        ];
+
+        assert_eq!(
+            format_srt(&track),
+            "1\n00:00:00,000 --> 00:00:01,500\nGood morning.\n\n\
+             2\n01:01:01,250 --> 01:01:02,000\nBye.\n"
+        );
+        assert_eq!(
+            format_vtt(&track),
+            "WEBVTT\n\n00:00:00.000 --> 00:00:01.500\nGood morning.\n\n\
+             01:01:01.250 --> 01:01:02.000\nBye.\n"
+        );
+    }
+}
@@ -9,8 +9,11 @@ mod theme;
 use clap::{Parser, Subcommand};
 
 use crate::commands::OutputFormat;
+use crate::commands::backup::BackupCommands;
 use crate::commands::batch::Provider as BatchProvider;
+use crate::commands::correct::LlmArgs;
 use crate::commands::model::ModelCommands;
+use crate::commands::translate::SubtitleFormat;
 use crate::error::{CliError, CliResult};
 
 #[derive(Parser)]
@@ -54,7 +57,13 @@ fn required_base_url(base_url: Option<String>) -> CliResult<String> {
 
 #[derive(Subcommand)]
 enum Commands {
-    Listen,
+    Listen {
+        /// Show live translated captions in this language; repeat for several
+        #[arg(long, value_name = "LANG")]
+        translate: Vec<String>,
+        #[command(flatten)]
+        llm: LlmArgs,
+    },
     Auth,
     Desktop,
     #[command(about = "Transcribe an audio file (batch mode)")]
@@ -73,11 +82,52 @@ enum Commands {
         json: bool,
         #[arg(long, short = 'q')]
         quiet: bool,
+        #[arg(long, help = "Correct the transcript with an LLM before writing it")]
+        correct: bool,
+        #[arg(long, value_name = "PATH", requires = "correct")]
+        report: Option<std::path::PathBuf>,
+        #[command(flatten)]
+        llm: LlmArgs,
+    },
+    #[command(about = "Correct a transcript JSON file with an LLM")]
+    Correct {
+        #[arg(value_name = "TRANSCRIPT")]
+        input: std::path::PathBuf,
+        #[arg(long, value_name = "PATH")]
+        output: Option<std::path::PathBuf>,
+        #[arg(long, value_name = "PATH")]
+        report: Option<std::path::PathBuf>,
+        #[arg(long, value_enum, default_value = "json")]
+        format: OutputFormat,
+        #[arg(long, short = 'q')]
+        quiet: bool,
+        #[command(flatten)]
+        llm: LlmArgs,
+    },
+    #[command(about = "Translate a transcript JSON file into subtitles with an LLM")]
+    Translate {
+        #[arg(value_name = "TRANSCRIPT")]
+        input: std::path::PathBuf,
+        #[arg(long = "to", value_name = "LANG", required = true)]
+        to: Vec<String>,
+        #[arg(long, value_name = "PATH")]
+        output: Option<std::path::PathBuf>,
+        #[arg(long, value_enum, default_value = "srt")]
+        format: SubtitleFormat,
+        #[arg(long, short = 'q')]
+        quiet: bool,
+        #[command(flatten)]
+        llm: LlmArgs,
     },
     Model {
         #[command(subcommand)]
         command: ModelCommands,
     },
+    #[command(about = "Encrypted vault backups to S3-compatible storage")]
+    Backup {
+        #[command(subcommand)]
+        command: BackupCommands,
+    },
 }
 
 #[tokio::main]
@@ -103,15 +153,20 @@ async fn run(cli: Cli) -> CliResult<()> {
     match command {
         Some(Commands::Auth) => commands::auth::run(),
         Some(Commands::Desktop) => commands::desktop::run().map(|_| ()),
-        Some(Commands::Listen) => commands::listen::run(commands::listen::Args {
-            base_url,
-            api_key,
-            model,
-            language,
-            record,
-        })
-        .await
-        .map(|_| ()),
+        Some(Commands::Listen { translate, llm }) => {
+            commands::listen::run(commands::listen::Args {
+                base_url,
+                api_key,
+                model,
+                language,
+                record,
+                translation: (!translate.is_empty()).then_some(commands::listen::TranslationArgs {
+                    languages: translate,
+                    llm,
+                }),
+            })
+            .await
+        }
         Some(Commands::Batch {
             input,
             provider,
@@ -120,6 +175,9 @@ async fn run(cli: Cli) -> CliResult<()> {
             json,
             format,
             quiet,
+            correct,
+            report,
+            llm,
         }) => {
             let base_url = if matches!(provider, BatchProvider::Cactus) {
                 base_url
@@ -138,10 +196,51 @@ async fn run(cli: Cli) -> CliResult<()> {
                 output,
                 format: if json { OutputFormat::Json } else { format },
                 quiet,
+                correct: correct.then_some(llm),
+                report,
+            })
+            .await
+        }
+        Some(Commands::Correct {
+            input,
+            output,
+            report,
+            format,
+            quiet,
+            llm,
+        }) => {
+            commands::correct::run(commands::correct::Args {
+                input,
+                llm,
+                language,
+                output,
+                report,
+                format,
+                quiet,
+            })
+            .await
+        }
+        Some(Commands::Translate {
+            input,
+            to,
+            output,
+            format,
+            quiet,
+            llm,
+        }) => {
+            commands::translate::run(commands::translate::Args {
+                input,
+                llm,
+                source_language: language,
+                target_languages: to,
+                output,
+                format,
+                quiet,
             })
             .await
         }
         Some(Commands::Model { command }) => commands::model::run(command).await,
+        Some(Commands::Backup { command }) => commands::backup::run(command).await,
         None => match commands::entry::run(commands::entry::Args {
             status_message: None,
         })
@@ -153,6 +252,7 @@ async fn run(cli: Cli) -> CliResult<()> {
                 model,
                 language,
                 record,
+                translation: None,
             })
             .await
             .map(|_| ()),
@@ -100,6 +100,7 @@ pub async fn main() {
         .plugin(tauri_plugin_os::init())
         .plugin(tauri_plugin_path2::init())
         .plugin(tauri_plugin_export::init())
+        .plugin(tauri_plugin_redaction::init())
         .plugin(tauri_plugin_process::init())
         .plugin(tauri_plugin_mcp::init())
         .plugin(tauri_plugin_misc::init())
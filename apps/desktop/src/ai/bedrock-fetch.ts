@@ -0,0 +1,66 @@
+import { Channel } from "@tauri-apps/api/core";
+
+import { commands as bedrockCommands } from "@hypr/plugin-bedrock";
+
+const encoder = new TextEncoder();
+
+const sse = (data: string) => encoder.encode(`data: ${data}\n\n`);
+
+const errorBody = (message: string) => JSON.stringify({ error: { message } });
+
+// Answers OpenAI-compatible chat completion requests through the bedrock plugin, which signs
+// them with the local AWS credentials. The request URL is ignored.
+export const bedrockFetch: typeof fetch = async (_input, init) => {
+  const request = JSON.parse(String(init?.body ?? "{}"));
+
+  if (!request.stream) {
+    const result = await bedrockCommands.converse(request);
+    return result.status === "ok"
+      ? new Response(JSON.stringify(result.data), {
+          headers: { "Content-Type": "application/json" },
+        })
+      : new Response(errorBody(result.error), {
+          status: 502,
+          headers: { "Content-Type": "application/json" },
+        });
+  }
+
+  let closed = false;
+  const body = new ReadableStream<Uint8Array>({
+    start(controller) {
+      const close = (last: string) => {
+        if (closed) return;
+        closed = true;
+        controller.enqueue(sse(last));
+        controller.close();
+      };
+
+      // The plugin ends a successful stream with "[DONE]" on the channel itself, since the
+      // command can resolve before the last chunks arrive.
+      const channel = new Channel<string>();
+      channel.onmessage = (chunk) => {
+        if (chunk === "[DONE]") {
+          close(chunk);
+        } else if (!closed) {
+          controller.enqueue(sse(chunk));
+        }
+      };
+
+      void bedrockCommands
+        .converseStream(request, channel)
+        .then((result) => {
+          if (result.status === "error") {
+            close(errorBody(result.error));
+          }
+        })
+        .catch((e) => close(errorBody(String(e))));
+    },
+    cancel() {
+      closed = true;
+    },
+  });
+
+  return new Response(body, {
+    headers: { "Content-Type": "text/event-stream" },
+  });
+};
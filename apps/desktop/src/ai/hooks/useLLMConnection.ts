@@ -11,6 +11,7 @@ import { useMemo } from "react";
 import type { CharTask } from "@hypr/api-client";
 import type { AIProviderStorage } from "@hypr/store";
 
+import { bedrockFetch } from "../bedrock-fetch";
 import { createTracedFetch, tracedFetch } from "../traced-fetch";
 
 import { useAuth } from "~/auth";
@@ -302,6 +303,16 @@ const createLanguageModel = (
       return wrapWithThinkingMiddleware(provider.chatModel(conn.modelId));
     }
 
+    case "amazon_bedrock": {
+      const provider = createOpenAICompatible({
+        fetch: bedrockFetch,
+        name: conn.providerId,
+        baseURL: "https://bedrock-runtime.amazonaws.com",
+        includeUsage: true,
+      });
+      return wrapWithThinkingMiddleware(provider.chatModel(conn.modelId));
+    }
+
     case "ollama": {
       const ollamaOrigin = new URL(conn.baseUrl.replace(/\/v1\/?$/, "")).origin;
       const ollamaFetch: typeof fetch = async (input, init) => {
@@ -0,0 +1,78 @@
+import type { TextStreamPart, ToolSet } from "ai";
+import { describe, expect, it, vi } from "vitest";
+
+import { RedactionSession, restoreTextStream } from "./redaction";
+
+vi.mock("@hypr/plugin-redaction", () => ({
+  commands: {
+    redact: vi.fn(async (_scope: string, texts: string[]) => ({
+      status: "ok",
+      data: {
+        applied: true,
+        texts: texts.map((text) =>
+          text.replaceAll("alice@example.com", "[EMAIL_1]"),
+        ),
+        placeholders: [
+          {
+            placeholder: "[EMAIL_1]",
+            original: "alice@example.com",
+            kind: "email",
+          },
+        ],
+      },
+    })),
+  },
+}));
+
+async function collect<T>(stream: AsyncIterable<T>): Promise<T[]> {
+  const chunks: T[] = [];
+  for await (const chunk of stream) {
+    chunks.push(chunk);
+  }
+  return chunks;
+}
+
+describe("RedactionSession", () => {
+  it("redacts every string in a value and restores it", async () => {
+    const session = new RedactionSession("chat");
+    const value = {
+      results: [{ name: "Alice", email: "alice@example.com", score: 1 }],
+    };
+
+    const redacted = await session.redactValue(value);
+    expect(redacted).toEqual({
+      results: [{ name: "Alice", email: "[EMAIL_1]", score: 1 }],
+    });
+    expect(session.restoreValue(redacted)).toEqual(value);
+  });
+
+  it("leaves unknown placeholders alone", async () => {
+    const session = new RedactionSession("chat");
+    await session.redact("alice@example.com");
+
+    expect(session.restore("[EMAIL_1] and [EMAIL_2]")).toBe(
+      "alice@example.com and [EMAIL_2]",
+    );
+  });
+
+  it("restores placeholders split across stream chunks", async () => {
+    const session = new RedactionSession("enhance");
+    await session.redact("alice@example.com");
+
+    async function* stream(): AsyncIterable<TextStreamPart<ToolSet>> {
+      yield { type: "text-start", id: "1" };
+      yield { type: "text-delta", id: "1", text: "- Email [EMA" };
+      yield { type: "text-delta", id: "1", text: "IL_1] today [" };
+      yield { type: "text-delta", id: "1", text: "sic]" };
+      yield { type: "text-end", id: "1" };
+    }
+
+    const chunks = await collect(restoreTextStream(stream(), session));
+    const text = chunks
+      .flatMap((chunk) => (chunk.type === "text-delta" ? [chunk.text] : []))
+      .join("");
+
+    expect(text).toBe("- Email alice@example.com today [sic]");
+    expect(chunks.at(-1)).toEqual({ type: "text-end", id: "1" });
+  });
+});
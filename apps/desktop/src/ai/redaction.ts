@@ -0,0 +1,223 @@
+import type { TextStreamPart, ToolSet, UIMessageChunk } from "ai";
+
+import {
+  type Placeholder,
+  commands as redactionCommands,
+  type RedactionScope,
+} from "@hypr/plugin-redaction";
+
+// Mirrors `PlaceholderMap::restore` in crates/redaction.
+const PLACEHOLDER = /\[[A-Z][A-Z0-9_]*_\d+\]/g;
+// A chunk ending in `[EMA` may finish the placeholder in the next chunk.
+const PARTIAL_PLACEHOLDER = /\[[A-Z0-9_]*$/;
+const MAX_PLACEHOLDER_LENGTH = 64;
+
+/**
+ * Redacts everything one LLM request sends and restores the model's answer locally.
+ * Placeholders are shared across calls, so a client named in the transcript and in a
+ * tool result stays one entity for the model.
+ */
+export class RedactionSession {
+  private placeholders: Placeholder[] = [];
+  // Each call extends the placeholder list, so calls from parallel tool runs are queued.
+  private queue: Promise<unknown> = Promise.resolve();
+
+  constructor(private readonly scope: RedactionScope) {}
+
+  async redact(text: string): Promise<string> {
+    const [redacted] = await this.redactAll([text]);
+    return redacted;
+  }
+
+  // Fails instead of passing text through, so nothing leaves unredacted when the
+  // workspace asked for redaction.
+  redactAll(texts: string[]): Promise<string[]> {
+    if (texts.length === 0) {
+      return Promise.resolve(texts);
+    }
+
+    const next = this.queue.then(() => this.redactNow(texts));
+    this.queue = next.catch(() => {});
+    return next;
+  }
+
+  private async redactNow(texts: string[]): Promise<string[]> {
+    const result = await redactionCommands.redact(
+      this.scope,
+      texts,
+      this.placeholders,
+    );
+    if (result.status === "error") {
+      throw new Error(result.error);
+    }
+
+    this.placeholders = result.data.placeholders;
+    return result.data.texts;
+  }
+
+  /** Redacts every string inside a JSON-like value, e.g. tool inputs and outputs. */
+  async redactValue<T>(value: T): Promise<T> {
+    const texts: string[] = [];
+    mapStrings(value, (text) => {
+      texts.push(text);
+      return text;
+    });
+
+    const redacted = await this.redactAll(texts);
+    let index = 0;
+    return mapStrings(value, () => redacted[index++]);
+  }
+
+  restore(text: string): string {
+    if (this.placeholders.length === 0) {
+      return text;
+    }
+
+    return text.replace(
+      PLACEHOLDER,
+      (found) =>
+        this.placeholders.find((p) => p.placeholder === found)?.original ??
+        found,
+    );
+  }
+
+  restoreValue<T>(value: T): T {
+    return mapStrings(value, (text) => this.restore(text));
+  }
+
+  /** Restores streamed text, holding back a tail that could be a split placeholder. */
+  createStreamRestorer() {
+    let pending = "";
+
+    return {
+      push: (text: string) => {
+        const combined = pending + text;
+        const partial = combined.match(PARTIAL_PLACEHOLDER);
+        const cut =
+          partial?.index !== undefined &&
+          combined.length - partial.index < MAX_PLACEHOLDER_LENGTH
+            ? partial.index
+            : combined.length;
+
+        pending = combined.slice(cut);
+        return this.restore(combined.slice(0, cut));
+      },
+      flush: () => {
+        const rest = pending;
+        pending = "";
+        return this.restore(rest);
+      },
+    };
+  }
+}
+
+export async function* restoreTextStream<TOOLS extends ToolSet = ToolSet>(
+  stream: AsyncIterable<TextStreamPart<TOOLS>>,
+  session: RedactionSession,
+): AsyncIterable<TextStreamPart<TOOLS>> {
+  const restorer = session.createStreamRestorer();
+  let lastId: string | null = null;
+
+  for await (const chunk of stream) {
+    if (chunk.type === "text-delta") {
+      lastId = chunk.id;
+      const text = restorer.push(chunk.text);
+      if (text) {
+        yield { ...chunk, text };
+      }
+      continue;
+    }
+
+    if (chunk.type === "text-end") {
+      const rest = restorer.flush();
+      if (rest) {
+        yield { type: "text-delta", id: chunk.id, text: rest };
+      }
+    }
+
+    yield chunk;
+  }
+
+  const rest = restorer.flush();
+  if (rest && lastId !== null) {
+    yield { type: "text-delta", id: lastId, text: rest };
+  }
+}
+
+export function restoreUIMessageStream(
+  session: RedactionSession,
+): TransformStream<UIMessageChunk, UIMessageChunk> {
+  const restorers = new Map<
+    string,
+    ReturnType<RedactionSession["createStreamRestorer"]>
+  >();
+
+  const restorerFor = (id: string) => {
+    let restorer = restorers.get(id);
+    if (!restorer) {
+      restorer = session.createStreamRestorer();
+      restorers.set(id, restorer);
+    }
+    return restorer;
+  };
+
+  return new TransformStream({
+    transform(chunk, controller) {
+      switch (chunk.type) {
+        case "text-delta": {
+          const delta = restorerFor(chunk.id).push(chunk.delta);
+          if (delta) {
+            controller.enqueue({ ...chunk, delta });
+          }
+          return;
+        }
+        case "text-end": {
+          const delta = restorerFor(chunk.id).flush();
+          restorers.delete(chunk.id);
+          if (delta) {
+            controller.enqueue({ type: "text-delta", id: chunk.id, delta });
+          }
+          controller.enqueue(chunk);
+          return;
+        }
+        case "tool-input-available":
+          controller.enqueue({
+            ...chunk,
+            input: session.restoreValue(chunk.input),
+          });
+          return;
+        case "tool-output-available":
+          controller.enqueue({
+            ...chunk,
+            output: session.restoreValue(chunk.output),
+          });
+          return;
+        default:
+          controller.enqueue(chunk);
+      }
+    },
+    flush(controller) {
+      for (const [id, restorer] of restorers) {
+        const delta = restorer.flush();
+        if (delta) {
+          controller.enqueue({ type: "text-delta", id, delta });
+        }
+      }
+    },
+  });
+}
+
+function mapStrings<T>(value: T, fn: (text: string) => string): T {
+  if (typeof value === "string") {
+    return fn(value) as T;
+  }
+  if (Array.isArray(value)) {
+    return value.map((item) => mapStrings(item, fn)) as T;
+  }
+  if (typeof value === "object" && value !== null) {
+    return Object.fromEntries(
+      Object.entries(value).map(([key, item]) => [key, mapStrings(item, fn)]),
+    ) as T;
+  }
+  return value;
+}
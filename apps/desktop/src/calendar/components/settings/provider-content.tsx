@@ -0,0 +1,147 @@
+import { useQuery } from "@tanstack/react-query";
+import { RefreshCwIcon } from "lucide-react";
+import { useCallback, useEffect, useMemo } from "react";
+
+import {
+  type CalendarProviderType,
+  commands as calendarCommands,
+} from "@hypr/plugin-calendar";
+import { commands as openerCommands } from "@hypr/plugin-opener2";
+import { Button } from "@hypr/ui/components/ui/button";
+import { cn } from "@hypr/utils";
+
+import { SyncIndicator } from "../apple/status";
+import { useSync } from "../context";
+
+import {
+  type CalendarGroup,
+  type CalendarItem,
+  CalendarSelection,
+} from "~/calendar/components/calendar-selection";
+import type { CalendarProvider } from "~/calendar/components/shared";
+import * as main from "~/store/tinybase/store/main";
+
+// CalDAV accounts and ICS feeds are listed in settings.json instead of going through OAuth.
+export function SettingsProviderContent({
+  config,
+}: {
+  config: CalendarProvider;
+}) {
+  const provider = config.id as CalendarProviderType;
+
+  const { data: isEnabled, refetch } = useQuery({
+    queryKey: ["calendarProviderEnabled", provider],
+    queryFn: async () => {
+      const result = await calendarCommands.isProviderEnabled(provider);
+      return result.status === "ok" && result.data;
+    },
+  });
+
+  if (!isEnabled) {
+    return (
+      <div className="flex flex-col gap-1 pt-1 pb-2">
+        <span className="text-xs text-neutral-600">
+          Add {config.displayName} to the <code>calendar</code> section of
+          settings.json.
+        </span>
+        <div className="flex items-center gap-2">
+          <button
+            onClick={() => openerCommands.openUrl(config.docsPath, null)}
+            className="cursor-pointer text-xs text-neutral-600 underline transition-colors hover:text-neutral-900"
+          >
+            Setup guide
+          </button>
+          <span className="text-xs text-neutral-400">·</span>
+          <button
+            onClick={() => refetch()}
+            className="cursor-pointer text-xs text-neutral-600 underline transition-colors hover:text-neutral-900"
+          >
+            Check again
+          </button>
+        </div>
+      </div>
+    );
+  }
+
+  return <SettingsCalendarSelection provider={provider} />;
+}
+
+function SettingsCalendarSelection({
+  provider,
+}: {
+  provider: CalendarProviderType;
+}) {
+  const { status, scheduleSync, scheduleDebouncedSync, cancelDebouncedSync } =
+    useSync();
+
+  const store = main.UI.useStore(main.STORE_ID);
+  const calendars = main.UI.useTable("calendars", main.STORE_ID);
+
+  useEffect(() => {
+    scheduleSync();
+  }, [scheduleSync]);
+
+  const groups = useMemo((): CalendarGroup[] => {
+    const providerCalendars = Object.entries(calendars).filter(
+      ([_, cal]) => cal.provider === provider,
+    );
+
+    const grouped = new Map<string, CalendarItem[]>();
+    for (const [id, cal] of providerCalendars) {
+      const source = cal.source || provider;
+      if (!grouped.has(source)) grouped.set(source, []);
+      grouped.get(source)!.push({
+        id,
+        title: cal.name || "Untitled",
+        color: cal.color ?? "#888",
+        enabled: cal.enabled ?? false,
+      });
+    }
+
+    return Array.from(grouped.entries()).map(([sourceName, calendars]) => ({
+      sourceName,
+      calendars,
+    }));
+  }, [calendars, provider]);
+
+  const handleToggle = useCallback(
+    (calendar: CalendarItem, enabled: boolean) => {
+      store?.setPartialRow("calendars", calendar.id, { enabled });
+      scheduleDebouncedSync();
+    },
+    [store, scheduleDebouncedSync],
+  );
+
+  const handleRefresh = useCallback(() => {
+    cancelDebouncedSync();
+    scheduleSync();
+  }, [scheduleSync, cancelDebouncedSync]);
+
+  const isLoading = status === "syncing";
+
+  return (
+    <div className="flex flex-col gap-2 pb-2">
+      <div className="flex items-center justify-end gap-2">
+        <SyncIndicator />
+
+        <Button
+          variant="ghost"
+          size="icon"
+          onClick={handleRefresh}
+          className="size-6"
+          disabled={isLoading}
+        >
+          <RefreshCwIcon
+            className={cn(["size-3.5", isLoading && "animate-spin"])}
+          />
+        </Button>
+      </div>
+
+      <CalendarSelection
+        groups={groups}
+        onToggle={handleToggle}
+        isLoading={isLoading}
+      />
+    </div>
+  );
+}
@@ -12,6 +12,7 @@ export type CalendarProvider = {
   platform?: "macos" | "all";
   docsPath: string;
   nangoIntegrationId?: string;
+  configuredInSettings?: boolean;
 };
 
 const _PROVIDERS = [
@@ -45,6 +46,28 @@ const _PROVIDERS = [
     docsPath: "https://char.com/docs/calendar/outlook",
     nangoIntegrationId: undefined,
   },
+  {
+    disabled: false,
+    id: "caldav",
+    displayName: "CalDAV",
+    badge: "",
+    icon: <Icon icon="mdi:calendar-sync" width={20} height={20} />,
+    platform: "all",
+    docsPath: "https://char.com/docs/calendar/caldav",
+    nangoIntegrationId: undefined,
+    configuredInSettings: true,
+  },
+  {
+    disabled: false,
+    id: "ics",
+    displayName: "ICS feed",
+    badge: "Read-only",
+    icon: <Icon icon="mdi:calendar-import" width={20} height={20} />,
+    platform: "all",
+    docsPath: "https://char.com/docs/calendar/ics",
+    nangoIntegrationId: undefined,
+    configuredInSettings: true,
+  },
 ] as const satisfies readonly CalendarProvider[];
 
 export const PROVIDERS = [..._PROVIDERS];
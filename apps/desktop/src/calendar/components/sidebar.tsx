@@ -11,6 +11,7 @@ import { AppleCalendarSelection } from "./apple/calendar-selection";
 import { AccessPermissionRow, TroubleShootingLink } from "./apple/permission";
 import { SyncProvider } from "./context";
 import { OAuthProviderContent } from "./oauth/provider-content";
+import { SettingsProviderContent } from "./settings/provider-content";
 import { PROVIDERS } from "./shared";
 
 import { usePermission } from "~/shared/hooks/usePermissions";
@@ -90,6 +91,10 @@ export function CalendarSidebarContent() {
                 {provider.nangoIntegrationId && (
                   <OAuthProviderContent config={provider} />
                 )}
+                {"configuredInSettings" in provider &&
+                  provider.configuredInSettings && (
+                    <SettingsProviderContent config={provider} />
+                  )}
               </AccordionContent>
             </AccordionItem>
           ),
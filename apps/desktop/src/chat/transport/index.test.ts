@@ -0,0 +1,110 @@
+import { simulateReadableStream, type UIMessageChunk } from "ai";
+import { MockLanguageModelV3 } from "ai/test";
+import { describe, expect, it, vi } from "vitest";
+
+import type { HyprUIMessage } from "../types";
+import { CustomChatTransport } from "./index";
+
+vi.mock("@hypr/plugin-template", () => ({
+  commands: {
+    render: vi.fn().mockResolvedValue({ status: "ok", data: "" }),
+  },
+}));
+
+vi.mock("@hypr/plugin-redaction", () => ({
+  commands: {
+    redact: vi.fn(async (_scope: string, texts: string[]) => ({
+      status: "ok",
+      data: {
+        applied: true,
+        texts: texts.map((text) =>
+          text.replaceAll("alice@example.com", "[EMAIL_1]"),
+        ),
+        placeholders: [
+          {
+            placeholder: "[EMAIL_1]",
+            original: "alice@example.com",
+            kind: "email",
+          },
+        ],
+      },
+    })),
+  },
+}));
+
+function mockModel(deltas: string[]) {
+  return new MockLanguageModelV3({
+    doStream: async () => ({
+      stream: simulateReadableStream({
+        chunks: [
+          { type: "text-start" as const, id: "1" },
+          ...deltas.map((delta) => ({
+            type: "text-delta" as const,
+            id: "1",
+            delta,
+          })),
+          { type: "text-end" as const, id: "1" },
+          {
+            type: "finish" as const,
+            finishReason: { unified: "stop" as const, raw: "stop" },
+            usage: {
+              inputTokens: {
+                total: 10,
+                noCache: 10,
+                cacheRead: undefined,
+                cacheWrite: undefined,
+              },
+              outputTokens: { total: 5, text: 5, reasoning: undefined },
+            },
+          },
+        ],
+      }),
+    }),
+  });
+}
+
+async function readAll(stream: ReadableStream<UIMessageChunk>) {
+  const chunks: UIMessageChunk[] = [];
+  const reader = stream.getReader();
+  while (true) {
+    const { done, value } = await reader.read();
+    if (done) {
+      break;
+    }
+    chunks.push(value);
+  }
+  return chunks;
+}
+
+describe("CustomChatTransport redaction", () => {
+  it("keeps PII out of the request and restores it in the answer", async () => {
+    const model = mockModel(["Sure, I'll email [EMA", "IL_1]."]);
+    const transport = new CustomChatTransport(model, {}, "system");
+
+    const messages: HyprUIMessage[] = [
+      {
+        id: "m1",
+        role: "user",
+        parts: [{ type: "text", text: "Draft a note to alice@example.com" }],
+      },
+    ];
+
+    const stream = await transport.sendMessages({
+      trigger: "submit-message",
+      chatId: "chat",
+      messageId: undefined,
+      messages,
+      abortSignal: undefined,
+    });
+    const chunks = await readAll(stream);
+
+    const request = JSON.stringify(model.doStreamCalls[0].prompt);
+    expect(request).toContain("[EMAIL_1]");
+    expect(request).not.toContain("alice@example.com");
+
+    const answer = chunks
+      .flatMap((chunk) => (chunk.type === "text-delta" ? [chunk.delta] : []))
+      .join("");
+    expect(answer).toBe("Sure, I'll email alice@example.com.");
+  });
+});
@@ -27,6 +27,8 @@ import {
   type ToolOutputPart,
 } from "./helpers";
 
+import { RedactionSession, restoreUIMessageStream } from "~/ai/redaction";
+
 export class CustomChatTransport implements ChatTransport<HyprUIMessage> {
   constructor(
     private model: LanguageModel,
@@ -143,11 +145,70 @@ export class CustomChatTransport implements ChatTransport<HyprUIMessage> {
     };
   }
 
+  // Tool inputs come from the model and may name placeholders; outputs go back to it.
+  private buildRedactingToolSet(
+    tools: ToolSet,
+    redaction: RedactionSession,
+  ): ToolSet {
+    return Object.fromEntries(
+      Object.entries(tools).map(([name, tool]) => {
+        const execute = (
+          tool as {
+            execute?: (input: unknown, ...rest: unknown[]) => Promise<unknown>;
+          }
+        ).execute;
+        if (typeof execute !== "function") {
+          return [name, tool];
+        }
+
+        return [
+          name,
+          {
+            ...tool,
+            execute: async (input: unknown, ...rest: unknown[]) => {
+              const output = await execute(
+                redaction.restoreValue(input),
+                ...rest,
+              );
+              return redaction.redactValue(output);
+            },
+          },
+        ];
+      }),
+    );
+  }
+
+  private async redactMessage(
+    msg: HyprUIMessage,
+    redaction: RedactionSession,
+  ): Promise<HyprUIMessage> {
+    const parts: unknown[] = [];
+    for (const part of msg.parts) {
+      if (part.type === "text") {
+        parts.push({ ...part, text: await redaction.redact(part.text) });
+      } else if (isToolOutputPart(part)) {
+        parts.push({
+          ...part,
+          input: await redaction.redactValue(part.input),
+          output: await redaction.redactValue(part.output),
+        });
+      } else {
+        parts.push(part);
+      }
+    }
+
+    return { ...msg, parts: parts as HyprUIMessage["parts"] };
+  }
+
   sendMessages: ChatTransport<HyprUIMessage>["sendMessages"] = async (
     options,
   ) => {
     const cache = new Map<string, string | null>();
-    const tools = this.buildHydratingToolSet(cache);
+    const redaction = new RedactionSession("chat");
+    const tools = this.buildRedactingToolSet(
+      this.buildHydratingToolSet(cache),
+      redaction,
+    );
 
     const effectiveContextRefs = extractContextRefsFromMessages(
       options.messages,
@@ -219,11 +280,17 @@ export class CustomChatTransport implements ChatTransport<HyprUIMessage> {
       }
     }
 
+    // Sequential, so placeholders are numbered in conversation order.
+    const redactedMessages: HyprUIMessage[] = [];
+    for (const msg of messagesWithContext) {
+      redactedMessages.push(await this.redactMessage(msg, redaction));
+    }
+
     const result = await agent.stream({
-      messages: await convertToModelMessages(messagesWithContext),
+      messages: await convertToModelMessages(redactedMessages),
     });
 
-    return result.toUIMessageStream({
+    const stream = result.toUIMessageStream({
       originalMessages: options.messages,
       messageMetadata: ({ part }: { part: { type: string } }) => {
         if (part.type === "start") {
@@ -245,6 +312,8 @@ export class CustomChatTransport implements ChatTransport<HyprUIMessage> {
         }
       },
     });
+
+    return stream.pipeThrough(restoreUIMessageStream(redaction));
   };
 
   reconnectToStream: ChatTransport<HyprUIMessage>["reconnectToStream"] =
@@ -112,7 +112,10 @@ export function TabContentNote({
   const sessionMode = useListener((state) => state.getSessionMode(tab.id));
   const updateSessionTabState = useTabs((state) => state.updateSessionTabState);
   const { conn } = useSTTConnection();
-  const startListening = useStartListening(tab.id);
+  const startListening = useStartListening(
+    tab.id,
+    tab.state.autoStartLanguage,
+  );
   const hasAttemptedAutoStart = useRef(false);
 
   useEffect(() => {
@@ -147,7 +150,11 @@ export function TabContentNote({
 
     hasAttemptedAutoStart.current = true;
     startListening();
-    updateSessionTabState(tab, { ...tab.state, autoStart: null });
+    updateSessionTabState(tab, {
+      ...tab.state,
+      autoStart: null,
+      autoStartLanguage: null,
+    });
   }, [
     tab.id,
     tab.state,
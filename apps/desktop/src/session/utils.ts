@@ -1,3 +1,4 @@
+import type { MeetingContext } from "@hypr/plugin-listener";
 import type { SessionEvent } from "@hypr/store";
 
 import type * as main from "~/store/tinybase/store/main";
@@ -25,6 +26,37 @@ export function getSessionEventById(
   return getSessionEvent(row);
 }
 
+// Calendar context sent with a recording so the listener can bias transcription toward the
+// participants' names and the event's terms. Sessions without an event send none.
+export function getSessionMeetingContext(
+  store: Store,
+  sessionId: string,
+): MeetingContext | null {
+  const event = getSessionEventById(store, sessionId);
+  if (!event) return null;
+
+  const participants: MeetingContext["participants"] = [];
+  store.forEachRow("mapping_session_participant", (mappingId, _forEachCell) => {
+    const mapping = store.getRow("mapping_session_participant", mappingId);
+    if (mapping?.session_id !== sessionId) return;
+
+    const humanId = mapping.human_id as string | undefined;
+    const human = humanId ? store.getRow("humans", humanId) : undefined;
+    if (!human) return;
+
+    participants.push({
+      name: (human.name as string) || null,
+      email: (human.email as string) || null,
+    });
+  });
+
+  return {
+    title: event.title,
+    description: event.description ?? null,
+    participants,
+  };
+}
+
 export function findSessionByTrackingId(
   store: Store,
   trackingId: string,
@@ -161,9 +161,11 @@ function ProviderContext({ providerId }: { providerId: ProviderId }) {
                 ? "Enter your **Azure OpenAI endpoint** (e.g. `https://your-resource.openai.azure.com`) as the Base URL and your **API key**. [Report issues](https://github.com/fastrepl/char/issues/3928)"
                 : providerId === "azure_ai"
                   ? "Enter your **Azure AI Foundry endpoint** as the Base URL and your **API key**. Supports Claude and other models deployed via Azure AI Foundry. [Report issues](https://github.com/fastrepl/char/issues/3928)"
-                  : providerId === "google_generative_ai"
-                    ? "Visit [AI Studio](https://aistudio.google.com/api-keys) to create an API key."
-                    : "";
+                  : providerId === "amazon_bedrock"
+                    ? "Uses your **AWS credentials and region** (environment, `~/.aws/config` profiles or SSO), so requests stay in your account. The identity needs `bedrock:InvokeModel` and `bedrock:InvokeModelWithResponseStream`."
+                    : providerId === "google_generative_ai"
+                      ? "Visit [AI Studio](https://aistudio.google.com/api-keys) to create an API key."
+                      : "";
 
   if (!content) {
     return null;
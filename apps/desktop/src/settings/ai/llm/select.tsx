@@ -23,6 +23,7 @@ import {
 import { listAnthropicModels } from "~/settings/ai/shared/list-anthropic";
 import { listAzureAIModels } from "~/settings/ai/shared/list-azure-ai";
 import { listAzureOpenAIModels } from "~/settings/ai/shared/list-azure-openai";
+import { listBedrockModels } from "~/settings/ai/shared/list-bedrock";
 import {
   type InputModality,
   type ListModelsResult,
@@ -281,6 +282,9 @@ function useConfiguredMapping(): Record<string, ProviderStatus> {
           case "azure_ai":
             listModelsFunc = () => listAzureAIModels(baseUrl, apiKey);
             break;
+          case "amazon_bedrock":
+            listModelsFunc = () => listBedrockModels(baseUrl, apiKey);
+            break;
           case "ollama":
             listModelsFunc = () => listOllamaModels(baseUrl, apiKey);
             break;
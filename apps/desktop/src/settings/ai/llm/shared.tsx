@@ -3,6 +3,7 @@ import {
   Anthropic,
   Azure,
   AzureAI,
+  Bedrock,
   LmStudio,
   Mistral,
   Ollama,
@@ -132,6 +133,14 @@ const _PROVIDERS = [
       { kind: "requires_config", fields: ["base_url", "api_key"] },
     ],
   },
+  {
+    id: "amazon_bedrock",
+    displayName: "Amazon Bedrock",
+    badge: "Beta",
+    icon: <Bedrock size={16} />,
+    baseUrl: undefined,
+    requirements: [],
+  },
   {
     id: "google_generative_ai",
     displayName: "Google Gemini",
@@ -0,0 +1,75 @@
+import { Effect, pipe } from "effect";
+
+import { commands as bedrockCommands } from "@hypr/plugin-bedrock";
+
+import {
+  DEFAULT_RESULT,
+  extractMetadataMap,
+  type InputModality,
+  type ListModelsResult,
+  type ModelIgnoreReason,
+  partition,
+  REQUEST_TIMEOUT,
+  shouldIgnoreCommonKeywords,
+} from "./list-common";
+
+// Region and credentials come from the AWS config the plugin loads, not from settings.
+export async function listBedrockModels(
+  _baseUrl: string,
+  _apiKey: string,
+): Promise<ListModelsResult> {
+  return pipe(
+    Effect.tryPromise(() =>
+      bedrockCommands.listFoundationModels({
+        byProvider: null,
+        byCustomizationType: null,
+        byOutputModality: "TEXT",
+        byInferenceType: null,
+      }),
+    ),
+    Effect.flatMap((result) =>
+      result.status === "ok"
+        ? Effect.succeed(result.data.modelSummaries)
+        : Effect.fail(result.error),
+    ),
+    Effect.map((summaries) => {
+      const models = summaries.filter(
+        (model): model is typeof model & { modelId: string } => !!model.modelId,
+      );
+
+      return {
+        ...partition(
+          models,
+          (model) => {
+            const reasons: ModelIgnoreReason[] = [];
+            if (shouldIgnoreCommonKeywords(model.modelId)) {
+              reasons.push("common_keyword");
+            }
+            if (!model.inputModalities?.includes("TEXT")) {
+              reasons.push("no_text_input");
+            }
+            if (model.responseStreamingSupported === false) {
+              reasons.push("no_completion");
+            }
+            return reasons.length > 0 ? reasons : null;
+          },
+          (model) => model.modelId,
+        ),
+        metadata: extractMetadataMap(
+          models,
+          (model) => model.modelId,
+          (model) => ({
+            input_modalities: (model.inputModalities ?? [])
+              .map((m) => m.toLowerCase())
+              .filter(
+                (m): m is InputModality => m === "text" || m === "image",
+              ),
+          }),
+        ),
+      };
+    }),
+    Effect.timeout(REQUEST_TIMEOUT),
+    Effect.catchAll(() => Effect.succeed(DEFAULT_RESULT)),
+    Effect.runPromise,
+  );
+}
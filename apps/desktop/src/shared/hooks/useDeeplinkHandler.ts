@@ -3,14 +3,50 @@ import { isTauri } from "@tauri-apps/api/core";
 import { useEffect } from "react";
 
 import { events as deeplink2Events } from "@hypr/plugin-deeplink2";
+import {
+  commands as importerCommands,
+  type TransformKind,
+} from "@hypr/plugin-importer";
 
 import { useAuth } from "~/auth";
+import { getEnhancerService } from "~/services/enhancer";
+import { importData } from "~/store/tinybase/store/importer";
+import * as main from "~/store/tinybase/store/main";
+import { save } from "~/store/tinybase/store/save";
+import {
+  createSession,
+  getOrCreateSessionForEventId,
+} from "~/store/tinybase/store/sessions";
+import { listenerStore } from "~/store/zustand/listener/instance";
 import { useTabs } from "~/store/zustand/tabs";
 
+async function importFromPath(store: main.Store, path: string, userId: string) {
+  const transform: TransformKind = path.toLowerCase().endsWith(".json")
+    ? "as_is"
+    : "markdown_vault";
+
+  const result = await importerCommands.runImportFromPath(
+    transform,
+    path,
+    userId,
+  );
+  if (result.status === "error") {
+    console.error(`[deeplink] import failed: ${result.error}`);
+    return;
+  }
+
+  const imported = await importData(store, result.data.data, save);
+  if (imported.status === "error") {
+    console.error(`[deeplink] import failed: ${imported.error}`);
+  }
+}
+
 export function useDeeplinkHandler() {
   const auth = useAuth();
   const queryClient = useQueryClient();
   const openNew = useTabs((state) => state.openNew);
+  const store = main.UI.useStore(main.STORE_ID);
+  const { user_id } = main.UI.useValues(main.STORE_ID);
 
   useEffect(() => {
     if (!isTauri()) {
@@ -38,11 +74,58 @@ export function useDeeplinkHandler() {
             openNew({ type: "calendar" });
           }
         }
+      } else if (payload.to === "/listen/start") {
+        if (!store) {
+          return;
+        }
+
+        const { event_id, language } = payload.search;
+        const sessionId = event_id
+          ? getOrCreateSessionForEventId(store, event_id)
+          : createSession(store);
+        openNew({
+          type: "sessions",
+          id: sessionId,
+          state: { view: null, autoStart: true, autoStartLanguage: language },
+        });
+      } else if (payload.to === "/listen/stop") {
+        if (listenerStore.getState().live.status !== "inactive") {
+          listenerStore.getState().stop();
+        }
+      } else if (payload.to === "/session/new") {
+        if (!store) {
+          return;
+        }
+
+        const { title, template } = payload.search;
+        const sessionId = createSession(store, title ?? undefined);
+        if (template && store.hasRow("templates", template)) {
+          getEnhancerService()?.ensureNote(sessionId, template);
+        }
+        openNew({ type: "sessions", id: sessionId });
+      } else if (payload.to === "/session/open") {
+        const { id } = payload.search;
+        if (store?.hasRow("sessions", id)) {
+          openNew({ type: "sessions", id });
+        }
+      } else if (payload.to === "/search") {
+        openNew({
+          type: "search",
+          state: { selectedTypes: null, initialQuery: payload.search.q },
+        });
+      } else if (payload.to === "/import") {
+        if (store) {
+          void importFromPath(
+            store as main.Store,
+            payload.search.path,
+            user_id ?? "",
+          );
+        }
       }
     });
 
     return () => {
       void unlisten.then((fn) => fn());
     };
-  }, [auth, openNew, queryClient]);
+  }, [auth, openNew, queryClient, store, user_id]);
 }
@@ -0,0 +1,115 @@
+import { simulateReadableStream } from "ai";
+import { MockLanguageModelV3 } from "ai/test";
+import { describe, expect, it, vi } from "vitest";
+
+import type { TaskArgsMapTransformed } from ".";
+import { enhanceWorkflow } from "./enhance-workflow";
+
+import type { Store } from "~/store/tinybase/store/main";
+
+vi.mock("@hypr/plugin-template", () => ({
+  commands: {
+    render: vi.fn(async (input: Record<string, unknown>) => ({
+      status: "ok",
+      data:
+        "enhanceUser" in input
+          ? "Transcript: Alice (alice@example.com) will send the contract."
+          : "You summarize meetings.",
+    })),
+  },
+}));
+
+vi.mock("@hypr/plugin-redaction", () => ({
+  commands: {
+    redact: vi.fn(async (_scope: string, texts: string[]) => ({
+      status: "ok",
+      data: {
+        applied: true,
+        texts: texts.map((text) =>
+          text.replaceAll("alice@example.com", "[EMAIL_1]"),
+        ),
+        placeholders: [
+          {
+            placeholder: "[EMAIL_1]",
+            original: "alice@example.com",
+            kind: "email",
+          },
+        ],
+      },
+    })),
+  },
+}));
+
+function mockModel(deltas: string[]) {
+  return new MockLanguageModelV3({
+    doStream: async () => ({
+      stream: simulateReadableStream({
+        chunks: [
+          { type: "text-start" as const, id: "1" },
+          ...deltas.map((delta) => ({
+            type: "text-delta" as const,
+            id: "1",
+            delta,
+          })),
+          { type: "text-end" as const, id: "1" },
+          {
+            type: "finish" as const,
+            finishReason: { unified: "stop" as const, raw: "stop" },
+            usage: {
+              inputTokens: {
+                total: 10,
+                noCache: 10,
+                cacheRead: undefined,
+                cacheWrite: undefined,
+              },
+              outputTokens: { total: 5, text: 5, reasoning: undefined },
+            },
+          },
+        ],
+      }),
+    }),
+  });
+}
+
+describe("enhanceWorkflow redaction", () => {
+  it("keeps PII out of the request and restores it in the summary", async () => {
+    const model = mockModel([
+      "# Action Items\n- Contract goes to [EM",
+      "AIL_1] this week",
+    ]);
+    const store = { getCell: () => undefined } as unknown as Store;
+    const args = {
+      language: "en",
+      session: { title: "Contract review" },
+      participants: [],
+      template: {
+        title: "",
+        description: null,
+        sections: [{ title: "Action Items", description: null }],
+      },
+      transcripts: [],
+      preMeetingMemo: "",
+      postMeetingMemo: "",
+    } as unknown as TaskArgsMapTransformed["enhance"];
+
+    let text = "";
+    for await (const chunk of enhanceWorkflow.executeWorkflow({
+      model,
+      args,
+      onProgress: () => {},
+      signal: new AbortController().signal,
+      store,
+    })) {
+      if (chunk.type === "text-delta") {
+        text += chunk.text;
+      }
+    }
+
+    const request = JSON.stringify(model.doStreamCalls[0].prompt);
+    expect(request).toContain("[EMAIL_1]");
+    expect(request).not.toContain("alice@example.com");
+    expect(text).toBe(
+      "# Action Items\n- Contract goes to alice@example.com this week",
+    );
+  });
+});
@@ -16,6 +16,7 @@ import { templateSectionSchema } from "@hypr/store";
 import type { TaskArgsMapTransformed, TaskConfig } from ".";
 import { createEnhanceValidator } from "./enhance-validator";
 
+import { RedactionSession, restoreTextStream } from "~/ai/redaction";
 import type { Store } from "~/store/tinybase/store/main";
 import { getCustomPrompt } from "~/store/tinybase/store/prompts";
 import { normalizeBulletPoints } from "~/store/zustand/ai-task/shared/transform_impl";
@@ -40,6 +41,7 @@ async function* executeWorkflow(params: {
   store: Store;
 }) {
   const { model, args, onProgress, signal, store } = params;
+  const redaction = new RedactionSession("enhance");
 
   const sections = await generateTemplateIfNeeded({
     model,
@@ -47,6 +49,7 @@ async function* executeWorkflow(params: {
     onProgress,
     signal,
     store,
+    redaction,
   });
   const argsWithTemplate: TaskArgsMapTransformed["enhance"] = {
     ...args,
@@ -54,16 +57,21 @@ async function* executeWorkflow(params: {
   };
 
   const system = await getSystemPrompt(argsWithTemplate);
-  const prompt = await getUserPrompt(argsWithTemplate, store);
+  const prompt = await redaction.redact(
+    await getUserPrompt(argsWithTemplate, store),
+  );
 
-  yield* generateSummary({
-    model,
-    args: argsWithTemplate,
-    system,
-    prompt,
-    onProgress,
-    signal,
-  });
+  yield* restoreTextStream(
+    generateSummary({
+      model,
+      args: argsWithTemplate,
+      system,
+      prompt,
+      onProgress,
+      signal,
+    }),
+    redaction,
+  );
 }
 
 async function getSystemPrompt(args: TaskArgsMapTransformed["enhance"]) {
@@ -135,14 +143,17 @@ async function generateTemplateIfNeeded(params: {
   onProgress: (step: any) => void;
   signal: AbortSignal;
   store: Store;
+  redaction: RedactionSession;
 }): Promise<TemplateSection[] | null> {
-  const { model, args, onProgress, signal, store } = params;
+  const { model, args, onProgress, signal, store, redaction } = params;
 
   if (!args.template) {
     onProgress({ type: "analyzing" });
 
     const schema = z.object({ sections: z.array(templateSectionSchema) });
-    const userPrompt = await getUserPrompt(args, store);
+    const userPrompt = await redaction.redact(
+      await getUserPrompt(args, store),
+    );
 
     const result = await generateStructuredOutput({
       model,
@@ -156,8 +167,8 @@ async function generateTemplateIfNeeded(params: {
     }
 
     return result.sections.map((s) => ({
-      title: s.title,
-      description: s.description ?? null,
+      title: redaction.restore(s.title),
+      description: s.description ? redaction.restore(s.description) : null,
     }));
   } else {
     return args.template.sections;
@@ -61,7 +61,10 @@ export type GeneralState = {
 export type GeneralActions = {
   start: (
     params: SessionParams,
-    options?: { handlePersist?: HandlePersistCallback },
+    options?: {
+      handlePersist?: HandlePersistCallback;
+      handleStopped?: () => void;
+    },
   ) => void;
   stop: () => void;
   setMuted: (value: boolean) => void;
@@ -239,6 +242,7 @@ export const createGeneralSlice = <
         );
 
         get().resetTranscript();
+        options?.handleStopped?.();
       }
     };
 
@@ -0,0 +1,130 @@
+import {
+  commands as listener2Commands,
+  type SpeakerHint,
+  type SpeakerKey,
+} from "@hypr/plugin-listener2";
+
+import { id } from "~/shared/utils";
+import type * as main from "~/store/tinybase/store/main";
+import { parseProviderSpeakerIndex } from "~/stt/speaker-hints";
+import type { SpeakerHintWithId } from "~/stt/types";
+import {
+  parseTranscriptHints,
+  parseTranscriptWords,
+  updateTranscriptHints,
+} from "~/stt/utils";
+
+type Store = NonNullable<ReturnType<typeof main.UI.useStore>>;
+
+const keyOf = (key: SpeakerKey) => `${key.type}:${key.value}`;
+
+// Names who spoke in the browser meeting, as the session's participants know them.
+function participantHumanIds(
+  store: Store,
+  sessionId: string,
+): Partial<Record<string, string>> {
+  const humanIds: Partial<Record<string, string>> = {};
+  store.forEachRow("mapping_session_participant", (mappingId, _forEachCell) => {
+    const mapping = store.getRow("mapping_session_participant", mappingId);
+    if (mapping?.session_id !== sessionId) return;
+
+    const humanId = mapping.human_id as string | undefined;
+    const name = humanId
+      ? (store.getCell("humans", humanId, "name") as string | undefined)
+      : undefined;
+    if (humanId && name) {
+      humanIds[name] = humanId;
+    }
+  });
+  return humanIds;
+}
+
+// After a live session, assigns diarized speakers to participants using who the browser
+// meeting showed as speaking. Words the user already assigned are left alone.
+export async function assignMeetingSpeakers(
+  store: Store,
+  sessionId: string,
+  transcriptId: string,
+  startedAt: number,
+) {
+  const humanIds = participantHumanIds(store, sessionId);
+  if (Object.keys(humanIds).length === 0) return;
+
+  const words = parseTranscriptWords(store, transcriptId);
+  if (words.length === 0) return;
+
+  const existingHints = parseTranscriptHints(store, transcriptId);
+  const speakerHints: SpeakerHint[] = [];
+  const assignedWordIds = new Set<string>();
+  existingHints.forEach((hint) => {
+    if (typeof hint.word_id !== "string") return;
+    if (hint.type === "user_speaker_assignment") {
+      assignedWordIds.add(hint.word_id);
+    } else if (hint.type === "provider_speaker_index") {
+      const parsed = parseProviderSpeakerIndex(hint.value);
+      if (parsed) {
+        speakerHints.push({
+          word_id: hint.word_id,
+          speaker_index: parsed.speaker_index,
+        });
+      }
+    }
+  });
+
+  const result = await listener2Commands.assignSpeakers({
+    started_at_ms: startedAt,
+    ended_at_ms: Date.now(),
+    words: words.map((word) => ({
+      id: word.id,
+      text: word.text,
+      start_ms: word.start_ms,
+      end_ms: word.end_ms,
+      channel: word.channel,
+      state: "final",
+    })),
+    hints: speakerHints,
+    human_ids: humanIds,
+  });
+  if (result.status === "error") {
+    console.error("[listener] speaker assignment failed:", result.error);
+    return;
+  }
+
+  const humanIdByKey = new Map<string, string>();
+  result.data.forEach(({ key, identity }) => {
+    if (identity.type === "assigned") {
+      humanIdByKey.set(keyOf(key), identity.value.id);
+    }
+  });
+  if (humanIdByKey.size === 0) return;
+
+  const speakerByWordId = new Map(
+    speakerHints.map((hint) => [hint.word_id, hint.speaker_index]),
+  );
+  const newHints: SpeakerHintWithId[] = [];
+  words.forEach((word) => {
+    if (assignedWordIds.has(word.id)) return;
+
+    const speakerIndex = speakerByWordId.get(word.id);
+    const key: SpeakerKey =
+      speakerIndex === undefined
+        ? { type: "channel", value: word.channel }
+        : { type: "index", value: speakerIndex };
+    const humanId = humanIdByKey.get(keyOf(key));
+    if (!humanId) return;
+
+    newHints.push({
+      id: id(),
+      word_id: word.id,
+      type: "user_speaker_assignment",
+      value: JSON.stringify({ human_id: humanId }),
+    });
+  });
+
+  if (newHints.length > 0) {
+    updateTranscriptHints(store, transcriptId, [
+      ...parseTranscriptHints(store, transcriptId),
+      ...newHints,
+    ]);
+  }
+}
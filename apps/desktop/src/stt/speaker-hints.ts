@@ -69,7 +69,7 @@ export function convertStorageHintsToRuntime(
   return hints;
 }
 
-const parseProviderSpeakerIndex = (
+export const parseProviderSpeakerIndex = (
   raw: unknown,
 ): ProviderSpeakerIndexHint | undefined => {
   if (raw == null) {
@@ -1,4 +1,4 @@
-import { useCallback } from "react";
+import { useCallback, useMemo } from "react";
 
 import { commands as analyticsCommands } from "@hypr/plugin-analytics";
 import type { TranscriptStorage } from "@hypr/store";
@@ -7,11 +7,15 @@ import { useListener } from "./contexts";
 import { useKeywords } from "./useKeywords";
 import { useSTTConnection } from "./useSTTConnection";
 
-import { getSessionEventById } from "~/session/utils";
+import {
+  getSessionEventById,
+  getSessionMeetingContext,
+} from "~/session/utils";
 import { useConfigValue } from "~/shared/config";
 import { id } from "~/shared/utils";
 import * as main from "~/store/tinybase/store/main";
 import type { HandlePersistCallback } from "~/store/zustand/listener/transcript";
+import { assignMeetingSpeakers } from "~/stt/meeting-speakers";
 import type { SpeakerHintWithId, WordWithId } from "~/stt/types";
 import {
   parseTranscriptHints,
@@ -20,12 +24,19 @@ import {
   updateTranscriptWords,
 } from "~/stt/utils";
 
-export function useStartListening(sessionId: string) {
+export function useStartListening(
+  sessionId: string,
+  languageOverride?: string | null,
+) {
   const { user_id } = main.UI.useValues(main.STORE_ID);
   const store = main.UI.useStore(main.STORE_ID);
 
   const record_enabled = useConfigValue("save_recordings");
-  const languages = useConfigValue("spoken_languages");
+  const spokenLanguages = useConfigValue("spoken_languages");
+  const languages = useMemo(
+    () => (languageOverride ? [languageOverride] : spokenLanguages),
+    [languageOverride, spokenLanguages],
+  );
 
   const start = useListener((state) => state.start);
   const { conn } = useSTTConnection();
@@ -134,9 +145,13 @@ export function useStartListening(sessionId: string) {
         base_url: conn.baseUrl,
         api_key: conn.apiKey,
         keywords,
+        meeting: getSessionMeetingContext(store, sessionId),
       },
       {
         handlePersist,
+        handleStopped: () => {
+          void assignMeetingSpeakers(store, sessionId, transcriptId, startedAt);
+        },
       },
     );
   }, [
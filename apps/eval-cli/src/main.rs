@@ -5,6 +5,7 @@ use clap::{Parser, Subcommand};
 use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
 
 mod report;
+mod stt;
 mod submissions;
 
 use hypr_eval::{
@@ -40,6 +41,29 @@ enum Commands {
         cache_dir: Option<String>,
     },
     List,
+    /// Benchmark speech-to-text providers against reference transcripts.
+    Stt {
+        /// Providers to compare, optionally with a model (`deepgram:nova-3`).
+        #[arg(short, long, value_delimiter = ',', required = true)]
+        providers: Vec<String>,
+
+        #[arg(long, value_enum, default_value = "batch")]
+        mode: stt::ModeArg,
+
+        #[arg(short, long, value_delimiter = ',')]
+        samples: Option<Vec<String>>,
+
+        /// JSON list of `{id, audio, transcript | words, diarization?}`; defaults to the
+        /// bundled english fixtures.
+        #[arg(long)]
+        manifest: Option<std::path::PathBuf>,
+
+        #[arg(short, long, default_value = "en")]
+        language: String,
+
+        #[arg(short, long, default_value = "table")]
+        output: String,
+    },
     Completion {
         #[arg(value_enum)]
         shell: Shell,
@@ -74,6 +98,19 @@ fn main() -> ExitCode {
         Commands::List => {
             list_cases();
         }
+        Commands::Stt {
+            providers,
+            mode,
+            samples,
+            manifest,
+            language,
+            output,
+        } => {
+            if let Err(e) = stt::run_stt(providers, mode, samples, manifest, language, output) {
+                eprintln!("Error: {}", e);
+                return ExitCode::FAILURE;
+            }
+        }
         Commands::Completion { shell } => {
             generate_completion(shell);
         }
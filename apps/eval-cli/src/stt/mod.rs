@@ -0,0 +1,104 @@
+mod report;
+mod run;
+mod samples;
+
+use std::path::PathBuf;
+
+use futures_util::future::join_all;
+use hypr_eval::stt::{SttMode, SttResult, SttSample};
+use owhisper_interface::ListenParams;
+
+use run::Target;
+
+#[derive(Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ModeArg {
+    Batch,
+    Live,
+    Both,
+}
+
+impl ModeArg {
+    fn modes(self) -> &'static [SttMode] {
+        match self {
+            ModeArg::Batch => &[SttMode::Batch],
+            ModeArg::Live => &[SttMode::Live],
+            ModeArg::Both => &[SttMode::Batch, SttMode::Live],
+        }
+    }
+}
+
+pub fn run_stt(
+    providers: Vec<String>,
+    mode: ModeArg,
+    sample_filter: Option<Vec<String>>,
+    manifest: Option<PathBuf>,
+    language: String,
+    output_format: String,
+) -> Result<(), String> {
+    let targets = providers
+        .iter()
+        .map(|spec| Target::parse(spec))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let samples = match &manifest {
+        Some(path) => samples::load_manifest(path)?,
+        None => samples::fixture_samples()?,
+    };
+    let samples = samples::filter_samples(samples, sample_filter.as_deref());
+    if samples.is_empty() {
+        return Err("no samples matched the filter".to_string());
+    }
+
+    let params = ListenParams {
+        languages: vec![
+            language
+                .parse()
+                .map_err(|_| format!("invalid language '{}'", language))?,
+        ],
+        ..Default::default()
+    };
+
+    let runtime = tokio::runtime::Runtime::new().map_err(|e| e.to_string())?;
+    let results: Vec<SttResult> = runtime
+        .block_on(join_all(targets.iter().map(|target| {
+            run_target(target, mode.modes(), &params, &samples)
+        })))
+        .into_iter()
+        .flatten()
+        .collect();
+
+    match output_format.as_str() {
+        "json" => report::render_json(&results),
+        "markdown" => report::render_tables(&results, true),
+        _ => report::render_tables(&results, false),
+    }
+}
+
+/// Samples run one at a time per provider so live sessions don't compete for rate limits.
+async fn run_target(
+    target: &Target,
+    modes: &[SttMode],
+    params: &ListenParams,
+    samples: &[SttSample],
+) -> Vec<SttResult> {
+    let label = target.label();
+    let mut results = Vec::new();
+
+    for &mode in modes {
+        for sample in samples {
+            eprintln!("{} {} ({})...", label, sample.id, mode.as_str());
+
+            let output = match mode {
+                SttMode::Batch => run::transcribe_batch(target, params, sample).await,
+                SttMode::Live => run::transcribe_live(target, params, sample).await,
+            };
+
+            results.push(match output {
+                Ok(output) => SttResult::score(sample, &label, mode, output),
+                Err(e) => SttResult::failed(sample, &label, mode, e),
+            });
+        }
+    }
+
+    results
+}
@@ -0,0 +1,217 @@
+use comfy_table::{
+    Cell, Color, ContentArrangement, Table,
+    presets::{ASCII_MARKDOWN, UTF8_FULL_CONDENSED},
+};
+
+use hypr_eval::stt::{DiarizationError, ErrorCounts, SttResult, SttSummary, summarize};
+
+pub fn render_json(results: &[SttResult]) -> std::result::Result<(), String> {
+    let counts = |c: &ErrorCounts| {
+        serde_json::json!({
+            "rate": c.rate(),
+            "substitutions": c.substitutions,
+            "deletions": c.deletions,
+            "insertions": c.insertions,
+            "reference_len": c.reference_len,
+        })
+    };
+    let der = |d: &Option<DiarizationError>| {
+        d.map(|d| {
+            serde_json::json!({
+                "rate": d.rate(),
+                "missed_ms": d.missed_ms,
+                "false_alarm_ms": d.false_alarm_ms,
+                "confusion_ms": d.confusion_ms,
+                "reference_ms": d.reference_ms,
+            })
+        })
+    };
+
+    let json = serde_json::to_string_pretty(&serde_json::json!({
+        "summary": summarize(results).iter().map(|s| {
+            serde_json::json!({
+                "provider": s.provider,
+                "mode": s.mode.as_str(),
+                "samples": s.samples,
+                "failed": s.failed,
+                "wer": counts(&s.wer),
+                "cer": counts(&s.cer),
+                "der": der(&s.der),
+                "mean_elapsed_ms": s.mean_elapsed_ms,
+                "mean_first_word_latency_ms": s.mean_first_word_latency_ms,
+            })
+        }).collect::<Vec<_>>(),
+        "results": results.iter().map(|r| {
+            serde_json::json!({
+                "sample_id": r.sample_id,
+                "provider": r.provider,
+                "mode": r.mode.as_str(),
+                "transcript": r.transcript,
+                "wer": counts(&r.wer),
+                "cer": counts(&r.cer),
+                "der": der(&r.der),
+                "elapsed_ms": r.elapsed_ms,
+                "first_word_latency_ms": r.first_word_latency_ms,
+                "error": r.error,
+            })
+        }).collect::<Vec<_>>(),
+    }))
+    .map_err(|e| format!("Failed to encode JSON: {}", e))?;
+
+    println!("{}", json);
+
+    check_errors(results)
+}
+
+pub fn render_tables(results: &[SttResult], markdown: bool) -> std::result::Result<(), String> {
+    let summaries = summarize(results);
+
+    if markdown {
+        println!("## Summary\n");
+    }
+    println!("{}", summary_table(&summaries, markdown));
+    println!();
+    if markdown {
+        println!("## Samples\n");
+    }
+    println!("{}", sample_table(results, markdown));
+
+    let errors: Vec<String> = results
+        .iter()
+        .filter_map(|r| {
+            r.error.as_ref().map(|e| {
+                format!(
+                    "{} {} ({}): {}",
+                    r.provider,
+                    r.sample_id,
+                    r.mode.as_str(),
+                    e
+                )
+            })
+        })
+        .collect();
+    if !errors.is_empty() {
+        eprintln!();
+        eprintln!("\x1b[31mErrors:\x1b[0m");
+        for detail in &errors {
+            eprintln!("\x1b[31m  - {}\x1b[0m", detail);
+        }
+    }
+
+    check_errors(results)
+}
+
+fn new_table(markdown: bool) -> Table {
+    let mut table = Table::new();
+    if markdown {
+        table.load_preset(ASCII_MARKDOWN);
+    } else {
+        table
+            .load_preset(UTF8_FULL_CONDENSED)
+            .set_content_arrangement(ContentArrangement::Dynamic);
+    }
+    table
+}
+
+fn summary_table(summaries: &[SttSummary], markdown: bool) -> Table {
+    let mut table = new_table(markdown);
+    table.set_header(vec![
+        "Provider",
+        "Mode",
+        "Samples",
+        "WER",
+        "CER",
+        "DER",
+        "First word",
+        "Time",
+    ]);
+
+    for s in summaries {
+        let samples = if s.failed > 0 {
+            Cell::new(format!("{} ({} failed)", s.samples, s.failed)).fg(Color::Red)
+        } else {
+            Cell::new(s.samples)
+        };
+
+        table.add_row(vec![
+            Cell::new(&s.provider),
+            Cell::new(s.mode.as_str()),
+            samples,
+            Cell::new(format_rate(s.wer.rate())),
+            Cell::new(format_rate(s.cer.rate())),
+            Cell::new(s.der.map(|d| format_rate(d.rate())).unwrap_or_else(dash)),
+            Cell::new(format_ms(s.mean_first_word_latency_ms)),
+            Cell::new(format_ms(s.mean_elapsed_ms)),
+        ]);
+    }
+
+    table
+}
+
+fn sample_table(results: &[SttResult], markdown: bool) -> Table {
+    let mut table = new_table(markdown);
+    table.set_header(vec![
+        "Sample",
+        "Provider",
+        "Mode",
+        "WER",
+        "CER",
+        "DER",
+        "First word",
+        "Time",
+    ]);
+
+    for r in results {
+        let mut row = vec![
+            Cell::new(&r.sample_id),
+            Cell::new(&r.provider),
+            Cell::new(r.mode.as_str()),
+        ];
+
+        if r.error.is_some() {
+            row.push(Cell::new("error").fg(Color::Red));
+            row.extend((0..4).map(|_| Cell::new("-")));
+        } else {
+            row.extend([
+                Cell::new(format!(
+                    "{} (S{} D{} I{})",
+                    format_rate(r.wer.rate()),
+                    r.wer.substitutions,
+                    r.wer.deletions,
+                    r.wer.insertions
+                )),
+                Cell::new(format_rate(r.cer.rate())),
+                Cell::new(r.der.map(|d| format_rate(d.rate())).unwrap_or_else(dash)),
+                Cell::new(format_ms(r.first_word_latency_ms)),
+                Cell::new(format_ms(Some(r.elapsed_ms))),
+            ]);
+        }
+
+        table.add_row(row);
+    }
+
+    table
+}
+
+fn check_errors(results: &[SttResult]) -> std::result::Result<(), String> {
+    if results.iter().any(|r| r.error.is_some()) {
+        return Err("benchmark failed".to_string());
+    }
+    Ok(())
+}
+
+fn format_rate(rate: f64) -> String {
+    format!("{:.1}%", rate * 100.0)
+}
+
+fn format_ms(ms: Option<u64>) -> String {
+    match ms {
+        Some(ms) if ms >= 1000 => format!("{:.2}s", ms as f64 / 1000.0),
+        Some(ms) => format!("{}ms", ms),
+        None => dash(),
+    }
+}
+
+fn dash() -> String {
+    "-".to_string()
+}
@@ -0,0 +1,316 @@
+use std::num::NonZeroU8;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use bytes::Bytes;
+use futures_util::StreamExt;
+use hypr_audio_utils::Source;
+use hypr_eval::stt::{SpeakerSegment, SttOutput, SttSample};
+use owhisper_client::{
+    AssemblyAIAdapter, BatchClient, BatchSttAdapter, DashScopeAdapter, DeepgramAdapter,
+    ElevenLabsAdapter, FinalizeHandle, FireworksAdapter, GladiaAdapter, ListenClient,
+    MistralAdapter, OpenAIAdapter, Provider, RealtimeSttAdapter, SonioxAdapter,
+};
+use owhisper_interface::stream::StreamResponse;
+use owhisper_interface::{ListenParams, MixedMessage};
+
+/// Live audio is sent in real time, one chunk per interval.
+const CHUNK_MS: u64 = 100;
+/// After finalizing, stop waiting once the provider has been quiet this long.
+const FINALIZE_IDLE: Duration = Duration::from_secs(5);
+/// Same-speaker words closer than this are one turn, so pauses between words don't count as
+/// missed speech.
+const TURN_GAP_SECS: f64 = 0.5;
+
+/// A provider to benchmark, with an optional model override (`deepgram:nova-3`).
+pub struct Target {
+    pub provider: Provider,
+    pub model: Option<String>,
+    api_key: String,
+}
+
+impl Target {
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let (name, model) = match spec.split_once(':') {
+            Some((name, model)) => (name, Some(model.to_string())),
+            None => (spec, None),
+        };
+        let provider: Provider = name
+            .trim()
+            .parse()
+            .map_err(|_| format!("unknown provider '{}'", name))?;
+        let api_key = std::env::var(provider.env_key_name()).map_err(|_| {
+            format!(
+                "{} environment variable is not set",
+                provider.env_key_name()
+            )
+        })?;
+
+        Ok(Self {
+            provider,
+            model,
+            api_key,
+        })
+    }
+
+    pub fn label(&self) -> String {
+        match &self.model {
+            Some(model) => format!("{}:{}", self.provider, model),
+            None => self.provider.to_string(),
+        }
+    }
+
+    fn params(&self, base: &ListenParams) -> ListenParams {
+        ListenParams {
+            model: self.model.clone(),
+            ..base.clone()
+        }
+    }
+}
+
+pub async fn transcribe_batch(
+    target: &Target,
+    params: &ListenParams,
+    sample: &SttSample,
+) -> Result<SttOutput, String> {
+    macro_rules! batch {
+        ($adapter:ty) => {
+            run_batch::<$adapter>(target, target.params(params), sample).await
+        };
+    }
+
+    match target.provider {
+        Provider::Deepgram => batch!(DeepgramAdapter),
+        Provider::AssemblyAI => batch!(AssemblyAIAdapter),
+        Provider::Soniox => batch!(SonioxAdapter),
+        Provider::OpenAI => batch!(OpenAIAdapter),
+        Provider::Gladia => batch!(GladiaAdapter),
+        Provider::ElevenLabs => batch!(ElevenLabsAdapter),
+        Provider::Mistral => batch!(MistralAdapter),
+        Provider::Fireworks => batch!(FireworksAdapter),
+        Provider::DashScope => Err(format!(
+            "{} does not support batch transcription",
+            target.provider
+        )),
+    }
+}
+
+pub async fn transcribe_live(
+    target: &Target,
+    params: &ListenParams,
+    sample: &SttSample,
+) -> Result<SttOutput, String> {
+    let params = ListenParams {
+        sample_rate: target.provider.default_live_sample_rate(),
+        channels: 1,
+        ..target.params(params)
+    };
+
+    macro_rules! live {
+        ($adapter:ty) => {
+            run_live::<$adapter>(target, params, sample).await
+        };
+    }
+
+    match target.provider {
+        Provider::Deepgram => live!(DeepgramAdapter),
+        Provider::AssemblyAI => live!(AssemblyAIAdapter),
+        Provider::Soniox => live!(SonioxAdapter),
+        Provider::OpenAI => live!(OpenAIAdapter),
+        Provider::Gladia => live!(GladiaAdapter),
+        Provider::ElevenLabs => live!(ElevenLabsAdapter),
+        Provider::Mistral => live!(MistralAdapter),
+        Provider::Fireworks => live!(FireworksAdapter),
+        Provider::DashScope => live!(DashScopeAdapter),
+    }
+}
+
+async fn run_batch<A: BatchSttAdapter>(
+    target: &Target,
+    params: ListenParams,
+    sample: &SttSample,
+) -> Result<SttOutput, String> {
+    let client = BatchClient::<A>::builder()
+        .api_base(target.provider.default_api_base())
+        .api_key(target.api_key.as_str())
+        .params(params)
+        .build();
+
+    let started = Instant::now();
+    let response = client
+        .transcribe_file(&sample.audio_path)
+        .await
+        .map_err(|e| e.to_string())?;
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+
+    let Some(alternative) = response
+        .results
+        .channels
+        .first()
+        .and_then(|channel| channel.alternatives.first())
+    else {
+        return Ok(SttOutput {
+            elapsed_ms,
+            ..Default::default()
+        });
+    };
+
+    Ok(SttOutput {
+        transcript: alternative.transcript.clone(),
+        speakers: speaker_turns(
+            alternative
+                .words
+                .iter()
+                .map(|w| (w.start, w.end, w.speaker.map(|s| s.to_string()))),
+        ),
+        elapsed_ms,
+        first_word_latency_ms: None,
+    })
+}
+
+async fn run_live<A: RealtimeSttAdapter>(
+    target: &Target,
+    params: ListenParams,
+    sample: &SttSample,
+) -> Result<SttOutput, String> {
+    let chunks = pcm_chunks(&sample.audio_path, params.sample_rate)?;
+    let audio_duration = Duration::from_millis(chunks.len() as u64 * CHUNK_MS);
+
+    let client = ListenClient::builder()
+        .adapter::<A>()
+        .api_base(target.provider.default_api_base())
+        .api_key(target.api_key.as_str())
+        .params(params)
+        .build_single()
+        .await;
+
+    let input = Box::pin(tokio_stream::StreamExt::throttle(
+        futures_util::stream::iter(chunks).map(MixedMessage::Audio),
+        Duration::from_millis(CHUNK_MS),
+    ));
+    let (stream, handle) = client
+        .from_realtime_audio(input)
+        .await
+        .map_err(|e| e.to_string())?;
+    futures_util::pin_mut!(stream);
+
+    let started = Instant::now();
+    let mut transcript = LiveTranscript::default();
+
+    let audio_sent = tokio::time::sleep(audio_duration);
+    tokio::pin!(audio_sent);
+    let mut closed = false;
+    while !closed {
+        tokio::select! {
+            item = stream.next() => match item {
+                Some(item) => transcript.push(item.map_err(|e| e.to_string())?, started.elapsed()),
+                None => closed = true,
+            },
+            _ = &mut audio_sent => break,
+        }
+    }
+
+    if !closed {
+        handle.finalize().await;
+        while let Ok(Some(item)) = tokio::time::timeout(FINALIZE_IDLE, stream.next()).await {
+            transcript.push(item.map_err(|e| e.to_string())?, started.elapsed());
+        }
+    }
+
+    Ok(transcript.finish(sample, started.elapsed()))
+}
+
+#[derive(Default)]
+struct LiveTranscript {
+    finals: Vec<String>,
+    words: Vec<(f64, f64, Option<String>)>,
+    first_word_at: Option<Duration>,
+}
+
+impl LiveTranscript {
+    fn push(&mut self, response: StreamResponse, elapsed: Duration) {
+        let StreamResponse::TranscriptResponse {
+            is_final, channel, ..
+        } = response
+        else {
+            return;
+        };
+        let Some(alternative) = channel.alternatives.into_iter().next() else {
+            return;
+        };
+
+        if self.first_word_at.is_none() && !alternative.transcript.trim().is_empty() {
+            self.first_word_at = Some(elapsed);
+        }
+
+        if is_final {
+            self.words.extend(
+                alternative
+                    .words
+                    .iter()
+                    .map(|w| (w.start, w.end, w.speaker.map(|s| s.to_string()))),
+            );
+            self.finals.push(alternative.transcript);
+        }
+    }
+
+    fn finish(self, sample: &SttSample, elapsed: Duration) -> SttOutput {
+        SttOutput {
+            transcript: self.finals.join(" "),
+            speakers: speaker_turns(self.words.into_iter()),
+            elapsed_ms: elapsed.as_millis() as u64,
+            first_word_latency_ms: self
+                .first_word_at
+                .map(|at| (at.as_millis() as u64).saturating_sub(sample.first_word_ms)),
+        }
+    }
+}
+
+/// Decodes to mono 16-bit PCM at `sample_rate`, split into `CHUNK_MS` pieces.
+fn pcm_chunks(path: &Path, sample_rate: u32) -> Result<Vec<Bytes>, String> {
+    let source = hypr_audio_utils::source_from_path(path).map_err(|e| e.to_string())?;
+    let channels = NonZeroU8::new(source.channels() as u8).unwrap_or(NonZeroU8::MIN);
+    let resampled =
+        hypr_audio_utils::resample_audio(source, sample_rate).map_err(|e| e.to_string())?;
+    let mono = hypr_audio_utils::mix_down_to_mono(&resampled, channels);
+
+    let samples_per_chunk = (sample_rate as u64 * CHUNK_MS / 1000) as usize;
+    Ok(mono
+        .chunks(samples_per_chunk)
+        .map(|chunk| {
+            hypr_audio_utils::f32_to_i16_samples(chunk)
+                .iter()
+                .flat_map(|s| s.to_le_bytes())
+                .collect::<Vec<u8>>()
+                .into()
+        })
+        .collect())
+}
+
+/// Merges consecutive same-speaker words into turns; unlabeled words are skipped.
+fn speaker_turns(words: impl Iterator<Item = (f64, f64, Option<String>)>) -> Vec<SpeakerSegment> {
+    let mut turns: Vec<SpeakerSegment> = Vec::new();
+
+    for (start, end, speaker) in words {
+        let Some(speaker) = speaker else {
+            continue;
+        };
+        let (start_ms, end_ms) = ((start * 1000.0) as u64, (end * 1000.0) as u64);
+
+        match turns.last_mut() {
+            Some(last)
+                if last.speaker == speaker
+                    && start_ms <= last.end_ms + (TURN_GAP_SECS * 1000.0) as u64 =>
+            {
+                last.end_ms = last.end_ms.max(end_ms);
+            }
+            _ => turns.push(SpeakerSegment {
+                start_ms,
+                end_ms,
+                speaker,
+            }),
+        }
+    }
+
+    turns
+}
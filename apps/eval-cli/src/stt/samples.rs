@@ -0,0 +1,146 @@
+use std::path::{Path, PathBuf};
+
+use hypr_eval::stt::{SpeakerSegment, SttSample};
+use serde::Deserialize;
+
+const DATA_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../crates/data/src");
+const FIXTURES: &[&str] = &["english_1", "english_2"];
+const AUDIO_FILES: &[&str] = &["audio.wav", "audio.flac", "audio.mp3"];
+
+/// One entry of a `--manifest` file. Relative paths resolve against the manifest's directory.
+#[derive(Deserialize)]
+struct ManifestEntry {
+    id: String,
+    audio: PathBuf,
+    /// Plain reference text.
+    #[serde(default)]
+    transcript: Option<String>,
+    /// Word-timed reference in the `crates/data` `transcription.json` format.
+    #[serde(default)]
+    words: Option<PathBuf>,
+    /// Speaker turns in the `crates/data` `diarization.json` format.
+    #[serde(default)]
+    diarization: Option<PathBuf>,
+}
+
+#[derive(Deserialize)]
+struct TimedWord {
+    start: u64,
+    text: String,
+}
+
+#[derive(Deserialize)]
+struct SpeakerTurn {
+    start: u64,
+    end: u64,
+    speaker: String,
+}
+
+/// The `crates/data` recordings that ship with word timings and speaker turns.
+pub fn fixture_samples() -> Result<Vec<SttSample>, String> {
+    FIXTURES
+        .iter()
+        .map(|id| {
+            let dir = Path::new(DATA_DIR).join(id);
+            let audio = AUDIO_FILES
+                .iter()
+                .map(|name| dir.join(name))
+                .find(|path| path.exists())
+                .ok_or_else(|| format!("{id}: no audio file in {}", dir.display()))?;
+
+            sample(
+                id,
+                audio,
+                None,
+                Some(&dir.join("transcription.json")),
+                Some(&dir.join("diarization.json")),
+            )
+        })
+        .collect()
+}
+
+pub fn load_manifest(path: &Path) -> Result<Vec<SttSample>, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+    let entries: Vec<ManifestEntry> = serde_json::from_str(&content)
+        .map_err(|e| format!("failed to parse {}: {}", path.display(), e))?;
+    let base = path.parent().unwrap_or(Path::new("."));
+
+    entries
+        .into_iter()
+        .map(|entry| {
+            sample(
+                &entry.id,
+                base.join(&entry.audio),
+                entry.transcript,
+                entry.words.map(|p| base.join(p)).as_deref(),
+                entry.diarization.map(|p| base.join(p)).as_deref(),
+            )
+        })
+        .collect()
+}
+
+pub fn filter_samples(samples: Vec<SttSample>, filter: Option<&[String]>) -> Vec<SttSample> {
+    match filter {
+        None => samples,
+        Some(filter) => {
+            let filter_set: std::collections::HashSet<String> =
+                filter.iter().map(|s| s.to_lowercase()).collect();
+            samples
+                .into_iter()
+                .filter(|s| filter_set.contains(&s.id.to_lowercase()))
+                .collect()
+        }
+    }
+}
+
+fn sample(
+    id: &str,
+    audio_path: PathBuf,
+    transcript: Option<String>,
+    words: Option<&Path>,
+    diarization: Option<&Path>,
+) -> Result<SttSample, String> {
+    if !audio_path.exists() {
+        return Err(format!("{id}: {} does not exist", audio_path.display()));
+    }
+
+    let (transcript, first_word_ms) = match (transcript, words) {
+        (Some(text), _) => (text, 0),
+        (None, Some(path)) => {
+            let words: Vec<TimedWord> = read_json(path)?;
+            let text: String = words.iter().map(|w| w.text.as_str()).collect();
+            (
+                text.trim().to_string(),
+                words.first().map(|w| w.start).unwrap_or(0),
+            )
+        }
+        (None, None) => return Err(format!("{id}: needs either `transcript` or `words`")),
+    };
+
+    let speakers = match diarization {
+        Some(path) => read_json::<Vec<SpeakerTurn>>(path)?
+            .into_iter()
+            .map(|turn| SpeakerSegment {
+                start_ms: turn.start,
+                end_ms: turn.end,
+                speaker: turn.speaker,
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+
+    Ok(SttSample {
+        id: id.to_string(),
+        audio_path,
+        transcript,
+        first_word_ms,
+        speakers,
+    })
+}
+
+fn read_json<T: serde::de::DeserializeOwned>(path: &Path) -> Result<T, String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&content).map_err(|e| format!("failed to parse {}: {}", path.display(), e))
+}
@@ -1,3 +1,7 @@
+mod oidc;
+
+use std::sync::Arc;
+
 use axum::{
     extract::{Request, State},
     http::StatusCode,
@@ -7,6 +11,7 @@ use axum::{
 use hypr_supabase_auth::{Error as SupabaseAuthError, server::SupabaseAuth};
 
 pub use hypr_supabase_auth::Claims;
+pub use oidc::OidcAuth;
 
 #[derive(Clone)]
 pub struct AuthContext {
@@ -14,20 +19,60 @@ pub struct AuthContext {
     pub claims: Claims,
 }
 
+#[derive(Clone)]
+enum Verifier {
+    Supabase(SupabaseAuth),
+    Oidc(OidcAuth),
+    None,
+}
+
+/// A pre-shared bearer token; requests using it authenticate as `subject`.
+#[derive(Clone)]
+pub struct ApiKey {
+    pub subject: String,
+    pub key: String,
+}
+
 #[derive(Clone)]
 pub struct AuthState {
-    inner: SupabaseAuth,
+    verifier: Verifier,
+    api_keys: Arc<Vec<ApiKey>>,
     required_entitlement: Option<String>,
 }
 
 impl AuthState {
     pub fn new(supabase_url: &str) -> Self {
         Self {
-            inner: SupabaseAuth::new(supabase_url),
+            verifier: Verifier::Supabase(SupabaseAuth::new(supabase_url)),
+            api_keys: Arc::default(),
             required_entitlement: None,
         }
     }
 
+    /// Accepts JWTs from a self-hosted OpenID Connect issuer instead of Supabase.
+    pub fn oidc(oidc: OidcAuth) -> Self {
+        Self {
+            verifier: Verifier::Oidc(oidc),
+            api_keys: Arc::default(),
+            required_entitlement: None,
+        }
+    }
+
+    /// Accepts only the given static keys.
+    pub fn api_keys(keys: Vec<ApiKey>) -> Self {
+        Self {
+            verifier: Verifier::None,
+            api_keys: Arc::new(keys),
+            required_entitlement: None,
+        }
+    }
+
+    /// Static keys checked before the token verifier, e.g. for service accounts next to OIDC.
+    pub fn with_api_keys(mut self, keys: Vec<ApiKey>) -> Self {
+        self.api_keys = Arc::new(keys);
+        self
+    }
+
     pub fn with_required_entitlement(mut self, entitlement: impl Into<String>) -> Self {
         self.required_entitlement = Some(entitlement.into());
         self
@@ -38,10 +83,45 @@ impl AuthState {
     }
 
     pub async fn verify_token(&self, token: &str) -> Result<Claims, AuthError> {
-        self.inner.verify_token(token).await.map_err(AuthError)
+        if let Some(key) = self
+            .api_keys
+            .iter()
+            .find(|key| constant_time_eq(key.key.as_bytes(), token.as_bytes()))
+        {
+            return Ok(Claims {
+                sub: key.subject.clone(),
+                email: None,
+                entitlements: Vec::new(),
+                subscription_status: None,
+                trial_end: None,
+            });
+        }
+
+        let claims = match &self.verifier {
+            Verifier::Supabase(supabase) => supabase.verify_token(token).await?,
+            Verifier::Oidc(oidc) => oidc.verify_token(token).await?,
+            Verifier::None => return Err(SupabaseAuthError::InvalidToken.into()),
+        };
+        Ok(claims)
+    }
+
+    async fn authorize(&self, token: &str) -> Result<Claims, AuthError> {
+        let claims = self.verify_token(token).await?;
+
+        if let Some(entitlement) = &self.required_entitlement
+            && !claims.entitlements.contains(entitlement)
+        {
+            return Err(SupabaseAuthError::MissingEntitlement(entitlement.clone()).into());
+        }
+
+        Ok(claims)
     }
 }
 
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
 pub struct AuthError(SupabaseAuthError);
 
 impl From<SupabaseAuthError> for AuthError {
@@ -86,10 +166,7 @@ pub async fn require_auth(
         .ok_or(SupabaseAuthError::InvalidAuthHeader)?
         .to_owned();
 
-    let claims = match &state.required_entitlement {
-        Some(entitlement) => state.inner.require_entitlement(&token, entitlement).await?,
-        None => state.inner.verify_token(&token).await?,
-    };
+    let claims = state.authorize(&token).await?;
 
     request
         .extensions_mut()
@@ -110,7 +187,7 @@ pub async fn optional_auth(
         && let Some(token) = SupabaseAuth::extract_token(auth_header)
     {
         let token = token.to_owned();
-        if let Ok(claims) = state.inner.verify_token(&token).await {
+        if let Ok(claims) = state.verify_token(&token).await {
             request
                 .extensions_mut()
                 .insert(AuthContext { token, claims });
@@ -164,6 +241,34 @@ mod tests {
         assert_eq!(state.required_entitlement, None);
     }
 
+    #[tokio::test]
+    async fn test_api_keys() {
+        let state = AuthState::api_keys(vec![ApiKey {
+            subject: "alice".to_string(),
+            key: "sk-team-alice".to_string(),
+        }]);
+
+        let claims = state.verify_token("sk-team-alice").await.ok().unwrap();
+        assert_eq!(claims.sub, "alice");
+        assert!(claims.entitlements.is_empty());
+        assert!(state.verify_token("sk-team-bob").await.is_err());
+        assert!(state.verify_token("").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_api_keys_respect_required_entitlement() {
+        let state = AuthState::api_keys(vec![ApiKey {
+            subject: "alice".to_string(),
+            key: "sk-team-alice".to_string(),
+        }])
+        .with_required_entitlement("hyprnote_pro");
+
+        assert!(matches!(
+            state.authorize("sk-team-alice").await,
+            Err(AuthError(SupabaseAuthError::MissingEntitlement(_)))
+        ));
+    }
+
     #[test]
     fn test_auth_state_with_required_entitlement() {
         let state =
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use hypr_supabase_auth::{Claims, Error, server::CachedJwks};
+use jsonwebtoken::{Algorithm, DecodingKey, Validation};
+use serde::Deserialize;
+use tokio::sync::OnceCell;
+
+const ALLOWED_ALGORITHMS: &[Algorithm] = &[
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::PS384,
+    Algorithm::PS512,
+    Algorithm::ES256,
+    Algorithm::ES384,
+    Algorithm::EdDSA,
+];
+
+#[derive(Deserialize)]
+struct Discovery {
+    jwks_uri: String,
+}
+
+/// Verifies access tokens from any OpenID Connect issuer (Keycloak, Authentik, Dex, ...).
+/// The JWKS location comes from the issuer's discovery document, fetched on first use.
+#[derive(Clone)]
+pub struct OidcAuth {
+    issuer: String,
+    audience: Option<String>,
+    jwks: Arc<OnceCell<CachedJwks>>,
+    http_client: reqwest::Client,
+}
+
+impl OidcAuth {
+    pub fn new(issuer: impl Into<String>, audience: Option<String>) -> Self {
+        Self {
+            issuer: issuer.into(),
+            audience,
+            jwks: Arc::new(OnceCell::new()),
+            http_client: reqwest::Client::new(),
+        }
+    }
+
+    async fn jwks(&self) -> Result<&CachedJwks, Error> {
+        self.jwks
+            .get_or_try_init(|| async {
+                let discovery: Discovery = self
+                    .http_client
+                    .get(format!(
+                        "{}/.well-known/openid-configuration",
+                        self.issuer.trim_end_matches('/')
+                    ))
+                    .send()
+                    .await
+                    .map_err(|_| Error::JwksFetchFailed)?
+                    .json()
+                    .await
+                    .map_err(|_| Error::JwksFetchFailed)?;
+
+                Ok(CachedJwks::new(discovery.jwks_uri))
+            })
+            .await
+    }
+
+    pub async fn verify_token(&self, token: &str) -> Result<Claims, Error> {
+        let header = jsonwebtoken::decode_header(token).map_err(|_| Error::InvalidToken)?;
+        if !ALLOWED_ALGORITHMS.contains(&header.alg) {
+            return Err(Error::InvalidToken);
+        }
+
+        let jwks = self.jwks().await?.get().await?;
+        let jwk = match header.kid.as_deref() {
+            Some(kid) => jwks.find(kid),
+            // Single-key issuers sometimes omit `kid`.
+            None if jwks.keys.len() == 1 => jwks.keys.first(),
+            None => None,
+        }
+        .ok_or(Error::InvalidToken)?;
+        let decoding_key = DecodingKey::from_jwk(jwk).map_err(|_| Error::InvalidToken)?;
+
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&self.issuer]);
+        match &self.audience {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+
+        jsonwebtoken::decode::<Claims>(token, &decoding_key, &validation)
+            .map(|data| data.claims)
+            .map_err(|_| Error::InvalidToken)
+    }
+}
@@ -0,0 +1,34 @@
+use axum::{extract::FromRequestParts, http::request::Parts};
+use hypr_supabase_auth::{Error as SupabaseAuthError, server::SupabaseAuth};
+
+use crate::error::SyncError;
+use crate::state::AppState;
+
+/// The Supabase user id (`sub`) of the bearer token; every change is scoped to it.
+pub struct AuthUser(pub String);
+
+impl FromRequestParts<AppState> for AuthUser {
+    type Rejection = SyncError;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let auth = state
+            .config
+            .auth
+            .as_ref()
+            .ok_or_else(|| SyncError::Auth("authentication is not configured".to_string()))?;
+
+        let header = parts
+            .headers
+            .get("Authorization")
+            .and_then(|h| h.to_str().ok())
+            .ok_or(SupabaseAuthError::MissingAuthHeader)?;
+        let token =
+            SupabaseAuth::extract_token(header).ok_or(SupabaseAuthError::InvalidAuthHeader)?;
+
+        let claims = auth.verify_token(token).await?;
+        Ok(Self(claims.sub))
+    }
+}
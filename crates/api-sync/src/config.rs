@@ -1,18 +1,27 @@
 use std::sync::Arc;
 
+use crate::store::SyncStore;
+
 #[derive(Clone)]
 pub struct SyncConfig {
     pub supabase_url: String,
     pub supabase_anon_key: String,
     pub auth: Option<Arc<hypr_supabase_auth::server::SupabaseAuth>>,
+    pub store: Arc<dyn SyncStore>,
 }
 
 impl SyncConfig {
-    pub fn new(supabase_url: impl Into<String>, supabase_anon_key: impl Into<String>) -> Self {
+    /// The store is required: use `SqliteStore` for anything that must survive a restart.
+    pub fn new(
+        supabase_url: impl Into<String>,
+        supabase_anon_key: impl Into<String>,
+        store: Arc<dyn SyncStore>,
+    ) -> Self {
         Self {
             supabase_url: supabase_url.into(),
             supabase_anon_key: supabase_anon_key.into(),
             auth: None,
+            store,
         }
     }
 
@@ -24,6 +24,12 @@ impl From<hypr_supabase_auth::Error> for SyncError {
     }
 }
 
+impl From<sqlx::Error> for SyncError {
+    fn from(err: sqlx::Error) -> Self {
+        Self::Internal(err.to_string())
+    }
+}
+
 impl IntoResponse for SyncError {
     fn into_response(self) -> Response {
         let (status, code, message) = match self {
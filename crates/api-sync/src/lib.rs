@@ -1,9 +1,15 @@
+mod auth;
 mod config;
 mod error;
 mod routes;
 mod state;
+mod store;
+mod sync;
+mod types;
 
 pub use config::SyncConfig;
 pub use error::{Result, SyncError};
 pub use routes::{openapi, router};
 pub use state::AppState;
+pub use store::{MemoryStore, NewRecord, SqliteStore, StoreFuture, SyncStore};
+pub use types::*;
@@ -1,10 +1,31 @@
-use axum::Router;
+pub(crate) mod sync;
+
+use axum::{Router, routing::post};
 use utoipa::OpenApi;
 
 use crate::state::AppState;
 
 #[derive(OpenApi)]
 #[openapi(
+    paths(
+        sync::push,
+        sync::pull,
+    ),
+    components(
+        schemas(
+            crate::types::EntityKind,
+            crate::types::VectorClock,
+            crate::types::ChangeInput,
+            crate::types::ChangeRecord,
+            crate::types::PushRequest,
+            crate::types::PushResponse,
+            crate::types::AcceptedChange,
+            crate::types::ConflictReason,
+            crate::types::SyncConflict,
+            crate::types::PullRequest,
+            crate::types::PullResponse,
+        )
+    ),
     tags(
         (name = "sync", description = "Sync management")
     )
@@ -16,5 +37,8 @@ pub fn openapi() -> utoipa::openapi::OpenApi {
 }
 
 pub fn router(state: AppState) -> Router {
-    Router::new().with_state(state)
+    Router::new()
+        .route("/push", post(sync::push))
+        .route("/pull", post(sync::pull))
+        .with_state(state)
 }
@@ -0,0 +1,47 @@
+use axum::{Json, extract::State};
+
+use crate::auth::AuthUser;
+use crate::error::Result;
+use crate::state::AppState;
+use crate::types::{PullRequest, PullResponse, PushRequest, PushResponse};
+
+#[utoipa::path(
+    post,
+    path = "/push",
+    request_body = PushRequest,
+    responses(
+        (status = 200, description = "Changes applied; concurrent edits are returned as conflicts", body = PushResponse),
+        (status = 400, description = "Invalid change"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error"),
+    ),
+    tag = "sync",
+)]
+pub async fn push(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Json(payload): Json<PushRequest>,
+) -> Result<Json<PushResponse>> {
+    let response = crate::sync::push(state.config.store.as_ref(), &user_id, payload).await?;
+    Ok(Json(response))
+}
+
+#[utoipa::path(
+    post,
+    path = "/pull",
+    request_body = PullRequest,
+    responses(
+        (status = 200, description = "Changes after the cursor", body = PullResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error"),
+    ),
+    tag = "sync",
+)]
+pub async fn pull(
+    State(state): State<AppState>,
+    AuthUser(user_id): AuthUser,
+    Json(payload): Json<PullRequest>,
+) -> Result<Json<PullResponse>> {
+    let response = crate::sync::pull(state.config.store.as_ref(), &user_id, payload).await?;
+    Ok(Json(response))
+}
@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use super::{NewRecord, StoreFuture, SyncStore};
+use crate::types::{ChangeRecord, EntityKind};
+
+type Key = (String, EntityKind, String);
+
+#[derive(Default)]
+pub struct MemoryStore {
+    inner: Mutex<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    next_seq: u64,
+    heads: HashMap<Key, ChangeRecord>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SyncStore for MemoryStore {
+    fn head<'a>(
+        &'a self,
+        user_id: &'a str,
+        kind: EntityKind,
+        entity_id: &'a str,
+    ) -> StoreFuture<'a, Option<ChangeRecord>> {
+        let key = (user_id.to_string(), kind, entity_id.to_string());
+        let head = self.inner.lock().unwrap().heads.get(&key).cloned();
+        Box::pin(async move { Ok(head) })
+    }
+
+    fn put<'a>(
+        &'a self,
+        user_id: &'a str,
+        record: NewRecord,
+        expected_seq: Option<u64>,
+    ) -> StoreFuture<'a, Option<u64>> {
+        let mut inner = self.inner.lock().unwrap();
+        let key = (user_id.to_string(), record.kind, record.entity_id.clone());
+
+        let current_seq = inner.heads.get(&key).map(|head| head.seq);
+        let seq = if current_seq == expected_seq {
+            inner.next_seq += 1;
+            let seq = inner.next_seq;
+            inner.heads.insert(
+                key,
+                ChangeRecord {
+                    seq,
+                    kind: record.kind,
+                    entity_id: record.entity_id,
+                    device_id: record.device_id,
+                    clock: record.clock,
+                    lamport: record.lamport,
+                    deleted: record.deleted,
+                    data: record.data,
+                    updated_at: record.updated_at,
+                },
+            );
+            Some(seq)
+        } else {
+            None
+        };
+
+        Box::pin(async move { Ok(seq) })
+    }
+
+    fn changes_since<'a>(
+        &'a self,
+        user_id: &'a str,
+        cursor: u64,
+        until: u64,
+        limit: u32,
+        exclude_device: Option<&'a str>,
+    ) -> StoreFuture<'a, Vec<ChangeRecord>> {
+        let mut changes: Vec<ChangeRecord> = self
+            .inner
+            .lock()
+            .unwrap()
+            .heads
+            .iter()
+            .filter(|((owner, _, _), head)| {
+                owner == user_id
+                    && head.seq > cursor
+                    && head.seq <= until
+                    && Some(head.device_id.as_str()) != exclude_device
+            })
+            .map(|(_, head)| head.clone())
+            .collect();
+        changes.sort_by_key(|change| change.seq);
+        changes.truncate(limit as usize);
+
+        Box::pin(async move { Ok(changes) })
+    }
+
+    fn latest_seq<'a>(&'a self, user_id: &'a str) -> StoreFuture<'a, u64> {
+        let latest = self
+            .inner
+            .lock()
+            .unwrap()
+            .heads
+            .iter()
+            .filter(|((owner, _, _), _)| owner == user_id)
+            .map(|(_, head)| head.seq)
+            .max()
+            .unwrap_or(0);
+
+        Box::pin(async move { Ok(latest) })
+    }
+}
@@ -0,0 +1,58 @@
+mod memory;
+mod sqlite;
+
+pub use memory::MemoryStore;
+pub use sqlite::SqliteStore;
+
+use std::future::Future;
+use std::pin::Pin;
+
+use crate::error::Result;
+use crate::types::{ChangeRecord, EntityKind, VectorClock};
+
+pub type StoreFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// A version about to become an entity's head; the store assigns `seq`.
+#[derive(Debug, Clone)]
+pub struct NewRecord {
+    pub kind: EntityKind,
+    pub entity_id: String,
+    pub device_id: String,
+    pub clock: VectorClock,
+    pub lamport: u64,
+    pub deleted: bool,
+    pub data: Option<serde_json::Value>,
+    pub updated_at: String,
+}
+
+/// Backing storage for the change feed. Only the latest version of each entity is kept, and
+/// every write moves it to the end of the user's feed with a fresh, increasing `seq`.
+pub trait SyncStore: Send + Sync + 'static {
+    fn head<'a>(
+        &'a self,
+        user_id: &'a str,
+        kind: EntityKind,
+        entity_id: &'a str,
+    ) -> StoreFuture<'a, Option<ChangeRecord>>;
+
+    /// Replaces the entity's head only if it is still at `expected_seq` (`None`: no head yet).
+    /// Returns the new `seq`, or `None` when another write got there first.
+    fn put<'a>(
+        &'a self,
+        user_id: &'a str,
+        record: NewRecord,
+        expected_seq: Option<u64>,
+    ) -> StoreFuture<'a, Option<u64>>;
+
+    /// Heads with `cursor < seq <= until` in feed order, optionally skipping one device's writes.
+    fn changes_since<'a>(
+        &'a self,
+        user_id: &'a str,
+        cursor: u64,
+        until: u64,
+        limit: u32,
+        exclude_device: Option<&'a str>,
+    ) -> StoreFuture<'a, Vec<ChangeRecord>>;
+
+    fn latest_seq<'a>(&'a self, user_id: &'a str) -> StoreFuture<'a, u64>;
+}
@@ -0,0 +1,201 @@
+use sqlx::Row;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions, SqliteRow};
+
+use super::{NewRecord, StoreFuture, SyncStore};
+use crate::error::{Result, SyncError};
+use crate::types::{ChangeRecord, EntityKind};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS sync_changes (
+    seq INTEGER PRIMARY KEY AUTOINCREMENT,
+    user_id TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    entity_id TEXT NOT NULL,
+    device_id TEXT NOT NULL,
+    clock TEXT NOT NULL,
+    lamport INTEGER NOT NULL,
+    deleted INTEGER NOT NULL,
+    data TEXT,
+    updated_at TEXT NOT NULL,
+    UNIQUE (user_id, kind, entity_id)
+);
+CREATE INDEX IF NOT EXISTS sync_changes_user_seq ON sync_changes (user_id, seq);
+";
+
+const COLUMNS: &str = "seq, kind, entity_id, device_id, clock, lamport, deleted, data, updated_at";
+
+/// One row per entity; `AUTOINCREMENT` keeps `seq` increasing even after heads are replaced.
+#[derive(Clone)]
+pub struct SqliteStore {
+    pool: SqlitePool,
+}
+
+impl SqliteStore {
+    /// Opens (and creates if needed) a database file, e.g. `sqlite://sync.db`.
+    pub async fn connect(url: &str) -> Result<Self> {
+        let options: SqliteConnectOptions = url.parse()?;
+        let pool = SqlitePoolOptions::new()
+            .connect_with(options.create_if_missing(true))
+            .await?;
+        Self::with_pool(pool).await
+    }
+
+    /// A private in-memory database, mostly for tests.
+    pub async fn in_memory() -> Result<Self> {
+        // Every connection to `:memory:` is a separate database, so keep exactly one.
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect("sqlite::memory:")
+            .await?;
+        Self::with_pool(pool).await
+    }
+
+    async fn with_pool(pool: SqlitePool) -> Result<Self> {
+        sqlx::raw_sql(SCHEMA).execute(&pool).await?;
+        Ok(Self { pool })
+    }
+}
+
+impl SyncStore for SqliteStore {
+    fn head<'a>(
+        &'a self,
+        user_id: &'a str,
+        kind: EntityKind,
+        entity_id: &'a str,
+    ) -> StoreFuture<'a, Option<ChangeRecord>> {
+        Box::pin(async move {
+            let row = sqlx::query(&format!(
+                "SELECT {COLUMNS} FROM sync_changes WHERE user_id = ? AND kind = ? AND entity_id = ?"
+            ))
+            .bind(user_id)
+            .bind(kind.as_str())
+            .bind(entity_id)
+            .fetch_optional(&self.pool)
+            .await?;
+
+            row.as_ref().map(record_from_row).transpose()
+        })
+    }
+
+    fn put<'a>(
+        &'a self,
+        user_id: &'a str,
+        record: NewRecord,
+        expected_seq: Option<u64>,
+    ) -> StoreFuture<'a, Option<u64>> {
+        Box::pin(async move {
+            let mut tx = self.pool.begin().await?;
+
+            if let Some(expected_seq) = expected_seq {
+                let removed = sqlx::query(
+                    "DELETE FROM sync_changes WHERE user_id = ? AND kind = ? AND entity_id = ? AND seq = ?",
+                )
+                .bind(user_id)
+                .bind(record.kind.as_str())
+                .bind(&record.entity_id)
+                .bind(expected_seq as i64)
+                .execute(&mut *tx)
+                .await?;
+
+                if removed.rows_affected() == 0 {
+                    return Ok(None);
+                }
+            }
+
+            let clock = serde_json::to_string(&record.clock)
+                .map_err(|e| SyncError::Internal(e.to_string()))?;
+            let data = record
+                .data
+                .as_ref()
+                .map(serde_json::to_string)
+                .transpose()
+                .map_err(|e| SyncError::Internal(e.to_string()))?;
+
+            let inserted = sqlx::query(
+                "INSERT INTO sync_changes (user_id, kind, entity_id, device_id, clock, lamport, deleted, data, updated_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+                 ON CONFLICT (user_id, kind, entity_id) DO NOTHING",
+            )
+            .bind(user_id)
+            .bind(record.kind.as_str())
+            .bind(&record.entity_id)
+            .bind(&record.device_id)
+            .bind(clock)
+            .bind(record.lamport as i64)
+            .bind(record.deleted)
+            .bind(data)
+            .bind(&record.updated_at)
+            .execute(&mut *tx)
+            .await?;
+
+            if inserted.rows_affected() == 0 {
+                return Ok(None);
+            }
+
+            tx.commit().await?;
+            Ok(Some(inserted.last_insert_rowid() as u64))
+        })
+    }
+
+    fn changes_since<'a>(
+        &'a self,
+        user_id: &'a str,
+        cursor: u64,
+        until: u64,
+        limit: u32,
+        exclude_device: Option<&'a str>,
+    ) -> StoreFuture<'a, Vec<ChangeRecord>> {
+        Box::pin(async move {
+            let rows = sqlx::query(&format!(
+                "SELECT {COLUMNS} FROM sync_changes
+                 WHERE user_id = ? AND seq > ? AND seq <= ? AND (? IS NULL OR device_id != ?)
+                 ORDER BY seq LIMIT ?"
+            ))
+            .bind(user_id)
+            .bind(cursor as i64)
+            .bind(until as i64)
+            .bind(exclude_device)
+            .bind(exclude_device)
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await?;
+
+            rows.iter().map(record_from_row).collect()
+        })
+    }
+
+    fn latest_seq<'a>(&'a self, user_id: &'a str) -> StoreFuture<'a, u64> {
+        Box::pin(async move {
+            let latest: Option<i64> =
+                sqlx::query_scalar("SELECT MAX(seq) FROM sync_changes WHERE user_id = ?")
+                    .bind(user_id)
+                    .fetch_one(&self.pool)
+                    .await?;
+            Ok(latest.unwrap_or(0) as u64)
+        })
+    }
+}
+
+fn record_from_row(row: &SqliteRow) -> Result<ChangeRecord> {
+    let kind: String = row.try_get("kind")?;
+    let clock: String = row.try_get("clock")?;
+    let data: Option<String> = row.try_get("data")?;
+
+    Ok(ChangeRecord {
+        seq: row.try_get::<i64, _>("seq")? as u64,
+        kind: EntityKind::parse(&kind)
+            .ok_or_else(|| SyncError::Internal(format!("unknown entity kind: {kind}")))?,
+        entity_id: row.try_get("entity_id")?,
+        device_id: row.try_get("device_id")?,
+        clock: serde_json::from_str(&clock).map_err(|e| SyncError::Internal(e.to_string()))?,
+        lamport: row.try_get::<i64, _>("lamport")? as u64,
+        deleted: row.try_get("deleted")?,
+        data: data
+            .map(|data| serde_json::from_str(&data))
+            .transpose()
+            .map_err(|e| SyncError::Internal(e.to_string()))?,
+        updated_at: row.try_get("updated_at")?,
+    })
+}
@@ -0,0 +1,420 @@
+use crate::error::{Result, SyncError};
+use crate::store::{NewRecord, SyncStore};
+use crate::types::{
+    AcceptedChange, ChangeInput, ClockOrdering, ConflictReason, PullRequest, PullResponse,
+    PushRequest, PushResponse, SyncConflict,
+};
+
+const DEFAULT_PULL_LIMIT: u32 = 500;
+const MAX_PULL_LIMIT: u32 = 1000;
+const MAX_PUSH_CHANGES: usize = 1000;
+// A head that keeps moving under us means another device is pushing the same entity; give up
+// after a few rounds rather than spin.
+const MAX_PUT_ATTEMPTS: usize = 5;
+
+enum Outcome {
+    Accepted(u64),
+    Conflict(SyncConflict),
+}
+
+pub async fn push(
+    store: &dyn SyncStore,
+    user_id: &str,
+    request: PushRequest,
+) -> Result<PushResponse> {
+    if request.device_id.trim().is_empty() {
+        return Err(SyncError::BadRequest("device_id is required".to_string()));
+    }
+    if request.changes.len() > MAX_PUSH_CHANGES {
+        return Err(SyncError::BadRequest(format!(
+            "at most {} changes per push",
+            MAX_PUSH_CHANGES
+        )));
+    }
+
+    let mut accepted = Vec::new();
+    let mut conflicts = Vec::new();
+
+    for change in request.changes {
+        let (kind, entity_id) = (change.kind, change.entity_id.clone());
+        match push_change(store, user_id, &request.device_id, change).await? {
+            Outcome::Accepted(seq) => accepted.push(AcceptedChange {
+                kind,
+                entity_id,
+                seq,
+            }),
+            Outcome::Conflict(conflict) => conflicts.push(conflict),
+        }
+    }
+
+    Ok(PushResponse {
+        accepted,
+        conflicts,
+    })
+}
+
+async fn push_change(
+    store: &dyn SyncStore,
+    user_id: &str,
+    device_id: &str,
+    change: ChangeInput,
+) -> Result<Outcome> {
+    if change.entity_id.is_empty() {
+        return Err(SyncError::BadRequest("entity_id is required".to_string()));
+    }
+    if change.clock.get(device_id) == 0 {
+        return Err(SyncError::BadRequest(format!(
+            "clock for {} has no entry for device {}",
+            change.entity_id, device_id
+        )));
+    }
+    if !change.deleted && change.data.is_none() {
+        return Err(SyncError::BadRequest(format!(
+            "change for {} has no data",
+            change.entity_id
+        )));
+    }
+
+    for _ in 0..MAX_PUT_ATTEMPTS {
+        let head = store.head(user_id, change.kind, &change.entity_id).await?;
+
+        if let Some(head) = &head {
+            let reason = match change.clock.compare(&head.clock) {
+                ClockOrdering::After => None,
+                // A retried push of what we already stored.
+                ClockOrdering::Equal => return Ok(Outcome::Accepted(head.seq)),
+                ClockOrdering::Before => Some(ConflictReason::Outdated),
+                ClockOrdering::Concurrent => Some(ConflictReason::Concurrent),
+            };
+            if let Some(reason) = reason {
+                return Ok(Outcome::Conflict(SyncConflict {
+                    kind: change.kind,
+                    entity_id: change.entity_id,
+                    reason,
+                    server: head.clone(),
+                }));
+            }
+        }
+
+        // Lamport timestamps only move forward per entity, so clients that fall back to
+        // last-writer-wins agree on the winner.
+        let lamport = head
+            .as_ref()
+            .map(|head| change.lamport.max(head.lamport + 1))
+            .unwrap_or(change.lamport);
+
+        let record = NewRecord {
+            kind: change.kind,
+            entity_id: change.entity_id.clone(),
+            device_id: device_id.to_string(),
+            clock: change.clock.clone(),
+            lamport,
+            deleted: change.deleted,
+            data: if change.deleted {
+                None
+            } else {
+                change.data.clone()
+            },
+            updated_at: chrono::Utc::now().to_rfc3339(),
+        };
+
+        if let Some(seq) = store
+            .put(user_id, record, head.as_ref().map(|head| head.seq))
+            .await?
+        {
+            return Ok(Outcome::Accepted(seq));
+        }
+    }
+
+    Err(SyncError::Internal(format!(
+        "gave up writing {} after concurrent updates",
+        change.entity_id
+    )))
+}
+
+pub async fn pull(
+    store: &dyn SyncStore,
+    user_id: &str,
+    request: PullRequest,
+) -> Result<PullResponse> {
+    let limit = request
+        .limit
+        .unwrap_or(DEFAULT_PULL_LIMIT)
+        .clamp(1, MAX_PULL_LIMIT);
+
+    // Read first and bound the page by it, so a write landing during the pull is left for the
+    // next one instead of being skipped by the cursor.
+    let latest = store.latest_seq(user_id).await?;
+    let changes = store
+        .changes_since(
+            user_id,
+            request.cursor,
+            latest,
+            limit,
+            request.device_id.as_deref(),
+        )
+        .await?;
+    let has_more = changes.len() == limit as usize;
+
+    // When the page isn't full, everything up to `latest` has been seen, including the
+    // caller's own changes that were filtered out.
+    let cursor = match changes.last() {
+        Some(last) if has_more => last.seq,
+        _ => latest.max(request.cursor),
+    };
+
+    Ok(PullResponse {
+        changes,
+        cursor,
+        has_more,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::{MemoryStore, SqliteStore, StoreFuture};
+    use crate::types::{ChangeRecord, EntityKind, VectorClock};
+
+    fn change(entity_id: &str, clock: &[(&str, u64)], title: &str) -> ChangeInput {
+        ChangeInput {
+            kind: EntityKind::Session,
+            entity_id: entity_id.to_string(),
+            clock: VectorClock(
+                clock
+                    .iter()
+                    .map(|(device, counter)| (device.to_string(), *counter))
+                    .collect(),
+            ),
+            lamport: 1,
+            deleted: false,
+            data: Some(serde_json::json!({ "title": title })),
+        }
+    }
+
+    fn push_request(device_id: &str, changes: Vec<ChangeInput>) -> PushRequest {
+        PushRequest {
+            device_id: device_id.to_string(),
+            changes,
+        }
+    }
+
+    async fn exercise(store: &dyn SyncStore) {
+        let pushed = push(
+            store,
+            "user",
+            push_request(
+                "laptop",
+                vec![
+                    change("s1", &[("laptop", 1)], "Weekly"),
+                    change("s2", &[("laptop", 1)], "1:1"),
+                ],
+            ),
+        )
+        .await
+        .unwrap();
+        let seqs: Vec<_> = pushed.accepted.iter().map(|a| a.seq).collect();
+        assert_eq!(seqs, vec![1, 2]);
+
+        let pulled = pull(
+            store,
+            "user",
+            PullRequest {
+                device_id: Some("desktop".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(pulled.changes.len(), 2);
+        assert_eq!(pulled.cursor, 2);
+        assert!(!pulled.has_more);
+
+        // The desktop saw laptop:1 and edits on top of it.
+        let pushed = push(
+            store,
+            "user",
+            push_request(
+                "desktop",
+                vec![change(
+                    "s1",
+                    &[("laptop", 1), ("desktop", 1)],
+                    "Weekly Sync",
+                )],
+            ),
+        )
+        .await
+        .unwrap();
+        assert_eq!(pushed.accepted.len(), 1);
+
+        // Meanwhile the laptop edited s1 again without pulling.
+        let pushed = push(
+            store,
+            "user",
+            push_request("laptop", vec![change("s1", &[("laptop", 2)], "Weekly 2")]),
+        )
+        .await
+        .unwrap();
+        assert!(pushed.accepted.is_empty());
+        assert_eq!(pushed.conflicts[0].reason, ConflictReason::Concurrent);
+        assert_eq!(
+            pushed.conflicts[0].server.data,
+            Some(serde_json::json!({ "title": "Weekly Sync" }))
+        );
+
+        // Replaying an already stored change is a no-op.
+        let pushed = push(
+            store,
+            "user",
+            push_request("laptop", vec![change("s2", &[("laptop", 1)], "1:1")]),
+        )
+        .await
+        .unwrap();
+        assert_eq!(pushed.accepted[0].seq, 2);
+
+        let mut tombstone = change("s2", &[("laptop", 2)], "");
+        tombstone.deleted = true;
+        push(store, "user", push_request("laptop", vec![tombstone]))
+            .await
+            .unwrap();
+
+        let pulled = pull(
+            store,
+            "user",
+            PullRequest {
+                cursor: 2,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        let ids: Vec<_> = pulled
+            .changes
+            .iter()
+            .map(|c| c.entity_id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["s1", "s2"]);
+        assert!(pulled.changes[1].deleted);
+        assert_eq!(pulled.changes[1].data, None);
+
+        let other_user = pull(store, "someone-else", PullRequest::default())
+            .await
+            .unwrap();
+        assert!(other_user.changes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn memory_store() {
+        exercise(&MemoryStore::new()).await;
+    }
+
+    #[tokio::test]
+    async fn sqlite_store() {
+        exercise(&SqliteStore::in_memory().await.unwrap()).await;
+    }
+
+    /// Lands another device's write right after the pull has read its page.
+    struct WriteDuringPull {
+        inner: MemoryStore,
+        pending: std::sync::Mutex<Option<NewRecord>>,
+    }
+
+    impl SyncStore for WriteDuringPull {
+        fn head<'a>(
+            &'a self,
+            user_id: &'a str,
+            kind: EntityKind,
+            entity_id: &'a str,
+        ) -> StoreFuture<'a, Option<ChangeRecord>> {
+            self.inner.head(user_id, kind, entity_id)
+        }
+
+        fn put<'a>(
+            &'a self,
+            user_id: &'a str,
+            record: NewRecord,
+            expected_seq: Option<u64>,
+        ) -> StoreFuture<'a, Option<u64>> {
+            self.inner.put(user_id, record, expected_seq)
+        }
+
+        fn changes_since<'a>(
+            &'a self,
+            user_id: &'a str,
+            cursor: u64,
+            until: u64,
+            limit: u32,
+            exclude_device: Option<&'a str>,
+        ) -> StoreFuture<'a, Vec<ChangeRecord>> {
+            Box::pin(async move {
+                let changes = self
+                    .inner
+                    .changes_since(user_id, cursor, until, limit, exclude_device)
+                    .await?;
+                let pending = self.pending.lock().unwrap().take();
+                if let Some(record) = pending {
+                    self.inner.put(user_id, record, None).await?;
+                }
+                Ok(changes)
+            })
+        }
+
+        fn latest_seq<'a>(&'a self, user_id: &'a str) -> StoreFuture<'a, u64> {
+            self.inner.latest_seq(user_id)
+        }
+    }
+
+    #[tokio::test]
+    async fn pull_keeps_writes_that_land_during_the_pull() {
+        let store = WriteDuringPull {
+            inner: MemoryStore::new(),
+            pending: std::sync::Mutex::new(None),
+        };
+        push(
+            &store,
+            "user",
+            push_request("laptop", vec![change("s1", &[("laptop", 1)], "Weekly")]),
+        )
+        .await
+        .unwrap();
+
+        *store.pending.lock().unwrap() = Some(NewRecord {
+            kind: EntityKind::Session,
+            entity_id: "s2".to_string(),
+            device_id: "phone".to_string(),
+            clock: VectorClock([("phone".to_string(), 1)].into_iter().collect()),
+            lamport: 1,
+            deleted: false,
+            data: Some(serde_json::json!({ "title": "1:1" })),
+            updated_at: chrono::Utc::now().to_rfc3339(),
+        });
+
+        let first = pull(&store, "user", PullRequest::default()).await.unwrap();
+        assert_eq!(first.changes.len(), 1);
+        assert_eq!(first.cursor, 1);
+
+        let second = pull(
+            &store,
+            "user",
+            PullRequest {
+                cursor: first.cursor,
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(second.changes[0].entity_id, "s2");
+        assert_eq!(second.cursor, 2);
+    }
+
+    #[tokio::test]
+    async fn rejects_clock_without_device_entry() {
+        let result = push(
+            &MemoryStore::new(),
+            "user",
+            push_request("laptop", vec![change("s1", &[("desktop", 1)], "x")]),
+        )
+        .await;
+        assert!(matches!(result, Err(SyncError::BadRequest(_))));
+    }
+}
@@ -0,0 +1,214 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityKind {
+    Session,
+    Transcript,
+    Human,
+    Tag,
+    Template,
+}
+
+impl EntityKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Session => "session",
+            Self::Transcript => "transcript",
+            Self::Human => "human",
+            Self::Tag => "tag",
+            Self::Template => "template",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "session" => Some(Self::Session),
+            "transcript" => Some(Self::Transcript),
+            "human" => Some(Self::Human),
+            "tag" => Some(Self::Tag),
+            "template" => Some(Self::Template),
+            _ => None,
+        }
+    }
+}
+
+/// Per-device edit counters. A device bumps its own entry on every local edit and merges in
+/// the clocks it receives, so two versions can be ordered or recognised as concurrent.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(transparent)]
+pub struct VectorClock(pub BTreeMap<String, u64>);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockOrdering {
+    Before,
+    Equal,
+    After,
+    Concurrent,
+}
+
+impl VectorClock {
+    pub fn get(&self, device_id: &str) -> u64 {
+        self.0.get(device_id).copied().unwrap_or(0)
+    }
+
+    pub fn increment(&mut self, device_id: &str) {
+        *self.0.entry(device_id.to_string()).or_insert(0) += 1;
+    }
+
+    pub fn merge(&mut self, other: &VectorClock) {
+        for (device_id, &counter) in &other.0 {
+            let entry = self.0.entry(device_id.clone()).or_insert(0);
+            *entry = (*entry).max(counter);
+        }
+    }
+
+    pub fn compare(&self, other: &VectorClock) -> ClockOrdering {
+        let mut ordering = Ordering::Equal;
+
+        for device_id in self.0.keys().chain(other.0.keys()) {
+            match (self.get(device_id).cmp(&other.get(device_id)), ordering) {
+                (Ordering::Equal, _) => {}
+                (next, Ordering::Equal) => ordering = next,
+                (next, current) if next != current => return ClockOrdering::Concurrent,
+                _ => {}
+            }
+        }
+
+        match ordering {
+            Ordering::Less => ClockOrdering::Before,
+            Ordering::Equal => ClockOrdering::Equal,
+            Ordering::Greater => ClockOrdering::After,
+        }
+    }
+}
+
+/// A change as pushed by a device. `clock` is the entity's clock after the edit, i.e. the last
+/// clock the device saw from the server merged with its own, with its own entry bumped.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ChangeInput {
+    pub kind: EntityKind,
+    pub entity_id: String,
+    pub clock: VectorClock,
+    #[serde(default)]
+    pub lamport: u64,
+    #[serde(default)]
+    pub deleted: bool,
+    #[serde(default)]
+    pub data: Option<serde_json::Value>,
+}
+
+/// The server's current version of an entity. Deletes are kept as tombstones (`deleted` with no
+/// `data`) so devices that were offline still learn about them.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ChangeRecord {
+    /// Position in the user's change feed; pass the highest one seen as the next pull cursor.
+    pub seq: u64,
+    pub kind: EntityKind,
+    pub entity_id: String,
+    pub device_id: String,
+    pub clock: VectorClock,
+    pub lamport: u64,
+    pub deleted: bool,
+    pub data: Option<serde_json::Value>,
+    pub updated_at: String,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct PushRequest {
+    pub device_id: String,
+    pub changes: Vec<ChangeInput>,
+}
+
+/// Pushing never moves the pull cursor: other devices may have written in between, and only a
+/// pull hands those out.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PushResponse {
+    pub accepted: Vec<AcceptedChange>,
+    pub conflicts: Vec<SyncConflict>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AcceptedChange {
+    pub kind: EntityKind,
+    pub entity_id: String,
+    pub seq: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictReason {
+    /// The server has edits the device hadn't seen; pull and re-apply.
+    Outdated,
+    /// Both sides edited independently; the device must merge and push with a merged clock.
+    Concurrent,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SyncConflict {
+    pub kind: EntityKind,
+    pub entity_id: String,
+    pub reason: ConflictReason,
+    pub server: ChangeRecord,
+}
+
+#[derive(Debug, Default, Deserialize, ToSchema)]
+pub struct PullRequest {
+    #[serde(default)]
+    pub cursor: u64,
+    #[serde(default)]
+    pub limit: Option<u32>,
+    /// Skips changes this device pushed itself.
+    #[serde(default)]
+    pub device_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PullResponse {
+    pub changes: Vec<ChangeRecord>,
+    pub cursor: u64,
+    pub has_more: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(entries: &[(&str, u64)]) -> VectorClock {
+        VectorClock(
+            entries
+                .iter()
+                .map(|(device, counter)| (device.to_string(), *counter))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn compares_vector_clocks() {
+        let a = clock(&[("laptop", 2), ("desktop", 1)]);
+
+        assert_eq!(a.compare(&a.clone()), ClockOrdering::Equal);
+        assert_eq!(
+            a.compare(&clock(&[("laptop", 2), ("desktop", 2)])),
+            ClockOrdering::Before
+        );
+        assert_eq!(a.compare(&clock(&[("laptop", 1)])), ClockOrdering::After);
+        assert_eq!(
+            a.compare(&clock(&[("laptop", 1), ("desktop", 2)])),
+            ClockOrdering::Concurrent
+        );
+    }
+
+    #[test]
+    fn merges_and_increments() {
+        let mut a = clock(&[("laptop", 2)]);
+        a.merge(&clock(&[("laptop", 1), ("desktop", 3)]));
+        a.increment("laptop");
+
+        assert_eq!(a, clock(&[("laptop", 3), ("desktop", 3)]));
+    }
+}
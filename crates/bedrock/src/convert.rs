@@ -0,0 +1,520 @@
+use serde_json::{Map, Value, json};
+
+use crate::{Error, Result};
+
+/// Request path for `model_id`, relative to `https://bedrock-runtime.{region}.amazonaws.com`.
+///
+/// Model ids contain `:` and inference profile ARNs contain `/`, so the id is percent-encoded.
+pub fn converse_path(model_id: &str, stream: bool) -> String {
+    let mut encoded = String::with_capacity(model_id.len());
+    for byte in model_id.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'.' | b'_' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+
+    let action = if stream {
+        "converse-stream"
+    } else {
+        "converse"
+    };
+    format!("/model/{encoded}/{action}")
+}
+
+/// Builds a Converse request body from an OpenAI chat completions body.
+///
+/// The model goes in the path (see [`converse_path`]), so `model` and `stream` are ignored.
+/// Fields Converse has no equivalent for, such as `response_format`, are dropped.
+pub fn converse_request(request: &Value) -> Result<Value> {
+    let messages = request
+        .get("messages")
+        .and_then(Value::as_array)
+        .ok_or_else(|| Error::InvalidRequest("messages must be an array".to_string()))?;
+
+    let mut system = Vec::new();
+    let mut turns: Vec<(&'static str, Vec<Value>)> = Vec::new();
+
+    for message in messages {
+        let role = message.get("role").and_then(Value::as_str).unwrap_or("");
+        let content = message.get("content");
+
+        let (role, blocks) = match role {
+            "system" | "developer" => {
+                system.extend(text_blocks(content)?);
+                continue;
+            }
+            "user" => ("user", user_blocks(content)?),
+            "assistant" => ("assistant", assistant_blocks(message)?),
+            // Converse carries tool output in the next user turn.
+            "tool" => ("user", vec![tool_result_block(message)?]),
+            other => {
+                return Err(Error::InvalidRequest(format!("unsupported role {other:?}")));
+            }
+        };
+
+        if blocks.is_empty() {
+            continue;
+        }
+        // Converse requires turns to alternate, e.g. parallel tool results become one turn.
+        match turns.last_mut() {
+            Some((last, existing)) if *last == role => existing.extend(blocks),
+            _ => turns.push((role, blocks)),
+        }
+    }
+
+    let has_tool_blocks = turns
+        .iter()
+        .flat_map(|(_, blocks)| blocks)
+        .any(|block| block.get("toolUse").is_some() || block.get("toolResult").is_some());
+
+    let mut body = Map::new();
+    body.insert(
+        "messages".to_string(),
+        turns
+            .into_iter()
+            .map(|(role, content)| json!({ "role": role, "content": content }))
+            .collect(),
+    );
+    if !system.is_empty() {
+        body.insert("system".to_string(), Value::Array(system));
+    }
+    if let Some(config) = inference_config(request) {
+        body.insert("inferenceConfig".to_string(), config);
+    }
+    if let Some(config) = tool_config(request, has_tool_blocks)? {
+        body.insert("toolConfig".to_string(), config);
+    }
+
+    Ok(Value::Object(body))
+}
+
+/// Builds an OpenAI `chat.completion` body from a Converse response.
+///
+/// Converse responses carry neither an id nor the model, so the caller passes them in.
+/// Reasoning output is wrapped in `<think>` tags, which clients already strip out.
+pub fn chat_completion(response: &Value, id: &str, model: &str, created: u64) -> Result<Value> {
+    let blocks = response
+        .pointer("/output/message/content")
+        .and_then(Value::as_array)
+        .ok_or_else(|| Error::InvalidResponse("missing output message".to_string()))?;
+
+    let mut reasoning = String::new();
+    let mut text = String::new();
+    let mut tool_calls = Vec::new();
+
+    for block in blocks {
+        if let Some(t) = block.get("text").and_then(Value::as_str) {
+            text.push_str(t);
+        } else if let Some(t) = block
+            .pointer("/reasoningContent/reasoningText/text")
+            .and_then(Value::as_str)
+        {
+            reasoning.push_str(t);
+        } else if let Some(tool_use) = block.get("toolUse") {
+            tool_calls.push(json!({
+                "id": tool_use.get("toolUseId").cloned().unwrap_or(Value::Null),
+                "type": "function",
+                "function": {
+                    "name": tool_use.get("name").cloned().unwrap_or(Value::Null),
+                    "arguments": tool_use.get("input").unwrap_or(&json!({})).to_string(),
+                },
+            }));
+        }
+    }
+
+    if !reasoning.is_empty() {
+        text = format!("<think>{reasoning}</think>{text}");
+    }
+
+    let mut message = json!({
+        "role": "assistant",
+        "content": if text.is_empty() { Value::Null } else { Value::String(text) },
+    });
+    if !tool_calls.is_empty() {
+        message["tool_calls"] = Value::Array(tool_calls);
+    }
+
+    let stop_reason = response
+        .get("stopReason")
+        .and_then(Value::as_str)
+        .unwrap_or("end_turn");
+
+    let mut completion = json!({
+        "id": id,
+        "object": "chat.completion",
+        "created": created,
+        "model": model,
+        "choices": [{
+            "index": 0,
+            "message": message,
+            "finish_reason": finish_reason(stop_reason),
+        }],
+    });
+    if let Some(usage) = response.get("usage") {
+        completion["usage"] = usage_info(usage);
+    }
+
+    Ok(completion)
+}
+
+/// Maps a Converse `stopReason` onto the OpenAI `finish_reason`.
+pub fn finish_reason(stop_reason: &str) -> &'static str {
+    match stop_reason {
+        "tool_use" => "tool_calls",
+        "max_tokens" | "model_context_window_exceeded" => "length",
+        "guardrail_intervened" | "content_filtered" => "content_filter",
+        _ => "stop",
+    }
+}
+
+/// Converse token usage in the OpenAI `usage` shape. Cached prompt tokens count as input.
+pub(crate) fn usage_info(usage: &Value) -> Value {
+    let count = |key: &str| usage.get(key).and_then(Value::as_u64).unwrap_or(0);
+
+    let prompt_tokens =
+        count("inputTokens") + count("cacheReadInputTokens") + count("cacheWriteInputTokens");
+    let completion_tokens = count("outputTokens");
+
+    json!({
+        "prompt_tokens": prompt_tokens,
+        "completion_tokens": completion_tokens,
+        "total_tokens": prompt_tokens + completion_tokens,
+    })
+}
+
+fn text_blocks(content: Option<&Value>) -> Result<Vec<Value>> {
+    match content {
+        None | Some(Value::Null) => Ok(Vec::new()),
+        Some(Value::String(text)) => Ok(text_block(text).into_iter().collect()),
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .filter_map(|part| match part.get("type").and_then(Value::as_str) {
+                Some("text") => part
+                    .get("text")
+                    .and_then(Value::as_str)
+                    .and_then(text_block)
+                    .map(Ok),
+                other => Some(Err(Error::InvalidRequest(format!(
+                    "unsupported content part {other:?}"
+                )))),
+            })
+            .collect(),
+        Some(_) => Err(Error::InvalidRequest(
+            "content must be a string or an array".to_string(),
+        )),
+    }
+}
+
+fn text_block(text: &str) -> Option<Value> {
+    // Converse rejects empty text blocks.
+    (!text.is_empty()).then(|| json!({ "text": text }))
+}
+
+fn user_blocks(content: Option<&Value>) -> Result<Vec<Value>> {
+    let Some(Value::Array(parts)) = content else {
+        return text_blocks(content);
+    };
+
+    let mut blocks = Vec::new();
+    for part in parts {
+        match part.get("type").and_then(Value::as_str) {
+            Some("image_url") => {
+                let url = part
+                    .pointer("/image_url/url")
+                    .and_then(Value::as_str)
+                    .unwrap_or("");
+                blocks.push(image_block(url)?);
+            }
+            _ => blocks.extend(text_blocks(Some(&Value::Array(vec![part.clone()])))?),
+        }
+    }
+    Ok(blocks)
+}
+
+/// Converse only takes inline images, so `image_url` must be a base64 data URL.
+fn image_block(url: &str) -> Result<Value> {
+    let (format, data) = url
+        .strip_prefix("data:image/")
+        .and_then(|rest| rest.split_once(";base64,"))
+        .ok_or_else(|| Error::InvalidRequest("images must be base64 data URLs".to_string()))?;
+
+    let format = match format {
+        "jpg" => "jpeg",
+        "png" | "jpeg" | "gif" | "webp" => format,
+        other => {
+            return Err(Error::InvalidRequest(format!(
+                "unsupported image format {other:?}"
+            )));
+        }
+    };
+
+    Ok(json!({ "image": { "format": format, "source": { "bytes": data } } }))
+}
+
+fn assistant_blocks(message: &Value) -> Result<Vec<Value>> {
+    let mut blocks = text_blocks(message.get("content"))?;
+
+    for call in message
+        .get("tool_calls")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let arguments = call
+            .pointer("/function/arguments")
+            .and_then(Value::as_str)
+            .unwrap_or("");
+        // Models sometimes emit an empty string for tools without parameters.
+        let input = if arguments.trim().is_empty() {
+            json!({})
+        } else {
+            serde_json::from_str(arguments).map_err(|e| {
+                Error::InvalidRequest(format!("tool call arguments are not JSON: {e}"))
+            })?
+        };
+
+        blocks.push(json!({
+            "toolUse": {
+                "toolUseId": call.get("id").and_then(Value::as_str).unwrap_or(""),
+                "name": call.pointer("/function/name").and_then(Value::as_str).unwrap_or(""),
+                "input": input,
+            }
+        }));
+    }
+
+    Ok(blocks)
+}
+
+fn tool_result_block(message: &Value) -> Result<Value> {
+    let tool_use_id = message
+        .get("tool_call_id")
+        .and_then(Value::as_str)
+        .ok_or_else(|| Error::InvalidRequest("tool message without tool_call_id".to_string()))?;
+
+    let mut content = text_blocks(message.get("content"))?;
+    if content.is_empty() {
+        content.push(json!({ "text": "(empty)" }));
+    }
+
+    Ok(json!({ "toolResult": { "toolUseId": tool_use_id, "content": content } }))
+}
+
+fn inference_config(request: &Value) -> Option<Value> {
+    let mut config = Map::new();
+
+    if let Some(max_tokens) = request
+        .get("max_completion_tokens")
+        .or_else(|| request.get("max_tokens"))
+        .filter(|v| v.is_u64())
+    {
+        config.insert("maxTokens".to_string(), max_tokens.clone());
+    }
+    if let Some(temperature) = request.get("temperature").filter(|v| v.is_number()) {
+        config.insert("temperature".to_string(), temperature.clone());
+    }
+    if let Some(top_p) = request.get("top_p").filter(|v| v.is_number()) {
+        config.insert("topP".to_string(), top_p.clone());
+    }
+    match request.get("stop") {
+        Some(Value::String(stop)) => {
+            config.insert("stopSequences".to_string(), json!([stop]));
+        }
+        Some(Value::Array(stops)) if !stops.is_empty() => {
+            config.insert("stopSequences".to_string(), Value::Array(stops.clone()));
+        }
+        _ => {}
+    }
+
+    (!config.is_empty()).then_some(Value::Object(config))
+}
+
+fn tool_config(request: &Value, has_tool_blocks: bool) -> Result<Option<Value>> {
+    let tools: Vec<Value> = request
+        .get("tools")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|tool| tool.get("function"))
+        .map(|function| {
+            let mut spec = json!({
+                "name": function.get("name").cloned().unwrap_or(Value::Null),
+                "inputSchema": {
+                    "json": function
+                        .get("parameters")
+                        .cloned()
+                        .unwrap_or_else(|| json!({ "type": "object", "properties": {} })),
+                },
+            });
+            if let Some(description) = function.get("description").filter(|d| d.is_string()) {
+                spec["description"] = description.clone();
+            }
+            json!({ "toolSpec": spec })
+        })
+        .collect();
+
+    if tools.is_empty() {
+        return Ok(None);
+    }
+
+    let tool_choice = match request.get("tool_choice") {
+        None | Some(Value::Null) => None,
+        Some(Value::String(choice)) => match choice.as_str() {
+            "auto" => Some(json!({ "auto": {} })),
+            "required" => Some(json!({ "any": {} })),
+            // Converse has no "none"; tools are only kept when the history needs them.
+            "none" if has_tool_blocks => None,
+            "none" => return Ok(None),
+            other => {
+                return Err(Error::InvalidRequest(format!(
+                    "unsupported tool_choice {other:?}"
+                )));
+            }
+        },
+        Some(choice) => {
+            let name = choice
+                .pointer("/function/name")
+                .and_then(Value::as_str)
+                .ok_or_else(|| Error::InvalidRequest("tool_choice without a name".to_string()))?;
+            Some(json!({ "tool": { "name": name } }))
+        }
+    };
+
+    let mut config = json!({ "tools": tools });
+    if let Some(tool_choice) = tool_choice {
+        config["toolChoice"] = tool_choice;
+    }
+    Ok(Some(config))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_converse_request() {
+        let request = json!({
+            "model": "ignored",
+            "messages": [
+                { "role": "system", "content": "Be brief." },
+                { "role": "user", "content": [
+                    { "type": "text", "text": "What's on my calendar?" },
+                    { "type": "image_url", "image_url": { "url": "data:image/png;base64,iVBORw0KGgo=" } },
+                ]},
+                { "role": "assistant", "content": null, "tool_calls": [
+                    { "id": "call_1", "type": "function", "function": { "name": "list_events", "arguments": "{\"day\":\"today\"}" } },
+                    { "id": "call_2", "type": "function", "function": { "name": "now", "arguments": "" } },
+                ]},
+                { "role": "tool", "tool_call_id": "call_1", "content": "Standup at 10" },
+                { "role": "tool", "tool_call_id": "call_2", "content": "09:00" },
+            ],
+            "tools": [
+                { "type": "function", "function": { "name": "list_events", "description": "List events", "parameters": { "type": "object", "properties": { "day": { "type": "string" } } } } },
+                { "type": "function", "function": { "name": "now" } },
+            ],
+            "tool_choice": "required",
+            "max_tokens": 256,
+            "temperature": 0.2,
+            "stop": "END",
+        });
+
+        assert_eq!(
+            converse_request(&request).unwrap(),
+            json!({
+                "system": [{ "text": "Be brief." }],
+                "messages": [
+                    { "role": "user", "content": [
+                        { "text": "What's on my calendar?" },
+                        { "image": { "format": "png", "source": { "bytes": "iVBORw0KGgo=" } } },
+                    ]},
+                    { "role": "assistant", "content": [
+                        { "toolUse": { "toolUseId": "call_1", "name": "list_events", "input": { "day": "today" } } },
+                        { "toolUse": { "toolUseId": "call_2", "name": "now", "input": {} } },
+                    ]},
+                    { "role": "user", "content": [
+                        { "toolResult": { "toolUseId": "call_1", "content": [{ "text": "Standup at 10" }] } },
+                        { "toolResult": { "toolUseId": "call_2", "content": [{ "text": "09:00" }] } },
+                    ]},
+                ],
+                "inferenceConfig": { "maxTokens": 256, "temperature": 0.2, "stopSequences": ["END"] },
+                "toolConfig": {
+                    "tools": [
+                        { "toolSpec": { "name": "list_events", "description": "List events", "inputSchema": { "json": { "type": "object", "properties": { "day": { "type": "string" } } } } } },
+                        { "toolSpec": { "name": "now", "inputSchema": { "json": { "type": "object", "properties": {} } } } },
+                    ],
+                    "toolChoice": { "any": {} },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn test_converse_request_rejects_remote_images() {
+        let request = json!({
+            "messages": [{ "role": "user", "content": [
+                { "type": "image_url", "image_url": { "url": "https://example.com/a.png" } },
+            ]}],
+        });
+        assert!(matches!(
+            converse_request(&request),
+            Err(Error::InvalidRequest(_))
+        ));
+    }
+
+    #[test]
+    fn test_tool_choice_none_drops_tools() {
+        let request = json!({
+            "messages": [{ "role": "user", "content": "hi" }],
+            "tools": [{ "type": "function", "function": { "name": "now" } }],
+            "tool_choice": "none",
+        });
+        assert!(
+            converse_request(&request)
+                .unwrap()
+                .get("toolConfig")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_chat_completion() {
+        let response = json!({
+            "output": { "message": { "role": "assistant", "content": [
+                { "reasoningContent": { "reasoningText": { "text": "Check the time." } } },
+                { "text": "Let me look." },
+                { "toolUse": { "toolUseId": "tooluse_1", "name": "now", "input": {} } },
+            ]}},
+            "stopReason": "tool_use",
+            "usage": { "inputTokens": 20, "outputTokens": 7, "totalTokens": 27, "cacheReadInputTokens": 5 },
+        });
+
+        assert_eq!(
+            chat_completion(&response, "req-1", "anthropic.claude", 1_700_000_000).unwrap(),
+            json!({
+                "id": "req-1",
+                "object": "chat.completion",
+                "created": 1_700_000_000,
+                "model": "anthropic.claude",
+                "choices": [{
+                    "index": 0,
+                    "message": {
+                        "role": "assistant",
+                        "content": "<think>Check the time.</think>Let me look.",
+                        "tool_calls": [{ "id": "tooluse_1", "type": "function", "function": { "name": "now", "arguments": "{}" } }],
+                    },
+                    "finish_reason": "tool_calls",
+                }],
+                "usage": { "prompt_tokens": 25, "completion_tokens": 7, "total_tokens": 32 },
+            })
+        );
+    }
+
+    #[test]
+    fn test_converse_path_encodes_model_id() {
+        assert_eq!(
+            converse_path("us.anthropic.claude-sonnet-4-20250514-v1:0", true),
+            "/model/us.anthropic.claude-sonnet-4-20250514-v1%3A0/converse-stream"
+        );
+    }
+}
@@ -0,0 +1,13 @@
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+    #[error("invalid response: {0}")]
+    InvalidResponse(String),
+    #[error("malformed event stream: {0}")]
+    EventStream(&'static str),
+    #[error("{kind}: {message}")]
+    Upstream { kind: String, message: String },
+}
@@ -0,0 +1,170 @@
+use crate::{Error, Result};
+
+const PRELUDE_LEN: usize = 12;
+const CRC_LEN: usize = 4;
+const MAX_MESSAGE_LEN: usize = 16 * 1024 * 1024;
+
+/// One frame of the `application/vnd.amazon.eventstream` encoding ConverseStream responds with.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    /// String-valued headers such as `:event-type`; other header types are skipped.
+    pub headers: Vec<(String, String)>,
+    pub payload: Vec<u8>,
+}
+
+impl Message {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Splits an event-stream byte stream into messages, buffering frames that span chunks.
+///
+/// Checksums are not verified; the stream already arrives over TLS.
+#[derive(Debug, Default)]
+pub struct EventStreamDecoder {
+    buffer: Vec<u8>,
+}
+
+impl EventStreamDecoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, chunk: &[u8]) -> Result<Vec<Message>> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut messages = Vec::new();
+        let mut offset = 0;
+
+        while self.buffer.len() - offset >= PRELUDE_LEN {
+            let frame = &self.buffer[offset..];
+            let total_len = read_u32(frame, 0) as usize;
+            let headers_len = read_u32(frame, 4) as usize;
+
+            if total_len < PRELUDE_LEN + CRC_LEN + headers_len || total_len > MAX_MESSAGE_LEN {
+                return Err(Error::EventStream("invalid frame length"));
+            }
+            if frame.len() < total_len {
+                break;
+            }
+
+            let headers_end = PRELUDE_LEN + headers_len;
+            messages.push(Message {
+                headers: parse_headers(&frame[PRELUDE_LEN..headers_end])?,
+                payload: frame[headers_end..total_len - CRC_LEN].to_vec(),
+            });
+            offset += total_len;
+        }
+
+        self.buffer.drain(..offset);
+        Ok(messages)
+    }
+
+    /// Whether a partial frame is still waiting for more bytes.
+    pub fn has_pending(&self) -> bool {
+        !self.buffer.is_empty()
+    }
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    u32::from_be_bytes([bytes[at], bytes[at + 1], bytes[at + 2], bytes[at + 3]])
+}
+
+fn parse_headers(mut bytes: &[u8]) -> Result<Vec<(String, String)>> {
+    const TRUNCATED: Error = Error::EventStream("truncated header");
+
+    let mut headers = Vec::new();
+
+    while let Some((&name_len, rest)) = bytes.split_first() {
+        let name_len = name_len as usize;
+        let (name, rest) = rest.split_at_checked(name_len).ok_or(TRUNCATED)?;
+        let (&value_type, rest) = rest.split_first().ok_or(TRUNCATED)?;
+
+        let value_len = match value_type {
+            // bool true / bool false carry no value.
+            0 | 1 => 0,
+            2 => 1,
+            3 => 2,
+            4 => 4,
+            5 | 8 => 8,
+            9 => 16,
+            // Byte arrays and strings are prefixed with a u16 length.
+            6 | 7 => {
+                let (len, _) = rest.split_at_checked(2).ok_or(TRUNCATED)?;
+                2 + u16::from_be_bytes([len[0], len[1]]) as usize
+            }
+            _ => return Err(Error::EventStream("unknown header type")),
+        };
+        let (value, rest) = rest.split_at_checked(value_len).ok_or(TRUNCATED)?;
+
+        if value_type == 7 {
+            headers.push((
+                String::from_utf8_lossy(name).into_owned(),
+                String::from_utf8_lossy(&value[2..]).into_owned(),
+            ));
+        }
+        bytes = rest;
+    }
+
+    Ok(headers)
+}
+
+/// One frame with string headers and zeroed checksums, which `EventStreamDecoder` accepts; used
+/// to fake ConverseStream responses in tests.
+pub fn encode(headers: &[(&str, &str)], payload: &[u8]) -> Vec<u8> {
+    let mut header_bytes = Vec::new();
+    for (name, value) in headers {
+        header_bytes.push(name.len() as u8);
+        header_bytes.extend_from_slice(name.as_bytes());
+        header_bytes.push(7);
+        header_bytes.extend_from_slice(&(value.len() as u16).to_be_bytes());
+        header_bytes.extend_from_slice(value.as_bytes());
+    }
+
+    let total_len = PRELUDE_LEN + header_bytes.len() + payload.len() + CRC_LEN;
+    let mut frame = Vec::with_capacity(total_len);
+    frame.extend_from_slice(&(total_len as u32).to_be_bytes());
+    frame.extend_from_slice(&(header_bytes.len() as u32).to_be_bytes());
+    frame.extend_from_slice(&[0; 4]);
+    frame.extend_from_slice(&header_bytes);
+    frame.extend_from_slice(payload);
+    frame.extend_from_slice(&[0; 4]);
+    frame
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decodes_frames_split_across_chunks() {
+        let mut bytes = encode(
+            &[(":event-type", "messageStart"), (":message-type", "event")],
+            br#"{"role":"assistant"}"#,
+        );
+        bytes.extend(encode(&[(":event-type", "messageStop")], b"{}"));
+
+        let mut decoder = EventStreamDecoder::new();
+        let mut messages = Vec::new();
+        for chunk in bytes.chunks(7) {
+            messages.extend(decoder.push(chunk).unwrap());
+        }
+
+        assert!(!decoder.has_pending());
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].header(":event-type"), Some("messageStart"));
+        assert_eq!(messages[0].header(":message-type"), Some("event"));
+        assert_eq!(messages[0].payload, br#"{"role":"assistant"}"#);
+        assert_eq!(messages[1].header(":event-type"), Some("messageStop"));
+    }
+
+    #[test]
+    fn test_rejects_invalid_length() {
+        let mut decoder = EventStreamDecoder::new();
+        assert!(decoder.push(&[0, 0, 0, 4, 0, 0, 0, 0, 0, 0, 0, 0]).is_err());
+    }
+}
@@ -0,0 +1,12 @@
+//! Translation between the OpenAI chat completions format and Amazon Bedrock's
+//! Converse / ConverseStream APIs, shared by the LLM proxy and the desktop plugin.
+
+mod convert;
+mod error;
+pub mod eventstream;
+mod stream;
+
+pub use convert::{chat_completion, converse_path, converse_request, finish_reason};
+pub use error::{Error, Result};
+pub use eventstream::{EventStreamDecoder, Message};
+pub use stream::{SSE_DONE, StreamTranslator, sse_event};
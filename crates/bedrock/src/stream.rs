@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+
+use serde_json::{Value, json};
+
+use crate::convert::{finish_reason, usage_info};
+use crate::{Error, Message, Result};
+
+/// Terminates an OpenAI-format SSE stream.
+pub const SSE_DONE: &str = "data: [DONE]\n\n";
+
+pub fn sse_event(chunk: &Value) -> String {
+    format!("data: {chunk}\n\n")
+}
+
+/// Turns ConverseStream events into OpenAI `chat.completion.chunk` objects.
+///
+/// Tool calls are numbered in the order they start, and reasoning deltas are wrapped in
+/// `<think>` tags like [`chat_completion`](crate::chat_completion) does. Usage arrives in a
+/// final chunk with no choices, as with `stream_options.include_usage`.
+pub struct StreamTranslator {
+    id: String,
+    model: String,
+    created: u64,
+    tool_calls: HashMap<u64, usize>,
+    in_reasoning: bool,
+}
+
+impl StreamTranslator {
+    pub fn new(id: impl Into<String>, model: impl Into<String>, created: u64) -> Self {
+        Self {
+            id: id.into(),
+            model: model.into(),
+            created,
+            tool_calls: HashMap::new(),
+            in_reasoning: false,
+        }
+    }
+
+    /// Chunks for one event. Exception events, such as throttling mid-stream, become
+    /// [`Error::Upstream`].
+    pub fn translate(&mut self, message: &Message) -> Result<Vec<Value>> {
+        let payload: Value = serde_json::from_slice(&message.payload).unwrap_or(Value::Null);
+
+        if message.header(":message-type") != Some("event") {
+            let kind = message
+                .header(":exception-type")
+                .or_else(|| message.header(":error-code"))
+                .unwrap_or("unknown");
+            let text = payload
+                .get("message")
+                .and_then(Value::as_str)
+                .or_else(|| message.header(":error-message"))
+                .unwrap_or("");
+            return Err(Error::Upstream {
+                kind: kind.to_string(),
+                message: text.to_string(),
+            });
+        }
+
+        let chunks = match message.header(":event-type").unwrap_or("") {
+            "messageStart" => vec![self.delta(json!({ "role": "assistant", "content": "" }))],
+            "contentBlockStart" => {
+                let Some(tool_use) = payload.pointer("/start/toolUse") else {
+                    return Ok(Vec::new());
+                };
+                let index = self.tool_calls.len();
+                self.tool_calls.insert(block_index(&payload), index);
+
+                let mut chunks = self.close_reasoning();
+                chunks.push(self.delta(json!({
+                    "tool_calls": [{
+                        "index": index,
+                        "id": tool_use.get("toolUseId").cloned().unwrap_or(Value::Null),
+                        "type": "function",
+                        "function": {
+                            "name": tool_use.get("name").cloned().unwrap_or(Value::Null),
+                            "arguments": "",
+                        },
+                    }],
+                })));
+                chunks
+            }
+            "contentBlockDelta" => self.content_delta(&payload),
+            "messageStop" => {
+                let stop_reason = payload
+                    .get("stopReason")
+                    .and_then(Value::as_str)
+                    .unwrap_or("end_turn");
+
+                let mut chunks = self.close_reasoning();
+                chunks.push(self.chunk(json!([{
+                    "index": 0,
+                    "delta": {},
+                    "finish_reason": finish_reason(stop_reason),
+                }])));
+                chunks
+            }
+            "metadata" => match payload.get("usage") {
+                Some(usage) => {
+                    let mut chunk = self.chunk(json!([]));
+                    chunk["usage"] = usage_info(usage);
+                    vec![chunk]
+                }
+                None => Vec::new(),
+            },
+            _ => Vec::new(),
+        };
+
+        Ok(chunks)
+    }
+
+    fn content_delta(&mut self, payload: &Value) -> Vec<Value> {
+        let Some(delta) = payload.get("delta") else {
+            return Vec::new();
+        };
+
+        if let Some(text) = delta.get("text").and_then(Value::as_str) {
+            let prefix = if std::mem::take(&mut self.in_reasoning) {
+                "</think>"
+            } else {
+                ""
+            };
+            return vec![self.delta(json!({ "content": format!("{prefix}{text}") }))];
+        }
+
+        if let Some(text) = delta
+            .pointer("/reasoningContent/text")
+            .and_then(Value::as_str)
+        {
+            let prefix = if std::mem::replace(&mut self.in_reasoning, true) {
+                ""
+            } else {
+                "<think>"
+            };
+            return vec![self.delta(json!({ "content": format!("{prefix}{text}") }))];
+        }
+
+        if let Some(input) = delta.pointer("/toolUse/input").and_then(Value::as_str)
+            && let Some(&index) = self.tool_calls.get(&block_index(payload))
+        {
+            return vec![self.delta(json!({
+                "tool_calls": [{ "index": index, "function": { "arguments": input } }],
+            }))];
+        }
+
+        Vec::new()
+    }
+
+    fn close_reasoning(&mut self) -> Vec<Value> {
+        if std::mem::take(&mut self.in_reasoning) {
+            vec![self.delta(json!({ "content": "</think>" }))]
+        } else {
+            Vec::new()
+        }
+    }
+
+    fn delta(&self, delta: Value) -> Value {
+        self.chunk(json!([{ "index": 0, "delta": delta, "finish_reason": null }]))
+    }
+
+    fn chunk(&self, choices: Value) -> Value {
+        json!({
+            "id": self.id,
+            "object": "chat.completion.chunk",
+            "created": self.created,
+            "model": self.model,
+            "choices": choices,
+        })
+    }
+}
+
+fn block_index(payload: &Value) -> u64 {
+    payload
+        .get("contentBlockIndex")
+        .and_then(Value::as_u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EventStreamDecoder;
+    use crate::eventstream::encode;
+
+    fn event(event_type: &str, payload: Value) -> Vec<u8> {
+        encode(
+            &[(":event-type", event_type), (":message-type", "event")],
+            payload.to_string().as_bytes(),
+        )
+    }
+
+    fn translate_all(frames: Vec<Vec<u8>>) -> Result<Vec<Value>> {
+        let mut decoder = EventStreamDecoder::new();
+        let mut translator = StreamTranslator::new("req-1", "model", 1);
+
+        let mut chunks = Vec::new();
+        for message in decoder.push(&frames.concat())? {
+            chunks.extend(translator.translate(&message)?);
+        }
+        Ok(chunks)
+    }
+
+    #[test]
+    fn test_translates_text_tool_calls_and_usage() {
+        let chunks = translate_all(vec![
+            event("messageStart", json!({ "role": "assistant" })),
+            event("contentBlockDelta", json!({ "contentBlockIndex": 0, "delta": { "reasoningContent": { "text": "Hmm." } } })),
+            event("contentBlockDelta", json!({ "contentBlockIndex": 1, "delta": { "text": "Checking." } })),
+            event("contentBlockStart", json!({ "contentBlockIndex": 2, "start": { "toolUse": { "toolUseId": "tooluse_1", "name": "now" } } })),
+            event("contentBlockDelta", json!({ "contentBlockIndex": 2, "delta": { "toolUse": { "input": "{\"tz\":" } } })),
+            event("contentBlockDelta", json!({ "contentBlockIndex": 2, "delta": { "toolUse": { "input": "\"UTC\"}" } } })),
+            event("contentBlockStop", json!({ "contentBlockIndex": 2 })),
+            event("messageStop", json!({ "stopReason": "tool_use" })),
+            event("metadata", json!({ "usage": { "inputTokens": 12, "outputTokens": 4, "totalTokens": 16 } })),
+        ])
+        .unwrap();
+
+        let deltas: Vec<&Value> = chunks
+            .iter()
+            .filter_map(|c| c.pointer("/choices/0/delta"))
+            .collect();
+        assert_eq!(deltas[1], &json!({ "content": "<think>Hmm." }));
+        assert_eq!(deltas[2], &json!({ "content": "</think>Checking." }));
+        assert_eq!(deltas[3]["tool_calls"][0]["id"], "tooluse_1");
+        assert_eq!(
+            deltas[5],
+            &json!({ "tool_calls": [{ "index": 0, "function": { "arguments": "\"UTC\"}" } }] })
+        );
+
+        assert_eq!(chunks[6]["choices"][0]["finish_reason"], "tool_calls");
+        assert_eq!(
+            chunks[7]["usage"],
+            json!({ "prompt_tokens": 12, "completion_tokens": 4, "total_tokens": 16 })
+        );
+    }
+
+    #[test]
+    fn test_exception_becomes_error() {
+        let frame = encode(
+            &[
+                (":message-type", "exception"),
+                (":exception-type", "throttlingException"),
+            ],
+            br#"{"message":"Too many requests"}"#,
+        );
+
+        match translate_all(vec![frame]) {
+            Err(Error::Upstream { kind, message }) => {
+                assert_eq!(kind, "throttlingException");
+                assert_eq!(message, "Too many requests");
+            }
+            other => panic!("expected upstream error, got {other:?}"),
+        }
+    }
+}
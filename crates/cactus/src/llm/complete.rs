@@ -47,13 +47,26 @@ unsafe extern "C" fn token_trampoline<F: FnMut(&str) -> bool>(
     }
 }
 
+pub(super) struct CompleteRequest {
+    messages: CString,
+    options: CString,
+    tools: Option<CString>,
+}
+
 pub(super) fn serialize_complete_request(
     messages: &[Message],
     options: &CompleteOptions,
-) -> Result<(CString, CString)> {
-    let messages_c = CString::new(serde_json::to_string(messages)?)?;
-    let options_c = CString::new(serde_json::to_string(options)?)?;
-    Ok((messages_c, options_c))
+) -> Result<CompleteRequest> {
+    let tools = match &options.tools {
+        Some(tools) if !tools.is_empty() => Some(CString::new(serde_json::to_string(tools)?)?),
+        _ => None,
+    };
+
+    Ok(CompleteRequest {
+        messages: CString::new(serde_json::to_string(messages)?)?,
+        options: CString::new(serde_json::to_string(options)?)?,
+        tools,
+    })
 }
 
 pub(super) fn complete_error(rc: i32) -> Error {
@@ -64,8 +77,7 @@ impl Model {
     fn call_complete(
         &self,
         guard: &InferenceGuard<'_>,
-        messages_c: &CString,
-        options_c: &CString,
+        request: &CompleteRequest,
         callback: Option<TokenCallback>,
         user_data: *mut std::ffi::c_void,
     ) -> (i32, Vec<u8>) {
@@ -74,11 +86,14 @@ impl Model {
         let rc = unsafe {
             cactus_sys::cactus_complete(
                 guard.raw_handle(),
-                messages_c.as_ptr(),
+                request.messages.as_ptr(),
                 buf.as_mut_ptr().cast::<std::ffi::c_char>(),
                 buf.len(),
-                options_c.as_ptr(),
-                std::ptr::null(),
+                request.options.as_ptr(),
+                request
+                    .tools
+                    .as_ref()
+                    .map_or(std::ptr::null(), |t| t.as_ptr()),
                 callback,
                 user_data,
             )
@@ -93,9 +108,8 @@ impl Model {
         options: &CompleteOptions,
     ) -> Result<CompletionResult> {
         let guard = self.lock_inference();
-        let (messages_c, options_c) = serialize_complete_request(messages, options)?;
-        let (rc, buf) =
-            self.call_complete(&guard, &messages_c, &options_c, None, std::ptr::null_mut());
+        let request = serialize_complete_request(messages, options)?;
+        let (rc, buf) = self.call_complete(&guard, &request, None, std::ptr::null_mut());
 
         if rc < 0 {
             return Err(complete_error(rc));
@@ -114,7 +128,7 @@ impl Model {
         F: FnMut(&str) -> bool,
     {
         let guard = self.lock_inference();
-        let (messages_c, options_c) = serialize_complete_request(messages, options)?;
+        let request = serialize_complete_request(messages, options)?;
 
         let state = CallbackState {
             on_token: UnsafeCell::new(&mut on_token),
@@ -125,8 +139,7 @@ impl Model {
 
         let (rc, buf) = self.call_complete(
             &guard,
-            &messages_c,
-            &options_c,
+            &request,
             Some(token_trampoline::<F>),
             &state as *const CallbackState<F> as *mut std::ffi::c_void,
         );
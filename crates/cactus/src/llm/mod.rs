@@ -20,4 +20,10 @@ pub struct CompleteOptions {
     pub stop_sequences: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub confidence_threshold: Option<f32>,
+    /// GBNF grammar for the sampler. Not every engine build enforces it, so check the output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub grammar: Option<String>,
+    /// OpenAI-style tool definitions. Sent to the engine alongside the options, not in them.
+    #[serde(skip)]
+    pub tools: Option<Vec<serde_json::Value>>,
 }
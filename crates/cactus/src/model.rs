@@ -13,6 +13,29 @@ pub enum ModelKind {
     Parakeet,
 }
 
+impl ModelKind {
+    /// Reads `model_type` from the `config.txt` that cactus ships alongside the weights. `None`
+    /// when the file is missing or names an architecture we don't handle.
+    pub fn from_model_dir(model_path: impl AsRef<Path>) -> Option<Self> {
+        let config = std::fs::read_to_string(model_path.as_ref().join("config.txt")).ok()?;
+        Self::from_config(&config)
+    }
+
+    fn from_config(config: &str) -> Option<Self> {
+        let model_type = config.lines().find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            (key.trim() == "model_type").then(|| value.trim().to_ascii_lowercase())
+        })?;
+
+        match model_type.as_str() {
+            t if t.starts_with("whisper") => Some(Self::Whisper),
+            t if t.starts_with("moonshine") => Some(Self::Moonshine),
+            t if t.starts_with("parakeet") => Some(Self::Parakeet),
+            _ => None,
+        }
+    }
+}
+
 pub struct Model {
     handle: NonNull<std::ffi::c_void>,
     inference_lock: Mutex<()>,
@@ -112,3 +135,23 @@ impl Drop for Model {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kind_from_config() {
+        assert_eq!(
+            ModelKind::from_config("vocab_size=51865\nmodel_type=whisper\n"),
+            Some(ModelKind::Whisper)
+        );
+        assert_eq!(
+            ModelKind::from_config("model_type = parakeet_tdt"),
+            Some(ModelKind::Parakeet)
+        );
+        assert_eq!(ModelKind::from_config("model_type=gemma"), None);
+        assert_eq!(ModelKind::from_config("vocab_size=51865"), None);
+        assert_eq!(ModelKind::from_model_dir("/nonexistent/model"), None);
+    }
+}
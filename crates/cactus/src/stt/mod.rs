@@ -41,4 +41,8 @@ pub struct TranscribeOptions {
     pub custom_vocabulary: Option<Vec<String>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub vocabulary_boost: Option<f32>,
+    /// Whisper only: translate into English instead of transcribing. Prompt-side, so it is not
+    /// passed to the FFI options.
+    #[serde(skip)]
+    pub translate: bool,
 }
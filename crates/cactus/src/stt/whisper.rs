@@ -8,6 +8,7 @@ enum WhisperToken<'a> {
     StartOfTranscript,
     Language(&'a str),
     Transcribe,
+    Translate,
     NoTimestamps,
 }
 
@@ -19,6 +20,7 @@ impl fmt::Display for WhisperToken<'_> {
             Self::StartOfTranscript => write!(f, "<|startoftranscript|>"),
             Self::Language(l) => write!(f, "<|{l}|>"),
             Self::Transcribe => write!(f, "<|transcribe|>"),
+            Self::Translate => write!(f, "<|translate|>"),
             Self::NoTimestamps => write!(f, "<|notimestamps|>"),
         }
     }
@@ -38,7 +40,11 @@ pub(super) fn build_whisper_prompt(options: &TranscribeOptions) -> String {
         tokens.push(WhisperToken::Language(lang.iso639_code()));
     }
 
-    tokens.push(WhisperToken::Transcribe);
+    tokens.push(if options.translate {
+        WhisperToken::Translate
+    } else {
+        WhisperToken::Transcribe
+    });
     tokens.push(WhisperToken::NoTimestamps);
 
     tokens.iter().map(|t| t.to_string()).collect()
@@ -83,4 +89,14 @@ mod tests {
         };
         insta::assert_snapshot!(build_whisper_prompt(&opts), @"<|startofprev|>안녕하세요<|startoftranscript|><|ko|><|transcribe|><|notimestamps|>");
     }
+
+    #[test]
+    fn translate() {
+        let opts = TranscribeOptions {
+            language: Some(Language::from(hypr_language::ISO639::De)),
+            translate: true,
+            ..Default::default()
+        };
+        insta::assert_snapshot!(build_whisper_prompt(&opts), @"<|startoftranscript|><|de|><|translate|><|notimestamps|>");
+    }
 }
@@ -0,0 +1,182 @@
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use url::Url;
+
+use crate::multistatus::{self, DavResponse};
+use crate::{CaldavCalendar, Error};
+
+const CALENDAR_PROPS: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:propfind xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav" xmlns:ic="http://apple.com/ns/ical/">
+  <d:prop>
+    <d:resourcetype/>
+    <d:displayname/>
+    <d:current-user-principal/>
+    <d:current-user-privilege-set/>
+    <c:calendar-home-set/>
+    <c:calendar-description/>
+    <c:supported-calendar-component-set/>
+    <ic:calendar-color/>
+  </d:prop>
+</d:propfind>"#;
+
+/// A CalDAV account (RFC 4791), such as Nextcloud, Fastmail or Radicale, using basic auth.
+#[derive(Clone)]
+pub struct CaldavClient {
+    http: reqwest::Client,
+    base_url: Url,
+    username: String,
+    password: String,
+}
+
+impl CaldavClient {
+    /// `url` can be the server's DAV root, the user's principal, or a single calendar.
+    pub fn new(
+        url: &str,
+        username: impl Into<String>,
+        password: impl Into<String>,
+    ) -> Result<Self, Error> {
+        let mut base_url = Url::parse(url.trim())?;
+        if !base_url.path().ends_with('/') {
+            base_url.set_path(&format!("{}/", base_url.path()));
+        }
+
+        Ok(Self {
+            http: reqwest::Client::new(),
+            base_url,
+            username: username.into(),
+            password: password.into(),
+        })
+    }
+
+    /// Discovers the event calendars of the account: the configured URL itself if it is a
+    /// calendar, otherwise every calendar in the principal's calendar home.
+    pub async fn list_calendars(&self) -> Result<Vec<CaldavCalendar>, Error> {
+        let base = self.propfind(&self.base_url, 0).await?;
+        if let Some(calendar) = base.iter().find(|r| is_event_calendar(r)) {
+            return Ok(vec![self.to_calendar(calendar)?]);
+        }
+
+        let principal = match base
+            .iter()
+            .find_map(|r| r.current_user_principal.as_deref())
+        {
+            Some(href) => self.base_url.join(href)?,
+            None => self.base_url.clone(),
+        };
+
+        let home_set = base.iter().find_map(|r| r.calendar_home_set.clone());
+        let home_set = match home_set {
+            Some(href) => Some(href),
+            None => self
+                .propfind(&principal, 0)
+                .await?
+                .into_iter()
+                .find_map(|r| r.calendar_home_set),
+        };
+        let home = match home_set {
+            Some(href) => self.base_url.join(&href)?,
+            None => principal,
+        };
+
+        self.propfind(&home, 1)
+            .await?
+            .iter()
+            .filter(|r| is_event_calendar(r))
+            .map(|r| self.to_calendar(r))
+            .collect()
+    }
+
+    /// The iCalendar data of every event in `calendar_url` that overlaps `[from, to)`. Recurring
+    /// events come back as their whole series, for the caller to expand.
+    pub async fn list_events(
+        &self,
+        calendar_url: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<Vec<String>, Error> {
+        let url = self.base_url.join(calendar_url)?;
+        let body = format!(
+            r#"<?xml version="1.0" encoding="utf-8"?>
+<c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
+  <d:prop>
+    <c:calendar-data/>
+  </d:prop>
+  <c:filter>
+    <c:comp-filter name="VCALENDAR">
+      <c:comp-filter name="VEVENT">
+        <c:time-range start="{}" end="{}"/>
+      </c:comp-filter>
+    </c:comp-filter>
+  </c:filter>
+</c:calendar-query>"#,
+            from.format("%Y%m%dT%H%M%SZ"),
+            to.format("%Y%m%dT%H%M%SZ"),
+        );
+
+        let responses = self.request("REPORT", &url, 1, body).await?;
+        Ok(responses
+            .into_iter()
+            .filter_map(|r| r.calendar_data)
+            .collect())
+    }
+
+    async fn propfind(&self, url: &Url, depth: u8) -> Result<Vec<DavResponse>, Error> {
+        self.request("PROPFIND", url, depth, CALENDAR_PROPS.to_string())
+            .await
+    }
+
+    async fn request(
+        &self,
+        method: &'static str,
+        url: &Url,
+        depth: u8,
+        body: String,
+    ) -> Result<Vec<DavResponse>, Error> {
+        let response = self
+            .http
+            .request(
+                Method::from_bytes(method.as_bytes()).expect("valid method"),
+                url.clone(),
+            )
+            .basic_auth(&self.username, Some(&self.password))
+            .header("Depth", depth.to_string())
+            .header("Content-Type", "application/xml; charset=utf-8")
+            .body(body)
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Error::Status {
+                method,
+                url: url.to_string(),
+                status: status.as_u16(),
+            });
+        }
+
+        multistatus::parse(&response.text().await?)
+    }
+
+    fn to_calendar(&self, response: &DavResponse) -> Result<CaldavCalendar, Error> {
+        let can_edit = (!response.privileges.is_empty()).then(|| {
+            response
+                .privileges
+                .iter()
+                .any(|p| matches!(p.as_str(), "all" | "write" | "write-content"))
+        });
+
+        Ok(CaldavCalendar {
+            url: self.base_url.join(&response.href)?.to_string(),
+            display_name: response.display_name.clone(),
+            description: response.description.clone(),
+            color: response.color.clone(),
+            can_edit,
+        })
+    }
+}
+
+/// Calendars that may hold events; task-only lists are skipped.
+fn is_event_calendar(response: &DavResponse) -> bool {
+    response.is_calendar
+        && (response.components.is_empty() || response.components.iter().any(|c| c == "VEVENT"))
+}
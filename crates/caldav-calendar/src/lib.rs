@@ -0,0 +1,8 @@
+mod client;
+mod error;
+mod multistatus;
+mod types;
+
+pub use client::CaldavClient;
+pub use error::Error;
+pub use types::*;
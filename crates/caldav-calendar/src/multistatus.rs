@@ -0,0 +1,241 @@
+use quick_xml::Reader;
+use quick_xml::escape::resolve_predefined_entity;
+use quick_xml::events::Event;
+
+use crate::Error;
+
+/// One `<response>` of a WebDAV multistatus body, reduced to the properties we ask for.
+/// Elements are matched by local name, since servers disagree on namespace prefixes.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct DavResponse {
+    pub href: String,
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    pub color: Option<String>,
+    pub is_calendar: bool,
+    /// `supported-calendar-component-set`, e.g. `["VEVENT", "VTODO"]`.
+    pub components: Vec<String>,
+    /// Privileges from `current-user-privilege-set`, e.g. `["read", "write"]`.
+    pub privileges: Vec<String>,
+    pub current_user_principal: Option<String>,
+    pub calendar_home_set: Option<String>,
+    pub calendar_data: Option<String>,
+}
+
+pub(crate) fn parse(xml: &str) -> Result<Vec<DavResponse>, Error> {
+    let mut reader = Reader::from_str(xml);
+    let mut responses = Vec::new();
+    let mut current: Option<DavResponse> = None;
+    let mut path: Vec<String> = Vec::new();
+    let mut text = String::new();
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) => {
+                let name = local_name(e.local_name().as_ref());
+                if name == "response" {
+                    current = Some(DavResponse::default());
+                }
+                path.push(name);
+                text.clear();
+            }
+            Event::Empty(e) => {
+                let name = local_name(e.local_name().as_ref());
+                if let Some(response) = current.as_mut() {
+                    let component = e
+                        .try_get_attribute("name")
+                        .ok()
+                        .flatten()
+                        .and_then(|attr| attr.unescape_value().ok())
+                        .map(|value| value.to_ascii_uppercase());
+                    on_empty(response, &path, &name, component);
+                }
+            }
+            Event::Text(e) => text.push_str(&e.xml10_content().map_err(quick_xml::Error::from)?),
+            Event::CData(e) => text.push_str(&e.decode().map_err(quick_xml::Error::from)?),
+            Event::GeneralRef(e) => {
+                if let Some(c) = e.resolve_char_ref()? {
+                    text.push(c);
+                } else {
+                    let entity = e.decode().map_err(quick_xml::Error::from)?;
+                    text.push_str(resolve_predefined_entity(&entity).unwrap_or_default());
+                }
+            }
+            Event::End(_) => {
+                let name = path.pop().unwrap_or_default();
+                if let Some(response) = current.as_mut() {
+                    on_end(response, &path, &name, std::mem::take(&mut text));
+                }
+                if name == "response"
+                    && let Some(response) = current.take()
+                {
+                    responses.push(response);
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Ok(responses)
+}
+
+fn on_empty(response: &mut DavResponse, path: &[String], name: &str, component: Option<String>) {
+    let parent = path.last().map(String::as_str);
+
+    match (parent, name) {
+        (Some("resourcetype"), "calendar") => response.is_calendar = true,
+        (Some("supported-calendar-component-set"), "comp") => {
+            response.components.extend(component);
+        }
+        (Some("privilege"), privilege) => response.privileges.push(privilege.to_string()),
+        _ => {}
+    }
+}
+
+fn on_end(response: &mut DavResponse, path: &[String], name: &str, text: String) {
+    let parent = path.last().map(String::as_str);
+    let text = text.trim();
+    let value = (!text.is_empty()).then(|| text.to_string());
+
+    match (parent, name) {
+        (Some("response"), "href") => response.href = text.to_string(),
+        (Some("prop"), "displayname") => response.display_name = value,
+        (Some("prop"), "calendar-description") => response.description = value,
+        (Some("prop"), "calendar-color") => response.color = value,
+        (Some("prop"), "calendar-data") => response.calendar_data = value,
+        (Some("current-user-principal"), "href") => response.current_user_principal = value,
+        (Some("calendar-home-set"), "href") => {
+            // Some servers list several homes; the first is the user's own.
+            if response.calendar_home_set.is_none() {
+                response.calendar_home_set = value;
+            }
+        }
+        _ => {}
+    }
+}
+
+fn local_name(name: &[u8]) -> String {
+    String::from_utf8_lossy(name).to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_calendar_home() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<d:multistatus xmlns:d="DAV:" xmlns:cal="urn:ietf:params:xml:ns:caldav" xmlns:ic="http://apple.com/ns/ical/">
+  <d:response>
+    <d:href>/remote.php/dav/calendars/jane/</d:href>
+    <d:propstat>
+      <d:prop><d:resourcetype><d:collection/></d:resourcetype></d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+  <d:response>
+    <d:href>/remote.php/dav/calendars/jane/personal/</d:href>
+    <d:propstat>
+      <d:prop>
+        <d:displayname>Personal &amp; Work</d:displayname>
+        <d:resourcetype><d:collection/><cal:calendar/></d:resourcetype>
+        <ic:calendar-color>#0082c9</ic:calendar-color>
+        <cal:supported-calendar-component-set>
+          <cal:comp name="VEVENT"/><cal:comp name="VTODO"/>
+        </cal:supported-calendar-component-set>
+        <d:current-user-privilege-set>
+          <d:privilege><d:read/></d:privilege>
+          <d:privilege><d:write/></d:privilege>
+        </d:current-user-privilege-set>
+      </d:prop>
+      <d:status>HTTP/1.1 200 OK</d:status>
+    </d:propstat>
+  </d:response>
+  <d:response>
+    <d:href>/remote.php/dav/calendars/jane/personal/standup.ics</d:href>
+    <d:propstat>
+      <d:prop>
+        <cal:calendar-data>BEGIN:VCALENDAR&#13;
+END:VCALENDAR</cal:calendar-data>
+      </d:prop>
+    </d:propstat>
+  </d:response>
+</d:multistatus>"#;
+
+        let responses = parse(xml).unwrap();
+        assert_eq!(responses.len(), 3);
+        assert!(!responses[0].is_calendar);
+
+        let calendar = &responses[1];
+        assert!(calendar.is_calendar);
+        assert_eq!(calendar.display_name.as_deref(), Some("Personal & Work"));
+        assert_eq!(calendar.color.as_deref(), Some("#0082c9"));
+        assert_eq!(calendar.components, ["VEVENT", "VTODO"]);
+        assert_eq!(calendar.privileges, ["read", "write"]);
+
+        assert_eq!(
+            responses[2].calendar_data.as_deref(),
+            Some("BEGIN:VCALENDAR\r\nEND:VCALENDAR")
+        );
+    }
+
+    #[test]
+    fn test_parse_principal_discovery() {
+        let root = r#"<?xml version="1.0" encoding="UTF-8"?>
+<multistatus xmlns="DAV:">
+  <response>
+    <href>/dav/</href>
+    <propstat>
+      <prop>
+        <resourcetype><collection/></resourcetype>
+        <current-user-principal><href>/dav/principals/user/jane%40example.com/</href></current-user-principal>
+      </prop>
+      <status>HTTP/1.1 200 OK</status>
+    </propstat>
+    <propstat>
+      <prop><C:calendar-home-set xmlns:C="urn:ietf:params:xml:ns:caldav"/></prop>
+      <status>HTTP/1.1 404 Not Found</status>
+    </propstat>
+  </response>
+</multistatus>"#;
+
+        let responses = parse(root).unwrap();
+        assert_eq!(responses.len(), 1);
+        assert_eq!(responses[0].href, "/dav/");
+        assert_eq!(
+            responses[0].current_user_principal.as_deref(),
+            Some("/dav/principals/user/jane%40example.com/")
+        );
+        assert_eq!(responses[0].calendar_home_set, None);
+        assert!(!responses[0].is_calendar);
+
+        let principal = r#"<?xml version="1.0" encoding="UTF-8"?>
+<D:multistatus xmlns:D="DAV:" xmlns:C="urn:ietf:params:xml:ns:caldav">
+  <D:response>
+    <D:href>/dav/principals/user/jane%40example.com/</D:href>
+    <D:propstat>
+      <D:prop>
+        <C:calendar-home-set>
+          <D:href>/dav/calendars/user/jane%40example.com/</D:href>
+          <D:href>/dav/calendars/shared/</D:href>
+        </C:calendar-home-set>
+      </D:prop>
+      <D:status>HTTP/1.1 200 OK</D:status>
+    </D:propstat>
+  </D:response>
+</D:multistatus>"#;
+
+        let responses = parse(principal).unwrap();
+        assert_eq!(
+            responses[0].calendar_home_set.as_deref(),
+            Some("/dav/calendars/user/jane%40example.com/")
+        );
+        assert_eq!(responses[0].current_user_principal, None);
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_xml() {
+        assert!(parse("<d:multistatus xmlns:d=\"DAV:\"><d:response></d:multistatus>").is_err());
+    }
+}
@@ -0,0 +1,12 @@
+/// A calendar collection that can hold events.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CaldavCalendar {
+    /// Absolute URL of the collection, which also serves as its id.
+    pub url: String,
+    pub display_name: Option<String>,
+    pub description: Option<String>,
+    /// CSS color from the Apple `calendar-color` property, usually `#RRGGBB` or `#RRGGBBAA`.
+    pub color: Option<String>,
+    /// Whether the current user has write access. `None` if the server didn't say.
+    pub can_edit: Option<bool>,
+}
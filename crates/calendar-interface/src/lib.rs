@@ -1,11 +1,17 @@
 use chrono::{DateTime, Utc};
 
+mod meeting;
+
+pub use meeting::{MeetingLink, MeetingPlatform};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
 #[serde(rename_all = "lowercase")]
 pub enum CalendarProviderType {
     Apple,
     Google,
     Outlook,
+    Caldav,
+    Ics,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, specta::Type)]
@@ -43,21 +49,26 @@ pub struct CreateEventInput {
 pub struct CalendarEvent {
     pub provider: CalendarProviderType,
 
-    /// Unique between events. Synthesized for Apple events (eventIdentifier:YYYY-MM-DD for recurring).
+    /// Unique between events. Synthesized for Apple events (eventIdentifier:YYYY-MM-DD for recurring),
+    /// and for recurring CalDAV/ICS events from the occurrence's RECURRENCE-ID
+    /// (UID:YYYYMMDDTHHMMSSZ, or UID:YYYYMMDD for all-day events).
     pub id: String,
     /// Calendar id.
     pub calendar_id: String,
 
     /// iCal identifier used for deduplication.
-    /// Apple: calendarItemExternalIdentifier, Google: iCalUID.
+    /// Apple: calendarItemExternalIdentifier, Google: iCalUID, CalDAV/ICS: UID.
     pub external_id: String,
 
     pub title: String,
     pub description: Option<String>,
     pub location: Option<String>,
     pub url: Option<String>,
-    /// Parsed from notes for Apple, Google provides url directly.
+    /// Join URL. Google and Outlook provide one directly; otherwise `meeting.url`.
     pub meeting_link: Option<String>,
+    /// Platform, meeting id, passcode and dial-in of the event's video meeting, detected from
+    /// conference data, url, location and description for every provider.
+    pub meeting: Option<MeetingLink>,
 
     /// ISO 8601. For Google, start of day for all day events (Apple already does that).
     pub started_at: String,
@@ -83,7 +94,7 @@ pub struct CalendarEvent {
     /// (same across all occurrences of a recurring event).
     pub recurring_event_id: Option<String>,
 
-    /// Raw data. JSON for both Apple and Google.
+    /// Raw data. JSON for both Apple and Google, the VEVENT text for CalDAV/ICS.
     pub raw: String,
 }
 
@@ -0,0 +1,329 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+use url::Url;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+#[serde(rename_all = "snake_case")]
+pub enum MeetingPlatform {
+    Zoom,
+    GoogleMeet,
+    Teams,
+    Webex,
+    Whereby,
+    Jitsi,
+    Chime,
+    SlackHuddle,
+}
+
+/// A video meeting recognized in an event's conference data, url, location or description.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct MeetingLink {
+    pub platform: MeetingPlatform,
+    /// Join URL, as written in the event.
+    pub url: String,
+    /// Zoom/Teams/Webex/Chime meeting number without spaces, the Meet code, or the room name
+    /// for Whereby and Jitsi.
+    pub meeting_id: Option<String>,
+    /// Passcode or dial-in PIN, when the invite spells it out.
+    pub passcode: Option<String>,
+    /// First dial-in phone number, e.g. `+1 669 444 9171`.
+    pub dial_in: Option<String>,
+}
+
+static URL_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"https?://[^\s<>"'()\[\]{}]+"#).unwrap());
+
+static MEET_CODE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[a-z]{3}-[a-z]{4}-[a-z]{3}$").unwrap());
+
+static MEETING_ID_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)\b(?:meeting|conference|webinar)\s*(?:id|number|code)\b(?:\s*\(access code\))?\s*[:#]?\s*(\d[\d ]{5,}\d)",
+    )
+    .unwrap()
+});
+
+static PASSCODE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(?:passcode|password|pin)\b\s*:?\s*([A-Za-z0-9]{3,})").unwrap()
+});
+
+static TEL_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)\btel:([+0-9%().\-]+)(?:;(\d+))?").unwrap());
+
+static PHONE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\+\d[\d \-().]{6,}\d").unwrap());
+
+impl MeetingLink {
+    /// The first meeting link in `text`, with details taken from the rest of it.
+    pub fn parse(text: &str) -> Option<Self> {
+        Self::find([text])
+    }
+
+    /// The first meeting link across `texts`, which should come in priority order (for example
+    /// the provider's join URL, then location, then description). Meeting id, passcode and
+    /// dial-in are filled from all of them, since invites often keep those apart from the link.
+    pub fn find<'a>(texts: impl IntoIterator<Item = &'a str>) -> Option<Self> {
+        let texts: Vec<&str> = texts.into_iter().collect();
+
+        let mut link = texts.iter().find_map(|text| {
+            URL_REGEX
+                .find_iter(text)
+                .find_map(|m| Self::from_url(m.as_str()))
+        })?;
+        for text in texts {
+            link.fill_from_text(text);
+        }
+
+        Some(link)
+    }
+
+    /// Recognizes a single join URL. Outlook Safe Links and Google redirects are unwrapped.
+    pub fn from_url(url: &str) -> Option<Self> {
+        let url = url
+            .trim_end_matches(['.', ',', ';', ':', '!', '?'])
+            .replace("&amp;", "&");
+        let parsed = Url::parse(&url).ok()?;
+        let host = parsed.host_str()?.to_ascii_lowercase();
+
+        if let Some(target) = redirect_target(&parsed, &host) {
+            return Self::from_url(&target);
+        }
+
+        let segments: Vec<&str> = parsed
+            .path_segments()
+            .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+            .unwrap_or_default();
+        let query = |key: &str| {
+            parsed
+                .query_pairs()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.into_owned())
+                .filter(|v| !v.is_empty())
+        };
+        let is = |domain: &str| host == domain || host.ends_with(&format!(".{domain}"));
+
+        let (platform, meeting_id, passcode) = if is("zoom.us") || is("zoomgov.com") {
+            let meeting_id = match segments.first().copied()? {
+                "j" | "w" | "s" | "wc" => segments
+                    .iter()
+                    .find(|s| s.chars().all(|c| c.is_ascii_digit()))
+                    .map(|s| s.to_string()),
+                "my" => segments.get(1).map(|s| s.to_string()),
+                _ => return None,
+            };
+            (MeetingPlatform::Zoom, meeting_id, None)
+        } else if host == "meet.google.com" {
+            let code = segments.first()?;
+            let meeting_id = MEET_CODE_REGEX.is_match(code).then(|| code.to_string());
+            (MeetingPlatform::GoogleMeet, meeting_id, None)
+        } else if is("teams.microsoft.com") || is("teams.live.com") || is("teams.microsoft.us") {
+            let meeting_id = match segments.as_slice() {
+                ["meet", id, ..] => Some(id.to_string()),
+                ["l", "meetup-join", ..] => None,
+                _ => return None,
+            };
+            (MeetingPlatform::Teams, meeting_id, query("p"))
+        } else if is("webex.com") {
+            let meeting_id = match segments.as_slice() {
+                [.., "meet", room] => Some(room.to_string()),
+                _ if parsed.path().contains("j.php") || parsed.path().contains("/wbxmjs/") => None,
+                _ => return None,
+            };
+            (MeetingPlatform::Webex, meeting_id, None)
+        } else if is("whereby.com") {
+            let [room] = segments.as_slice() else {
+                return None;
+            };
+            (MeetingPlatform::Whereby, Some(room.to_string()), None)
+        } else if host == "meet.jit.si" || host == "8x8.vc" {
+            let room = segments.last()?;
+            (MeetingPlatform::Jitsi, Some(room.to_string()), None)
+        } else if host == "chime.aws" || host == "app.chime.aws" {
+            let meeting_id = segments
+                .iter()
+                .find(|s| s.chars().all(|c| c.is_ascii_digit()))
+                .map(|s| s.to_string())
+                .or_else(|| query("pin"));
+            (MeetingPlatform::Chime, Some(meeting_id?), None)
+        } else if host == "app.slack.com" {
+            let meeting_id = match segments.as_slice() {
+                ["huddle", _, channel, ..] => Some(channel.to_string()),
+                ["huddle", ..] => None,
+                _ => return None,
+            };
+            (MeetingPlatform::SlackHuddle, meeting_id, None)
+        } else {
+            return None;
+        };
+
+        Some(Self {
+            platform,
+            url,
+            meeting_id,
+            passcode,
+            dial_in: None,
+        })
+    }
+
+    /// Fills whichever of meeting id, passcode and dial-in are still missing from invite text,
+    /// e.g. `Meeting ID: 867 4631 3244` and `Passcode: 291681`.
+    pub fn fill_from_text(&mut self, text: &str) {
+        let text = text
+            .replace("&nbsp;", " ")
+            .replace("&#160;", " ")
+            .replace("&amp;", "&");
+
+        if self.meeting_id.is_none() {
+            self.meeting_id = MEETING_ID_REGEX
+                .captures(&text)
+                .map(|c| c[1].replace(' ', ""));
+        }
+        // `tel:+1-650-817-8427;205595809#` carries the dial-in PIN after the number.
+        let tel = TEL_REGEX.captures(&text);
+        if self.passcode.is_none() {
+            // Requiring a digit skips prose such as "no password needed".
+            self.passcode = PASSCODE_REGEX
+                .captures_iter(&text)
+                .map(|c| c[1].to_string())
+                .find(|value| value.chars().any(|c| c.is_ascii_digit()))
+                .or_else(|| tel.as_ref()?.get(2).map(|pin| pin.as_str().to_string()));
+        }
+        if self.dial_in.is_none() {
+            self.dial_in = tel
+                .and_then(|c| urlencoding::decode(&c[1]).ok().map(|s| s.into_owned()))
+                .or_else(|| PHONE_REGEX.find(&text).map(|m| m.as_str().to_string()))
+                .map(|number| number.trim().to_string())
+                .filter(|number| number.chars().filter(char::is_ascii_digit).count() >= 7);
+        }
+    }
+}
+
+/// Links rewritten by Outlook Safe Links or Google's redirector point at the real URL in a
+/// query parameter.
+fn redirect_target(url: &Url, host: &str) -> Option<String> {
+    let key = if host.ends_with("safelinks.protection.outlook.com") {
+        "url"
+    } else if (host == "www.google.com" || host == "google.com") && url.path() == "/url" {
+        "q"
+    } else {
+        return None;
+    };
+
+    url.query_pairs()
+        .find(|(k, _)| k == key)
+        .map(|(_, v)| v.into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_zoom_invite() {
+        let text = "<p>Join Zoom Meeting<br/>https://hyprnote.zoom.us/j/86746313244?pwd=zFIICnVHzPim44QcYGbLCAAqtBrGzx.1<br/><br/>\
+            View meeting insights<br/>https://hyprnote.zoom.us/launch/edl?muid=8fff7a40<br/>\
+            Meeting ID: 867 4631 3244<br/>Passcode: 291681<br/>\
+            One tap mobile<br/>+16694449171,,86746313244#,,,,*291681# US</p>";
+
+        assert_eq!(
+            MeetingLink::parse(text),
+            Some(MeetingLink {
+                platform: MeetingPlatform::Zoom,
+                url: "https://hyprnote.zoom.us/j/86746313244?pwd=zFIICnVHzPim44QcYGbLCAAqtBrGzx.1"
+                    .to_string(),
+                meeting_id: Some("86746313244".to_string()),
+                passcode: Some("291681".to_string()),
+                dial_in: Some("+16694449171".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_meet_dial_in() {
+        let text = "Google Meet으로 참석: https://meet.google.com/xkf-xcmo-rwh\n\
+            또는 다음 전화번호로 전화 걸기: (US) +1 402-732-7278 PIN: 765104423#\n\
+            전화번호 더보기: https://tel.meet/xkf-xcmo-rwh?pin=5171333427182&hs=7";
+
+        let link = MeetingLink::parse(text).unwrap();
+        assert_eq!(link.platform, MeetingPlatform::GoogleMeet);
+        assert_eq!(link.url, "https://meet.google.com/xkf-xcmo-rwh");
+        assert_eq!(link.meeting_id.as_deref(), Some("xkf-xcmo-rwh"));
+        assert_eq!(link.passcode.as_deref(), Some("765104423"));
+        assert_eq!(link.dial_in.as_deref(), Some("+1 402-732-7278"));
+
+        let link = MeetingLink::parse(
+            "https://meet.google.com/xhv-ubut-zph\ntel:+1%20650-817-8427;205595809%23",
+        )
+        .unwrap();
+        assert_eq!(link.dial_in.as_deref(), Some("+1 650-817-8427"));
+        assert_eq!(link.passcode.as_deref(), Some("205595809"));
+    }
+
+    #[test]
+    fn test_find_across_fields() {
+        let location = "Microsoft Teams Meeting";
+        let description = "Join: https://nam11.safelinks.protection.outlook.com/?url=https%3A%2F%2Fteams.microsoft.com%2Fmeet%2F2345678901234%3Fp%3DaB3dE5fG&data=x\n\
+            Meeting ID: 234 567 890 123 4\nPasscode: aB3dE5fG";
+
+        let link = MeetingLink::find([location, description]).unwrap();
+        assert_eq!(link.platform, MeetingPlatform::Teams);
+        assert_eq!(
+            link.url,
+            "https://teams.microsoft.com/meet/2345678901234?p=aB3dE5fG"
+        );
+        assert_eq!(link.meeting_id.as_deref(), Some("2345678901234"));
+        assert_eq!(link.passcode.as_deref(), Some("aB3dE5fG"));
+    }
+
+    #[test]
+    fn test_platforms() {
+        let cases = [
+            (
+                "https://acme.webex.com/acme/j.php?MTID=m1234",
+                MeetingPlatform::Webex,
+                None,
+            ),
+            (
+                "https://acme.webex.com/meet/jane.doe",
+                MeetingPlatform::Webex,
+                Some("jane.doe"),
+            ),
+            (
+                "https://whereby.com/design-review",
+                MeetingPlatform::Whereby,
+                Some("design-review"),
+            ),
+            (
+                "https://meet.jit.si/WeeklySync",
+                MeetingPlatform::Jitsi,
+                Some("WeeklySync"),
+            ),
+            (
+                "https://chime.aws/4567890123",
+                MeetingPlatform::Chime,
+                Some("4567890123"),
+            ),
+            (
+                "https://app.slack.com/huddle/T0123ABCD/C0456EFGH",
+                MeetingPlatform::SlackHuddle,
+                Some("C0456EFGH"),
+            ),
+            (
+                "https://teams.microsoft.com/l/meetup-join/19%3ameeting_abc%40thread.v2/0",
+                MeetingPlatform::Teams,
+                None,
+            ),
+        ];
+
+        for (url, platform, meeting_id) in cases {
+            let link = MeetingLink::from_url(url).unwrap_or_else(|| panic!("{url}"));
+            assert_eq!(link.platform, platform, "{url}");
+            assert_eq!(link.meeting_id.as_deref(), meeting_id, "{url}");
+        }
+
+        assert!(MeetingLink::parse("https://hyprnote.zoom.us/u/kdoIeyBH9b").is_none());
+        assert!(MeetingLink::parse("https://app.cal.com/video/d713v9w1d2krBptPtwUAnJ").is_none());
+        assert!(MeetingLink::parse("https://example.com/meet/standup").is_none());
+    }
+}
@@ -8,7 +8,7 @@ const MAX_URL_LENGTH: usize = 2048;
 const MAX_PARTICIPANTS: usize = 30;
 const MAX_PARTICIPANT_NAME_LENGTH: usize = 80;
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
 struct IncomingMessage {
     #[serde(rename = "type")]
     msg_type: String,
@@ -16,6 +16,8 @@ struct IncomingMessage {
     is_active: Option<bool>,
     muted: Option<bool>,
     participants: Option<Vec<Participant>>,
+    speakers: Option<Vec<String>>,
+    timestamp_ms: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -37,6 +39,89 @@ struct MeetingState {
     is_active: bool,
     muted: bool,
     participants: Vec<Participant>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    speaker_timeline: Option<PathBuf>,
+}
+
+/// One line of a session's speaker timeline: who is speaking from `timestamp_ms` until the
+/// next line. An empty `speakers` list means nobody is.
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct ActiveSpeakerEvent {
+    timestamp_ms: u64,
+    speakers: Vec<String>,
+}
+
+/// Appends active-speaker changes for one meeting to
+/// `chrome_speakers/<started_at_ms>-<meeting code>.jsonl` next to the state file.
+struct SpeakerTimeline {
+    url: String,
+    path: PathBuf,
+    last: Option<ActiveSpeakerEvent>,
+}
+
+impl SpeakerTimeline {
+    fn new(url: &str, state_path: &Path, started_at_ms: u64) -> Self {
+        let code: String = url
+            .trim_start_matches("https://meet.google.com/")
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '-')
+            .collect();
+        let file_name = if code.is_empty() {
+            format!("{started_at_ms}.jsonl")
+        } else {
+            format!("{started_at_ms}-{code}.jsonl")
+        };
+
+        Self {
+            url: url.to_owned(),
+            path: state_path.with_file_name("chrome_speakers").join(file_name),
+            last: None,
+        }
+    }
+
+    fn record(&mut self, timestamp_ms: u64, speakers: Vec<String>) -> io::Result<()> {
+        if self
+            .last
+            .as_ref()
+            .is_some_and(|last| last.speakers == speakers)
+        {
+            return Ok(());
+        }
+        if self.last.is_none() && speakers.is_empty() {
+            return Ok(());
+        }
+
+        // Events can arrive slightly out of order; keep the file monotonic.
+        let timestamp_ms = self
+            .last
+            .as_ref()
+            .map_or(timestamp_ms, |last| timestamp_ms.max(last.timestamp_ms));
+        let event = ActiveSpeakerEvent {
+            timestamp_ms,
+            speakers,
+        };
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let mut line = serde_json::to_vec(&event)?;
+        line.push(b'\n');
+        file.write_all(&line)?;
+
+        self.last = Some(event);
+        Ok(())
+    }
+
+    /// Ends the last turn so it does not stretch past the meeting.
+    fn close(mut self, timestamp_ms: u64) {
+        if let Err(e) = self.record(timestamp_ms, Vec::new()) {
+            eprintln!("failed to write speaker timeline: {e}");
+        }
+    }
 }
 
 fn default_state_path() -> io::Result<PathBuf> {
@@ -70,6 +155,11 @@ fn read_message(reader: &mut impl Read) -> io::Result<Option<Vec<u8>>> {
 enum ProcessedMessage {
     Ignore,
     Update(Option<MeetingState>),
+    ActiveSpeakers {
+        url: String,
+        timestamp_ms: Option<u64>,
+        speakers: Vec<String>,
+    },
 }
 
 fn normalize_url(url: Option<String>) -> Option<String> {
@@ -104,6 +194,25 @@ fn normalize_participants(participants: Option<Vec<Participant>>) -> Vec<Partici
         .collect()
 }
 
+fn normalize_speakers(speakers: Option<Vec<String>>) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for speaker in speakers.unwrap_or_default() {
+        let name = speaker.trim();
+        if name.is_empty() || name.len() > MAX_PARTICIPANT_NAME_LENGTH {
+            continue;
+        }
+        if normalized.iter().any(|existing| existing == name) {
+            continue;
+        }
+
+        normalized.push(name.to_owned());
+        if normalized.len() >= MAX_PARTICIPANTS {
+            break;
+        }
+    }
+    normalized
+}
+
 fn process_message(msg: IncomingMessage) -> ProcessedMessage {
     match msg.msg_type.as_str() {
         "meeting_state" => {
@@ -120,6 +229,7 @@ fn process_message(msg: IncomingMessage) -> ProcessedMessage {
                 is_active: true,
                 muted: msg.muted.unwrap_or(false),
                 participants: normalize_participants(msg.participants),
+                speaker_timeline: None,
             }))
         }
         "meeting_ended" => {
@@ -129,6 +239,17 @@ fn process_message(msg: IncomingMessage) -> ProcessedMessage {
 
             ProcessedMessage::Update(None)
         }
+        "active_speaker" => {
+            let Some(url) = normalize_url(msg.url) else {
+                return ProcessedMessage::Ignore;
+            };
+
+            ProcessedMessage::ActiveSpeakers {
+                url,
+                timestamp_ms: msg.timestamp_ms,
+                speakers: normalize_speakers(msg.speakers),
+            }
+        }
         _ => ProcessedMessage::Ignore,
     }
 }
@@ -153,6 +274,8 @@ fn timestamp_ms() -> u64 {
 }
 
 fn run(reader: &mut impl Read, state_path: &Path) {
+    let mut timeline: Option<SpeakerTimeline> = None;
+
     loop {
         match read_message(reader) {
             Ok(Some(data)) => {
@@ -161,11 +284,46 @@ fn run(reader: &mut impl Read, state_path: &Path) {
                     Err(_) => continue,
                 };
 
-                let meeting = match process_message(msg) {
+                let mut meeting = match process_message(msg) {
                     ProcessedMessage::Ignore => continue,
                     ProcessedMessage::Update(meeting) => meeting,
+                    ProcessedMessage::ActiveSpeakers {
+                        url,
+                        timestamp_ms: at,
+                        speakers,
+                    } => {
+                        // Speakers are only recorded against the meeting the state file reports.
+                        if let Some(timeline) = timeline.as_mut().filter(|t| t.url == url)
+                            && let Err(e) =
+                                timeline.record(at.unwrap_or_else(timestamp_ms), speakers)
+                        {
+                            eprintln!("failed to write speaker timeline: {e}");
+                        }
+                        continue;
+                    }
                 };
 
+                match meeting.as_mut() {
+                    Some(meeting) => {
+                        if timeline.as_ref().is_none_or(|t| t.url != meeting.url) {
+                            if let Some(previous) = timeline.take() {
+                                previous.close(timestamp_ms());
+                            }
+                            timeline = Some(SpeakerTimeline::new(
+                                &meeting.url,
+                                state_path,
+                                timestamp_ms(),
+                            ));
+                        }
+                        meeting.speaker_timeline = timeline.as_ref().map(|t| t.path.clone());
+                    }
+                    None => {
+                        if let Some(previous) = timeline.take() {
+                            previous.close(timestamp_ms());
+                        }
+                    }
+                }
+
                 let state = ChromeState {
                     version: 1,
                     timestamp_ms: timestamp_ms(),
@@ -183,6 +341,10 @@ fn run(reader: &mut impl Read, state_path: &Path) {
             }
         }
     }
+
+    if let Some(timeline) = timeline {
+        timeline.close(timestamp_ms());
+    }
 }
 
 fn main() {
@@ -261,6 +423,7 @@ mod tests {
                 name: "Alice".into(),
                 is_self: true,
             }]),
+            ..Default::default()
         };
         let result = process_message(msg);
         match result {
@@ -282,6 +445,7 @@ mod tests {
             is_active: Some(false),
             muted: None,
             participants: None,
+            ..Default::default()
         };
         assert_eq!(process_message(msg), ProcessedMessage::Update(None));
     }
@@ -294,6 +458,7 @@ mod tests {
             is_active: Some(false),
             muted: Some(false),
             participants: None,
+            ..Default::default()
         };
         assert_eq!(process_message(msg), ProcessedMessage::Update(None));
     }
@@ -306,6 +471,7 @@ mod tests {
             is_active: Some(true),
             muted: None,
             participants: None,
+            ..Default::default()
         };
         let result = process_message(msg);
         match result {
@@ -324,6 +490,7 @@ mod tests {
             is_active: Some(true),
             muted: Some(false),
             participants: None,
+            ..Default::default()
         };
         assert_eq!(process_message(msg), ProcessedMessage::Ignore);
     }
@@ -336,6 +503,7 @@ mod tests {
             is_active: Some(true),
             muted: Some(false),
             participants: None,
+            ..Default::default()
         };
         assert_eq!(process_message(msg), ProcessedMessage::Ignore);
     }
@@ -357,6 +525,7 @@ mod tests {
                     is_self: false,
                 },
             ]),
+            ..Default::default()
         };
 
         let result = process_message(msg);
@@ -369,6 +538,41 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_process_active_speaker_is_sanitized() {
+        let msg = IncomingMessage {
+            msg_type: "active_speaker".into(),
+            url: Some("https://meet.google.com/abc".into()),
+            speakers: Some(vec![
+                "  Alice ".into(),
+                "".into(),
+                "Alice".into(),
+                "Bob".into(),
+            ]),
+            timestamp_ms: Some(1_000),
+            ..Default::default()
+        };
+        assert_eq!(
+            process_message(msg),
+            ProcessedMessage::ActiveSpeakers {
+                url: "https://meet.google.com/abc".into(),
+                timestamp_ms: Some(1_000),
+                speakers: vec!["Alice".into(), "Bob".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_process_active_speaker_invalid_url_is_ignored() {
+        let msg = IncomingMessage {
+            msg_type: "active_speaker".into(),
+            url: Some("https://example.com/abc".into()),
+            speakers: Some(vec!["Alice".into()]),
+            ..Default::default()
+        };
+        assert_eq!(process_message(msg), ProcessedMessage::Ignore);
+    }
+
     // --- write_state + full round-trip ---
 
     #[test]
@@ -384,6 +588,7 @@ mod tests {
                 is_active: true,
                 muted: false,
                 participants: vec![],
+                speaker_timeline: None,
             }),
         };
 
@@ -484,4 +689,67 @@ mod tests {
         let parsed: serde_json::Value = serde_json::from_str(&contents).unwrap();
         assert_eq!(parsed["meeting"]["url"], "https://meet.google.com/xyz");
     }
+
+    fn read_timeline(path: &Path) -> Vec<ActiveSpeakerEvent> {
+        std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_run_records_speaker_timeline() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_path = dir.path().join("chrome_state.json");
+
+        let active = r#"{"type":"meeting_state","url":"https://meet.google.com/abc-defg-hij","is_active":true,"muted":false,"participants":[]}"#;
+        let alice = r#"{"type":"active_speaker","url":"https://meet.google.com/abc-defg-hij","speakers":["Alice"],"timestamp_ms":1000}"#;
+        let alice_again = r#"{"type":"active_speaker","url":"https://meet.google.com/abc-defg-hij","speakers":["Alice"],"timestamp_ms":1500}"#;
+        let other_meeting = r#"{"type":"active_speaker","url":"https://meet.google.com/zzz-zzzz-zzz","speakers":["Mallory"],"timestamp_ms":1600}"#;
+        let bob = r#"{"type":"active_speaker","url":"https://meet.google.com/abc-defg-hij","speakers":["Bob"],"timestamp_ms":2000}"#;
+
+        let mut input = Vec::new();
+        for msg in [active, alice, alice_again, other_meeting, bob] {
+            input.extend(encode_message(msg));
+        }
+
+        let mut cursor = Cursor::new(input);
+        run(&mut cursor, &state_path);
+
+        let contents = std::fs::read_to_string(&state_path).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&contents).unwrap();
+        let timeline_path = PathBuf::from(parsed["meeting"]["speaker_timeline"].as_str().unwrap());
+        assert!(
+            timeline_path
+                .to_string_lossy()
+                .ends_with("-abc-defg-hij.jsonl")
+        );
+
+        let events = read_timeline(&timeline_path);
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            events[0],
+            ActiveSpeakerEvent {
+                timestamp_ms: 1000,
+                speakers: vec!["Alice".into()],
+            }
+        );
+        assert_eq!(events[1].speakers, vec!["Bob".to_string()]);
+        // Input ended, so the last turn is closed.
+        assert!(events[2].speakers.is_empty());
+    }
+
+    #[test]
+    fn test_run_active_speaker_without_meeting_is_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_path = dir.path().join("chrome_state.json");
+
+        let alice = r#"{"type":"active_speaker","url":"https://meet.google.com/abc-defg-hij","speakers":["Alice"]}"#;
+        let mut cursor = Cursor::new(encode_message(alice));
+        run(&mut cursor, &state_path);
+
+        assert!(!state_path.exists());
+        assert!(!dir.path().join("chrome_speakers").exists());
+    }
 }
@@ -100,6 +100,7 @@ fn parse_store(store: &Value) -> Result<Collection> {
         enhanced_notes,
         tags,
         tag_mappings,
+        attachments: vec![],
     })
 }
 
@@ -10,6 +10,7 @@
 //! - Response caching for reproducibility
 //! - Progress tracking
 //! - OpenRouter API integration
+//! - Speech-to-text accuracy metrics (WER, CER, DER) in [`stt`]
 //!
 //! ## Quick Start
 //!
@@ -48,6 +49,7 @@ mod submission;
 mod testing;
 
 pub mod constants;
+pub mod stt;
 
 #[cfg(test)]
 pub use testing::*;
@@ -0,0 +1,185 @@
+const FRAME_MS: u64 = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpeakerSegment {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub speaker: String,
+}
+
+/// Time attributed to each kind of diarization error, in milliseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiarizationError {
+    pub missed_ms: u64,
+    pub false_alarm_ms: u64,
+    pub confusion_ms: u64,
+    /// Total reference speaker time; overlapped speech counts once per speaker.
+    pub reference_ms: u64,
+}
+
+impl DiarizationError {
+    pub fn errors_ms(&self) -> u64 {
+        self.missed_ms + self.false_alarm_ms + self.confusion_ms
+    }
+
+    pub fn rate(&self) -> f64 {
+        if self.reference_ms == 0 {
+            return if self.errors_ms() == 0 { 0.0 } else { 1.0 };
+        }
+        self.errors_ms() as f64 / self.reference_ms as f64
+    }
+}
+
+impl std::ops::AddAssign for DiarizationError {
+    fn add_assign(&mut self, other: Self) {
+        self.missed_ms += other.missed_ms;
+        self.false_alarm_ms += other.false_alarm_ms;
+        self.confusion_ms += other.confusion_ms;
+        self.reference_ms += other.reference_ms;
+    }
+}
+
+/// Frame-level DER without a forgiveness collar.
+///
+/// Speaker labels are arbitrary on both sides, so each reference speaker is first mapped to
+/// the hypothesis speaker it overlaps most (one-to-one, largest overlaps first).
+pub fn diarization_error_rate(
+    reference: &[SpeakerSegment],
+    hypothesis: &[SpeakerSegment],
+) -> DiarizationError {
+    let (ref_count, ref_frames) = rasterize(reference);
+    let (hyp_count, hyp_frames) = rasterize(hypothesis);
+    let frame_count = ref_frames.len().max(hyp_frames.len());
+    let at = |frames: &[Vec<usize>], i: usize| frames.get(i).cloned().unwrap_or_default();
+
+    let mut overlap = vec![vec![0u64; hyp_count]; ref_count];
+    for i in 0..frame_count {
+        for &r in &at(&ref_frames, i) {
+            for &h in &at(&hyp_frames, i) {
+                overlap[r][h] += 1;
+            }
+        }
+    }
+    let mapping = map_speakers(&overlap);
+
+    let mut error = DiarizationError::default();
+    for i in 0..frame_count {
+        let (speakers, guesses) = (at(&ref_frames, i), at(&hyp_frames, i));
+        let correct = speakers
+            .iter()
+            .filter(|&&r| mapping[r].is_some_and(|h| guesses.contains(&h)))
+            .count();
+
+        error.reference_ms += speakers.len() as u64 * FRAME_MS;
+        error.missed_ms += speakers.len().saturating_sub(guesses.len()) as u64 * FRAME_MS;
+        error.false_alarm_ms += guesses.len().saturating_sub(speakers.len()) as u64 * FRAME_MS;
+        error.confusion_ms += (speakers.len().min(guesses.len()) - correct) as u64 * FRAME_MS;
+    }
+    error
+}
+
+/// Returns the number of distinct speakers and, per frame, the speakers active in it.
+fn rasterize(segments: &[SpeakerSegment]) -> (usize, Vec<Vec<usize>>) {
+    let mut speakers: Vec<&str> = Vec::new();
+    let mut frames: Vec<Vec<usize>> = Vec::new();
+
+    for segment in segments {
+        let index = match speakers.iter().position(|s| *s == segment.speaker) {
+            Some(index) => index,
+            None => {
+                speakers.push(&segment.speaker);
+                speakers.len() - 1
+            }
+        };
+
+        let start = ((segment.start_ms + FRAME_MS / 2) / FRAME_MS) as usize;
+        let end = ((segment.end_ms + FRAME_MS / 2) / FRAME_MS) as usize;
+        if frames.len() < end {
+            frames.resize(end, Vec::new());
+        }
+        for frame in frames.iter_mut().take(end).skip(start) {
+            if !frame.contains(&index) {
+                frame.push(index);
+            }
+        }
+    }
+
+    (speakers.len(), frames)
+}
+
+fn map_speakers(overlap: &[Vec<u64>]) -> Vec<Option<usize>> {
+    let mut pairs: Vec<(u64, usize, usize)> = overlap
+        .iter()
+        .enumerate()
+        .flat_map(|(r, row)| {
+            row.iter()
+                .enumerate()
+                .filter(|(_, &frames)| frames > 0)
+                .map(move |(h, &frames)| (frames, r, h))
+        })
+        .collect();
+    pairs.sort_by_key(|&(frames, _, _)| std::cmp::Reverse(frames));
+
+    let mut mapping = vec![None; overlap.len()];
+    let mut taken = Vec::new();
+    for (_, r, h) in pairs {
+        if mapping[r].is_none() && !taken.contains(&h) {
+            mapping[r] = Some(h);
+            taken.push(h);
+        }
+    }
+    mapping
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seg(start_ms: u64, end_ms: u64, speaker: &str) -> SpeakerSegment {
+        SpeakerSegment {
+            start_ms,
+            end_ms,
+            speaker: speaker.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_der_ignores_label_names() {
+        let reference = [seg(0, 1000, "alice"), seg(1000, 2000, "bob")];
+        let hypothesis = [seg(0, 1000, "1"), seg(1000, 2000, "0")];
+        let error = diarization_error_rate(&reference, &hypothesis);
+        assert_eq!(error.errors_ms(), 0);
+        assert_eq!(error.reference_ms, 2000);
+    }
+
+    #[test]
+    fn test_der_breakdown() {
+        let reference = [seg(0, 1000, "alice"), seg(1000, 2000, "bob")];
+        let hypothesis = [
+            seg(0, 1500, "0"),
+            seg(1500, 1800, "1"),
+            seg(2000, 2500, "1"),
+        ];
+        let error = diarization_error_rate(&reference, &hypothesis);
+        assert_eq!(
+            error,
+            DiarizationError {
+                missed_ms: 200,
+                false_alarm_ms: 500,
+                confusion_ms: 500,
+                reference_ms: 2000,
+            }
+        );
+        assert!((error.rate() - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_der_overlapping_speech() {
+        let reference = [seg(0, 1000, "alice"), seg(500, 1000, "bob")];
+        let hypothesis = [seg(0, 1000, "a")];
+        let error = diarization_error_rate(&reference, &hypothesis);
+        assert_eq!(error.reference_ms, 1500);
+        assert_eq!(error.missed_ms, 500);
+        assert_eq!(error.confusion_ms, 0);
+    }
+}
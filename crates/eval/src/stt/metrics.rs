@@ -0,0 +1,135 @@
+use super::normalize_text;
+
+/// Edit operations aligning a hypothesis to its reference.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ErrorCounts {
+    pub substitutions: usize,
+    pub deletions: usize,
+    pub insertions: usize,
+    pub reference_len: usize,
+}
+
+impl ErrorCounts {
+    pub fn errors(&self) -> usize {
+        self.substitutions + self.deletions + self.insertions
+    }
+
+    /// Errors per reference token. Exceeds 1.0 when the hypothesis inserts a lot.
+    pub fn rate(&self) -> f64 {
+        if self.reference_len == 0 {
+            return if self.errors() == 0 { 0.0 } else { 1.0 };
+        }
+        self.errors() as f64 / self.reference_len as f64
+    }
+}
+
+impl std::ops::AddAssign for ErrorCounts {
+    fn add_assign(&mut self, other: Self) {
+        self.substitutions += other.substitutions;
+        self.deletions += other.deletions;
+        self.insertions += other.insertions;
+        self.reference_len += other.reference_len;
+    }
+}
+
+/// Word error rate over normalized words.
+pub fn word_error_rate(reference: &str, hypothesis: &str) -> ErrorCounts {
+    align(&normalize_text(reference), &normalize_text(hypothesis))
+}
+
+/// Character error rate over normalized words joined by single spaces.
+pub fn character_error_rate(reference: &str, hypothesis: &str) -> ErrorCounts {
+    let chars = |text: &str| -> Vec<char> { normalize_text(text).join(" ").chars().collect() };
+    align(&chars(reference), &chars(hypothesis))
+}
+
+/// Levenshtein alignment that keeps the operation breakdown of the cheapest path.
+fn align<T: PartialEq>(reference: &[T], hypothesis: &[T]) -> ErrorCounts {
+    let insertions_only = |n: usize| ErrorCounts {
+        insertions: n,
+        ..Default::default()
+    };
+
+    let mut prev: Vec<ErrorCounts> = (0..=hypothesis.len()).map(insertions_only).collect();
+    let mut curr = vec![ErrorCounts::default(); hypothesis.len() + 1];
+
+    for (i, r) in reference.iter().enumerate() {
+        curr[0] = ErrorCounts {
+            deletions: i + 1,
+            ..Default::default()
+        };
+
+        for (j, h) in hypothesis.iter().enumerate() {
+            let mut diagonal = prev[j];
+            if r != h {
+                diagonal.substitutions += 1;
+            }
+            let mut deletion = prev[j + 1];
+            deletion.deletions += 1;
+            let mut insertion = curr[j];
+            insertion.insertions += 1;
+
+            curr[j + 1] = [diagonal, deletion, insertion]
+                .into_iter()
+                .min_by_key(ErrorCounts::errors)
+                .expect("three candidates");
+        }
+
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    ErrorCounts {
+        reference_len: reference.len(),
+        ..prev[hypothesis.len()]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_error_rate() {
+        let counts = word_error_rate("The cat sat on the mat.", "the cat sat on a hat mat");
+        assert_eq!(
+            counts,
+            ErrorCounts {
+                substitutions: 1,
+                deletions: 0,
+                insertions: 1,
+                reference_len: 6,
+            }
+        );
+        assert!((counts.rate() - 2.0 / 6.0).abs() < 1e-9);
+
+        let counts = word_error_rate("hello big world", "hello");
+        assert_eq!(counts.deletions, 2);
+        assert_eq!(counts.errors(), 2);
+    }
+
+    #[test]
+    fn test_word_error_rate_ignores_case_and_punctuation() {
+        assert_eq!(word_error_rate("Hello, World!", "hello world").errors(), 0);
+    }
+
+    #[test]
+    fn test_character_error_rate() {
+        let counts = character_error_rate("kitten", "sitting");
+        assert_eq!(counts.errors(), 3);
+        assert_eq!(counts.reference_len, 6);
+    }
+
+    #[test]
+    fn test_empty_reference() {
+        assert_eq!(word_error_rate("", "").rate(), 0.0);
+        assert_eq!(word_error_rate("", "noise").rate(), 1.0);
+    }
+
+    #[test]
+    fn test_add_assign() {
+        let mut total = word_error_rate("a b", "a c");
+        total += word_error_rate("d e f", "d e f");
+        assert_eq!(total.errors(), 1);
+        assert_eq!(total.reference_len, 5);
+    }
+}
@@ -0,0 +1,223 @@
+//! Transcription quality metrics: word/character error rate after normalization, and
+//! diarization error rate.
+
+mod diarization;
+mod metrics;
+mod normalize;
+
+pub use diarization::{DiarizationError, SpeakerSegment, diarization_error_rate};
+pub use metrics::{ErrorCounts, character_error_rate, word_error_rate};
+pub use normalize::normalize_text;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SttMode {
+    Batch,
+    Live,
+}
+
+impl SttMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SttMode::Batch => "batch",
+            SttMode::Live => "live",
+        }
+    }
+}
+
+/// A reference recording with its ground truth.
+#[derive(Debug, Clone)]
+pub struct SttSample {
+    pub id: String,
+    pub audio_path: std::path::PathBuf,
+    pub transcript: String,
+    /// When the first reference word starts; live latency is measured from here.
+    pub first_word_ms: u64,
+    pub speakers: Vec<SpeakerSegment>,
+}
+
+/// What a provider returned for one sample.
+#[derive(Debug, Clone, Default)]
+pub struct SttOutput {
+    pub transcript: String,
+    pub speakers: Vec<SpeakerSegment>,
+    /// Request time in batch mode, session time in live mode.
+    pub elapsed_ms: u64,
+    /// Live mode only: from the first spoken word reaching the provider to it being
+    /// transcribed.
+    pub first_word_latency_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone)]
+pub struct SttResult {
+    pub sample_id: String,
+    pub provider: String,
+    pub mode: SttMode,
+    pub transcript: String,
+    pub wer: ErrorCounts,
+    pub cer: ErrorCounts,
+    /// Only when both the reference and the provider label speakers.
+    pub der: Option<DiarizationError>,
+    pub elapsed_ms: u64,
+    pub first_word_latency_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl SttResult {
+    pub fn score(sample: &SttSample, provider: &str, mode: SttMode, output: SttOutput) -> Self {
+        let der = (!sample.speakers.is_empty() && !output.speakers.is_empty())
+            .then(|| diarization_error_rate(&sample.speakers, &output.speakers));
+
+        Self {
+            sample_id: sample.id.clone(),
+            provider: provider.to_string(),
+            mode,
+            wer: word_error_rate(&sample.transcript, &output.transcript),
+            cer: character_error_rate(&sample.transcript, &output.transcript),
+            der,
+            elapsed_ms: output.elapsed_ms,
+            first_word_latency_ms: output.first_word_latency_ms,
+            transcript: output.transcript,
+            error: None,
+        }
+    }
+
+    pub fn failed(sample: &SttSample, provider: &str, mode: SttMode, error: String) -> Self {
+        Self {
+            sample_id: sample.id.clone(),
+            provider: provider.to_string(),
+            mode,
+            transcript: String::new(),
+            wer: ErrorCounts::default(),
+            cer: ErrorCounts::default(),
+            der: None,
+            elapsed_ms: 0,
+            first_word_latency_ms: None,
+            error: Some(error),
+        }
+    }
+}
+
+/// Corpus-level numbers for one provider and mode. Error rates pool the counts of every
+/// successful sample, so long recordings weigh more than short ones.
+#[derive(Debug, Clone)]
+pub struct SttSummary {
+    pub provider: String,
+    pub mode: SttMode,
+    pub samples: usize,
+    pub failed: usize,
+    pub wer: ErrorCounts,
+    pub cer: ErrorCounts,
+    pub der: Option<DiarizationError>,
+    pub mean_elapsed_ms: Option<u64>,
+    pub mean_first_word_latency_ms: Option<u64>,
+}
+
+pub fn summarize(results: &[SttResult]) -> Vec<SttSummary> {
+    let mut keys: Vec<(&str, SttMode)> = results
+        .iter()
+        .map(|r| (r.provider.as_str(), r.mode))
+        .collect();
+    keys.sort();
+    keys.dedup();
+
+    keys.into_iter()
+        .map(|(provider, mode)| {
+            let group: Vec<&SttResult> = results
+                .iter()
+                .filter(|r| r.provider == provider && r.mode == mode)
+                .collect();
+            let ok: Vec<&SttResult> = group
+                .iter()
+                .copied()
+                .filter(|r| r.error.is_none())
+                .collect();
+
+            let mut wer = ErrorCounts::default();
+            let mut cer = ErrorCounts::default();
+            let mut der: Option<DiarizationError> = None;
+            for r in &ok {
+                wer += r.wer;
+                cer += r.cer;
+                if let Some(d) = r.der {
+                    *der.get_or_insert_with(Default::default) += d;
+                }
+            }
+
+            SttSummary {
+                provider: provider.to_string(),
+                mode,
+                samples: group.len(),
+                failed: group.len() - ok.len(),
+                wer,
+                cer,
+                der,
+                mean_elapsed_ms: mean(ok.iter().map(|r| r.elapsed_ms)),
+                mean_first_word_latency_ms: mean(ok.iter().filter_map(|r| r.first_word_latency_ms)),
+            }
+        })
+        .collect()
+}
+
+fn mean(values: impl Iterator<Item = u64>) -> Option<u64> {
+    let (sum, count) = values.fold((0u64, 0u64), |(sum, count), v| (sum + v, count + 1));
+    (count > 0).then(|| sum / count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(id: &str, transcript: &str) -> SttSample {
+        SttSample {
+            id: id.to_string(),
+            audio_path: std::path::PathBuf::new(),
+            transcript: transcript.to_string(),
+            first_word_ms: 0,
+            speakers: Vec::new(),
+        }
+    }
+
+    fn output(transcript: &str, latency: Option<u64>) -> SttOutput {
+        SttOutput {
+            transcript: transcript.to_string(),
+            elapsed_ms: 1000,
+            first_word_latency_ms: latency,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_summarize_pools_counts_per_provider() {
+        let short = sample("short", "one two");
+        let long = sample("long", "a b c d e f g h");
+
+        let results = vec![
+            SttResult::score(&short, "deepgram", SttMode::Live, output("one", Some(300))),
+            SttResult::score(
+                &long,
+                "deepgram",
+                SttMode::Live,
+                output("a b c d e f g h", None),
+            ),
+            SttResult::failed(&long, "soniox", SttMode::Live, "timeout".to_string()),
+            SttResult::score(&short, "deepgram", SttMode::Batch, output("one two", None)),
+        ];
+
+        let summaries = summarize(&results);
+        assert_eq!(summaries.len(), 3);
+
+        let live = &summaries[1];
+        assert_eq!(
+            (live.provider.as_str(), live.mode),
+            ("deepgram", SttMode::Live)
+        );
+        assert_eq!(live.wer.errors(), 1);
+        assert_eq!(live.wer.reference_len, 10);
+        assert_eq!(live.mean_first_word_latency_ms, Some(300));
+        assert!(live.der.is_none());
+
+        let soniox = &summaries[2];
+        assert_eq!((soniox.samples, soniox.failed), (1, 1));
+        assert_eq!(soniox.mean_elapsed_ms, None);
+    }
+}
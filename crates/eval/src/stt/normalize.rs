@@ -0,0 +1,53 @@
+/// Hesitations that transcripts include or drop inconsistently.
+const FILLERS: &[&str] = &["uh", "um", "umm", "uhm", "hmm", "mm", "mhm", "er", "ah"];
+
+/// Lowercases, strips punctuation and fillers, and splits on whitespace, so casing and
+/// punctuation style don't count as recognition errors.
+///
+/// Apostrophes inside a word are kept (`don't` stays one token); any other non-alphanumeric
+/// character, hyphens included, separates words.
+pub fn normalize_text(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut cleaned = String::with_capacity(text.len());
+
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_alphanumeric() {
+            cleaned.extend(c.to_lowercase());
+        } else if matches!(c, '\'' | '\u{2019}')
+            && i > 0
+            && chars[i - 1].is_alphanumeric()
+            && chars.get(i + 1).is_some_and(|next| next.is_alphanumeric())
+        {
+            cleaned.push('\'');
+        } else {
+            cleaned.push(' ');
+        }
+    }
+
+    cleaned
+        .split_whitespace()
+        .filter(|word| !FILLERS.contains(word))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_text() {
+        assert_eq!(
+            normalize_text("Well, um... I DON'T know—maybe it's well-known?"),
+            vec![
+                "well", "i", "don't", "know", "maybe", "it's", "well", "known"
+            ]
+        );
+        assert_eq!(normalize_text("'quoted' ’tis"), vec!["quoted", "tis"]);
+        assert_eq!(
+            normalize_text("안녕하세요, 여러분!"),
+            vec!["안녕하세요", "여러분"]
+        );
+        assert!(normalize_text(" ... ").is_empty());
+    }
+}
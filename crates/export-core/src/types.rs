@@ -31,3 +31,27 @@ pub struct ExportInput {
     pub transcript: Option<Transcript>,
     pub metadata: Option<ExportMetadata>,
 }
+
+impl ExportInput {
+    /// Rewrites every user-facing string, e.g. to redact it before rendering.
+    pub fn map_text(mut self, mut f: impl FnMut(&str) -> String) -> Self {
+        self.enhanced_md = f(&self.enhanced_md);
+
+        if let Some(transcript) = &mut self.transcript {
+            for item in &mut transcript.items {
+                item.speaker = item.speaker.as_deref().map(&mut f);
+                item.text = f(&item.text);
+            }
+        }
+
+        if let Some(metadata) = &mut self.metadata {
+            metadata.title = f(&metadata.title);
+            metadata.event_title = metadata.event_title.as_deref().map(&mut f);
+            for participant in &mut metadata.participants {
+                *participant = f(participant);
+            }
+        }
+
+        self
+    }
+}
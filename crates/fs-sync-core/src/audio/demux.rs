@@ -0,0 +1,209 @@
+use std::fs::File;
+use std::path::Path;
+
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{CODEC_TYPE_NULL, DecoderOptions};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::{FormatOptions, Track};
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+use crate::error::AudioProcessingError;
+
+// Containers that commonly carry a video track (or several audio tracks) next to the audio we want.
+const CONTAINER_EXTENSIONS: [&str; 6] = ["mp4", "m4a", "m4v", "mov", "mkv", "webm"];
+
+pub struct DemuxedAudio {
+    pub samples: Vec<f32>,
+    pub sample_rate: u32,
+    pub channels: u16,
+}
+
+pub fn is_container(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| {
+            CONTAINER_EXTENSIONS
+                .iter()
+                .any(|candidate| ext.eq_ignore_ascii_case(candidate))
+        })
+        .unwrap_or(false)
+}
+
+pub fn decode(path: &Path) -> Result<DemuxedAudio, AudioProcessingError> {
+    let file = File::open(path)?;
+    let stream = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(ext) = path.extension().and_then(|ext| ext.to_str()) {
+        hint.with_extension(ext);
+    }
+
+    let probed = symphonia::default::get_probe().format(
+        &hint,
+        stream,
+        &FormatOptions::default(),
+        &MetadataOptions::default(),
+    )?;
+    let mut format = probed.format;
+
+    let track = select_track(format.tracks(), format.default_track())
+        .ok_or(AudioProcessingError::NoAudioTrack)?;
+    let track_id = track.id;
+    let mut sample_rate = track.codec_params.sample_rate.unwrap_or(0);
+    let mut channels = track
+        .codec_params
+        .channels
+        .map(|channels| channels.count() as u16)
+        .unwrap_or(0);
+
+    let mut decoder =
+        symphonia::default::get_codecs().make(&track.codec_params, &DecoderOptions::default())?;
+
+    let mut samples = Vec::new();
+    let mut buffer: Option<SampleBuffer<f32>> = None;
+
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(err))
+                if err.kind() == std::io::ErrorKind::UnexpectedEof =>
+            {
+                break;
+            }
+            // The stream changed parameters mid-file (e.g. a chained Ogg stream); keep decoding
+            // the same track with a decoder built for its new parameters.
+            Err(SymphoniaError::ResetRequired) => {
+                let track = format
+                    .tracks()
+                    .iter()
+                    .find(|track| track.id == track_id)
+                    .ok_or(AudioProcessingError::NoAudioTrack)?;
+                decoder = symphonia::default::get_codecs()
+                    .make(&track.codec_params, &DecoderOptions::default())?;
+                continue;
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        if packet.track_id() != track_id {
+            continue;
+        }
+
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            Err(SymphoniaError::DecodeError(err)) => {
+                tracing::warn!("skipping_undecodable_packet: {}", err);
+                continue;
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        let spec = *decoded.spec();
+        sample_rate = spec.rate;
+        channels = spec.channels.count() as u16;
+
+        let required = decoded.capacity() as u64;
+        let buffer = match &mut buffer {
+            Some(existing) if existing.capacity() as u64 >= required * channels as u64 => existing,
+            slot => slot.insert(SampleBuffer::new(required, spec)),
+        };
+        buffer.copy_interleaved_ref(decoded);
+        samples.extend_from_slice(buffer.samples());
+    }
+
+    if samples.is_empty() {
+        return Err(AudioProcessingError::EmptyInput);
+    }
+    if channels == 0 {
+        return Err(AudioProcessingError::InvalidChannelCount);
+    }
+
+    Ok(DemuxedAudio {
+        samples,
+        sample_rate,
+        channels,
+    })
+}
+
+// Picks the audio track to import: the container's default track when it is decodable audio,
+// otherwise the first track that is.
+fn select_track<'a>(tracks: &'a [Track], default: Option<&'a Track>) -> Option<&'a Track> {
+    let codecs = symphonia::default::get_codecs();
+    let is_decodable = |track: &&Track| {
+        track.codec_params.codec != CODEC_TYPE_NULL
+            && track.codec_params.sample_rate.is_some()
+            && codecs.get_codec(track.codec_params.codec).is_some()
+    };
+
+    default
+        .filter(is_decodable)
+        .or_else(|| tracks.iter().find(is_decodable))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use symphonia::core::audio::Channels;
+    use symphonia::core::codecs::{CODEC_TYPE_AAC, CODEC_TYPE_PCM_S16LE, CodecParameters};
+
+    fn audio_track(id: u32, codec: symphonia::core::codecs::CodecType) -> Track {
+        let mut params = CodecParameters::new();
+        params
+            .for_codec(codec)
+            .with_sample_rate(48_000)
+            .with_channels(Channels::FRONT_LEFT | Channels::FRONT_RIGHT);
+        Track::new(id, params)
+    }
+
+    fn video_track(id: u32) -> Track {
+        Track::new(id, CodecParameters::new())
+    }
+
+    #[test]
+    fn select_track_skips_video() {
+        let tracks = vec![video_track(1), audio_track(2, CODEC_TYPE_AAC)];
+        assert_eq!(select_track(&tracks, tracks.first()).map(|t| t.id), Some(2));
+    }
+
+    #[test]
+    fn select_track_prefers_default_track() {
+        let tracks = vec![
+            audio_track(1, CODEC_TYPE_AAC),
+            audio_track(2, CODEC_TYPE_PCM_S16LE),
+        ];
+        assert_eq!(select_track(&tracks, tracks.get(1)).map(|t| t.id), Some(2));
+    }
+
+    #[test]
+    fn select_track_falls_back_to_first_decodable() {
+        let tracks = vec![
+            video_track(1),
+            audio_track(2, CODEC_TYPE_AAC),
+            audio_track(3, CODEC_TYPE_AAC),
+        ];
+        assert_eq!(select_track(&tracks, None).map(|t| t.id), Some(2));
+    }
+
+    #[test]
+    fn select_track_none_without_audio() {
+        let tracks = vec![video_track(1)];
+        assert!(select_track(&tracks, tracks.first()).is_none());
+    }
+
+    #[test]
+    fn is_container_matches_case_insensitively() {
+        assert!(is_container(Path::new("call.MP4")));
+        assert!(is_container(Path::new("call.webm")));
+        assert!(!is_container(Path::new("call.wav")));
+    }
+
+    #[test]
+    fn decode_mp4_fixture() {
+        let audio = decode(Path::new(hypr_data::english_1::AUDIO_MP4_PATH)).unwrap();
+        assert!(audio.sample_rate > 0);
+        assert!(audio.channels > 0);
+        assert!(!audio.samples.is_empty());
+    }
+}
@@ -9,6 +9,8 @@ use hypr_audio_utils::{
 
 use crate::error::{AudioImportError, AudioProcessingError};
 
+mod demux;
+
 const TARGET_SAMPLE_RATE_HZ: u32 = 16_000;
 const AUDIO_FORMATS: [&str; 3] = ["audio.mp3", "audio.wav", "audio.ogg"];
 
@@ -67,9 +69,28 @@ pub fn import_audio(
     tmp_path: &Path,
     target_path: &Path,
 ) -> Result<PathBuf, AudioProcessingError> {
+    if demux::is_container(source_path) {
+        return import_audio_from_demuxer(source_path, tmp_path, target_path);
+    }
+
     let file = File::open(source_path)?;
     match rodio::Decoder::try_from(file) {
-        Ok(decoder) => import_audio_from_decoder(decoder, tmp_path, target_path),
+        Ok(decoder) => import_audio_from_source(decoder, tmp_path, target_path),
+        Err(_) => import_audio_from_demuxer(source_path, tmp_path, target_path),
+    }
+}
+
+fn import_audio_from_demuxer(
+    source_path: &Path,
+    tmp_path: &Path,
+    target_path: &Path,
+) -> Result<PathBuf, AudioProcessingError> {
+    match demux::decode(source_path) {
+        Ok(audio) => {
+            let source =
+                rodio::buffer::SamplesBuffer::new(audio.channels, audio.sample_rate, audio.samples);
+            import_audio_from_source(source, tmp_path, target_path)
+        }
         Err(_original_err) => {
             #[cfg(target_os = "macos")]
             {
@@ -78,25 +99,25 @@ pub fn import_audio(
                 let result = (|| {
                     let file = File::open(&wav_path)?;
                     let decoder = rodio::Decoder::try_from(file)?;
-                    import_audio_from_decoder(decoder, tmp_path, target_path)
+                    import_audio_from_source(decoder, tmp_path, target_path)
                 })();
                 let _ = std::fs::remove_file(&wav_path);
                 result
             }
             #[cfg(not(target_os = "macos"))]
             {
-                Err(_original_err.into())
+                Err(_original_err)
             }
         }
     }
 }
 
-fn import_audio_from_decoder<R: std::io::Read + std::io::Seek>(
-    decoder: rodio::Decoder<R>,
+fn import_audio_from_source<S: Source>(
+    source: S,
     tmp_path: &Path,
     target_path: &Path,
 ) -> Result<PathBuf, AudioProcessingError> {
-    let channel_count_raw = decoder.channels().max(1);
+    let channel_count_raw = source.channels().max(1);
     let channel_count_u8 = u8::try_from(channel_count_raw).map_err(|_| {
         AudioProcessingError::UnsupportedChannelCount {
             count: channel_count_raw,
@@ -105,7 +126,7 @@ fn import_audio_from_decoder<R: std::io::Read + std::io::Seek>(
     let channel_count =
         NonZeroU8::new(channel_count_u8).ok_or(AudioProcessingError::InvalidChannelCount)?;
 
-    let samples = resample_audio(decoder, TARGET_SAMPLE_RATE_HZ)?;
+    let samples = resample_audio(source, TARGET_SAMPLE_RATE_HZ)?;
     let mono_samples = if channel_count.get() > 1 {
         mix_down_to_mono(&samples, channel_count)
     } else {
@@ -202,4 +223,19 @@ mod tests {
         test_import_aiff: hypr_data::english_1::AUDIO_AIFF_PATH,
         test_import_caf: hypr_data::english_1::AUDIO_CAF_PATH,
     }
+
+    #[test]
+    fn import_mp4_resamples_to_target_rate() {
+        let source_path = std::path::Path::new(hypr_data::english_1::AUDIO_MP4_PATH);
+        let temp = TempDir::new().unwrap();
+        let tmp_path = temp.path().join("tmp.ogg");
+        let target_path = temp.path().join("target.ogg");
+
+        import_audio(source_path, &tmp_path, &target_path).unwrap();
+
+        let metadata = hypr_audio_utils::audio_file_metadata(&target_path).unwrap();
+        assert_eq!(metadata.sample_rate, TARGET_SAMPLE_RATE_HZ);
+        assert_eq!(metadata.channels, 1);
+        assert!(!tmp_path.exists());
+    }
 }
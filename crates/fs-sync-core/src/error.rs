@@ -32,7 +32,11 @@ pub enum AudioProcessingError {
     #[error(transparent)]
     Decoder(#[from] rodio::decoder::DecoderError),
     #[error(transparent)]
+    Demux(#[from] symphonia::core::errors::Error),
+    #[error(transparent)]
     AudioUtils(#[from] hypr_audio_utils::Error),
+    #[error("audio_import_no_decodable_audio_track")]
+    NoAudioTrack,
     #[error("audio_import_unsupported_channel_count")]
     UnsupportedChannelCount { count: u16 },
     #[error("audio_import_invalid_channel_count")]
@@ -0,0 +1,459 @@
+// Subset of https://github.com/ggml-org/llama.cpp/blob/master/examples/json_schema_to_grammar.py
+//
+// Supported: type (incl. unions), enum, const, properties/required, items, minItems/maxItems,
+// minLength/maxLength, anyOf/oneOf, local $ref. Objects never get extra properties, and
+// `pattern`/`format` fall back to a plain string.
+
+use std::collections::{HashMap, HashSet};
+
+use serde_json::Value;
+
+const WS: &str = r##"ws ::= | " " | "\n" [ \t]{0,20}"##;
+const CHAR: &str = r##"char ::= [^"\\\x7F\x00-\x1F] | [\\] (["\\bfnrt] | "u" [0-9a-fA-F]{4})"##;
+
+const PRIMITIVES: &[(&str, &str)] = &[
+    ("boolean", r##"("true" | "false") ws"##),
+    ("null", r##""null" ws"##),
+    ("string", r##""\"" char* "\"" ws"##),
+    ("integer", r##"("-"? ([0-9] | [1-9] [0-9]{0,15})) ws"##),
+    (
+        "number",
+        r##"("-"? ([0-9] | [1-9] [0-9]{0,15})) ("." [0-9]+)? ([eE] [-+]? [0-9]{1,15})? ws"##,
+    ),
+    (
+        "value",
+        r##"object | array | string | number | boolean | null"##,
+    ),
+    (
+        "object",
+        r##""{" ws ( string ":" ws value ("," ws string ":" ws value)* )? "}" ws"##,
+    ),
+    ("array", r##""[" ws ( value ("," ws value)* )? "]" ws"##),
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum JsonSchemaError {
+    #[error("unsupported schema: {0}")]
+    Unsupported(String),
+    #[error("unresolvable $ref: {0}")]
+    UnresolvedRef(String),
+}
+
+/// Grammar for any JSON object, i.e. `response_format: {"type": "json_object"}`.
+pub fn json_object_grammar() -> String {
+    JsonSchemaGrammar::new().build("ws object")
+}
+
+/// Grammar whose root matches exactly one JSON document valid against `schema`.
+pub fn json_schema_grammar(schema: &Value) -> Result<String, JsonSchemaError> {
+    let mut builder = JsonSchemaGrammar::new();
+    let rule = builder.add_schema("root-schema", schema)?;
+    Ok(builder.build(&format!("ws {rule}")))
+}
+
+/// Builds grammars that embed one or more schemas in a larger output, e.g. tool calls.
+///
+/// Every generated rule consumes the whitespace that follows its value.
+#[derive(Default)]
+pub struct JsonSchemaGrammar {
+    rules: Vec<(String, String)>,
+    names: HashSet<String>,
+    refs: HashMap<String, String>,
+}
+
+impl JsonSchemaGrammar {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the rules for `schema` and returns the name of its entry rule.
+    pub fn add_schema(&mut self, name: &str, schema: &Value) -> Result<String, JsonSchemaError> {
+        self.refs.clear();
+        let name = sanitize(name);
+        self.visit(schema, schema, &name)
+    }
+
+    /// Adds a rule, renaming it when `name` is taken, and returns the final name.
+    pub fn add_rule(&mut self, name: &str, body: impl Into<String>) -> String {
+        let base = sanitize(name);
+        let mut name = base.clone();
+        let mut i = 1;
+        while self.names.contains(&name) || is_reserved(&name) {
+            name = format!("{base}-{i}");
+            i += 1;
+        }
+
+        self.names.insert(name.clone());
+        self.rules.push((name.clone(), body.into()));
+        name
+    }
+
+    pub fn build(&self, root: &str) -> String {
+        let mut lines = vec![format!("root ::= {root}")];
+        lines.extend(
+            self.rules
+                .iter()
+                .map(|(name, body)| format!("{name} ::= {body}")),
+        );
+        lines.extend(
+            PRIMITIVES
+                .iter()
+                .map(|(name, body)| format!("{name} ::= {body}")),
+        );
+        lines.push(CHAR.to_string());
+        lines.push(WS.to_string());
+        lines.join("\n")
+    }
+
+    fn visit(
+        &mut self,
+        schema: &Value,
+        root: &Value,
+        name: &str,
+    ) -> Result<String, JsonSchemaError> {
+        let schema = match schema {
+            Value::Bool(true) => return Ok("value".to_string()),
+            Value::Bool(false) => {
+                return Err(JsonSchemaError::Unsupported("`false` schema".to_string()));
+            }
+            Value::Object(map) => map,
+            _ => return Err(JsonSchemaError::Unsupported(schema.to_string())),
+        };
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            return self.visit_ref(reference, root);
+        }
+
+        if schema.contains_key("allOf") || schema.contains_key("not") {
+            return Err(JsonSchemaError::Unsupported(
+                "allOf and not are not supported".to_string(),
+            ));
+        }
+
+        if let Some(value) = schema.get("const") {
+            return Ok(self.add_rule(name, format!("{} ws", literal_json(value))));
+        }
+
+        if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+            let alternatives = values.iter().map(literal_json).collect::<Vec<_>>();
+            return Ok(self.add_rule(name, format!("({}) ws", alternatives.join(" | "))));
+        }
+
+        if let Some(variants) = schema
+            .get("anyOf")
+            .or_else(|| schema.get("oneOf"))
+            .and_then(Value::as_array)
+        {
+            let alternatives = variants
+                .iter()
+                .enumerate()
+                .map(|(i, variant)| self.visit(variant, root, &format!("{name}-{i}")))
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok(self.add_rule(name, alternatives.join(" | ")));
+        }
+
+        match schema.get("type") {
+            Some(Value::Array(types)) => {
+                let alternatives = types
+                    .iter()
+                    .map(|t| {
+                        let mut variant = schema.clone();
+                        variant.insert("type".to_string(), t.clone());
+                        let suffix = t.as_str().unwrap_or("type");
+                        self.visit(&Value::Object(variant), root, &format!("{name}-{suffix}"))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok(self.add_rule(name, alternatives.join(" | ")))
+            }
+            Some(Value::String(t)) => self.visit_type(t, schema, root, name),
+            Some(other) => Err(JsonSchemaError::Unsupported(format!("type {other}"))),
+            None if schema.contains_key("properties") => {
+                self.visit_type("object", schema, root, name)
+            }
+            None if schema.contains_key("items") => self.visit_type("array", schema, root, name),
+            None => Ok("value".to_string()),
+        }
+    }
+
+    fn visit_type(
+        &mut self,
+        t: &str,
+        schema: &serde_json::Map<String, Value>,
+        root: &Value,
+        name: &str,
+    ) -> Result<String, JsonSchemaError> {
+        match t {
+            "object" => self.visit_object(schema, root, name),
+            "array" => {
+                let item = match schema.get("items") {
+                    Some(items) => self.visit(items, root, &format!("{name}-item"))?,
+                    None => "value".to_string(),
+                };
+                let min = schema.get("minItems").and_then(Value::as_u64).unwrap_or(0);
+                let max = schema.get("maxItems").and_then(Value::as_u64);
+                let body = format!(r#""[" ws {} "]" ws"#, repeat_separated(&item, min, max));
+                Ok(self.add_rule(name, body))
+            }
+            "string" => {
+                let min = schema.get("minLength").and_then(Value::as_u64);
+                let max = schema.get("maxLength").and_then(Value::as_u64);
+                if min.is_none() && max.is_none() {
+                    return Ok("string".to_string());
+                }
+                let min = min.unwrap_or(0);
+                let repeat = match max {
+                    Some(max) => format!("{{{min},{max}}}"),
+                    None => format!("{{{min},}}"),
+                };
+                Ok(self.add_rule(name, format!(r#""\"" char{repeat} "\"" ws"#)))
+            }
+            "integer" | "number" | "boolean" | "null" => Ok(t.to_string()),
+            other => Err(JsonSchemaError::Unsupported(format!("type {other:?}"))),
+        }
+    }
+
+    fn visit_object(
+        &mut self,
+        schema: &serde_json::Map<String, Value>,
+        root: &Value,
+        name: &str,
+    ) -> Result<String, JsonSchemaError> {
+        let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+            return Ok("object".to_string());
+        };
+        let required = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|r| r.iter().filter_map(Value::as_str).collect::<HashSet<_>>())
+            .unwrap_or_default();
+
+        let mut required_kvs = Vec::new();
+        let mut optional_kvs = Vec::new();
+        for (key, property) in properties {
+            let value = self.visit(property, root, &format!("{name}-{key}"))?;
+            let kv = format!(
+                r#"{} ws ":" ws {value}"#,
+                literal_json(&Value::from(key.as_str()))
+            );
+            if required.contains(key.as_str()) {
+                required_kvs.push(kv);
+            } else {
+                optional_kvs.push(kv);
+            }
+        }
+
+        let mut body = required_kvs.join(r#" "," ws "#);
+        if required_kvs.is_empty() {
+            // Without a required property to anchor the commas, each optional property can
+            // open the object and only later ones may follow it.
+            let alternatives = (0..optional_kvs.len())
+                .map(|i| {
+                    let mut alt = optional_kvs[i].clone();
+                    for kv in &optional_kvs[i + 1..] {
+                        alt.push_str(&format!(r#" ("," ws {kv})?"#));
+                    }
+                    alt
+                })
+                .collect::<Vec<_>>();
+            if !alternatives.is_empty() {
+                body = format!("({})?", alternatives.join(" | "));
+            }
+        } else {
+            for kv in &optional_kvs {
+                body.push_str(&format!(r#" ("," ws {kv})?"#));
+            }
+        }
+
+        Ok(self.add_rule(name, format!(r#""{{" ws {body} "}}" ws"#)))
+    }
+
+    fn visit_ref(&mut self, reference: &str, root: &Value) -> Result<String, JsonSchemaError> {
+        if let Some(rule) = self.refs.get(reference) {
+            return Ok(rule.clone());
+        }
+
+        let target = reference
+            .strip_prefix('#')
+            .and_then(|pointer| root.pointer(pointer))
+            .ok_or_else(|| JsonSchemaError::UnresolvedRef(reference.to_string()))?;
+
+        // Reserve the name before visiting so recursive schemas refer back to it.
+        let ref_name = reference.rsplit('/').next().unwrap_or("ref");
+        let placeholder = self.add_rule(&format!("ref-{ref_name}"), "");
+        self.refs.insert(reference.to_string(), placeholder.clone());
+
+        let rule = self.visit(target, root, &format!("{placeholder}-def"))?;
+        if let Some((_, body)) = self.rules.iter_mut().find(|(n, _)| *n == placeholder) {
+            *body = rule;
+        }
+        Ok(placeholder)
+    }
+}
+
+fn repeat_separated(item: &str, min: u64, max: Option<u64>) -> String {
+    if max == Some(0) {
+        return String::new();
+    }
+
+    let rest_min = min.saturating_sub(1);
+    let rest = match max {
+        Some(max) => format!("{{{rest_min},{}}}", max - 1),
+        None if rest_min == 0 => "*".to_string(),
+        None => format!("{{{rest_min},}}"),
+    };
+    let list = format!(r#"{item} ("," ws {item}){rest}"#);
+
+    if min == 0 { format!("({list})?") } else { list }
+}
+
+/// GBNF literal that matches `value` serialized as compact JSON.
+fn literal_json(value: &Value) -> String {
+    literal(&value.to_string())
+}
+
+/// GBNF string literal matching `text` verbatim.
+pub fn literal(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    out.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn sanitize(name: &str) -> String {
+    let name = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect::<String>();
+    if name.is_empty() {
+        "rule".to_string()
+    } else {
+        name
+    }
+}
+
+fn is_reserved(name: &str) -> bool {
+    name == "root" || name == "ws" || name == "char" || PRIMITIVES.iter().any(|(n, _)| *n == name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate(grammar: &str, input: &str) -> bool {
+        gbnf_validator::Validator::new()
+            .unwrap()
+            .validate(grammar, input)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_object_schema_rules() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "age": { "type": "integer" },
+                "tags": { "type": "array", "items": { "enum": ["a", "b"] }, "maxItems": 2 }
+            },
+            "required": ["name"]
+        });
+
+        let grammar = json_schema_grammar(&schema).unwrap();
+        let root = grammar.lines().next().unwrap();
+        assert_eq!(root, "root ::= ws root-schema");
+        assert!(grammar.contains(
+            r#"root-schema ::= "{" ws "\"name\"" ws ":" ws string ("," ws "\"age\"" ws ":" ws integer)? ("," ws "\"tags\"" ws ":" ws root-schema-tags)? "}" ws"#
+        ));
+        assert!(grammar.contains(r#"root-schema-tags-item ::= ("\"a\"" | "\"b\"") ws"#));
+        assert!(grammar.contains(
+            r#"root-schema-tags ::= "[" ws (root-schema-tags-item ("," ws root-schema-tags-item){0,1})? "]" ws"#
+        ));
+    }
+
+    #[test]
+    fn test_recursive_ref() {
+        let schema = serde_json::json!({
+            "$ref": "#/$defs/node",
+            "$defs": {
+                "node": {
+                    "type": "object",
+                    "properties": {
+                        "children": { "type": "array", "items": { "$ref": "#/$defs/node" } }
+                    },
+                    "required": ["children"]
+                }
+            }
+        });
+
+        let grammar = json_schema_grammar(&schema).unwrap();
+        assert!(grammar.contains("root ::= ws ref-node\n"));
+        assert!(grammar.contains("ref-node ::= ref-node-def\n"));
+        assert!(grammar.contains(r#""[" ws (ref-node ("," ws ref-node)*)? "]" ws"#));
+    }
+
+    #[test]
+    fn test_unsupported_schema() {
+        let schema = serde_json::json!({ "allOf": [{ "type": "string" }] });
+        assert!(matches!(
+            json_schema_grammar(&schema),
+            Err(JsonSchemaError::Unsupported(_))
+        ));
+
+        let schema = serde_json::json!({ "$ref": "#/$defs/missing" });
+        assert!(matches!(
+            json_schema_grammar(&schema),
+            Err(JsonSchemaError::UnresolvedRef(_))
+        ));
+    }
+
+    #[test]
+    #[ignore]
+    fn test_json_schema_grammar() {
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "minLength": 1 },
+                "age": { "type": ["integer", "null"] },
+                "role": { "const": "admin" }
+            },
+            "required": ["name", "role"]
+        });
+        let grammar = json_schema_grammar(&schema).unwrap();
+
+        for (input, expected) in [
+            (r#"{"name": "Ada", "role": "admin"}"#, true),
+            (r#"{"name": "Ada", "role": "admin", "age": null}"#, true),
+            (r#"{"name": "Ada", "role": "admin", "age": 36}"#, true),
+            (r#"{"name": "", "role": "admin"}"#, false),
+            (r#"{"name": "Ada", "role": "user"}"#, false),
+            (r#"{"role": "admin"}"#, false),
+        ] {
+            assert_eq!(validate(&grammar, input), expected, "failed: {}", input);
+        }
+    }
+
+    #[test]
+    #[ignore]
+    fn test_json_object_grammar() {
+        let grammar = json_object_grammar();
+
+        for (input, expected) in [
+            (r#"{"a": [1, 2.5, {"b": null}]}"#, true),
+            ("{}", true),
+            ("[1, 2]", false),
+            ("not json", false),
+        ] {
+            assert_eq!(validate(&grammar, input), expected, "failed: {}", input);
+        }
+    }
+}
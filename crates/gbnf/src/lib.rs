@@ -1,5 +1,9 @@
 // https://github.com/ggml-org/llama.cpp/blob/master/grammars/README.md
 
+mod json_schema;
+
+pub use json_schema::*;
+
 #[derive(specta::Type, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "task")]
 pub enum Grammar {
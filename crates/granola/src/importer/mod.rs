@@ -68,6 +68,7 @@ pub async fn import_all_from_path(path: &Path) -> Result<Collection> {
         enhanced_notes: vec![],
         tags,
         tag_mappings,
+        attachments: vec![],
     })
 }
 
@@ -0,0 +1,567 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
+use hypr_calendar_interface::{
+    AttendeeRole, AttendeeStatus, CalendarEvent, CalendarProviderType, EventAttendee, EventPerson,
+    EventStatus, MeetingLink,
+};
+
+use crate::Error;
+use crate::datetime::{IcalTime, Timezones, parse_duration};
+use crate::parser::{Component, Property, parse};
+use crate::recurrence::RecurrenceRule;
+
+/// A parsed `VCALENDAR`: an ICS feed, or one CalDAV resource.
+#[derive(Debug, Clone)]
+pub struct IcsCalendar {
+    /// `X-WR-CALNAME`, set by most feed exporters.
+    pub name: Option<String>,
+    /// `X-WR-CALDESC`.
+    pub description: Option<String>,
+    /// `X-APPLE-CALENDAR-COLOR`, a CSS hex color.
+    pub color: Option<String>,
+    events: Vec<Component>,
+    timezones: Timezones,
+}
+
+/// Where the expanded events come from, and who is looking at them.
+#[derive(Debug, Clone, Copy)]
+pub struct ExpandOptions<'a> {
+    pub provider: CalendarProviderType,
+    pub calendar_id: &'a str,
+    /// Marks the organizer and attendee with this address as the current user.
+    pub owner_email: Option<&'a str>,
+}
+
+impl IcsCalendar {
+    pub fn parse(input: &str) -> Result<Self, Error> {
+        let components = parse(input)?;
+        let calendar = components
+            .iter()
+            .find(|c| c.name == "VCALENDAR")
+            .ok_or_else(|| Error::Parse {
+                line: 1,
+                message: "no VCALENDAR component".to_string(),
+            })?;
+
+        // Some servers split one calendar across several VCALENDAR blocks in a single file.
+        let events = components
+            .iter()
+            .filter(|c| c.name == "VCALENDAR")
+            .flat_map(|c| c.children("VEVENT").cloned())
+            .collect();
+
+        Ok(Self {
+            name: calendar.text("X-WR-CALNAME"),
+            description: calendar.text("X-WR-CALDESC"),
+            color: calendar.text("X-APPLE-CALENDAR-COLOR"),
+            events,
+            timezones: Timezones::new(calendar),
+        })
+    }
+
+    /// Events overlapping `[from, to)`, with recurring series expanded into one event per
+    /// occurrence. `EXDATE`s are removed, `RDATE`s added, and instances with a matching
+    /// `RECURRENCE-ID` replace the generated ones.
+    pub fn events_between(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        options: &ExpandOptions<'_>,
+    ) -> Vec<CalendarEvent> {
+        let mut masters: Vec<(String, &Component)> = Vec::new();
+        let mut overrides: HashMap<String, Vec<&Component>> = HashMap::new();
+
+        for component in &self.events {
+            let uid = event_uid(component);
+            if component.property("RECURRENCE-ID").is_some() {
+                overrides.entry(uid).or_default().push(component);
+            } else {
+                masters.push((uid, component));
+            }
+        }
+
+        let mut events = Vec::new();
+
+        for (uid, master) in masters {
+            let series_overrides = overrides.remove(&uid).unwrap_or_default();
+            let Some(event) = EventData::new(master, &self.timezones) else {
+                continue;
+            };
+
+            if !event.is_recurring() {
+                if event.overlaps(event.start, from, to) {
+                    events.push(self.convert(&event, &uid, None, options));
+                }
+                continue;
+            }
+
+            let mut overridden: HashMap<DateTime<Utc>, EventData> = series_overrides
+                .into_iter()
+                .filter_map(|c| {
+                    let recurrence_id = self.timezones.parse(c.property("RECURRENCE-ID")?)?;
+                    Some((recurrence_id.to_utc(), EventData::new(c, &self.timezones)?))
+                })
+                .collect();
+
+            for start in event.instances(to) {
+                let original = start.to_utc();
+                match overridden.remove(&original) {
+                    Some(instance) => {
+                        if instance.status != EventStatus::Cancelled
+                            && instance.overlaps(instance.start, from, to)
+                        {
+                            events.push(self.convert(&instance, &uid, Some(start), options));
+                        }
+                    }
+                    None => {
+                        if event.overlaps(start, from, to) {
+                            events.push(self.convert(&event, &uid, Some(start), options));
+                        }
+                    }
+                }
+            }
+
+            // Overrides that no longer line up with a generated occurrence are still explicit
+            // instances of the series.
+            for instance in overridden.into_values() {
+                if instance.status != EventStatus::Cancelled
+                    && instance.overlaps(instance.start, from, to)
+                {
+                    let start = instance.recurrence_id.unwrap_or(instance.start);
+                    events.push(self.convert(&instance, &uid, Some(start), options));
+                }
+            }
+        }
+
+        // Instances whose series isn't part of this calendar, e.g. a single invitation to one
+        // occurrence of someone else's meeting.
+        for (uid, components) in overrides {
+            for component in components {
+                let Some(instance) = EventData::new(component, &self.timezones) else {
+                    continue;
+                };
+                if instance.status != EventStatus::Cancelled
+                    && instance.overlaps(instance.start, from, to)
+                {
+                    let start = instance.recurrence_id.unwrap_or(instance.start);
+                    events.push(self.convert(&instance, &uid, Some(start), options));
+                }
+            }
+        }
+
+        events.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+        events
+    }
+
+    fn convert(
+        &self,
+        event: &EventData<'_>,
+        uid: &str,
+        occurrence: Option<IcalTime>,
+        options: &ExpandOptions<'_>,
+    ) -> CalendarEvent {
+        let component = event.component;
+        let start = occurrence
+            .filter(|_| event.recurrence_id.is_none())
+            .unwrap_or(event.start);
+        let end = start.to_utc() + event.duration;
+
+        let id = match occurrence {
+            Some(original) => format!("{uid}:{}", original.recurrence_id()),
+            None => uid.to_string(),
+        };
+
+        let description = component.text("DESCRIPTION");
+        let location = component.text("LOCATION");
+        let url = component.text("URL");
+        let meeting = MeetingLink::find(
+            [url.as_deref(), location.as_deref(), description.as_deref()]
+                .into_iter()
+                .flatten(),
+        );
+
+        let is_current_user = |email: &Option<String>| {
+            options
+                .owner_email
+                .zip(email.as_deref())
+                .is_some_and(|(owner, email)| owner.eq_ignore_ascii_case(email))
+        };
+
+        let organizer = component.property("ORGANIZER").map(|p| {
+            let (name, email) = person(p);
+            EventPerson {
+                is_current_user: is_current_user(&email),
+                name,
+                email,
+            }
+        });
+
+        let attendees = component
+            .properties("ATTENDEE")
+            .map(|p| {
+                let (name, email) = person(p);
+                EventAttendee {
+                    is_current_user: is_current_user(&email),
+                    name,
+                    email,
+                    status: attendee_status(p.param("PARTSTAT")),
+                    role: attendee_role(p.param("ROLE")),
+                }
+            })
+            .collect();
+
+        CalendarEvent {
+            provider: options.provider,
+            id,
+            calendar_id: options.calendar_id.to_string(),
+            external_id: uid.to_string(),
+            title: component
+                .text("SUMMARY")
+                .unwrap_or_else(|| "Untitled".to_string()),
+            description,
+            location,
+            url,
+            meeting_link: meeting.as_ref().map(|m| m.url.clone()),
+            meeting,
+            started_at: start.to_utc().to_rfc3339(),
+            ended_at: end.to_rfc3339(),
+            timezone: component
+                .property("DTSTART")
+                .and_then(|p| p.param("TZID"))
+                .map(str::to_string),
+            is_all_day: start.is_date,
+            status: event.status,
+            organizer,
+            attendees,
+            has_recurrence_rules: occurrence.is_some(),
+            recurring_event_id: occurrence.map(|_| uid.to_string()),
+            raw: component.raw.clone(),
+        }
+    }
+}
+
+/// The parts of a `VEVENT` needed to place it, and its occurrences, in time.
+struct EventData<'a> {
+    component: &'a Component,
+    start: IcalTime,
+    duration: Duration,
+    status: EventStatus,
+    recurrence_id: Option<IcalTime>,
+    rules: Vec<RecurrenceRule>,
+    rdates: Vec<IcalTime>,
+    exdates: Vec<IcalTime>,
+}
+
+impl<'a> EventData<'a> {
+    fn new(component: &'a Component, timezones: &Timezones) -> Option<Self> {
+        let start = timezones.parse(component.property("DTSTART")?)?;
+
+        let duration = component
+            .property("DTEND")
+            .and_then(|p| timezones.parse(p))
+            .map(|end| end.to_utc() - start.to_utc())
+            .or_else(|| {
+                component
+                    .property("DURATION")
+                    .and_then(|p| parse_duration(&p.value))
+            })
+            .unwrap_or_else(|| {
+                if start.is_date {
+                    Duration::days(1)
+                } else {
+                    Duration::zero()
+                }
+            })
+            .max(Duration::zero());
+
+        let times = |name: &str| -> Vec<IcalTime> {
+            component
+                .properties(name)
+                .flat_map(|p| timezones.parse_list(p))
+                .collect()
+        };
+
+        Some(Self {
+            component,
+            start,
+            duration,
+            status: match component
+                .property("STATUS")
+                .map(|p| p.value.to_ascii_uppercase())
+            {
+                Some(status) if status == "CANCELLED" => EventStatus::Cancelled,
+                Some(status) if status == "TENTATIVE" => EventStatus::Tentative,
+                _ => EventStatus::Confirmed,
+            },
+            recurrence_id: component
+                .property("RECURRENCE-ID")
+                .and_then(|p| timezones.parse(p)),
+            rules: component
+                .properties("RRULE")
+                .filter_map(|p| RecurrenceRule::parse(&p.value, timezones))
+                .collect(),
+            rdates: times("RDATE"),
+            exdates: times("EXDATE"),
+        })
+    }
+
+    fn is_recurring(&self) -> bool {
+        !self.rules.is_empty() || !self.rdates.is_empty()
+    }
+
+    /// Start times of every occurrence beginning before `to`, in order.
+    fn instances(&self, to: DateTime<Utc>) -> Vec<IcalTime> {
+        let zone = self.start.zone;
+        // A day of slack covers the offset between UTC and the event's wall-clock time.
+        let horizon = if self.start.is_date {
+            to.naive_utc()
+        } else {
+            zone.local_from_utc(to)
+        } + Duration::days(1);
+
+        let mut starts: Vec<IcalTime> = self
+            .rules
+            .iter()
+            .flat_map(|rule| rule.occurrences(self.start.local, zone, horizon))
+            .chain(std::iter::once(self.start.local))
+            .map(|local| IcalTime {
+                local,
+                ..self.start
+            })
+            .collect();
+
+        starts.extend(self.rdates.iter().map(|rdate| IcalTime {
+            local: rdate.local_in_zone_of(&self.start),
+            ..self.start
+        }));
+
+        starts.retain(|start| !self.exdates.iter().any(|exdate| exdate.matches(start)));
+        starts.sort_by_key(|start| start.to_utc());
+        starts.dedup_by_key(|start| start.to_utc());
+        starts
+    }
+
+    /// Whether the occurrence starting at `start` overlaps `[from, to)`.
+    fn overlaps(&self, start: IcalTime, from: DateTime<Utc>, to: DateTime<Utc>) -> bool {
+        let start = start.to_utc();
+        let end = start + self.duration;
+        start < to && (end > from || (end == start && start >= from))
+    }
+}
+
+fn event_uid(component: &Component) -> String {
+    component
+        .text("UID")
+        .map(|uid| uid.trim().to_string())
+        .unwrap_or_else(|| {
+            // Without a UID, fall back to something stable across refetches of the same feed.
+            let start = component
+                .property("DTSTART")
+                .map(|p| p.value.as_str())
+                .unwrap_or_default();
+            let summary = component.text("SUMMARY").unwrap_or_default();
+            format!("{start}-{summary}")
+        })
+}
+
+/// The `CN` parameter and `mailto:` address of an `ORGANIZER` or `ATTENDEE`.
+fn person(property: &Property) -> (Option<String>, Option<String>) {
+    let name = property
+        .param("CN")
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty());
+    let value = property.value.trim();
+    let email = value
+        .get(..7)
+        .filter(|scheme| scheme.eq_ignore_ascii_case("mailto:"))
+        .map(|_| value[7..].trim().to_string())
+        .or_else(|| property.param("EMAIL").map(str::to_string))
+        .filter(|email| email.contains('@'));
+
+    (name, email)
+}
+
+/// RFC 5545 PARTSTAT, normalized the same way as the Apple and Google conversions.
+fn attendee_status(partstat: Option<&str>) -> AttendeeStatus {
+    match partstat.map(str::to_ascii_uppercase).as_deref() {
+        Some("ACCEPTED" | "DELEGATED" | "COMPLETED" | "IN-PROCESS") => AttendeeStatus::Accepted,
+        Some("TENTATIVE") => AttendeeStatus::Tentative,
+        Some("DECLINED") => AttendeeStatus::Declined,
+        _ => AttendeeStatus::Pending,
+    }
+}
+
+/// RFC 5545 ROLE, which defaults to REQ-PARTICIPANT.
+fn attendee_role(role: Option<&str>) -> AttendeeRole {
+    match role.map(str::to_ascii_uppercase).as_deref() {
+        Some("CHAIR") => AttendeeRole::Chair,
+        Some("OPT-PARTICIPANT") => AttendeeRole::Optional,
+        Some("NON-PARTICIPANT") => AttendeeRole::NonParticipant,
+        _ => AttendeeRole::Required,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPTIONS: ExpandOptions<'static> = ExpandOptions {
+        provider: CalendarProviderType::Ics,
+        calendar_id: "team",
+        owner_email: Some("jane@example.com"),
+    };
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn starts(events: &[CalendarEvent]) -> Vec<&str> {
+        events.iter().map(|e| e.started_at.as_str()).collect()
+    }
+
+    #[test]
+    fn test_weekly_series_with_exdate_and_override() {
+        let ics = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+X-WR-CALNAME:Team\r\n\
+BEGIN:VEVENT\r\n\
+UID:standup@example.com\r\n\
+SUMMARY:Standup\r\n\
+DTSTART;TZID=Europe/Berlin:20250303T093000\r\n\
+DTEND;TZID=Europe/Berlin:20250303T094500\r\n\
+RRULE:FREQ=WEEKLY;BYDAY=MO,TH;UNTIL=20250331T235959Z\r\n\
+EXDATE;TZID=Europe/Berlin:20250306T093000\r\n\
+DESCRIPTION:Join: https://acme.zoom.us/j/123456789?pwd=abc\\nAgenda in doc\r\n\
+ORGANIZER;CN=Jane Doe:mailto:jane@example.com\r\n\
+ATTENDEE;CN=Sam;PARTSTAT=TENTATIVE;ROLE=OPT-PARTICIPANT:mailto:sam@example.com\r\n\
+ATTENDEE;PARTSTAT=DECLINED:mailto:alex@example.com\r\n\
+END:VEVENT\r\n\
+BEGIN:VEVENT\r\n\
+UID:standup@example.com\r\n\
+RECURRENCE-ID;TZID=Europe/Berlin:20250310T093000\r\n\
+SUMMARY:Standup (moved)\r\n\
+DTSTART;TZID=Europe/Berlin:20250310T110000\r\n\
+DTEND;TZID=Europe/Berlin:20250310T111500\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+        let calendar = IcsCalendar::parse(ics).unwrap();
+        assert_eq!(calendar.name.as_deref(), Some("Team"));
+
+        let events = calendar.events_between(
+            utc("2025-03-01T00:00:00Z"),
+            utc("2025-03-15T00:00:00Z"),
+            &OPTIONS,
+        );
+
+        assert_eq!(
+            starts(&events),
+            [
+                "2025-03-03T08:30:00+00:00",
+                "2025-03-10T10:00:00+00:00",
+                "2025-03-13T08:30:00+00:00",
+            ]
+        );
+        assert_eq!(events[1].title, "Standup (moved)");
+        assert_eq!(events[1].id, "standup@example.com:20250310T083000Z");
+        assert_eq!(
+            events[0].recurring_event_id.as_deref(),
+            Some("standup@example.com")
+        );
+        assert_eq!(
+            events[0].meeting_link.as_deref(),
+            Some("https://acme.zoom.us/j/123456789?pwd=abc")
+        );
+        assert!(events[0].organizer.as_ref().unwrap().is_current_user);
+        assert_eq!(events[0].attendees[0].name.as_deref(), Some("Sam"));
+        assert_eq!(events[0].attendees[0].status, AttendeeStatus::Tentative);
+        assert_eq!(events[0].attendees[0].role, AttendeeRole::Optional);
+        assert_eq!(events[0].attendees[1].status, AttendeeStatus::Declined);
+
+        // DST starts on March 30th; the wall-clock time stays at 09:30.
+        let events = calendar.events_between(
+            utc("2025-03-27T00:00:00Z"),
+            utc("2025-05-01T00:00:00Z"),
+            &OPTIONS,
+        );
+        assert_eq!(
+            starts(&events),
+            ["2025-03-27T08:30:00+00:00", "2025-03-31T07:30:00+00:00"]
+        );
+    }
+
+    #[test]
+    fn test_monthly_and_all_day_rules() {
+        let ics = "BEGIN:VCALENDAR\n\
+BEGIN:VEVENT\n\
+UID:review\n\
+SUMMARY:Monthly review\n\
+DTSTART:20250131T150000Z\n\
+DURATION:PT1H\n\
+RRULE:FREQ=MONTHLY;BYDAY=-1FR;COUNT=3\n\
+END:VEVENT\n\
+BEGIN:VEVENT\n\
+UID:offsite\n\
+SUMMARY:Offsite\n\
+DTSTART;VALUE=DATE:20250204\n\
+DTEND;VALUE=DATE:20250206\n\
+STATUS:TENTATIVE\n\
+END:VEVENT\n\
+END:VCALENDAR\n";
+
+        let calendar = IcsCalendar::parse(ics).unwrap();
+        let events = calendar.events_between(
+            utc("2025-01-01T00:00:00Z"),
+            utc("2025-12-31T00:00:00Z"),
+            &OPTIONS,
+        );
+
+        assert_eq!(
+            starts(&events),
+            [
+                "2025-01-31T15:00:00+00:00",
+                "2025-02-04T00:00:00+00:00",
+                "2025-02-28T15:00:00+00:00",
+                "2025-03-28T15:00:00+00:00",
+            ]
+        );
+        assert!(events[1].is_all_day);
+        assert_eq!(events[1].ended_at, "2025-02-06T00:00:00+00:00");
+        assert_eq!(events[1].status, EventStatus::Tentative);
+        assert!(!events[1].has_recurrence_rules);
+        assert_eq!(events[2].ended_at, "2025-02-28T16:00:00+00:00");
+    }
+
+    #[test]
+    fn test_date_exdate_and_dst_gap() {
+        let ics = "BEGIN:VCALENDAR\n\
+BEGIN:VEVENT\n\
+UID:backup\n\
+SUMMARY:Backup window\n\
+DTSTART;TZID=Europe/Berlin:20250328T023000\n\
+DURATION:PT30M\n\
+RRULE:FREQ=DAILY;COUNT=4\n\
+EXDATE;VALUE=DATE:20250329\n\
+END:VEVENT\n\
+END:VCALENDAR\n";
+
+        let calendar = IcsCalendar::parse(ics).unwrap();
+        let events = calendar.events_between(
+            utc("2025-03-01T00:00:00Z"),
+            utc("2025-04-30T00:00:00Z"),
+            &OPTIONS,
+        );
+
+        // 02:30 doesn't exist on March 30th in Berlin, so it moves to 03:30 CEST.
+        assert_eq!(
+            starts(&events),
+            [
+                "2025-03-28T01:30:00+00:00",
+                "2025-03-30T01:30:00+00:00",
+                "2025-03-31T00:30:00+00:00",
+            ]
+        );
+        assert_eq!(events[1].id, "backup:20250330T013000Z");
+    }
+}
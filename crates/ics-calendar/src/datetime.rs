@@ -0,0 +1,381 @@
+use chrono::{
+    DateTime, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc,
+};
+use chrono_tz::Tz;
+
+use crate::parser::{Component, Property};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Zone {
+    Utc,
+    /// No zone given; interpreted in the calendar's default zone, or the system one.
+    Floating,
+    Named(Tz),
+    /// A `VTIMEZONE` we could not map to an IANA zone, approximated by its standard offset.
+    Fixed(FixedOffset),
+}
+
+impl Zone {
+    pub fn to_utc(self, local: NaiveDateTime) -> DateTime<Utc> {
+        match self {
+            Zone::Utc => local.and_utc(),
+            Zone::Floating => resolve_local(&Local, local),
+            Zone::Named(tz) => resolve_local(&tz, local),
+            Zone::Fixed(offset) => resolve_local(&offset, local),
+        }
+    }
+
+    pub fn local_from_utc(self, utc: DateTime<Utc>) -> NaiveDateTime {
+        match self {
+            Zone::Utc => utc.naive_utc(),
+            Zone::Floating => utc.with_timezone(&Local).naive_local(),
+            Zone::Named(tz) => utc.with_timezone(&tz).naive_local(),
+            Zone::Fixed(offset) => utc.with_timezone(&offset).naive_local(),
+        }
+    }
+}
+
+/// Times that fall into a DST gap are moved forward by an hour, the way calendar apps do.
+fn resolve_local<Z: TimeZone>(zone: &Z, local: NaiveDateTime) -> DateTime<Utc> {
+    zone.from_local_datetime(&local)
+        .earliest()
+        .or_else(|| {
+            zone.from_local_datetime(&(local + Duration::hours(1)))
+                .earliest()
+        })
+        .map(|dt| dt.with_timezone(&Utc))
+        .unwrap_or_else(|| local.and_utc())
+}
+
+/// A `DATE` or `DATE-TIME` value together with the zone its wall-clock time belongs to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct IcalTime {
+    pub local: NaiveDateTime,
+    pub zone: Zone,
+    pub is_date: bool,
+}
+
+impl IcalTime {
+    pub fn to_utc(self) -> DateTime<Utc> {
+        if self.is_date {
+            // All-day events start at midnight UTC, matching what the Google conversion produces.
+            self.local.and_utc()
+        } else {
+            self.zone.to_utc(self.local)
+        }
+    }
+
+    /// This time's wall-clock value in `zone`. Dates become the end of that day, so a date-only
+    /// `UNTIL` still includes occurrences on its last day.
+    pub fn local_in(self, zone: Zone) -> NaiveDateTime {
+        if self.is_date {
+            self.local.date().and_time(NaiveTime::MIN) + Duration::days(1) - Duration::seconds(1)
+        } else if self.zone == zone {
+            self.local
+        } else {
+            zone.local_from_utc(self.to_utc())
+        }
+    }
+
+    /// This time moved onto `other`'s zone, keeping `other`'s time of day for dates.
+    pub fn local_in_zone_of(&self, other: &IcalTime) -> NaiveDateTime {
+        if other.is_date || self.is_date {
+            self.local.date().and_time(other.local.time())
+        } else {
+            self.local_in(other.zone)
+        }
+    }
+
+    /// The `RECURRENCE-ID` value naming this occurrence: the UTC instant, or the date for
+    /// all-day events.
+    pub fn recurrence_id(&self) -> String {
+        if self.is_date {
+            self.local.format("%Y%m%d").to_string()
+        } else {
+            self.to_utc().format("%Y%m%dT%H%M%SZ").to_string()
+        }
+    }
+
+    /// `EXDATE` matching: by instant for timed events, by day when either side is a date.
+    pub fn matches(&self, occurrence: &IcalTime) -> bool {
+        if self.is_date || occurrence.is_date {
+            self.local.date() == occurrence.local.date()
+        } else {
+            self.to_utc() == occurrence.to_utc()
+        }
+    }
+}
+
+/// Resolves `TZID` parameters against IANA names and the calendar's own `VTIMEZONE`s.
+#[derive(Debug, Clone)]
+pub(crate) struct Timezones {
+    definitions: Vec<(String, Zone)>,
+    default: Zone,
+}
+
+impl Timezones {
+    pub fn new(calendar: &Component) -> Self {
+        let definitions = calendar
+            .children("VTIMEZONE")
+            .filter_map(|vtimezone| {
+                let tzid = vtimezone.property("TZID")?.value.clone();
+                let zone = vtimezone
+                    .property("X-LIC-LOCATION")
+                    .and_then(|p| iana_zone(&p.value))
+                    .or_else(|| iana_zone(&tzid))
+                    .or_else(|| standard_offset(vtimezone).map(Zone::Fixed))?;
+                Some((tzid, zone))
+            })
+            .collect();
+
+        let default = calendar
+            .property("X-WR-TIMEZONE")
+            .and_then(|p| iana_zone(&p.value))
+            .unwrap_or(Zone::Floating);
+
+        Self {
+            definitions,
+            default,
+        }
+    }
+
+    pub fn resolve(&self, tzid: &str) -> Zone {
+        self.definitions
+            .iter()
+            .find(|(id, _)| id == tzid)
+            .map(|(_, zone)| *zone)
+            .or_else(|| iana_zone(tzid))
+            .unwrap_or(self.default)
+    }
+
+    /// Parses a `DATE` or `DATE-TIME` property, such as `DTSTART` or `RECURRENCE-ID`.
+    pub fn parse(&self, property: &Property) -> Option<IcalTime> {
+        self.parse_list(property).into_iter().next()
+    }
+
+    /// Parses a comma-separated list of values, as used by `EXDATE` and `RDATE`.
+    pub fn parse_list(&self, property: &Property) -> Vec<IcalTime> {
+        let date_only = property
+            .param("VALUE")
+            .is_some_and(|v| v.eq_ignore_ascii_case("DATE"));
+        let zone = property
+            .param("TZID")
+            .map(|tzid| self.resolve(tzid))
+            .unwrap_or(self.default);
+
+        property
+            .value
+            .split(',')
+            .filter_map(|value| self.parse_value(value.trim(), zone, date_only))
+            .collect()
+    }
+
+    pub fn parse_value(&self, value: &str, zone: Zone, date_only: bool) -> Option<IcalTime> {
+        if date_only || value.len() == 8 {
+            let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+            return Some(IcalTime {
+                local: date.and_time(NaiveTime::MIN),
+                zone,
+                is_date: true,
+            });
+        }
+
+        let (value, zone) = match value.strip_suffix(['Z', 'z']) {
+            Some(value) => (value, Zone::Utc),
+            None => (value, zone),
+        };
+        let local = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+
+        Some(IcalTime {
+            local,
+            zone,
+            is_date: false,
+        })
+    }
+}
+
+/// Accepts plain IANA names as well as the prefixed forms some clients write, like
+/// `/mozilla.org/20050126_1/Europe/Berlin`.
+fn iana_zone(tzid: &str) -> Option<Zone> {
+    let tzid = tzid.trim().trim_matches('"');
+    if tzid.eq_ignore_ascii_case("UTC") || tzid.eq_ignore_ascii_case("GMT") {
+        return Some(Zone::Utc);
+    }
+
+    std::iter::once(0)
+        .chain(tzid.match_indices('/').map(|(i, _)| i + 1))
+        .find_map(|start| tzid[start..].parse::<Tz>().ok())
+        .map(Zone::Named)
+}
+
+fn standard_offset(vtimezone: &Component) -> Option<FixedOffset> {
+    let observance = vtimezone
+        .children("STANDARD")
+        .next()
+        .or_else(|| vtimezone.children("DAYLIGHT").next())?;
+    parse_utc_offset(&observance.property("TZOFFSETTO")?.value)
+}
+
+fn parse_utc_offset(value: &str) -> Option<FixedOffset> {
+    let value = value.trim();
+    let (sign, digits) = match value.as_bytes().first()? {
+        b'+' => (1, &value[1..]),
+        b'-' => (-1, &value[1..]),
+        _ => (1, value),
+    };
+    if digits.len() < 4 || !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let hours: i32 = digits[0..2].parse().ok()?;
+    let minutes: i32 = digits[2..4].parse().ok()?;
+    let seconds: i32 = digits.get(4..6).and_then(|s| s.parse().ok()).unwrap_or(0);
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60 + seconds))
+}
+
+/// Parses a `DURATION` value such as `PT1H30M` or `P1D`.
+pub(crate) fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let (negative, value) = match value.as_bytes().first()? {
+        b'-' => (true, &value[1..]),
+        b'+' => (false, &value[1..]),
+        _ => (false, value),
+    };
+    let mut rest = value.strip_prefix(['P', 'p'])?;
+    let mut total = Duration::zero();
+    let mut in_time = false;
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix(['T', 't']) {
+            in_time = true;
+            rest = after;
+            continue;
+        }
+
+        let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+        let amount: i64 = rest[..digits].parse().ok()?;
+        let unit = rest[digits..].chars().next()?.to_ascii_uppercase();
+        let part = match (unit, in_time) {
+            ('W', false) => Duration::try_weeks(amount),
+            ('D', false) => Duration::try_days(amount),
+            ('H', true) => Duration::try_hours(amount),
+            ('M', true) => Duration::try_minutes(amount),
+            ('S', true) => Duration::try_seconds(amount),
+            _ => return None,
+        }?;
+        total = total.checked_add(&part)?;
+        rest = &rest[digits + 1..];
+    }
+
+    Some(if negative { -total } else { total })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse;
+
+    #[test]
+    fn test_parse_duration() {
+        let cases = [
+            ("PT1H30M", Some(Duration::minutes(90))),
+            ("P1D", Some(Duration::days(1))),
+            ("P2W", Some(Duration::weeks(2))),
+            ("P1DT2H3M4S", Some(Duration::seconds(93_784))),
+            ("-PT15M", Some(Duration::minutes(-15))),
+            ("+PT15M", Some(Duration::minutes(15))),
+            ("P", Some(Duration::zero())),
+            ("PT1D", None),
+            ("P1H", None),
+            ("1H", None),
+            ("PT", Some(Duration::zero())),
+            ("P99999999999999W", None),
+            ("PT99999999999999999999S", None),
+            ("P60000000000D60000000000D", None),
+        ];
+
+        for (value, expected) in cases {
+            assert_eq!(parse_duration(value), expected, "{value}");
+        }
+    }
+
+    #[test]
+    fn test_parse_utc_offset() {
+        assert_eq!(parse_utc_offset("+0100"), FixedOffset::east_opt(3600));
+        assert_eq!(parse_utc_offset("-0530"), FixedOffset::east_opt(-19_800));
+        assert_eq!(parse_utc_offset("+013045"), FixedOffset::east_opt(5445));
+        assert_eq!(parse_utc_offset("+1"), None);
+    }
+
+    #[test]
+    fn test_resolves_timezones() {
+        let calendar = parse(
+            "BEGIN:VCALENDAR\n\
+X-WR-TIMEZONE:America/New_York\n\
+BEGIN:VTIMEZONE\n\
+TZID:Custom Standard Time\n\
+BEGIN:STANDARD\n\
+TZOFFSETTO:+0530\n\
+END:STANDARD\n\
+END:VTIMEZONE\n\
+BEGIN:VTIMEZONE\n\
+TZID:W. Europe\n\
+X-LIC-LOCATION:Europe/Berlin\n\
+END:VTIMEZONE\n\
+END:VCALENDAR\n",
+        )
+        .unwrap();
+        let timezones = Timezones::new(&calendar[0]);
+
+        assert_eq!(
+            timezones.resolve("W. Europe"),
+            Zone::Named(chrono_tz::Europe::Berlin)
+        );
+        assert_eq!(
+            timezones.resolve("Custom Standard Time"),
+            Zone::Fixed(FixedOffset::east_opt(19_800).unwrap())
+        );
+        assert_eq!(
+            timezones.resolve("/mozilla.org/20050126_1/Asia/Tokyo"),
+            Zone::Named(chrono_tz::Asia::Tokyo)
+        );
+        assert_eq!(timezones.resolve("UTC"), Zone::Utc);
+        assert_eq!(
+            timezones.resolve("Nowhere"),
+            Zone::Named(chrono_tz::America::New_York)
+        );
+    }
+
+    #[test]
+    fn test_dst_gap_moves_forward() {
+        let berlin = Zone::Named(chrono_tz::Europe::Berlin);
+        let local = NaiveDate::from_ymd_opt(2025, 3, 30)
+            .unwrap()
+            .and_hms_opt(2, 30, 0)
+            .unwrap();
+
+        assert_eq!(
+            berlin.to_utc(local).to_rfc3339(),
+            "2025-03-30T01:30:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_recurrence_id_and_matches() {
+        let timezones = Timezones::new(&Component::default());
+        let berlin = Zone::Named(chrono_tz::Europe::Berlin);
+        let timed = timezones
+            .parse_value("20250310T093000", berlin, false)
+            .unwrap();
+        let utc = timezones
+            .parse_value("20250310T083000Z", berlin, false)
+            .unwrap();
+        let date = timezones.parse_value("20250310", berlin, false).unwrap();
+
+        assert_eq!(timed.recurrence_id(), "20250310T083000Z");
+        assert_eq!(date.recurrence_id(), "20250310");
+        assert!(timed.matches(&utc));
+        assert!(date.matches(&timed));
+        assert_eq!(date.local_in(berlin).to_string(), "2025-03-10 23:59:59");
+    }
+}
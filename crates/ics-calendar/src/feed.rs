@@ -0,0 +1,26 @@
+use crate::Error;
+
+/// Reads an ICS feed from an `http(s)://` or `webcal://` URL, a `file://` URL, or a local path.
+pub async fn fetch_feed(source: &str) -> Result<String, Error> {
+    let source = source.trim();
+
+    if let Some(rest) = source.strip_prefix("webcal://") {
+        return fetch_url(&format!("https://{rest}")).await;
+    }
+    if source.starts_with("https://") || source.starts_with("http://") {
+        return fetch_url(source).await;
+    }
+
+    let path = source.strip_prefix("file://").unwrap_or(source);
+    tokio::fs::read_to_string(path)
+        .await
+        .map_err(|source| Error::Io {
+            path: path.to_string(),
+            source,
+        })
+}
+
+async fn fetch_url(url: &str) -> Result<String, Error> {
+    let response = reqwest::get(url).await?.error_for_status()?;
+    Ok(response.text().await?)
+}
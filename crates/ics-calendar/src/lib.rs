@@ -0,0 +1,10 @@
+mod calendar;
+mod datetime;
+mod error;
+mod feed;
+mod parser;
+mod recurrence;
+
+pub use calendar::{ExpandOptions, IcsCalendar};
+pub use error::Error;
+pub use feed::fetch_feed;
@@ -0,0 +1,259 @@
+use crate::Error;
+
+/// A content line such as `DTSTART;TZID=Europe/Berlin:20250101T100000`, after unfolding.
+#[derive(Debug, Clone)]
+pub(crate) struct Property {
+    pub name: String,
+    pub params: Vec<(String, String)>,
+    pub value: String,
+}
+
+impl Property {
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The value with TEXT escapes (RFC 5545 3.3.11) resolved.
+    pub fn text(&self) -> String {
+        unescape_text(&self.value)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Component {
+    pub name: String,
+    pub properties: Vec<Property>,
+    pub components: Vec<Component>,
+    /// The unfolded content lines, from `BEGIN` to `END`.
+    pub raw: String,
+}
+
+impl Component {
+    pub fn property(&self, name: &str) -> Option<&Property> {
+        self.properties
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn properties<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Property> {
+        self.properties
+            .iter()
+            .filter(move |p| p.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn text(&self, name: &str) -> Option<String> {
+        self.property(name)
+            .map(Property::text)
+            .filter(|text| !text.trim().is_empty())
+    }
+
+    pub fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Component> {
+        self.components
+            .iter()
+            .filter(move |c| c.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Parses every top-level component in `input`, usually a single `VCALENDAR`.
+pub(crate) fn parse(input: &str) -> Result<Vec<Component>, Error> {
+    let mut roots = Vec::new();
+    let mut stack: Vec<Component> = Vec::new();
+
+    for (line_no, line) in unfold(input) {
+        let property = parse_line(&line).ok_or_else(|| Error::Parse {
+            line: line_no,
+            message: format!("malformed content line '{}'", truncate(&line)),
+        })?;
+
+        if property.name.eq_ignore_ascii_case("BEGIN") {
+            stack.push(Component {
+                name: property.value.to_ascii_uppercase(),
+                ..Default::default()
+            });
+        } else if property.name.eq_ignore_ascii_case("END") {
+            let mut component = stack.pop().ok_or_else(|| Error::Parse {
+                line: line_no,
+                message: format!("unexpected END:{}", property.value),
+            })?;
+            if !component.name.eq_ignore_ascii_case(&property.value) {
+                return Err(Error::Parse {
+                    line: line_no,
+                    message: format!(
+                        "expected END:{}, found END:{}",
+                        component.name, property.value
+                    ),
+                });
+            }
+            component.raw.push_str(&line);
+
+            match stack.last_mut() {
+                Some(parent) => parent.components.push(component),
+                None => roots.push(component),
+            }
+        } else if let Some(current) = stack.last_mut() {
+            current.properties.push(property);
+        }
+
+        for component in &mut stack {
+            component.raw.push_str(&line);
+            component.raw.push_str("\r\n");
+        }
+    }
+
+    if let Some(open) = stack.last() {
+        return Err(Error::Parse {
+            line: input.lines().count(),
+            message: format!("missing END:{}", open.name),
+        });
+    }
+
+    Ok(roots)
+}
+
+/// Joins folded lines (RFC 5545 3.1), keeping the number of the line each one started on.
+fn unfold(input: &str) -> Vec<(usize, String)> {
+    let mut lines: Vec<(usize, String)> = Vec::new();
+
+    for (idx, line) in input.lines().enumerate() {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+
+        if let Some(rest) = line.strip_prefix([' ', '\t'])
+            && let Some((_, last)) = lines.last_mut()
+        {
+            last.push_str(rest);
+        } else if !line.trim().is_empty() {
+            lines.push((idx + 1, line.to_string()));
+        }
+    }
+
+    lines
+}
+
+fn parse_line(line: &str) -> Option<Property> {
+    let name_end = line.find([';', ':'])?;
+    let name = line[..name_end].trim().to_ascii_uppercase();
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut params = Vec::new();
+    let mut rest = &line[name_end..];
+
+    while let Some(after) = rest.strip_prefix(';') {
+        let eq = after.find('=')?;
+        let key = after[..eq].trim().to_ascii_uppercase();
+        let mut value = String::new();
+        let mut chars = after[eq + 1..].char_indices();
+        let mut in_quotes = false;
+        let mut end = after.len() - eq - 1;
+
+        for (i, c) in chars.by_ref() {
+            match c {
+                '"' => in_quotes = !in_quotes,
+                ';' | ':' if !in_quotes => {
+                    end = i;
+                    break;
+                }
+                _ => value.push(c),
+            }
+        }
+
+        params.push((key, value));
+        rest = &after[eq + 1 + end..];
+    }
+
+    let value = rest.strip_prefix(':')?;
+    Some(Property {
+        name,
+        params,
+        value: value.to_string(),
+    })
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n' | 'N') => out.push('\n'),
+            Some(other) => out.push(other),
+            None => out.push('\\'),
+        }
+    }
+
+    out
+}
+
+fn truncate(line: &str) -> String {
+    line.chars().take(60).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_nested_components() {
+        let input = "BEGIN:VCALENDAR\r\n\
+VERSION:2.0\r\n\
+BEGIN:VEVENT\r\n\
+SUMMARY:Planning\\, Q3\\nRoom 4\r\n\
+DTSTART;TZID=\"America/New_York\";VALUE=DATE-TIME:20250101T100000\r\n\
+DESCRIPTION:Line one\r\n  continued\r\n\
+END:VEVENT\r\n\
+END:VCALENDAR\r\n";
+
+        let roots = parse(input).unwrap();
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0].name, "VCALENDAR");
+
+        let event = roots[0].children("vevent").next().unwrap();
+        assert_eq!(
+            event.text("SUMMARY").as_deref(),
+            Some("Planning, Q3\nRoom 4")
+        );
+        assert_eq!(
+            event.text("description").as_deref(),
+            Some("Line one continued")
+        );
+
+        let start = event.property("DTSTART").unwrap();
+        assert_eq!(start.param("tzid"), Some("America/New_York"));
+        assert_eq!(start.param("VALUE"), Some("DATE-TIME"));
+        assert_eq!(start.value, "20250101T100000");
+        assert!(event.raw.starts_with("BEGIN:VEVENT\r\n"));
+        assert!(event.raw.ends_with("END:VEVENT"));
+    }
+
+    #[test]
+    fn test_quoted_param_keeps_separators() {
+        let property = parse_line("ATTENDEE;CN=\"Doe; Jane: PM\":mailto:jane@example.com").unwrap();
+        assert_eq!(property.param("CN"), Some("Doe; Jane: PM"));
+        assert_eq!(property.value, "mailto:jane@example.com");
+    }
+
+    #[test]
+    fn test_reports_malformed_input() {
+        let cases = [
+            ("BEGIN:VCALENDAR\nnot a property\nEND:VCALENDAR\n", 2),
+            ("BEGIN:VCALENDAR\nBEGIN:VEVENT\nEND:VCALENDAR\n", 3),
+            ("END:VEVENT\n", 1),
+            ("BEGIN:VCALENDAR\nBEGIN:VEVENT\n", 2),
+        ];
+
+        for (input, expected_line) in cases {
+            match parse(input) {
+                Err(Error::Parse { line, .. }) => assert_eq!(line, expected_line, "{input:?}"),
+                other => panic!("expected a parse error for {input:?}, got {other:?}"),
+            }
+        }
+    }
+}
@@ -0,0 +1,479 @@
+use chrono::{Datelike, Duration, Months, NaiveDate, NaiveDateTime, Weekday};
+
+use crate::datetime::{IcalTime, Timezones, Zone};
+
+/// Upper bound on recurrence periods walked per event, so a malformed rule can't loop forever.
+const MAX_PERIODS: u32 = 50_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// The subset of RFC 5545 `RRULE` that calendar servers emit for meetings. Sub-daily
+/// frequencies and `BYHOUR`/`BYMINUTE`/`BYWEEKNO`/`BYYEARDAY` are not supported.
+#[derive(Debug, Clone)]
+pub(crate) struct RecurrenceRule {
+    freq: Frequency,
+    interval: u32,
+    count: Option<u32>,
+    until: Option<IcalTime>,
+    by_day: Vec<(Option<i32>, Weekday)>,
+    by_month_day: Vec<i32>,
+    by_month: Vec<u32>,
+    by_set_pos: Vec<i32>,
+    week_start: Weekday,
+}
+
+impl RecurrenceRule {
+    pub fn parse(value: &str, timezones: &Timezones) -> Option<Self> {
+        let mut rule = Self {
+            freq: Frequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            by_month: Vec::new(),
+            by_set_pos: Vec::new(),
+            week_start: Weekday::Mon,
+        };
+        let mut freq = None;
+
+        for part in value.split(';') {
+            let Some((key, value)) = part.split_once('=') else {
+                continue;
+            };
+            let values = || value.split(',').map(str::trim);
+
+            match key.trim().to_ascii_uppercase().as_str() {
+                "FREQ" => {
+                    freq = Some(match value.trim().to_ascii_uppercase().as_str() {
+                        "DAILY" => Frequency::Daily,
+                        "WEEKLY" => Frequency::Weekly,
+                        "MONTHLY" => Frequency::Monthly,
+                        "YEARLY" => Frequency::Yearly,
+                        _ => return None,
+                    })
+                }
+                "INTERVAL" => rule.interval = value.trim().parse().ok().filter(|n| *n > 0)?,
+                "COUNT" => rule.count = Some(value.trim().parse().ok()?),
+                "UNTIL" => {
+                    rule.until = Some(timezones.parse_value(value.trim(), Zone::Utc, false)?)
+                }
+                "BYDAY" => rule.by_day = values().map(parse_by_day).collect::<Option<_>>()?,
+                "BYMONTHDAY" => {
+                    rule.by_month_day = values().map(|v| v.parse().ok()).collect::<Option<_>>()?
+                }
+                "BYMONTH" => {
+                    rule.by_month = values().map(|v| v.parse().ok()).collect::<Option<_>>()?
+                }
+                "BYSETPOS" => {
+                    rule.by_set_pos = values().map(|v| v.parse().ok()).collect::<Option<_>>()?
+                }
+                "WKST" => rule.week_start = parse_weekday(value.trim())?,
+                "BYHOUR" | "BYMINUTE" | "BYSECOND" | "BYWEEKNO" | "BYYEARDAY" => return None,
+                _ => {}
+            }
+        }
+
+        rule.freq = freq?;
+        Some(rule)
+    }
+
+    /// Occurrence start times in the event's wall-clock time, beginning with `start` itself and
+    /// ending at the rule's `COUNT`/`UNTIL` or once past `horizon`.
+    pub fn occurrences(
+        &self,
+        start: NaiveDateTime,
+        zone: Zone,
+        horizon: NaiveDateTime,
+    ) -> Vec<NaiveDateTime> {
+        let until = self.until.map(|until| until.local_in(zone));
+        let count = self.count.map(|count| count.max(1));
+        let mut occurrences = vec![start];
+
+        for period in 0..MAX_PERIODS {
+            let Some((period_start, mut candidates)) = self.period(start, period) else {
+                break;
+            };
+            if period_start > horizon.date() || until.is_some_and(|u| period_start > u.date()) {
+                break;
+            }
+
+            candidates.sort();
+            candidates.dedup();
+            if !self.by_set_pos.is_empty() {
+                candidates = select_positions(&candidates, &self.by_set_pos);
+            }
+
+            for date in candidates {
+                let occurrence = date.and_time(start.time());
+                if occurrence <= start {
+                    continue;
+                }
+                if count.is_some_and(|c| occurrences.len() >= c as usize)
+                    || occurrence > horizon
+                    || until.is_some_and(|u| occurrence > u)
+                {
+                    return occurrences;
+                }
+                occurrences.push(occurrence);
+            }
+            if count.is_some_and(|c| occurrences.len() >= c as usize) {
+                break;
+            }
+        }
+
+        occurrences
+    }
+
+    /// The first day of the `n`th period after `start`, and the candidate dates within it.
+    fn period(&self, start: NaiveDateTime, n: u32) -> Option<(NaiveDate, Vec<NaiveDate>)> {
+        let step = n.checked_mul(self.interval)?;
+        let start = start.date();
+
+        match self.freq {
+            Frequency::Daily => {
+                let date = start.checked_add_signed(Duration::days(step as i64))?;
+                let matches = (self.by_month.is_empty() || self.by_month.contains(&date.month()))
+                    && (self.by_month_day.is_empty() || month_days(date, &self.by_month_day))
+                    && (self.by_day.is_empty()
+                        || self.by_day.iter().any(|(_, wd)| *wd == date.weekday()));
+                Some((date, if matches { vec![date] } else { vec![] }))
+            }
+            Frequency::Weekly => {
+                let offset = days_since(start.weekday(), self.week_start);
+                let week = start.checked_add_signed(Duration::days(7 * step as i64 - offset))?;
+                let weekdays: Vec<Weekday> = if self.by_day.is_empty() {
+                    vec![start.weekday()]
+                } else {
+                    self.by_day.iter().map(|(_, wd)| *wd).collect()
+                };
+                let dates = weekdays
+                    .into_iter()
+                    .filter_map(|wd| {
+                        week.checked_add_signed(Duration::days(days_since(wd, self.week_start)))
+                    })
+                    .filter(|date| {
+                        self.by_month.is_empty() || self.by_month.contains(&date.month())
+                    })
+                    .collect();
+                Some((week, dates))
+            }
+            Frequency::Monthly => {
+                let month = start.with_day(1)?.checked_add_months(Months::new(step))?;
+                if !self.by_month.is_empty() && !self.by_month.contains(&month.month()) {
+                    return Some((month, vec![]));
+                }
+                Some((month, self.month_dates(month, start.day())))
+            }
+            Frequency::Yearly => {
+                let year = start.year().checked_add(step as i32)?;
+                let year_start = NaiveDate::from_ymd_opt(year, 1, 1)?;
+
+                let has_ordinals = self.by_day.iter().any(|(ord, _)| ord.is_some());
+                if self.by_month.is_empty() && self.by_month_day.is_empty() && has_ordinals {
+                    let dates = self
+                        .by_day
+                        .iter()
+                        .filter_map(|(ord, wd)| {
+                            let all =
+                                weekdays_between(year_start, year_start.with_year(year + 1)?, *wd);
+                            pick(&all, *ord)
+                        })
+                        .flatten()
+                        .collect();
+                    return Some((year_start, dates));
+                }
+
+                let months: Vec<u32> = if !self.by_month.is_empty() {
+                    self.by_month.clone()
+                } else if !self.by_month_day.is_empty() || !self.by_day.is_empty() {
+                    (1..=12).collect()
+                } else {
+                    vec![start.month()]
+                };
+                let dates = months
+                    .into_iter()
+                    .filter_map(|m| NaiveDate::from_ymd_opt(year, m, 1))
+                    .flat_map(|month| self.month_dates(month, start.day()))
+                    .collect();
+                Some((year_start, dates))
+            }
+        }
+    }
+
+    /// Candidate dates in the month starting at `month`, from `BYMONTHDAY` and `BYDAY`, or the
+    /// start's day of the month when neither is set. Months without that day are skipped.
+    fn month_dates(&self, month: NaiveDate, default_day: u32) -> Vec<NaiveDate> {
+        let Some(next_month) = month.checked_add_months(Months::new(1)) else {
+            return vec![];
+        };
+        let days_in_month = (next_month - month).num_days() as i32;
+
+        let by_month_day: Vec<NaiveDate> = self
+            .by_month_day
+            .iter()
+            .filter_map(|&day| {
+                let day = if day < 0 {
+                    days_in_month + day + 1
+                } else {
+                    day
+                };
+                (1..=days_in_month)
+                    .contains(&day)
+                    .then(|| month.with_day(day as u32))
+                    .flatten()
+            })
+            .collect();
+
+        let by_day: Vec<NaiveDate> = self
+            .by_day
+            .iter()
+            .filter_map(|(ord, wd)| pick(&weekdays_between(month, next_month, *wd), *ord))
+            .flatten()
+            .collect();
+
+        match (self.by_month_day.is_empty(), self.by_day.is_empty()) {
+            (true, true) => month.with_day(default_day).into_iter().collect(),
+            (false, true) => by_month_day,
+            (true, false) => by_day,
+            (false, false) => by_month_day
+                .into_iter()
+                .filter(|date| by_day.contains(date))
+                .collect(),
+        }
+    }
+}
+
+fn month_days(date: NaiveDate, by_month_day: &[i32]) -> bool {
+    let Some(next_month) = date
+        .with_day(1)
+        .and_then(|first| first.checked_add_months(Months::new(1)))
+    else {
+        return false;
+    };
+    let days_in_month = next_month.pred_opt().map(|d| d.day() as i32).unwrap_or(31);
+
+    by_month_day.iter().any(|&day| {
+        let day = if day < 0 {
+            days_in_month + day + 1
+        } else {
+            day
+        };
+        day == date.day() as i32
+    })
+}
+
+fn weekdays_between(from: NaiveDate, to: NaiveDate, weekday: Weekday) -> Vec<NaiveDate> {
+    let first = from + Duration::days(days_since(weekday, from.weekday()));
+    first
+        .iter_days()
+        .step_by(7)
+        .take_while(|date| *date < to)
+        .collect()
+}
+
+/// All of `dates` without an ordinal, otherwise the `n`th (or `n`th from last, if negative).
+fn pick(dates: &[NaiveDate], ordinal: Option<i32>) -> Option<Vec<NaiveDate>> {
+    match ordinal {
+        None => Some(dates.to_vec()),
+        Some(n) => {
+            let idx = if n > 0 { n - 1 } else { dates.len() as i32 + n };
+            let date = *dates.get(usize::try_from(idx).ok()?)?;
+            Some(vec![date])
+        }
+    }
+}
+
+fn select_positions(candidates: &[NaiveDate], positions: &[i32]) -> Vec<NaiveDate> {
+    let mut selected: Vec<NaiveDate> = positions
+        .iter()
+        .filter_map(|&pos| pick(candidates, Some(pos)))
+        .flatten()
+        .collect();
+    selected.sort();
+    selected.dedup();
+    selected
+}
+
+/// Days from `from` forward to the next `weekday` (0 if they are the same day).
+fn days_since(weekday: Weekday, from: Weekday) -> i64 {
+    (weekday.num_days_from_monday() as i64 - from.num_days_from_monday() as i64).rem_euclid(7)
+}
+
+fn parse_by_day(value: &str) -> Option<(Option<i32>, Weekday)> {
+    let split = value.len().checked_sub(2)?;
+    let (ordinal, weekday) = value.split_at(split);
+    let ordinal = match ordinal.trim_start_matches('+') {
+        "" => None,
+        n => Some(n.parse().ok().filter(|n: &i32| *n != 0)?),
+    };
+    Some((ordinal, parse_weekday(weekday)?))
+}
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    Some(match value.to_ascii_uppercase().as_str() {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::Component;
+
+    fn dt(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M").unwrap()
+    }
+
+    fn expand(rule: &str, start: &str, horizon: &str) -> Vec<String> {
+        let timezones = Timezones::new(&Component::default());
+        RecurrenceRule::parse(rule, &timezones)
+            .unwrap()
+            .occurrences(dt(start), Zone::Utc, dt(horizon))
+            .into_iter()
+            .map(|o| o.format("%Y-%m-%dT%H:%M").to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_by_set_pos_picks_last_weekday_of_month() {
+        assert_eq!(
+            expand(
+                "FREQ=MONTHLY;BYDAY=MO,TU,WE,TH,FR;BYSETPOS=-1;COUNT=3",
+                "2025-01-31T10:00",
+                "2026-01-01T00:00",
+            ),
+            ["2025-01-31T10:00", "2025-02-28T10:00", "2025-03-31T10:00"]
+        );
+    }
+
+    #[test]
+    fn test_yearly_by_month_and_ordinal_day() {
+        assert_eq!(
+            expand(
+                "FREQ=YEARLY;BYMONTH=11;BYDAY=4TH;COUNT=3",
+                "2025-11-27T12:00",
+                "2030-01-01T00:00",
+            ),
+            ["2025-11-27T12:00", "2026-11-26T12:00", "2027-11-25T12:00"]
+        );
+        assert_eq!(
+            expand(
+                "FREQ=YEARLY;COUNT=3",
+                "2024-02-29T09:00",
+                "2040-01-01T00:00"
+            ),
+            ["2024-02-29T09:00", "2028-02-29T09:00", "2032-02-29T09:00"]
+        );
+    }
+
+    #[test]
+    fn test_interval_skips_periods() {
+        assert_eq!(
+            expand(
+                "FREQ=WEEKLY;INTERVAL=2;BYDAY=TU,TH;COUNT=5",
+                "2025-03-04T09:00",
+                "2026-01-01T00:00",
+            ),
+            [
+                "2025-03-04T09:00",
+                "2025-03-06T09:00",
+                "2025-03-18T09:00",
+                "2025-03-20T09:00",
+                "2025-04-01T09:00",
+            ]
+        );
+        assert_eq!(
+            expand(
+                "FREQ=MONTHLY;INTERVAL=3",
+                "2025-01-15T09:00",
+                "2025-12-31T00:00"
+            ),
+            [
+                "2025-01-15T09:00",
+                "2025-04-15T09:00",
+                "2025-07-15T09:00",
+                "2025-10-15T09:00",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_count_and_until_stop_at_whichever_comes_first() {
+        assert_eq!(
+            expand(
+                "FREQ=DAILY;COUNT=10;UNTIL=20250103T090000Z",
+                "2025-01-01T09:00",
+                "2026-01-01T00:00",
+            ),
+            ["2025-01-01T09:00", "2025-01-02T09:00", "2025-01-03T09:00"]
+        );
+        assert_eq!(
+            expand(
+                "FREQ=DAILY;COUNT=2;UNTIL=20250103T090000Z",
+                "2025-01-01T09:00",
+                "2026-01-01T00:00",
+            ),
+            ["2025-01-01T09:00", "2025-01-02T09:00"]
+        );
+    }
+
+    #[test]
+    fn test_date_until_includes_its_last_day() {
+        assert_eq!(
+            expand(
+                "FREQ=DAILY;UNTIL=20250103",
+                "2025-01-01T17:00",
+                "2026-01-01T00:00"
+            ),
+            ["2025-01-01T17:00", "2025-01-02T17:00", "2025-01-03T17:00"]
+        );
+    }
+
+    #[test]
+    fn test_horizon_bounds_open_ended_rules() {
+        assert_eq!(
+            expand("FREQ=DAILY", "2025-01-01T09:00", "2025-01-03T08:00"),
+            ["2025-01-01T09:00", "2025-01-02T09:00"]
+        );
+    }
+
+    #[test]
+    fn test_monthly_skips_months_without_the_day() {
+        assert_eq!(
+            expand(
+                "FREQ=MONTHLY;COUNT=3",
+                "2025-01-31T09:00",
+                "2026-01-01T00:00"
+            ),
+            ["2025-01-31T09:00", "2025-03-31T09:00", "2025-05-31T09:00"]
+        );
+    }
+
+    #[test]
+    fn test_rejects_unsupported_rules() {
+        let timezones = Timezones::new(&Component::default());
+        for rule in [
+            "FREQ=HOURLY",
+            "FREQ=DAILY;BYHOUR=9",
+            "FREQ=DAILY;INTERVAL=0",
+            "INTERVAL=2",
+            "FREQ=WEEKLY;BYDAY=0MO",
+        ] {
+            assert!(RecurrenceRule::parse(rule, &timezones).is_none(), "{rule}");
+        }
+    }
+}
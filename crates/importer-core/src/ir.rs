@@ -16,6 +16,7 @@ pub struct Collection {
     pub enhanced_notes: Vec<EnhancedNote>,
     pub tags: Vec<Tag>,
     pub tag_mappings: Vec<TagMapping>,
+    pub attachments: Vec<Attachment>,
 }
 
 impl std::fmt::Display for Collection {
@@ -29,6 +30,7 @@ impl std::fmt::Display for Collection {
         writeln!(f, "enhanced_notes: {}", self.enhanced_notes.len())?;
         writeln!(f, "tags: {}", self.tags.len())?;
         writeln!(f, "tag_mappings: {}", self.tag_mappings.len())?;
+        writeln!(f, "attachments: {}", self.attachments.len())?;
 
         if let Some(s) = self.sessions.first() {
             writeln!(f, "\n[First Session]")?;
@@ -274,3 +276,23 @@ common_derives! {
         pub session_id: String,
     }
 }
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AttachmentKind {
+    Audio,
+    #[default]
+    File,
+}
+
+common_derives! {
+    pub struct Attachment {
+        #[serde(default)]
+        pub session_id: String,
+        #[serde(default)]
+        pub kind: AttachmentKind,
+        pub source_path: String,
+        #[serde(default)]
+        pub filename: String,
+    }
+}
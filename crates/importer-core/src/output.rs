@@ -1,4 +1,4 @@
-use crate::ir::Collection;
+use crate::ir::{Collection, SpeakerHint, Word};
 use serde::Serialize;
 use serde_json::{Map, Value};
 
@@ -62,6 +62,15 @@ struct WordInTranscript {
     channel: i32,
 }
 
+#[derive(Serialize)]
+struct SpeakerHintInTranscript {
+    id: String,
+    word_id: String,
+    #[serde(rename = "type")]
+    hint_type: String,
+    value: String,
+}
+
 #[derive(Serialize)]
 struct SessionParticipantOutput {
     user_id: String,
@@ -216,6 +225,19 @@ fn insert_transcripts_and_words(tables: &mut Map<String, Value>, data: &Collecti
             })
             .collect();
 
+        let hints_json: Vec<SpeakerHintInTranscript> = transcript
+            .speaker_hints
+            .iter()
+            .filter(|hint| is_app_speaker_hint(hint, &transcript.words))
+            .enumerate()
+            .map(|(idx, hint)| SpeakerHintInTranscript {
+                id: format!("{}-hint-{}", transcript.id, idx),
+                word_id: hint.word_id.clone(),
+                hint_type: hint.hint_type.clone(),
+                value: hint.value.clone(),
+            })
+            .collect();
+
         let value = TranscriptOutput {
             user_id: user_id.to_string(),
             created_at: normalize_datetime(&transcript.created_at),
@@ -223,7 +245,7 @@ fn insert_transcripts_and_words(tables: &mut Map<String, Value>, data: &Collecti
             started_at: transcript.start_ms.unwrap_or(0.0) as i64,
             ended_at: transcript.end_ms.map(|ms| ms as i64).unwrap_or(0),
             words: serde_json::to_string(&words_json).unwrap_or_else(|_| "[]".to_string()),
-            speaker_hints: "[]".to_string(),
+            speaker_hints: serde_json::to_string(&hints_json).unwrap_or_else(|_| "[]".to_string()),
         };
         transcript_entries.insert(transcript.id.clone(), serde_json::to_value(value).unwrap());
     }
@@ -231,6 +253,23 @@ fn insert_transcripts_and_words(tables: &mut Map<String, Value>, data: &Collecti
     tables.insert("transcripts".to_string(), Value::Object(transcript_entries));
 }
 
+// Hints are only exported in the shapes the app reads, attached to a word of the same transcript.
+// Anything else, e.g. hints carried over from another app's export, would break the transcript
+// view, so it is dropped here rather than by each source.
+fn is_app_speaker_hint(hint: &SpeakerHint, words: &[Word]) -> bool {
+    if !words.iter().any(|word| word.id == hint.word_id) {
+        return false;
+    }
+    let Ok(value) = serde_json::from_str::<Value>(&hint.value) else {
+        return false;
+    };
+    match hint.hint_type.as_str() {
+        "user_speaker_assignment" => value.get("human_id").is_some_and(Value::is_string),
+        "provider_speaker_index" => value.get("speaker_index").is_some_and(Value::is_u64),
+        _ => false,
+    }
+}
+
 fn insert_participants(tables: &mut Map<String, Value>, data: &Collection, user_id: &str) {
     if data.participants.is_empty() {
         return;
@@ -6,6 +6,10 @@ pub enum Error {
     NotSupportedLanguage(String),
     #[error("Invalid language code: {0}")]
     InvalidLanguageCode(String),
+    #[error("Language detection failed: {0}")]
+    DetectionFailed(String),
+    #[error("Language detector is busy")]
+    DetectorBusy,
 }
 
 impl Serialize for Error {
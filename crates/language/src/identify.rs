@@ -0,0 +1,137 @@
+use crate::Language;
+
+/// Sample rate detectors expect their input at.
+pub const DETECTION_SAMPLE_RATE: u32 = 16000;
+/// Below this, the declared languages are kept.
+pub const DEFAULT_MIN_CONFIDENCE: f32 = 0.7;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanguageScore {
+    pub language: Language,
+    pub probability: f32,
+}
+
+/// Identifies the spoken language from mono audio at [`DETECTION_SAMPLE_RATE`].
+pub trait SpokenLanguageDetector: Send + Sync {
+    fn detect(&self, samples: &[f32]) -> Result<Vec<LanguageScore>, crate::Error>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LanguageSource {
+    Detected,
+    Declared,
+}
+
+impl LanguageSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LanguageSource::Detected => "detected",
+            LanguageSource::Declared => "declared",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpokenLanguage {
+    pub languages: Vec<Language>,
+    pub source: LanguageSource,
+    /// Probability of the top detected language, if detection produced anything.
+    pub confidence: Option<f32>,
+}
+
+impl SpokenLanguage {
+    pub fn declared(languages: &[Language]) -> Self {
+        Self {
+            languages: languages.to_vec(),
+            source: LanguageSource::Declared,
+            confidence: None,
+        }
+    }
+}
+
+/// Picks the detected language when the detector is confident enough, otherwise keeps the
+/// declared ones. A declared language with a region wins over the bare detected code.
+pub fn resolve_spoken_language(
+    scores: &[LanguageScore],
+    declared: &[Language],
+    min_confidence: f32,
+) -> SpokenLanguage {
+    let Some(best) = scores
+        .iter()
+        .max_by(|a, b| a.probability.total_cmp(&b.probability))
+    else {
+        return SpokenLanguage::declared(declared);
+    };
+
+    if best.probability < min_confidence {
+        return SpokenLanguage {
+            confidence: Some(best.probability),
+            ..SpokenLanguage::declared(declared)
+        };
+    }
+
+    let language = declared
+        .iter()
+        .find(|lang| lang.iso639() == best.language.iso639())
+        .cloned()
+        .unwrap_or_else(|| best.language.clone());
+
+    SpokenLanguage {
+        languages: vec![language],
+        source: LanguageSource::Detected,
+        confidence: Some(best.probability),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ISO639;
+
+    fn score(iso639: ISO639, probability: f32) -> LanguageScore {
+        LanguageScore {
+            language: Language::new(iso639),
+            probability,
+        }
+    }
+
+    #[test]
+    fn test_confident_detection_overrides_declared() {
+        let declared = [Language::new(ISO639::En)];
+        let scores = [score(ISO639::En, 0.05), score(ISO639::Ko, 0.92)];
+
+        let spoken = resolve_spoken_language(&scores, &declared, DEFAULT_MIN_CONFIDENCE);
+        assert_eq!(spoken.source, LanguageSource::Detected);
+        assert_eq!(spoken.languages, vec![Language::new(ISO639::Ko)]);
+        assert_eq!(spoken.confidence, Some(0.92));
+    }
+
+    #[test]
+    fn test_detection_keeps_declared_region() {
+        let declared = [
+            Language::with_region(ISO639::En, "US"),
+            Language::with_region(ISO639::De, "AT"),
+        ];
+        let scores = [score(ISO639::De, 0.81), score(ISO639::En, 0.12)];
+
+        let spoken = resolve_spoken_language(&scores, &declared, DEFAULT_MIN_CONFIDENCE);
+        assert_eq!(spoken.languages, vec![Language::with_region(ISO639::De, "AT")]);
+    }
+
+    #[test]
+    fn test_falls_back_to_declared() {
+        let declared = [Language::new(ISO639::En), Language::new(ISO639::De)];
+
+        let unsure = resolve_spoken_language(
+            &[score(ISO639::De, 0.4), score(ISO639::Nl, 0.35)],
+            &declared,
+            DEFAULT_MIN_CONFIDENCE,
+        );
+        assert_eq!(unsure.source, LanguageSource::Declared);
+        assert_eq!(unsure.languages, declared.to_vec());
+        assert_eq!(unsure.confidence, Some(0.4));
+
+        let silent = resolve_spoken_language(&[], &declared, DEFAULT_MIN_CONFIDENCE);
+        assert_eq!(silent, SpokenLanguage::declared(&declared));
+    }
+}
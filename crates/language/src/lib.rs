@@ -9,6 +9,9 @@ pub use detect::detect;
 #[cfg(feature = "whisper")]
 mod whisper;
 
+mod identify;
+pub use identify::*;
+
 use std::str::FromStr;
 
 pub use codes_iso_639::part_1::LanguageCode as ISO639;
@@ -143,6 +143,8 @@ async fn main() {
         base_url,
         api_key,
         keywords: vec![],
+        vocabulary: Default::default(),
+        meeting: None,
     };
 
     let started = ractor::call!(root_ref, RootMsg::StartSession, params)
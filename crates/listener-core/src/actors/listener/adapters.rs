@@ -116,6 +116,7 @@ fn build_listen_params(args: &ListenerArgs) -> owhisper_interface::ListenParams
         languages: args.languages.clone(),
         sample_rate: super::super::SAMPLE_RATE,
         keywords: args.keywords.clone(),
+        vocabulary: args.vocabulary.clone(),
         custom_query: Some(std::collections::HashMap::from([(
             "redemption_time_ms".to_string(),
             redemption_time_ms.to_string(),
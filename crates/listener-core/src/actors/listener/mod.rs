@@ -9,6 +9,7 @@ use ractor::{Actor, ActorName, ActorProcessingErr, ActorRef, SupervisionEvent};
 use tokio::time::error::Elapsed;
 use tracing::Instrument;
 
+use hypr_transcript::VocabularyCorrector;
 use owhisper_interface::stream::StreamResponse;
 use owhisper_interface::{ControlMessage, MixedMessage};
 
@@ -40,6 +41,7 @@ pub struct ListenerArgs {
     pub base_url: String,
     pub api_key: String,
     pub keywords: Vec<String>,
+    pub vocabulary: owhisper_interface::Vocabulary,
     pub mode: crate::actors::ChannelMode,
     pub session_started_at: Instant,
     pub session_started_at_unix: SystemTime,
@@ -48,6 +50,7 @@ pub struct ListenerArgs {
 
 pub struct ListenerState {
     pub args: ListenerArgs,
+    corrector: VocabularyCorrector,
     tx: ChannelSender,
     rx_task: tokio::task::JoinHandle<()>,
     shutdown_tx: Option<tokio::sync::oneshot::Sender<()>>,
@@ -110,6 +113,7 @@ impl Actor for ListenerActor {
             });
 
             let state = ListenerState {
+                corrector: VocabularyCorrector::new(&args.vocabulary),
                 args,
                 tx,
                 rx_task,
@@ -205,6 +209,8 @@ impl Actor for ListenerActor {
                     crate::actors::ChannelMode::MicAndSpeaker => {}
                 }
 
+                state.corrector.correct_stream_response(&mut response);
+
                 state
                     .args
                     .runtime
@@ -6,7 +6,9 @@ use ractor::{Actor, ActorCell, ActorProcessingErr, ActorRef, SupervisionEvent};
 use tracing::Instrument;
 
 use crate::actors::session::lifecycle;
-use crate::actors::session::types::{SessionContext, session_span, session_supervisor_name};
+use crate::actors::session::types::{
+    SessionContext, persist_meeting_context, session_span, session_supervisor_name,
+};
 use crate::actors::{
     ChannelMode, ListenerActor, ListenerArgs, RecArgs, RecMsg, RecorderActor, SourceActor,
     SourceArgs,
@@ -64,6 +66,18 @@ impl Actor for SessionActor {
         let span = session_span(&session_id);
 
         async {
+            if let Some(meeting) = ctx.params.meeting.clone() {
+                let app_dir = ctx.app_dir.clone();
+                let session_id = session_id.clone();
+                let persisted = tokio::task::spawn_blocking(move || {
+                    persist_meeting_context(&app_dir, &session_id, &meeting)
+                })
+                .await;
+                if let Ok(Err(e)) = persisted {
+                    tracing::warn!(error.message = %e, "meeting_context_persist_failed");
+                }
+            }
+
             let (source_ref, _) = Actor::spawn_linked(
                 Some(SourceActor::name()),
                 SourceActor,
@@ -131,7 +145,8 @@ impl Actor for SessionActor {
                     model: state.ctx.params.model.clone(),
                     base_url: state.ctx.params.base_url.clone(),
                     api_key: state.ctx.params.api_key.clone(),
-                    keywords: state.ctx.params.keywords.clone(),
+                    keywords: state.ctx.params.listen_keywords(),
+                    vocabulary: state.ctx.params.vocabulary.clone(),
                     mode,
                     session_started_at: state.ctx.started_at_instant,
                     session_started_at_unix: state.ctx.started_at_system,
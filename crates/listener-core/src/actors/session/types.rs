@@ -1,10 +1,13 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Instant, SystemTime};
 
 use crate::ListenerRuntime;
+use crate::actors::find_session_dir;
 
 pub const SESSION_SUPERVISOR_PREFIX: &str = "session_supervisor_";
+/// Written next to the recording, so correcting the transcript later uses the same glossary.
+pub const MEETING_CONTEXT_FILENAME: &str = "meeting.json";
 
 pub fn session_span(session_id: &str) -> tracing::Span {
     tracing::info_span!("session", hyprnote.session.id = %session_id)
@@ -21,6 +24,34 @@ pub struct SessionParams {
     pub base_url: String,
     pub api_key: String,
     pub keywords: Vec<String>,
+    #[serde(default)]
+    pub vocabulary: owhisper_interface::Vocabulary,
+    /// Calendar context for the recording; its glossary is sent ahead of `keywords`.
+    #[serde(default)]
+    pub meeting: Option<hypr_transcript::MeetingContext>,
+}
+
+impl SessionParams {
+    pub fn listen_keywords(&self) -> Vec<String> {
+        match &self.meeting {
+            Some(meeting) => meeting.with_keywords(&self.keywords),
+            None => self.keywords.clone(),
+        }
+    }
+}
+
+/// Saves the session's calendar context into its directory under `app_dir`.
+pub fn persist_meeting_context(
+    app_dir: &Path,
+    session_id: &str,
+    meeting: &hypr_transcript::MeetingContext,
+) -> std::io::Result<()> {
+    let dir = find_session_dir(app_dir, session_id);
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(
+        dir.join(MEETING_CONTEXT_FILENAME),
+        serde_json::to_vec_pretty(meeting)?,
+    )
 }
 
 #[derive(Clone)]
@@ -1,6 +1,7 @@
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
+use hypr_transcript::VocabularyCorrector;
 use owhisper_client::StreamingBatchStream;
 use owhisper_interface::stream::StreamResponse;
 use ractor::{Actor, ActorName, ActorProcessingErr, ActorRef, SpawnErr};
@@ -151,6 +152,7 @@ struct BatchState {
     done_notifier: BatchDoneNotifier,
     final_result: Option<crate::Result<BatchRunOutput>>,
     accumulator: StreamBatchAccumulator,
+    corrector: VocabularyCorrector,
 }
 
 impl BatchState {
@@ -197,6 +199,7 @@ impl Actor for BatchActor {
             done_notifier: args.done_notifier,
             final_result: None,
             accumulator: StreamBatchAccumulator::new(),
+            corrector: VocabularyCorrector::new(&args.listen_params.vocabulary),
         })
     }
 
@@ -226,10 +229,11 @@ impl Actor for BatchActor {
     ) -> Result<(), ActorProcessingErr> {
         match message {
             BatchMsg::StreamResponse {
-                response,
+                mut response,
                 percentage,
             } => {
                 tracing::info!("batch stream response received");
+                state.corrector.correct_stream_response(&mut response);
                 state.accumulator.observe(&response);
                 state.emit_streamed(*response, percentage);
             }
@@ -4,6 +4,7 @@ mod bootstrap;
 
 use std::sync::Arc;
 
+use hypr_transcript::VocabularyCorrector;
 use owhisper_client::{
     ArgmaxAdapter, AssemblyAIAdapter, BatchSttAdapter, DeepgramAdapter, ElevenLabsAdapter,
     FireworksAdapter, GladiaAdapter, HyprnoteAdapter, MistralAdapter, OpenAIAdapter, SonioxAdapter,
@@ -48,6 +49,8 @@ pub struct BatchParams {
     pub languages: Vec<hypr_language::Language>,
     #[serde(default)]
     pub keywords: Vec<String>,
+    #[serde(default)]
+    pub vocabulary: owhisper_interface::Vocabulary,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -143,6 +146,7 @@ async fn run_batch_inner(
         sample_rate: metadata.sample_rate,
         languages: params.languages.clone(),
         keywords: params.keywords.clone(),
+        vocabulary: params.vocabulary.clone(),
         custom_query: None,
     };
 
@@ -187,7 +191,7 @@ async fn run_batch_simple<A: BatchSttAdapter>(
             .build();
 
         tracing::debug!("transcribing file: {}", params.file_path);
-        let response = match client.transcribe_file(&params.file_path).await {
+        let mut response = match client.transcribe_file(&params.file_path).await {
             Ok(response) => response,
             Err(err) => {
                 let raw_error = format!("{err:?}");
@@ -201,6 +205,7 @@ async fn run_batch_simple<A: BatchSttAdapter>(
             }
         };
         tracing::info!("batch transcription completed");
+        VocabularyCorrector::new(&params.vocabulary).correct_batch_response(&mut response);
 
         Ok(BatchRunOutput {
             session_id: params.session_id,
@@ -3,6 +3,7 @@ mod denoise;
 mod error;
 mod events;
 mod runtime;
+mod speakers;
 mod subtitle;
 
 pub use batch::{BatchParams, BatchProvider, BatchRunMode, BatchRunOutput, run_batch};
@@ -10,6 +11,7 @@ pub use denoise::{DenoiseParams, run_denoise};
 pub use error::*;
 pub use events::*;
 pub use runtime::*;
+pub use speakers::*;
 pub use subtitle::*;
 
 use std::str::FromStr;
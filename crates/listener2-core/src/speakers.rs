@@ -0,0 +1,182 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use hypr_transcript::{ActiveSpeakerEvent, FinalizedWord, SpeakerHint, SpeakerTimeline};
+
+pub use hypr_transcript::SpeakerAssignment;
+
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct SpeakerAssignmentParams {
+    /// Wall-clock start of the recording; word times are relative to it.
+    pub started_at_ms: u64,
+    pub ended_at_ms: u64,
+    pub words: Vec<FinalizedWord>,
+    pub hints: Vec<SpeakerHint>,
+    /// Participant names as the meeting shows them, mapped to human ids.
+    pub human_ids: HashMap<String, String>,
+}
+
+/// Where the Chrome native host writes one `<started_at_ms>-<meeting code>.jsonl` timeline
+/// per meeting.
+pub fn chrome_speaker_timeline_dir() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("char").join("chrome_speakers"))
+}
+
+/// Maps diarized speakers onto participants using the browser timelines that overlap the
+/// recording. Returns nothing when no meeting was open while it ran.
+pub fn assign_speakers_from_timelines<P: AsRef<Path>>(
+    timeline_dir: P,
+    params: &SpeakerAssignmentParams,
+) -> std::result::Result<Vec<SpeakerAssignment>, String> {
+    let events = read_timelines(
+        timeline_dir.as_ref(),
+        params.started_at_ms,
+        params.ended_at_ms,
+    )
+    .map_err(|e| e.to_string())?;
+
+    let timeline = SpeakerTimeline::from_events(events, params.started_at_ms);
+    if timeline.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    Ok(timeline.assign(&params.words, &params.hints, &params.human_ids))
+}
+
+fn read_timelines(
+    dir: &Path,
+    started_at_ms: u64,
+    ended_at_ms: u64,
+) -> std::io::Result<Vec<ActiveSpeakerEvent>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let mut events = Vec::new();
+    for entry in entries {
+        let path = entry?.path();
+        if path.extension().is_none_or(|ext| ext != "jsonl") {
+            continue;
+        }
+
+        // The file name starts with when the meeting was first seen.
+        let meeting_started_at_ms = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.split('-').next())
+            .and_then(|ms| ms.parse::<u64>().ok());
+        if meeting_started_at_ms.is_none_or(|ms| ms > ended_at_ms) {
+            continue;
+        }
+
+        let contents = std::fs::read_to_string(&path)?;
+        let meeting: Vec<ActiveSpeakerEvent> = contents
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect();
+
+        // The host closes every meeting with an empty event, so the last one marks its end.
+        if meeting
+            .last()
+            .is_some_and(|last| last.timestamp_ms >= started_at_ms)
+        {
+            events.extend(meeting);
+        }
+    }
+
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hypr_transcript::WordState;
+    use owhisper_interface::SpeakerIdentity;
+
+    const STARTED_AT: u64 = 1_700_000_000_000;
+
+    fn write_timeline(dir: &Path, name: &str, events: &[(u64, &[&str])]) {
+        let contents: String = events
+            .iter()
+            .map(|(at, speakers)| {
+                let event = ActiveSpeakerEvent {
+                    timestamp_ms: *at,
+                    speakers: speakers.iter().map(|s| s.to_string()).collect(),
+                };
+                serde_json::to_string(&event).unwrap() + "\n"
+            })
+            .collect();
+        std::fs::write(dir.join(name), contents).unwrap();
+    }
+
+    fn params(words: Vec<FinalizedWord>) -> SpeakerAssignmentParams {
+        SpeakerAssignmentParams {
+            started_at_ms: STARTED_AT,
+            ended_at_ms: STARTED_AT + 10_000,
+            hints: words
+                .iter()
+                .map(|w| SpeakerHint {
+                    word_id: w.id.clone(),
+                    speaker_index: 0,
+                })
+                .collect(),
+            words,
+            human_ids: HashMap::from([("Alice".to_string(), "human-alice".to_string())]),
+        }
+    }
+
+    fn word(id: &str, start_ms: i64, end_ms: i64) -> FinalizedWord {
+        FinalizedWord {
+            id: id.to_string(),
+            text: id.to_string(),
+            start_ms,
+            end_ms,
+            channel: 1,
+            state: WordState::Final,
+        }
+    }
+
+    #[test]
+    fn assigns_from_the_meeting_that_overlaps_the_recording() {
+        let dir = tempfile::tempdir().unwrap();
+        write_timeline(
+            dir.path(),
+            &format!("{}-abc-defg-hij.jsonl", STARTED_AT - 60_000),
+            &[(STARTED_AT - 1_000, &["Alice"]), (STARTED_AT + 20_000, &[])],
+        );
+        // An earlier meeting that was over before the recording started.
+        write_timeline(
+            dir.path(),
+            &format!("{}-zzz-zzzz-zzz.jsonl", STARTED_AT - 600_000),
+            &[
+                (STARTED_AT - 500_000, &["Mallory"]),
+                (STARTED_AT - 400_000, &[]),
+            ],
+        );
+
+        let words = vec![word("a", 0, 2_000), word("b", 2_000, 4_000)];
+        let assignments = assign_speakers_from_timelines(dir.path(), &params(words)).unwrap();
+
+        assert_eq!(assignments.len(), 1);
+        assert_eq!(
+            assignments[0].identity,
+            SpeakerIdentity::Assigned {
+                id: "human-alice".to_string(),
+                label: "Alice".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn missing_timeline_dir_assigns_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let words = vec![word("a", 0, 2_000)];
+
+        let assignments =
+            assign_speakers_from_timelines(dir.path().join("missing"), &params(words)).unwrap();
+        assert!(assignments.is_empty());
+    }
+}
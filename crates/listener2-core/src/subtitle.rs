@@ -3,10 +3,10 @@ use aspasia::{Subtitle as SubtitleTrait, TimedSubtitleFile, WebVttSubtitle};
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "specta", derive(specta::Type))]
 pub struct Token {
-    text: String,
-    start_time: u64,
-    end_time: u64,
-    speaker: Option<String>,
+    pub text: String,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub speaker: Option<String>,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
@@ -21,7 +21,7 @@ pub struct VttWord {
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[cfg_attr(feature = "specta", derive(specta::Type))]
 pub struct Subtitle {
-    tokens: Vec<Token>,
+    pub tokens: Vec<Token>,
 }
 
 impl From<TimedSubtitleFile> for Subtitle {
@@ -1,6 +1,7 @@
 mod error;
 mod manager;
 mod service;
+mod structured;
 
 pub use error::*;
 pub use manager::{ModelLoader, ModelManager, ModelManagerBuilder};
@@ -1,6 +1,10 @@
 use std::{
     future::Future,
     pin::Pin,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
     task::{Context, Poll},
 };
 
@@ -14,6 +18,7 @@ use hypr_llm_types::{Response as LlmResponse, StreamingParser};
 use tower::Service;
 
 use crate::ModelManager;
+use crate::structured::{self, ChatMessage, ExpectedOutput, ResponseFormat, Tool, ToolChoice};
 
 #[derive(Clone)]
 pub struct CompleteService {
@@ -60,8 +65,20 @@ impl Service<Request<Body>> for CompleteService {
                 }
             };
 
-            let messages = convert_messages(&request.messages);
-            let options = build_options(&request);
+            let mut structured = match structured::resolve(
+                request.tools.as_deref(),
+                request.tool_choice.as_ref(),
+                request.response_format.as_ref(),
+            ) {
+                Ok(s) => s,
+                Err(e) => {
+                    return Ok((StatusCode::BAD_REQUEST, e).into_response());
+                }
+            };
+
+            let messages = structured::convert_messages(&request.messages);
+            let expected = structured.expected.take();
+            let options = build_options(&request, structured);
 
             if request.stream.unwrap_or(false) {
                 let completion_stream =
@@ -74,9 +91,20 @@ impl Service<Request<Body>> for CompleteService {
                         }
                     };
 
-                Ok(build_streaming_response(completion_stream, &request.model))
+                Ok(build_streaming_response(
+                    completion_stream,
+                    &request.model,
+                    expected,
+                ))
             } else {
-                Ok(build_non_streaming_response(&model, messages, options, &request.model).await)
+                Ok(build_non_streaming_response(
+                    &model,
+                    messages,
+                    options,
+                    &request.model,
+                    expected.as_ref(),
+                )
+                .await)
             }
         })
     }
@@ -97,41 +125,55 @@ struct ChatCompletionRequest {
     max_tokens: Option<u32>,
     #[serde(default)]
     max_completion_tokens: Option<u32>,
-}
-
-#[derive(serde::Deserialize)]
-struct ChatMessage {
-    role: String,
     #[serde(default)]
-    content: Option<String>,
-}
-
-fn convert_messages(messages: &[ChatMessage]) -> Vec<hypr_llm_types::Message> {
-    messages
-        .iter()
-        .map(|m| hypr_llm_types::Message {
-            role: m.role.clone(),
-            content: m.content.clone().unwrap_or_default(),
-        })
-        .collect()
+    tools: Option<Vec<Tool>>,
+    #[serde(default)]
+    tool_choice: Option<ToolChoice>,
+    #[serde(default)]
+    response_format: Option<ResponseFormat>,
 }
 
-fn build_options(request: &ChatCompletionRequest) -> hypr_cactus::CompleteOptions {
+fn build_options(
+    request: &ChatCompletionRequest,
+    structured: structured::Structured,
+) -> hypr_cactus::CompleteOptions {
     hypr_cactus::CompleteOptions {
         temperature: request.temperature,
         top_p: request.top_p,
         max_tokens: request.max_completion_tokens.or(request.max_tokens),
+        grammar: structured.grammar,
+        tools: structured.tools,
         ..Default::default()
     }
 }
 
+fn tool_call_json(name: String, arguments: &impl serde::Serialize) -> serde_json::Value {
+    serde_json::json!({
+        "id": format!("call_{}", uuid::Uuid::new_v4()),
+        "type": "function",
+        "function": {
+            "name": name,
+            "arguments": serde_json::to_string(arguments).unwrap_or_default()
+        }
+    })
+}
+
+fn finish_reason(called_tools: bool) -> &'static str {
+    if called_tools { "tool_calls" } else { "stop" }
+}
+
 fn model_name(model: &Option<String>) -> &str {
     model.as_deref().unwrap_or("cactus")
 }
 
+fn mismatch_message(error: &str) -> String {
+    format!("completion does not match the requested format: {error}")
+}
+
 fn build_streaming_response(
     completion_stream: hypr_cactus::CompletionStream,
     model: &Option<String>,
+    expected: Option<ExpectedOutput>,
 ) -> Response {
     let id = format!("chatcmpl-{}", uuid::Uuid::new_v4());
     let created = std::time::SystemTime::now()
@@ -142,28 +184,39 @@ fn build_streaming_response(
 
     let id_for_events = id.clone();
     let model_for_events = model_name.clone();
+    let tool_calls = Arc::new(AtomicUsize::new(0));
+    let tool_calls_for_events = Arc::clone(&tool_calls);
+    // Text and the first bad tool call, checked against `expected` once the stream ends.
+    let content = Arc::new(Mutex::new(String::new()));
+    let content_for_events = Arc::clone(&content);
+    let mismatch = Arc::new(Mutex::new(None::<String>));
+    let mismatch_for_events = Arc::clone(&mismatch);
+    let expected = expected.map(Arc::new);
+    let expected_for_events = expected.clone();
 
     let data_events = completion_stream.filter_map(move |item| {
         let id = id_for_events.clone();
         let model_name = model_for_events.clone();
+        let tool_calls = Arc::clone(&tool_calls_for_events);
+        let content = Arc::clone(&content_for_events);
+        let mismatch = Arc::clone(&mismatch_for_events);
+        let expected = expected_for_events.clone();
 
         async move {
             let delta = match item {
                 LlmResponse::TextDelta(text) => {
+                    content.lock().unwrap().push_str(&text);
                     serde_json::json!({ "content": text, "role": "assistant" })
                 }
                 LlmResponse::ToolCall { name, arguments } => {
-                    serde_json::json!({
-                        "tool_calls": [{
-                            "index": 0,
-                            "id": format!("call_{}", uuid::Uuid::new_v4()),
-                            "type": "function",
-                            "function": {
-                                "name": name,
-                                "arguments": serde_json::to_string(&arguments).unwrap_or_default()
-                            }
-                        }]
-                    })
+                    if let Some(expected) = &expected
+                        && let Err(e) = expected.check_tool_call(&name, &arguments)
+                    {
+                        mismatch.lock().unwrap().get_or_insert(e);
+                    }
+                    let mut call = tool_call_json(name, &arguments);
+                    call["index"] = tool_calls.fetch_add(1, Ordering::Relaxed).into();
+                    serde_json::json!({ "role": "assistant", "tool_calls": [call] })
                 }
                 LlmResponse::Reasoning(_) => return None,
             };
@@ -186,19 +239,40 @@ fn build_streaming_response(
         }
     });
 
-    let stop_chunk = serde_json::json!({
-        "id": id,
-        "object": "chat.completion.chunk",
-        "created": created,
-        "model": model_name,
-        "choices": [{"index": 0, "delta": {}, "finish_reason": "stop"}]
-    });
+    let stop_event = stream::once(async move {
+        let called_tools = tool_calls.load(Ordering::Relaxed) > 0;
+        let mismatch = mismatch.lock().unwrap().take().or_else(|| {
+            let expected = expected.as_ref()?;
+            let content = content.lock().unwrap();
+            expected.check_content(&content, called_tools).err()
+        });
+
+        // The text is already out; all that is left is to not report a clean stop.
+        if let Some(e) = mismatch {
+            let error = serde_json::json!({
+                "error": { "message": mismatch_message(&e), "type": "server_error" }
+            });
+            return Ok::<_, std::convert::Infallible>(
+                sse::Event::default().data(serde_json::to_string(&error).unwrap_or_default()),
+            );
+        }
+
+        let stop_chunk = serde_json::json!({
+            "id": id,
+            "object": "chat.completion.chunk",
+            "created": created,
+            "model": model_name,
+            "choices": [{
+                "index": 0,
+                "delta": {},
+                "finish_reason": finish_reason(called_tools)
+            }]
+        });
 
-    let stop_event = stream::once(futures_util::future::ready(
         Ok::<_, std::convert::Infallible>(
             sse::Event::default().data(serde_json::to_string(&stop_chunk).unwrap_or_default()),
-        ),
-    ));
+        )
+    });
 
     let done_event = stream::once(futures_util::future::ready(
         Ok::<_, std::convert::Infallible>(sse::Event::default().data("[DONE]")),
@@ -214,6 +288,7 @@ async fn build_non_streaming_response(
     messages: Vec<hypr_llm_types::Message>,
     options: hypr_cactus::CompleteOptions,
     model_label: &Option<String>,
+    expected: Option<&ExpectedOutput>,
 ) -> Response {
     let model = std::sync::Arc::clone(model);
 
@@ -246,25 +321,31 @@ async fn build_non_streaming_response(
         match item {
             LlmResponse::TextDelta(text) => content.push_str(&text),
             LlmResponse::ToolCall { name, arguments } => {
-                tool_calls.push(serde_json::json!({
-                    "id": format!("call_{}", uuid::Uuid::new_v4()),
-                    "type": "function",
-                    "function": {
-                        "name": name,
-                        "arguments": serde_json::to_string(&arguments).unwrap_or_default()
-                    }
-                }));
+                if let Some(expected) = expected
+                    && let Err(e) = expected.check_tool_call(&name, &arguments)
+                {
+                    return (StatusCode::INTERNAL_SERVER_ERROR, mismatch_message(&e))
+                        .into_response();
+                }
+                tool_calls.push(tool_call_json(name, &arguments));
             }
             LlmResponse::Reasoning(_) => {}
         }
     }
 
+    if let Some(expected) = expected
+        && let Err(e) = expected.check_content(&content, !tool_calls.is_empty())
+    {
+        return (StatusCode::INTERNAL_SERVER_ERROR, mismatch_message(&e)).into_response();
+    }
+
     let id = format!("chatcmpl-{}", uuid::Uuid::new_v4());
     let created = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap_or_default()
         .as_secs();
 
+    let finish_reason = finish_reason(!tool_calls.is_empty());
     let mut message = serde_json::json!({ "role": "assistant" });
     if !content.is_empty() {
         message["content"] = serde_json::Value::String(content);
@@ -281,7 +362,7 @@ async fn build_non_streaming_response(
         "choices": [{
             "index": 0,
             "message": message,
-            "finish_reason": "stop"
+            "finish_reason": finish_reason
         }],
         "usage": {
             "prompt_tokens": completion.prefill_tokens,
@@ -0,0 +1,543 @@
+use hypr_gbnf::{JsonSchemaGrammar, json_object_grammar, json_schema_grammar, literal};
+use serde_json::Value;
+
+const TOOL_CALL_OPEN: &str = "<tool_call>\n";
+const TOOL_CALL_CLOSE: &str = "\n</tool_call>";
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct Tool {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub function: FunctionDefinition,
+}
+
+#[derive(Clone, serde::Serialize, serde::Deserialize)]
+pub(crate) struct FunctionDefinition {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parameters: Option<Value>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+pub(crate) enum ToolChoice {
+    Mode(ToolChoiceMode),
+    Function { function: FunctionName },
+}
+
+#[derive(Clone, Copy, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ToolChoiceMode {
+    None,
+    Auto,
+    Required,
+}
+
+#[derive(serde::Deserialize)]
+pub(crate) struct FunctionName {
+    pub name: String,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum ResponseFormat {
+    Text,
+    JsonObject,
+    JsonSchema { json_schema: JsonSchemaFormat },
+}
+
+#[derive(serde::Deserialize)]
+pub(crate) struct JsonSchemaFormat {
+    #[serde(default)]
+    pub schema: Option<Value>,
+}
+
+#[derive(serde::Deserialize)]
+pub(crate) struct ChatMessage {
+    pub role: String,
+    #[serde(default)]
+    pub content: Option<MessageContent>,
+    #[serde(default)]
+    pub tool_calls: Option<Vec<ToolCallMessage>>,
+}
+
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+pub(crate) enum MessageContent {
+    Text(String),
+    Parts(Vec<ContentPart>),
+}
+
+#[derive(serde::Deserialize)]
+pub(crate) struct ContentPart {
+    #[serde(default)]
+    pub text: Option<String>,
+}
+
+#[derive(serde::Deserialize)]
+pub(crate) struct ToolCallMessage {
+    pub function: FunctionCall,
+}
+
+#[derive(serde::Deserialize)]
+pub(crate) struct FunctionCall {
+    pub name: String,
+    #[serde(default)]
+    pub arguments: String,
+}
+
+/// Tools and sampling grammar for one request.
+pub(crate) struct Structured {
+    pub tools: Option<Vec<Value>>,
+    pub grammar: Option<String>,
+    /// What the grammar asks for. The engine may not enforce it, so the output is checked too.
+    pub expected: Option<ExpectedOutput>,
+}
+
+/// Output a grammar constrains the model to: tool calls whose arguments match the tool's
+/// parameters, or a JSON answer matching `answer`.
+pub(crate) struct ExpectedOutput {
+    tools: Vec<(String, Option<Value>)>,
+    answer: Option<Value>,
+}
+
+impl ExpectedOutput {
+    pub fn check_tool_call(
+        &self,
+        name: &str,
+        arguments: &std::collections::HashMap<String, Value>,
+    ) -> Result<(), String> {
+        let (_, parameters) = self
+            .tools
+            .iter()
+            .find(|(tool, _)| tool == name)
+            .ok_or_else(|| format!("call to unknown tool {name:?}"))?;
+
+        match parameters {
+            Some(schema) => {
+                let arguments = Value::Object(arguments.clone().into_iter().collect());
+                validate(schema, &arguments).map_err(|e| format!("tool {name:?}: {e}"))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// `content` is the text outside tool calls; it must be empty when a tool was called.
+    pub fn check_content(&self, content: &str, called_tools: bool) -> Result<(), String> {
+        let content = content.trim();
+        if called_tools && !content.is_empty() {
+            return Err("text alongside tool calls".to_string());
+        }
+        if called_tools {
+            return Ok(());
+        }
+
+        let Some(schema) = &self.answer else {
+            return Err("expected a tool call".to_string());
+        };
+        let answer: Value =
+            serde_json::from_str(content).map_err(|e| format!("answer is not JSON: {e}"))?;
+        validate(schema, &answer).map_err(|e| format!("answer: {e}"))
+    }
+}
+
+fn validate(schema: &Value, value: &Value) -> Result<(), String> {
+    jsonschema::validator_for(schema)
+        .map_err(|e| format!("invalid schema: {e}"))?
+        .validate(value)
+        .map_err(|e| e.to_string())
+}
+
+/// Flattens OpenAI messages into the role/content pairs the model's chat template takes.
+/// Earlier assistant tool calls are written back in the `<tool_call>` form the model emits.
+pub(crate) fn convert_messages(messages: &[ChatMessage]) -> Vec<hypr_llm_types::Message> {
+    messages
+        .iter()
+        .map(|m| {
+            let mut content = match &m.content {
+                Some(MessageContent::Text(text)) => text.clone(),
+                Some(MessageContent::Parts(parts)) => parts
+                    .iter()
+                    .filter_map(|p| p.text.as_deref())
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                None => String::new(),
+            };
+
+            for call in m.tool_calls.iter().flatten() {
+                let arguments = serde_json::from_str::<Value>(&call.function.arguments)
+                    .unwrap_or_else(|_| Value::String(call.function.arguments.clone()));
+                let call = format!(
+                    r#"{{"name": {}, "arguments": {arguments}}}"#,
+                    Value::from(call.function.name.as_str())
+                );
+
+                if !content.is_empty() {
+                    content.push('\n');
+                }
+                content.push_str(TOOL_CALL_OPEN);
+                content.push_str(&call);
+                content.push_str(TOOL_CALL_CLOSE);
+            }
+
+            hypr_llm_types::Message {
+                role: m.role.clone(),
+                content,
+            }
+        })
+        .collect()
+}
+
+/// Decides which tools reach the model and which grammar constrains its output.
+///
+/// `tool_choice: "required"` or a named function forces `<tool_call>` blocks whose arguments
+/// match the tool's parameter schema. With `"auto"`, a JSON response format still lets the model
+/// call a tool instead of answering.
+pub(crate) fn resolve(
+    tools: Option<&[Tool]>,
+    tool_choice: Option<&ToolChoice>,
+    response_format: Option<&ResponseFormat>,
+) -> Result<Structured, String> {
+    let tools = tools.filter(|t| !t.is_empty());
+    let mode = match tool_choice {
+        Some(ToolChoice::Mode(mode)) => *mode,
+        Some(ToolChoice::Function { .. }) => ToolChoiceMode::Required,
+        None => ToolChoiceMode::Auto,
+    };
+
+    let Some(tools) = tools.filter(|_| mode != ToolChoiceMode::None) else {
+        if mode == ToolChoiceMode::Required {
+            return Err("tool_choice requires at least one tool".to_string());
+        }
+        return Ok(Structured {
+            tools: None,
+            grammar: response_grammar(response_format)?,
+            expected: answer_schema(response_format).map(|answer| ExpectedOutput {
+                tools: Vec::new(),
+                answer: Some(answer),
+            }),
+        });
+    };
+
+    let callable = match tool_choice {
+        Some(ToolChoice::Function { function }) => {
+            let tool = tools
+                .iter()
+                .find(|t| t.function.name == function.name)
+                .ok_or_else(|| format!("tool_choice names unknown function {:?}", function.name))?;
+            std::slice::from_ref(tool)
+        }
+        _ => tools,
+    };
+
+    let grammar = match (mode, response_format) {
+        (ToolChoiceMode::Required, _) => Some(tool_call_grammar(callable, None)?),
+        (_, Some(ResponseFormat::JsonObject | ResponseFormat::JsonSchema { .. })) => {
+            Some(tool_call_grammar(callable, response_format)?)
+        }
+        _ => None,
+    };
+
+    let expected = grammar.as_ref().map(|_| ExpectedOutput {
+        tools: callable
+            .iter()
+            .map(|t| (t.function.name.clone(), t.function.parameters.clone()))
+            .collect(),
+        answer: match mode {
+            ToolChoiceMode::Required => None,
+            _ => answer_schema(response_format),
+        },
+    });
+
+    let tools = tools
+        .iter()
+        .map(|t| serde_json::to_value(t).map_err(|e| e.to_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Structured {
+        tools: Some(tools),
+        grammar,
+        expected,
+    })
+}
+
+fn answer_schema(format: Option<&ResponseFormat>) -> Option<Value> {
+    let object = || serde_json::json!({ "type": "object" });
+    match format {
+        None | Some(ResponseFormat::Text) => None,
+        Some(ResponseFormat::JsonObject) => Some(object()),
+        Some(ResponseFormat::JsonSchema { json_schema }) => {
+            Some(json_schema.schema.clone().unwrap_or_else(object))
+        }
+    }
+}
+
+fn response_grammar(format: Option<&ResponseFormat>) -> Result<Option<String>, String> {
+    match format {
+        None | Some(ResponseFormat::Text) => Ok(None),
+        Some(ResponseFormat::JsonObject) => Ok(Some(json_object_grammar())),
+        Some(ResponseFormat::JsonSchema { json_schema }) => match &json_schema.schema {
+            Some(schema) => json_schema_grammar(schema)
+                .map(Some)
+                .map_err(|e| e.to_string()),
+            None => Ok(Some(json_object_grammar())),
+        },
+    }
+}
+
+/// One or more `<tool_call>` blocks, or a single JSON answer when `answer` is set.
+fn tool_call_grammar(tools: &[Tool], answer: Option<&ResponseFormat>) -> Result<String, String> {
+    let mut builder = JsonSchemaGrammar::new();
+
+    let mut calls = Vec::with_capacity(tools.len());
+    for tool in tools {
+        let name = &tool.function.name;
+        let arguments = match &tool.function.parameters {
+            Some(schema) => builder
+                .add_schema(&format!("{name}-arguments"), schema)
+                .map_err(|e| format!("tool {name:?}: {e}"))?,
+            None => "object".to_string(),
+        };
+
+        let prefix = format!(r#"{{"name": {}, "arguments": "#, Value::from(name.as_str()));
+        calls.push(format!("{} {arguments} \"}}\"", literal(&prefix)));
+    }
+
+    let call = builder.add_rule(
+        "tool-call",
+        format!(
+            "{} ({}) {}",
+            literal(TOOL_CALL_OPEN),
+            calls.join(" | "),
+            literal(TOOL_CALL_CLOSE)
+        ),
+    );
+    let calls = format!(r#"{call} ("\n" {call})*"#);
+
+    let root = match answer {
+        Some(ResponseFormat::JsonSchema { json_schema }) => match &json_schema.schema {
+            Some(schema) => {
+                let answer = builder
+                    .add_schema("answer", schema)
+                    .map_err(|e| e.to_string())?;
+                format!("{calls} | ws {answer}")
+            }
+            None => format!("{calls} | ws object"),
+        },
+        Some(ResponseFormat::JsonObject) => format!("{calls} | ws object"),
+        _ => calls,
+    };
+
+    Ok(builder.build(&root))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tools() -> Vec<Tool> {
+        serde_json::from_value(serde_json::json!([
+            {
+                "type": "function",
+                "function": {
+                    "name": "search",
+                    "description": "Search past meetings",
+                    "parameters": {
+                        "type": "object",
+                        "properties": { "query": { "type": "string" } },
+                        "required": ["query"]
+                    }
+                }
+            },
+            { "type": "function", "function": { "name": "now" } }
+        ]))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_convert_messages_replays_tool_calls() {
+        let messages: Vec<ChatMessage> = serde_json::from_value(serde_json::json!([
+            { "role": "user", "content": [{ "type": "text", "text": "Find the budget call" }] },
+            {
+                "role": "assistant",
+                "content": null,
+                "tool_calls": [{
+                    "id": "call_1",
+                    "type": "function",
+                    "function": { "name": "search", "arguments": "{\"query\":\"budget\"}" }
+                }]
+            },
+            { "role": "tool", "tool_call_id": "call_1", "content": "[]" }
+        ]))
+        .unwrap();
+
+        let converted = convert_messages(&messages);
+        assert_eq!(converted[0].content, "Find the budget call");
+        assert_eq!(
+            converted[1].content,
+            "<tool_call>\n{\"name\": \"search\", \"arguments\": {\"query\":\"budget\"}}\n</tool_call>"
+        );
+        assert_eq!(converted[2].role, "tool");
+        assert_eq!(converted[2].content, "[]");
+    }
+
+    #[test]
+    fn test_auto_tool_choice_has_no_grammar() {
+        let tools = tools();
+        let structured = resolve(Some(&tools), None, None).unwrap();
+
+        assert_eq!(structured.tools.unwrap().len(), 2);
+        assert!(structured.grammar.is_none());
+    }
+
+    #[test]
+    fn test_named_tool_choice_constrains_arguments() {
+        let tools = tools();
+        let choice: ToolChoice = serde_json::from_value(
+            serde_json::json!({ "type": "function", "function": { "name": "search" } }),
+        )
+        .unwrap();
+
+        let grammar = resolve(Some(&tools), Some(&choice), None)
+            .unwrap()
+            .grammar
+            .unwrap();
+
+        assert!(grammar.starts_with(r#"root ::= tool-call ("\n" tool-call)*"#));
+        assert!(
+            grammar.contains(r#"search-arguments ::= "{" ws "\"query\"" ws ":" ws string "}" ws"#)
+        );
+        assert!(grammar.contains(
+            r#"tool-call ::= "<tool_call>\n" ("{\"name\": \"search\", \"arguments\": " search-arguments "}") "\n</tool_call>""#
+        ));
+        assert!(!grammar.contains("\\\"now\\\""));
+
+        let unknown: ToolChoice = serde_json::from_value(
+            serde_json::json!({ "type": "function", "function": { "name": "missing" } }),
+        )
+        .unwrap();
+        assert!(resolve(Some(&tools), Some(&unknown), None).is_err());
+    }
+
+    #[test]
+    fn test_response_format() {
+        let format: ResponseFormat = serde_json::from_value(serde_json::json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": "action_items",
+                "strict": true,
+                "schema": {
+                    "type": "object",
+                    "properties": { "items": { "type": "array", "items": { "type": "string" } } },
+                    "required": ["items"]
+                }
+            }
+        }))
+        .unwrap();
+
+        let none = ToolChoice::Mode(ToolChoiceMode::None);
+        let tools = tools();
+        let structured = resolve(Some(&tools), Some(&none), Some(&format)).unwrap();
+        assert!(structured.tools.is_none());
+        assert!(
+            structured
+                .grammar
+                .unwrap()
+                .starts_with("root ::= ws root-schema\n")
+        );
+
+        let structured = resolve(Some(&tools), None, Some(&format)).unwrap();
+        assert!(
+            structured
+                .grammar
+                .unwrap()
+                .starts_with(r#"root ::= tool-call ("\n" tool-call)* | ws answer"#)
+        );
+
+        let text: ResponseFormat =
+            serde_json::from_value(serde_json::json!({ "type": "text" })).unwrap();
+        assert!(resolve(None, None, Some(&text)).unwrap().grammar.is_none());
+    }
+
+    #[test]
+    fn test_expected_output_rejects_answers_off_schema() {
+        let format: ResponseFormat = serde_json::from_value(serde_json::json!({
+            "type": "json_schema",
+            "json_schema": {
+                "schema": {
+                    "type": "object",
+                    "properties": { "items": { "type": "array", "items": { "type": "string" } } },
+                    "required": ["items"]
+                }
+            }
+        }))
+        .unwrap();
+        let expected = resolve(None, None, Some(&format))
+            .unwrap()
+            .expected
+            .unwrap();
+
+        assert!(
+            expected
+                .check_content(r#" {"items": ["a"]}"#, false)
+                .is_ok()
+        );
+        assert!(expected.check_content(r#"{"items": [1]}"#, false).is_err());
+        assert!(expected.check_content(r#"{"todo": []}"#, false).is_err());
+        assert!(
+            expected
+                .check_content("Sure! Here are the items.", false)
+                .is_err()
+        );
+        assert!(expected.check_content("", false).is_err());
+
+        let text: ResponseFormat =
+            serde_json::from_value(serde_json::json!({ "type": "text" })).unwrap();
+        assert!(resolve(None, None, Some(&text)).unwrap().expected.is_none());
+    }
+
+    #[test]
+    fn test_expected_output_checks_tool_arguments() {
+        let tools = tools();
+        let required = ToolChoice::Mode(ToolChoiceMode::Required);
+        let expected = resolve(Some(&tools), Some(&required), None)
+            .unwrap()
+            .expected
+            .unwrap();
+
+        let arguments = |value: Value| serde_json::from_value(value).unwrap();
+        assert!(
+            expected
+                .check_tool_call(
+                    "search",
+                    &arguments(serde_json::json!({ "query": "budget" }))
+                )
+                .is_ok()
+        );
+        assert!(
+            expected
+                .check_tool_call("search", &arguments(serde_json::json!({ "query": 1 })))
+                .is_err()
+        );
+        assert!(
+            expected
+                .check_tool_call("delete", &arguments(serde_json::json!({})))
+                .is_err()
+        );
+        assert!(expected.check_content("", true).is_ok());
+        assert!(
+            expected
+                .check_content(r#"{"query": "budget"}"#, false)
+                .is_err()
+        );
+
+        assert!(
+            resolve(Some(&tools), None, None)
+                .unwrap()
+                .expected
+                .is_none()
+        );
+    }
+}
@@ -285,6 +285,15 @@ async fn completions_handler(
         scope.set_context("gen_ai.request", sentry::protocol::Context::Other(ctx));
     });
 
+    let response_model = models.first().cloned().or_else(|| request.model.clone());
+    let url = match provider.request_url(&request, &models, stream) {
+        Ok(url) => url,
+        Err(e) => {
+            tracing::error!(error.message = %e, "failed_to_build_provider_request");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Invalid request").into_response();
+        }
+    };
+
     let provider_request = match provider.build_request(&request, models, stream) {
         Ok(req) => req,
         Err(e) => {
@@ -309,7 +318,7 @@ async fn completions_handler(
         (|| async {
             let mut req_builder = state
                 .client
-                .post(provider.base_url())
+                .post(&url)
                 .header("Content-Type", "application/json")
                 .header(
                     "Authorization",
@@ -376,9 +385,16 @@ async fn completions_handler(
         "llm_completion_request_finished"
     );
 
+    // Error bodies are passed through as they are.
+    let translator = if response.status().is_success() {
+        provider.response_translator(response_model.as_deref(), response.headers())
+    } else {
+        None
+    };
+
     if stream {
-        handle_stream_response(state, response, start_time, analytics_ctx).await
+        handle_stream_response(state, response, translator, start_time, analytics_ctx).await
     } else {
-        handle_non_stream_response(state, response, start_time, analytics_ctx).await
+        handle_non_stream_response(state, response, translator, start_time, analytics_ctx).await
     }
 }
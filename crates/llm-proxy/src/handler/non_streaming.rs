@@ -7,12 +7,14 @@ use axum::{
 };
 
 use crate::analytics::GenerationEvent;
+use crate::provider::ResponseTranslator;
 
 use super::{AnalyticsContext, AppState, ProxyError, spawn_analytics_report};
 
 pub(super) async fn handle_non_stream_response(
     state: AppState,
     response: reqwest::Response,
+    translator: Option<Box<dyn ResponseTranslator>>,
     start_time: Instant,
     analytics_ctx: AnalyticsContext,
 ) -> Response {
@@ -39,6 +41,21 @@ pub(super) async fn handle_non_stream_response(
         Err(e) => return ProxyError::BodyRead(e).into_response(),
     };
 
+    let body_bytes = match translator {
+        Some(mut translator) => match translator.translate_body(&body_bytes) {
+            Ok(translated) => bytes::Bytes::from(translated),
+            Err(e) => {
+                tracing::error!(error.message = %e, "provider_response_translation_failed");
+                return (
+                    axum::http::StatusCode::BAD_GATEWAY,
+                    "Failed to read response",
+                )
+                    .into_response();
+            }
+        },
+        None => body_bytes,
+    };
+
     if let Ok(metadata) = state.config.provider.parse_response(&body_bytes) {
         span.record("gen_ai.response.id", metadata.generation_id.as_str());
         if let Some(model) = metadata.model.as_deref() {
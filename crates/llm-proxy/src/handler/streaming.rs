@@ -6,12 +6,14 @@ use axum::{body::Body, response::Response};
 use futures_util::StreamExt;
 
 use crate::analytics::GenerationEvent;
+use crate::provider::ResponseTranslator;
 
 use super::{AnalyticsContext, AppState, report_with_cost};
 
 pub(super) async fn handle_stream_response(
     state: AppState,
     response: reqwest::Response,
+    mut translator: Option<Box<dyn ResponseTranslator>>,
     start_time: Instant,
     analytics_ctx: AnalyticsContext,
 ) -> Response {
@@ -53,6 +55,13 @@ pub(super) async fn handle_stream_response(
         while let Some(chunk_result) = upstream.next().await {
             match chunk_result {
                 Ok(chunk) => {
+                    let chunk = match translator.as_mut() {
+                        Some(translator) => bytes::Bytes::from(translator.translate_chunk(&chunk)),
+                        None => chunk,
+                    };
+                    if chunk.is_empty() {
+                        continue;
+                    }
                     if analytics.is_some() {
                         provider.parse_stream_chunk(&chunk, &mut accumulator);
                     }
@@ -65,6 +74,12 @@ pub(super) async fn handle_stream_response(
             }
         }
 
+        if let Some(tail) = translator.as_mut().map(|translator| translator.finish())
+            && !tail.is_empty()
+        {
+            yield Ok(bytes::Bytes::from(tail));
+        }
+
         if let Some(generation_id) = accumulator.generation_id {
                 stream_span.record("gen_ai.response.id", generation_id.as_str());
                 if let Some(model) = accumulator.model.as_deref() {
@@ -92,6 +92,11 @@ impl Default for StaticModelResolver {
 }
 
 impl StaticModelResolver {
+    /// Starts from no models at all instead of the hosted defaults, which are OpenRouter ids.
+    pub fn new(models: HashMap<String, Vec<String>>) -> Self {
+        Self { models }
+    }
+
     pub fn with_models(mut self, key: impl Into<String>, models: Vec<String>) -> Self {
         self.models.insert(key.into(), models);
         self
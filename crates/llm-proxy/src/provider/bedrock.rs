@@ -0,0 +1,159 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use hypr_bedrock::{EventStreamDecoder, SSE_DONE, StreamTranslator};
+use reqwest::header::HeaderMap;
+
+use crate::types::ChatCompletionRequest;
+
+use super::{
+    GenerationMetadata, Provider, ProviderError, ResponseTranslator, StreamAccumulator,
+    accumulate_stream_chunk, parse_chat_completion,
+};
+
+const REQUEST_ID_HEADER: &str = "x-amzn-requestid";
+
+/// Amazon Bedrock through the Converse and ConverseStream APIs, authenticated with a Bedrock
+/// API key. Requests and responses are translated to and from the OpenAI format, so clients
+/// and analytics see the same shapes as with any other provider.
+pub struct BedrockProvider {
+    pub base_url: String,
+}
+
+impl BedrockProvider {
+    pub fn new(region: &str) -> Self {
+        Self::with_base_url(format!("https://bedrock-runtime.{region}.amazonaws.com"))
+    }
+
+    /// For VPC endpoints, e.g. `https://vpce-0123.bedrock-runtime.us-east-1.vpce.amazonaws.com`.
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+        }
+    }
+}
+
+impl Provider for BedrockProvider {
+    fn name(&self) -> &str {
+        "bedrock"
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn request_url(
+        &self,
+        request: &ChatCompletionRequest,
+        models: &[String],
+        stream: bool,
+    ) -> Result<String, ProviderError> {
+        let model = models.first().or(request.model.as_ref()).ok_or_else(|| {
+            ProviderError::InvalidRequest("no model configured or requested".to_string())
+        })?;
+
+        Ok(format!(
+            "{}{}",
+            self.base_url.trim_end_matches('/'),
+            hypr_bedrock::converse_path(model, stream)
+        ))
+    }
+
+    fn build_request(
+        &self,
+        request: &ChatCompletionRequest,
+        _models: Vec<String>,
+        _stream: bool,
+    ) -> Result<serde_json::Value, ProviderError> {
+        let body = serde_json::to_value(request)?;
+        hypr_bedrock::converse_request(&body)
+            .map_err(|e| ProviderError::InvalidRequest(e.to_string()))
+    }
+
+    fn response_translator(
+        &self,
+        model: Option<&str>,
+        headers: &HeaderMap,
+    ) -> Option<Box<dyn ResponseTranslator>> {
+        let id = headers
+            .get(REQUEST_ID_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default();
+        let created = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        Some(Box::new(BedrockTranslator {
+            id: id.to_string(),
+            model: model.unwrap_or_default().to_string(),
+            created,
+            decoder: EventStreamDecoder::new(),
+            stream: StreamTranslator::new(id, model.unwrap_or_default(), created),
+            failed: false,
+        }))
+    }
+
+    fn parse_response(&self, body: &[u8]) -> Result<GenerationMetadata, ProviderError> {
+        parse_chat_completion(body)
+    }
+
+    fn parse_stream_chunk(&self, chunk: &[u8], accumulator: &mut StreamAccumulator) {
+        accumulate_stream_chunk(chunk, accumulator);
+    }
+}
+
+struct BedrockTranslator {
+    id: String,
+    model: String,
+    created: u64,
+    decoder: EventStreamDecoder,
+    stream: StreamTranslator,
+    failed: bool,
+}
+
+impl BedrockTranslator {
+    fn translate_events(&mut self, chunk: &[u8]) -> Result<String, hypr_bedrock::Error> {
+        let mut out = String::new();
+        for message in self.decoder.push(chunk)? {
+            for event in self.stream.translate(&message)? {
+                out.push_str(&hypr_bedrock::sse_event(&event));
+            }
+        }
+        Ok(out)
+    }
+}
+
+impl ResponseTranslator for BedrockTranslator {
+    fn translate_body(&mut self, body: &[u8]) -> Result<Vec<u8>, ProviderError> {
+        let response: serde_json::Value =
+            serde_json::from_slice(body).map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        let completion =
+            hypr_bedrock::chat_completion(&response, &self.id, &self.model, self.created)
+                .map_err(|e| ProviderError::ParseError(e.to_string()))?;
+        Ok(serde_json::to_vec(&completion)?)
+    }
+
+    fn translate_chunk(&mut self, chunk: &[u8]) -> Vec<u8> {
+        if self.failed {
+            return Vec::new();
+        }
+
+        match self.translate_events(chunk) {
+            Ok(events) => events.into_bytes(),
+            Err(e) => {
+                tracing::warn!(error.message = %e, "bedrock_stream_failed");
+                self.failed = true;
+                let error = serde_json::json!({ "error": { "message": e.to_string() } });
+                hypr_bedrock::sse_event(&error).into_bytes()
+            }
+        }
+    }
+
+    fn finish(&mut self) -> Vec<u8> {
+        if self.failed {
+            Vec::new()
+        } else {
+            SSE_DONE.as_bytes().to_vec()
+        }
+    }
+}
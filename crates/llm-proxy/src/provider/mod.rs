@@ -1,11 +1,16 @@
+mod bedrock;
+mod openai_compatible;
 mod openrouter;
 
+pub use bedrock::BedrockProvider;
+pub use openai_compatible::OpenAiCompatibleProvider;
 pub use openrouter::OpenRouterProvider;
 
 use reqwest::Client;
+use reqwest::header::HeaderMap;
 use serde::{Deserialize, Serialize};
 
-use crate::types::ChatCompletionRequest;
+use crate::types::{ChatCompletionRequest, UsageInfo};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenerationMetadata {
@@ -51,11 +56,37 @@ pub enum ProviderError {
     InvalidRequest(String),
 }
 
+/// Rewrites a successful upstream response into the OpenAI format, for providers with their
+/// own wire format.
+pub trait ResponseTranslator: Send {
+    fn translate_body(&mut self, body: &[u8]) -> Result<Vec<u8>, ProviderError>;
+
+    /// OpenAI-format SSE bytes for a chunk of the upstream stream; may be empty while a frame
+    /// is incomplete.
+    fn translate_chunk(&mut self, chunk: &[u8]) -> Vec<u8>;
+
+    /// Bytes to send once the upstream stream ends, such as `data: [DONE]`.
+    fn finish(&mut self) -> Vec<u8> {
+        Vec::new()
+    }
+}
+
 pub trait Provider: Send + Sync {
     fn name(&self) -> &str;
 
     fn base_url(&self) -> &str;
 
+    /// Where the request is sent. Providers that put the model in the path override this.
+    fn request_url(
+        &self,
+        request: &ChatCompletionRequest,
+        models: &[String],
+        stream: bool,
+    ) -> Result<String, ProviderError> {
+        let _ = (request, models, stream);
+        Ok(self.base_url().to_string())
+    }
+
     fn build_request(
         &self,
         request: &ChatCompletionRequest,
@@ -63,6 +94,16 @@ pub trait Provider: Send + Sync {
         stream: bool,
     ) -> Result<serde_json::Value, ProviderError>;
 
+    /// `parse_response` and `parse_stream_chunk` see the translated bytes.
+    fn response_translator(
+        &self,
+        model: Option<&str>,
+        headers: &HeaderMap,
+    ) -> Option<Box<dyn ResponseTranslator>> {
+        let _ = (model, headers);
+        None
+    }
+
     fn parse_response(&self, body: &[u8]) -> Result<GenerationMetadata, ProviderError>;
 
     fn parse_stream_chunk(&self, chunk: &[u8], accumulator: &mut StreamAccumulator);
@@ -85,3 +126,67 @@ pub trait Provider: Send + Sync {
         vec![]
     }
 }
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionResponse {
+    id: String,
+    model: Option<String>,
+    usage: Option<UsageInfo>,
+}
+
+/// Usage metadata from a non-streaming OpenAI-format `chat.completion` body.
+fn parse_chat_completion(body: &[u8]) -> Result<GenerationMetadata, ProviderError> {
+    let parsed: ChatCompletionResponse =
+        serde_json::from_slice(body).map_err(|e| ProviderError::ParseError(e.to_string()))?;
+
+    Ok(GenerationMetadata {
+        generation_id: parsed.id,
+        model: parsed.model,
+        input_tokens: parsed.usage.as_ref().map(|u| u.input_tokens()).unwrap_or(0),
+        output_tokens: parsed
+            .usage
+            .as_ref()
+            .map(|u| u.output_tokens())
+            .unwrap_or(0),
+    })
+}
+
+/// Picks id, model and usage out of OpenAI-format SSE `chat.completion.chunk` events.
+fn accumulate_stream_chunk(chunk: &[u8], accumulator: &mut StreamAccumulator) {
+    let Ok(text) = std::str::from_utf8(chunk) else {
+        return;
+    };
+
+    for line in text.lines() {
+        let Some(data) = line.strip_prefix("data: ") else {
+            continue;
+        };
+
+        if data.trim() == "[DONE]" {
+            continue;
+        }
+
+        let Ok(parsed) = serde_json::from_str::<serde_json::Value>(data) else {
+            continue;
+        };
+
+        if accumulator.generation_id.is_none() {
+            accumulator.generation_id = parsed.get("id").and_then(|v| v.as_str()).map(String::from);
+        }
+
+        if accumulator.model.is_none() {
+            accumulator.model = parsed
+                .get("model")
+                .and_then(|v| v.as_str())
+                .map(String::from);
+        }
+
+        if let Some(usage) = parsed
+            .get("usage")
+            .and_then(|u| serde_json::from_value::<UsageInfo>(u.clone()).ok())
+        {
+            accumulator.input_tokens = usage.input_tokens();
+            accumulator.output_tokens = usage.output_tokens();
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use crate::types::ChatCompletionRequest;
+
+use super::{
+    GenerationMetadata, Provider, ProviderError, StreamAccumulator, accumulate_stream_chunk,
+    parse_chat_completion,
+};
+
+/// Any server speaking the OpenAI chat completions API directly: OpenAI itself, vLLM, Ollama,
+/// llama.cpp, LiteLLM and so on. Unlike OpenRouter there is no server-side fallback, so only
+/// the first resolved model is sent.
+pub struct OpenAiCompatibleProvider {
+    pub base_url: String,
+}
+
+const CHAT_COMPLETIONS_PATH: &str = "/chat/completions";
+
+impl OpenAiCompatibleProvider {
+    /// Takes the API base, e.g. `http://localhost:11434/v1`, or the full chat completions
+    /// endpoint; requests always go to the latter.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        let base_url = base_url.into();
+        let base = base_url.trim_end_matches('/');
+        let base_url = if base.ends_with(CHAT_COMPLETIONS_PATH) {
+            base.to_string()
+        } else {
+            format!("{base}{CHAT_COMPLETIONS_PATH}")
+        };
+        Self { base_url }
+    }
+}
+
+impl Provider for OpenAiCompatibleProvider {
+    fn name(&self) -> &str {
+        "openai_compatible"
+    }
+
+    fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    fn build_request(
+        &self,
+        request: &ChatCompletionRequest,
+        models: Vec<String>,
+        stream: bool,
+    ) -> Result<serde_json::Value, ProviderError> {
+        let mut body = serde_json::to_value(request)?;
+        let obj = body.as_object_mut().unwrap();
+
+        // Without a configured model, keep whatever the client asked for.
+        if let Some(model) = models.into_iter().next() {
+            obj.insert("model".to_string(), serde_json::Value::String(model));
+        }
+        if !obj.get("model").is_some_and(|model| model.is_string()) {
+            return Err(ProviderError::InvalidRequest(
+                "no model configured or requested".to_string(),
+            ));
+        }
+
+        obj.insert("stream".to_string(), serde_json::Value::Bool(stream));
+        if stream {
+            obj.insert(
+                "stream_options".to_string(),
+                serde_json::json!({ "include_usage": true }),
+            );
+        }
+
+        Ok(body)
+    }
+
+    fn parse_response(&self, body: &[u8]) -> Result<GenerationMetadata, ProviderError> {
+        parse_chat_completion(body)
+    }
+
+    fn parse_stream_chunk(&self, chunk: &[u8], accumulator: &mut StreamAccumulator) {
+        accumulate_stream_chunk(chunk, accumulator);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(model: Option<&str>) -> ChatCompletionRequest {
+        serde_json::from_value(serde_json::json!({
+            "model": model,
+            "messages": [{ "role": "user", "content": "hi" }],
+            "temperature": 0.2,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn endpoint() {
+        for url in [
+            "http://localhost:11434/v1",
+            "http://localhost:11434/v1/",
+            "http://localhost:11434/v1/chat/completions",
+        ] {
+            assert_eq!(
+                OpenAiCompatibleProvider::new(url).base_url(),
+                "http://localhost:11434/v1/chat/completions",
+                "{url}"
+            );
+        }
+    }
+
+    #[test]
+    fn build_request_uses_first_configured_model() {
+        let provider = OpenAiCompatibleProvider::new("http://localhost:11434/v1");
+        let body = provider
+            .build_request(
+                &request(Some("gpt-4o")),
+                vec!["llama3.1:8b".to_string(), "qwen2.5:14b".to_string()],
+                true,
+            )
+            .unwrap();
+
+        assert_eq!(body["model"], "llama3.1:8b");
+        assert_eq!(body["stream"], true);
+        assert_eq!(body["stream_options"]["include_usage"], true);
+        assert_eq!(body["messages"][0]["content"], "hi");
+    }
+
+    #[test]
+    fn build_request_keeps_requested_model() {
+        let provider = OpenAiCompatibleProvider::new("http://localhost:11434/v1");
+        let body = provider
+            .build_request(&request(Some("gpt-4o")), vec![], false)
+            .unwrap();
+
+        assert_eq!(body["model"], "gpt-4o");
+        assert_eq!(body["stream"], false);
+        assert!(body.get("stream_options").is_none());
+    }
+
+    #[test]
+    fn build_request_needs_a_model() {
+        let provider = OpenAiCompatibleProvider::new("http://localhost:11434/v1");
+        let result = provider.build_request(&request(None), vec![], false);
+
+        assert!(matches!(result, Err(ProviderError::InvalidRequest(_))));
+    }
+}
@@ -3,11 +3,13 @@ use hypr_openrouter::{
     ProviderSortUnion,
 };
 use reqwest::Client;
-use serde::Deserialize;
 
-use crate::types::{ChatCompletionRequest, UsageInfo};
+use crate::types::ChatCompletionRequest;
 
-use super::{GenerationMetadata, Provider, ProviderError, StreamAccumulator};
+use super::{
+    GenerationMetadata, Provider, ProviderError, StreamAccumulator, accumulate_stream_chunk,
+    parse_chat_completion,
+};
 
 pub const OPENROUTER_URL: &str = "https://openrouter.ai/api/v1/chat/completions";
 
@@ -31,13 +33,6 @@ impl OpenRouterProvider {
     }
 }
 
-#[derive(Debug, Deserialize)]
-struct OpenRouterResponse {
-    pub id: String,
-    pub model: Option<String>,
-    pub usage: Option<UsageInfo>,
-}
-
 impl Provider for OpenRouterProvider {
     fn name(&self) -> &str {
         "openrouter"
@@ -74,59 +69,11 @@ impl Provider for OpenRouterProvider {
     }
 
     fn parse_response(&self, body: &[u8]) -> Result<GenerationMetadata, ProviderError> {
-        let parsed: OpenRouterResponse =
-            serde_json::from_slice(body).map_err(|e| ProviderError::ParseError(e.to_string()))?;
-
-        Ok(GenerationMetadata {
-            generation_id: parsed.id,
-            model: parsed.model,
-            input_tokens: parsed.usage.as_ref().map(|u| u.input_tokens()).unwrap_or(0),
-            output_tokens: parsed
-                .usage
-                .as_ref()
-                .map(|u| u.output_tokens())
-                .unwrap_or(0),
-        })
+        parse_chat_completion(body)
     }
 
     fn parse_stream_chunk(&self, chunk: &[u8], accumulator: &mut StreamAccumulator) {
-        let Ok(text) = std::str::from_utf8(chunk) else {
-            return;
-        };
-
-        for line in text.lines() {
-            let Some(data) = line.strip_prefix("data: ") else {
-                continue;
-            };
-
-            if data.trim() == "[DONE]" {
-                continue;
-            }
-
-            let Ok(parsed) = serde_json::from_str::<serde_json::Value>(data) else {
-                continue;
-            };
-
-            if accumulator.generation_id.is_none() {
-                accumulator.generation_id =
-                    parsed.get("id").and_then(|v| v.as_str()).map(String::from);
-            }
-
-            if accumulator.model.is_none() {
-                accumulator.model = parsed
-                    .get("model")
-                    .and_then(|v| v.as_str())
-                    .map(String::from);
-            }
-
-            if let Some(usage) = parsed
-                .get("usage")
-                .and_then(|u| serde_json::from_value::<UsageInfo>(u.clone()).ok())
-            {
-                accumulator.input_tokens = usage.input_tokens();
-                accumulator.output_tokens = usage.output_tokens();
-            }
-        }
+        accumulate_stream_chunk(chunk, accumulator);
     }
 
     fn fetch_cost(
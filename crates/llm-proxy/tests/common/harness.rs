@@ -4,7 +4,7 @@ use std::sync::Arc;
 
 use axum::body::Body;
 use axum::http::Request;
-use llm_proxy::provider::OpenRouterProvider;
+use llm_proxy::provider::{BedrockProvider, OpenRouterProvider};
 use llm_proxy::{GenerationEvent, LlmProxyConfig, MODEL_KEY_DEFAULT, StaticModelResolver};
 use wiremock::matchers::{header, method, path};
 use wiremock::{Mock, MockServer, ResponseTemplate};
@@ -63,6 +63,31 @@ impl TestHarness {
             .with_model_resolver(Arc::new(resolver))
     }
 
+    pub fn config_bedrock(&self) -> LlmProxyConfig {
+        let resolver = StaticModelResolver::default()
+            .with_models(MODEL_KEY_DEFAULT, vec!["amazon.nova-lite-v1:0".into()]);
+        LlmProxyConfig::new("test-api-key")
+            .with_provider(Arc::new(BedrockProvider::with_base_url(
+                self.mock_server.uri(),
+            )))
+            .with_model_resolver(Arc::new(resolver))
+            .with_analytics(Arc::new(self.analytics.clone()))
+    }
+
+    pub async fn mount_bedrock_response(&self, action: &str, content_type: &str, body: Vec<u8>) {
+        Mock::given(method("POST"))
+            .and(path(format!("/model/amazon.nova-lite-v1%3A0/{action}")))
+            .and(header("Authorization", "Bearer test-api-key"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(body, content_type)
+                    .insert_header("x-amzn-requestid", "bedrock-req-1"),
+            )
+            .expect(1)
+            .mount(&self.mock_server)
+            .await;
+    }
+
     pub async fn mount_json_response(&self, response: serde_json::Value) {
         Mock::given(method("POST"))
             .and(path("/"))
@@ -136,6 +161,14 @@ pub fn stream_chunks(id: &str) -> [String; 4] {
     ]
 }
 
+/// One `application/vnd.amazon.eventstream` frame, as ConverseStream sends it.
+pub fn bedrock_event(event_type: &str, payload: serde_json::Value) -> Vec<u8> {
+    hypr_bedrock::eventstream::encode(
+        &[(":event-type", event_type), (":message-type", "event")],
+        payload.to_string().as_bytes(),
+    )
+}
+
 pub fn completion_response(id: &str, model: &str, content: &str) -> serde_json::Value {
     serde_json::json!({
         "id": id,
@@ -149,6 +149,94 @@ mod error_handling {
     }
 }
 
+mod bedrock {
+    use super::*;
+
+    #[tokio::test]
+    async fn non_streaming() {
+        let harness = TestHarness::new().await;
+        let converse = serde_json::json!({
+            "output": { "message": { "role": "assistant", "content": [{ "text": "hello" }] } },
+            "stopReason": "end_turn",
+            "usage": { "inputTokens": 10, "outputTokens": 1, "totalTokens": 11 },
+        });
+        harness
+            .mount_bedrock_response(
+                "converse",
+                "application/json",
+                serde_json::to_vec(&converse).unwrap(),
+            )
+            .await;
+
+        let response = router(harness.config_bedrock())
+            .oneshot(build_request(simple_message(
+                "Say 'hello' and nothing else.",
+            )))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response_to_json(response).await;
+        assert_eq!(body["id"], "bedrock-req-1");
+        assert_eq!(body["object"], "chat.completion");
+        assert_eq!(body["choices"][0]["message"]["content"], "hello");
+        assert_eq!(body["choices"][0]["finish_reason"], "stop");
+
+        let event = harness.analytics.get_single_event().await;
+        assert_eq!(event.generation_id, "bedrock-req-1");
+        assert_eq!(event.model, "amazon.nova-lite-v1:0");
+        assert_eq!(event.input_tokens, 10);
+        assert_eq!(event.output_tokens, 1);
+    }
+
+    #[tokio::test]
+    async fn streaming() {
+        let harness = TestHarness::new().await;
+        let frames = [
+            bedrock_event("messageStart", serde_json::json!({ "role": "assistant" })),
+            bedrock_event(
+                "contentBlockDelta",
+                serde_json::json!({ "contentBlockIndex": 0, "delta": { "text": "hello" } }),
+            ),
+            bedrock_event(
+                "messageStop",
+                serde_json::json!({ "stopReason": "end_turn" }),
+            ),
+            bedrock_event(
+                "metadata",
+                serde_json::json!({ "usage": { "inputTokens": 8, "outputTokens": 1, "totalTokens": 9 } }),
+            ),
+        ];
+        harness
+            .mount_bedrock_response(
+                "converse-stream",
+                "application/vnd.amazon.eventstream",
+                frames.concat(),
+            )
+            .await;
+
+        let response = router(harness.config_bedrock())
+            .oneshot(build_request(stream_request(
+                "Say 'hello' and nothing else.",
+            )))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_str = response_to_string(response).await;
+        assert!(body_str.contains(r#""content":"hello""#));
+        assert!(body_str.contains(r#""finish_reason":"stop""#));
+        assert!(body_str.ends_with("data: [DONE]\n\n"));
+
+        let event = harness.analytics.get_single_event().await;
+        assert_eq!(event.generation_id, "bedrock-req-1");
+        assert_eq!(event.input_tokens, 8);
+        assert_eq!(event.output_tokens, 1);
+    }
+}
+
 mod e2e {
     use super::*;
 
@@ -2,6 +2,7 @@ use std::path::PathBuf;
 use std::sync::Arc;
 
 use axum::{Router, error_handling::HandleError, http::StatusCode};
+use hypr_ws_utils::ConnectionManager;
 use tower_http::cors::{self, CorsLayer};
 
 mod axum_server;
@@ -12,6 +13,10 @@ pub use axum_server::LocalAxumServer;
 
 use runtime::{LocalServerRuntime, NoopRuntime};
 
+/// The model loads its weights per session, so live sessions and OpenAI-compatible requests
+/// share a single slot.
+const MAX_CONNECTIONS: usize = 1;
+
 pub struct LocalSttServer {
     inner: LocalAxumServer,
 }
@@ -35,16 +40,22 @@ impl LocalSttServer {
     ) -> std::io::Result<Self> {
         tracing::info!(model_path = %model_path.display(), "starting local STT server");
 
+        let connections = ConnectionManager::with_limit(MAX_CONNECTIONS);
         let cactus_service = HandleError::new(
             hypr_transcribe_cactus::TranscribeService::builder()
-                .model_path(model_path)
+                .model_path(model_path.clone())
                 .cactus_config(cactus_config)
+                .connection_manager(connections.clone())
                 .build(),
             |err: String| async move { (StatusCode::INTERNAL_SERVER_ERROR, err) },
         );
 
         let router = Router::new()
             .route_service("/v1/listen", cactus_service)
+            .merge(hypr_transcribe_cactus::openai_router(
+                model_path,
+                connections,
+            ))
             .layer(
                 CorsLayer::new()
                     .allow_origin(cors::Any)
@@ -168,7 +168,7 @@ impl AssemblyAIAdapter {
             language_detection,
             speaker_labels: Some(true),
             multichannel: None,
-            keyterms_prompt: params.keywords.clone(),
+            keyterms_prompt: params.keyterms(),
         };
 
         let mut transcript_url = base_url.clone();
@@ -65,8 +65,9 @@ impl RealtimeSttAdapter for AssemblyAIAdapter {
                 query_pairs.append_pair("max_turn_silence", max_silence);
             }
 
-            if !params.keywords.is_empty() {
-                let keyterms_json = serde_json::to_string(&params.keywords).unwrap_or_default();
+            let keyterms = params.keyterms();
+            if !keyterms.is_empty() {
+                let keyterms_json = serde_json::to_string(&keyterms).unwrap_or_default();
                 query_pairs.append_pair("keyterms_prompt", &keyterms_json);
             }
         }
@@ -137,10 +137,8 @@ fn build_cactus_batch_url(api_base: &str, params: &ListenParams) -> url::Url {
         url.query_pairs_mut()
             .append_pair("language", lang.iso639().code());
     }
-    if !params.keywords.is_empty() {
-        for kw in &params.keywords {
-            url.query_pairs_mut().append_pair("keywords", kw);
-        }
+    for kw in params.keyterms() {
+        url.query_pairs_mut().append_pair("keywords", &kw);
     }
     if let Some(ref model) = params.model {
         url.query_pairs_mut().append_pair("model", model);
@@ -10,7 +10,8 @@ impl KeywordQueryStrategy for DeepgramKeywordStrategy {
         query_pairs: &mut Serializer<'a, UrlQuery>,
         params: &ListenParams,
     ) {
-        if params.keywords.is_empty() {
+        let terms = params.vocabulary_terms();
+        if terms.is_empty() {
             return;
         }
 
@@ -29,8 +30,20 @@ impl KeywordQueryStrategy for DeepgramKeywordStrategy {
             50
         };
 
-        for keyword in params.keywords.iter().take(max_keywords) {
-            query_pairs.append_pair(param_name, keyword);
+        for term in terms.iter().take(max_keywords) {
+            // Only `keywords` takes an intensifier; keyterm prompting has no weighting.
+            match term.boost.filter(|_| use_keywords) {
+                Some(boost) => query_pairs.append_pair(
+                    param_name,
+                    &format!("{}:{}", term.text, keyword_intensifier(boost)),
+                ),
+                None => query_pairs.append_pair(param_name, &term.text),
+            };
         }
     }
 }
+
+/// Maps a 0.0–1.0 boost onto Deepgram's recommended 1–10 intensifier range.
+fn keyword_intensifier(boost: f32) -> f32 {
+    (1.0 + boost.clamp(0.0, 1.0) * 9.0).round()
+}
@@ -247,6 +247,44 @@ mod tests {
         assert!(!url_str.contains("redemption_time_ms="));
     }
 
+    #[test]
+    fn test_vocabulary_terms() {
+        let adapter = DeepgramAdapter::default();
+        let vocabulary = owhisper_interface::Vocabulary {
+            terms: vec![owhisper_interface::VocabularyTerm {
+                text: "Hyprnote".to_string(),
+                boost: Some(0.5),
+                ..Default::default()
+            }],
+            replacements: vec![],
+        };
+
+        let nova_3 = owhisper_interface::ListenParams {
+            model: Some("nova-3".to_string()),
+            keywords: vec!["hyprnote".to_string(), "transcription".to_string()],
+            vocabulary: vocabulary.clone(),
+            ..Default::default()
+        };
+        let url = adapter.build_ws_url(API_BASE, &nova_3, 1);
+        let keyterms: Vec<_> = url
+            .query_pairs()
+            .filter(|(k, _)| k == "keyterm")
+            .map(|(_, v)| v.into_owned())
+            .collect();
+        assert_eq!(keyterms, ["Hyprnote", "transcription"]);
+
+        let nova_2 = owhisper_interface::ListenParams {
+            model: Some("nova-2".to_string()),
+            vocabulary,
+            ..Default::default()
+        };
+        let url = adapter.build_ws_url(API_BASE, &nova_2, 1);
+        assert!(
+            url.query_pairs()
+                .any(|(k, v)| k == "keywords" && v == "Hyprnote:6")
+        );
+    }
+
     macro_rules! single_test {
         ($name:ident, $params:expr) => {
             #[tokio::test]
@@ -212,7 +212,8 @@ impl GladiaAdapter {
             code_switching: (params.languages.len() > 1).then_some(true),
         });
 
-        let custom_vocabulary = (!params.keywords.is_empty()).then(|| params.keywords.clone());
+        let keyterms = params.keyterms();
+        let custom_vocabulary = (!keyterms.is_empty()).then_some(keyterms);
 
         let default = crate::providers::Provider::Gladia.default_batch_model();
         let model = match params.model.as_deref() {
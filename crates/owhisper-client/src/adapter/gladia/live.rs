@@ -4,8 +4,8 @@ use std::collections::HashMap;
 use std::sync::{Mutex, OnceLock};
 
 use hypr_ws_client::client::Message;
-use owhisper_interface::ListenParams;
 use owhisper_interface::stream::{Alternatives, Channel, Metadata, StreamResponse};
+use owhisper_interface::{ListenParams, VocabularyTerm};
 use serde::{Deserialize, Serialize};
 
 use super::GladiaAdapter;
@@ -123,13 +123,10 @@ impl RealtimeSttAdapter for GladiaAdapter {
                 None => None,
             };
 
-            let has_keywords = !params.keywords.is_empty();
+            let terms = params.vocabulary_terms();
+            let has_keywords = !terms.is_empty();
             let custom_vocabulary_config = has_keywords.then(|| CustomVocabularyConfig {
-                vocabulary: params
-                    .keywords
-                    .iter()
-                    .map(|k| CustomVocabularyEntry::Simple(k.clone()))
-                    .collect(),
+                vocabulary: terms.into_iter().map(CustomVocabularyEntry::from).collect(),
                 default_intensity: None,
             });
 
@@ -354,7 +351,6 @@ struct CustomVocabularyConfig {
 #[serde(untagged)]
 enum CustomVocabularyEntry {
     Simple(String),
-    #[allow(dead_code)]
     Detailed {
         value: String,
         #[serde(skip_serializing_if = "Option::is_none")]
@@ -366,6 +362,21 @@ enum CustomVocabularyEntry {
     },
 }
 
+impl From<VocabularyTerm> for CustomVocabularyEntry {
+    fn from(term: VocabularyTerm) -> Self {
+        if term.boost.is_none() && term.sounds_like.is_empty() {
+            return Self::Simple(term.text);
+        }
+
+        Self::Detailed {
+            value: term.text,
+            pronunciations: (!term.sounds_like.is_empty()).then_some(term.sounds_like),
+            intensity: term.boost.map(|b| f64::from(b.clamp(0.0, 1.0))),
+            language: None,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(untagged)]
 enum InitResponse {
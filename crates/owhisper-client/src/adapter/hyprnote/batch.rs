@@ -53,8 +53,8 @@ async fn do_transcribe_file(
         for lang in &params.languages {
             q.append_pair("language", &lang.to_string());
         }
-        for kw in &params.keywords {
-            q.append_pair("keyword", kw);
+        for kw in params.keyterms() {
+            q.append_pair("keyword", &kw);
         }
         if let Some(custom) = &params.custom_query {
             for (key, value) in custom {
@@ -42,8 +42,8 @@ impl RealtimeSttAdapter for HyprnoteAdapter {
                 query.append_pair("language", lang.to_string().as_str());
             }
 
-            for keyword in &params.keywords {
-                query.append_pair("keyword", keyword);
+            for keyword in params.keyterms() {
+                query.append_pair("keyword", &keyword);
             }
 
             if let Some(custom) = &params.custom_query {
@@ -74,8 +74,9 @@ impl SonioxAdapter {
         if !language_hints.is_empty() {
             body["language_hints"] = serde_json::json!(language_hints);
         }
-        if !params.keywords.is_empty() {
-            body["context"] = serde_json::json!({ "terms": params.keywords });
+        let terms = params.keyterms();
+        if !terms.is_empty() {
+            body["context"] = serde_json::json!({ "terms": terms });
         }
 
         let transcription_id = soniox::create_transcription(client, &body, api_key)
@@ -58,11 +58,12 @@ impl RealtimeSttAdapter for SonioxAdapter {
 
         let model = SonioxAdapter::resolve_model(params.model.as_deref()).live_model();
 
-        let context = if params.keywords.is_empty() {
+        let terms = params.keyterms();
+        let context = if terms.is_empty() {
             None
         } else {
             Some(Context {
-                terms: params.keywords.clone(),
+                terms,
                 ..Default::default()
             })
         };
@@ -31,6 +31,8 @@ common_derives! {
         WhisperCpp(WhisperCppModelConfig),
         #[serde(rename = "moonshine")]
         Moonshine(MoonshineModelConfig),
+        #[serde(rename = "cactus")]
+        Cactus(CactusModelConfig),
     }
 }
 
@@ -41,6 +43,17 @@ impl ModelConfig {
             ModelConfig::Deepgram(config) => &config.id,
             ModelConfig::WhisperCpp(config) => &config.id,
             ModelConfig::Moonshine(config) => &config.id,
+            ModelConfig::Cactus(config) => &config.id,
+        }
+    }
+
+    /// How many sessions the model serves at once, if limited.
+    pub fn max_concurrency(&self) -> Option<usize> {
+        match self {
+            ModelConfig::Deepgram(config) => config.max_concurrency,
+            ModelConfig::WhisperCpp(config) => config.max_concurrency,
+            ModelConfig::Cactus(config) => config.max_concurrency,
+            ModelConfig::Aws(_) | ModelConfig::Moonshine(_) => None,
         }
     }
 }
@@ -97,6 +110,10 @@ common_derives! {
         pub id: String,
         pub api_key: Option<String>,
         pub base_url: Option<String>,
+        /// Model requested from the upstream; when unset the client's `model` is dropped and
+        /// the upstream default is used.
+        pub model: Option<String>,
+        pub max_concurrency: Option<usize>,
     }
 }
 
@@ -104,6 +121,7 @@ common_derives! {
     pub struct WhisperCppModelConfig {
         pub id: String,
         pub assets_dir: String,
+        pub max_concurrency: Option<usize>,
     }
 }
 
@@ -115,6 +133,14 @@ common_derives! {
     }
 }
 
+common_derives! {
+    pub struct CactusModelConfig {
+        pub id: String,
+        pub assets_dir: String,
+        pub max_concurrency: Option<usize>,
+    }
+}
+
 common_derives! {
     pub enum MoonshineModelSize {
         #[serde(rename = "tiny")]
@@ -4,10 +4,14 @@ pub mod batch_sse;
 pub mod openapi;
 pub mod progress;
 pub mod stream;
+pub mod vocabulary;
 
 #[cfg(feature = "openapi")]
 pub use openapi::openapi;
 pub use progress::{InferencePhase, InferenceProgress};
+pub use vocabulary::{
+    Vocabulary, VocabularyProfile, VocabularyReplacement, VocabularySettings, VocabularyTerm,
+};
 
 #[macro_export]
 macro_rules! common_derives {
@@ -146,6 +150,8 @@ common_derives! {
         #[serde(default)]
         pub keywords: Vec<String>,
         #[serde(default)]
+        pub vocabulary: Vocabulary,
+        #[serde(default)]
         #[cfg_attr(feature = "openapi", schema(value_type = Option<Object>))]
         pub custom_query: Option<std::collections::HashMap<String, String>>,
     }
@@ -159,6 +165,7 @@ impl Default for ListenParams {
             sample_rate: Self::default_sample_rate(),
             languages: Vec::new(),
             keywords: Vec::new(),
+            vocabulary: Vocabulary::default(),
             custom_query: None,
         }
     }
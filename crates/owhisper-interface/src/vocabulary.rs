@@ -0,0 +1,295 @@
+use crate::common_derives;
+
+common_derives! {
+    #[derive(Default)]
+    pub struct VocabularyTerm {
+        /// Canonical spelling, e.g. "Hyprnote".
+        pub text: String,
+        /// Relative emphasis from 0.0 to 1.0. Adapters with a weighting knob scale it to their
+        /// own range; the rest ignore it.
+        #[serde(default)]
+        pub boost: Option<f32>,
+        /// How the term tends to be misheard, e.g. "hyper note". Sent as pronunciation hints
+        /// where supported, and always rewritten to `text` after transcription.
+        #[serde(default)]
+        pub sounds_like: Vec<String>,
+    }
+}
+
+common_derives! {
+    pub struct VocabularyReplacement {
+        pub from: String,
+        pub to: String,
+    }
+}
+
+common_derives! {
+    /// Terms and replacements for one transcription, merged from the enabled profiles.
+    #[derive(Default)]
+    pub struct Vocabulary {
+        #[serde(default)]
+        pub terms: Vec<VocabularyTerm>,
+        #[serde(default)]
+        pub replacements: Vec<VocabularyReplacement>,
+    }
+}
+
+common_derives! {
+    /// A named set of terms stored under `vocabulary.profiles` in the vault's `settings.json`.
+    pub struct VocabularyProfile {
+        pub id: String,
+        pub name: String,
+        #[serde(default = "VocabularyProfile::default_enabled")]
+        pub enabled: bool,
+        #[serde(default)]
+        pub terms: Vec<VocabularyTerm>,
+        #[serde(default)]
+        pub replacements: Vec<VocabularyReplacement>,
+    }
+}
+
+impl VocabularyProfile {
+    fn default_enabled() -> bool {
+        true
+    }
+}
+
+common_derives! {
+    /// The `vocabulary` section of `settings.json`.
+    #[derive(Default)]
+    pub struct VocabularySettings {
+        #[serde(default)]
+        pub profiles: Vec<VocabularyProfile>,
+    }
+}
+
+impl VocabularySettings {
+    pub const KEY: &'static str = "vocabulary";
+
+    /// Reads the section from the whole settings document. A missing or malformed section
+    /// yields no profiles; a malformed one is logged.
+    pub fn from_settings(settings: &serde_json::Value) -> Self {
+        match settings.get(Self::KEY) {
+            Some(section) => serde_json::from_value(section.clone()).unwrap_or_else(|e| {
+                tracing::warn!(error.message = %e, "vocabulary_settings_invalid");
+                Self::default()
+            }),
+            None => Self::default(),
+        }
+    }
+
+    pub fn vocabulary(&self) -> Vocabulary {
+        Vocabulary::from_profiles(&self.profiles)
+    }
+}
+
+impl Vocabulary {
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty() && self.replacements.is_empty()
+    }
+
+    /// Merges enabled profiles in order. A term that appears in several profiles keeps its
+    /// first definition.
+    pub fn from_profiles<'a>(profiles: impl IntoIterator<Item = &'a VocabularyProfile>) -> Self {
+        let mut vocabulary = Self::default();
+
+        for profile in profiles.into_iter().filter(|p| p.enabled) {
+            for term in &profile.terms {
+                vocabulary.push_term(term.clone());
+            }
+            vocabulary
+                .replacements
+                .extend(profile.replacements.iter().cloned());
+        }
+
+        vocabulary
+    }
+
+    /// The enabled profiles from the vault's settings, followed by this vocabulary. Settings
+    /// that failed to load leave it unchanged.
+    pub fn with_vault_settings<E: std::fmt::Display>(
+        self,
+        settings: Result<serde_json::Value, E>,
+    ) -> Self {
+        match settings {
+            Ok(settings) => VocabularySettings::from_settings(&settings)
+                .vocabulary()
+                .merged(self),
+            Err(e) => {
+                tracing::warn!(error.message = %e, "vocabulary_settings_load_failed");
+                self
+            }
+        }
+    }
+
+    /// Appends `other` after this vocabulary, skipping terms already present.
+    pub fn merged(mut self, other: Vocabulary) -> Self {
+        for term in other.terms {
+            self.push_term(term);
+        }
+        self.replacements.extend(other.replacements);
+        self
+    }
+
+    fn push_term(&mut self, term: VocabularyTerm) {
+        let text = term.text.trim();
+        if text.is_empty() || self.contains(text) {
+            return;
+        }
+
+        self.terms.push(VocabularyTerm {
+            text: text.to_string(),
+            ..term
+        });
+    }
+
+    fn contains(&self, text: &str) -> bool {
+        self.terms.iter().any(|t| t.text.eq_ignore_ascii_case(text))
+    }
+}
+
+impl crate::ListenParams {
+    /// Vocabulary terms followed by `keywords` not already among them, so adapters that
+    /// truncate keep the explicitly configured terms.
+    pub fn vocabulary_terms(&self) -> Vec<VocabularyTerm> {
+        let mut merged = Vocabulary::default();
+
+        for term in &self.vocabulary.terms {
+            merged.push_term(term.clone());
+        }
+        for keyword in &self.keywords {
+            merged.push_term(VocabularyTerm {
+                text: keyword.clone(),
+                ..Default::default()
+            });
+        }
+
+        merged.terms
+    }
+
+    /// Plain term list for providers that only accept strings.
+    pub fn keyterms(&self) -> Vec<String> {
+        self.vocabulary_terms()
+            .into_iter()
+            .map(|term| term.text)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ListenParams;
+
+    fn term(text: &str) -> VocabularyTerm {
+        VocabularyTerm {
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_from_profiles_skips_disabled_and_duplicates() {
+        let profiles = [
+            VocabularyProfile {
+                id: "product".to_string(),
+                name: "Product".to_string(),
+                enabled: true,
+                terms: vec![
+                    VocabularyTerm {
+                        boost: Some(0.8),
+                        ..term("Hyprnote")
+                    },
+                    term("  "),
+                ],
+                replacements: vec![VocabularyReplacement {
+                    from: "hyper note".to_string(),
+                    to: "Hyprnote".to_string(),
+                }],
+            },
+            VocabularyProfile {
+                id: "people".to_string(),
+                name: "People".to_string(),
+                enabled: true,
+                terms: vec![term("hyprnote"), term("Yujong")],
+                replacements: vec![],
+            },
+            VocabularyProfile {
+                id: "old".to_string(),
+                name: "Old".to_string(),
+                enabled: false,
+                terms: vec![term("Legacy")],
+                replacements: vec![],
+            },
+        ];
+
+        let vocabulary = Vocabulary::from_profiles(&profiles);
+        let texts: Vec<_> = vocabulary.terms.iter().map(|t| t.text.as_str()).collect();
+
+        assert_eq!(texts, ["Hyprnote", "Yujong"]);
+        assert_eq!(vocabulary.terms[0].boost, Some(0.8));
+        assert_eq!(vocabulary.replacements.len(), 1);
+    }
+
+    #[test]
+    fn test_settings_section() {
+        let settings = serde_json::json!({
+            "general": {},
+            "vocabulary": {
+                "profiles": [{
+                    "id": "product",
+                    "name": "Product",
+                    "terms": [{ "text": "Hyprnote", "sounds_like": ["hyper note"] }],
+                }],
+            },
+        });
+
+        let vocabulary = VocabularySettings::from_settings(&settings).vocabulary();
+        assert_eq!(vocabulary.terms[0].sounds_like, ["hyper note"]);
+
+        let malformed = serde_json::json!({ "vocabulary": { "profiles": "nope" } });
+        assert!(
+            VocabularySettings::from_settings(&malformed)
+                .profiles
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_with_vault_settings_puts_profiles_first() {
+        let settings = serde_json::json!({
+            "vocabulary": {
+                "profiles": [{ "id": "p", "name": "P", "terms": [{ "text": "Hyprnote" }] }],
+            },
+        });
+        let session = Vocabulary {
+            terms: vec![term("hyprnote"), term("Char")],
+            replacements: vec![],
+        };
+
+        let merged = session
+            .clone()
+            .with_vault_settings(Ok::<_, String>(settings));
+        let texts: Vec<_> = merged.terms.iter().map(|t| t.text.as_str()).collect();
+        assert_eq!(texts, ["Hyprnote", "Char"]);
+
+        let unchanged = session
+            .clone()
+            .with_vault_settings(Err("settings.json unreadable"));
+        assert_eq!(unchanged, session);
+    }
+
+    #[test]
+    fn test_keyterms_puts_vocabulary_before_keywords() {
+        let params = ListenParams {
+            keywords: vec!["transcription".to_string(), "hyprnote".to_string()],
+            vocabulary: Vocabulary {
+                terms: vec![term("Hyprnote")],
+                replacements: vec![],
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(params.keyterms(), ["Hyprnote", "transcription"]);
+    }
+}
@@ -0,0 +1,13 @@
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Config(#[from] owhisper_config::Error),
+    #[error("no models configured")]
+    NoModels,
+    #[error("model id '{0}' is used more than once")]
+    DuplicateModel(String),
+    #[error("model '{id}': {kind} models are not supported by this server")]
+    UnsupportedModel { id: String, kind: &'static str },
+    #[error("model '{id}': {reason}")]
+    InvalidModel { id: String, reason: String },
+}
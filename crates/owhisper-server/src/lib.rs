@@ -0,0 +1,246 @@
+mod error;
+mod model;
+mod upstream;
+mod whisper;
+
+pub use error::*;
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use axum::{
+    Json, Router,
+    extract::{Request, State},
+    http::{StatusCode, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{any, get},
+};
+use tower_http::cors::{self, CorsLayer};
+
+use model::Model;
+
+const MAX_BATCH_BYTES: usize = 100 * 1024 * 1024;
+
+#[derive(Clone)]
+struct AppState {
+    /// In config order; the first one serves requests that don't name a model.
+    models: Arc<Vec<Model>>,
+    api_key: Option<Arc<str>>,
+}
+
+/// Serves every model in `config` behind the Deepgram-compatible `/v1/listen` endpoint,
+/// picking the model from the `model` query parameter.
+pub fn router(config: &owhisper_config::Config) -> Result<Router, Error> {
+    if config.models.is_empty() {
+        return Err(Error::NoModels);
+    }
+
+    let mut seen = HashSet::new();
+    let mut models = Vec::with_capacity(config.models.len());
+    for model_config in &config.models {
+        if !seen.insert(model_config.id()) {
+            return Err(Error::DuplicateModel(model_config.id().to_string()));
+        }
+        models.push(Model::from_config(model_config)?);
+    }
+
+    let state = AppState {
+        models: Arc::new(models),
+        api_key: config
+            .general
+            .as_ref()
+            .and_then(|general| general.api_key.as_deref())
+            .map(Arc::from),
+    };
+
+    let api = Router::new()
+        .route("/v1/listen", any(listen))
+        .route("/v1/models", get(list_models))
+        .route_layer(middleware::from_fn_with_state(
+            state.clone(),
+            require_api_key,
+        ));
+
+    Ok(Router::new()
+        .route("/health", get(|| async { "ok" }))
+        .merge(api)
+        .with_state(state)
+        .layer(
+            CorsLayer::new()
+                .allow_origin(cors::Any)
+                .allow_methods(cors::Any)
+                .allow_headers(cors::Any),
+        ))
+}
+
+async fn listen(State(state): State<AppState>, request: Request) -> Response {
+    let requested = request.uri().query().and_then(|query| {
+        url::form_urlencoded::parse(query.as_bytes())
+            .find(|(key, _)| key == "model")
+            .map(|(_, value)| value.into_owned())
+    });
+
+    let model = match requested.as_deref() {
+        None => state.models.first(),
+        Some(id) => state.models.iter().find(|model| model.id == id),
+    };
+    let Some(model) = model else {
+        let available = state
+            .models
+            .iter()
+            .map(|model| model.id.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        return (
+            StatusCode::NOT_FOUND,
+            format!(
+                "unknown model '{}', available: {available}",
+                requested.unwrap_or_default()
+            ),
+        )
+            .into_response();
+    };
+
+    tracing::info!(model = %model.id, "listen_request");
+    model.serve(request).await
+}
+
+async fn list_models(State(state): State<AppState>) -> Json<Vec<String>> {
+    Json(state.models.iter().map(|model| model.id.clone()).collect())
+}
+
+/// Deepgram clients send `Token <key>`; `Bearer <key>` is accepted too.
+async fn require_api_key(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let Some(expected) = state.api_key.as_deref() else {
+        return next.run(request).await;
+    };
+
+    let provided = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| {
+            value
+                .strip_prefix("Token ")
+                .or_else(|| value.strip_prefix("Bearer "))
+        });
+
+    if provided.is_some_and(|provided| constant_time_eq(provided.as_bytes(), expected.as_bytes())) {
+        next.run(request).await
+    } else {
+        StatusCode::UNAUTHORIZED.into_response()
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use axum::body::Body;
+    use owhisper_config::{Config, DeepgramModelConfig, GeneralConfig, ModelConfig};
+    use tower::ServiceExt;
+
+    fn config(api_key: Option<&str>) -> Config {
+        Config {
+            general: Some(GeneralConfig {
+                api_key: api_key.map(Into::into),
+            }),
+            models: vec![
+                ModelConfig::Deepgram(DeepgramModelConfig {
+                    id: "nova".into(),
+                    ..Default::default()
+                }),
+                ModelConfig::Deepgram(DeepgramModelConfig {
+                    id: "office".into(),
+                    base_url: Some("http://10.0.0.5:8080/v1".into()),
+                    ..Default::default()
+                }),
+            ],
+            ..Default::default()
+        }
+    }
+
+    async fn status(router: Router, uri: &str, authorization: Option<&str>) -> StatusCode {
+        let mut request = Request::builder().uri(uri);
+        if let Some(authorization) = authorization {
+            request = request.header(header::AUTHORIZATION, authorization);
+        }
+        router
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+            .status()
+    }
+
+    #[tokio::test]
+    async fn unknown_model_is_not_found() {
+        let router = router(&config(None)).unwrap();
+        assert_eq!(
+            status(router, "/v1/listen?model=large", None).await,
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[tokio::test]
+    async fn api_key_is_required_when_configured() {
+        let router = router(&config(Some("secret"))).unwrap();
+        assert_eq!(
+            status(router.clone(), "/v1/models", None).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(router.clone(), "/v1/models", Some("Token secreT")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(router.clone(), "/v1/models", Some("Token secret")).await,
+            StatusCode::OK
+        );
+        assert_eq!(status(router, "/health", None).await, StatusCode::OK);
+    }
+
+    #[test]
+    fn rejects_duplicate_and_unsupported_models() {
+        let mut duplicate = config(None);
+        duplicate.models.push(duplicate.models[0].clone());
+        assert!(matches!(
+            router(&duplicate),
+            Err(Error::DuplicateModel(id)) if id == "nova"
+        ));
+
+        let unsupported = Config {
+            models: vec![ModelConfig::Aws(owhisper_config::AwsModelConfig {
+                id: "aws".into(),
+                region: "us-east-1".into(),
+                access_key_id: String::new(),
+                secret_access_key: String::new(),
+            })],
+            ..Default::default()
+        };
+        assert!(matches!(
+            router(&unsupported),
+            Err(Error::UnsupportedModel { kind: "aws", .. })
+        ));
+    }
+
+    #[test]
+    fn rejects_zero_max_concurrency() {
+        let zero = Config {
+            models: vec![ModelConfig::Deepgram(DeepgramModelConfig {
+                id: "nova".into(),
+                max_concurrency: Some(0),
+                ..Default::default()
+            })],
+            ..Default::default()
+        };
+        assert!(matches!(
+            router(&zero),
+            Err(Error::InvalidModel { id, .. }) if id == "nova"
+        ));
+    }
+}
@@ -0,0 +1,65 @@
+use std::net::SocketAddr;
+use std::path::PathBuf;
+
+use clap::Parser;
+use tower_http::trace::TraceLayer;
+
+/// Serves the models from an owhisper config over the Deepgram-compatible `/v1/listen` API.
+#[derive(Parser)]
+#[command(version)]
+struct Args {
+    /// Config file; defaults to the global owhisper config
+    #[arg(long, short, env = "OWHISPER_CONFIG")]
+    config: Option<PathBuf>,
+    /// Binding beyond loopback requires `general.api_key` in the config
+    #[arg(long, env = "OWHISPER_HOST", default_value = "127.0.0.1")]
+    host: std::net::IpAddr,
+    #[arg(long, short, env = "OWHISPER_PORT", default_value_t = 52693)]
+    port: u16,
+}
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()),
+        )
+        .init();
+
+    if let Err(e) = run(Args::parse()).await {
+        eprintln!("error: {e}");
+        std::process::exit(1);
+    }
+}
+
+async fn run(args: Args) -> Result<(), Box<dyn std::error::Error>> {
+    let config =
+        owhisper_config::Config::new(args.config.map(|path| path.to_string_lossy().into_owned()))?;
+    let has_api_key = config
+        .general
+        .as_ref()
+        .is_some_and(|general| general.api_key.is_some());
+    if !args.host.is_loopback() && !has_api_key {
+        return Err(format!(
+            "refusing to listen on {} without general.api_key in the config",
+            args.host
+        )
+        .into());
+    }
+    let router = owhisper_server::router(&config)?.layer(TraceLayer::new_for_http());
+
+    let addr = SocketAddr::from((args.host, args.port));
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!(
+        addr = %addr,
+        models = ?config.models.iter().map(|model| model.id()).collect::<Vec<_>>(),
+        "server_listening"
+    );
+
+    axum::serve(listener, router)
+        .with_graceful_shutdown(async {
+            tokio::signal::ctrl_c().await.ok();
+        })
+        .await?;
+    Ok(())
+}
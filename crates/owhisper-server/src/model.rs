@@ -0,0 +1,203 @@
+use std::path::{Path, PathBuf};
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use futures_util::StreamExt;
+use hypr_ws_utils::{ConnectionGuard, ConnectionManager};
+use owhisper_config::ModelConfig;
+use tower::ServiceExt;
+
+use crate::Error;
+use crate::upstream::Upstream;
+use crate::whisper;
+
+/// Local models load weights per session, so unless configured otherwise they serve one at a
+/// time. Upstreams are unlimited by default.
+const DEFAULT_LOCAL_CONCURRENCY: usize = 1;
+
+enum Backend {
+    WhisperCpp {
+        service: hypr_transcribe_whisper_local::TranscribeService,
+        model_path: PathBuf,
+    },
+    Cactus(hypr_transcribe_cactus::TranscribeService),
+    Upstream(Upstream),
+}
+
+pub(crate) struct Model {
+    pub(crate) id: String,
+    backend: Backend,
+    connections: Option<ConnectionManager>,
+}
+
+impl Model {
+    pub(crate) fn from_config(config: &ModelConfig) -> Result<Self, Error> {
+        let id = config.id().to_string();
+        let invalid = |reason: String| Error::InvalidModel {
+            id: id.clone(),
+            reason,
+        };
+        // A zero-permit semaphore would turn every session away.
+        if config.max_concurrency() == Some(0) {
+            return Err(invalid("max_concurrency must be at least 1".to_string()));
+        }
+        let local_connections = || {
+            ConnectionManager::with_limit(
+                config
+                    .max_concurrency()
+                    .unwrap_or(DEFAULT_LOCAL_CONCURRENCY),
+            )
+        };
+
+        let (backend, connections) = match config {
+            ModelConfig::WhisperCpp(whisper) => {
+                let model_path =
+                    whisper_model_file(Path::new(&whisper.assets_dir)).map_err(invalid)?;
+                let connections = local_connections();
+                let service = hypr_transcribe_whisper_local::TranscribeService::builder()
+                    .model_path(model_path.clone())
+                    .connection_manager(connections.clone())
+                    .build();
+                (
+                    Backend::WhisperCpp {
+                        service,
+                        model_path,
+                    },
+                    Some(connections),
+                )
+            }
+            ModelConfig::Cactus(cactus) => {
+                let model_path = PathBuf::from(&cactus.assets_dir);
+                if !model_path.exists() {
+                    return Err(invalid(format!("{} does not exist", model_path.display())));
+                }
+                let connections = local_connections();
+                let service = hypr_transcribe_cactus::TranscribeService::builder()
+                    .model_path(model_path)
+                    .connection_manager(connections.clone())
+                    .build();
+                (Backend::Cactus(service), Some(connections))
+            }
+            ModelConfig::Deepgram(deepgram) => (
+                Backend::Upstream(Upstream::new(deepgram).map_err(invalid)?),
+                deepgram.max_concurrency.map(ConnectionManager::with_limit),
+            ),
+            ModelConfig::Aws(_) => {
+                return Err(Error::UnsupportedModel { id, kind: "aws" });
+            }
+            ModelConfig::Moonshine(_) => {
+                return Err(Error::UnsupportedModel {
+                    id,
+                    kind: "moonshine",
+                });
+            }
+        };
+
+        Ok(Self {
+            id,
+            backend,
+            connections,
+        })
+    }
+
+    pub(crate) async fn serve(&self, request: Request) -> Response {
+        let websocket = is_websocket(&request);
+
+        match &self.backend {
+            // The local services take their own connection guard for streaming sessions.
+            Backend::WhisperCpp { service, .. } if websocket => {
+                call(service.clone(), request).await
+            }
+            Backend::WhisperCpp { model_path, .. } => match self.acquire() {
+                Ok(guard) => hold(whisper::batch(model_path.clone(), request).await, guard),
+                Err(busy) => busy.into_response(),
+            },
+            Backend::Cactus(service) if websocket => call(service.clone(), request).await,
+            Backend::Cactus(service) => match self.acquire() {
+                Ok(guard) => hold(call(service.clone(), request).await, guard),
+                Err(busy) => busy.into_response(),
+            },
+            Backend::Upstream(upstream) => match self.acquire() {
+                Ok(guard) if websocket => upstream.stream(request, guard).await,
+                Ok(guard) => hold(upstream.batch(request).await, guard),
+                Err(busy) => busy.into_response(),
+            },
+        }
+    }
+
+    fn acquire(&self) -> Result<Option<ConnectionGuard>, Busy> {
+        match &self.connections {
+            None => Ok(None),
+            Some(connections) => connections.acquire_connection().map(Some).ok_or(Busy),
+        }
+    }
+}
+
+/// The model already serves as many sessions as it is allowed to.
+struct Busy;
+
+impl IntoResponse for Busy {
+    fn into_response(self) -> Response {
+        (StatusCode::SERVICE_UNAVAILABLE, "too_many_connections").into_response()
+    }
+}
+
+async fn call<S>(service: S, request: Request) -> Response
+where
+    S: tower::Service<Request, Response = Response, Error = String>,
+{
+    service
+        .oneshot(request)
+        .await
+        .unwrap_or_else(|err| (StatusCode::INTERNAL_SERVER_ERROR, err).into_response())
+}
+
+/// Keeps the guard until the response body is fully sent, so streamed batch results count
+/// against the limit too.
+fn hold(response: Response, guard: Option<ConnectionGuard>) -> Response {
+    let Some(guard) = guard else {
+        return response;
+    };
+
+    let (parts, body) = response.into_parts();
+    let body = body.into_data_stream().map(move |chunk| {
+        let _guard = &guard;
+        chunk
+    });
+    Response::from_parts(parts, Body::from_stream(body))
+}
+
+fn is_websocket(request: &Request) -> bool {
+    request
+        .headers()
+        .get("upgrade")
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.eq_ignore_ascii_case("websocket"))
+        .unwrap_or(false)
+}
+
+/// `assets_dir` may name the ggml file itself or a directory holding exactly one.
+fn whisper_model_file(assets_dir: &Path) -> Result<PathBuf, String> {
+    if assets_dir.is_file() {
+        return Ok(assets_dir.to_path_buf());
+    }
+
+    let entries = std::fs::read_dir(assets_dir)
+        .map_err(|e| format!("cannot read {}: {e}", assets_dir.display()))?;
+    let mut models = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "bin"));
+
+    match (models.next(), models.next()) {
+        (Some(model), None) => Ok(model),
+        (None, _) => Err(format!("no .bin model in {}", assets_dir.display())),
+        (Some(_), Some(_)) => Err(format!(
+            "{} holds several .bin models; point assets_dir at one",
+            assets_dir.display()
+        )),
+    }
+}
@@ -0,0 +1,180 @@
+use axum::{
+    body::Body,
+    extract::{FromRequestParts, Request, ws::WebSocketUpgrade},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use hypr_transcribe_proxy::WebSocketProxy;
+use hypr_ws_utils::ConnectionGuard;
+use owhisper_config::DeepgramModelConfig;
+use url::Url;
+
+use crate::MAX_BATCH_BYTES;
+
+const DEFAULT_BASE_URL: &str = "https://api.deepgram.com/v1";
+
+/// A Deepgram-compatible server that requests are relayed to unchanged, apart from auth and
+/// the `model` parameter.
+pub(crate) struct Upstream {
+    base_url: Url,
+    api_key: Option<String>,
+    model: Option<String>,
+    http_client: reqwest::Client,
+}
+
+impl Upstream {
+    pub(crate) fn new(config: &DeepgramModelConfig) -> Result<Self, String> {
+        let base_url = config.base_url.as_deref().unwrap_or(DEFAULT_BASE_URL);
+        let base_url = Url::parse(base_url).map_err(|e| format!("invalid base_url: {e}"))?;
+        if !matches!(base_url.scheme(), "http" | "https") || base_url.cannot_be_a_base() {
+            return Err(format!("base_url must be an http(s) URL, got '{base_url}'"));
+        }
+
+        Ok(Self {
+            base_url,
+            api_key: config.api_key.clone(),
+            model: config.model.clone(),
+            http_client: reqwest::Client::new(),
+        })
+    }
+
+    fn listen_url(&self, query: Option<&str>, websocket: bool) -> Url {
+        let mut url = self.base_url.clone();
+        url.path_segments_mut()
+            .expect("checked in new")
+            .pop_if_empty()
+            .push("listen");
+        if websocket {
+            let scheme = if url.scheme() == "https" { "wss" } else { "ws" };
+            url.set_scheme(scheme).expect("http schemes map to ws");
+        }
+
+        let params = url::form_urlencoded::parse(query.unwrap_or_default().as_bytes())
+            .filter(|(key, _)| key != "model")
+            .collect::<Vec<_>>();
+        url.set_query(None);
+        if !params.is_empty() || self.model.is_some() {
+            let mut pairs = url.query_pairs_mut();
+            pairs.extend_pairs(params);
+            if let Some(model) = &self.model {
+                pairs.append_pair("model", model);
+            }
+        }
+        url
+    }
+
+    pub(crate) async fn stream(
+        &self,
+        request: Request,
+        guard: Option<ConnectionGuard>,
+    ) -> Response {
+        let url = self.listen_url(request.uri().query(), true);
+        let (mut parts, _body) = request.into_parts();
+        let ws = match WebSocketUpgrade::from_request_parts(&mut parts, &()).await {
+            Ok(ws) => ws,
+            Err(e) => return e.into_response(),
+        };
+
+        let mut builder = WebSocketProxy::builder().upstream_url(url.as_str());
+        if let Some(api_key) = &self.api_key {
+            builder = builder.header("Authorization", format!("Token {api_key}"));
+        }
+        let proxy = match builder.build() {
+            Ok(proxy) => proxy,
+            Err(e) => return (StatusCode::BAD_GATEWAY, e.to_string()).into_response(),
+        };
+
+        ws.on_upgrade(move |socket| async move {
+            let _guard = guard;
+            if let Err(e) = proxy.handle(socket).await {
+                tracing::error!(error.message = %e, "upstream_proxy_error");
+            }
+        })
+        .into_response()
+    }
+
+    pub(crate) async fn batch(&self, request: Request) -> Response {
+        let url = self.listen_url(request.uri().query(), false);
+        let (parts, body) = request.into_parts();
+        let body = match axum::body::to_bytes(body, MAX_BATCH_BYTES).await {
+            Ok(body) => body,
+            Err(e) => return (StatusCode::PAYLOAD_TOO_LARGE, e.to_string()).into_response(),
+        };
+
+        let mut upstream = self.http_client.post(url).body(body);
+        for name in [header::CONTENT_TYPE, header::ACCEPT] {
+            if let Some(value) = parts.headers.get(&name) {
+                upstream = upstream.header(name, value);
+            }
+        }
+        if let Some(api_key) = &self.api_key {
+            upstream = upstream.header(header::AUTHORIZATION, format!("Token {api_key}"));
+        }
+
+        let upstream = match upstream.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                tracing::error!(error.message = %e, "upstream_request_failed");
+                return (StatusCode::BAD_GATEWAY, e.to_string()).into_response();
+            }
+        };
+
+        let mut response = Response::builder().status(upstream.status());
+        if let Some(content_type) = upstream.headers().get(header::CONTENT_TYPE) {
+            response = response.header(header::CONTENT_TYPE, content_type);
+        }
+        response
+            .body(Body::from_stream(upstream.bytes_stream()))
+            .unwrap_or_else(|e| (StatusCode::BAD_GATEWAY, e.to_string()).into_response())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn upstream(base_url: Option<&str>, model: Option<&str>) -> Upstream {
+        Upstream::new(&DeepgramModelConfig {
+            id: "remote".into(),
+            base_url: base_url.map(Into::into),
+            model: model.map(Into::into),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn listen_url_replaces_model() {
+        let upstream = upstream(None, Some("nova-3"));
+        assert_eq!(
+            upstream
+                .listen_url(Some("model=remote&language=en&channels=2"), true)
+                .as_str(),
+            "wss://api.deepgram.com/v1/listen?language=en&channels=2&model=nova-3"
+        );
+    }
+
+    #[test]
+    fn listen_url_drops_model_without_override() {
+        let upstream = upstream(Some("http://10.0.0.5:8080/v1/"), None);
+        assert_eq!(
+            upstream.listen_url(Some("model=remote"), false).as_str(),
+            "http://10.0.0.5:8080/v1/listen"
+        );
+        assert_eq!(
+            upstream.listen_url(None, true).as_str(),
+            "ws://10.0.0.5:8080/v1/listen"
+        );
+    }
+
+    #[test]
+    fn rejects_non_http_base_url() {
+        assert!(
+            Upstream::new(&DeepgramModelConfig {
+                base_url: Some("ftp://example.com".into()),
+                ..Default::default()
+            })
+            .is_err()
+        );
+    }
+}
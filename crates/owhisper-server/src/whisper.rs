@@ -0,0 +1,136 @@
+use std::io::Write;
+use std::path::PathBuf;
+
+use axum::{
+    Json,
+    extract::Request,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use owhisper_interface::{Word2, batch};
+
+use crate::MAX_BATCH_BYTES;
+
+/// Transcribes an uploaded recording in one pass. whisper.cpp decodes from a file, so the
+/// body is spooled to a temporary one first.
+pub(crate) async fn batch(model_path: PathBuf, request: Request) -> Response {
+    let body = match axum::body::to_bytes(request.into_body(), MAX_BATCH_BYTES).await {
+        Ok(body) => body,
+        Err(e) => return (StatusCode::PAYLOAD_TOO_LARGE, e.to_string()).into_response(),
+    };
+
+    let result = tokio::task::spawn_blocking(move || {
+        let mut audio = tempfile::NamedTempFile::new().map_err(|e| e.to_string())?;
+        audio.write_all(&body).map_err(|e| e.to_string())?;
+        hypr_transcribe_whisper_local::process_recorded(&model_path, audio.path())
+            .map_err(|e| e.to_string())
+    })
+    .await;
+
+    match result {
+        Ok(Ok(segments)) => Json(batch_response(segments)).into_response(),
+        Ok(Err(e)) => {
+            tracing::error!(error.message = %e, "whisper_batch_failed");
+            (StatusCode::INTERNAL_SERVER_ERROR, e).into_response()
+        }
+        // Undecodable audio or a model that fails to load panics inside the task.
+        Err(e) => {
+            tracing::error!(error.message = %e, "whisper_batch_panicked");
+            (StatusCode::INTERNAL_SERVER_ERROR, "transcription_failed").into_response()
+        }
+    }
+}
+
+/// whisper.cpp times whole segments, so their words share the segment's span evenly.
+fn batch_response(segments: Vec<Word2>) -> batch::Response {
+    let mut words = Vec::new();
+    let mut confidence_sum = 0.0;
+    for segment in &segments {
+        let start = segment.start_ms.unwrap_or_default() as f64 / 1000.0;
+        let end = segment.end_ms.unwrap_or_default() as f64 / 1000.0;
+        let confidence = segment.confidence.unwrap_or_default() as f64;
+        confidence_sum += confidence;
+
+        let texts = segment.text.split_whitespace().collect::<Vec<_>>();
+        let step = (end - start).max(0.0) / texts.len().max(1) as f64;
+        words.extend(texts.iter().enumerate().map(|(i, text)| batch::Word {
+            word: text.to_string(),
+            start: start + i as f64 * step,
+            end: start + (i + 1) as f64 * step,
+            confidence,
+            speaker: None,
+            punctuated_word: Some(text.to_string()),
+        }));
+    }
+
+    let transcript = words
+        .iter()
+        .map(|word| word.word.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let confidence = if segments.is_empty() {
+        0.0
+    } else {
+        confidence_sum / segments.len() as f64
+    };
+    let duration = words.last().map(|word| word.end).unwrap_or_default();
+
+    batch::Response {
+        metadata: serde_json::json!({
+            "request_id": uuid::Uuid::new_v4().to_string(),
+            "duration": duration,
+            "channels": 1,
+        }),
+        results: batch::Results {
+            channels: vec![batch::Channel {
+                alternatives: vec![batch::Alternatives {
+                    transcript,
+                    confidence,
+                    words,
+                }],
+            }],
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(text: &str, start_ms: u64, end_ms: u64) -> Word2 {
+        Word2 {
+            text: text.to_string(),
+            speaker: None,
+            confidence: Some(0.5),
+            start_ms: Some(start_ms),
+            end_ms: Some(end_ms),
+        }
+    }
+
+    #[test]
+    fn splits_segments_into_timed_words() {
+        let response = batch_response(vec![
+            segment(" Hello there.", 0, 1_000),
+            segment(" Bye.", 2_000, 2_500),
+        ]);
+
+        let alternative = &response.results.channels[0].alternatives[0];
+        assert_eq!(alternative.transcript, "Hello there. Bye.");
+        let spans = alternative
+            .words
+            .iter()
+            .map(|word| (word.start, word.end))
+            .collect::<Vec<_>>();
+        assert_eq!(spans, vec![(0.0, 0.5), (0.5, 1.0), (2.0, 2.5)]);
+        assert_eq!(response.metadata["duration"], 2.5);
+    }
+
+    #[test]
+    fn silence_has_an_empty_transcript() {
+        let response = batch_response(Vec::new());
+
+        let alternative = &response.results.channels[0].alternatives[0];
+        assert!(alternative.transcript.is_empty());
+        assert!(alternative.words.is_empty());
+    }
+}
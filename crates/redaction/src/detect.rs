@@ -0,0 +1,164 @@
+use std::ops::Range;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::PiiKind;
+
+static EMAIL_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\.[a-zA-Z]{2,}").expect("Invalid regex")
+});
+
+static IBAN_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b[A-Z]{2}\d{2}(?: ?[A-Z0-9]){11,30}\b").expect("Invalid regex"));
+
+static CARD_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b\d(?:[ -]?\d){12,18}\b").expect("Invalid regex"));
+
+// US SSN and UK National Insurance numbers. Other schemes go in custom patterns.
+static GOVERNMENT_ID_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\b(?:\d{3}-\d{2}-\d{4}|[A-CEGHJ-PR-TW-Z]{2} ?\d{2} ?\d{2} ?\d{2} ?[A-D])\b")
+        .expect("Invalid regex")
+});
+
+static PHONE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?:\+\d{1,3}[ .-]?)?(?:\(\d{1,4}\)[ .-]?)?\d{2,4}(?:[ .-]?\d{2,4}){1,4}\b")
+        .expect("Invalid regex")
+});
+
+static ISO_DATE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\d{4}-\d{2}-\d{2}$").expect("Invalid regex"));
+
+/// Spans of `kind` in `text`, after checksum or shape validation.
+pub(crate) fn find(kind: PiiKind, text: &str) -> Vec<Range<usize>> {
+    let (regex, valid): (&Regex, fn(&str) -> bool) = match kind {
+        PiiKind::Email => (&EMAIL_REGEX, |_| true),
+        PiiKind::Iban => (&IBAN_REGEX, is_valid_iban),
+        PiiKind::Card => (&CARD_REGEX, is_valid_card),
+        PiiKind::GovernmentId => (&GOVERNMENT_ID_REGEX, is_valid_government_id),
+        PiiKind::Phone => (&PHONE_REGEX, is_valid_phone),
+        PiiKind::Custom | PiiKind::Term => return Vec::new(),
+    };
+
+    regex
+        .find_iter(text)
+        .filter(|m| valid(m.as_str()))
+        .map(|m| m.range())
+        .collect()
+}
+
+fn digits(s: &str) -> Vec<u32> {
+    s.chars().filter_map(|c| c.to_digit(10)).collect()
+}
+
+fn is_valid_card(s: &str) -> bool {
+    let digits = digits(s);
+    if !(13..=19).contains(&digits.len()) {
+        return false;
+    }
+
+    let sum: u32 = digits
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, &d)| match (i % 2 == 1, d * 2) {
+            (true, doubled) if doubled > 9 => doubled - 9,
+            (true, doubled) => doubled,
+            (false, _) => d,
+        })
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+fn is_valid_iban(s: &str) -> bool {
+    let compact: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+    if !(15..=34).contains(&compact.len()) {
+        return false;
+    }
+
+    let (head, tail) = compact.split_at(4);
+    let mut remainder = 0u32;
+    for c in tail.chars().chain(head.chars()) {
+        let Some(value) = c.to_digit(36) else {
+            return false;
+        };
+        remainder = if value >= 10 {
+            (remainder * 100 + value) % 97
+        } else {
+            (remainder * 10 + value) % 97
+        };
+    }
+    remainder == 1
+}
+
+fn is_valid_government_id(s: &str) -> bool {
+    match s.split_once('-') {
+        Some((area, _)) => area != "000" && area != "666" && !area.starts_with('9'),
+        None => is_valid_ni_prefix(&s[..2]),
+    }
+}
+
+/// HMRC never uses O as the second prefix letter, and never allocates these prefixes.
+fn is_valid_ni_prefix(prefix: &str) -> bool {
+    !prefix.ends_with('O') && !matches!(prefix, "BG" | "GB" | "KN" | "NK" | "NT" | "TN" | "ZZ")
+}
+
+fn is_valid_phone(s: &str) -> bool {
+    if ISO_DATE_REGEX.is_match(s) {
+        return false;
+    }
+
+    let count = digits(s).len();
+    (9..=15).contains(&count) || (s.starts_with('+') && count >= 7)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches(kind: PiiKind, text: &str) -> Vec<&str> {
+        find(kind, text).into_iter().map(|r| &text[r]).collect()
+    }
+
+    #[test]
+    fn test_card_requires_luhn() {
+        assert_eq!(
+            matches(PiiKind::Card, "paid with 4111 1111 1111 1111 today"),
+            ["4111 1111 1111 1111"]
+        );
+        assert!(matches(PiiKind::Card, "order 4111 1111 1111 1112").is_empty());
+    }
+
+    #[test]
+    fn test_iban_requires_checksum() {
+        assert_eq!(
+            matches(
+                PiiKind::Iban,
+                "send it to GB82 WEST 1234 5698 7654 32 please"
+            ),
+            ["GB82 WEST 1234 5698 7654 32"]
+        );
+        assert!(matches(PiiKind::Iban, "GB83 WEST 1234 5698 7654 32").is_empty());
+    }
+
+    #[test]
+    fn test_government_ids() {
+        assert_eq!(
+            matches(PiiKind::GovernmentId, "SSN 123-45-6789, NI AB 12 34 56 C"),
+            ["123-45-6789", "AB 12 34 56 C"]
+        );
+        assert!(matches(PiiKind::GovernmentId, "666-12-3456").is_empty());
+        assert!(matches(PiiKind::GovernmentId, "GB 12 34 56 A, AO 12 34 56 B").is_empty());
+    }
+
+    #[test]
+    fn test_phone_skips_dates_and_short_numbers() {
+        assert_eq!(
+            matches(
+                PiiKind::Phone,
+                "call +1 415 555 0132 or (020) 7946 0958 before 2024-06-01, room 1204"
+            ),
+            ["+1 415 555 0132", "(020) 7946 0958"]
+        );
+    }
+}
@@ -0,0 +1,10 @@
+mod detect;
+mod error;
+mod placeholder;
+mod redactor;
+mod types;
+
+pub use error::{Error, Result};
+pub use placeholder::{Placeholder, PlaceholderMap};
+pub use redactor::Redactor;
+pub use types::*;
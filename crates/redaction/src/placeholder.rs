@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::PiiKind;
+
+static PLACEHOLDER_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\[[A-Z][A-Z0-9_]*_\d+\]").expect("Invalid regex"));
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct Placeholder {
+    pub placeholder: String,
+    pub original: String,
+    pub kind: PiiKind,
+}
+
+/// Placeholders handed out while redacting, kept locally to restore model output.
+///
+/// The same value always maps to the same placeholder, so a client named in both the
+/// transcript and the notes stays one entity for the model.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(from = "Vec<Placeholder>", into = "Vec<Placeholder>")]
+pub struct PlaceholderMap {
+    entries: Vec<Placeholder>,
+    by_value: HashMap<(String, String), usize>,
+    counters: HashMap<String, usize>,
+}
+
+impl PlaceholderMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn entries(&self) -> &[Placeholder] {
+        &self.entries
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub(crate) fn placeholder_for(&mut self, kind: PiiKind, label: &str, value: &str) -> &str {
+        let key = (label.to_string(), normalize(kind, value));
+        let index = match self.by_value.get(&key) {
+            Some(&index) => index,
+            None => {
+                let counter = self.counters.entry(label.to_string()).or_default();
+                *counter += 1;
+                self.entries.push(Placeholder {
+                    placeholder: format!("[{label}_{counter}]"),
+                    original: value.to_string(),
+                    kind,
+                });
+                self.by_value.insert(key, self.entries.len() - 1);
+                self.entries.len() - 1
+            }
+        };
+        &self.entries[index].placeholder
+    }
+
+    /// Puts the original values back. Placeholders the map does not know are left as is.
+    pub fn restore(&self, text: &str) -> String {
+        PLACEHOLDER_REGEX
+            .replace_all(text, |caps: &regex::Captures| {
+                let found = &caps[0];
+                self.entries
+                    .iter()
+                    .find(|e| e.placeholder == found)
+                    .map_or_else(|| found.to_string(), |e| e.original.clone())
+            })
+            .into_owned()
+    }
+}
+
+fn normalize(kind: PiiKind, value: &str) -> String {
+    match kind {
+        PiiKind::Email | PiiKind::Term => value.to_lowercase(),
+        PiiKind::Phone | PiiKind::Card | PiiKind::Iban | PiiKind::GovernmentId => value
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '+')
+            .collect::<String>()
+            .to_uppercase(),
+        PiiKind::Custom => value.to_string(),
+    }
+}
+
+impl From<Vec<Placeholder>> for PlaceholderMap {
+    fn from(entries: Vec<Placeholder>) -> Self {
+        let mut map = PlaceholderMap::default();
+        for entry in entries {
+            let Some((label, counter)) = entry
+                .placeholder
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .rsplit_once('_')
+            else {
+                continue;
+            };
+
+            let counter = counter.parse::<usize>().unwrap_or(0);
+            let current = map.counters.entry(label.to_string()).or_default();
+            *current = (*current).max(counter);
+            map.by_value.insert(
+                (label.to_string(), normalize(entry.kind, &entry.original)),
+                map.entries.len(),
+            );
+            map.entries.push(entry);
+        }
+        map
+    }
+}
+
+impl From<PlaceholderMap> for Vec<Placeholder> {
+    fn from(map: PlaceholderMap) -> Self {
+        map.entries
+    }
+}
@@ -0,0 +1,264 @@
+use std::ops::Range;
+
+use regex::{Regex, RegexBuilder};
+
+use crate::{Error, PiiKind, PlaceholderMap, RedactionConfig, detect};
+
+enum Detector {
+    BuiltIn(PiiKind),
+    Pattern { label: String, regex: Regex },
+    Terms(Regex),
+}
+
+struct Span {
+    range: Range<usize>,
+    kind: PiiKind,
+    label: String,
+}
+
+/// Replaces sensitive values with placeholders such as `[EMAIL_1]`.
+///
+/// Detectors run against the original text. Where matches overlap the longest one wins, so
+/// a term inside an email address stays part of the email; ties go to dictionary terms, then
+/// custom patterns, then the built-in kinds in the order the config lists them.
+pub struct Redactor {
+    detectors: Vec<Detector>,
+}
+
+impl Redactor {
+    pub fn new(config: &RedactionConfig) -> Result<Self, Error> {
+        let mut detectors = Vec::new();
+
+        let mut terms: Vec<&str> = config
+            .terms
+            .iter()
+            .map(|t| t.trim())
+            .filter(|t| !t.is_empty())
+            .collect();
+        if !terms.is_empty() {
+            // Longest first so "Acme Holdings" wins over "Acme".
+            terms.sort_by_key(|t| std::cmp::Reverse(t.len()));
+            let alternation = terms
+                .iter()
+                .map(|t| bounded_term(t))
+                .collect::<Vec<_>>()
+                .join("|");
+            let regex = RegexBuilder::new(&alternation)
+                .case_insensitive(true)
+                .build()
+                .map_err(|source| Error::InvalidPattern {
+                    name: "terms".to_string(),
+                    source,
+                })?;
+            detectors.push(Detector::Terms(regex));
+        }
+
+        for pattern in &config.patterns {
+            let regex = Regex::new(&pattern.pattern).map_err(|source| Error::InvalidPattern {
+                name: pattern.name.clone(),
+                source,
+            })?;
+            detectors.push(Detector::Pattern {
+                label: pattern_label(&pattern.name),
+                regex,
+            });
+        }
+
+        for kind in &config.kinds {
+            if PiiKind::BUILT_IN.contains(kind) {
+                detectors.push(Detector::BuiltIn(*kind));
+            }
+        }
+
+        Ok(Self { detectors })
+    }
+
+    pub fn redact(&self, text: &str, placeholders: &mut PlaceholderMap) -> String {
+        let mut candidates: Vec<Span> = Vec::new();
+
+        for detector in &self.detectors {
+            let (kind, label, ranges) = match detector {
+                Detector::BuiltIn(kind) => {
+                    (*kind, kind.label().to_string(), detect::find(*kind, text))
+                }
+                Detector::Pattern { label, regex } => (
+                    PiiKind::Custom,
+                    label.clone(),
+                    regex
+                        .find_iter(text)
+                        .map(|m| m.range())
+                        .filter(|r| !r.is_empty())
+                        .collect(),
+                ),
+                Detector::Terms(regex) => (
+                    PiiKind::Term,
+                    PiiKind::Term.label().to_string(),
+                    regex.find_iter(text).map(|m| m.range()).collect(),
+                ),
+            };
+
+            candidates.extend(ranges.into_iter().map(|range| Span {
+                range,
+                kind,
+                label: label.clone(),
+            }));
+        }
+
+        // Stable sort keeps detector order among spans of equal length.
+        candidates.sort_by_key(|s| std::cmp::Reverse(s.range.len()));
+        let mut spans: Vec<Span> = Vec::new();
+        for span in candidates {
+            if spans
+                .iter()
+                .all(|s| span.range.end <= s.range.start || span.range.start >= s.range.end)
+            {
+                spans.push(span);
+            }
+        }
+        spans.sort_by_key(|s| s.range.start);
+
+        let mut out = String::with_capacity(text.len());
+        let mut last = 0;
+        for span in spans {
+            out.push_str(&text[last..span.range.start]);
+            out.push_str(placeholders.placeholder_for(
+                span.kind,
+                &span.label,
+                &text[span.range.clone()],
+            ));
+            last = span.range.end;
+        }
+        out.push_str(&text[last..]);
+        out
+    }
+}
+
+/// Matches `term` only where its neighbours are not word characters. `regex` has no lookaround,
+/// so an edge that is itself a non-word character, as in "C++" or "@acme", asserts `\B`
+/// instead of `\b`.
+fn bounded_term(term: &str) -> String {
+    let boundary = |c: Option<char>| match c {
+        Some(c) if c.is_alphanumeric() || c == '_' => r"\b",
+        _ => r"\B",
+    };
+    format!(
+        "(?:{}{}{})",
+        boundary(term.chars().next()),
+        regex::escape(term),
+        boundary(term.chars().next_back())
+    )
+}
+
+fn pattern_label(name: &str) -> String {
+    let label = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(str::to_ascii_uppercase)
+        .collect::<Vec<_>>()
+        .join("_");
+
+    if label.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        label
+    } else {
+        PiiKind::Custom.label().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CustomPattern;
+
+    fn config(terms: &[&str], patterns: &[(&str, &str)]) -> RedactionConfig {
+        RedactionConfig {
+            terms: terms.iter().map(|t| t.to_string()).collect(),
+            patterns: patterns
+                .iter()
+                .map(|(name, pattern)| CustomPattern {
+                    name: name.to_string(),
+                    pattern: pattern.to_string(),
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_redact_and_restore() {
+        let redactor = Redactor::new(&config(&["Acme Holdings", "Acme"], &[])).unwrap();
+        let mut placeholders = PlaceholderMap::new();
+
+        let transcript = redactor.redact(
+            "Acme Holdings asked us to email jane@acme.com or call +44 20 7946 0958.",
+            &mut placeholders,
+        );
+        assert_eq!(
+            transcript,
+            "[TERM_1] asked us to email [EMAIL_1] or call [PHONE_1]."
+        );
+
+        let notes = redactor.redact(
+            "Follow up with ACME HOLDINGS and Jane@Acme.com",
+            &mut placeholders,
+        );
+        assert_eq!(notes, "Follow up with [TERM_1] and [EMAIL_1]");
+
+        let response = "Send [TERM_1] the deck via [EMAIL_1]; [PHONE_2] is unknown.";
+        assert_eq!(
+            placeholders.restore(response),
+            "Send Acme Holdings the deck via jane@acme.com; [PHONE_2] is unknown."
+        );
+    }
+
+    #[test]
+    fn test_terms_with_non_word_edges() {
+        let redactor = Redactor::new(&config(&["C++", "@acme"], &[])).unwrap();
+        let mut placeholders = PlaceholderMap::new();
+
+        assert_eq!(
+            redactor.redact(
+                "Ask @acme about C++, not C++x or user@acme.",
+                &mut placeholders
+            ),
+            "Ask [TERM_1] about [TERM_2], not C++x or user@acme."
+        );
+    }
+
+    #[test]
+    fn test_custom_patterns_and_kinds() {
+        let mut config = config(&[], &[("matter number", r"\bM-\d{5}\b")]);
+        config.kinds = vec![PiiKind::Card];
+        let redactor = Redactor::new(&config).unwrap();
+        let mut placeholders = PlaceholderMap::new();
+
+        assert_eq!(
+            redactor.redact(
+                "Bill M-20931 to 4111-1111-1111-1111, cc bob@example.com",
+                &mut placeholders
+            ),
+            "Bill [MATTER_NUMBER_1] to [CARD_1], cc bob@example.com"
+        );
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let result = Redactor::new(&config(&[], &[("broken", "(")]));
+        assert!(matches!(result, Err(Error::InvalidPattern { name, .. }) if name == "broken"));
+    }
+
+    #[test]
+    fn test_placeholder_map_roundtrip() {
+        let redactor = Redactor::new(&RedactionConfig::default()).unwrap();
+        let mut placeholders = PlaceholderMap::new();
+        redactor.redact("a@b.io c@d.io", &mut placeholders);
+
+        let json = serde_json::to_string(&placeholders).unwrap();
+        let mut restored: PlaceholderMap = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.entries(), placeholders.entries());
+
+        assert_eq!(
+            redactor.redact("c@d.io e@f.io", &mut restored),
+            "[EMAIL_2] [EMAIL_3]"
+        );
+    }
+}
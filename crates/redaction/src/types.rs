@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(rename_all = "snake_case")]
+pub enum PiiKind {
+    Email,
+    Phone,
+    Card,
+    Iban,
+    GovernmentId,
+    Custom,
+    Term,
+}
+
+impl PiiKind {
+    pub const BUILT_IN: [PiiKind; 5] = [
+        PiiKind::Email,
+        PiiKind::Phone,
+        PiiKind::Card,
+        PiiKind::Iban,
+        PiiKind::GovernmentId,
+    ];
+
+    /// Prefix of the placeholders for this kind, e.g. `EMAIL` in `[EMAIL_1]`.
+    pub fn label(&self) -> &'static str {
+        match self {
+            PiiKind::Email => "EMAIL",
+            PiiKind::Phone => "PHONE",
+            PiiKind::Card => "CARD",
+            PiiKind::Iban => "IBAN",
+            PiiKind::GovernmentId => "ID",
+            PiiKind::Custom => "CUSTOM",
+            PiiKind::Term => "TERM",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct CustomPattern {
+    /// Becomes the placeholder prefix, e.g. `matter number` yields `[MATTER_NUMBER_1]`.
+    pub name: String,
+    pub pattern: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct RedactionConfig {
+    #[serde(default = "default_kinds")]
+    pub kinds: Vec<PiiKind>,
+    #[serde(default)]
+    pub patterns: Vec<CustomPattern>,
+    /// Matched case-insensitively on word boundaries, e.g. client or project names.
+    #[serde(default)]
+    pub terms: Vec<String>,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            kinds: default_kinds(),
+            patterns: Vec::new(),
+            terms: Vec::new(),
+        }
+    }
+}
+
+fn default_kinds() -> Vec<PiiKind> {
+    PiiKind::BUILT_IN.to_vec()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+#[serde(rename_all = "snake_case")]
+pub enum RedactionScope {
+    Enhance,
+    Chat,
+    Export,
+}
+
+/// Workspace settings, stored under `redaction` in the vault's settings file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "specta", derive(specta::Type))]
+pub struct RedactionSettings {
+    #[serde(default)]
+    pub enhance: bool,
+    #[serde(default)]
+    pub chat: bool,
+    #[serde(default)]
+    pub export: bool,
+    #[serde(flatten)]
+    pub config: RedactionConfig,
+}
+
+impl RedactionSettings {
+    pub fn applies_to(&self, scope: RedactionScope) -> bool {
+        match scope {
+            RedactionScope::Enhance => self.enhance,
+            RedactionScope::Chat => self.chat,
+            RedactionScope::Export => self.export,
+        }
+    }
+}
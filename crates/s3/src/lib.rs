@@ -6,6 +6,7 @@ use aws_sdk_s3::operation::create_bucket::CreateBucketError;
 use aws_sdk_s3::operation::create_multipart_upload::CreateMultipartUploadError;
 use aws_sdk_s3::operation::delete_object::DeleteObjectError;
 use aws_sdk_s3::operation::get_object::GetObjectError;
+use aws_sdk_s3::operation::list_objects_v2::ListObjectsV2Error;
 use aws_sdk_s3::operation::put_object::PutObjectError;
 use aws_sdk_s3::operation::upload_part::UploadPartError;
 use aws_sdk_s3::presigning::PresigningConfig;
@@ -17,6 +18,8 @@ pub struct ClientBuilder {
     bucket: Option<String>,
     access_key_id: Option<String>,
     secret_access_key: Option<String>,
+    region: Option<String>,
+    force_path_style: bool,
 }
 
 #[derive(Clone)]
@@ -55,6 +58,8 @@ pub enum ApiError {
     UploadPartError(#[from] SdkError<UploadPartError>),
     #[error("Error while completing multipart upload: {0}")]
     CompleteMultipartUploadError(#[from] SdkError<CompleteMultipartUploadError>),
+    #[error("Error while listing objects: {0}")]
+    ListObjectsError(#[from] SdkError<ListObjectsV2Error>),
 }
 
 impl ClientBuilder {
@@ -78,6 +83,17 @@ impl ClientBuilder {
         self
     }
 
+    pub fn region(mut self, region: impl Into<String>) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+
+    // MinIO and most self-hosted endpoints only serve `endpoint/bucket/key` URLs.
+    pub fn force_path_style(mut self, force_path_style: bool) -> Self {
+        self.force_path_style = force_path_style;
+        self
+    }
+
     pub async fn build(self) -> Client {
         let creds = aws_credential_types::Credentials::from_keys(
             self.access_key_id.unwrap(),
@@ -88,12 +104,17 @@ impl ClientBuilder {
         let cfg = aws_config::from_env()
             .endpoint_url(self.endpoint_url.unwrap())
             // https://www.tigrisdata.com/docs/concepts/regions/
-            .region(aws_config::Region::new("auto"))
+            .region(aws_config::Region::new(
+                self.region.unwrap_or_else(|| "auto".to_string()),
+            ))
             .credentials_provider(creds)
             .load()
             .await;
 
-        let s3 = aws_sdk_s3::Client::new(&cfg);
+        let s3_config = aws_sdk_s3::config::Builder::from(&cfg)
+            .force_path_style(self.force_path_style)
+            .build();
+        let s3 = aws_sdk_s3::Client::from_conf(s3_config);
 
         Client {
             s3,
@@ -283,6 +304,55 @@ impl<'a> UserClient<'a> {
         Ok(())
     }
 
+    pub async fn put_bytes(&self, file_name: &str, content: Vec<u8>) -> Result<(), ApiError> {
+        let _ = self
+            .s3
+            .put_object()
+            .bucket(&self.bucket)
+            .key(format!("{}/{}", self.folder(), file_name))
+            .body(content.into())
+            .content_type("application/octet-stream")
+            .send()
+            .await?;
+
+        Ok(())
+    }
+
+    // File names under `prefix`, relative to the user's folder like every other method here.
+    pub async fn list(&self, prefix: &str) -> Result<Vec<String>, ApiError> {
+        let folder = format!("{}/", self.folder());
+        let mut names = Vec::new();
+        let mut continuation_token = None;
+
+        loop {
+            let res = self
+                .s3
+                .list_objects_v2()
+                .bucket(&self.bucket)
+                .prefix(format!("{}{}", folder, prefix))
+                .set_continuation_token(continuation_token)
+                .send()
+                .await?;
+
+            names.extend(
+                res.contents()
+                    .iter()
+                    .filter_map(|object| object.key())
+                    .filter_map(|key| key.strip_prefix(&folder))
+                    .map(str::to_string),
+            );
+
+            match res.next_continuation_token() {
+                Some(token) if res.is_truncated() == Some(true) => {
+                    continuation_token = Some(token.to_string());
+                }
+                _ => break,
+            }
+        }
+
+        Ok(names)
+    }
+
     pub async fn delete(&self, file_name: &str) -> Result<(), ApiError> {
         let _ = self
             .s3
@@ -311,6 +381,7 @@ mod tests {
             .endpoint_url(format!("http://127.0.0.1:{}", port))
             .bucket("test")
             .credentials("minioadmin", "minioadmin")
+            .force_path_style(true)
             .build()
             .await;
 
@@ -28,8 +28,9 @@ impl Cache {
     }
 }
 
+/// A JWKS endpoint, re-fetched at most every ten minutes.
 #[derive(Clone)]
-pub(super) struct CachedJwks {
+pub struct CachedJwks {
     url: String,
     cache: Arc<RwLock<Cache>>,
     http_client: reqwest::Client,
@@ -1,7 +1,7 @@
 use jsonwebtoken::{Algorithm, DecodingKey, Validation};
 
 mod jwks;
-use jwks::*;
+pub use jwks::CachedJwks;
 
 #[derive(Clone)]
 pub struct SupabaseAuth {
@@ -3,6 +3,7 @@ mod enhance;
 mod title;
 mod tool;
 mod transcript_patch;
+mod transcript_translate;
 mod types;
 mod validate;
 
@@ -11,6 +12,7 @@ pub use enhance::*;
 pub use title::*;
 pub use tool::*;
 pub use transcript_patch::*;
+pub use transcript_translate::*;
 pub use types::*;
 pub use validate::*;
 
@@ -34,6 +36,8 @@ common_derives! {
         ToolSearchSessions(ToolSearchSessions),
         TranscriptPatchSystem(TranscriptPatchSystem),
         TranscriptPatchUser(Box<TranscriptPatchUser>),
+        TranscriptTranslateSystem(TranscriptTranslateSystem),
+        TranscriptTranslateUser(Box<TranscriptTranslateUser>),
     }
 }
 
@@ -58,6 +62,8 @@ pub fn render(t: Template) -> Result<String, Error> {
         Template::ToolSearchSessions(t) => askama::Template::render(&t),
         Template::TranscriptPatchSystem(t) => askama::Template::render(&t),
         Template::TranscriptPatchUser(t) => askama::Template::render(&*t),
+        Template::TranscriptTranslateSystem(t) => askama::Template::render(&t),
+        Template::TranscriptTranslateUser(t) => askama::Template::render(&*t),
     }?;
 
     Ok(value)
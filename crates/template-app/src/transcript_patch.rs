@@ -6,6 +6,8 @@ common_derives! {
     #[template(path = "transcript-patch.system.md.jinja")]
     pub struct TranscriptPatchSystem {
         pub language: Option<String>,
+        #[serde(default)]
+        pub glossary: Vec<String>,
     }
 }
 
@@ -26,13 +28,26 @@ mod tests {
         test_language_as_specified,
         TranscriptPatchSystem {
             language: Some("ko".to_string()),
+            glossary: vec![],
         },
         |v| v.contains("Korean")
     );
 
+    tpl_assert!(
+        test_glossary_listed,
+        TranscriptPatchSystem {
+            language: None,
+            glossary: vec!["Yujong Lee".to_string(), "Hyprnote".to_string()],
+        },
+        |v| v.contains("# Glossary") && v.contains("- Yujong Lee\n- Hyprnote")
+    );
+
     tpl_snapshot!(
         test_transcript_patch_system,
-        TranscriptPatchSystem { language: None },
+        TranscriptPatchSystem {
+            language: None,
+            glossary: vec![],
+        },
         fixed_date = "2025-01-01",
         @r#"
     # General Instructions
@@ -0,0 +1,108 @@
+use crate::common_derives;
+use hypr_askama_utils::filters;
+
+common_derives! {
+    #[derive(askama::Template)]
+    #[template(path = "transcript-translate.system.md.jinja")]
+    pub struct TranscriptTranslateSystem {
+        pub source_language: Option<String>,
+        pub target_language: Option<String>,
+    }
+}
+
+common_derives! {
+    pub struct TranslationContext {
+        pub source: String,
+        pub translation: String,
+    }
+}
+
+common_derives! {
+    #[derive(askama::Template)]
+    #[template(path = "transcript-translate.user.md.jinja")]
+    pub struct TranscriptTranslateUser {
+        pub context: Vec<TranslationContext>,
+        pub segments_json: String,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hypr_askama_utils::{tpl_assert, tpl_snapshot};
+
+    tpl_assert!(
+        test_languages_as_specified,
+        TranscriptTranslateSystem {
+            source_language: Some("de".to_string()),
+            target_language: Some("ko".to_string()),
+        },
+        |v| v.contains("from German into Korean")
+    );
+
+    tpl_snapshot!(
+        test_transcript_translate_system,
+        TranscriptTranslateSystem {
+            source_language: None,
+            target_language: Some("en".to_string()),
+        },
+        fixed_date = "2025-01-01",
+        @r#"
+    # General Instructions
+
+    Current date: 2025-01-01
+
+    You translate live meeting transcript segments into English.
+
+    # Output Contract
+
+    - Output exactly one JSON object with this shape: {"translations":[{"id":"...","text":"..."}]}.
+    - Return one entry for every input segment, with the same `id`.
+    - Do not wrap the JSON in markdown code fences.
+    - Do not include any explanation.
+
+    # Translation Guidance
+
+    - Segments are consecutive pieces of one conversation and may end mid-sentence. Translate each segment on its own so it can be shown next to the original.
+    - Use the previous segments only to resolve pronouns, terminology and tone. Never translate them again.
+    - Keep names, product names and numbers as spoken.
+    - Translate faithfully. Do not summarize, explain or add content.
+    - If a segment is already in English, return it unchanged.
+    "#
+    );
+
+    tpl_snapshot!(
+        test_transcript_translate_user,
+        TranscriptTranslateUser {
+            context: vec![TranslationContext {
+                source: "Guten Morgen zusammen.".to_string(),
+                translation: "Good morning, everyone.".to_string(),
+            }],
+            segments_json: "{\"segments\":[{\"id\":\"w3\",\"text\":\"Fangen wir an.\"}]}"
+                .to_string(),
+        },
+        @r#"
+    Previous segments, already translated:
+
+    - Guten Morgen zusammen.
+      → Good morning, everyone.
+
+    Translate these segments:
+
+    {"segments":[{"id":"w3","text":"Fangen wir an."}]}
+    "#
+    );
+
+    tpl_snapshot!(
+        test_transcript_translate_user_without_context,
+        TranscriptTranslateUser {
+            context: vec![],
+            segments_json: "{\"segments\":[]}".to_string(),
+        },
+        @r#"
+    Translate these segments:
+
+    {"segments":[]}
+    "#
+    );
+}
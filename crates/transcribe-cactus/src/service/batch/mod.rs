@@ -19,6 +19,7 @@ use owhisper_interface::batch_sse::{BatchSseMessage, EVENT_NAME};
 use tokio::sync::mpsc;
 
 use transcribe::transcribe_batch;
+pub(crate) use transcribe::{Segment, Transcription, transcribe_audio};
 
 pub async fn handle_batch(
     body: Bytes,
@@ -13,21 +13,93 @@ use super::chunk::{TARGET_SAMPLE_RATE, chunk_mono_audio};
 use super::response::{build_batch_words, build_segment_stream_response};
 use hypr_audio_utils::content_type_to_extension;
 
+/// One VAD chunk of speech; words are spread evenly across it.
+pub(crate) struct Segment {
+    pub start: f64,
+    pub end: f64,
+    pub text: String,
+    pub confidence: f64,
+    pub words: Vec<batch::Word>,
+}
+
+pub(crate) struct Transcription {
+    pub duration: f64,
+    pub confidence: f64,
+    pub segments: Vec<Segment>,
+}
+
+pub(super) fn transcribe_batch(
+    audio_data: &[u8],
+    content_type: &str,
+    params: &ListenParams,
+    model_path: &Path,
+    event_tx: Option<mpsc::UnboundedSender<BatchSseMessage>>,
+) -> Result<batch::Response, crate::Error> {
+    let custom_vocabulary = if params.keywords.is_empty() {
+        None
+    } else {
+        Some(params.keywords.clone())
+    };
+
+    let options = hypr_cactus::TranscribeOptions {
+        language: hypr_cactus::constrain_to(&params.languages),
+        custom_vocabulary,
+        ..Default::default()
+    };
+
+    let transcription = transcribe_audio(audio_data, content_type, &options, model_path, event_tx)?;
+
+    let transcript = transcription
+        .segments
+        .iter()
+        .map(|segment| segment.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let words = transcription
+        .segments
+        .into_iter()
+        .flat_map(|segment| segment.words)
+        .collect();
+
+    let metadata = crate::service::build_metadata(model_path);
+    let mut metadata_json = serde_json::to_value(&metadata).unwrap_or_default();
+    if let Some(obj) = metadata_json.as_object_mut() {
+        obj.insert(
+            "duration".to_string(),
+            serde_json::json!(transcription.duration),
+        );
+        obj.insert("channels".to_string(), serde_json::json!(1));
+    }
+
+    Ok(batch::Response {
+        metadata: metadata_json,
+        results: batch::Results {
+            channels: vec![batch::Channel {
+                alternatives: vec![batch::Alternatives {
+                    transcript,
+                    confidence: transcription.confidence,
+                    words,
+                }],
+            }],
+        },
+    })
+}
+
 #[tracing::instrument(
-    skip(audio_data, event_tx),
+    skip(audio_data, options, event_tx),
     fields(
         hyprnote.audio.size_bytes = audio_data.len(),
         hyprnote.file.mime_type = content_type,
         hyprnote.model.path = %model_path.display()
     )
 )]
-pub(super) fn transcribe_batch(
+pub(crate) fn transcribe_audio(
     audio_data: &[u8],
     content_type: &str,
-    params: &ListenParams,
+    options: &hypr_cactus::TranscribeOptions,
     model_path: &Path,
     event_tx: Option<mpsc::UnboundedSender<BatchSseMessage>>,
-) -> Result<batch::Response, crate::Error> {
+) -> Result<Transcription, crate::Error> {
     let extension = content_type_to_extension(content_type);
     let mut temp_file = tempfile::Builder::new()
         .prefix("cactus_batch_")
@@ -63,28 +135,16 @@ pub(super) fn transcribe_batch(
         }
     };
 
-    let custom_vocabulary = if params.keywords.is_empty() {
-        None
-    } else {
-        Some(params.keywords.clone())
-    };
-
-    let options = hypr_cactus::TranscribeOptions {
-        language: hypr_cactus::constrain_to(&params.languages),
-        custom_vocabulary,
-        ..Default::default()
-    };
-
     let metadata = crate::service::build_metadata(model_path);
     let channel_index = [0, 1];
 
-    let (all_words, transcript, avg_confidence) = if chunks.is_empty() {
-        (vec![], String::new(), 0.0)
+    let (segments, confidence) = if chunks.is_empty() {
+        (vec![], 0.0)
     } else {
         transcribe_chunks(
             &chunks,
             &model,
-            &options,
+            options,
             total_duration,
             event_tx,
             &metadata,
@@ -92,23 +152,10 @@ pub(super) fn transcribe_batch(
         )?
     };
 
-    let mut metadata_json = serde_json::to_value(&metadata).unwrap_or_default();
-    if let Some(obj) = metadata_json.as_object_mut() {
-        obj.insert("duration".to_string(), serde_json::json!(total_duration));
-        obj.insert("channels".to_string(), serde_json::json!(1));
-    }
-
-    Ok(batch::Response {
-        metadata: metadata_json,
-        results: batch::Results {
-            channels: vec![batch::Channel {
-                alternatives: vec![batch::Alternatives {
-                    transcript,
-                    confidence: avg_confidence,
-                    words: all_words,
-                }],
-            }],
-        },
+    Ok(Transcription {
+        duration: total_duration,
+        confidence,
+        segments,
     })
 }
 
@@ -120,8 +167,8 @@ fn transcribe_chunks(
     event_tx: Option<mpsc::UnboundedSender<BatchSseMessage>>,
     metadata: &owhisper_interface::stream::Metadata,
     channel_index: &[i32],
-) -> Result<(Vec<batch::Word>, String, f64), crate::Error> {
-    let mut all_words = Vec::new();
+) -> Result<(Vec<Segment>, f64), crate::Error> {
+    let mut segments = Vec::new();
     let mut all_transcripts = Vec::new();
     let mut cumulative_confidence = 0.0;
 
@@ -194,7 +241,6 @@ fn transcribe_chunks(
                 w.start += chunk_start_sec;
                 w.end += chunk_start_sec;
             }
-            all_words.extend(words);
 
             if let Some(ref tx) = event_tx {
                 let segment_resp = build_segment_stream_response(
@@ -210,15 +256,21 @@ fn transcribe_chunks(
                 });
             }
 
+            segments.push(Segment {
+                start: chunk_start_sec,
+                end: chunk_start_sec + chunk_duration_sec,
+                text: chunk_text.clone(),
+                confidence: cactus_response.confidence as f64,
+                words,
+            });
             all_transcripts.push(chunk_text);
         }
         cumulative_confidence += cactus_response.confidence as f64;
     }
 
-    let transcript = all_transcripts.join(" ");
     let avg_confidence = cumulative_confidence / chunks.len() as f64;
 
-    Ok((all_words, transcript, avg_confidence))
+    Ok((segments, avg_confidence))
 }
 
 fn parse_timestamp_token(token: &str) -> Option<f64> {
@@ -1,5 +1,7 @@
 mod batch;
+mod openai;
 mod streaming;
+pub use openai::*;
 pub use streaming::*;
 
 use std::path::Path;
@@ -0,0 +1,284 @@
+use std::str::FromStr;
+
+use axum::{
+    Json,
+    http::header,
+    response::{IntoResponse, Response},
+};
+
+use crate::service::batch::{Segment, Transcription};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) enum ResponseFormat {
+    #[default]
+    Json,
+    Text,
+    Srt,
+    Vtt,
+    VerboseJson,
+}
+
+impl FromStr for ResponseFormat {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "text" => Ok(Self::Text),
+            "srt" => Ok(Self::Srt),
+            "vtt" => Ok(Self::Vtt),
+            "verbose_json" => Ok(Self::VerboseJson),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Task {
+    Transcribe,
+    Translate,
+}
+
+impl Task {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Transcribe => "transcribe",
+            Self::Translate => "translate",
+        }
+    }
+}
+
+/// What `verbose_json` reports besides the segments themselves.
+pub(super) struct VerboseOptions {
+    pub task: Task,
+    pub language: Option<String>,
+    pub temperature: f32,
+    pub segments: bool,
+    pub words: bool,
+}
+
+pub(super) fn render(
+    transcription: &Transcription,
+    format: ResponseFormat,
+    verbose: &VerboseOptions,
+) -> Response {
+    match format {
+        ResponseFormat::Json => {
+            Json(serde_json::json!({ "text": text(transcription) })).into_response()
+        }
+        ResponseFormat::Text => plain(text(transcription), "text/plain; charset=utf-8"),
+        ResponseFormat::Srt => plain(srt(&transcription.segments), "application/x-subrip"),
+        ResponseFormat::Vtt => plain(vtt(&transcription.segments), "text/vtt; charset=utf-8"),
+        ResponseFormat::VerboseJson => Json(verbose_json(transcription, verbose)).into_response(),
+    }
+}
+
+fn plain(body: String, content_type: &'static str) -> Response {
+    ([(header::CONTENT_TYPE, content_type)], body).into_response()
+}
+
+fn text(transcription: &Transcription) -> String {
+    transcription
+        .segments
+        .iter()
+        .map(|segment| segment.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn srt(segments: &[Segment]) -> String {
+    segments
+        .iter()
+        .enumerate()
+        .map(|(i, segment)| {
+            format!(
+                "{}\n{} --> {}\n{}\n\n",
+                i + 1,
+                timestamp(segment.start, ','),
+                timestamp(segment.end, ','),
+                segment.text
+            )
+        })
+        .collect()
+}
+
+fn vtt(segments: &[Segment]) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+    for segment in segments {
+        out.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            timestamp(segment.start, '.'),
+            timestamp(segment.end, '.'),
+            segment.text
+        ));
+    }
+    out
+}
+
+/// `HH:MM:SS,mmm` for SRT, `HH:MM:SS.mmm` for WebVTT.
+fn timestamp(seconds: f64, separator: char) -> String {
+    let ms = (seconds.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}{separator}{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        ms % 1000
+    )
+}
+
+fn verbose_json(transcription: &Transcription, options: &VerboseOptions) -> serde_json::Value {
+    let mut body = serde_json::json!({
+        "task": options.task.as_str(),
+        "duration": transcription.duration,
+        "text": text(transcription),
+    });
+
+    if let Some(language) = &options.language {
+        body["language"] = serde_json::json!(language);
+    }
+
+    if options.segments {
+        body["segments"] = transcription
+            .segments
+            .iter()
+            .enumerate()
+            .map(|(id, segment)| {
+                serde_json::json!({
+                    "id": id,
+                    // Whisper reports the window offset in 10ms frames.
+                    "seek": (segment.start * 100.0).round() as u64,
+                    "start": segment.start,
+                    "end": segment.end,
+                    "text": segment.text,
+                    "temperature": options.temperature,
+                    "avg_logprob": segment.confidence.max(f64::MIN_POSITIVE).ln(),
+                })
+            })
+            .collect();
+    }
+
+    if options.words {
+        body["words"] = transcription
+            .segments
+            .iter()
+            .flat_map(|segment| &segment.words)
+            .map(|word| {
+                serde_json::json!({
+                    "word": word.word,
+                    "start": word.start,
+                    "end": word.end,
+                })
+            })
+            .collect();
+    }
+
+    body
+}
+
+#[cfg(test)]
+mod tests {
+    use owhisper_interface::batch;
+
+    use super::*;
+
+    fn transcription() -> Transcription {
+        let word = |word: &str, start: f64, end: f64| batch::Word {
+            word: word.to_string(),
+            start,
+            end,
+            confidence: 0.9,
+            speaker: None,
+            punctuated_word: Some(word.to_string()),
+        };
+
+        Transcription {
+            duration: 3725.5,
+            confidence: 0.9,
+            segments: vec![
+                Segment {
+                    start: 0.0,
+                    end: 1.5,
+                    text: "Hello there.".to_string(),
+                    confidence: 0.9,
+                    words: vec![word("Hello", 0.0, 0.75), word("there.", 0.75, 1.5)],
+                },
+                Segment {
+                    start: 3723.25,
+                    end: 3725.0,
+                    text: "Bye.".to_string(),
+                    confidence: 0.9,
+                    words: vec![word("Bye.", 3723.25, 3725.0)],
+                },
+            ],
+        }
+    }
+
+    fn verbose(segments: bool, words: bool) -> VerboseOptions {
+        VerboseOptions {
+            task: Task::Transcribe,
+            language: Some("en".to_string()),
+            temperature: 0.0,
+            segments,
+            words,
+        }
+    }
+
+    #[test]
+    fn timestamps() {
+        assert_eq!(timestamp(0.0, ','), "00:00:00,000");
+        assert_eq!(timestamp(61.0015, '.'), "00:01:01.002");
+        assert_eq!(timestamp(3723.25, ','), "01:02:03,250");
+        assert_eq!(timestamp(-1.0, '.'), "00:00:00.000");
+    }
+
+    #[test]
+    fn srt_cues() {
+        insta::assert_snapshot!(srt(&transcription().segments), @r"
+        1
+        00:00:00,000 --> 00:00:01,500
+        Hello there.
+
+        2
+        01:02:03,250 --> 01:02:05,000
+        Bye.
+        ");
+    }
+
+    #[test]
+    fn vtt_cues() {
+        insta::assert_snapshot!(vtt(&transcription().segments), @r"
+        WEBVTT
+
+        00:00:00.000 --> 00:00:01.500
+        Hello there.
+
+        01:02:03.250 --> 01:02:05.000
+        Bye.
+        ");
+    }
+
+    #[test]
+    fn verbose_json_granularities() {
+        let transcription = transcription();
+
+        let body = verbose_json(&transcription, &verbose(true, false));
+        assert_eq!(body["text"], "Hello there. Bye.");
+        assert_eq!(body["language"], "en");
+        assert_eq!(body["segments"].as_array().unwrap().len(), 2);
+        assert_eq!(body["segments"][1]["start"], 3723.25);
+        assert!(body.get("words").is_none());
+
+        let body = verbose_json(&transcription, &verbose(false, true));
+        assert!(body.get("segments").is_none());
+        assert_eq!(body["words"].as_array().unwrap().len(), 3);
+        assert_eq!(body["words"][2]["word"], "Bye.");
+    }
+
+    #[test]
+    fn parses_response_formats() {
+        assert_eq!("srt".parse(), Ok(ResponseFormat::Srt));
+        assert_eq!("verbose_json".parse(), Ok(ResponseFormat::VerboseJson));
+        assert!("xml".parse::<ResponseFormat>().is_err());
+    }
+}
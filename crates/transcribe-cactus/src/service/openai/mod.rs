@@ -0,0 +1,454 @@
+mod format;
+
+use std::path::{Path, PathBuf};
+
+use axum::{
+    Json, Router,
+    extract::{
+        DefaultBodyLimit, Multipart, State,
+        multipart::{Field, MultipartRejection},
+    },
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::post,
+};
+use bytes::Bytes;
+use hypr_language::Language;
+use hypr_ws_utils::ConnectionManager;
+
+use super::batch::transcribe_audio;
+use format::{ResponseFormat, Task, VerboseOptions};
+
+const MAX_UPLOAD_BYTES: usize = 100 * 1024 * 1024;
+
+/// OpenAI-compatible `/v1/audio/transcriptions` and `/v1/audio/translations`, backed by the
+/// same batch pipeline as `/v1/listen`. The `model` field is accepted but ignored. Each request
+/// holds a connection from `connections` while the model is loaded.
+pub fn openai_router(model_path: PathBuf, connections: ConnectionManager) -> Router {
+    let state = OpenAiState {
+        translates: supports_translation(&model_path),
+        model_path,
+        connections,
+    };
+
+    Router::new()
+        .route("/v1/audio/transcriptions", post(transcriptions))
+        .route("/v1/audio/translations", post(translations))
+        .layer(DefaultBodyLimit::max(MAX_UPLOAD_BYTES))
+        .with_state(state)
+}
+
+#[derive(Clone)]
+struct OpenAiState {
+    model_path: PathBuf,
+    connections: ConnectionManager,
+    translates: bool,
+}
+
+async fn transcriptions(
+    State(state): State<OpenAiState>,
+    multipart: Result<Multipart, MultipartRejection>,
+) -> Response {
+    handle(&state, multipart, Task::Transcribe).await
+}
+
+async fn translations(
+    State(state): State<OpenAiState>,
+    multipart: Result<Multipart, MultipartRejection>,
+) -> Response {
+    if !state.translates {
+        return ApiError::invalid(
+            "the loaded model does not support translation; use a whisper model",
+            Some("model"),
+        )
+        .into_response();
+    }
+    handle(&state, multipart, Task::Translate).await
+}
+
+async fn handle(
+    state: &OpenAiState,
+    multipart: Result<Multipart, MultipartRejection>,
+    task: Task,
+) -> Response {
+    let request = match multipart {
+        Ok(multipart) => match AudioRequest::parse(multipart).await {
+            Ok(request) => request,
+            Err(e) => return e.into_response(),
+        },
+        Err(e) => return ApiError::invalid(e.body_text(), None).into_response(),
+    };
+
+    let options = hypr_cactus::TranscribeOptions {
+        // Translation always targets English; whisper detects the source language itself.
+        language: match task {
+            Task::Transcribe => request.language.clone(),
+            Task::Translate => None,
+        },
+        temperature: request.temperature,
+        initial_prompt: request.prompt,
+        translate: task == Task::Translate,
+        ..Default::default()
+    };
+
+    let verbose = VerboseOptions {
+        task,
+        language: match task {
+            Task::Transcribe => request.language.map(|l| l.iso639_code().to_string()),
+            Task::Translate => Some("en".to_string()),
+        },
+        temperature: request.temperature.unwrap_or_default(),
+        segments: request.segment_timestamps,
+        words: request.word_timestamps,
+    };
+
+    let Some(guard) = state.connections.acquire_connection() else {
+        return ApiError::busy().into_response();
+    };
+
+    let model_path = state.model_path.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        // Released once the model is dropped, even if the client has gone away.
+        let _guard = guard;
+        transcribe_audio(
+            &request.file,
+            &request.content_type,
+            &options,
+            &model_path,
+            None,
+        )
+    })
+    .await;
+
+    match result {
+        Ok(Ok(transcription)) => format::render(&transcription, request.response_format, &verbose),
+        Ok(Err(e)) => {
+            tracing::error!(error.message = %e, "openai_transcription_failed");
+            ApiError::server(e.to_string()).into_response()
+        }
+        Err(e) => {
+            tracing::error!(error.message = %e, "openai_task_panicked");
+            ApiError::server("internal error").into_response()
+        }
+    }
+}
+
+struct AudioRequest {
+    file: Bytes,
+    content_type: String,
+    language: Option<Language>,
+    prompt: Option<String>,
+    temperature: Option<f32>,
+    response_format: ResponseFormat,
+    segment_timestamps: bool,
+    word_timestamps: bool,
+}
+
+impl AudioRequest {
+    async fn parse(mut multipart: Multipart) -> Result<Self, ApiError> {
+        let mut file = None;
+        let mut language = None;
+        let mut prompt = None;
+        let mut temperature = None;
+        let mut response_format = ResponseFormat::default();
+        let mut granularities = Vec::new();
+
+        while let Some(field) = multipart
+            .next_field()
+            .await
+            .map_err(|e| ApiError::invalid(e.body_text(), None))?
+        {
+            let name = field.name().unwrap_or_default().to_string();
+            match name.as_str() {
+                "file" => {
+                    let content_type = audio_content_type(field.file_name(), field.content_type());
+                    let bytes = field
+                        .bytes()
+                        .await
+                        .map_err(|e| ApiError::invalid(e.body_text(), Some("file")))?;
+                    file = Some((bytes, content_type));
+                }
+                "language" => {
+                    let value = field_text(field).await?;
+                    if !value.is_empty() {
+                        language = Some(value.parse::<Language>().map_err(|_| {
+                            ApiError::invalid(
+                                format!("unsupported language '{value}'"),
+                                Some("language"),
+                            )
+                        })?);
+                    }
+                }
+                "prompt" => {
+                    prompt = Some(field_text(field).await?).filter(|p| !p.is_empty());
+                }
+                "temperature" => {
+                    let value = field_text(field).await?;
+                    temperature = Some(value.parse::<f32>().map_err(|_| {
+                        ApiError::invalid(
+                            format!("invalid temperature '{value}'"),
+                            Some("temperature"),
+                        )
+                    })?);
+                }
+                "response_format" => {
+                    let value = field_text(field).await?;
+                    response_format = value.parse().map_err(|_| {
+                        ApiError::invalid(
+                            format!(
+                                "unsupported response_format '{value}'; expected json, text, srt, verbose_json or vtt"
+                            ),
+                            Some("response_format"),
+                        )
+                    })?;
+                }
+                "timestamp_granularities[]" | "timestamp_granularities" => {
+                    granularities.push(field_text(field).await?);
+                }
+                _ => {}
+            }
+        }
+
+        let Some((file, content_type)) = file.filter(|(bytes, _)| !bytes.is_empty()) else {
+            return Err(ApiError::invalid("an audio file is required", Some("file")));
+        };
+
+        if let Some(other) = granularities
+            .iter()
+            .find(|g| !matches!(g.as_str(), "segment" | "word"))
+        {
+            return Err(ApiError::invalid(
+                format!("unsupported timestamp granularity '{other}'"),
+                Some("timestamp_granularities"),
+            ));
+        }
+
+        Ok(Self {
+            file,
+            content_type,
+            language,
+            prompt,
+            temperature,
+            response_format,
+            segment_timestamps: granularities.is_empty()
+                || granularities.iter().any(|g| g == "segment"),
+            word_timestamps: granularities.iter().any(|g| g == "word"),
+        })
+    }
+}
+
+async fn field_text(field: Field<'_>) -> Result<String, ApiError> {
+    let name = field.name().unwrap_or_default().to_string();
+    field
+        .text()
+        .await
+        .map(|value| value.trim().to_string())
+        .map_err(|e| ApiError::invalid(e.body_text(), Some(&name)))
+}
+
+/// Uploads often arrive as `application/octet-stream`, so the file extension wins when it names
+/// a known audio format.
+fn audio_content_type(file_name: Option<&str>, content_type: Option<&str>) -> String {
+    let extension = file_name
+        .and_then(|name| Path::new(name).extension())
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase);
+
+    let from_extension = match extension.as_deref() {
+        Some("wav") => Some("audio/wav"),
+        Some("mp3" | "mpga" | "mpeg") => Some("audio/mpeg"),
+        Some("m4a" | "mp4") => Some("audio/mp4"),
+        Some("ogg" | "oga") => Some("audio/ogg"),
+        Some("flac") => Some("audio/flac"),
+        Some("webm") => Some("audio/webm"),
+        Some("aac") => Some("audio/aac"),
+        _ => None,
+    };
+
+    from_extension
+        .or(content_type)
+        .unwrap_or("application/octet-stream")
+        .to_string()
+}
+
+/// The cactus runtime only translates with whisper checkpoints.
+fn supports_translation(model_path: &Path) -> bool {
+    hypr_cactus::ModelKind::from_model_dir(model_path) == Some(hypr_cactus::ModelKind::Whisper)
+}
+
+/// Errors in the OpenAI shape, so SDKs surface the message instead of a decode failure.
+struct ApiError {
+    status: StatusCode,
+    kind: &'static str,
+    message: String,
+    param: Option<String>,
+}
+
+impl ApiError {
+    fn invalid(message: impl Into<String>, param: Option<&str>) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            kind: "invalid_request_error",
+            message: message.into(),
+            param: param.map(Into::into),
+        }
+    }
+
+    fn busy() -> Self {
+        Self {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            kind: "server_error",
+            message: "the model is already serving as many requests as it allows".to_string(),
+            param: None,
+        }
+    }
+
+    fn server(message: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            kind: "server_error",
+            message: message.into(),
+            param: None,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (
+            self.status,
+            Json(serde_json::json!({
+                "error": {
+                    "message": self.message,
+                    "type": self.kind,
+                    "param": self.param,
+                    "code": null,
+                }
+            })),
+        )
+            .into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::Body, http::Request};
+    use tower::ServiceExt;
+
+    use super::*;
+
+    const BOUNDARY: &str = "X-BOUNDARY";
+
+    fn multipart_request(uri: &str, fields: &[(&str, &str)]) -> Request<Body> {
+        let mut body = String::new();
+        for (name, value) in fields {
+            body.push_str(&format!(
+                "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n"
+            ));
+        }
+        body.push_str(&format!("--{BOUNDARY}--\r\n"));
+
+        Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header(
+                "content-type",
+                format!("multipart/form-data; boundary={BOUNDARY}"),
+            )
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    async fn error(router: Router, request: Request<Body>) -> (StatusCode, serde_json::Value) {
+        let response = router.oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn rejects_requests_before_loading_the_model() {
+        let router = openai_router(
+            PathBuf::from("/nonexistent/whisper-small-int8"),
+            ConnectionManager::default(),
+        );
+
+        let (status, body) = error(
+            router.clone(),
+            multipart_request("/v1/audio/transcriptions", &[("model", "whisper-1")]),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["param"], "file");
+
+        let (status, body) = error(
+            router.clone(),
+            multipart_request(
+                "/v1/audio/transcriptions",
+                &[("file", "RIFF"), ("response_format", "xml")],
+            ),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["param"], "response_format");
+
+        let (status, body) = error(
+            router,
+            multipart_request("/v1/audio/translations", &[("file", "RIFF")]),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["type"], "invalid_request_error");
+        assert_eq!(body["error"]["param"], "model");
+    }
+
+    #[test]
+    fn content_type_prefers_known_extensions() {
+        assert_eq!(
+            audio_content_type(Some("call.MP3"), Some("application/octet-stream")),
+            "audio/mpeg"
+        );
+        assert_eq!(
+            audio_content_type(Some("blob"), Some("audio/ogg")),
+            "audio/ogg"
+        );
+        assert_eq!(audio_content_type(None, None), "application/octet-stream");
+    }
+
+    #[test]
+    fn translation_requires_whisper() {
+        let model_dir = |model_type: &str| {
+            let dir = tempfile::tempdir().unwrap();
+            std::fs::write(
+                dir.path().join("config.txt"),
+                format!("model_type={model_type}\n"),
+            )
+            .unwrap();
+            dir
+        };
+
+        assert!(supports_translation(model_dir("whisper").path()));
+        assert!(!supports_translation(model_dir("parakeet").path()));
+        // The directory name alone says nothing about the model.
+        assert!(!supports_translation(Path::new(
+            "/models/cactus/whisper-small-int8-apple"
+        )));
+    }
+
+    #[tokio::test]
+    async fn rejects_requests_beyond_the_connection_limit() {
+        let connections = ConnectionManager::with_limit(1);
+        let _session = connections.acquire_connection().unwrap();
+        let router = openai_router(PathBuf::from("/nonexistent/model"), connections);
+
+        let (status, body) = error(
+            router,
+            multipart_request("/v1/audio/transcriptions", &[("file", "RIFF")]),
+        )
+        .await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["error"]["type"], "server_error");
+    }
+}
@@ -51,6 +51,11 @@ impl TranscribeServiceBuilder {
         self
     }
 
+    pub fn connection_manager(mut self, connection_manager: ConnectionManager) -> Self {
+        self.connection_manager = Some(connection_manager);
+        self
+    }
+
     pub fn build(self) -> TranscribeService {
         TranscribeService {
             model_path: self
@@ -101,7 +106,11 @@ impl Service<Request<Body>> for TranscribeService {
                     }
                 };
 
-                let guard = connection_manager.acquire_connection();
+                let Some(guard) = connection_manager.acquire_connection() else {
+                    return Ok(
+                        (StatusCode::SERVICE_UNAVAILABLE, "too_many_connections").into_response()
+                    );
+                };
 
                 Ok(ws_upgrade
                     .on_upgrade(move |socket| async move {
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
+use hypr_language::SpokenLanguageDetector;
 use owhisper_client::Provider;
 
 use crate::analytics::SttAnalyticsReporter;
@@ -31,6 +32,7 @@ pub struct SttProxyConfig {
     pub analytics: Option<Arc<dyn SttAnalyticsReporter>>,
     pub upstream_urls: HashMap<Provider, String>,
     pub hyprnote_routing: Option<HyprnoteRoutingConfig>,
+    pub language_detector: Option<Arc<dyn SpokenLanguageDetector>>,
     pub supabase: SupabaseConfig,
     pub callback: CallbackConfig,
 }
@@ -44,6 +46,7 @@ impl SttProxyConfig {
             analytics: None,
             upstream_urls: HashMap::new(),
             hyprnote_routing: None,
+            language_detector: None,
             supabase: SupabaseConfig {
                 url: Some(supabase.supabase_url.clone()),
                 service_role_key: Some(supabase.supabase_service_role_key.clone()),
@@ -55,6 +58,28 @@ impl SttProxyConfig {
         }
     }
 
+    /// A config that only relays to the given providers. Without Supabase there is no storage
+    /// for async batch jobs, so those requests fail with a missing-config error.
+    pub fn from_api_keys(api_keys: HashMap<Provider, String>) -> Self {
+        Self {
+            api_keys,
+            default_provider: Provider::Deepgram,
+            connect_timeout: Duration::from_millis(DEFAULT_CONNECT_TIMEOUT_MS),
+            analytics: None,
+            upstream_urls: HashMap::new(),
+            hyprnote_routing: None,
+            language_detector: None,
+            supabase: SupabaseConfig {
+                url: None,
+                service_role_key: None,
+            },
+            callback: CallbackConfig {
+                api_base_url: None,
+                secret: None,
+            },
+        }
+    }
+
     pub fn with_default_provider(mut self, provider: Provider) -> Self {
         self.default_provider = provider;
         self
@@ -80,6 +105,13 @@ impl SttProxyConfig {
         self
     }
 
+    /// Lets hyprnote-routed batch requests pick languages, and so providers, from the audio
+    /// instead of trusting the declared `language` parameter.
+    pub fn with_language_detector(mut self, detector: Arc<dyn SpokenLanguageDetector>) -> Self {
+        self.language_detector = Some(detector);
+        self
+    }
+
     pub fn provider_selector(&self) -> ProviderSelector {
         ProviderSelector::new(
             self.api_keys.clone(),
@@ -0,0 +1,179 @@
+use std::num::NonZeroU8;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use hypr_audio_utils::Source;
+use hypr_language::{
+    DEFAULT_MIN_CONFIDENCE, DETECTION_SAMPLE_RATE, Language, LanguageScore, SpokenLanguage,
+    SpokenLanguageDetector, resolve_spoken_language,
+};
+use rodio::buffer::SamplesBuffer;
+
+use crate::query_params::QueryParams;
+
+/// How much audio a live session holds back to pick its providers by the spoken language.
+pub(crate) const LIVE_DETECTION_WINDOW: Duration = Duration::from_secs(5);
+/// How much speech the detector gets.
+const DETECTION_WINDOW: Duration = Duration::from_secs(10);
+/// Less speech than this and the declared languages are kept.
+const MIN_SPEECH: Duration = Duration::from_secs(1);
+/// Recordings that stay silent longer than this before anyone speaks are not searched further.
+const MAX_LEADING_SILENCE: Duration = Duration::from_secs(60);
+const FRAME_MS: usize = 100;
+const SPEECH_RMS: f32 = 0.01;
+
+/// Clients opt out with `detect_language=false`, e.g. when the user picked the language on
+/// purpose for a recording with a long foreign-language intro.
+pub(crate) fn detection_enabled(params: &QueryParams) -> bool {
+    params
+        .get_first("detect_language")
+        .is_none_or(|v| !v.eq_ignore_ascii_case("false"))
+}
+
+/// Identifies the language from the first seconds of speech in `path`, falling back to
+/// `declared` when there is too little speech or the detector is unsure.
+pub(crate) async fn identify_file_language(
+    detector: Arc<dyn SpokenLanguageDetector>,
+    path: &Path,
+    declared: &[Language],
+) -> SpokenLanguage {
+    let path = path.to_path_buf();
+    let result =
+        tokio::task::spawn_blocking(move || detect(&*detector, &speech_samples(&path)?)).await;
+    resolve(result, declared)
+}
+
+/// Like [`identify_file_language`], for the start of a live stream given as interleaved
+/// 16-bit little-endian PCM.
+pub(crate) async fn identify_pcm_language(
+    detector: Arc<dyn SpokenLanguageDetector>,
+    pcm: Vec<u8>,
+    sample_rate: u32,
+    channels: u8,
+    declared: &[Language],
+) -> SpokenLanguage {
+    let result = tokio::task::spawn_blocking(move || {
+        detect(&*detector, &pcm_samples(&pcm, sample_rate, channels)?)
+    })
+    .await;
+    resolve(result, declared)
+}
+
+fn detect(
+    detector: &dyn SpokenLanguageDetector,
+    samples: &[f32],
+) -> Result<Vec<LanguageScore>, String> {
+    let Some(window) = speech_window(samples) else {
+        return Ok(Vec::new());
+    };
+    detector.detect(window).map_err(|e| e.to_string())
+}
+
+fn resolve(
+    result: Result<Result<Vec<LanguageScore>, String>, tokio::task::JoinError>,
+    declared: &[Language],
+) -> SpokenLanguage {
+    let scores = match result {
+        Ok(Ok(scores)) => scores,
+        Ok(Err(e)) => {
+            tracing::warn!(error.message = %e, "language_detection_failed");
+            return SpokenLanguage::declared(declared);
+        }
+        Err(e) => {
+            tracing::warn!(error.message = ?e, "language_detection_task_join_failed");
+            return SpokenLanguage::declared(declared);
+        }
+    };
+
+    let spoken = resolve_spoken_language(&scores, declared, DEFAULT_MIN_CONFIDENCE);
+    tracing::info!(
+        hyprnote.stt.language_codes = ?spoken.languages,
+        hyprnote.stt.language_source = spoken.source.as_str(),
+        hyprnote.stt.language_confidence = ?spoken.confidence,
+        "spoken_language_identified"
+    );
+    spoken
+}
+
+fn speech_samples(path: &Path) -> Result<Vec<f32>, String> {
+    let source = hypr_audio_utils::source_from_path(path).map_err(|e| e.to_string())?;
+    let channels = NonZeroU8::new(source.channels() as u8).unwrap_or(NonZeroU8::MIN);
+
+    let head = source.take_duration(MAX_LEADING_SILENCE + DETECTION_WINDOW);
+    let resampled =
+        hypr_audio_utils::resample_audio(head, DETECTION_SAMPLE_RATE).map_err(|e| e.to_string())?;
+    Ok(hypr_audio_utils::mix_down_to_mono(&resampled, channels))
+}
+
+fn pcm_samples(pcm: &[u8], sample_rate: u32, channels: u8) -> Result<Vec<f32>, String> {
+    let channels = NonZeroU8::new(channels).unwrap_or(NonZeroU8::MIN);
+    let mono =
+        hypr_audio_utils::mix_down_to_mono(&hypr_audio_utils::bytes_to_f32_samples(pcm), channels);
+    hypr_audio_utils::resample_audio(
+        SamplesBuffer::new(1, sample_rate, mono),
+        DETECTION_SAMPLE_RATE,
+    )
+    .map_err(|e| e.to_string())
+}
+
+/// Skips leading silence and returns up to `DETECTION_WINDOW` of what follows.
+fn speech_window(samples: &[f32]) -> Option<&[f32]> {
+    let rate = DETECTION_SAMPLE_RATE as usize;
+    let frame = rate * FRAME_MS / 1000;
+
+    let start = samples
+        .chunks(frame)
+        .position(|chunk| rms(chunk) >= SPEECH_RMS)?
+        * frame;
+    let end = samples
+        .len()
+        .min(start + rate * DETECTION_WINDOW.as_millis() as usize / 1000);
+
+    (end - start >= rate * MIN_SPEECH.as_millis() as usize / 1000).then(|| &samples[start..end])
+}
+
+fn rms(samples: &[f32]) -> f32 {
+    let sum: f32 = samples.iter().map(|s| s * s).sum();
+    (sum / samples.len() as f32).sqrt()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(seconds: f32) -> Vec<f32> {
+        let len = (DETECTION_SAMPLE_RATE as f32 * seconds) as usize;
+        (0..len).map(|i| (i as f32 * 0.05).sin() * 0.3).collect()
+    }
+
+    #[test]
+    fn test_speech_window_skips_leading_silence() {
+        let rate = DETECTION_SAMPLE_RATE as usize;
+        let mut samples = vec![0.0; rate * 3];
+        samples.extend(tone(20.0));
+
+        let window = speech_window(&samples).unwrap();
+        assert_eq!(window.len(), rate * 10);
+        assert!(rms(&window[..rate / 10]) >= SPEECH_RMS);
+    }
+
+    #[test]
+    fn test_pcm_samples_mixes_interleaved_channels() {
+        let frame = [16384i16.to_le_bytes(), 0i16.to_le_bytes()].concat();
+        let pcm = frame.repeat(DETECTION_SAMPLE_RATE as usize);
+
+        let samples = pcm_samples(&pcm, DETECTION_SAMPLE_RATE, 2).unwrap();
+        assert_eq!(samples.len(), DETECTION_SAMPLE_RATE as usize);
+        assert!((samples[0] - 0.25).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_speech_window_needs_enough_speech() {
+        let mut samples = vec![0.0; DETECTION_SAMPLE_RATE as usize * 3];
+        assert!(speech_window(&samples).is_none());
+
+        samples.extend(tone(0.5));
+        assert!(speech_window(&samples).is_none());
+    }
+}
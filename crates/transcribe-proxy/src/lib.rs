@@ -3,6 +3,7 @@ mod config;
 mod env;
 mod error;
 mod hyprnote_routing;
+mod language_id;
 mod openapi;
 mod provider_selector;
 mod query_params;
@@ -21,6 +22,8 @@ pub use hyprnote_routing::{
 };
 pub use openapi::openapi;
 pub use provider_selector::{ProviderSelector, SelectedProvider};
-pub use relay::{ClientRequestBuilder, UpstreamError, WebSocketProxy, detect_upstream_error};
+pub use relay::{
+    ClientRequestBuilder, FailoverProxy, UpstreamError, WebSocketProxy, detect_upstream_error,
+};
 pub use routes::{callback_router, listen_router, router};
 pub use upstream_url::UpstreamUrlBuilder;
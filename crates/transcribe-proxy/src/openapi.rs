@@ -19,6 +19,7 @@ enum BatchListenSuccessResponse {
     params(
         CommonListenParams,
         ("callback" = Option<String>, Query, description = "When set, enables async callback mode. Body should be JSON with a `url` field instead of raw audio"),
+        ("detect_language" = Option<bool>, Query, description = "With hyprnote routing, identify the spoken language from the audio and route on it when confident (default `true` when the server has a detector)"),
     ),
     request_body(
         description = "Raw audio bytes (sync mode) or JSON `{ \"url\": \"<file_id>\" }` (callback mode)",
@@ -0,0 +1,789 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::body::Body;
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::http::Response;
+use axum::response::IntoResponse;
+use futures_util::{SinkExt, StreamExt};
+use sentry::SentryFutureExt;
+use tokio_tungstenite::tungstenite::Message as TungsteniteMessage;
+use tokio_tungstenite::{MaybeTlsStream, WebSocketStream};
+
+use hypr_language::{Language, LanguageSource, SpokenLanguageDetector};
+use owhisper_client::Provider;
+use owhisper_interface::ControlMessage;
+use owhisper_interface::stream::StreamResponse;
+
+use super::handler::WebSocketProxy;
+use super::pending::{FlushError, PendingState, QueuedPayload};
+use super::replay::ReplayBuffer;
+use super::types::{
+    ClientReceiver, ClientSender, ControlMessageTypes, DEFAULT_CLOSE_CODE, FirstMessageTransformer,
+    ProviderOnCloseCallback, UpstreamSender, convert, is_control_message,
+};
+use crate::language_id::{LIVE_DETECTION_WINDOW, identify_pcm_language};
+
+const SAMPLE_BYTES: u64 = 2;
+const NORMAL_CLOSE_CODE: u16 = 1000;
+
+type Upstream = WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>;
+
+/// Builds the provider chain for the languages actually spoken.
+pub type UpstreamResolver =
+    Arc<dyn Fn(&[Language]) -> Vec<(Provider, WebSocketProxy)> + Send + Sync>;
+
+/// Picks the upstreams by the language heard in the first seconds of the session instead of
+/// the declared one.
+pub struct LanguageRouting {
+    pub detector: Arc<dyn SpokenLanguageDetector>,
+    pub declared: Vec<Language>,
+    pub resolve: UpstreamResolver,
+}
+
+/// Relays a live session to the first upstream of a provider chain and moves on to the next
+/// one when the upstream dies mid-stream.
+///
+/// Audio not yet covered by a final result on every channel is kept in a [`ReplayBuffer`] and
+/// replayed to the next provider. Its results are shifted by the replay start, so the client
+/// sees one continuous timeline, and results for audio the client already has as finals are
+/// dropped. Every result names the provider that produced it in `metadata.extra.provider`.
+///
+/// With [`LanguageRouting`], the first [`LIVE_DETECTION_WINDOW`] of audio is held back and
+/// the chain is chosen by the language spoken in it; the held audio is then replayed.
+///
+/// Upstreams must emit normalized `StreamResponse` JSON (after their response transformer).
+#[derive(Clone)]
+pub struct FailoverProxy {
+    upstreams: Vec<(Provider, WebSocketProxy)>,
+    sample_rate: u32,
+    channels: u8,
+    bytes_per_second: u64,
+    on_close: Option<ProviderOnCloseCallback>,
+    language_routing: Option<Arc<LanguageRouting>>,
+}
+
+enum RelayEnd {
+    /// The client is gone or the session ended normally.
+    Closed,
+    /// The upstream failed while the client was still streaming.
+    UpstreamLost { code: u16, reason: String },
+}
+
+struct Session {
+    replay: ReplayBuffer,
+    provider: Option<Provider>,
+    /// Seconds added to the current upstream's timestamps.
+    offset_secs: f64,
+    /// Per channel, where the last final result sent to the client ends, in session seconds.
+    final_end_secs: Vec<f64>,
+    /// `final_end_secs` at the last failover. The new upstream transcribes the replayed audio
+    /// again, and its results up to here are duplicates.
+    replayed_until: Vec<f64>,
+    /// The client sent `Finalize` or `CloseStream`, so the upstream closing is expected.
+    closing: bool,
+    /// Client text messages received before the first upstream was connected.
+    held: Vec<String>,
+}
+
+impl FailoverProxy {
+    pub fn new(
+        upstreams: Vec<(Provider, WebSocketProxy)>,
+        sample_rate: u32,
+        channels: u8,
+        on_close: Option<ProviderOnCloseCallback>,
+    ) -> Self {
+        Self {
+            upstreams,
+            sample_rate,
+            channels: channels.max(1),
+            bytes_per_second: SAMPLE_BYTES * channels.max(1) as u64 * sample_rate as u64,
+            on_close,
+            language_routing: None,
+        }
+    }
+
+    pub fn with_language_routing(mut self, routing: LanguageRouting) -> Self {
+        self.language_routing = Some(Arc::new(routing));
+        self
+    }
+
+    pub async fn handle_upgrade(&self, ws: WebSocketUpgrade) -> Response<Body> {
+        let proxy = self.clone();
+        let hub = sentry::Hub::current();
+        ws.on_upgrade(move |socket| {
+            async move {
+                if let Err(e) = proxy.handle(socket).await {
+                    tracing::error!(error.message = %e, "failover_proxy_error");
+                }
+            }
+            .bind_hub(sentry::Hub::new_from_top(hub))
+        })
+        .into_response()
+    }
+
+    async fn handle(&self, client_socket: WebSocket) -> Result<(), crate::ProxyError> {
+        let (mut client_tx, mut client_rx) = client_socket.split();
+        let mut session = Session {
+            replay: ReplayBuffer::new(self.channels),
+            provider: None,
+            offset_secs: 0.0,
+            final_end_secs: vec![0.0; self.channels as usize],
+            replayed_until: Vec::new(),
+            closing: false,
+            held: Vec::new(),
+        };
+
+        let start_time = Instant::now();
+        let routed;
+        let upstreams = match &self.language_routing {
+            Some(routing) => {
+                let Some(upstreams) = self
+                    .route_by_language(routing, &mut client_rx, &mut session)
+                    .await
+                else {
+                    return Ok(());
+                };
+                routed = upstreams;
+                &routed
+            }
+            None => &self.upstreams,
+        };
+        let mut remaining = upstreams.iter();
+
+        let (mut proxy, mut upstream) = match self.connect_next(&mut remaining, &mut session).await
+        {
+            Ok(connected) => connected,
+            Err(e) => {
+                let _ = client_tx
+                    .send(convert::to_axum_close(DEFAULT_CLOSE_CODE, e.to_string()))
+                    .await;
+                return Err(e);
+            }
+        };
+
+        // How long each provider served, so usage is reported against the one that did the work.
+        let mut usage: Vec<(Provider, Duration)> = Vec::new();
+        let mut served_since = start_time;
+        loop {
+            let end = self
+                .relay(
+                    proxy,
+                    upstream,
+                    &mut client_tx,
+                    &mut client_rx,
+                    &mut session,
+                )
+                .await;
+
+            if let Some(provider) = session.provider {
+                record_usage(&mut usage, provider, served_since.elapsed());
+            }
+            served_since = Instant::now();
+
+            let RelayEnd::UpstreamLost { code, reason } = end else {
+                break;
+            };
+
+            tracing::warn!(
+                hyprnote.stt.provider.name = ?session.provider,
+                hyprnote.ws.close.code = code,
+                hyprnote.ws.close.reason = %reason,
+                "upstream_lost_mid_stream"
+            );
+
+            match self.connect_next(&mut remaining, &mut session).await {
+                Ok(connected) => (proxy, upstream) = connected,
+                Err(_) => {
+                    let _ = client_tx.send(convert::to_axum_close(code, reason)).await;
+                    break;
+                }
+            }
+        }
+
+        let duration = start_time.elapsed();
+        if let Some(on_close) = &self.on_close {
+            for (provider, served) in usage {
+                on_close(provider, served).await;
+            }
+        }
+
+        tracing::info!(
+            hyprnote.duration_ms = duration.as_millis() as u64,
+            "failover_proxy_closed"
+        );
+
+        Ok(())
+    }
+
+    /// Holds back the start of the session until it has enough audio to tell the language,
+    /// then resolves the upstreams for it. Returns `None` if the client left in the meantime.
+    async fn route_by_language(
+        &self,
+        routing: &LanguageRouting,
+        client_rx: &mut ClientReceiver,
+        session: &mut Session,
+    ) -> Option<Vec<(Provider, WebSocketProxy)>> {
+        let window = LIVE_DETECTION_WINDOW.as_secs() * self.bytes_per_second;
+
+        while session.replay.end() < window && !session.closing {
+            match client_rx.next().await? {
+                Ok(Message::Binary(bytes)) => session.replay.push(bytes.to_vec()),
+                Ok(Message::Text(text)) => {
+                    let text = text.to_string();
+                    if matches!(
+                        serde_json::from_str::<ControlMessage>(&text),
+                        Ok(ControlMessage::Finalize | ControlMessage::CloseStream)
+                    ) {
+                        session.closing = true;
+                    }
+                    session.held.push(text);
+                }
+                Ok(Message::Ping(_) | Message::Pong(_)) => {}
+                Ok(Message::Close(_)) | Err(_) => return None,
+            }
+        }
+
+        let pcm = session.replay.chunks().flatten().copied().collect();
+        let spoken = identify_pcm_language(
+            routing.detector.clone(),
+            pcm,
+            self.sample_rate,
+            self.channels,
+            &routing.declared,
+        )
+        .await;
+
+        if spoken.source == LanguageSource::Detected && spoken.languages != routing.declared {
+            let upstreams = (routing.resolve)(&spoken.languages);
+            if !upstreams.is_empty() {
+                return Some(upstreams);
+            }
+        }
+        Some(self.upstreams.clone())
+    }
+
+    /// Connects to the next reachable upstream and replays the unacknowledged audio to it.
+    async fn connect_next<'a>(
+        &self,
+        remaining: &mut impl Iterator<Item = &'a (Provider, WebSocketProxy)>,
+        session: &mut Session,
+    ) -> Result<(&'a WebSocketProxy, Upstream), crate::ProxyError> {
+        for (provider, proxy) in remaining.by_ref() {
+            let Ok(mut upstream) = proxy.connect_upstream().await else {
+                continue;
+            };
+
+            if let Some(msg) = &proxy.initial_message
+                && upstream
+                    .send(TungsteniteMessage::Text(msg.as_str().into()))
+                    .await
+                    .is_err()
+            {
+                tracing::warn!(hyprnote.stt.provider.name = %provider, "initial_message_send_failed");
+                continue;
+            }
+
+            let mut replayed = true;
+            for chunk in session.replay.chunks() {
+                if upstream
+                    .send(TungsteniteMessage::Binary(chunk.to_vec().into()))
+                    .await
+                    .is_err()
+                {
+                    replayed = false;
+                    break;
+                }
+            }
+            if !replayed {
+                tracing::warn!(hyprnote.stt.provider.name = %provider, "replay_send_failed");
+                continue;
+            }
+
+            let offset_secs = session.replay.start() as f64 / self.bytes_per_second as f64;
+            if let Some(previous) = session.provider {
+                tracing::info!(
+                    hyprnote.stt.provider.name = %provider,
+                    hyprnote.stt.previous_provider.name = %previous,
+                    hyprnote.stt.failover.offset_ms = (offset_secs * 1000.0) as u64,
+                    hyprnote.stt.failover.replayed_ms = (session.replay.end() - session.replay.start())
+                        * 1000
+                        / self.bytes_per_second,
+                    "upstream_failover"
+                );
+                session.replayed_until = session.final_end_secs.clone();
+            }
+
+            session.provider = Some(*provider);
+            session.offset_secs = offset_secs;
+            return Ok((proxy, upstream));
+        }
+
+        Err(crate::ProxyError::ConnectionFailed(
+            "no upstream provider left".to_string(),
+        ))
+    }
+
+    async fn relay(
+        &self,
+        proxy: &WebSocketProxy,
+        upstream: Upstream,
+        client_tx: &mut ClientSender,
+        client_rx: &mut ClientReceiver,
+        session: &mut Session,
+    ) -> RelayEnd {
+        let (mut upstream_tx, mut upstream_rx) = upstream.split();
+        let mut pending_error: Option<(u16, String)> = None;
+        let mut pending = PendingState::default();
+        // Each upstream gets its own first message, e.g. for auth carried in the payload.
+        let mut first_msg_transformer = proxy.transform_first_message.clone();
+
+        for text in std::mem::take(&mut session.held) {
+            if let Err(reason) = forward_text(
+                proxy,
+                &mut first_msg_transformer,
+                &mut pending,
+                &mut upstream_tx,
+                text,
+            )
+            .await
+            {
+                return upstream_lost(pending_error, reason);
+            }
+        }
+
+        loop {
+            tokio::select! {
+                msg_opt = client_rx.next() => {
+                    let msg = match msg_opt {
+                        Some(Ok(msg)) => msg,
+                        Some(Err(e)) => {
+                            tracing::error!(
+                                error.type = "ws_client_receive_error",
+                                error.message = %e,
+                                "client_receive_error"
+                            );
+                            let _ = upstream_tx.send(convert::to_tungstenite_close(DEFAULT_CLOSE_CODE, "client_error".to_string())).await;
+                            return RelayEnd::Closed;
+                        }
+                        None => {
+                            let _ = upstream_tx.send(convert::to_tungstenite_close(DEFAULT_CLOSE_CODE, "client_disconnected".to_string())).await;
+                            return RelayEnd::Closed;
+                        }
+                    };
+
+                    match msg {
+                        Message::Binary(bytes) => {
+                            let data = bytes.to_vec();
+                            session.replay.push(data.clone());
+                            if let Err(reason) = forward(&mut pending, &mut upstream_tx, &proxy.control_message_types, data, false).await {
+                                return upstream_lost(pending_error, reason);
+                            }
+                        }
+                        Message::Text(text) => {
+                            let text = text.to_string();
+                            if matches!(
+                                serde_json::from_str::<ControlMessage>(&text),
+                                Ok(ControlMessage::Finalize | ControlMessage::CloseStream)
+                            ) {
+                                session.closing = true;
+                            }
+
+                            if let Err(reason) = forward_text(proxy, &mut first_msg_transformer, &mut pending, &mut upstream_tx, text).await {
+                                return upstream_lost(pending_error, reason);
+                            }
+                        }
+                        Message::Ping(data) => {
+                            let _ = upstream_tx.send(TungsteniteMessage::Ping(data.to_vec().into())).await;
+                        }
+                        Message::Pong(data) => {
+                            let _ = upstream_tx.send(TungsteniteMessage::Pong(data.to_vec().into())).await;
+                        }
+                        Message::Close(frame) => {
+                            let (code, reason) = convert::extract_axum_close(frame, "client_closed");
+                            tracing::info!(
+                                hyprnote.ws.close.code = code,
+                                hyprnote.ws.close.reason = %reason,
+                                "ws_client_close_received"
+                            );
+                            let _ = upstream_tx.send(convert::to_tungstenite_close(code, reason.clone())).await;
+                            let _ = client_tx.send(convert::to_axum_close(code, reason)).await;
+                            return RelayEnd::Closed;
+                        }
+                    }
+                }
+
+                msg_opt = upstream_rx.next() => {
+                    let msg = match msg_opt {
+                        Some(Ok(msg)) => msg,
+                        Some(Err(e)) => {
+                            tracing::error!(
+                                error.type = "ws_upstream_receive_error",
+                                error.message = %e,
+                                "upstream_receive_error"
+                            );
+                            let (code, reason) = pending_error
+                                .unwrap_or((DEFAULT_CLOSE_CODE, format!("upstream_error: {}", e)));
+                            return Self::upstream_ended(session, client_tx, code, reason, false).await;
+                        }
+                        None => {
+                            let (code, reason) = pending_error
+                                .unwrap_or((DEFAULT_CLOSE_CODE, "upstream_disconnected".to_string()));
+                            return Self::upstream_ended(session, client_tx, code, reason, false).await;
+                        }
+                    };
+
+                    match msg {
+                        TungsteniteMessage::Text(text) => {
+                            // Errors become the close reason if no other provider can take over,
+                            // so they are not forwarded as results.
+                            if let Some(upstream_err) = Provider::detect_any_error(text.as_bytes()) {
+                                tracing::warn!(
+                                    http.response.status_code = upstream_err.http_code,
+                                    hyprnote.stt.provider.error_code = ?upstream_err.provider_code,
+                                    error.message = %upstream_err.message,
+                                    "upstream_error_detected"
+                                );
+                                pending_error = Some((
+                                    upstream_err.to_ws_close_code(),
+                                    upstream_err.message.clone(),
+                                ));
+                                continue;
+                            }
+
+                            let output = match &proxy.response_transformer {
+                                Some(transformer) => match transformer(text.as_str()) {
+                                    Some(transformed) => transformed,
+                                    None => continue,
+                                },
+                                None => text.to_string(),
+                            };
+                            let Some(output) = self.rewrite(session, &output) else {
+                                continue;
+                            };
+
+                            if client_tx.send(Message::Text(output.into())).await.is_err() {
+                                let _ = upstream_tx.send(convert::to_tungstenite_close(DEFAULT_CLOSE_CODE, "client_send_failed".to_string())).await;
+                                return RelayEnd::Closed;
+                            }
+                        }
+                        TungsteniteMessage::Binary(data) => {
+                            if client_tx.send(Message::Binary(data.to_vec().into())).await.is_err() {
+                                return RelayEnd::Closed;
+                            }
+                        }
+                        TungsteniteMessage::Ping(data) => {
+                            let _ = client_tx.send(Message::Ping(data.to_vec().into())).await;
+                        }
+                        TungsteniteMessage::Pong(data) => {
+                            let _ = client_tx.send(Message::Pong(data.to_vec().into())).await;
+                        }
+                        TungsteniteMessage::Close(frame) => {
+                            let graceful = pending_error.is_none();
+                            let (code, reason) = pending_error.unwrap_or_else(|| {
+                                convert::extract_tungstenite_close(frame, "upstream_closed")
+                            });
+                            tracing::info!(
+                                hyprnote.ws.close.code = code,
+                                hyprnote.ws.close.reason = %reason,
+                                "ws_upstream_close_received"
+                            );
+                            let graceful = graceful && code == NORMAL_CLOSE_CODE;
+                            return Self::upstream_ended(session, client_tx, code, reason, graceful).await;
+                        }
+                        TungsteniteMessage::Frame(_) => {}
+                    }
+                }
+            }
+        }
+    }
+
+    /// An upstream closing on its own is only a failure if the client didn't ask to finish.
+    async fn upstream_ended(
+        session: &Session,
+        client_tx: &mut ClientSender,
+        code: u16,
+        reason: String,
+        graceful: bool,
+    ) -> RelayEnd {
+        if session.closing || graceful {
+            let _ = client_tx.send(convert::to_axum_close(code, reason)).await;
+            return RelayEnd::Closed;
+        }
+        RelayEnd::UpstreamLost { code, reason }
+    }
+
+    fn rewrite(&self, session: &mut Session, text: &str) -> Option<String> {
+        let Some(provider) = session.provider else {
+            return Some(text.to_string());
+        };
+
+        let (output, final_ends) =
+            rewrite_response(text, session.offset_secs, provider, &session.replayed_until);
+        for (channel, end_secs) in final_ends {
+            if let Some(last) = session.final_end_secs.get_mut(channel) {
+                *last = last.max(end_secs);
+            }
+            session
+                .replay
+                .ack(channel, (end_secs * self.bytes_per_second as f64) as u64);
+        }
+        output
+    }
+}
+
+/// Sends a client payload through a pending queue, so control messages are classified and
+/// ordered the same way [`WebSocketProxy`] does.
+async fn forward(
+    pending: &mut PendingState,
+    upstream_tx: &mut UpstreamSender,
+    control_types: &Option<ControlMessageTypes>,
+    data: Vec<u8>,
+    is_text: bool,
+) -> Result<(), &'static str> {
+    let is_control = control_types
+        .as_ref()
+        .is_some_and(|types| is_control_message(&data, types));
+    pending.enqueue(QueuedPayload { data, is_text }, is_control)?;
+    pending.flush_to(upstream_tx).await.map_err(|e| match e {
+        FlushError::SendFailed => "upstream_send_failed",
+        FlushError::InvalidUtf8 => "invalid_utf8_in_message",
+    })
+}
+
+/// Sends a client text message the way [`WebSocketProxy`] does: the first one through the
+/// first-message transformer, then through the provider's control message filter.
+async fn forward_text(
+    proxy: &WebSocketProxy,
+    first_msg_transformer: &mut Option<FirstMessageTransformer>,
+    pending: &mut PendingState,
+    upstream_tx: &mut UpstreamSender,
+    text: String,
+) -> Result<(), &'static str> {
+    let text = match first_msg_transformer.take() {
+        Some(transformer) => transformer(text),
+        None => text,
+    };
+
+    let text = match proxy.client_message_filter.as_ref() {
+        Some(filter) => match filter(text) {
+            Some(s) => s,
+            None => return Ok(()),
+        },
+        None => text,
+    };
+
+    forward(
+        pending,
+        upstream_tx,
+        &proxy.control_message_types,
+        text.into_bytes(),
+        true,
+    )
+    .await
+}
+
+fn record_usage(usage: &mut Vec<(Provider, Duration)>, provider: Provider, served: Duration) {
+    match usage.iter_mut().find(|(p, _)| *p == provider) {
+        Some((_, total)) => *total += served,
+        None => usage.push((provider, served)),
+    }
+}
+
+fn upstream_lost(pending_error: Option<(u16, String)>, default_reason: &str) -> RelayEnd {
+    let (code, reason) = pending_error.unwrap_or((DEFAULT_CLOSE_CODE, default_reason.to_string()));
+    RelayEnd::UpstreamLost { code, reason }
+}
+
+/// Shifts a normalized response onto the session timeline and stamps the serving provider.
+///
+/// Results that end at or before `replayed_until` for their channel were already sent as
+/// finals by the previous upstream and are dropped. Returns the rewritten JSON, or `None` if
+/// nothing is left, and where each channel's final results end, in session seconds.
+fn rewrite_response(
+    text: &str,
+    offset_secs: f64,
+    provider: Provider,
+    replayed_until: &[f64],
+) -> (Option<String>, Vec<(usize, f64)>) {
+    let (responses, is_array) = match serde_json::from_str::<Vec<StreamResponse>>(text) {
+        Ok(responses) => (responses, true),
+        Err(_) => match serde_json::from_str::<StreamResponse>(text) {
+            Ok(response) => (vec![response], false),
+            Err(_) => return (Some(text.to_string()), Vec::new()),
+        },
+    };
+
+    let mut final_ends: Vec<(usize, f64)> = Vec::new();
+    let mut kept = Vec::with_capacity(responses.len());
+    for mut response in responses {
+        response.apply_offset(offset_secs);
+
+        if let StreamResponse::TranscriptResponse {
+            start,
+            duration,
+            is_final,
+            metadata,
+            channel_index,
+            ..
+        } = &mut response
+        {
+            let channel = channel_index.first().copied().unwrap_or(0).max(0) as usize;
+            let end = *start + *duration;
+            if replayed_until
+                .get(channel)
+                .is_some_and(|until| end <= *until)
+            {
+                continue;
+            }
+
+            metadata
+                .extra
+                .get_or_insert_with(HashMap::new)
+                .insert("provider".to_string(), provider.to_string().into());
+
+            if *is_final {
+                match final_ends.iter_mut().find(|(c, _)| *c == channel) {
+                    Some((_, prev)) => *prev = prev.max(end),
+                    None => final_ends.push((channel, end)),
+                }
+            }
+        }
+
+        kept.push(response);
+    }
+
+    let output = match kept.as_slice() {
+        [] => return (None, Vec::new()),
+        [response] if !is_array => serde_json::to_string(response),
+        responses => serde_json::to_string(responses),
+    };
+
+    match output {
+        Ok(output) => (Some(output), final_ends),
+        Err(_) => (Some(text.to_string()), Vec::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn results(channel: i32, start: f64, duration: f64, is_final: bool) -> String {
+        serde_json::json!({
+            "type": "Results",
+            "start": start,
+            "duration": duration,
+            "is_final": is_final,
+            "speech_final": is_final,
+            "from_finalize": false,
+            "channel_index": [channel, 2],
+            "channel": {
+                "alternatives": [{
+                    "transcript": "hello",
+                    "confidence": 0.9,
+                    "words": [{
+                        "word": "hello",
+                        "start": start,
+                        "end": start + duration,
+                        "confidence": 0.9,
+                        "speaker": null,
+                        "punctuated_word": "hello",
+                        "language": null
+                    }]
+                }]
+            },
+            "metadata": {
+                "request_id": "r",
+                "model_uuid": "m",
+                "model_info": { "name": "", "version": "", "arch": "" }
+            }
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_rewrite_shifts_timestamps_and_stamps_provider() {
+        let (output, final_ends) =
+            rewrite_response(&results(0, 1.0, 2.0, true), 30.0, Provider::Soniox, &[]);
+
+        let value: serde_json::Value = serde_json::from_str(&output.unwrap()).unwrap();
+        assert_eq!(value["start"], 31.0);
+        assert_eq!(value["channel"]["alternatives"][0]["words"][0]["end"], 33.0);
+        assert_eq!(value["metadata"]["extra"]["provider"], "soniox");
+        assert_eq!(final_ends, vec![(0, 33.0)]);
+    }
+
+    #[test]
+    fn test_rewrite_handles_arrays_and_partials() {
+        let text = format!(
+            "[{},{},{}]",
+            results(0, 0.0, 1.0, true),
+            results(1, 0.0, 0.5, true),
+            results(0, 1.0, 0.5, false)
+        );
+        let (output, final_ends) = rewrite_response(&text, 0.0, Provider::Deepgram, &[]);
+
+        let value: serde_json::Value = serde_json::from_str(&output.unwrap()).unwrap();
+        assert_eq!(value.as_array().unwrap().len(), 3);
+        assert_eq!(value[2]["metadata"]["extra"]["provider"], "deepgram");
+        assert_eq!(final_ends, vec![(0, 1.0), (1, 0.5)]);
+    }
+
+    #[test]
+    fn test_rewrite_drops_results_already_sent_as_finals() {
+        let replayed_until = [1.0, 0.5];
+
+        let (output, final_ends) = rewrite_response(
+            &results(0, 0.0, 0.5, true),
+            0.5,
+            Provider::Soniox,
+            &replayed_until,
+        );
+        assert_eq!(output, None);
+        assert!(final_ends.is_empty());
+
+        let (output, final_ends) = rewrite_response(
+            &results(1, 0.0, 0.5, true),
+            0.5,
+            Provider::Soniox,
+            &replayed_until,
+        );
+        assert!(output.is_some());
+        assert_eq!(final_ends, vec![(1, 1.0)]);
+
+        let text = format!(
+            "[{},{}]",
+            results(0, 0.0, 0.5, false),
+            results(0, 0.5, 0.5, false)
+        );
+        let (output, _) = rewrite_response(&text, 0.5, Provider::Soniox, &replayed_until);
+        let value: serde_json::Value = serde_json::from_str(&output.unwrap()).unwrap();
+        assert_eq!(value.as_array().unwrap().len(), 1);
+        assert_eq!(value[0]["start"], 1.0);
+    }
+
+    #[test]
+    fn test_record_usage_sums_per_provider() {
+        let mut usage = Vec::new();
+        record_usage(&mut usage, Provider::Deepgram, Duration::from_secs(30));
+        record_usage(&mut usage, Provider::Soniox, Duration::from_secs(20));
+        record_usage(&mut usage, Provider::Deepgram, Duration::from_secs(5));
+
+        assert_eq!(
+            usage,
+            vec![
+                (Provider::Deepgram, Duration::from_secs(35)),
+                (Provider::Soniox, Duration::from_secs(20)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rewrite_passes_through_unknown_payloads() {
+        let (output, final_ends) = rewrite_response("not json", 10.0, Provider::Deepgram, &[]);
+        assert_eq!(output.as_deref(), Some("not json"));
+        assert!(final_ends.is_empty());
+    }
+}
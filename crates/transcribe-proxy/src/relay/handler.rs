@@ -25,13 +25,13 @@ use super::types::{
 #[derive(Clone)]
 pub struct WebSocketProxy {
     upstream_request: ClientRequestBuilder,
-    control_message_types: Option<ControlMessageTypes>,
-    transform_first_message: Option<FirstMessageTransformer>,
-    initial_message: Option<InitialMessage>,
-    response_transformer: Option<ResponseTransformer>,
+    pub(super) control_message_types: Option<ControlMessageTypes>,
+    pub(super) transform_first_message: Option<FirstMessageTransformer>,
+    pub(super) initial_message: Option<InitialMessage>,
+    pub(super) response_transformer: Option<ResponseTransformer>,
     connect_timeout: Duration,
     on_close: Option<OnCloseCallback>,
-    client_message_filter: Option<ClientMessageFilter>,
+    pub(super) client_message_filter: Option<ClientMessageFilter>,
 }
 
 impl WebSocketProxy {
@@ -61,7 +61,7 @@ impl WebSocketProxy {
         WebSocketProxyBuilder::default()
     }
 
-    async fn connect_upstream(
+    pub(super) async fn connect_upstream(
         &self,
     ) -> Result<WebSocketStream<MaybeTlsStream<tokio::net::TcpStream>>, crate::ProxyError> {
         let mut req = self
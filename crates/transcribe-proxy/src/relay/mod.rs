@@ -1,12 +1,18 @@
 mod builder;
 mod channel_split;
+mod failover;
 mod handler;
 mod pending;
+mod replay;
 mod types;
 mod upstream_error;
 
 pub use builder::ClientRequestBuilder;
 pub use channel_split::ChannelSplitProxy;
+pub use failover::{FailoverProxy, LanguageRouting, UpstreamResolver};
 pub use handler::WebSocketProxy;
-pub use types::{ClientMessageFilter, InitialMessage, OnCloseCallback, ResponseTransformer};
+pub use types::{
+    ClientMessageFilter, InitialMessage, OnCloseCallback, ProviderOnCloseCallback,
+    ResponseTransformer,
+};
 pub use upstream_error::{UpstreamError, detect_upstream_error};
@@ -0,0 +1,141 @@
+use std::collections::VecDeque;
+
+use super::pending::MAX_PENDING_QUEUE_BYTES;
+
+/// Recent client audio that the upstream has not confirmed with a final result yet.
+///
+/// Positions are byte offsets into the session's audio stream. Channels are interleaved, so
+/// audio is only dropped once every channel has confirmed it. The buffer holds at most
+/// `MAX_PENDING_QUEUE_BYTES`; older audio is dropped even if unacknowledged.
+pub struct ReplayBuffer {
+    chunks: VecDeque<Vec<u8>>,
+    start: u64,
+    end: u64,
+    bytes: usize,
+    acked: Vec<u64>,
+}
+
+impl ReplayBuffer {
+    pub fn new(channels: u8) -> Self {
+        Self {
+            chunks: VecDeque::new(),
+            start: 0,
+            end: 0,
+            bytes: 0,
+            acked: vec![0; channels.max(1) as usize],
+        }
+    }
+
+    pub fn push(&mut self, data: Vec<u8>) {
+        self.end += data.len() as u64;
+        self.bytes += data.len();
+        self.chunks.push_back(data);
+
+        while self.bytes > MAX_PENDING_QUEUE_BYTES {
+            self.pop_front();
+        }
+    }
+
+    /// Records that `channel` is final up to `position`, then drops chunks that end at or
+    /// before the lowest position across channels. A chunk that straddles it is kept, so
+    /// replay may repeat a fraction of a chunk but never skips audio.
+    pub fn ack(&mut self, channel: usize, position: u64) {
+        let Some(acked) = self.acked.get_mut(channel) else {
+            return;
+        };
+        *acked = (*acked).max(position);
+
+        let position = self.acked.iter().copied().min().unwrap_or(0);
+        while let Some(front) = self.chunks.front() {
+            if self.start + front.len() as u64 > position {
+                break;
+            }
+            self.pop_front();
+        }
+    }
+
+    /// Stream position of the first buffered byte.
+    pub fn start(&self) -> u64 {
+        self.start
+    }
+
+    /// Total bytes received so far.
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+
+    pub fn chunks(&self) -> impl Iterator<Item = &[u8]> {
+        self.chunks.iter().map(Vec::as_slice)
+    }
+
+    fn pop_front(&mut self) {
+        if let Some(chunk) = self.chunks.pop_front() {
+            self.start += chunk.len() as u64;
+            self.bytes -= chunk.len();
+        }
+    }
+
+    #[cfg(test)]
+    pub fn total_bytes(&self) -> usize {
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ack_drops_confirmed_chunks() {
+        let mut buffer = ReplayBuffer::new(1);
+        buffer.push(vec![0; 100]);
+        buffer.push(vec![1; 100]);
+        buffer.push(vec![2; 100]);
+
+        buffer.ack(0, 100);
+        assert_eq!(buffer.start(), 100);
+        assert_eq!(buffer.total_bytes(), 200);
+
+        buffer.ack(0, 150);
+        assert_eq!(buffer.start(), 100);
+        assert_eq!(buffer.chunks().next().unwrap()[0], 1);
+
+        buffer.ack(0, 300);
+        assert_eq!(buffer.start(), 300);
+        assert_eq!(buffer.end(), 300);
+        assert_eq!(buffer.chunks().count(), 0);
+    }
+
+    #[test]
+    fn test_ack_waits_for_the_lagging_channel() {
+        let mut buffer = ReplayBuffer::new(2);
+        for i in 0..4 {
+            buffer.push(vec![i; 100]);
+        }
+
+        buffer.ack(0, 400);
+        assert_eq!(buffer.start(), 0);
+
+        buffer.ack(1, 200);
+        assert_eq!(buffer.start(), 200);
+
+        // A late result for the leading channel does not move its ack back.
+        buffer.ack(0, 100);
+        buffer.ack(1, 400);
+        assert_eq!(buffer.start(), 400);
+    }
+
+    #[test]
+    fn test_push_respects_pending_limit() {
+        let mut buffer = ReplayBuffer::new(1);
+        let chunk = MAX_PENDING_QUEUE_BYTES / 4;
+
+        for _ in 0..6 {
+            buffer.push(vec![0; chunk]);
+        }
+
+        assert!(buffer.total_bytes() <= MAX_PENDING_QUEUE_BYTES);
+        assert_eq!(buffer.start(), (chunk * 2) as u64);
+        assert_eq!(buffer.end(), (chunk * 6) as u64);
+    }
+}
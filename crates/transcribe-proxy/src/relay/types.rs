@@ -12,6 +12,13 @@ pub const DEFAULT_CLOSE_CODE: u16 = 1011;
 
 pub type OnCloseCallback =
     Arc<dyn Fn(Duration) -> Pin<Box<dyn Future<Output = ()> + Send>> + Send + Sync>;
+/// Like [`OnCloseCallback`], for relays that may switch providers mid-session; called once
+/// per provider with how long it served.
+pub type ProviderOnCloseCallback = Arc<
+    dyn Fn(owhisper_client::Provider, Duration) -> Pin<Box<dyn Future<Output = ()> + Send>>
+        + Send
+        + Sync,
+>;
 pub type ControlMessageTypes = Arc<HashSet<&'static str>>;
 pub type FirstMessageTransformer = Arc<dyn Fn(String) -> String + Send + Sync>;
 pub type InitialMessage = Arc<String>;
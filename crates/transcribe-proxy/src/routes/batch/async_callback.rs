@@ -61,6 +61,7 @@ pub(super) async fn handle_callback(
     let (status, provider_request_id, raw_result, error) = if is_local {
         handle_sync_fallback(
             state,
+            params,
             &provider_str,
             provider,
             &listen_params,
@@ -69,6 +70,7 @@ pub(super) async fn handle_callback(
         )
         .await?
     } else {
+        // Both callback providers are asked to identify the language themselves.
         let provider_request_id =
             handle_remote_callback(state, &provider_str, provider, &audio_url, &id).await?;
         (
@@ -104,6 +106,7 @@ pub(super) async fn handle_callback(
 
 async fn handle_sync_fallback(
     state: &AppState,
+    params: &QueryParams,
     provider_str: &str,
     provider: Provider,
     listen_params: &ListenParams,
@@ -160,19 +163,25 @@ async fn handle_sync_fallback(
         "sync_fallback_audio_downloaded"
     );
 
+    let mut listen_params = listen_params.clone();
+    let spoken = super::sync::identify_language(
+        state,
+        params,
+        &listen_params.languages,
+        &audio_bytes,
+        content_type,
+    )
+    .await;
+    listen_params.languages = spoken.languages;
+
     let selected = state
         .config
         .provider_selector()
         .select(Some(provider))
         .map_err(|_| RouteError::MissingConfig("api_key not configured for provider"))?;
 
-    match super::sync::transcribe_with_provider(
-        &selected,
-        listen_params.clone(),
-        audio_bytes,
-        content_type,
-    )
-    .await
+    match super::sync::transcribe_with_provider(&selected, listen_params, audio_bytes, content_type)
+        .await
     {
         Ok(response) => {
             let raw_result = serde_json::to_value(&response)
@@ -100,18 +100,23 @@ pub(super) fn build_listen_params(params: &QueryParams) -> ListenParams {
     }
 }
 
-fn write_to_temp_file(
-    bytes: &Bytes,
+/// Spools the upload to a file for the provider clients, off the async runtime.
+async fn write_to_temp_file(
+    bytes: Bytes,
     content_type: &str,
 ) -> Result<tempfile::NamedTempFile, std::io::Error> {
     let extension = content_type_to_extension(content_type);
-    let mut temp_file = tempfile::Builder::new()
-        .prefix("batch_audio_")
-        .suffix(&format!(".{}", extension))
-        .tempfile()?;
-
-    temp_file.write_all(bytes)?;
-    temp_file.flush()?;
-
-    Ok(temp_file)
+    tokio::task::spawn_blocking(move || {
+        let mut temp_file = tempfile::Builder::new()
+            .prefix("batch_audio_")
+            .suffix(&format!(".{}", extension))
+            .tempfile()?;
+
+        temp_file.write_all(&bytes)?;
+        temp_file.flush()?;
+
+        Ok(temp_file)
+    })
+    .await
+    .map_err(std::io::Error::other)?
 }
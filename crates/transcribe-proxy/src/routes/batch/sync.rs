@@ -7,6 +7,7 @@ use axum::{
     response::{IntoResponse, Response},
 };
 use backon::{ExponentialBuilder, Retryable};
+use hypr_language::{Language, SpokenLanguage};
 use owhisper_client::{
     AssemblyAIAdapter, BatchClient, DeepgramAdapter, ElevenLabsAdapter, FireworksAdapter,
     GladiaAdapter, MistralAdapter, OpenAIAdapter, Provider, SonioxAdapter,
@@ -15,6 +16,7 @@ use owhisper_interface::ListenParams;
 use owhisper_interface::batch::Response as BatchResponse;
 
 use crate::hyprnote_routing::{RetryConfig, RoutingMode};
+use crate::language_id::{detection_enabled, identify_file_language};
 use crate::provider_selector::SelectedProvider;
 use crate::query_params::QueryParams;
 
@@ -61,6 +63,9 @@ impl std::fmt::Display for BatchAttemptError {
 struct BatchRoutingTrace {
     request_model: Option<String>,
     request_languages: Vec<String>,
+    routed_languages: Vec<String>,
+    language_source: &'static str,
+    language_confidence: Option<f32>,
     provider_chain: Vec<String>,
     attempts: Vec<BatchRoutingAttempt>,
     outcome: String,
@@ -100,11 +105,17 @@ fn resolve_listen_params_for_provider(
 pub(super) async fn handle_hyprnote_batch(
     state: &AppState,
     params: &QueryParams,
-    listen_params: ListenParams,
+    mut listen_params: ListenParams,
     body: Bytes,
     content_type: &str,
 ) -> Response {
-    let provider_chain = state.resolve_hyprnote_provider_chain_for_mode(RoutingMode::Batch, params);
+    let request_languages = language_codes(&listen_params.languages);
+    let spoken =
+        identify_language(state, params, &listen_params.languages, &body, content_type).await;
+    listen_params.languages = spoken.languages;
+
+    let provider_chain = state
+        .resolve_hyprnote_provider_chain_for_mode(RoutingMode::Batch, &listen_params.languages);
 
     if provider_chain.is_empty() {
         return (
@@ -134,11 +145,10 @@ pub(super) async fn handle_hyprnote_batch(
     let mut providers_tried = Vec::new();
     let mut trace = BatchRoutingTrace {
         request_model: listen_params.model.clone(),
-        request_languages: listen_params
-            .languages
-            .iter()
-            .map(|lang| lang.iso639().code().to_string())
-            .collect(),
+        request_languages,
+        routed_languages: language_codes(&listen_params.languages),
+        language_source: spoken.source.as_str(),
+        language_confidence: spoken.confidence,
         provider_chain: provider_chain
             .iter()
             .map(|selected| selected.provider().to_string())
@@ -212,6 +222,36 @@ pub(super) async fn handle_hyprnote_batch(
         .into_response()
 }
 
+pub(super) async fn identify_language(
+    state: &AppState,
+    params: &QueryParams,
+    declared: &[Language],
+    body: &Bytes,
+    content_type: &str,
+) -> SpokenLanguage {
+    let Some(detector) = state.config.language_detector.clone() else {
+        return SpokenLanguage::declared(declared);
+    };
+    if !detection_enabled(params) {
+        return SpokenLanguage::declared(declared);
+    }
+
+    match write_to_temp_file(body.clone(), content_type).await {
+        Ok(temp_file) => identify_file_language(detector, temp_file.path(), declared).await,
+        Err(e) => {
+            tracing::warn!(error.message = %e, "language_detection_failed");
+            SpokenLanguage::declared(declared)
+        }
+    }
+}
+
+fn language_codes(languages: &[Language]) -> Vec<String> {
+    languages
+        .iter()
+        .map(|lang| lang.iso639().code().to_string())
+        .collect()
+}
+
 async fn transcribe_with_retry(
     selected: &SelectedProvider,
     params: ListenParams,
@@ -253,7 +293,8 @@ pub(super) async fn transcribe_with_provider(
     audio_bytes: Bytes,
     content_type: &str,
 ) -> Result<BatchResponse, BatchAttemptError> {
-    let temp_file = write_to_temp_file(&audio_bytes, content_type)
+    let temp_file = write_to_temp_file(audio_bytes, content_type)
+        .await
         .map_err(|e| BatchAttemptError::Client(format!("failed to create temp file: {e}")))?;
 
     let file_path = temp_file.path();
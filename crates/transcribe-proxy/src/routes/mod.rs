@@ -14,6 +14,7 @@ use axum::{
     response::{IntoResponse, Response},
     routing::{get, post},
 };
+use hypr_language::Language;
 use owhisper_client::Provider;
 
 use crate::config::SttProxyConfig;
@@ -128,17 +129,16 @@ impl AppState {
     pub fn resolve_hyprnote_provider_chain_for_mode(
         &self,
         mode: RoutingMode,
-        params: &QueryParams,
+        languages: &[Language],
     ) -> Vec<SelectedProvider> {
         let Some(router) = self.router.as_ref() else {
             return vec![];
         };
 
-        let languages = params.get_languages();
         let available_providers = self.selector.available_providers();
 
         router
-            .select_provider_chain_with_mode(mode, &languages, &available_providers)
+            .select_provider_chain_with_mode(mode, languages, &available_providers)
             .into_iter()
             .filter_map(|p| self.selector.select(Some(p)).ok())
             .collect()
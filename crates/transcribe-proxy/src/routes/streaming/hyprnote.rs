@@ -7,16 +7,24 @@ use owhisper_client::{
 };
 use owhisper_interface::ListenParams;
 
+use hypr_language::Language;
+
 use crate::config::SttProxyConfig;
+use crate::hyprnote_routing::RoutingMode;
+use crate::language_id::detection_enabled;
 use crate::provider_selector::SelectedProvider;
 use crate::query_params::{QueryParams, QueryValue};
-use crate::relay::{ChannelSplitProxy, ClientMessageFilter, WebSocketProxy};
+use crate::relay::{
+    ChannelSplitProxy, ClientMessageFilter, FailoverProxy, LanguageRouting, UpstreamResolver,
+    WebSocketProxy,
+};
 use crate::routes::AppState;
 use crate::routes::model_resolution::resolve_model_live;
 
 use super::AnalyticsContext;
 use super::common::{
-    ProxyBuildError, build_on_close_callback, finalize_proxy_builder, parse_param,
+    ProxyBuildError, build_on_close_callback, build_provider_on_close_callback,
+    finalize_proxy_builder, parse_param,
 };
 use super::session::init_session;
 
@@ -116,6 +124,7 @@ fn build_client_message_filter(provider: Provider) -> ClientMessageFilter {
 pub enum StreamingProxy {
     Single(WebSocketProxy),
     ChannelSplit(ChannelSplitProxy),
+    Failover(FailoverProxy),
 }
 
 fn build_proxy_with_adapter(
@@ -279,6 +288,117 @@ pub async fn build_proxy(
     selected: &SelectedProvider,
     params: &QueryParams,
     analytics_ctx: AnalyticsContext,
+) -> Result<StreamingProxy, ProxyBuildError> {
+    let primary = build_primary_proxy(state, selected, params, analytics_ctx.clone()).await?;
+    Ok(with_failover(
+        state,
+        selected,
+        params,
+        &analytics_ctx,
+        primary,
+    ))
+}
+
+/// Wraps a single-upstream session so it can continue on the next providers of the routing
+/// chain, and re-route by the spoken language once the first seconds are in. Channel-split
+/// sessions and providers that need a session-init call per connection are not failover
+/// targets.
+fn with_failover(
+    state: &AppState,
+    selected: &SelectedProvider,
+    params: &QueryParams,
+    analytics_ctx: &AnalyticsContext,
+    primary: StreamingProxy,
+) -> StreamingProxy {
+    let StreamingProxy::Single(primary) = primary else {
+        return primary;
+    };
+
+    let language_routing = state
+        .config
+        .language_detector
+        .clone()
+        .filter(|_| detection_enabled(params))
+        .map(|detector| LanguageRouting {
+            detector,
+            declared: params.get_languages(),
+            resolve: upstream_resolver(state, params),
+        });
+
+    let fallbacks: Vec<(Provider, WebSocketProxy)> = chain_upstreams(state, params)
+        .into_iter()
+        .filter(|(provider, _)| *provider != selected.provider())
+        .collect();
+
+    if fallbacks.is_empty() && language_routing.is_none() {
+        return StreamingProxy::Single(primary);
+    }
+
+    let mut upstreams = vec![(selected.provider(), primary)];
+    upstreams.extend(fallbacks);
+
+    let proxy = FailoverProxy::new(
+        upstreams,
+        parse_param(params, "sample_rate", 16000),
+        parse_param(params, "channels", 1),
+        build_provider_on_close_callback(&state.config, analytics_ctx),
+    );
+    StreamingProxy::Failover(match language_routing {
+        Some(routing) => proxy.with_language_routing(routing),
+        None => proxy,
+    })
+}
+
+/// Proxies for the live routing chain of `params`' languages, skipping providers that can't
+/// be connected without a session-init call.
+fn chain_upstreams(state: &AppState, params: &QueryParams) -> Vec<(Provider, WebSocketProxy)> {
+    state
+        .resolve_hyprnote_provider_chain_for_mode(RoutingMode::Live, &params.get_languages())
+        .iter()
+        .filter(|selected| {
+            selected.upstream_url().is_some()
+                || !matches!(selected.provider().auth(), Auth::SessionInit { .. })
+        })
+        .filter_map(|selected| {
+            let api_base = selected
+                .upstream_url()
+                .unwrap_or(selected.provider().default_api_base());
+            match build_proxy_with_adapter(
+                selected,
+                params,
+                &state.config,
+                api_base,
+                AnalyticsContext {
+                    fingerprint: None,
+                    user_id: None,
+                },
+            ) {
+                Ok(StreamingProxy::Single(proxy)) => Some((selected.provider(), proxy)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn upstream_resolver(state: &AppState, params: &QueryParams) -> UpstreamResolver {
+    let state = state.clone();
+    let params = params.clone();
+    Arc::new(move |languages: &[Language]| {
+        let mut params = params.clone();
+        params.remove("languages");
+        params.insert(
+            "language".to_string(),
+            QueryValue::Multi(languages.iter().map(Language::bcp47_code).collect()),
+        );
+        chain_upstreams(&state, &params)
+    })
+}
+
+async fn build_primary_proxy(
+    state: &AppState,
+    selected: &SelectedProvider,
+    params: &QueryParams,
+    analytics_ctx: AnalyticsContext,
 ) -> Result<StreamingProxy, ProxyBuildError> {
     let provider = selected.provider();
     let api_base = selected
@@ -19,6 +19,7 @@ use common::{ProxyBuildError, parse_param};
 
 use hypr_analytics::{AuthenticatedUserId, DeviceFingerprint};
 
+#[derive(Clone)]
 pub struct AnalyticsContext {
     pub fingerprint: Option<String>,
     pub user_id: Option<String>,
@@ -206,5 +207,6 @@ pub async fn handler(
     match proxy {
         hyprnote::StreamingProxy::Single(p) => p.handle_upgrade(ws).await.into_response(),
         hyprnote::StreamingProxy::ChannelSplit(p) => p.handle_upgrade(ws).await.into_response(),
+        hyprnote::StreamingProxy::Failover(p) => p.handle_upgrade(ws).await.into_response(),
     }
 }
@@ -0,0 +1,205 @@
+mod common;
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use axum::Router;
+use axum::extract::WebSocketUpgrade;
+use axum::routing::get;
+use futures_util::{SinkExt, StreamExt};
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::Message;
+
+use common::{
+    Direction, MockUpstreamConfig, WsMessage, WsRecording, start_mock_server_with_config,
+};
+use owhisper_client::Provider;
+use transcribe_proxy::{FailoverProxy, WebSocketProxy};
+
+const SAMPLE_RATE: u32 = 16000;
+const CHANNELS: u8 = 2;
+const CHUNK_BYTES: usize = 6400; // 100ms of 16-bit stereo audio
+const TEST_RESPONSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn final_result(channel: i32, start: f64, duration: f64, transcript: &str) -> String {
+    serde_json::json!({
+        "type": "Results",
+        "start": start,
+        "duration": duration,
+        "is_final": true,
+        "speech_final": true,
+        "from_finalize": false,
+        "channel_index": [channel, CHANNELS],
+        "channel": {
+            "alternatives": [{
+                "transcript": transcript,
+                "confidence": 0.9,
+                "words": [{
+                    "word": transcript,
+                    "start": start,
+                    "end": start + duration,
+                    "confidence": 0.9,
+                    "speaker": null,
+                    "punctuated_word": transcript,
+                    "language": null
+                }]
+            }]
+        },
+        "metadata": {
+            "request_id": "r",
+            "model_uuid": "m",
+            "model_info": { "name": "", "version": "", "arch": "" }
+        }
+    })
+    .to_string()
+}
+
+fn recording(results: &[String], close: bool) -> WsRecording {
+    let mut recording = WsRecording::default();
+    for result in results {
+        recording.push(WsMessage::text(Direction::ServerToClient, 500, result));
+    }
+    if close {
+        recording.push(WsMessage::close(
+            Direction::ServerToClient,
+            500,
+            1000,
+            "done",
+        ));
+    }
+    recording
+}
+
+async fn start_failover_proxy(upstreams: Vec<(Provider, String)>) -> SocketAddr {
+    let upstreams = upstreams
+        .into_iter()
+        .map(|(provider, url)| {
+            let proxy = WebSocketProxy::builder()
+                .upstream_url(url)
+                .build()
+                .expect("Failed to build upstream proxy");
+            (provider, proxy)
+        })
+        .collect();
+    let proxy = FailoverProxy::new(upstreams, SAMPLE_RATE, CHANNELS, None);
+
+    let app = Router::new().route(
+        "/listen",
+        get(move |ws: WebSocketUpgrade| {
+            let proxy = proxy.clone();
+            async move { proxy.handle_upgrade(ws).await }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    addr
+}
+
+#[tokio::test]
+async fn test_failover_replays_unfinalized_audio_without_duplicates() {
+    let _ = tracing_subscriber::fmt::try_init();
+
+    // The primary finalizes 1.0s on channel 0 but only 0.5s on channel 1, then drops the
+    // connection without a close frame.
+    let primary = start_mock_server_with_config(
+        recording(
+            &[
+                final_result(0, 0.0, 1.0, "hello world"),
+                final_result(1, 0.0, 0.5, "hi"),
+            ],
+            false,
+        ),
+        MockUpstreamConfig::default().use_timing(true),
+    )
+    .await
+    .expect("Failed to start primary mock server");
+
+    // Replay starts at 0.5s, so the fallback sees channel 0's "world" again.
+    let fallback = start_mock_server_with_config(
+        recording(
+            &[
+                final_result(0, 0.0, 0.5, "world"),
+                final_result(1, 0.0, 0.5, "there"),
+                final_result(0, 0.5, 0.3, "again"),
+            ],
+            true,
+        ),
+        MockUpstreamConfig::default().use_timing(true),
+    )
+    .await
+    .expect("Failed to start fallback mock server");
+
+    let proxy_addr = start_failover_proxy(vec![
+        (Provider::Deepgram, primary.ws_url()),
+        (Provider::Soniox, fallback.ws_url()),
+    ])
+    .await;
+
+    let (ws_stream, _) = connect_async(format!("ws://{}/listen", proxy_addr))
+        .await
+        .expect("Failed to connect to proxy");
+    let (mut sender, mut receiver) = ws_stream.split();
+
+    for _ in 0..15 {
+        sender
+            .send(Message::Binary(vec![0u8; CHUNK_BYTES].into()))
+            .await
+            .unwrap();
+    }
+
+    let mut results: Vec<serde_json::Value> = Vec::new();
+    let mut close_code = None;
+    let collect = async {
+        while let Some(Ok(msg)) = receiver.next().await {
+            match msg {
+                Message::Text(text) => results.push(serde_json::from_str(&text).unwrap()),
+                Message::Close(frame) => {
+                    close_code = frame.map(|f| u16::from(f.code));
+                    break;
+                }
+                _ => {}
+            }
+        }
+    };
+    let _ = tokio::time::timeout(TEST_RESPONSE_TIMEOUT, collect).await;
+
+    assert_eq!(close_code, Some(1000));
+
+    for (channel, expected) in [
+        (0, vec![("hello world", "deepgram"), ("again", "soniox")]),
+        (1, vec![("hi", "deepgram"), ("there", "soniox")]),
+    ] {
+        let finals: Vec<&serde_json::Value> = results
+            .iter()
+            .filter(|r| r["is_final"] == true && r["channel_index"][0] == channel)
+            .collect();
+
+        let transcripts: Vec<(&str, &str)> = finals
+            .iter()
+            .map(|r| {
+                (
+                    r["channel"]["alternatives"][0]["transcript"]
+                        .as_str()
+                        .unwrap(),
+                    r["metadata"]["extra"]["provider"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(transcripts, expected, "channel {channel}");
+
+        let mut end = 0.0;
+        for result in finals {
+            let start = result["start"].as_f64().unwrap();
+            assert!(
+                (start - end).abs() < 1e-6,
+                "channel {channel}: result starts at {start}, previous ended at {end}"
+            );
+            end = start + result["duration"].as_f64().unwrap();
+        }
+    }
+}
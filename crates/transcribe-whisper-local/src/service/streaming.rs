@@ -48,6 +48,11 @@ impl TranscribeServiceBuilder {
         self
     }
 
+    pub fn connection_manager(mut self, connection_manager: ConnectionManager) -> Self {
+        self.connection_manager = Some(connection_manager);
+        self
+    }
+
     pub fn build(self) -> TranscribeService {
         TranscribeService {
             model_path: self.model_path.unwrap(),
@@ -91,6 +96,13 @@ where
                 }
             };
 
+            // Taken before loading the model, so a refused session costs nothing.
+            let Some(guard) = connection_manager.acquire_connection() else {
+                return Ok(
+                    (StatusCode::SERVICE_UNAVAILABLE, "too_many_connections").into_response()
+                );
+            };
+
             let model = match hypr_whisper_local::Whisper::builder()
                 .model_path(model_path.to_str().unwrap())
                 .languages(
@@ -113,8 +125,6 @@ where
                 }
             };
 
-            let guard = connection_manager.acquire_connection();
-
             Ok(ws_upgrade
                 .on_upgrade(move |socket| async move {
                     handle_websocket_connection(socket, params, model, guard).await;
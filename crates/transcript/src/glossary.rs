@@ -0,0 +1,487 @@
+use hypr_calendar_interface::{AttendeeStatus, CalendarEvent};
+
+/// Keyword lists get truncated by several providers, so the glossary stays well below their
+/// limits and leaves room for the user's own keywords.
+const MAX_TERMS: usize = 30;
+
+/// Longer capitalized runs are usually headings or sentences in title case, not a single name.
+const MAX_PHRASE_WORDS: usize = 3;
+
+/// Personal mail providers and calendar resources, whose domains say nothing about a company.
+const GENERIC_DOMAINS: &[&str] = &[
+    "gmail.com",
+    "googlemail.com",
+    "outlook.com",
+    "hotmail.com",
+    "live.com",
+    "msn.com",
+    "yahoo.com",
+    "icloud.com",
+    "me.com",
+    "mac.com",
+    "aol.com",
+    "proton.me",
+    "protonmail.com",
+    "gmx.com",
+    "gmx.de",
+    "naver.com",
+    "daum.net",
+    "qq.com",
+    "163.com",
+    "calendar.google.com",
+];
+
+/// Labels that sit between a company name and the country code, as in "acme.co.uk".
+const SECOND_LEVEL_LABELS: &[&str] = &["co", "com", "org", "net", "ac", "gov", "edu", "ne", "or"];
+
+/// Capitalized words that show up in invites without being worth transcribing specially.
+const STOPWORDS: &[&str] = &[
+    "a",
+    "am",
+    "agenda",
+    "an",
+    "and",
+    "april",
+    "august",
+    "call",
+    "calendar",
+    "click",
+    "daily",
+    "december",
+    "dial",
+    "february",
+    "for",
+    "friday",
+    "fyi",
+    "gmt",
+    "hello",
+    "hi",
+    "i",
+    "id",
+    "invitation",
+    "january",
+    "join",
+    "july",
+    "june",
+    "link",
+    "march",
+    "may",
+    "meet",
+    "meeting",
+    "monday",
+    "more",
+    "notes",
+    "november",
+    "october",
+    "of",
+    "on",
+    "or",
+    "passcode",
+    "password",
+    "phone",
+    "pin",
+    "please",
+    "pm",
+    "re",
+    "reply",
+    "saturday",
+    "september",
+    "sunday",
+    "sync",
+    "tbd",
+    "teams",
+    "thanks",
+    "the",
+    "thursday",
+    "to",
+    "tuesday",
+    "url",
+    "utc",
+    "wednesday",
+    "weekly",
+    "with",
+    "zoom",
+];
+
+/// What is known about a meeting before recording starts, used to bias transcription toward
+/// the people and terms likely to come up.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct MeetingContext {
+    pub title: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub participants: Vec<MeetingParticipant>,
+}
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct MeetingParticipant {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
+impl From<&CalendarEvent> for MeetingContext {
+    fn from(event: &CalendarEvent) -> Self {
+        let organizer = event.organizer.iter().map(|p| MeetingParticipant {
+            name: p.name.clone(),
+            email: p.email.clone(),
+        });
+        let attendees = event
+            .attendees
+            .iter()
+            .filter(|a| a.status != AttendeeStatus::Declined)
+            .map(|a| MeetingParticipant {
+                name: a.name.clone(),
+                email: a.email.clone(),
+            });
+
+        Self {
+            title: event.title.clone(),
+            description: event.description.clone(),
+            participants: organizer.chain(attendees).collect(),
+        }
+    }
+}
+
+impl MeetingContext {
+    /// Reads a saved context, or a calendar event as the calendar plugin returns it.
+    pub fn from_json(json: &[u8]) -> serde_json::Result<Self> {
+        match serde_json::from_slice::<CalendarEvent>(json) {
+            Ok(event) => Ok(Self::from(&event)),
+            Err(_) => serde_json::from_slice(json),
+        }
+    }
+
+    /// Terms likely to be spoken in the meeting, most reliable first: participant names, company
+    /// names from their email domains, then capitalized terms from the title and description.
+    pub fn glossary(&self) -> Vec<String> {
+        let mut glossary = Glossary::default();
+
+        for participant in &self.participants {
+            for term in participant.name_terms() {
+                glossary.push(term);
+            }
+        }
+        for participant in &self.participants {
+            if let Some(company) = participant.email.as_deref().and_then(company_from_email) {
+                glossary.push(company);
+            }
+        }
+
+        let description = self.description.as_deref().map(strip_markup);
+        for text in std::iter::once(self.title.as_str()).chain(description.as_deref()) {
+            for term in capitalized_terms(text) {
+                glossary.push(term);
+            }
+        }
+
+        glossary.terms
+    }
+
+    /// Prepends the glossary to `keywords`, dropping duplicates.
+    pub fn with_keywords(&self, keywords: &[String]) -> Vec<String> {
+        let mut glossary = Glossary {
+            terms: self.glossary(),
+        };
+        for keyword in keywords {
+            glossary.push_unbounded(keyword.clone());
+        }
+        glossary.terms
+    }
+}
+
+impl MeetingParticipant {
+    /// The full name and, for multi-part names, the first name on its own, since that is how
+    /// people are usually addressed. Falls back to a name spelled out in the email address.
+    fn name_terms(&self) -> Vec<String> {
+        let name = self
+            .name
+            .as_deref()
+            .filter(|name| !name.contains('@'))
+            .map(strip_parenthesized)
+            .filter(|name| !name.trim().is_empty())
+            .or_else(|| self.email.as_deref().and_then(name_from_email));
+
+        let Some(name) = name else {
+            return vec![];
+        };
+
+        let parts: Vec<&str> = name.split_whitespace().collect();
+        let mut terms = vec![parts.join(" ")];
+        if parts.len() > 1 && parts[0].chars().count() > 1 {
+            terms.push(parts[0].to_string());
+        }
+        terms
+    }
+}
+
+#[derive(Default)]
+struct Glossary {
+    terms: Vec<String>,
+}
+
+impl Glossary {
+    fn push(&mut self, term: String) {
+        if self.terms.len() < MAX_TERMS {
+            self.push_unbounded(term);
+        }
+    }
+
+    fn push_unbounded(&mut self, term: String) {
+        let term = term.trim();
+        if term.chars().count() < 2 || self.terms.iter().any(|t| t.eq_ignore_ascii_case(term)) {
+            return;
+        }
+        self.terms.push(term.to_string());
+    }
+}
+
+fn name_from_email(email: &str) -> Option<String> {
+    let (local, _) = email.split_once('@')?;
+    let parts: Vec<&str> = local.split(['.', '_']).collect();
+    if parts.len() < 2
+        || !parts
+            .iter()
+            .all(|p| p.len() > 1 && p.chars().all(char::is_alphabetic))
+    {
+        return None;
+    }
+
+    Some(
+        parts
+            .iter()
+            .map(|p| capitalize(p))
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+fn company_from_email(email: &str) -> Option<String> {
+    let (_, domain) = email.rsplit_once('@')?;
+    let domain = domain.trim().to_lowercase();
+    if GENERIC_DOMAINS
+        .iter()
+        .any(|generic| domain == *generic || domain.ends_with(&format!(".{generic}")))
+    {
+        return None;
+    }
+
+    let mut labels: Vec<&str> = domain.split('.').collect();
+    labels.pop()?;
+    if labels.len() > 1 && SECOND_LEVEL_LABELS.contains(labels.last()?) {
+        labels.pop();
+    }
+
+    let company = labels.pop()?;
+    (company.chars().count() > 1 && company.chars().any(char::is_alphabetic))
+        .then(|| capitalize(company))
+}
+
+/// Capitalized words that are not just starting a sentence, plus acronyms and mixed-case terms
+/// anywhere. Adjacent ones are kept together, so "Project Phoenix" stays one term.
+fn capitalized_terms(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+
+    for line in text.lines() {
+        let mut sentence_start = true;
+        let mut phrase: Vec<&str> = Vec::new();
+
+        for token in line.split_whitespace() {
+            let word = token.trim_matches(|c: char| !c.is_alphanumeric());
+            let breaks = word.is_empty() || !token.ends_with(word);
+
+            if is_term(token, word, sentence_start) {
+                phrase.push(word);
+            } else {
+                flush_phrase(&mut phrase, &mut terms);
+            }
+            if breaks {
+                flush_phrase(&mut phrase, &mut terms);
+            }
+
+            sentence_start = token.ends_with(['.', '!', '?']);
+        }
+        flush_phrase(&mut phrase, &mut terms);
+    }
+
+    terms
+}
+
+fn is_term(token: &str, word: &str, sentence_start: bool) -> bool {
+    if token.contains("://") || token.contains('@') || token.starts_with("www.") {
+        return false;
+    }
+    if !word.chars().any(char::is_alphabetic) || word.chars().all(char::is_numeric) {
+        return false;
+    }
+    if STOPWORDS.contains(&word.to_lowercase().as_str()) {
+        return false;
+    }
+
+    let mut chars = word.chars();
+    let first_upper = chars.next().is_some_and(char::is_uppercase);
+    let inner_upper = chars.any(char::is_uppercase);
+
+    if inner_upper {
+        word.chars().count() >= 2
+    } else {
+        first_upper && !sentence_start && word.chars().count() >= 3
+    }
+}
+
+fn flush_phrase(phrase: &mut Vec<&str>, terms: &mut Vec<String>) {
+    if phrase.len() > MAX_PHRASE_WORDS {
+        terms.extend(phrase.iter().map(|w| w.to_string()));
+    } else if !phrase.is_empty() {
+        terms.push(phrase.join(" "));
+    }
+    phrase.clear();
+}
+
+/// Calendar descriptions are often HTML; tags and entities would otherwise look like terms.
+fn strip_markup(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_tag = false;
+
+    for c in text.chars() {
+        match c {
+            '<' => {
+                in_tag = true;
+                out.push('\n');
+            }
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+
+    out.replace("&nbsp;", " ").replace("&amp;", "&")
+}
+
+fn strip_parenthesized(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    let mut depth = 0usize;
+
+    for c in name.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth = depth.saturating_sub(1),
+            _ if depth == 0 => out.push(c),
+            _ => {}
+        }
+    }
+
+    out
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn participant(name: Option<&str>, email: Option<&str>) -> MeetingParticipant {
+        MeetingParticipant {
+            name: name.map(str::to_string),
+            email: email.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_glossary_from_meeting() {
+        let context = MeetingContext {
+            title: "Acme x Hyprnote: Project Phoenix kickoff".to_string(),
+            description: Some(
+                "<p>Walk through the GraphQL migration with Yujong.</p>\
+                 <p>Join Zoom Meeting https://zoom.us/j/123</p>"
+                    .to_string(),
+            ),
+            participants: vec![
+                participant(Some("Yujong Lee (Fastrepl)"), Some("yujong@fastrepl.com")),
+                participant(None, Some("jane.doe@acme.co.uk")),
+                participant(Some("room@resource.calendar.google.com"), None),
+                participant(Some("Sam"), Some("sam@gmail.com")),
+            ],
+        };
+
+        assert_eq!(
+            context.glossary(),
+            [
+                "Yujong Lee",
+                "Yujong",
+                "Jane Doe",
+                "Jane",
+                "Sam",
+                "Fastrepl",
+                "Acme",
+                "Hyprnote",
+                "Project Phoenix",
+                "GraphQL",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_with_keywords_puts_glossary_first() {
+        let context = MeetingContext {
+            title: "Weekly sync".to_string(),
+            participants: vec![participant(Some("Jane Doe"), None)],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            context.with_keywords(&["jane".to_string(), "roadmap".to_string()]),
+            ["Jane Doe", "Jane", "roadmap"]
+        );
+    }
+
+    #[test]
+    fn test_from_json_reads_calendar_events_and_saved_contexts() {
+        let event = serde_json::json!({
+            "provider": "google",
+            "id": "e1",
+            "calendar_id": "c1",
+            "external_id": "x1",
+            "title": "Roadmap review",
+            "description": null,
+            "location": null,
+            "url": null,
+            "meeting_link": null,
+            "meeting": null,
+            "started_at": "2025-01-01T10:00:00Z",
+            "ended_at": "2025-01-01T11:00:00Z",
+            "timezone": null,
+            "is_all_day": false,
+            "status": "confirmed",
+            "organizer": { "name": "Jane Doe", "email": null, "is_current_user": false },
+            "attendees": [
+                {
+                    "name": "Sam Park",
+                    "email": null,
+                    "is_current_user": false,
+                    "status": "declined",
+                    "role": "required"
+                }
+            ],
+            "has_recurrence_rules": false,
+            "recurring_event_id": null,
+            "raw": "{}"
+        });
+        let context = MeetingContext::from_json(event.to_string().as_bytes()).unwrap();
+        assert_eq!(context.title, "Roadmap review");
+        assert_eq!(context.participants.len(), 1);
+        assert_eq!(context.participants[0].name.as_deref(), Some("Jane Doe"));
+
+        let saved = serde_json::to_vec(&context).unwrap();
+        let context = MeetingContext::from_json(&saved).unwrap();
+        assert_eq!(context.glossary(), ["Jane Doe", "Jane"]);
+    }
+}
@@ -1,12 +1,23 @@
 mod accumulator;
+mod glossary;
 mod postprocessor;
 mod processor;
+mod speakers;
+mod translator;
 mod types;
+mod vocabulary;
 mod words;
 
+pub use glossary::{MeetingContext, MeetingParticipant};
 pub use postprocessor::{
     TranscriptPostprocessor, TranscriptPostprocessorError, TranscriptPostprocessorRequest,
     TranscriptPostprocessorResult,
 };
 pub use processor::TranscriptProcessor;
+pub use speakers::{ActiveSpeakerEvent, SpeakerAssignment, SpeakerKey, SpeakerTimeline};
+pub use translator::{
+    TranscriptTranslator, TranscriptTranslatorError, TranscriptTranslatorRequest,
+    TranslatedSegment, TranslationBatch, TranslationDelta, TranslationOutcome, TranslationRange,
+};
 pub use types::{FinalizedWord, PartialWord, RawWord, SpeakerHint, TranscriptDelta, WordState};
+pub use vocabulary::VocabularyCorrector;
@@ -11,6 +11,7 @@ use crate::{FinalizedWord, WordState};
 #[derive(Debug, Clone)]
 pub struct TranscriptPostprocessor {
     language: Option<String>,
+    glossary: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -65,7 +66,10 @@ struct PatchEnvelope {
 
 impl TranscriptPostprocessor {
     pub fn new() -> Self {
-        Self { language: None }
+        Self {
+            language: None,
+            glossary: vec![],
+        }
     }
 
     pub fn with_language(mut self, language: impl Into<String>) -> Self {
@@ -73,6 +77,13 @@ impl TranscriptPostprocessor {
         self
     }
 
+    /// Spellings the model should prefer for names and terms, e.g. from
+    /// [`crate::MeetingContext::glossary`].
+    pub fn with_glossary(mut self, glossary: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.glossary = glossary.into_iter().map(Into::into).collect();
+        self
+    }
+
     pub fn build_request(
         &self,
         words: &[FinalizedWord],
@@ -83,6 +94,7 @@ impl TranscriptPostprocessor {
         let system_prompt =
             render_template(Template::TranscriptPatchSystem(TranscriptPatchSystem {
                 language: self.language.clone(),
+                glossary: self.glossary.clone(),
             }))?;
         let user_prompt = render_template(Template::TranscriptPatchUser(Box::new(
             TranscriptPatchUser {
@@ -175,7 +187,7 @@ fn parse_patch_envelope(content: &str) -> Result<PatchEnvelope, TranscriptPostpr
     serde_json::from_str(&normalized).map_err(TranscriptPostprocessorError::InvalidJson)
 }
 
-fn normalize_json_payload(content: &str) -> String {
+pub(crate) fn normalize_json_payload(content: &str) -> String {
     let trimmed = content.trim();
     let without_fences = strip_code_fences(trimmed);
     if let (Some(start), Some(end)) = (without_fences.find('{'), without_fences.rfind('}')) {
@@ -333,4 +345,14 @@ mod tests {
         assert!(request.user_prompt.contains("\"words\""));
         assert!(request.transcript_json.contains("\"helo\""));
     }
+
+    #[test]
+    fn includes_glossary_in_system_prompt() {
+        let request = TranscriptPostprocessor::new()
+            .with_glossary(["Yujong Lee"])
+            .build_request(&sample_words())
+            .unwrap();
+
+        assert!(request.system_prompt.contains("- Yujong Lee"));
+    }
 }
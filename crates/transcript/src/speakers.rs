@@ -0,0 +1,307 @@
+use std::collections::{HashMap, HashSet};
+
+use owhisper_interface::SpeakerIdentity;
+
+use crate::types::{FinalizedWord, SpeakerHint};
+
+/// Speaking time a name needs with a speaker before it is assigned.
+const MIN_OVERLAP_MS: f64 = 1_000.0;
+/// Share of a speaker's overlapped time the winning name must exceed.
+const MIN_SHARE: f64 = 0.5;
+
+/// An active-speaker change as the Chrome native host reports it: who is speaking from
+/// `timestamp_ms` (wall clock) until the next event.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct ActiveSpeakerEvent {
+    pub timestamp_ms: u64,
+    pub speakers: Vec<String>,
+}
+
+/// What a name is assigned to: a diarized speaker, or a whole channel for words without a
+/// speaker hint.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    serde::Serialize,
+    serde::Deserialize,
+    specta::Type,
+)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum SpeakerKey {
+    Index(i32),
+    Channel(i32),
+}
+
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct SpeakerAssignment {
+    pub key: SpeakerKey,
+    pub identity: SpeakerIdentity,
+    pub overlap_ms: i64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Turn {
+    start_ms: i64,
+    end_ms: i64,
+    speakers: Vec<String>,
+}
+
+/// Active-speaker turns on the transcript's clock, i.e. relative to when recording started.
+#[derive(Debug, Clone, Default)]
+pub struct SpeakerTimeline {
+    turns: Vec<Turn>,
+}
+
+impl SpeakerTimeline {
+    pub fn from_events(
+        events: impl IntoIterator<Item = ActiveSpeakerEvent>,
+        session_started_at_ms: u64,
+    ) -> Self {
+        let mut events: Vec<ActiveSpeakerEvent> = events.into_iter().collect();
+        events.sort_by_key(|e| e.timestamp_ms);
+
+        let relative = |ms: u64| ms as i64 - session_started_at_ms as i64;
+        let turns = events
+            .iter()
+            .enumerate()
+            .filter(|(_, event)| !event.speakers.is_empty())
+            .map(|(i, event)| Turn {
+                start_ms: relative(event.timestamp_ms),
+                end_ms: events
+                    .get(i + 1)
+                    .map_or(i64::MAX, |next| relative(next.timestamp_ms)),
+                speakers: event.speakers.clone(),
+            })
+            .filter(|turn| turn.end_ms > turn.start_ms)
+            .collect();
+
+        Self { turns }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.turns.is_empty()
+    }
+
+    /// Assigns diarized speakers (or channels, for words without a hint) to the participant
+    /// who was shown as speaking for most of their words.
+    ///
+    /// `human_ids` maps the names the meeting shows to human ids; names without one still
+    /// compete for speakers but are never assigned. Each name goes to at most one speaker.
+    /// Speakers whose time is split between several names with no clear majority, such as a
+    /// shared room microphone, stay unassigned.
+    pub fn assign(
+        &self,
+        words: &[FinalizedWord],
+        hints: &[SpeakerHint],
+        human_ids: &HashMap<String, String>,
+    ) -> Vec<SpeakerAssignment> {
+        let speaker_by_word: HashMap<&str, i32> = hints
+            .iter()
+            .map(|hint| (hint.word_id.as_str(), hint.speaker_index))
+            .collect();
+
+        let mut overlaps: HashMap<(SpeakerKey, &str), f64> = HashMap::new();
+        let mut totals: HashMap<SpeakerKey, f64> = HashMap::new();
+
+        for word in words {
+            let key = match speaker_by_word.get(word.id.as_str()) {
+                Some(&index) => SpeakerKey::Index(index),
+                None => SpeakerKey::Channel(word.channel),
+            };
+            let end_ms = word.end_ms.max(word.start_ms + 1);
+
+            for turn in &self.turns {
+                let overlap = end_ms.min(turn.end_ms) - word.start_ms.max(turn.start_ms);
+                if overlap <= 0 {
+                    continue;
+                }
+
+                // Overlapping talkers share the credit.
+                let share = overlap as f64 / turn.speakers.len() as f64;
+                for name in &turn.speakers {
+                    *overlaps.entry((key, name.as_str())).or_default() += share;
+                }
+                *totals.entry(key).or_default() += overlap as f64;
+            }
+        }
+
+        let mut candidates: Vec<((SpeakerKey, &str), f64)> = overlaps.into_iter().collect();
+        candidates.sort_by(|(a, a_ms), (b, b_ms)| b_ms.total_cmp(a_ms).then_with(|| a.cmp(b)));
+
+        let mut assigned_keys = HashSet::new();
+        let mut assigned_names = HashSet::new();
+        let mut assignments = Vec::new();
+
+        for ((key, name), overlap_ms) in candidates {
+            if overlap_ms < MIN_OVERLAP_MS || overlap_ms <= totals[&key] * MIN_SHARE {
+                continue;
+            }
+            if assigned_keys.contains(&key) || assigned_names.contains(name) {
+                continue;
+            }
+
+            assigned_keys.insert(key);
+            assigned_names.insert(name);
+            let Some(human_id) = human_ids.get(name) else {
+                continue;
+            };
+            assignments.push(SpeakerAssignment {
+                key,
+                identity: SpeakerIdentity::Assigned {
+                    id: human_id.clone(),
+                    label: name.to_string(),
+                },
+                overlap_ms: overlap_ms.round() as i64,
+            });
+        }
+
+        assignments.sort_by_key(|a| a.key);
+        assignments
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::WordState;
+
+    const STARTED_AT: u64 = 1_700_000_000_000;
+
+    fn event(offset_ms: u64, speakers: &[&str]) -> ActiveSpeakerEvent {
+        ActiveSpeakerEvent {
+            timestamp_ms: STARTED_AT + offset_ms,
+            speakers: speakers.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    fn word(id: &str, start_ms: i64, end_ms: i64, channel: i32) -> FinalizedWord {
+        FinalizedWord {
+            id: id.to_string(),
+            text: id.to_string(),
+            start_ms,
+            end_ms,
+            channel,
+            state: WordState::Final,
+        }
+    }
+
+    fn hint(word_id: &str, speaker_index: i32) -> SpeakerHint {
+        SpeakerHint {
+            word_id: word_id.to_string(),
+            speaker_index,
+        }
+    }
+
+    fn humans(names: &[&str]) -> HashMap<String, String> {
+        names
+            .iter()
+            .map(|name| (name.to_string(), format!("human-{}", name.to_lowercase())))
+            .collect()
+    }
+
+    fn label(assignment: &SpeakerAssignment) -> &str {
+        match &assignment.identity {
+            SpeakerIdentity::Assigned { label, .. } => label,
+            SpeakerIdentity::Unassigned { .. } => panic!("expected assigned identity"),
+        }
+    }
+
+    #[test]
+    fn test_assigns_diarized_speakers() {
+        let timeline = SpeakerTimeline::from_events(
+            [
+                event(0, &["Alice"]),
+                event(4_000, &["Bob"]),
+                event(8_000, &[]),
+            ],
+            STARTED_AT,
+        );
+
+        let words = [
+            word("w1", 500, 1_800, 1),
+            word("w2", 1_900, 3_600, 1),
+            word("w3", 4_200, 5_500, 1),
+            word("w4", 5_600, 7_400, 1),
+        ];
+        let hints = [hint("w1", 0), hint("w2", 0), hint("w3", 1), hint("w4", 1)];
+
+        let assignments = timeline.assign(&words, &hints, &humans(&["Alice", "Bob"]));
+        assert_eq!(assignments.len(), 2);
+        assert_eq!(assignments[0].key, SpeakerKey::Index(0));
+        assert_eq!(
+            assignments[0].identity,
+            SpeakerIdentity::Assigned {
+                id: "human-alice".to_string(),
+                label: "Alice".to_string(),
+            }
+        );
+        assert_eq!(assignments[1].key, SpeakerKey::Index(1));
+        assert_eq!(label(&assignments[1]), "Bob");
+    }
+
+    #[test]
+    fn test_falls_back_to_channel_and_skips_mixed_speakers() {
+        let timeline = SpeakerTimeline::from_events(
+            [
+                event(0, &["Me"]),
+                event(3_000, &["Alice"]),
+                event(5_000, &["Bob"]),
+                event(7_000, &[]),
+            ],
+            STARTED_AT,
+        );
+
+        // Mic channel without diarization; the remote channel mixes Alice and Bob evenly.
+        let words = [
+            word("w1", 0, 2_500, 0),
+            word("w2", 3_000, 5_000, 1),
+            word("w3", 5_000, 7_000, 1),
+        ];
+
+        let assignments = timeline.assign(&words, &[], &humans(&["Me", "Alice", "Bob"]));
+        assert_eq!(assignments.len(), 1);
+        assert_eq!(assignments[0].key, SpeakerKey::Channel(0));
+        assert_eq!(label(&assignments[0]), "Me");
+    }
+
+    #[test]
+    fn test_aligns_to_session_start() {
+        // Recording started ten seconds into the meeting.
+        let timeline = SpeakerTimeline::from_events(
+            [event(10_000, &["Alice"]), event(12_000, &[])],
+            STARTED_AT + 10_000,
+        );
+        let assignments = timeline.assign(
+            &[word("w1", 200, 1_800, 0)],
+            &[hint("w1", 3)],
+            &humans(&["Alice"]),
+        );
+        assert_eq!(assignments.len(), 1);
+        assert_eq!(assignments[0].key, SpeakerKey::Index(3));
+        assert_eq!(assignments[0].overlap_ms, 1_600);
+    }
+
+    #[test]
+    fn test_names_without_a_human_stay_unassigned() {
+        let timeline = SpeakerTimeline::from_events(
+            [
+                event(0, &["Alice"]),
+                event(2_000, &["Guest"]),
+                event(4_000, &[]),
+            ],
+            STARTED_AT,
+        );
+
+        let words = [word("w1", 0, 2_000, 0), word("w2", 2_000, 4_000, 1)];
+        let assignments = timeline.assign(&words, &[], &humans(&["Alice"]));
+        assert_eq!(assignments.len(), 1);
+        assert_eq!(assignments[0].key, SpeakerKey::Channel(0));
+        assert_eq!(label(&assignments[0]), "Alice");
+    }
+}
@@ -0,0 +1,850 @@
+use std::{
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    error::Error as StdError,
+    future::Future,
+};
+
+use hypr_template_app::{
+    Template, TranscriptTranslateSystem, TranscriptTranslateUser, TranslationContext,
+    render as render_template,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::postprocessor::normalize_json_payload;
+use crate::{FinalizedWord, TranscriptDelta, WordState};
+
+const DEFAULT_MAX_SEGMENT_WORDS: usize = 24;
+const DEFAULT_MAX_BATCH_SEGMENTS: usize = 8;
+const DEFAULT_CONTEXT_SEGMENTS: usize = 3;
+/// A pause longer than this ends a segment even without punctuation.
+const SEGMENT_GAP_MS: i64 = 1500;
+
+/// Translates finalized transcript words into one or more target languages,
+/// as a separate track next to the transcript.
+///
+/// Each channel's words are grouped into short segments (sentence punctuation,
+/// long pauses, or a word limit end a segment). Each segment is keyed by
+/// the ids of its first and last word, so the frontend can show the
+/// translation under the words it belongs to.
+///
+/// Like [`crate::TranscriptPostprocessor`], this is transport-agnostic:
+///
+/// 1. Feed every [`TranscriptDelta`] to [`push`](Self::push).
+/// 2. Call [`take_batch`](Self::take_batch) whenever the previous batch is
+///    done. Segments that complete while a request is running are sent
+///    together in the next batch.
+/// 3. Run the requests from [`build_requests`](Self::build_requests) against
+///    any LLM and hand the raw output to
+///    [`apply_response`](Self::apply_response), or use
+///    [`process_with`](Self::process_with) for both. After a failed request,
+///    hand the batch to [`requeue`](Self::requeue) so a later batch retries it.
+///
+/// Words are only translated once they are `Final`. When words are replaced
+/// (a correction started or resolved), the segments containing them are
+/// dropped, reported in [`TranslationDelta::removed`], and re-translated
+/// with the new text. Responses for dropped segments are ignored.
+#[derive(Debug, Clone)]
+pub struct TranscriptTranslator {
+    target_languages: Vec<String>,
+    source_language: Option<String>,
+    max_segment_words: usize,
+    max_batch_segments: usize,
+    context_segments: usize,
+    /// Final words not yet assigned to a segment, ordered by channel and start time.
+    buffer: Vec<FinalizedWord>,
+    segments: BTreeMap<u64, TrackedSegment>,
+    /// Recent (source, translation) pairs by language.
+    context: HashMap<String, VecDeque<(String, String)>>,
+    next_segment_id: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct TranscriptTranslatorRequest {
+    pub language: String,
+    pub segments_json: String,
+    pub system_prompt: String,
+    pub user_prompt: String,
+}
+
+/// Segments taken from the translator, to be sent in one request per target language.
+#[derive(Debug, Clone)]
+pub struct TranslationBatch {
+    segments: Vec<(u64, SourceSegment)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct TranslationRange {
+    pub start_word_id: String,
+    pub end_word_id: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, specta::Type)]
+pub struct TranslatedSegment {
+    #[serde(flatten)]
+    pub range: TranslationRange,
+    pub channel: i32,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub language: String,
+    pub source_text: String,
+    pub text: String,
+}
+
+/// Delta of the translation track.
+///
+/// The frontend should first remove translations of every range in
+/// `removed` (all languages), then store `segments`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, specta::Type)]
+pub struct TranslationDelta {
+    pub segments: Vec<TranslatedSegment>,
+    pub removed: Vec<TranslationRange>,
+}
+
+/// What [`TranscriptTranslator::process_with`] got through. One language failing
+/// does not discard the others.
+#[derive(Debug, Default)]
+pub struct TranslationOutcome {
+    pub delta: TranslationDelta,
+    /// Target languages whose request or response failed.
+    pub failed: Vec<(String, TranscriptTranslatorError)>,
+}
+
+impl TranslationDelta {
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty() && self.removed.is_empty()
+    }
+
+    fn extend(&mut self, other: TranslationDelta) {
+        self.segments.extend(other.segments);
+        self.removed.extend(other.removed);
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TranscriptTranslatorError {
+    #[error(transparent)]
+    Template(#[from] hypr_template_app::Error),
+    #[error("transcript translation runner failed")]
+    Runner(#[source] Box<dyn StdError + Send + Sync>),
+    #[error("failed to parse translation response: {0}")]
+    InvalidJson(serde_json::Error),
+    #[error("failed to serialize transcript segments: {0}")]
+    SerializeSegments(serde_json::Error),
+}
+
+#[derive(Debug, Clone)]
+struct SourceSegment {
+    range: TranslationRange,
+    channel: i32,
+    start_ms: i64,
+    end_ms: i64,
+    text: String,
+}
+
+#[derive(Debug, Clone)]
+struct TrackedSegment {
+    words: Vec<FinalizedWord>,
+    source: SourceSegment,
+    /// Translated text by language.
+    translations: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SegmentsDocument {
+    segments: Vec<SegmentEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct TranslationsEnvelope {
+    translations: Vec<SegmentEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SegmentEntry {
+    id: String,
+    text: String,
+}
+
+impl TranscriptTranslator {
+    pub fn new<I, S>(target_languages: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self {
+            target_languages: target_languages.into_iter().map(Into::into).collect(),
+            source_language: None,
+            max_segment_words: DEFAULT_MAX_SEGMENT_WORDS,
+            max_batch_segments: DEFAULT_MAX_BATCH_SEGMENTS,
+            context_segments: DEFAULT_CONTEXT_SEGMENTS,
+            buffer: vec![],
+            segments: BTreeMap::new(),
+            context: HashMap::new(),
+            next_segment_id: 1,
+        }
+    }
+
+    pub fn with_source_language(mut self, language: impl Into<String>) -> Self {
+        self.source_language = Some(language.into());
+        self
+    }
+
+    pub fn with_max_segment_words(mut self, max: usize) -> Self {
+        self.max_segment_words = max.max(1);
+        self
+    }
+
+    pub fn with_max_batch_segments(mut self, max: usize) -> Self {
+        self.max_batch_segments = max.max(1);
+        self
+    }
+
+    /// Number of previous segments (with their translations) sent along for context.
+    pub fn with_context_segments(mut self, count: usize) -> Self {
+        self.context_segments = count;
+        self
+    }
+
+    pub fn target_languages(&self) -> &[String] {
+        &self.target_languages
+    }
+
+    /// Track the words in `delta`. Returns the translations invalidated by replaced words.
+    pub fn push(&mut self, delta: &TranscriptDelta) -> TranslationDelta {
+        let mut removed = vec![];
+
+        if !delta.replaced_ids.is_empty() {
+            let replaced: HashSet<&str> = delta.replaced_ids.iter().map(String::as_str).collect();
+            self.buffer
+                .retain(|word| !replaced.contains(word.id.as_str()));
+
+            let invalidated: Vec<u64> = self
+                .segments
+                .iter()
+                .filter(|(_, segment)| {
+                    segment
+                        .words
+                        .iter()
+                        .any(|word| replaced.contains(word.id.as_str()))
+                })
+                .map(|(id, _)| *id)
+                .collect();
+
+            for id in invalidated {
+                let segment = self
+                    .segments
+                    .remove(&id)
+                    .expect("segment id was just collected");
+                if !segment.translations.is_empty() {
+                    removed.push(segment.source.range);
+                }
+                self.buffer.extend(
+                    segment
+                        .words
+                        .into_iter()
+                        .filter(|word| !replaced.contains(word.id.as_str())),
+                );
+            }
+        }
+
+        self.buffer.extend(
+            delta
+                .new_words
+                .iter()
+                .filter(|word| word.state == WordState::Final)
+                .cloned(),
+        );
+        self.buffer
+            .sort_by_key(|word| (word.channel, word.start_ms));
+
+        TranslationDelta {
+            segments: vec![],
+            removed,
+        }
+    }
+
+    /// Take the next batch of complete segments. With `flush`, trailing words
+    /// that don't end a segment yet are included too (e.g. at session end or
+    /// after a period of silence).
+    pub fn take_batch(&mut self, flush: bool) -> Option<TranslationBatch> {
+        if self.target_languages.is_empty() {
+            return None;
+        }
+
+        let buffer = std::mem::take(&mut self.buffer);
+        let mut complete = vec![];
+        // Channels talk over each other, so a sentence on one channel stays open
+        // while words arrive on another.
+        for channel_words in buffer.chunk_by(|a, b| a.channel == b.channel) {
+            let (segments, trailing) = self.segment_channel(channel_words, flush);
+            complete.extend(segments);
+            self.buffer.extend(trailing);
+        }
+
+        complete.sort_by_key(|segment| segment[0].start_ms);
+        let rest = complete.split_off(complete.len().min(self.max_batch_segments));
+        self.buffer.extend(rest.into_iter().flatten());
+        self.buffer
+            .sort_by_key(|word| (word.channel, word.start_ms));
+        let batch = complete;
+
+        let segments: Vec<(u64, SourceSegment)> = batch
+            .into_iter()
+            .filter_map(|words| {
+                let source = SourceSegment::from_words(&words)?;
+                let id = self.next_segment_id;
+                self.next_segment_id += 1;
+                self.segments.insert(
+                    id,
+                    TrackedSegment {
+                        words,
+                        source: source.clone(),
+                        translations: BTreeMap::new(),
+                    },
+                );
+                Some((id, source))
+            })
+            .collect();
+
+        (!segments.is_empty()).then_some(TranslationBatch { segments })
+    }
+
+    /// Split one channel's words into complete segments and the trailing words that
+    /// don't end a segment yet.
+    fn segment_channel(
+        &self,
+        words: &[FinalizedWord],
+        flush: bool,
+    ) -> (Vec<Vec<FinalizedWord>>, Vec<FinalizedWord>) {
+        let mut segments = vec![];
+        let mut current: Vec<FinalizedWord> = vec![];
+        let mut words = words.iter().peekable();
+
+        while let Some(word) = words.next() {
+            current.push(word.clone());
+
+            let complete = ends_sentence(&word.text)
+                || current.len() >= self.max_segment_words
+                || match words.peek() {
+                    Some(next) => next.start_ms - word.end_ms > SEGMENT_GAP_MS,
+                    None => flush,
+                };
+            if complete {
+                segments.push(std::mem::take(&mut current));
+            }
+        }
+
+        (segments, current)
+    }
+
+    /// One request per target language.
+    pub fn build_requests(
+        &self,
+        batch: &TranslationBatch,
+    ) -> Result<Vec<TranscriptTranslatorRequest>, TranscriptTranslatorError> {
+        let document = SegmentsDocument {
+            segments: batch
+                .segments
+                .iter()
+                .map(|(id, segment)| SegmentEntry {
+                    id: id.to_string(),
+                    text: segment.text.clone(),
+                })
+                .collect(),
+        };
+        let segments_json = serde_json::to_string_pretty(&document)
+            .map_err(TranscriptTranslatorError::SerializeSegments)?;
+
+        self.target_languages
+            .iter()
+            .map(|language| {
+                let system_prompt = render_template(Template::TranscriptTranslateSystem(
+                    TranscriptTranslateSystem {
+                        source_language: self.source_language.clone(),
+                        target_language: Some(language.clone()),
+                    },
+                ))?;
+                let user_prompt = render_template(Template::TranscriptTranslateUser(Box::new(
+                    TranscriptTranslateUser {
+                        context: self
+                            .context
+                            .get(language)
+                            .map(|context| {
+                                context
+                                    .iter()
+                                    .map(|(source, translation)| TranslationContext {
+                                        source: source.clone(),
+                                        translation: translation.clone(),
+                                    })
+                                    .collect()
+                            })
+                            .unwrap_or_default(),
+                        segments_json: segments_json.clone(),
+                    },
+                )))?;
+
+                Ok(TranscriptTranslatorRequest {
+                    language: language.clone(),
+                    segments_json: segments_json.clone(),
+                    system_prompt,
+                    user_prompt,
+                })
+            })
+            .collect()
+    }
+
+    /// Store the translations in `raw_response` for `language`. Segments the
+    /// model skipped stay untranslated; segments invalidated since the batch
+    /// was taken are ignored.
+    pub fn apply_response(
+        &mut self,
+        batch: &TranslationBatch,
+        language: &str,
+        raw_response: &str,
+    ) -> Result<TranslationDelta, TranscriptTranslatorError> {
+        let envelope: TranslationsEnvelope =
+            serde_json::from_str(&normalize_json_payload(raw_response))
+                .map_err(TranscriptTranslatorError::InvalidJson)?;
+        let mut translations: HashMap<String, String> = envelope
+            .translations
+            .into_iter()
+            .map(|entry| (entry.id, entry.text.trim().to_string()))
+            .collect();
+
+        let mut delta = TranslationDelta::default();
+        let context = self.context.entry(language.to_string()).or_default();
+
+        for (id, source) in &batch.segments {
+            let Some(segment) = self.segments.get_mut(id) else {
+                continue;
+            };
+            let Some(text) = translations.remove(&id.to_string()) else {
+                continue;
+            };
+            if text.is_empty() {
+                continue;
+            }
+
+            segment
+                .translations
+                .insert(language.to_string(), text.clone());
+
+            context.push_back((source.text.clone(), text.clone()));
+            while context.len() > self.context_segments {
+                context.pop_front();
+            }
+
+            delta.segments.push(source.translated(language, text));
+        }
+
+        Ok(delta)
+    }
+
+    /// Run every target language of `batch` through `run`, one after another.
+    /// Languages that fail are reported in [`TranslationOutcome::failed`]; the
+    /// rest are applied.
+    pub async fn process_with<F, Fut, E>(
+        &mut self,
+        batch: &TranslationBatch,
+        mut run: F,
+    ) -> Result<TranslationOutcome, TranscriptTranslatorError>
+    where
+        F: FnMut(TranscriptTranslatorRequest) -> Fut,
+        Fut: Future<Output = Result<String, E>>,
+        E: StdError + Send + Sync + 'static,
+    {
+        let mut outcome = TranslationOutcome::default();
+
+        for request in self.build_requests(batch)? {
+            let language = request.language.clone();
+            let result = match run(request).await {
+                Ok(raw_response) => self.apply_response(batch, &language, &raw_response),
+                Err(err) => Err(TranscriptTranslatorError::Runner(Box::new(err))),
+            };
+            match result {
+                Ok(delta) => outcome.delta.extend(delta),
+                Err(error) => outcome.failed.push((language, error)),
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    /// Hand back a batch whose requests failed. Its segments that still miss a
+    /// target language return to the buffer and are re-segmented into a later
+    /// batch; translations they already have are dropped and reported in
+    /// [`TranslationDelta::removed`].
+    pub fn requeue(&mut self, batch: TranslationBatch) -> TranslationDelta {
+        let mut removed = vec![];
+
+        for (id, _) in batch.segments {
+            // Invalidated segments already put their words back.
+            let Some(segment) = self.segments.get(&id) else {
+                continue;
+            };
+            if self
+                .target_languages
+                .iter()
+                .all(|language| segment.translations.contains_key(language))
+            {
+                continue;
+            }
+
+            let segment = self
+                .segments
+                .remove(&id)
+                .expect("segment id was just looked up");
+            if !segment.translations.is_empty() {
+                removed.push(segment.source.range);
+            }
+            self.buffer.extend(segment.words);
+        }
+        self.buffer
+            .sort_by_key(|word| (word.channel, word.start_ms));
+
+        TranslationDelta {
+            segments: vec![],
+            removed,
+        }
+    }
+
+    /// All current translations into `language`, in transcript order. Usable
+    /// as a second subtitle track or for search.
+    pub fn track(&self, language: &str) -> Vec<TranslatedSegment> {
+        let mut track: Vec<TranslatedSegment> = self
+            .segments
+            .values()
+            .filter_map(|segment| {
+                let text = segment.translations.get(language)?;
+                Some(segment.source.translated(language, text.clone()))
+            })
+            .collect();
+        track.sort_by_key(|segment| (segment.start_ms, segment.channel));
+        track
+    }
+}
+
+impl SourceSegment {
+    fn from_words(words: &[FinalizedWord]) -> Option<Self> {
+        let (first, last) = (words.first()?, words.last()?);
+        let text: String = words.iter().map(|word| word.text.as_str()).collect();
+
+        Some(Self {
+            range: TranslationRange {
+                start_word_id: first.id.clone(),
+                end_word_id: last.id.clone(),
+            },
+            channel: first.channel,
+            start_ms: first.start_ms,
+            end_ms: last.end_ms,
+            text: text.trim().to_string(),
+        })
+    }
+
+    fn translated(&self, language: &str, text: String) -> TranslatedSegment {
+        TranslatedSegment {
+            range: self.range.clone(),
+            channel: self.channel,
+            start_ms: self.start_ms,
+            end_ms: self.end_ms,
+            language: language.to_string(),
+            source_text: self.text.clone(),
+            text,
+        }
+    }
+}
+
+impl TranslationBatch {
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+}
+
+fn ends_sentence(text: &str) -> bool {
+    text.trim_end()
+        .ends_with(['.', '?', '!', '…', '。', '？', '！'])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(id: &str, text: &str, start_ms: i64, channel: i32) -> FinalizedWord {
+        FinalizedWord {
+            id: id.to_string(),
+            text: text.to_string(),
+            start_ms,
+            end_ms: start_ms + 200,
+            channel,
+            state: WordState::Final,
+        }
+    }
+
+    fn delta(new_words: Vec<FinalizedWord>, replaced_ids: &[&str]) -> TranscriptDelta {
+        TranscriptDelta {
+            new_words,
+            hints: vec![],
+            replaced_ids: replaced_ids.iter().map(|id| id.to_string()).collect(),
+            partials: vec![],
+        }
+    }
+
+    fn respond(batch: &TranslationBatch, texts: &[&str]) -> String {
+        let translations: Vec<_> = batch
+            .segments
+            .iter()
+            .zip(texts)
+            .map(|((id, _), text)| serde_json::json!({ "id": id.to_string(), "text": text }))
+            .collect();
+        serde_json::json!({ "translations": translations }).to_string()
+    }
+
+    #[test]
+    fn segments_at_sentence_ends_and_keeps_trailing_words() {
+        let mut translator = TranscriptTranslator::new(["en"]);
+        translator.push(&delta(
+            vec![
+                word("w1", " Guten", 0, 0),
+                word("w2", " Morgen.", 300, 0),
+                word("w3", " Fangen", 600, 0),
+                word("w4", " wir", 900, 0),
+            ],
+            &[],
+        ));
+
+        let batch = translator.take_batch(false).unwrap();
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch.segments[0].1.text, "Guten Morgen.");
+        assert!(translator.take_batch(false).is_none());
+
+        let batch = translator.take_batch(true).unwrap();
+        assert_eq!(batch.segments[0].1.text, "Fangen wir");
+    }
+
+    #[test]
+    fn splits_segments_by_channel_and_pause() {
+        let mut translator = TranscriptTranslator::new(["en"]);
+        translator.push(&delta(
+            vec![
+                word("w1", " hallo", 0, 0),
+                word("w2", " hallo", 100, 1),
+                word("w3", " ja", 5000, 0),
+            ],
+            &[],
+        ));
+
+        let batch = translator.take_batch(true).unwrap();
+        let texts: Vec<_> = batch
+            .segments
+            .iter()
+            .map(|(_, s)| s.text.as_str())
+            .collect();
+        assert_eq!(texts, vec!["hallo", "hallo", "ja"]);
+    }
+
+    #[test]
+    fn keeps_sentences_open_while_another_channel_speaks() {
+        let mut translator = TranscriptTranslator::new(["en"]);
+        translator.push(&delta(
+            vec![word("w1", " Guten", 0, 0), word("w2", " Ja.", 100, 1)],
+            &[],
+        ));
+
+        let batch = translator.take_batch(false).unwrap();
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch.segments[0].1.text, "Ja.");
+
+        translator.push(&delta(vec![word("w3", " Morgen.", 300, 0)], &[]));
+        let batch = translator.take_batch(false).unwrap();
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch.segments[0].1.text, "Guten Morgen.");
+    }
+
+    #[test]
+    fn leaves_segments_over_the_batch_limit_for_the_next_batch() {
+        let mut translator = TranscriptTranslator::new(["en"]).with_max_batch_segments(1);
+        translator.push(&delta(
+            vec![word("w1", " Eins.", 300, 0), word("w2", " Zwei.", 0, 1)],
+            &[],
+        ));
+
+        let batch = translator.take_batch(false).unwrap();
+        assert_eq!(batch.segments[0].1.text, "Zwei.");
+        let batch = translator.take_batch(false).unwrap();
+        assert_eq!(batch.segments[0].1.text, "Eins.");
+        assert!(translator.take_batch(true).is_none());
+    }
+
+    #[test]
+    fn skips_pending_words() {
+        let mut translator = TranscriptTranslator::new(["en"]);
+        let mut pending = word("w1", " Hallo.", 0, 0);
+        pending.state = WordState::Pending;
+        translator.push(&delta(vec![pending], &[]));
+
+        assert!(translator.take_batch(true).is_none());
+    }
+
+    #[test]
+    fn applies_translations_and_builds_track() {
+        let mut translator = TranscriptTranslator::new(["en", "ko"]).with_source_language("de");
+        translator.push(&delta(
+            vec![word("w1", " Guten", 0, 0), word("w2", " Morgen.", 300, 0)],
+            &[],
+        ));
+        let batch = translator.take_batch(false).unwrap();
+
+        let requests = translator.build_requests(&batch).unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(
+            requests[0]
+                .system_prompt
+                .contains("from German into English")
+        );
+        assert!(requests[1].system_prompt.contains("into Korean"));
+        assert!(requests[0].user_prompt.contains("Guten Morgen."));
+
+        let delta = translator
+            .apply_response(
+                &batch,
+                "en",
+                &format!("```json\n{}\n```", respond(&batch, &["Good morning."])),
+            )
+            .unwrap();
+        assert_eq!(delta.segments.len(), 1);
+        assert_eq!(delta.segments[0].range.start_word_id, "w1");
+        assert_eq!(delta.segments[0].range.end_word_id, "w2");
+        assert_eq!(delta.segments[0].source_text, "Guten Morgen.");
+
+        let track = translator.track("en");
+        assert_eq!(track.len(), 1);
+        assert_eq!(track[0].text, "Good morning.");
+        assert!(translator.track("ko").is_empty());
+    }
+
+    #[test]
+    fn carries_previous_segments_as_context() {
+        let mut translator = TranscriptTranslator::new(["en"]).with_context_segments(1);
+        translator.push(&delta(
+            vec![word("w1", " Eins.", 0, 0), word("w2", " Zwei.", 300, 0)],
+            &[],
+        ));
+        let batch = translator.take_batch(false).unwrap();
+        translator
+            .apply_response(&batch, "en", &respond(&batch, &["One.", "Two."]))
+            .unwrap();
+
+        translator.push(&delta(vec![word("w3", " Drei.", 600, 0)], &[]));
+        let batch = translator.take_batch(false).unwrap();
+        let request = &translator.build_requests(&batch).unwrap()[0];
+
+        assert!(request.user_prompt.contains("Zwei."));
+        assert!(request.user_prompt.contains("Two."));
+        assert!(!request.user_prompt.contains("One."));
+    }
+
+    #[test]
+    fn replaced_words_invalidate_translations() {
+        let mut translator = TranscriptTranslator::new(["en"]);
+        translator.push(&delta(
+            vec![word("w1", " Guten", 0, 0), word("w2", " Morgn.", 300, 0)],
+            &[],
+        ));
+        let batch = translator.take_batch(false).unwrap();
+        translator
+            .apply_response(&batch, "en", &respond(&batch, &["Good morgn."]))
+            .unwrap();
+
+        let mut pending = word("w2", " Morgn.", 300, 0);
+        pending.state = WordState::Pending;
+        let removed = translator.push(&delta(vec![pending], &["w2"])).removed;
+        assert_eq!(
+            removed,
+            vec![TranslationRange {
+                start_word_id: "w1".to_string(),
+                end_word_id: "w2".to_string(),
+            }]
+        );
+        assert!(translator.track("en").is_empty());
+        assert!(translator.take_batch(false).is_none());
+
+        translator.push(&delta(vec![word("w5", " Morgen.", 300, 0)], &["w2"]));
+        let batch = translator.take_batch(false).unwrap();
+        assert_eq!(batch.segments[0].1.text, "Guten Morgen.");
+        assert_eq!(batch.segments[0].1.range.end_word_id, "w5");
+    }
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("offline")]
+    struct Offline;
+
+    #[tokio::test]
+    async fn keeps_other_languages_when_one_fails_and_requeues_the_batch() {
+        let mut translator = TranscriptTranslator::new(["en", "ko"]);
+        translator.push(&delta(vec![word("w1", " Hallo.", 0, 0)], &[]));
+        let batch = translator.take_batch(false).unwrap();
+
+        let response = respond(&batch, &["Hello."]);
+        let outcome = translator
+            .process_with(&batch, |request| {
+                let response = response.clone();
+                async move {
+                    match request.language.as_str() {
+                        "en" => Ok(response),
+                        _ => Err(Offline),
+                    }
+                }
+            })
+            .await
+            .unwrap();
+        assert_eq!(outcome.delta.segments.len(), 1);
+        assert_eq!(outcome.delta.segments[0].text, "Hello.");
+        assert_eq!(outcome.failed.len(), 1);
+        assert_eq!(outcome.failed[0].0, "ko");
+
+        let removed = translator.requeue(batch).removed;
+        assert_eq!(removed.len(), 1);
+        assert!(translator.track("en").is_empty());
+
+        let batch = translator.take_batch(false).unwrap();
+        assert_eq!(batch.segments[0].1.text, "Hallo.");
+    }
+
+    #[test]
+    fn requeue_keeps_fully_translated_segments() {
+        let mut translator = TranscriptTranslator::new(["en"]);
+        translator.push(&delta(
+            vec![word("w1", " Eins.", 0, 0), word("w2", " Zwei.", 300, 0)],
+            &[],
+        ));
+        let batch = translator.take_batch(false).unwrap();
+        translator
+            .apply_response(&batch, "en", &respond(&batch, &["One."]))
+            .unwrap();
+
+        assert!(translator.requeue(batch).is_empty());
+        assert_eq!(translator.track("en").len(), 1);
+        let batch = translator.take_batch(false).unwrap();
+        assert_eq!(batch.len(), 1);
+        assert_eq!(batch.segments[0].1.text, "Zwei.");
+    }
+
+    #[test]
+    fn ignores_responses_for_invalidated_segments() {
+        let mut translator = TranscriptTranslator::new(["en"]);
+        translator.push(&delta(vec![word("w1", " Hallo.", 0, 0)], &[]));
+        let batch = translator.take_batch(false).unwrap();
+
+        translator.push(&delta(vec![word("w2", " Hallo!", 0, 0)], &["w1"]));
+
+        let delta = translator
+            .apply_response(&batch, "en", &respond(&batch, &["Hello."]))
+            .unwrap();
+        assert!(delta.is_empty());
+        assert!(translator.track("en").is_empty());
+    }
+}
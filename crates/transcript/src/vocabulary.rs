@@ -0,0 +1,462 @@
+use std::collections::HashMap;
+use std::ops::Range;
+
+use owhisper_interface::{Vocabulary, batch, stream};
+
+use crate::words::spacing_from_slice;
+
+/// Joined spans shorter than this must match the source phrase word for word, so "a i" does
+/// not become "AI".
+const MIN_JOINED_LEN: usize = 5;
+
+#[derive(Debug, Clone)]
+struct Rule {
+    to: String,
+    words: usize,
+}
+
+#[derive(Debug)]
+struct Edit {
+    range: Range<usize>,
+    word: String,
+    display: String,
+    leading: String,
+}
+
+/// Rewrites transcribed words with a [`Vocabulary`]: explicit replacements, each term's
+/// `sounds_like` variants, and spacing or casing variants of the term itself ("hypr note",
+/// "hyprnote" → "Hyprnote").
+///
+/// Matching ignores case, whitespace and punctuation, and a match may span several tokens as
+/// long as it starts and ends on word boundaries. Surrounding punctuation is kept. The pass is
+/// idempotent, so it is safe to run on providers that already applied the vocabulary natively;
+/// it is what makes replacements and phonetic hints work on those that cannot.
+///
+/// Each response is corrected on its own; a phrase split across two responses is left as is.
+#[derive(Debug, Clone, Default)]
+pub struct VocabularyCorrector {
+    rules: HashMap<String, Rule>,
+    max_span: usize,
+}
+
+impl VocabularyCorrector {
+    pub fn new(vocabulary: &Vocabulary) -> Self {
+        let mut corrector = Self::default();
+
+        for replacement in &vocabulary.replacements {
+            corrector.add_rule(&replacement.from, &replacement.to);
+        }
+        for term in &vocabulary.terms {
+            for variant in &term.sounds_like {
+                corrector.add_rule(variant, &term.text);
+            }
+            corrector.add_rule(&term.text, &term.text);
+        }
+
+        corrector
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Corrects the words and transcript of a `TranscriptResponse`; other responses are left
+    /// untouched.
+    pub fn correct_stream_response(&self, response: &mut stream::StreamResponse) {
+        if self.is_empty() {
+            return;
+        }
+
+        if let stream::StreamResponse::TranscriptResponse { channel, .. } = response {
+            for alt in &mut channel.alternatives {
+                self.correct_words(&mut alt.words, &mut alt.transcript);
+            }
+        }
+    }
+
+    pub fn correct_batch_response(&self, response: &mut batch::Response) {
+        if self.is_empty() {
+            return;
+        }
+
+        for channel in &mut response.results.channels {
+            for alt in &mut channel.alternatives {
+                self.correct_words(&mut alt.words, &mut alt.transcript);
+            }
+        }
+    }
+
+    /// Corrects plain text, treating each whitespace-separated token as a word.
+    pub fn correct_text(&self, text: &str) -> String {
+        let tokens: Vec<(&str, Option<&str>)> =
+            text.split_whitespace().map(|t| (t, None)).collect();
+        let spaced = spacing_from_slice(tokens.iter().copied(), text);
+        let edits = self.plan(&tokens, &spaced);
+
+        let mut out = rebuild(&spaced, &edits);
+        let trailing = &text[text.trim_end().len()..];
+        out.push_str(trailing);
+        out
+    }
+
+    fn add_rule(&mut self, from: &str, to: &str) {
+        let key = normalize(from);
+        let to = to.trim();
+        if key.is_empty() || to.is_empty() {
+            return;
+        }
+
+        let words = from.split_whitespace().count();
+        self.max_span = self.max_span.max(words + 1);
+        self.rules.entry(key).or_insert_with(|| Rule {
+            to: to.to_string(),
+            words,
+        });
+    }
+
+    fn correct_words<W: ResponseWord>(&self, words: &mut Vec<W>, transcript: &mut String) {
+        let tokens: Vec<(&str, Option<&str>)> = words.iter().map(ResponseWord::token).collect();
+        let spaced = spacing_from_slice(tokens.iter().copied(), transcript);
+        let edits = self.plan(&tokens, &spaced);
+        if edits.is_empty() {
+            return;
+        }
+
+        let new_transcript = rebuild(&spaced, &edits);
+        for edit in edits.iter().rev() {
+            let merged = merge(&words[edit.range.clone()], edit);
+            words.splice(edit.range.clone(), [merged]);
+        }
+        *transcript = new_transcript;
+    }
+
+    fn plan(&self, tokens: &[(&str, Option<&str>)], spaced: &[String]) -> Vec<Edit> {
+        let starts_word = |i: usize| i == 0 || i == spaced.len() || starts_with_space(&spaced[i]);
+        let mut edits = Vec::new();
+        let mut i = 0;
+
+        while i < tokens.len() {
+            if !starts_word(i) {
+                i += 1;
+                continue;
+            }
+
+            let longest = self.max_span.min(tokens.len() - i);
+            let found = (1..=longest).rev().find_map(|n| {
+                let end = i + n;
+                if !starts_word(end) {
+                    return None;
+                }
+
+                let key: String = spaced[i..end].iter().map(|s| normalize(s)).collect();
+                let rule = self.rules.get(&key)?;
+                let words = 1 + (i + 1..end).filter(|&k| starts_word(k)).count();
+                if words != rule.words && key.chars().count() < MIN_JOINED_LEN {
+                    return None;
+                }
+                Some((end, rule))
+            });
+
+            let Some((end, rule)) = found else {
+                i += 1;
+                continue;
+            };
+
+            let first = display(tokens[i]).trim();
+            let last = display(tokens[end - 1]).trim();
+            let prefix = &first[..first.len() - first.trim_start_matches(is_punct).len()];
+            let suffix = &last[last.trim_end_matches(is_punct).len()..];
+            let joined: String = spaced[i..end].concat();
+
+            if joined.trim().trim_matches(is_punct) != rule.to {
+                let leading = &spaced[i][..spaced[i].len() - spaced[i].trim_start().len()];
+                edits.push(Edit {
+                    range: i..end,
+                    word: rule.to.clone(),
+                    display: format!("{prefix}{}{suffix}", rule.to),
+                    leading: leading.to_string(),
+                });
+            }
+            i = end;
+        }
+
+        edits
+    }
+}
+
+impl Edit {
+    /// `(word, punctuated_word)` for the merged token. Without a punctuated form, `word` is what
+    /// gets displayed, so it keeps the punctuation.
+    fn texts(&self, punctuated: bool) -> (String, Option<String>) {
+        if punctuated {
+            (self.word.clone(), Some(self.display.clone()))
+        } else {
+            (self.display.clone(), None)
+        }
+    }
+}
+
+/// The fields a correction reads and rewrites, shared by stream and batch words.
+trait ResponseWord: Clone {
+    fn token(&self) -> (&str, Option<&str>);
+    fn end(&self) -> f64;
+    fn confidence(&self) -> f64;
+    fn merged(&self, end: f64, confidence: f64, word: String, punctuated: Option<String>) -> Self;
+}
+
+macro_rules! impl_response_word {
+    ($word:ty) => {
+        impl ResponseWord for $word {
+            fn token(&self) -> (&str, Option<&str>) {
+                (self.word.as_str(), self.punctuated_word.as_deref())
+            }
+
+            fn end(&self) -> f64 {
+                self.end
+            }
+
+            fn confidence(&self) -> f64 {
+                self.confidence
+            }
+
+            fn merged(
+                &self,
+                end: f64,
+                confidence: f64,
+                word: String,
+                punctuated_word: Option<String>,
+            ) -> Self {
+                Self {
+                    end,
+                    confidence,
+                    word,
+                    punctuated_word,
+                    ..self.clone()
+                }
+            }
+        }
+    };
+}
+
+impl_response_word!(stream::Word);
+impl_response_word!(batch::Word);
+
+/// Replaces the words an edit spans with one word: timing from the first start to the last end,
+/// and the lowest confidence among them.
+fn merge<W: ResponseWord>(span: &[W], edit: &Edit) -> W {
+    let first = &span[0];
+    let end = span[span.len() - 1].end();
+    let confidence = span
+        .iter()
+        .map(ResponseWord::confidence)
+        .fold(f64::INFINITY, f64::min);
+    let (word, punctuated_word) = edit.texts(first.token().1.is_some());
+    first.merged(end, confidence, word, punctuated_word)
+}
+
+fn rebuild(spaced: &[String], edits: &[Edit]) -> String {
+    let mut out = String::new();
+    let mut edits = edits.iter().peekable();
+    let mut i = 0;
+
+    while i < spaced.len() {
+        match edits.next_if(|e| e.range.start == i) {
+            Some(edit) => {
+                out.push_str(&edit.leading);
+                out.push_str(&edit.display);
+                i = edit.range.end;
+            }
+            None => {
+                out.push_str(&spaced[i]);
+                i += 1;
+            }
+        }
+    }
+
+    out
+}
+
+fn display<'a>((word, punctuated): (&'a str, Option<&'a str>)) -> &'a str {
+    punctuated.unwrap_or(word)
+}
+
+fn starts_with_space(s: &str) -> bool {
+    s.starts_with(char::is_whitespace)
+}
+
+fn is_punct(c: char) -> bool {
+    !c.is_alphanumeric()
+}
+
+fn normalize(s: &str) -> String {
+    s.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use owhisper_interface::{VocabularyReplacement, VocabularyTerm};
+
+    use super::*;
+
+    fn corrector() -> VocabularyCorrector {
+        VocabularyCorrector::new(&Vocabulary {
+            terms: vec![
+                VocabularyTerm {
+                    text: "Hyprnote".to_string(),
+                    sounds_like: vec!["hyper note".to_string()],
+                    ..Default::default()
+                },
+                VocabularyTerm {
+                    text: "AI".to_string(),
+                    ..Default::default()
+                },
+            ],
+            replacements: vec![VocabularyReplacement {
+                from: "john doe".to_string(),
+                to: "Jon Doh".to_string(),
+            }],
+        })
+    }
+
+    fn word(text: &str, start: f64, end: f64, confidence: f64) -> stream::Word {
+        stream::Word {
+            word: text.trim_matches(is_punct).to_lowercase(),
+            start,
+            end,
+            confidence,
+            speaker: Some(0),
+            punctuated_word: Some(text.to_string()),
+            language: None,
+        }
+    }
+
+    fn stream_response(transcript: &str, words: Vec<stream::Word>) -> stream::StreamResponse {
+        stream::StreamResponse::TranscriptResponse {
+            start: 0.0,
+            duration: 1.0,
+            is_final: true,
+            speech_final: true,
+            from_finalize: false,
+            channel: stream::Channel {
+                alternatives: vec![stream::Alternatives {
+                    transcript: transcript.to_string(),
+                    words,
+                    confidence: 0.9,
+                    languages: vec![],
+                }],
+            },
+            metadata: stream::Metadata::default(),
+            channel_index: vec![0, 1],
+        }
+    }
+
+    #[test]
+    fn test_correct_text() {
+        let corrector = corrector();
+
+        assert_eq!(
+            corrector.correct_text("I use hyper note, and HYPRNOTE daily. "),
+            "I use Hyprnote, and Hyprnote daily. "
+        );
+        assert_eq!(
+            corrector.correct_text("Ask john doe about a i and ai."),
+            "Ask Jon Doh about a i and AI."
+        );
+        assert_eq!(
+            corrector.correct_text("the hyper notebook"),
+            "the hyper notebook"
+        );
+    }
+
+    #[test]
+    fn test_correct_stream_response() {
+        let corrector = corrector();
+        let mut response = stream_response(
+            "We use hyper note.",
+            vec![
+                word("We", 0.0, 0.2, 0.9),
+                word("use", 0.2, 0.4, 0.9),
+                word("hyper", 0.4, 0.7, 0.6),
+                word("note.", 0.7, 1.0, 0.8),
+            ],
+        );
+
+        corrector.correct_stream_response(&mut response);
+
+        let stream::StreamResponse::TranscriptResponse { channel, .. } = response else {
+            unreachable!();
+        };
+        let alt = &channel.alternatives[0];
+        assert_eq!(alt.transcript, "We use Hyprnote.");
+        assert_eq!(alt.words.len(), 3);
+        assert_eq!(alt.words[2].word, "Hyprnote");
+        assert_eq!(alt.words[2].punctuated_word.as_deref(), Some("Hyprnote."));
+        assert_eq!((alt.words[2].start, alt.words[2].end), (0.4, 1.0));
+        assert_eq!(alt.words[2].confidence, 0.6);
+    }
+
+    #[test]
+    fn test_correct_stream_response_subword_tokens() {
+        let corrector = corrector();
+        let mut response = stream_response(
+            " hyprnote rocks",
+            vec![
+                word(" hy", 0.0, 0.2, 0.9),
+                word("pr", 0.2, 0.3, 0.7),
+                word("note", 0.3, 0.5, 0.9),
+                word(" rocks", 0.5, 0.8, 0.9),
+            ],
+        );
+
+        corrector.correct_stream_response(&mut response);
+
+        let stream::StreamResponse::TranscriptResponse { channel, .. } = response else {
+            unreachable!();
+        };
+        let alt = &channel.alternatives[0];
+        assert_eq!(alt.transcript, " Hyprnote rocks");
+        assert_eq!(alt.words.len(), 2);
+        assert_eq!((alt.words[0].end, alt.words[0].confidence), (0.5, 0.7));
+    }
+
+    #[test]
+    fn test_correct_batch_response() {
+        let corrector = corrector();
+        let words: Vec<batch::Word> = [
+            word("Ask", 0.0, 0.3, 0.9),
+            word("john", 0.3, 0.6, 0.5),
+            word("doe", 0.6, 0.9, 0.8),
+        ]
+        .into_iter()
+        .map(|w| batch::Word {
+            punctuated_word: None,
+            ..w.into()
+        })
+        .collect();
+        let mut response = batch::Response {
+            metadata: serde_json::Value::Null,
+            results: batch::Results {
+                channels: vec![batch::Channel {
+                    alternatives: vec![batch::Alternatives {
+                        transcript: "ask john doe".to_string(),
+                        confidence: 0.9,
+                        words,
+                    }],
+                }],
+            },
+        };
+
+        corrector.correct_batch_response(&mut response);
+
+        let alt = &response.results.channels[0].alternatives[0];
+        assert_eq!(alt.transcript, "ask Jon Doh");
+        assert_eq!(alt.words.len(), 2);
+        assert_eq!(alt.words[1].word, "Jon Doh");
+        assert_eq!(alt.words[1].punctuated_word, None);
+        assert_eq!((alt.words[1].start, alt.words[1].end), (0.3, 0.9));
+        assert_eq!(alt.words[1].confidence, 0.5);
+    }
+}
@@ -94,7 +94,7 @@ fn spacing_from_transcript(raw: &[Word], transcript: &str) -> Vec<String> {
 /// Core spacing oracle: aligns each token to the transcript string and
 /// recovers the whitespace that precedes it. Works for any word source that
 /// provides `(word, punctuated_word)` pairs.
-fn spacing_from_slice<'a>(
+pub(super) fn spacing_from_slice<'a>(
     tokens: impl Iterator<Item = (&'a str, Option<&'a str>)>,
     transcript: &str,
 ) -> Vec<String> {
@@ -0,0 +1,149 @@
+use std::io::{self, Read};
+
+const MIN_SIZE: usize = 256 * 1024;
+const AVG_SIZE: usize = 1024 * 1024;
+const MAX_SIZE: usize = 4 * 1024 * 1024;
+
+// FastCDC normalized chunking: a harder mask before the average size and an easier one after
+// keeps most chunks close to `AVG_SIZE`. The gear hash shifts left, so its top bits cover the
+// most recent 64 bytes.
+const MASK_SMALL: u64 = !0 << (64 - 22);
+const MASK_LARGE: u64 = !0 << (64 - 18);
+
+const GEAR: [u64; 256] = gear_table();
+
+const fn gear_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut state = 0x6368_6172_7661_756c_u64;
+    let mut i = 0;
+    while i < 256 {
+        // splitmix64
+        state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+}
+
+/// Splits a stream at content-defined boundaries, so an edit only changes the chunks around
+/// it and everything else deduplicates against earlier snapshots.
+pub(crate) struct Chunker<R> {
+    reader: R,
+    buf: Vec<u8>,
+    eof: bool,
+}
+
+impl<R: Read> Chunker<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        Self {
+            reader,
+            buf: Vec::with_capacity(MAX_SIZE),
+            eof: false,
+        }
+    }
+
+    fn fill(&mut self) -> io::Result<()> {
+        let mut block = [0u8; 64 * 1024];
+        while !self.eof && self.buf.len() < MAX_SIZE {
+            match self.reader.read(&mut block) {
+                Ok(0) => self.eof = true,
+                Ok(n) => self.buf.extend_from_slice(&block[..n]),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for Chunker<R> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Err(e) = self.fill() {
+            return Some(Err(e));
+        }
+        if self.buf.is_empty() {
+            return None;
+        }
+
+        let cut = cut_point(&self.buf);
+        Some(Ok(self.buf.drain(..cut).collect()))
+    }
+}
+
+fn cut_point(data: &[u8]) -> usize {
+    if data.len() <= MIN_SIZE {
+        return data.len();
+    }
+
+    let normal = data.len().min(AVG_SIZE);
+    let end = data.len().min(MAX_SIZE);
+    let mut hash = 0u64;
+
+    for (i, &byte) in data.iter().enumerate().take(end).skip(MIN_SIZE) {
+        hash = (hash << 1).wrapping_add(GEAR[byte as usize]);
+        let mask = if i < normal { MASK_SMALL } else { MASK_LARGE };
+        if hash & mask == 0 {
+            return i + 1;
+        }
+    }
+
+    end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noise(len: usize, seed: u64) -> Vec<u8> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect()
+    }
+
+    fn chunks(data: &[u8]) -> Vec<Vec<u8>> {
+        Chunker::new(data).collect::<io::Result<_>>().unwrap()
+    }
+
+    #[test]
+    fn respects_size_bounds() {
+        let data = noise(20 * 1024 * 1024, 1);
+        let chunks = chunks(&data);
+
+        assert_eq!(chunks.concat(), data);
+        let (last, rest) = chunks.split_last().unwrap();
+        assert!(last.len() <= MAX_SIZE);
+        for chunk in rest {
+            assert!((MIN_SIZE..=MAX_SIZE).contains(&chunk.len()));
+        }
+    }
+
+    #[test]
+    fn insertion_only_touches_nearby_chunks() {
+        let data = noise(16 * 1024 * 1024, 7);
+        let mut edited = data.clone();
+        edited.splice(8 * 1024 * 1024..8 * 1024 * 1024, *b"hello");
+
+        let before = chunks(&data);
+        let after = chunks(&edited);
+        let changed = after.iter().filter(|c| !before.contains(c)).count();
+
+        assert!(changed <= 2, "{changed} of {} chunks changed", after.len());
+    }
+
+    #[test]
+    fn small_and_empty_inputs() {
+        assert!(chunks(b"").is_empty());
+        assert_eq!(chunks(b"note"), vec![b"note".to_vec()]);
+    }
+}
@@ -0,0 +1,186 @@
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use chacha20poly1305::{
+    AeadCore, KeyInit, XChaCha20Poly1305, XNonce,
+    aead::{Aead, OsRng, Payload, rand_core::RngCore},
+};
+
+use crate::error::{Error, Result};
+
+const NONCE_LEN: usize = 24;
+
+/// The user-held secret for a repository. Nothing that can decrypt a backup ever leaves the
+/// device; lose this key (or the passphrase it was derived from) and the backup is unreadable.
+#[derive(Clone)]
+pub struct BackupKey([u8; 32]);
+
+impl BackupKey {
+    pub fn generate() -> Self {
+        let mut key = [0u8; 32];
+        OsRng.fill_bytes(&mut key);
+        Self(key)
+    }
+
+    pub fn from_passphrase(passphrase: &str, kdf: &KdfParams) -> Result<Self> {
+        let salt = STANDARD
+            .decode(&kdf.salt)
+            .map_err(|e| Error::InvalidKey(e.to_string()))?;
+        let params = scrypt::Params::new(kdf.log_n, kdf.r, kdf.p, 32)
+            .map_err(|e| Error::InvalidKey(e.to_string()))?;
+
+        let mut key = [0u8; 32];
+        scrypt::scrypt(passphrase.as_bytes(), &salt, &params, &mut key)
+            .map_err(|e| Error::InvalidKey(e.to_string()))?;
+        Ok(Self(key))
+    }
+
+    pub fn to_base64(&self) -> String {
+        STANDARD.encode(self.0)
+    }
+
+    pub fn from_base64(value: &str) -> Result<Self> {
+        let bytes = STANDARD
+            .decode(value.trim())
+            .map_err(|e| Error::InvalidKey(e.to_string()))?;
+        let key = bytes
+            .try_into()
+            .map_err(|_| Error::InvalidKey("expected 32 bytes".to_string()))?;
+        Ok(Self(key))
+    }
+}
+
+impl std::fmt::Debug for BackupKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("BackupKey(..)")
+    }
+}
+
+/// scrypt settings stored in the repository config so a passphrase can be turned back into the
+/// same key on another machine.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct KdfParams {
+    pub salt: String,
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+}
+
+impl KdfParams {
+    pub(crate) fn generate() -> Self {
+        let mut salt = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+
+        let recommended = scrypt::Params::recommended();
+        Self {
+            salt: STANDARD.encode(salt),
+            // Unoptimized scrypt at the recommended cost takes seconds per derivation.
+            log_n: if cfg!(test) { 10 } else { recommended.log_n() },
+            r: recommended.r(),
+            p: recommended.p(),
+        }
+    }
+}
+
+/// Keys derived from a [`BackupKey`]: one for naming chunks, one for encrypting objects.
+pub(crate) struct Keys {
+    id: [u8; 32],
+    cipher: XChaCha20Poly1305,
+}
+
+impl Keys {
+    pub(crate) fn derive(key: &BackupKey) -> Self {
+        let id = blake3::derive_key("char vault-backup 2026-10 chunk id", &key.0);
+        let data = blake3::derive_key("char vault-backup 2026-10 encryption", &key.0);
+
+        Self {
+            id,
+            cipher: XChaCha20Poly1305::new(&data.into()),
+        }
+    }
+
+    /// Keyed, so the storage provider can't confirm whether a known file is in the backup.
+    pub(crate) fn chunk_id(&self, plaintext: &[u8]) -> String {
+        blake3::keyed_hash(&self.id, plaintext).to_hex().to_string()
+    }
+
+    /// `nonce || ciphertext`. The object name is authenticated too, so objects can't be swapped
+    /// around in the bucket without decryption failing.
+    pub(crate) fn seal(&self, name: &str, plaintext: &[u8]) -> Vec<u8> {
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext,
+                    aad: name.as_bytes(),
+                },
+            )
+            .expect("encrypting into a Vec cannot fail");
+
+        let mut sealed = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+        sealed
+    }
+
+    pub(crate) fn open(&self, name: &str, sealed: &[u8]) -> Result<Vec<u8>> {
+        let Some((nonce, ciphertext)) = sealed.split_first_chunk::<NONCE_LEN>() else {
+            return Err(Error::Decrypt(name.to_string()));
+        };
+
+        self.cipher
+            .decrypt(
+                &XNonce::from(*nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: name.as_bytes(),
+                },
+            )
+            .map_err(|_| Error::Decrypt(name.to_string()))
+    }
+}
+
+pub(crate) fn random_hex(len: usize) -> String {
+    let mut bytes = vec![0u8; len];
+    OsRng.fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seal_round_trip() {
+        let keys = Keys::derive(&BackupKey::generate());
+        let sealed = keys.seal("chunks/ab/abc", b"meeting notes");
+
+        assert_eq!(
+            keys.open("chunks/ab/abc", &sealed).unwrap(),
+            b"meeting notes"
+        );
+        assert!(keys.open("chunks/ab/abd", &sealed).is_err());
+
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(keys.open("chunks/ab/abc", &tampered).is_err());
+    }
+
+    #[test]
+    fn chunk_ids_depend_on_key() {
+        let a = Keys::derive(&BackupKey::generate());
+        let b = Keys::derive(&BackupKey::generate());
+
+        assert_eq!(a.chunk_id(b"same"), a.chunk_id(b"same"));
+        assert_ne!(a.chunk_id(b"same"), b.chunk_id(b"same"));
+    }
+
+    #[test]
+    fn key_base64_round_trip() {
+        let key = BackupKey::generate();
+        let parsed = BackupKey::from_base64(&key.to_base64()).unwrap();
+
+        assert_eq!(parsed.0, key.0);
+        assert!(BackupKey::from_base64("c2hvcnQ=").is_err());
+    }
+}
@@ -0,0 +1,43 @@
+use thiserror::Error;
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    S3(Box<hypr_s3::ApiError>),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error("invalid backup key: {0}")]
+    InvalidKey(String),
+    #[error("no backup repository found")]
+    NotInitialized,
+    #[error("a backup repository already exists here")]
+    AlreadyInitialized,
+    #[error("unsupported repository version {0}")]
+    UnsupportedVersion(u32),
+    #[error("wrong key or passphrase")]
+    WrongKey,
+    #[error("failed to decrypt {0}")]
+    Decrypt(String),
+    #[error("chunk {0} does not match its id")]
+    CorruptChunk(String),
+    #[error("snapshot '{0}' not found")]
+    SnapshotNotFound(String),
+    #[error("snapshot prefix '{0}' is ambiguous")]
+    AmbiguousSnapshot(String),
+    #[error("refusing to restore outside the target: {0}")]
+    UnsafePath(String),
+    #[error("restore target {0} is not empty")]
+    TargetNotEmpty(String),
+    #[error("retention policy keeps nothing")]
+    EmptyRetention,
+}
+
+impl From<hypr_s3::ApiError> for Error {
+    fn from(err: hypr_s3::ApiError) -> Self {
+        Self::S3(Box::new(err))
+    }
+}
@@ -0,0 +1,12 @@
+mod chunker;
+mod crypto;
+mod error;
+mod repository;
+mod snapshot;
+mod store;
+
+pub use crypto::{BackupKey, KdfParams};
+pub use error::{Error, Result};
+pub use repository::{BackupSummary, PruneSummary, Repository, Secret};
+pub use snapshot::{FileEntry, Retention, Snapshot};
+pub use store::{LocalStore, ObjectStore, S3Store, StoreFuture};
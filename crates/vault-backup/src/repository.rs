@@ -0,0 +1,566 @@
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{BufReader, Write};
+use std::path::{Component, Path, PathBuf};
+
+use base64::{Engine as _, engine::general_purpose::STANDARD};
+use serde::{Deserialize, Serialize};
+
+use crate::chunker::Chunker;
+use crate::crypto::{BackupKey, KdfParams, Keys, random_hex};
+use crate::error::{Error, Result};
+use crate::snapshot::{FileEntry, Retention, Snapshot};
+use crate::store::ObjectStore;
+
+const CONFIG: &str = "config";
+const CHUNKS: &str = "chunks/";
+const SNAPSHOTS: &str = "snapshots/";
+const VERSION: u32 = 1;
+const CHECK: &[u8] = b"char vault backup";
+
+// Directories that are either rebuilt locally or version-controlled elsewhere.
+const SKIPPED_DIRS: &[&str] = &[".git"];
+
+/// Unencrypted, since it is needed before the key is known. It holds only the KDF salt and a
+/// sealed constant that tells a wrong key apart from corrupted data.
+#[derive(Serialize, Deserialize)]
+struct Config {
+    version: u32,
+    kdf: Option<KdfParams>,
+    check: String,
+}
+
+pub enum Secret {
+    Key(BackupKey),
+    Passphrase(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct BackupSummary {
+    pub snapshot_id: String,
+    pub files: usize,
+    pub changed_files: usize,
+    pub new_chunks: usize,
+    pub uploaded_bytes: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PruneSummary {
+    pub removed_snapshots: Vec<String>,
+    pub removed_chunks: usize,
+}
+
+/// An encrypted, deduplicated backup repository.
+///
+/// ```text
+/// config                  Config (plaintext JSON)
+/// chunks/<id[..2]>/<id>   sealed file content, id = keyed BLAKE3 of the plaintext
+/// snapshots/<id>          sealed Snapshot JSON
+/// ```
+pub struct Repository<S> {
+    store: S,
+    keys: Keys,
+}
+
+impl<S: ObjectStore> Repository<S> {
+    pub async fn init(store: S, secret: Secret) -> Result<Self> {
+        if !store.list(CONFIG).await?.is_empty() {
+            return Err(Error::AlreadyInitialized);
+        }
+
+        let (key, kdf) = match secret {
+            Secret::Key(key) => (key, None),
+            Secret::Passphrase(passphrase) => {
+                let kdf = KdfParams::generate();
+                (BackupKey::from_passphrase(&passphrase, &kdf)?, Some(kdf))
+            }
+        };
+        let keys = Keys::derive(&key);
+
+        let config = Config {
+            version: VERSION,
+            kdf,
+            check: STANDARD.encode(keys.seal(CONFIG, CHECK)),
+        };
+        store.put(CONFIG, serde_json::to_vec(&config)?).await?;
+
+        Ok(Self { store, keys })
+    }
+
+    pub async fn open(store: S, secret: Secret) -> Result<Self> {
+        if store.list(CONFIG).await?.is_empty() {
+            return Err(Error::NotInitialized);
+        }
+        let config: Config = serde_json::from_slice(&store.get(CONFIG).await?)?;
+        if config.version != VERSION {
+            return Err(Error::UnsupportedVersion(config.version));
+        }
+
+        let key = match (secret, &config.kdf) {
+            (Secret::Key(key), _) => key,
+            (Secret::Passphrase(passphrase), Some(kdf)) => {
+                BackupKey::from_passphrase(&passphrase, kdf)?
+            }
+            (Secret::Passphrase(_), None) => {
+                return Err(Error::InvalidKey(
+                    "this repository was created with a key, not a passphrase".to_string(),
+                ));
+            }
+        };
+        let keys = Keys::derive(&key);
+
+        let check = STANDARD
+            .decode(&config.check)
+            .map_err(|_| Error::Decrypt(CONFIG.to_string()))?;
+        match keys.open(CONFIG, &check) {
+            Ok(plain) if plain == CHECK => Ok(Self { store, keys }),
+            _ => Err(Error::WrongKey),
+        }
+    }
+
+    /// Snapshots `source`. Files whose size and modification time match the previous snapshot
+    /// are not read again, and only chunks the repository doesn't have yet are uploaded.
+    pub async fn backup(&self, source: &Path) -> Result<BackupSummary> {
+        let mut known: HashSet<String> = self
+            .store
+            .list(CHUNKS)
+            .await?
+            .iter()
+            .filter_map(|name| name.rsplit('/').next().map(str::to_string))
+            .collect();
+
+        let previous: HashMap<String, FileEntry> = self
+            .snapshots()
+            .await?
+            .pop()
+            .map(|snapshot| {
+                snapshot
+                    .files
+                    .into_iter()
+                    .map(|file| (file.path.clone(), file))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut paths = Vec::new();
+        collect_files(source, source, &mut paths)?;
+
+        let mut files = Vec::with_capacity(paths.len());
+        let mut changed_files = 0;
+        let mut new_chunks = 0;
+        let mut uploaded_bytes = 0;
+
+        for (relative, path) in paths {
+            let metadata = std::fs::metadata(&path)?;
+            let modified = metadata.modified()?;
+
+            if let Some(previous) = previous.get(&relative)
+                && previous.size == metadata.len()
+                && previous.modified == modified
+                && previous.chunks.iter().all(|id| known.contains(id))
+            {
+                files.push(previous.clone());
+                continue;
+            }
+
+            let mut chunks = Vec::new();
+            let mut size = 0;
+            for chunk in Chunker::new(BufReader::new(File::open(&path)?)) {
+                let chunk = chunk?;
+                let id = self.keys.chunk_id(&chunk);
+                size += chunk.len() as u64;
+
+                if known.insert(id.clone()) {
+                    let name = chunk_name(&id);
+                    let sealed = self.keys.seal(&name, &chunk);
+                    uploaded_bytes += sealed.len() as u64;
+                    self.store.put(&name, sealed).await?;
+                    new_chunks += 1;
+                }
+                chunks.push(id);
+            }
+
+            changed_files += 1;
+            files.push(FileEntry {
+                path: relative,
+                size,
+                modified,
+                chunks,
+            });
+        }
+
+        let created_at = chrono::Utc::now();
+        let snapshot = Snapshot {
+            id: format!("{}-{}", created_at.format("%Y%m%dT%H%M%SZ"), random_hex(4)),
+            created_at,
+            source: source.display().to_string(),
+            files,
+        };
+        let name = format!("{SNAPSHOTS}{}", snapshot.id);
+        self.store
+            .put(
+                &name,
+                self.keys.seal(&name, &serde_json::to_vec(&snapshot)?),
+            )
+            .await?;
+
+        Ok(BackupSummary {
+            snapshot_id: snapshot.id,
+            files: snapshot.files.len(),
+            changed_files,
+            new_chunks,
+            uploaded_bytes,
+        })
+    }
+
+    /// Oldest first.
+    pub async fn snapshots(&self) -> Result<Vec<Snapshot>> {
+        let mut snapshots = Vec::new();
+        for name in self.store.list(SNAPSHOTS).await? {
+            let plain = self.keys.open(&name, &self.store.get(&name).await?)?;
+            snapshots.push(serde_json::from_slice::<Snapshot>(&plain)?);
+        }
+        snapshots.sort_by_key(|snapshot| snapshot.created_at);
+        Ok(snapshots)
+    }
+
+    /// Looks a snapshot up by id, unique id prefix, or `latest`.
+    pub async fn find_snapshot(&self, query: &str) -> Result<Snapshot> {
+        let mut snapshots = self.snapshots().await?;
+        if query == "latest" {
+            return snapshots
+                .pop()
+                .ok_or_else(|| Error::SnapshotNotFound(query.to_string()));
+        }
+
+        let mut matches: Vec<Snapshot> = snapshots
+            .into_iter()
+            .filter(|snapshot| snapshot.id.starts_with(query))
+            .collect();
+        match matches.len() {
+            0 => Err(Error::SnapshotNotFound(query.to_string())),
+            1 => Ok(matches.remove(0)),
+            _ => Err(Error::AmbiguousSnapshot(query.to_string())),
+        }
+    }
+
+    /// Writes every file of `snapshot` under `target`, which must be empty or missing.
+    pub async fn restore(&self, snapshot: &Snapshot, target: &Path) -> Result<()> {
+        if target.exists() && std::fs::read_dir(target)?.next().is_some() {
+            return Err(Error::TargetNotEmpty(target.display().to_string()));
+        }
+
+        // Validate everything up front so a bad manifest doesn't leave a half-written restore.
+        let destinations = snapshot
+            .files
+            .iter()
+            .map(|file| safe_join(target, &file.path))
+            .collect::<Result<Vec<_>>>()?;
+
+        for (file, destination) in snapshot.files.iter().zip(destinations) {
+            if let Some(parent) = destination.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+
+            let mut out = File::create(&destination)?;
+            for id in &file.chunks {
+                let name = chunk_name(id);
+                let plain = self.keys.open(&name, &self.store.get(&name).await?)?;
+                if self.keys.chunk_id(&plain) != *id {
+                    return Err(Error::CorruptChunk(id.clone()));
+                }
+                out.write_all(&plain)?;
+            }
+            out.set_modified(file.modified)?;
+        }
+
+        Ok(())
+    }
+
+    /// Deletes snapshots outside `retention`, then every chunk no remaining snapshot uses.
+    /// Don't run this while another machine is backing up to the same repository: chunks it
+    /// has uploaded but not yet referenced would be collected.
+    pub async fn prune(&self, retention: Retention) -> Result<PruneSummary> {
+        if retention.is_empty() {
+            return Err(Error::EmptyRetention);
+        }
+
+        let snapshots = self.snapshots().await?;
+        let keep = retention.apply(&snapshots);
+        let mut summary = PruneSummary::default();
+
+        let mut referenced = HashSet::new();
+        for snapshot in &snapshots {
+            if keep.contains(&snapshot.id) {
+                referenced.extend(snapshot.chunk_ids().map(str::to_string));
+            } else {
+                self.store
+                    .delete(&format!("{SNAPSHOTS}{}", snapshot.id))
+                    .await?;
+                summary.removed_snapshots.push(snapshot.id.clone());
+            }
+        }
+
+        for name in self.store.list(CHUNKS).await? {
+            let id = name.rsplit('/').next().unwrap_or_default();
+            if !referenced.contains(id) {
+                self.store.delete(&name).await?;
+                summary.removed_chunks += 1;
+            }
+        }
+
+        Ok(summary)
+    }
+}
+
+fn chunk_name(id: &str) -> String {
+    format!("{CHUNKS}{}/{id}", &id[..2])
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<(String, PathBuf)>) -> Result<()> {
+    let mut entries = std::fs::read_dir(dir)?.collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let path = entry.path();
+        // Symlinks are skipped rather than followed, so a link can't pull in files outside
+        // the vault.
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            if !SKIPPED_DIRS.contains(&entry.file_name().to_string_lossy().as_ref()) {
+                collect_files(root, &path, files)?;
+            }
+        } else if file_type.is_file()
+            && let Ok(relative) = path.strip_prefix(root)
+        {
+            let parts: Vec<_> = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect();
+            files.push((parts.join("/"), path));
+        }
+    }
+    Ok(())
+}
+
+fn safe_join(target: &Path, relative: &str) -> Result<PathBuf> {
+    let mut path = target.to_path_buf();
+    for part in relative.split('/') {
+        let mut components = Path::new(part).components();
+        match (components.next(), components.next()) {
+            (Some(Component::Normal(name)), None) => path.push(name),
+            _ => return Err(Error::UnsafePath(relative.to_string())),
+        }
+    }
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::store::LocalStore;
+
+    fn write(root: &Path, relative: &str, content: &[u8]) {
+        let path = root.join(relative);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn read(root: &Path, relative: &str) -> Vec<u8> {
+        std::fs::read(root.join(relative)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn incremental_backup_and_restore() {
+        let vault = tempfile::tempdir().unwrap();
+        let bucket = tempfile::tempdir().unwrap();
+        let key = BackupKey::generate();
+
+        write(
+            vault.path(),
+            "sessions/a/_meta.json",
+            b"{\"title\":\"Weekly\"}",
+        );
+        write(vault.path(), "sessions/a/_memo.md", b"# Notes\n");
+        write(vault.path(), ".git/HEAD", b"ref: refs/heads/main\n");
+
+        let repo = Repository::init(LocalStore::new(bucket.path()), Secret::Key(key.clone()))
+            .await
+            .unwrap();
+        let first = repo.backup(vault.path()).await.unwrap();
+        assert_eq!(first.files, 2);
+        assert_eq!(first.new_chunks, 2);
+
+        write(
+            vault.path(),
+            "sessions/a/_memo.md",
+            b"# Notes\n\n- ship it\n",
+        );
+        write(vault.path(), "sessions/b/_memo.md", b"# Notes\n");
+        let second = repo.backup(vault.path()).await.unwrap();
+        assert_eq!(second.files, 3);
+        assert_eq!(second.changed_files, 2);
+        // The new session's memo is identical to the old one and deduplicates against it.
+        assert_eq!(second.new_chunks, 1);
+
+        let repo = Repository::open(LocalStore::new(bucket.path()), Secret::Key(key))
+            .await
+            .unwrap();
+        let snapshots = repo.snapshots().await.unwrap();
+        assert_eq!(snapshots.len(), 2);
+
+        let target = tempfile::tempdir().unwrap();
+        let old = repo.find_snapshot(&first.snapshot_id[..22]).await.unwrap();
+        repo.restore(&old, target.path()).await.unwrap();
+        assert_eq!(read(target.path(), "sessions/a/_memo.md"), b"# Notes\n");
+        assert!(!target.path().join("sessions/b").exists());
+        assert!(!target.path().join(".git").exists());
+
+        let target = tempfile::tempdir().unwrap();
+        let latest = repo.find_snapshot("latest").await.unwrap();
+        repo.restore(&latest, target.path()).await.unwrap();
+        assert_eq!(
+            read(target.path(), "sessions/a/_memo.md"),
+            b"# Notes\n\n- ship it\n"
+        );
+        assert_eq!(read(target.path(), "sessions/b/_memo.md"), b"# Notes\n");
+        assert!(matches!(
+            repo.restore(&latest, target.path()).await,
+            Err(Error::TargetNotEmpty(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn bucket_holds_no_plaintext() {
+        let vault = tempfile::tempdir().unwrap();
+        let bucket = tempfile::tempdir().unwrap();
+        write(vault.path(), "secret-meeting.md", b"acquisition of initech");
+
+        let repo = Repository::init(
+            LocalStore::new(bucket.path()),
+            Secret::Key(BackupKey::generate()),
+        )
+        .await
+        .unwrap();
+        repo.backup(vault.path()).await.unwrap();
+
+        let store = LocalStore::new(bucket.path());
+        for name in store.list("").await.unwrap() {
+            let data = store.get(&name).await.unwrap();
+            let text = String::from_utf8_lossy(&data);
+            assert!(!text.contains("initech"), "{name} leaks content");
+            assert!(!text.contains("secret-meeting"), "{name} leaks file names");
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_wrong_key_and_reinit() {
+        let bucket = tempfile::tempdir().unwrap();
+        Repository::init(
+            LocalStore::new(bucket.path()),
+            Secret::Key(BackupKey::generate()),
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(
+            Repository::open(
+                LocalStore::new(bucket.path()),
+                Secret::Key(BackupKey::generate())
+            )
+            .await,
+            Err(Error::WrongKey)
+        ));
+        assert!(matches!(
+            Repository::init(
+                LocalStore::new(bucket.path()),
+                Secret::Key(BackupKey::generate())
+            )
+            .await,
+            Err(Error::AlreadyInitialized)
+        ));
+        assert!(matches!(
+            Repository::open(
+                LocalStore::new(bucket.path().join("missing")),
+                Secret::Key(BackupKey::generate())
+            )
+            .await,
+            Err(Error::NotInitialized)
+        ));
+    }
+
+    #[tokio::test]
+    async fn passphrase_repository() {
+        let bucket = tempfile::tempdir().unwrap();
+        let passphrase = || Secret::Passphrase("correct horse battery staple".to_string());
+
+        Repository::init(LocalStore::new(bucket.path()), passphrase())
+            .await
+            .unwrap();
+        assert!(
+            Repository::open(LocalStore::new(bucket.path()), passphrase())
+                .await
+                .is_ok()
+        );
+        assert!(matches!(
+            Repository::open(
+                LocalStore::new(bucket.path()),
+                Secret::Passphrase("wrong".to_string())
+            )
+            .await,
+            Err(Error::WrongKey)
+        ));
+    }
+
+    #[tokio::test]
+    async fn prune_collects_unreferenced_chunks() {
+        let vault = tempfile::tempdir().unwrap();
+        let bucket = tempfile::tempdir().unwrap();
+        let repo = Repository::init(
+            LocalStore::new(bucket.path()),
+            Secret::Key(BackupKey::generate()),
+        )
+        .await
+        .unwrap();
+
+        write(vault.path(), "a.md", b"first");
+        write(vault.path(), "b.md", b"stays");
+        repo.backup(vault.path()).await.unwrap();
+        write(vault.path(), "a.md", b"second");
+        let latest = repo.backup(vault.path()).await.unwrap();
+
+        let summary = repo
+            .prune(Retention {
+                keep_last: 1,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(summary.removed_snapshots.len(), 1);
+        assert_eq!(summary.removed_chunks, 1);
+
+        let target = tempfile::tempdir().unwrap();
+        let snapshot = repo.find_snapshot(&latest.snapshot_id).await.unwrap();
+        repo.restore(&snapshot, target.path()).await.unwrap();
+        assert_eq!(read(target.path(), "a.md"), b"second");
+        assert_eq!(read(target.path(), "b.md"), b"stays");
+
+        assert!(matches!(
+            repo.prune(Retention::default()).await,
+            Err(Error::EmptyRetention)
+        ));
+    }
+
+    #[test]
+    fn safe_join_rejects_escapes() {
+        let target = Path::new("/restore");
+
+        assert_eq!(
+            safe_join(target, "sessions/a/_memo.md").unwrap(),
+            target.join("sessions").join("a").join("_memo.md")
+        );
+        for path in ["../etc/passwd", "a/../../b", "/etc/passwd", "a//b", "."] {
+            assert!(safe_join(target, path).is_err(), "{path}");
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::HashSet;
+use std::time::SystemTime;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// The encrypted manifest of one backup run. Files point at chunks by id, so unchanged content
+/// is shared between every snapshot that contains it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub id: String,
+    pub created_at: DateTime<Utc>,
+    /// The directory that was backed up, for display only.
+    pub source: String,
+    pub files: Vec<FileEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileEntry {
+    /// `/`-separated, relative to the backed up directory.
+    pub path: String,
+    pub size: u64,
+    pub modified: SystemTime,
+    pub chunks: Vec<String>,
+}
+
+impl Snapshot {
+    pub fn total_size(&self) -> u64 {
+        self.files.iter().map(|file| file.size).sum()
+    }
+
+    pub(crate) fn chunk_ids(&self) -> impl Iterator<Item = &str> {
+        self.files
+            .iter()
+            .flat_map(|file| file.chunks.iter().map(String::as_str))
+    }
+}
+
+/// Which snapshots `prune` keeps. Each rule keeps the newest snapshot in each of the last N
+/// periods that have one; a snapshot kept by any rule survives.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Retention {
+    pub keep_last: usize,
+    pub keep_daily: usize,
+    pub keep_weekly: usize,
+    pub keep_monthly: usize,
+}
+
+impl Retention {
+    pub fn is_empty(&self) -> bool {
+        self.keep_last == 0
+            && self.keep_daily == 0
+            && self.keep_weekly == 0
+            && self.keep_monthly == 0
+    }
+
+    /// Ids of the snapshots to keep.
+    pub(crate) fn apply(&self, snapshots: &[Snapshot]) -> HashSet<String> {
+        let mut newest_first: Vec<&Snapshot> = snapshots.iter().collect();
+        newest_first.sort_by_key(|snapshot| std::cmp::Reverse(snapshot.created_at));
+
+        let mut keep: HashSet<String> = newest_first
+            .iter()
+            .take(self.keep_last)
+            .map(|snapshot| snapshot.id.clone())
+            .collect();
+
+        let periods: [(usize, &str); 3] = [
+            (self.keep_daily, "%Y-%m-%d"),
+            (self.keep_weekly, "%G-W%V"),
+            (self.keep_monthly, "%Y-%m"),
+        ];
+        for (count, format) in periods {
+            let mut seen = HashSet::new();
+            for snapshot in &newest_first {
+                if seen.len() == count {
+                    break;
+                }
+                if seen.insert(snapshot.created_at.format(format).to_string()) {
+                    keep.insert(snapshot.id.clone());
+                }
+            }
+        }
+
+        keep
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(id: &str, created_at: &str) -> Snapshot {
+        Snapshot {
+            id: id.to_string(),
+            created_at: created_at.parse().unwrap(),
+            source: String::new(),
+            files: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn retention_keeps_newest_per_period() {
+        let snapshots = vec![
+            snapshot("a", "2026-08-30T09:00:00Z"),
+            snapshot("b", "2026-09-28T09:00:00Z"),
+            snapshot("c", "2026-10-01T09:00:00Z"),
+            snapshot("d", "2026-10-01T18:00:00Z"),
+            snapshot("e", "2026-10-02T09:00:00Z"),
+        ];
+
+        let keep = |retention: Retention| {
+            let mut ids: Vec<_> = retention.apply(&snapshots).into_iter().collect();
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(
+            keep(Retention {
+                keep_last: 1,
+                ..Default::default()
+            }),
+            vec!["e"]
+        );
+        assert_eq!(
+            keep(Retention {
+                keep_daily: 2,
+                ..Default::default()
+            }),
+            vec!["d", "e"]
+        );
+        // 2026-09-28 is a Monday, so it shares a week with the October snapshots.
+        assert_eq!(
+            keep(Retention {
+                keep_weekly: 2,
+                ..Default::default()
+            }),
+            vec!["a", "e"]
+        );
+        assert_eq!(
+            keep(Retention {
+                keep_monthly: 3,
+                ..Default::default()
+            }),
+            vec!["a", "b", "e"]
+        );
+    }
+}
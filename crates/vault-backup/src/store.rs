@@ -0,0 +1,136 @@
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+
+use crate::error::Result;
+
+pub type StoreFuture<'a, T> = Pin<Box<dyn Future<Output = Result<T>> + Send + 'a>>;
+
+/// Flat key/value storage for repository objects. Names are `/`-separated and relative to the
+/// repository root; the store never sees plaintext.
+pub trait ObjectStore: Send + Sync {
+    fn put<'a>(&'a self, name: &'a str, data: Vec<u8>) -> StoreFuture<'a, ()>;
+
+    fn get<'a>(&'a self, name: &'a str) -> StoreFuture<'a, Vec<u8>>;
+
+    /// Names starting with `prefix`.
+    fn list<'a>(&'a self, prefix: &'a str) -> StoreFuture<'a, Vec<String>>;
+
+    fn delete<'a>(&'a self, name: &'a str) -> StoreFuture<'a, ()>;
+}
+
+/// Any S3-compatible bucket (AWS, R2, Tigris, MinIO, ...). Each repository lives in its own
+/// folder of the bucket.
+pub struct S3Store {
+    client: hypr_s3::Client,
+    repository: String,
+}
+
+impl S3Store {
+    pub fn new(client: hypr_s3::Client, repository: impl Into<String>) -> Self {
+        Self {
+            client,
+            repository: repository.into(),
+        }
+    }
+}
+
+impl ObjectStore for S3Store {
+    fn put<'a>(&'a self, name: &'a str, data: Vec<u8>) -> StoreFuture<'a, ()> {
+        Box::pin(async move {
+            self.client
+                .for_user(&self.repository)
+                .put_bytes(name, data)
+                .await?;
+            Ok(())
+        })
+    }
+
+    fn get<'a>(&'a self, name: &'a str) -> StoreFuture<'a, Vec<u8>> {
+        Box::pin(async move {
+            let data = self.client.for_user(&self.repository).get(name).await?;
+            Ok(data.to_vec())
+        })
+    }
+
+    fn list<'a>(&'a self, prefix: &'a str) -> StoreFuture<'a, Vec<String>> {
+        Box::pin(async move { Ok(self.client.for_user(&self.repository).list(prefix).await?) })
+    }
+
+    fn delete<'a>(&'a self, name: &'a str) -> StoreFuture<'a, ()> {
+        Box::pin(async move {
+            self.client.for_user(&self.repository).delete(name).await?;
+            Ok(())
+        })
+    }
+}
+
+/// A repository in a local directory, e.g. an external drive.
+pub struct LocalStore {
+    root: PathBuf,
+}
+
+impl LocalStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl ObjectStore for LocalStore {
+    fn put<'a>(&'a self, name: &'a str, data: Vec<u8>) -> StoreFuture<'a, ()> {
+        Box::pin(async move {
+            let path = self.root.join(name);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            // Write then rename, so an interrupted backup never leaves a truncated object.
+            let tmp = path.with_extension("tmp");
+            std::fs::write(&tmp, data)?;
+            std::fs::rename(&tmp, &path)?;
+            Ok(())
+        })
+    }
+
+    fn get<'a>(&'a self, name: &'a str) -> StoreFuture<'a, Vec<u8>> {
+        Box::pin(async move { Ok(std::fs::read(self.root.join(name))?) })
+    }
+
+    fn list<'a>(&'a self, prefix: &'a str) -> StoreFuture<'a, Vec<String>> {
+        Box::pin(async move {
+            let mut names = Vec::new();
+            if self.root.exists() {
+                collect_names(&self.root, &self.root, &mut names)?;
+            }
+            names.retain(|name| name.starts_with(prefix));
+            names.sort();
+            Ok(names)
+        })
+    }
+
+    fn delete<'a>(&'a self, name: &'a str) -> StoreFuture<'a, ()> {
+        Box::pin(async move {
+            match std::fs::remove_file(self.root.join(name)) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+                _ => Ok(()),
+            }
+        })
+    }
+}
+
+fn collect_names(root: &Path, dir: &Path, names: &mut Vec<String>) -> std::io::Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_names(root, &path, names)?;
+        } else if path.extension().is_none_or(|ext| ext != "tmp")
+            && let Ok(relative) = path.strip_prefix(root)
+        {
+            let parts: Vec<_> = relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect();
+            names.push(parts.join("/"));
+        }
+    }
+    Ok(())
+}
@@ -36,23 +36,8 @@ impl WhisperBuilder {
     pub fn build(self) -> Result<Whisper, crate::Error> {
         unsafe { Self::suppress_log() };
 
-        let context_param = {
-            let mut p = WhisperContextParameters {
-                gpu_device: 0,
-                use_gpu: true,
-                flash_attn: false, // crash on macos
-                ..Default::default()
-            };
-            p.dtw_parameters.mode = whisper_rs::DtwMode::None;
-            p
-        };
-
         let model_path = self.model_path.unwrap();
-        if !std::path::Path::new(&model_path).exists() {
-            return Err(crate::Error::ModelNotFound);
-        }
-
-        let ctx = WhisperContext::new_with_params(&model_path, context_param)?;
+        let ctx = load_context(&model_path)?;
         let state = ctx.create_state()?;
         let token_beg = ctx.token_beg();
 
@@ -66,7 +51,7 @@ impl WhisperBuilder {
         })
     }
 
-    unsafe fn suppress_log() {
+    pub(crate) unsafe fn suppress_log() {
         unsafe extern "C" fn noop_callback(
             _level: whisper_rs::whisper_rs_sys::ggml_log_level,
             _text: *const ::std::os::raw::c_char,
@@ -77,6 +62,86 @@ impl WhisperBuilder {
     }
 }
 
+fn load_context(model_path: &str) -> Result<WhisperContext, crate::Error> {
+    if !std::path::Path::new(model_path).exists() {
+        return Err(crate::Error::ModelNotFound);
+    }
+
+    let context_param = {
+        let mut p = WhisperContextParameters {
+            gpu_device: 0,
+            use_gpu: true,
+            flash_attn: false, // crash on macos
+            ..Default::default()
+        };
+        p.dtw_parameters.mode = whisper_rs::DtwMode::None;
+        p
+    };
+
+    Ok(WhisperContext::new_with_params(model_path, context_param)?)
+}
+
+/// Probability per whisper language index, from the first 30 seconds of `audio`.
+fn language_probabilities(
+    state: &mut WhisperState,
+    audio: &[f32],
+) -> Result<Vec<f32>, crate::Error> {
+    state.pcm_to_mel(audio, 1)?;
+    let (_lang_id, lang_probs) = state.lang_detect(0, 1)?;
+    Ok(lang_probs)
+}
+
+/// Spoken-language identification with whisper's language head, without decoding any text.
+///
+/// Holds `pool_size` decoder states sharing one model, so at most that many detections run at
+/// once; a call that finds every state in use fails with `DetectorBusy` instead of queueing.
+pub struct WhisperLanguageDetector {
+    states: Vec<std::sync::Mutex<WhisperState>>,
+}
+
+impl WhisperLanguageDetector {
+    pub fn new(model_path: impl Into<String>, pool_size: usize) -> Result<Self, crate::Error> {
+        unsafe { WhisperBuilder::suppress_log() };
+
+        let ctx = load_context(&model_path.into())?;
+        let states = (0..pool_size.max(1))
+            .map(|_| ctx.create_state().map(std::sync::Mutex::new))
+            .collect::<Result<_, _>>()?;
+        Ok(Self { states })
+    }
+}
+
+impl hypr_language::SpokenLanguageDetector for WhisperLanguageDetector {
+    fn detect(
+        &self,
+        samples: &[f32],
+    ) -> Result<Vec<hypr_language::LanguageScore>, hypr_language::Error> {
+        let mut state = self
+            .states
+            .iter()
+            .find_map(|state| match state.try_lock() {
+                Ok(guard) => Some(guard),
+                Err(std::sync::TryLockError::Poisoned(e)) => Some(e.into_inner()),
+                Err(std::sync::TryLockError::WouldBlock) => None,
+            })
+            .ok_or(hypr_language::Error::DetectorBusy)?;
+        let probs = language_probabilities(&mut state, samples)
+            .map_err(|e| hypr_language::Error::DetectionFailed(e.to_string()))?;
+
+        Ok(probs
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, probability)| {
+                let language = Language::from_repr(u8::try_from(index).ok()?)?;
+                Some(hypr_language::LanguageScore {
+                    language: language.try_into().ok()?,
+                    probability,
+                })
+            })
+            .collect())
+    }
+}
+
 pub struct Whisper {
     #[allow(dead_code)]
     id: String,
@@ -204,8 +269,7 @@ impl Whisper {
         }
 
         let lang_str = {
-            self.state.pcm_to_mel(audio, 1)?;
-            let (_lang_id, lang_probs) = self.state.lang_detect(0, 1)?;
+            let lang_probs = language_probabilities(&mut self.state, audio)?;
 
             let mut best_lang = None;
             let mut best_prob = f32::NEG_INFINITY;
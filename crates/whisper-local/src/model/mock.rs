@@ -37,3 +37,20 @@ impl Whisper {
         }])
     }
 }
+
+pub struct WhisperLanguageDetector {}
+
+impl WhisperLanguageDetector {
+    pub fn new(_model_path: impl Into<String>, _pool_size: usize) -> Result<Self, crate::Error> {
+        Ok(Self {})
+    }
+}
+
+impl hypr_language::SpokenLanguageDetector for WhisperLanguageDetector {
+    fn detect(
+        &self,
+        _samples: &[f32],
+    ) -> Result<Vec<hypr_language::LanguageScore>, hypr_language::Error> {
+        Ok(vec![])
+    }
+}
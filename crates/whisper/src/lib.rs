@@ -1,6 +1,8 @@
 // https://github.com/openai/whisper/blob/ba3f3cd/whisper/tokenizer.py#L10-L128
 #[repr(u8)]
-#[derive(Debug, Copy, Clone, strum::EnumString, strum::Display, strum::AsRefStr)]
+#[derive(
+    Debug, Copy, Clone, strum::EnumString, strum::Display, strum::AsRefStr, strum::FromRepr,
+)]
 pub enum Language {
     #[strum(serialize = "en")]
     En,
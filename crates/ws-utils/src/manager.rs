@@ -1,21 +1,43 @@
 use std::sync::{Arc, Mutex};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tokio_util::sync::CancellationToken;
 
+/// Tracks live sessions for a transcription service. The default manager keeps a single
+/// session and cancels it when a new one arrives; `with_limit` serves several side by side
+/// and refuses the rest.
 #[derive(Clone)]
 pub struct ConnectionManager {
     inner: Arc<Mutex<Option<CancellationToken>>>,
+    limit: Option<Arc<Semaphore>>,
 }
 
 impl Default for ConnectionManager {
     fn default() -> Self {
         Self {
             inner: Arc::new(Mutex::new(None)),
+            limit: None,
         }
     }
 }
 
 impl ConnectionManager {
-    pub fn acquire_connection(&self) -> ConnectionGuard {
+    pub fn with_limit(max_connections: usize) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(None)),
+            limit: Some(Arc::new(Semaphore::new(max_connections))),
+        }
+    }
+
+    /// Returns `None` when the manager already serves its limit of sessions.
+    pub fn acquire_connection(&self) -> Option<ConnectionGuard> {
+        if let Some(limit) = &self.limit {
+            let permit = limit.clone().try_acquire_owned().ok()?;
+            return Some(ConnectionGuard {
+                token: CancellationToken::new(),
+                _permit: Some(permit),
+            });
+        }
+
         let mut slot = self.inner.lock().unwrap();
 
         if let Some(old) = slot.take() {
@@ -25,12 +47,16 @@ impl ConnectionManager {
         let token = CancellationToken::new();
         *slot = Some(token.clone());
 
-        ConnectionGuard { token }
+        Some(ConnectionGuard {
+            token,
+            _permit: None,
+        })
     }
 }
 
 pub struct ConnectionGuard {
     token: CancellationToken,
+    _permit: Option<OwnedSemaphorePermit>,
 }
 
 impl ConnectionGuard {
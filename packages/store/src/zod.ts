@@ -49,7 +49,13 @@ export const eventParticipantSchema = z.object({
   is_current_user: z.boolean().optional(),
 });
 
-export const calendarProviderSchema = z.enum(["apple", "google", "outlook"]);
+export const calendarProviderSchema = z.enum([
+  "apple",
+  "google",
+  "outlook",
+  "caldav",
+  "ics",
+]);
 export type CalendarProvider = z.infer<typeof calendarProviderSchema>;
 
 export const eventSchema = z.object({
@@ -1,4 +1,4 @@
-const COMMANDS: &[&str] = &["list_foundation_models"];
+const COMMANDS: &[&str] = &["list_foundation_models", "converse", "converse_stream"];
 
 fn main() {
     tauri_plugin::Builder::new(COMMANDS).build();
@@ -27,6 +27,34 @@ export const commands = {
       else return { status: "error", error: e as any };
     }
   },
+  async converse(request: JsonValue): Promise<Result<JsonValue, string>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("plugin:bedrock|converse", { request }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
+  async converseStream(
+    request: JsonValue,
+    channel: TAURI_CHANNEL<string>,
+  ): Promise<Result<null, string>> {
+    try {
+      return {
+        status: "ok",
+        data: await TAURI_INVOKE("plugin:bedrock|converse_stream", {
+          request,
+          channel,
+        }),
+      };
+    } catch (e) {
+      if (e instanceof Error) throw e;
+      else return { status: "error", error: e as any };
+    }
+  },
 };
 
 /** user-defined events **/
@@ -46,6 +74,13 @@ export type FoundationModelSummary = {
   inferenceTypesSupported: string[] | null;
   modelArn: string | null;
 };
+export type JsonValue =
+  | null
+  | boolean
+  | number
+  | string
+  | JsonValue[]
+  | Partial<{ [key in string]: JsonValue }>;
 export type ListFoundationModelsRequest = {
   byProvider: string | null;
   byCustomizationType: string | null;
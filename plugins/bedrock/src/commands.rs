@@ -41,3 +41,28 @@ pub(crate) async fn list_foundation_models<R: tauri::Runtime>(
         .await
         .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn converse<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    request: serde_json::Value,
+) -> Result<serde_json::Value, String> {
+    app.bedrock()
+        .converse(request)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn converse_stream<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    request: serde_json::Value,
+    channel: tauri::ipc::Channel<String>,
+) -> Result<(), String> {
+    app.bedrock()
+        .converse_stream(request, channel)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -6,6 +6,16 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub enum Error {
     #[error("AWS SDK error: {0}")]
     AwsSdk(String),
+    #[error("Bedrock error: {0}")]
+    Bedrock(String),
+    #[error(transparent)]
+    Translate(#[from] hypr_bedrock::Error),
+    #[error(transparent)]
+    Http(#[from] reqwest::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+    #[error(transparent)]
+    Tauri(#[from] tauri::Error),
     #[error(transparent)]
     Io(#[from] std::io::Error),
 }
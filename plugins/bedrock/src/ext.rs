@@ -1,9 +1,14 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use aws_sdk_bedrock::types::{InferenceType, ModelCustomization, ModelModality};
+use futures_util::StreamExt;
+use hypr_bedrock::{EventStreamDecoder, StreamTranslator};
+use tauri::ipc::Channel;
 
-use crate::Result;
 use crate::commands::{
     FoundationModelSummary, ListFoundationModelsRequest, ListFoundationModelsResponse,
 };
+use crate::{Error, Result, runtime};
 
 pub struct Bedrock<'a, R: tauri::Runtime, M: tauri::Manager<R>> {
     manager: &'a M,
@@ -96,6 +101,80 @@ impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> Bedrock<'a, R, M> {
 
         Ok(ListFoundationModelsResponse { model_summaries })
     }
+
+    /// Runs an OpenAI-format chat completion request through Converse and returns an OpenAI
+    /// `chat.completion`, so inference stays in the account the AWS credentials belong to.
+    pub async fn converse(&self, request: serde_json::Value) -> Result<serde_json::Value> {
+        let model = model_id(&request)?;
+        let body = serde_json::to_vec(&hypr_bedrock::converse_request(&request)?)?;
+
+        let state = self.manager.state::<crate::ManagedState>();
+        let path = hypr_bedrock::converse_path(&model, false);
+        let response = runtime::send(state.config().await, state.http(), &path, body).await?;
+
+        let id = request_id(&response);
+        let output: serde_json::Value = response.json().await?;
+        Ok(hypr_bedrock::chat_completion(
+            &output,
+            &id,
+            &model,
+            unix_now(),
+        )?)
+    }
+
+    /// Like [`Self::converse`] through ConverseStream, sending each `chat.completion.chunk`
+    /// as JSON on `channel`, then `[DONE]`.
+    pub async fn converse_stream(
+        &self,
+        request: serde_json::Value,
+        channel: Channel<String>,
+    ) -> Result<()> {
+        let model = model_id(&request)?;
+        let body = serde_json::to_vec(&hypr_bedrock::converse_request(&request)?)?;
+
+        let state = self.manager.state::<crate::ManagedState>();
+        let path = hypr_bedrock::converse_path(&model, true);
+        let response = runtime::send(state.config().await, state.http(), &path, body).await?;
+
+        let mut translator = StreamTranslator::new(request_id(&response), model, unix_now());
+        let mut decoder = EventStreamDecoder::new();
+        let mut upstream = response.bytes_stream();
+
+        while let Some(chunk) = upstream.next().await {
+            for message in decoder.push(&chunk?)? {
+                for event in translator.translate(&message)? {
+                    channel.send(event.to_string())?;
+                }
+            }
+        }
+
+        channel.send("[DONE]".to_string())?;
+        Ok(())
+    }
+}
+
+fn model_id(request: &serde_json::Value) -> Result<String> {
+    request
+        .get("model")
+        .and_then(|m| m.as_str())
+        .map(String::from)
+        .ok_or_else(|| Error::Bedrock("request has no model".to_string()))
+}
+
+fn request_id(response: &reqwest::Response) -> String {
+    response
+        .headers()
+        .get("x-amzn-requestid")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
 }
 
 pub trait BedrockPluginExt<R: tauri::Runtime> {
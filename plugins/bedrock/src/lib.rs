@@ -4,31 +4,43 @@ use tokio::sync::OnceCell;
 mod commands;
 mod error;
 mod ext;
+mod runtime;
 
 pub use error::{Error, Result};
 pub use ext::*;
 
 pub struct BedrockState {
+    config: OnceCell<aws_config::SdkConfig>,
     client: OnceCell<aws_sdk_bedrock::Client>,
+    http: reqwest::Client,
 }
 
 impl BedrockState {
     fn new() -> Self {
         Self {
+            config: OnceCell::new(),
             client: OnceCell::new(),
+            http: reqwest::Client::new(),
         }
     }
 
+    /// Region and credentials from the standard AWS chain: environment, profiles, SSO and so on.
+    pub async fn config(&self) -> &aws_config::SdkConfig {
+        self.config
+            .get_or_init(|| aws_config::defaults(aws_config::BehaviorVersion::latest()).load())
+            .await
+    }
+
     pub async fn client(&self) -> &aws_sdk_bedrock::Client {
+        let config = self.config().await;
         self.client
-            .get_or_init(|| async {
-                let config = aws_config::defaults(aws_config::BehaviorVersion::latest())
-                    .load()
-                    .await;
-                aws_sdk_bedrock::Client::new(&config)
-            })
+            .get_or_init(|| async { aws_sdk_bedrock::Client::new(config) })
             .await
     }
+
+    pub fn http(&self) -> &reqwest::Client {
+        &self.http
+    }
 }
 
 pub type ManagedState = BedrockState;
@@ -40,6 +52,8 @@ fn make_specta_builder<R: tauri::Runtime>() -> tauri_specta::Builder<R> {
         .plugin_name(PLUGIN_NAME)
         .commands(tauri_specta::collect_commands![
             commands::list_foundation_models::<tauri::Wry>,
+            commands::converse::<tauri::Wry>,
+            commands::converse_stream::<tauri::Wry>,
         ])
         .error_handling(tauri_specta::ErrorHandlingMode::Result)
 }
@@ -0,0 +1,70 @@
+use std::time::SystemTime;
+
+use aws_credential_types::provider::ProvideCredentials;
+use aws_sigv4::http_request::{SignableBody, SignableRequest, SigningSettings, sign};
+use aws_sigv4::sign::v4;
+
+use crate::{Error, Result};
+
+const SIGNING_NAME: &str = "bedrock";
+
+/// POSTs a SigV4-signed JSON body to `bedrock-runtime` in the configured region. Non-2xx
+/// responses become [`Error::Bedrock`] with the service's message.
+pub(crate) async fn send(
+    config: &aws_config::SdkConfig,
+    http: &reqwest::Client,
+    path: &str,
+    body: Vec<u8>,
+) -> Result<reqwest::Response> {
+    let region = config
+        .region()
+        .ok_or_else(|| Error::AwsSdk("no AWS region configured".to_string()))?;
+    let credentials = config
+        .credentials_provider()
+        .ok_or_else(|| Error::AwsSdk("no AWS credentials configured".to_string()))?
+        .provide_credentials()
+        .await
+        .map_err(|e| Error::AwsSdk(e.to_string()))?;
+
+    let url = format!("https://bedrock-runtime.{region}.amazonaws.com{path}");
+    let headers = [("content-type", "application/json")];
+
+    let identity = credentials.into();
+    let params = v4::SigningParams::builder()
+        .identity(&identity)
+        .region(region.as_ref())
+        .name(SIGNING_NAME)
+        .time(SystemTime::now())
+        .settings(SigningSettings::default())
+        .build()
+        .map_err(|e| Error::AwsSdk(e.to_string()))?
+        .into();
+    let signable = SignableRequest::new(
+        "POST",
+        &url,
+        headers.into_iter(),
+        SignableBody::Bytes(&body),
+    )
+    .map_err(|e| Error::AwsSdk(e.to_string()))?;
+    let (instructions, _) = sign(signable, &params)
+        .map_err(|e| Error::AwsSdk(e.to_string()))?
+        .into_parts();
+
+    let mut request = http.post(&url).body(body);
+    for (name, value) in headers.into_iter().chain(instructions.headers()) {
+        request = request.header(name, value);
+    }
+
+    let response = request.send().await?;
+    if response.status().is_success() {
+        return Ok(response);
+    }
+
+    let status = response.status();
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    let message = body
+        .get("message")
+        .and_then(|m| m.as_str())
+        .map_or_else(|| status.to_string(), String::from);
+    Err(Error::Bedrock(message))
+}
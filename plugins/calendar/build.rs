@@ -5,6 +5,7 @@ const COMMANDS: &[&str] = &[
     "list_events",
     "open_calendar",
     "create_event",
+    "set_caldav_password",
 ];
 
 fn main() {
@@ -48,6 +48,14 @@ async createEvent(provider: CalendarProviderType, input: CreateEventInput) : Pro
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
+},
+async setCaldavPassword(accountId: string, password: string) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plugin:calendar|set_caldav_password", { accountId, password }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
 }
 }
 
@@ -71,7 +79,9 @@ export type AttendeeStatus = "pending" | "accepted" | "tentative" | "declined"
 export type CalendarChangedEvent = null
 export type CalendarEvent = { provider: CalendarProviderType; 
 /**
- * Unique between events. Synthesized for Apple events (eventIdentifier:YYYY-MM-DD for recurring).
+ * Unique between events. Synthesized for Apple events (eventIdentifier:YYYY-MM-DD for recurring),
+ * and for recurring CalDAV/ICS events from the occurrence's RECURRENCE-ID
+ * (UID:YYYYMMDDTHHMMSSZ, or UID:YYYYMMDD for all-day events).
  */
 id: string; 
 /**
@@ -80,13 +90,18 @@ id: string;
 calendar_id: string; 
 /**
  * iCal identifier used for deduplication.
- * Apple: calendarItemExternalIdentifier, Google: iCalUID.
+ * Apple: calendarItemExternalIdentifier, Google: iCalUID, CalDAV/ICS: UID.
  */
 external_id: string; title: string; description: string | null; location: string | null; url: string | null; 
 /**
- * Parsed from notes for Apple, Google provides url directly.
+ * Join URL. Google and Outlook provide one directly; otherwise `meeting.url`.
  */
 meeting_link: string | null; 
+/**
+ * Platform, meeting id, passcode and dial-in of the event's video meeting, detected from
+ * conference data, url, location and description for every provider.
+ */
+meeting: MeetingLink | null; 
 /**
  * ISO 8601. For Google, start of day for all day events (Apple already does that).
  */
@@ -107,11 +122,11 @@ status: EventStatus; organizer: EventPerson | null; attendees: EventAttendee[];
  */
 recurring_event_id: string | null; 
 /**
- * Raw data. JSON for both Apple and Google.
+ * Raw data. JSON for both Apple and Google, the VEVENT text for CalDAV/ICS.
  */
 raw: string }
 export type CalendarListItem = { provider: CalendarProviderType; id: string; title: string; source: string | null; color: string | null; is_primary: boolean | null; can_edit: boolean | null; raw: string }
-export type CalendarProviderType = "apple" | "google" | "outlook"
+export type CalendarProviderType = "apple" | "google" | "outlook" | "caldav" | "ics"
 export type CreateEventInput = { calendar_tracking_id: string; title: string; started_at: string; ended_at: string; is_all_day: boolean | null; location: string | null; notes: string | null; url: string | null }
 export type EventAttendee = { name: string | null; 
 /**
@@ -149,6 +164,28 @@ email: string | null;
  */
 is_current_user: boolean }
 export type EventStatus = "confirmed" | "tentative" | "cancelled"
+/**
+ * A video meeting recognized in an event's conference data, url, location or description.
+ */
+export type MeetingLink = { platform: MeetingPlatform; 
+/**
+ * Join URL, as written in the event.
+ */
+url: string; 
+/**
+ * Zoom/Teams/Webex/Chime meeting number without spaces, the Meet code, or the room name
+ * for Whereby and Jitsi.
+ */
+meeting_id: string | null; 
+/**
+ * Passcode or dial-in PIN, when the invite spells it out.
+ */
+passcode: string | null; 
+/**
+ * First dial-in phone number, e.g. `+1 669 444 9171`.
+ */
+dial_in: string | null }
+export type MeetingPlatform = "zoom" | "google_meet" | "teams" | "webex" | "whereby" | "jitsi" | "chime" | "slack_huddle"
 
 /** tauri-specta globals **/
 
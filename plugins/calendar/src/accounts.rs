@@ -0,0 +1,130 @@
+use hypr_caldav_calendar::CaldavClient;
+
+use crate::error::Error;
+
+const SETTINGS_KEY: &str = "calendar";
+const KEYCHAIN_SERVICE: &str = "com.hyprnote.calendar.caldav";
+
+/// The `calendar` section of settings.json. CalDAV accounts and ICS feeds have no OAuth flow, so
+/// they are configured there rather than connected through the web app.
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+pub struct CalendarSettings {
+    #[serde(default)]
+    pub caldav: Vec<CaldavAccount>,
+    #[serde(default)]
+    pub ics: Vec<IcsFeed>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+pub struct CaldavAccount {
+    /// Stable identifier, used as the prefix of calendar ids. Must not contain `:`.
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    /// DAV root, principal or calendar URL, e.g. `https://cloud.example.com/remote.php/dav`.
+    pub url: String,
+    pub username: String,
+    /// The password is not part of settings; it lives in the OS keychain under the account id.
+    /// Address the account receives invites on. Defaults to `username` when that is an email.
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct IcsFeed {
+    /// Stable identifier, used as the calendar id.
+    pub id: String,
+    #[serde(default)]
+    pub name: Option<String>,
+    /// `http(s)://` or `webcal://` URL, or a path to a local `.ics` file.
+    pub url: String,
+    #[serde(default)]
+    pub color: Option<String>,
+}
+
+impl CalendarSettings {
+    pub fn from_settings(settings: &serde_json::Value) -> Self {
+        match settings.get(SETTINGS_KEY) {
+            Some(value) => serde_json::from_value(value.clone()).unwrap_or_else(|e| {
+                tracing::warn!(error.message = %e, "calendar_settings_invalid");
+                Self::default()
+            }),
+            None => Self::default(),
+        }
+    }
+
+    /// Resolves an id built by [`caldav_calendar_id`] to its account and collection URL.
+    pub fn caldav_calendar<'a>(&'a self, id: &'a str) -> Option<(&'a CaldavAccount, &'a str)> {
+        let (account_id, url) = id.split_once(':')?;
+        let account = self.caldav.iter().find(|a| a.id == account_id)?;
+        Some((account, url))
+    }
+
+    pub fn ics_feed(&self, id: &str) -> Option<&IcsFeed> {
+        self.ics.iter().find(|feed| feed.id == id)
+    }
+}
+
+impl CaldavAccount {
+    pub fn client(&self) -> Result<CaldavClient, Error> {
+        let password = match keychain_entry(&self.id)?.get_password() {
+            Ok(password) => password,
+            Err(keyring::Error::NoEntry) => return Err(Error::MissingPassword(self.id.clone())),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(CaldavClient::new(&self.url, &self.username, password)?)
+    }
+
+    pub fn owner_email(&self) -> Option<&str> {
+        self.email.as_deref().or_else(|| {
+            self.username
+                .contains('@')
+                .then_some(self.username.as_str())
+        })
+    }
+}
+
+pub fn set_caldav_password(account_id: &str, password: &str) -> Result<(), Error> {
+    Ok(keychain_entry(account_id)?.set_password(password)?)
+}
+
+/// Moves passwords that older versions kept in settings.json into the keychain. Returns whether
+/// `settings` changed and should be saved.
+pub fn migrate_caldav_passwords(settings: &mut serde_json::Value) -> Result<bool, Error> {
+    let Some(accounts) = settings
+        .get_mut(SETTINGS_KEY)
+        .and_then(|calendar| calendar.get_mut("caldav"))
+        .and_then(serde_json::Value::as_array_mut)
+    else {
+        return Ok(false);
+    };
+
+    let mut changed = false;
+    for account in accounts
+        .iter_mut()
+        .filter_map(serde_json::Value::as_object_mut)
+    {
+        let Some(id) = account
+            .get("id")
+            .and_then(|id| id.as_str())
+            .map(str::to_string)
+        else {
+            continue;
+        };
+        if let Some(serde_json::Value::String(password)) = account.get("password") {
+            set_caldav_password(&id, password)?;
+        }
+        changed |= account.remove("password").is_some();
+    }
+
+    Ok(changed)
+}
+
+fn keychain_entry(account_id: &str) -> Result<keyring::Entry, Error> {
+    Ok(keyring::Entry::new(KEYCHAIN_SERVICE, account_id)?)
+}
+
+/// Collection URLs are only unique per account, so calendar ids carry the account id as well.
+pub fn caldav_calendar_id(account_id: &str, url: &str) -> String {
+    format!("{account_id}:{url}")
+}
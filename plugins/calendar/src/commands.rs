@@ -57,3 +57,13 @@ pub fn create_event<R: tauri::Runtime>(
 ) -> Result<String, Error> {
     app.calendar().create_event(provider, input)
 }
+
+#[tauri::command]
+#[specta::specta]
+pub async fn set_caldav_password<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    account_id: String,
+    password: String,
+) -> Result<(), Error> {
+    app.calendar().set_caldav_password(&account_id, &password)
+}
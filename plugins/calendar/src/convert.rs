@@ -2,20 +2,24 @@ use hypr_apple_calendar::types::{
     AppleCalendar, AppleEvent, EventStatus as AppleEventStatus, Participant, ParticipantRole,
     ParticipantStatus,
 };
+use hypr_caldav_calendar::CaldavCalendar;
 use hypr_calendar_interface::{
     AttendeeRole, AttendeeStatus, CalendarEvent, CalendarListItem, CalendarProviderType,
-    EventAttendee, EventPerson, EventStatus,
+    EventAttendee, EventFilter, EventPerson, EventStatus, MeetingLink,
 };
 use hypr_google_calendar::{
     AccessRole as GoogleAccessRole, Attendee as GoogleAttendee, AttendeeResponseStatus,
     CalendarListEntry as GoogleCalendar, Event as GoogleEvent, EventDateTime,
     EventStatus as GoogleEventStatus,
 };
+use hypr_ics_calendar::{ExpandOptions, IcsCalendar};
 use hypr_outlook_calendar::{
     Attendee as OutlookAttendee, AttendeeType, Calendar as OutlookCalendar, Event as OutlookEvent,
     EventShowAs, ResponseType as OutlookResponseType,
 };
 
+use crate::accounts::{CaldavAccount, IcsFeed, caldav_calendar_id};
+
 pub fn convert_google_calendars(calendars: Vec<GoogleCalendar>) -> Vec<CalendarListItem> {
     calendars
         .into_iter()
@@ -87,6 +91,50 @@ pub fn convert_apple_calendars(calendars: Vec<AppleCalendar>) -> Vec<CalendarLis
         .collect()
 }
 
+pub fn convert_caldav_calendars(
+    account: &CaldavAccount,
+    calendars: Vec<CaldavCalendar>,
+) -> Vec<CalendarListItem> {
+    calendars
+        .into_iter()
+        .map(|calendar| {
+            let raw = serde_json::to_string(&calendar).unwrap_or_default();
+
+            CalendarListItem {
+                provider: CalendarProviderType::Caldav,
+                id: caldav_calendar_id(&account.id, &calendar.url),
+                title: calendar
+                    .display_name
+                    .unwrap_or_else(|| "Untitled".to_string()),
+                source: Some(account.name.clone().unwrap_or(account.username.clone())),
+                color: calendar.color,
+                is_primary: None,
+                can_edit: calendar.can_edit,
+                raw,
+            }
+        })
+        .collect()
+}
+
+pub fn convert_ics_calendar(feed: &IcsFeed, calendar: &IcsCalendar) -> CalendarListItem {
+    let raw = serde_json::to_string(feed).unwrap_or_default();
+
+    CalendarListItem {
+        provider: CalendarProviderType::Ics,
+        id: feed.id.clone(),
+        title: feed
+            .name
+            .clone()
+            .or(calendar.name.clone())
+            .unwrap_or_else(|| feed.url.clone()),
+        source: calendar.description.clone(),
+        color: feed.color.clone().or(calendar.color.clone()),
+        is_primary: None,
+        can_edit: Some(false),
+        raw,
+    }
+}
+
 fn apple_color_to_css(color: hypr_apple_calendar::types::CalendarColor) -> String {
     format!(
         "rgba({}, {}, {}, {})",
@@ -115,6 +163,25 @@ pub fn convert_apple_events(events: Vec<AppleEvent>) -> Vec<CalendarEvent> {
     events.into_iter().map(convert_apple_event).collect()
 }
 
+/// Expands recurring series from CalDAV resources or an ICS feed into the filter's range.
+pub fn convert_ics_events(
+    calendars: &[IcsCalendar],
+    provider: CalendarProviderType,
+    filter: &EventFilter,
+    owner_email: Option<&str>,
+) -> Vec<CalendarEvent> {
+    let options = ExpandOptions {
+        provider,
+        calendar_id: &filter.calendar_tracking_id,
+        owner_email,
+    };
+
+    calendars
+        .iter()
+        .flat_map(|calendar| calendar.events_between(filter.from, filter.to, &options))
+        .collect()
+}
+
 fn convert_google_event(event: GoogleEvent, calendar_id: &str) -> CalendarEvent {
     let raw = serde_json::to_string(&event).unwrap_or_default();
 
@@ -149,10 +216,12 @@ fn convert_google_event(event: GoogleEvent, calendar_id: &str) -> CalendarEvent
         .map(convert_google_attendee)
         .collect();
 
-    let meeting_link = event
+    let provided_link = event
         .hangout_link
         .clone()
         .or_else(|| extract_video_entry_point(&event));
+    let meeting = detect_google_meeting(&event, provided_link.as_deref());
+    let meeting_link = provided_link.or_else(|| meeting.as_ref().map(|m| m.url.clone()));
 
     let has_recurrence_rules = event.recurring_event_id.is_some()
         || event.recurrence.as_ref().is_some_and(|r| !r.is_empty());
@@ -167,6 +236,7 @@ fn convert_google_event(event: GoogleEvent, calendar_id: &str) -> CalendarEvent
         location: event.location,
         url: event.html_link,
         meeting_link,
+        meeting,
         started_at,
         ended_at,
         timezone,
@@ -218,12 +288,24 @@ fn convert_outlook_event(event: OutlookEvent, calendar_id: &str) -> CalendarEven
         .map(convert_outlook_attendee)
         .collect();
 
-    let meeting_link = event.online_meeting_url.clone().or_else(|| {
+    let provided_link = event.online_meeting_url.clone().or_else(|| {
         event
             .online_meeting
             .as_ref()
             .and_then(|meeting| meeting.join_url.clone())
     });
+    let description = event.body.and_then(|body| body.content);
+    let location = event.location.and_then(|location| location.display_name);
+    let meeting = MeetingLink::find(
+        [
+            provided_link.as_deref(),
+            location.as_deref(),
+            description.as_deref(),
+        ]
+        .into_iter()
+        .flatten(),
+    );
+    let meeting_link = provided_link.or_else(|| meeting.as_ref().map(|m| m.url.clone()));
 
     CalendarEvent {
         id: event.id,
@@ -231,10 +313,11 @@ fn convert_outlook_event(event: OutlookEvent, calendar_id: &str) -> CalendarEven
         provider: CalendarProviderType::Outlook,
         external_id: event.ical_uid.unwrap_or_default(),
         title: event.subject.unwrap_or_default(),
-        description: event.body.and_then(|body| body.content),
-        location: event.location.and_then(|location| location.display_name),
+        description,
+        location,
         url: event.web_link,
         meeting_link,
+        meeting,
         started_at,
         ended_at,
         timezone,
@@ -262,6 +345,16 @@ fn convert_apple_event(event: AppleEvent) -> CalendarEvent {
     let organizer = event.organizer.as_ref().map(convert_person);
     let attendees = event.attendees.iter().map(convert_apple_attendee).collect();
 
+    let meeting = MeetingLink::find(
+        [
+            event.url.as_deref(),
+            event.location.as_deref(),
+            event.notes.as_deref(),
+        ]
+        .into_iter()
+        .flatten(),
+    );
+
     let recurring_event_id = if event.has_recurrence_rules {
         Some(
             event
@@ -283,7 +376,8 @@ fn convert_apple_event(event: AppleEvent) -> CalendarEvent {
         description: event.notes,
         location: event.location,
         url: event.url,
-        meeting_link: None,
+        meeting_link: meeting.as_ref().map(|m| m.url.clone()),
+        meeting,
         started_at: event.start_date.to_rfc3339(),
         ended_at: event.end_date.to_rfc3339(),
         timezone: event.time_zone,
@@ -357,6 +451,50 @@ fn extract_video_entry_point(event: &GoogleEvent) -> Option<String> {
         .map(|ep| ep.uri.clone())
 }
 
+/// Conference data carries the meeting code, passcode and phone entry point separately, so it
+/// fills in what the join URL alone does not tell.
+fn detect_google_meeting(event: &GoogleEvent, provided_link: Option<&str>) -> Option<MeetingLink> {
+    let mut meeting = MeetingLink::find(
+        [
+            provided_link,
+            event.location.as_deref(),
+            event.description.as_deref(),
+        ]
+        .into_iter()
+        .flatten(),
+    )?;
+
+    let entry_points = event
+        .conference_data
+        .as_ref()
+        .and_then(|data| data.entry_points.as_deref())
+        .unwrap_or_default();
+    for entry_point in entry_points {
+        match entry_point.entry_point_type {
+            hypr_google_calendar::EntryPointType::Video => {
+                if meeting.meeting_id.is_none() {
+                    meeting.meeting_id = entry_point.meeting_code.clone();
+                }
+                if meeting.passcode.is_none() {
+                    meeting.passcode = entry_point
+                        .passcode
+                        .clone()
+                        .or_else(|| entry_point.password.clone());
+                }
+            }
+            hypr_google_calendar::EntryPointType::Phone => {
+                meeting.fill_from_text(&entry_point.uri);
+                if meeting.passcode.is_none() {
+                    meeting.passcode = entry_point.pin.clone();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(meeting)
+}
+
 fn convert_outlook_status(is_cancelled: Option<bool>, show_as: Option<EventShowAs>) -> EventStatus {
     if is_cancelled.unwrap_or(false) {
         EventStatus::Cancelled
@@ -23,6 +23,18 @@ pub enum Error {
     Api(String),
     #[error("apple calendar error: {0}")]
     Apple(String),
+    #[error("caldav error: {0}")]
+    Caldav(#[from] hypr_caldav_calendar::Error),
+    #[error("no password stored for caldav account '{0}'")]
+    MissingPassword(String),
+    #[error("keychain error: {0}")]
+    Keychain(#[from] keyring::Error),
+    #[error("ics error: {0}")]
+    Ics(#[from] hypr_ics_calendar::Error),
+    #[error("settings error: {0}")]
+    Settings(String),
+    #[error("unknown calendar: {0}")]
+    UnknownCalendar(String),
 }
 
 impl serde::Serialize for Error {
@@ -7,7 +7,9 @@ use hypr_google_calendar::{CalendarListEntry as GoogleCalendar, Event as GoogleE
 use hypr_outlook_calendar::{Calendar as OutlookCalendar, Event as OutlookEvent};
 use tauri_plugin_auth::AuthPluginExt;
 use tauri_plugin_permissions::PermissionsPluginExt;
+use tauri_plugin_settings::SettingsPluginExt;
 
+use crate::accounts::CalendarSettings;
 use crate::error::Error;
 use crate::fetch;
 
@@ -22,10 +24,17 @@ pub fn available_providers() -> Vec<CalendarProviderType> {
         CalendarProviderType::Apple,
         CalendarProviderType::Google,
         CalendarProviderType::Outlook,
+        CalendarProviderType::Caldav,
+        CalendarProviderType::Ics,
     ];
 
     #[cfg(not(target_os = "macos"))]
-    let providers = vec![CalendarProviderType::Google, CalendarProviderType::Outlook];
+    let providers = vec![
+        CalendarProviderType::Google,
+        CalendarProviderType::Outlook,
+        CalendarProviderType::Caldav,
+        CalendarProviderType::Ics,
+    ];
 
     providers
 }
@@ -48,6 +57,24 @@ impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> CalendarExt<'a, R, M> {
                 let calendars = self.list_outlook_calendars().await?;
                 Ok(crate::convert::convert_outlook_calendars(calendars))
             }
+            CalendarProviderType::Caldav => {
+                let settings = self.calendar_settings().await?;
+                let mut items = Vec::new();
+                for account in &settings.caldav {
+                    let calendars = fetch::list_caldav_calendars(account).await?;
+                    items.extend(crate::convert::convert_caldav_calendars(account, calendars));
+                }
+                Ok(items)
+            }
+            CalendarProviderType::Ics => {
+                let settings = self.calendar_settings().await?;
+                let mut items = Vec::new();
+                for feed in &settings.ics {
+                    let calendar = fetch::fetch_ics_feed(feed).await?;
+                    items.push(crate::convert::convert_ics_calendar(feed, &calendar));
+                }
+                Ok(items)
+            }
         }
     }
 
@@ -71,6 +98,32 @@ impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> CalendarExt<'a, R, M> {
                 let events = self.list_outlook_events(filter).await?;
                 Ok(crate::convert::convert_outlook_events(events, &calendar_id))
             }
+            CalendarProviderType::Caldav => {
+                let settings = self.calendar_settings().await?;
+                let (account, calendar_url) = settings
+                    .caldav_calendar(&filter.calendar_tracking_id)
+                    .ok_or_else(|| Error::UnknownCalendar(filter.calendar_tracking_id.clone()))?;
+                let calendars = fetch::list_caldav_events(account, calendar_url, &filter).await?;
+                Ok(crate::convert::convert_ics_events(
+                    &calendars,
+                    provider,
+                    &filter,
+                    account.owner_email(),
+                ))
+            }
+            CalendarProviderType::Ics => {
+                let settings = self.calendar_settings().await?;
+                let feed = settings
+                    .ics_feed(&filter.calendar_tracking_id)
+                    .ok_or_else(|| Error::UnknownCalendar(filter.calendar_tracking_id.clone()))?;
+                let calendar = fetch::fetch_ics_feed(feed).await?;
+                Ok(crate::convert::convert_ics_events(
+                    &[calendar],
+                    provider,
+                    &filter,
+                    None,
+                ))
+            }
         }
     }
 
@@ -129,7 +182,32 @@ impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> CalendarExt<'a, R, M> {
                 let config = self.manager.state::<crate::PluginConfig>();
                 fetch::has_nango_connection(&config.api_base_url, &token, "outlook-calendar").await
             }
+            CalendarProviderType::Caldav => Ok(!self.calendar_settings().await?.caldav.is_empty()),
+            CalendarProviderType::Ics => Ok(!self.calendar_settings().await?.ics.is_empty()),
+        }
+    }
+
+    pub fn set_caldav_password(&self, account_id: &str, password: &str) -> Result<(), Error> {
+        crate::accounts::set_caldav_password(account_id, password)
+    }
+
+    async fn calendar_settings(&self) -> Result<CalendarSettings, Error> {
+        let mut settings = self
+            .manager
+            .settings()
+            .load()
+            .await
+            .map_err(|e| Error::Settings(e.to_string()))?;
+
+        if crate::accounts::migrate_caldav_passwords(&mut settings)? {
+            self.manager
+                .settings()
+                .save(settings.clone())
+                .await
+                .map_err(|e| Error::Settings(e.to_string()))?;
         }
+
+        Ok(CalendarSettings::from_settings(&settings))
     }
 
     fn get_access_token(&self) -> Result<String, Error> {
@@ -1,7 +1,10 @@
+use hypr_caldav_calendar::CaldavCalendar;
 use hypr_calendar_interface::EventFilter;
 use hypr_google_calendar::{CalendarListEntry as GoogleCalendar, Event as GoogleEvent};
+use hypr_ics_calendar::IcsCalendar;
 use hypr_outlook_calendar::{Calendar as OutlookCalendar, Event as OutlookEvent};
 
+use crate::accounts::{CaldavAccount, IcsFeed};
 use crate::error::Error;
 
 pub async fn has_nango_connection(
@@ -107,3 +110,35 @@ pub async fn list_outlook_events(
 
     Ok(response.into_inner().value)
 }
+
+pub async fn list_caldav_calendars(account: &CaldavAccount) -> Result<Vec<CaldavCalendar>, Error> {
+    Ok(account.client()?.list_calendars().await?)
+}
+
+pub async fn list_caldav_events(
+    account: &CaldavAccount,
+    calendar_url: &str,
+    filter: &EventFilter,
+) -> Result<Vec<IcsCalendar>, Error> {
+    let documents = account
+        .client()?
+        .list_events(calendar_url, filter.from, filter.to)
+        .await?;
+
+    // Each event is its own resource; one the parser rejects shouldn't hide the others.
+    Ok(documents
+        .iter()
+        .filter_map(|document| match IcsCalendar::parse(document) {
+            Ok(calendar) => Some(calendar),
+            Err(e) => {
+                tracing::warn!(error.message = %e, "caldav_event_parse_failed");
+                None
+            }
+        })
+        .collect())
+}
+
+pub async fn fetch_ics_feed(feed: &IcsFeed) -> Result<IcsCalendar, Error> {
+    let text = hypr_ics_calendar::fetch_feed(&feed.url).await?;
+    Ok(IcsCalendar::parse(&text)?)
+}
@@ -1,3 +1,4 @@
+mod accounts;
 mod commands;
 mod convert;
 mod error;
@@ -25,6 +26,7 @@ fn make_specta_builder<R: tauri::Runtime>() -> tauri_specta::Builder<R> {
             commands::list_events::<tauri::Wry>,
             commands::open_calendar::<tauri::Wry>,
             commands::create_event::<tauri::Wry>,
+            commands::set_caldav_password::<tauri::Wry>,
         ])
         .events(tauri_specta::collect_events![CalendarChangedEvent])
         .error_handling(tauri_specta::ErrorHandlingMode::Result)
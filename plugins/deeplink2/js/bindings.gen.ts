@@ -41,9 +41,40 @@ deepLinkEvent: "plugin:deeplink2:deep-link-event"
 
 export type AuthCallbackSearch = { access_token: string; refresh_token: string }
 export type BillingRefreshSearch = Record<string, never>
-export type DeepLink = { to: "/auth/callback"; search: AuthCallbackSearch } | { to: "/billing/refresh"; search: BillingRefreshSearch } | { to: "/integration/callback"; search: IntegrationCallbackSearch }
+export type DeepLink = { to: "/auth/callback"; search: AuthCallbackSearch } | { to: "/billing/refresh"; search: BillingRefreshSearch } | { to: "/integration/callback"; search: IntegrationCallbackSearch } | { to: "/listen/start"; search: ListenStartSearch } | { to: "/listen/stop"; search: ListenStopSearch } | { to: "/session/new"; search: SessionNewSearch } | { to: "/session/open"; search: SessionOpenSearch } | { to: "/search"; search: SearchQuerySearch } | { to: "/import"; search: ImportSearch }
+/**
+ * Deep links that drive the app rather than complete a flow it started. External callers
+ * can only trigger the ones listed in `DeepLinkSettings::allowed_actions`.
+ */
+export type DeepLinkAction = "listen_start" | "listen_stop" | "session_new" | "session_open" | "search" | "import"
 export type DeepLinkEvent = DeepLink
+/**
+ * Stored under `deeplink` in the vault's settings file.
+ */
+export type DeepLinkSettings = { allowed_actions?: DeepLinkAction[] }
+export type ImportSearch = { 
+/**
+ * Absolute path to a Markdown vault directory or an exported JSON file.
+ */
+path: string }
 export type IntegrationCallbackSearch = { integration_id: string; status: string; return_to: string | null }
+export type ListenStartSearch = { 
+/**
+ * Calendar event to attach the new session to.
+ */
+event_id: string | null; 
+/**
+ * Spoken language for this recording, e.g. `en` or `pt-BR`.
+ */
+language: string | null }
+export type ListenStopSearch = Record<string, never>
+export type SearchQuerySearch = { q: string }
+export type SessionNewSearch = { title: string | null; 
+/**
+ * Template to enhance the note with.
+ */
+template: string | null }
+export type SessionOpenSearch = { id: string }
 
 /** tauri-specta globals **/
 
@@ -12,6 +12,13 @@ pub enum Error {
     UrlParse(#[from] url::ParseError),
     #[error("query decode error: {0}")]
     QueryDecode(#[from] serde_qs::Error),
+    #[error("{0} is not a callback and cannot be delivered through the callback server")]
+    NotACallback(&'static str),
+    #[error("invalid deep link parameter {name}: {reason}")]
+    InvalidParam {
+        name: &'static str,
+        reason: &'static str,
+    },
 }
 
 impl Serialize for Error {
@@ -8,16 +8,20 @@ mod docs;
 
 pub use error::{Error, Result};
 pub use types::{
-    AuthCallbackSearch, BillingRefreshSearch, DeepLink, DeepLinkEvent, IntegrationCallbackSearch,
+    AuthCallbackSearch, BillingRefreshSearch, DeepLink, DeepLinkAction, DeepLinkEvent,
+    DeepLinkSettings, ImportSearch, IntegrationCallbackSearch, ListenStartSearch, ListenStopSearch,
+    SearchQuerySearch, SessionNewSearch, SessionOpenSearch,
 };
 
 use std::str::FromStr;
 
 use tauri::Manager;
 use tauri_plugin_deep_link::DeepLinkExt;
+use tauri_plugin_settings::SettingsPluginExt;
 use tauri_specta::Event;
 
 const PLUGIN_NAME: &str = "deeplink2";
+const SETTINGS_KEY: &str = "deeplink";
 
 fn redact_url(url_str: &str) -> String {
     match url::Url::parse(url_str) {
@@ -31,6 +35,30 @@ fn redact_url(url_str: &str) -> String {
     }
 }
 
+async fn load_settings<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> DeepLinkSettings {
+    let settings = match app.settings().load().await {
+        Ok(settings) => settings,
+        Err(e) => {
+            tracing::warn!(error.message = %e, "deeplink_settings_load_failed");
+            return DeepLinkSettings::default();
+        }
+    };
+
+    match settings.get(SETTINGS_KEY) {
+        Some(value) => serde_json::from_value(value.clone()).unwrap_or_else(|e| {
+            tracing::warn!(error.message = %e, "deeplink_settings_invalid");
+            DeepLinkSettings::default()
+        }),
+        None => DeepLinkSettings::default(),
+    }
+}
+
+fn emit_deep_link<R: tauri::Runtime>(app: &tauri::AppHandle<R>, deep_link: DeepLink) {
+    if let Err(e) = DeepLinkEvent(deep_link).emit(app) {
+        tracing::error!(error = ?e, "deeplink_event_emit_failed");
+    }
+}
+
 fn make_specta_builder<R: tauri::Runtime>() -> tauri_specta::Builder<R> {
     tauri_specta::Builder::<R>::new()
         .plugin_name(PLUGIN_NAME)
@@ -40,6 +68,7 @@ fn make_specta_builder<R: tauri::Runtime>() -> tauri_specta::Builder<R> {
         ])
         .events(tauri_specta::collect_events![types::DeepLinkEvent])
         .typ::<types::DeepLink>()
+        .typ::<types::DeepLinkSettings>()
         .error_handling(tauri_specta::ErrorHandlingMode::Result)
 }
 
@@ -63,9 +92,22 @@ pub fn init<R: tauri::Runtime>() -> tauri::plugin::TauriPlugin<R> {
                     match DeepLink::from_str(url_str) {
                         Ok(deep_link) => {
                             tracing::info!(path = deep_link.path(), "deeplink_parsed");
-                            if let Err(e) = DeepLinkEvent(deep_link).emit(&app_handle) {
-                                tracing::error!(error = ?e, "deeplink_event_emit_failed");
-                            }
+                            let Some(action) = deep_link.action() else {
+                                emit_deep_link(&app_handle, deep_link);
+                                continue;
+                            };
+
+                            let app_handle = app_handle.clone();
+                            tauri::async_runtime::spawn(async move {
+                                if load_settings(&app_handle).await.allows(action) {
+                                    emit_deep_link(&app_handle, deep_link);
+                                } else {
+                                    tracing::warn!(
+                                        action = action.as_ref(),
+                                        "deeplink_action_not_allowed"
+                                    );
+                                }
+                            });
                         }
                         Err(e) => {
                             tracing::debug!(error = ?e, url = %redacted, "deeplink_parse_failed");
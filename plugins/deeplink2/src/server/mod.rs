@@ -58,6 +58,8 @@ pub fn render_html_from_callback(path: &str, query: &str, scheme: &str) -> Strin
     render_html_from_parse_result(parse_result.as_ref(), scheme)
 }
 
+/// Only callbacks of flows the app started itself are accepted. Any local web page can reach
+/// this server while it runs, so action links must go through the OS handler and its allow-list.
 pub fn parse_callback(path: &str, query: &str) -> Result<DeepLink, crate::Error> {
     let path = path.trim_start_matches('/');
     let pseudo_url = if query.is_empty() {
@@ -66,7 +68,11 @@ pub fn parse_callback(path: &str, query: &str) -> Result<DeepLink, crate::Error>
         format!("local://{path}?{query}")
     };
 
-    DeepLink::from_str(&pseudo_url)
+    let deep_link = DeepLink::from_str(&pseudo_url)?;
+    if deep_link.action().is_some() {
+        return Err(crate::Error::NotACallback(deep_link.path()));
+    }
+    Ok(deep_link)
 }
 
 fn render_html_from_parse_result<E>(parse_result: Result<&DeepLink, &E>, scheme: &str) -> String {
@@ -122,6 +128,16 @@ fn ui_content(deep_link: &DeepLink) -> (bool, &'static str, &'static str) {
             "Connection failed",
             "Something went wrong. Please close this window and try again.",
         ),
+        DeepLink::ListenStart(_)
+        | DeepLink::ListenStop(_)
+        | DeepLink::SessionNew(_)
+        | DeepLink::SessionOpen(_)
+        | DeepLink::Search(_)
+        | DeepLink::Import(_) => (
+            true,
+            "Opening Char",
+            "Click the button below to return to the app.",
+        ),
     }
 }
 
@@ -264,3 +280,34 @@ pub async fn stop<R: tauri::Runtime>(app: tauri::AppHandle<R>) -> Result<(), Str
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_callback_server_rejects_action_links() {
+        for (path, query) in [
+            ("/listen/start", ""),
+            ("/listen/stop", ""),
+            ("/session/new", "title=x"),
+            ("/session/open", "id=abc"),
+            ("/search", "q=pricing"),
+            ("/import", "path=%2FUsers%2Fme%2Fvault"),
+        ] {
+            assert!(
+                matches!(
+                    parse_callback(path, query),
+                    Err(crate::Error::NotACallback(_))
+                ),
+                "{path} must not be emitted from the callback server"
+            );
+        }
+
+        let link = parse_callback("/auth/callback", "access_token=a&refresh_token=b").unwrap();
+        assert!(matches!(link, DeepLink::AuthCallback(_)));
+
+        let html = render_html_from_callback("/listen/start", "", "char");
+        assert!(html.contains("Something went wrong"));
+    }
+}
@@ -0,0 +1,49 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Deep links that drive the app rather than complete a flow it started. External callers
+/// can only trigger the ones listed in `DeepLinkSettings::allowed_actions`.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Type, strum::AsRefStr,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum DeepLinkAction {
+    ListenStart,
+    ListenStop,
+    SessionNew,
+    SessionOpen,
+    Search,
+    Import,
+}
+
+/// Stored under `deeplink` in the vault's settings file.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DeepLinkSettings {
+    #[serde(default = "default_allowed_actions")]
+    pub allowed_actions: Vec<DeepLinkAction>,
+}
+
+impl Default for DeepLinkSettings {
+    fn default() -> Self {
+        Self {
+            allowed_actions: default_allowed_actions(),
+        }
+    }
+}
+
+// Starting a recording or importing files from a link has to be opted into.
+fn default_allowed_actions() -> Vec<DeepLinkAction> {
+    vec![
+        DeepLinkAction::ListenStop,
+        DeepLinkAction::SessionNew,
+        DeepLinkAction::SessionOpen,
+        DeepLinkAction::Search,
+    ]
+}
+
+impl DeepLinkSettings {
+    pub fn allows(&self, action: DeepLinkAction) -> bool {
+        self.allowed_actions.contains(&action)
+    }
+}
@@ -17,3 +17,5 @@ impl fmt::Debug for AuthCallbackSearch {
             .finish()
     }
 }
+
+impl super::Validate for AuthCallbackSearch {}
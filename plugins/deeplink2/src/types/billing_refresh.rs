@@ -3,3 +3,5 @@ use specta::Type;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct BillingRefreshSearch {}
+
+impl super::Validate for BillingRefreshSearch {}
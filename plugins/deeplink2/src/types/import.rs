@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use super::Validate;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ImportSearch {
+    /// Absolute path to a Markdown vault directory or an exported JSON file.
+    pub path: String,
+}
+
+impl Validate for ImportSearch {
+    fn validate(&self) -> crate::Result<()> {
+        if self.path.contains('\0') || !std::path::Path::new(&self.path).is_absolute() {
+            return Err(crate::Error::InvalidParam {
+                name: "path",
+                reason: "must be an absolute path",
+            });
+        }
+        Ok(())
+    }
+}
@@ -7,3 +7,5 @@ pub struct IntegrationCallbackSearch {
     pub status: String,
     pub return_to: Option<String>,
 }
+
+impl super::Validate for IntegrationCallbackSearch {}
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use super::{Validate, validate_id, validate_language};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ListenStartSearch {
+    /// Calendar event to attach the new session to.
+    pub event_id: Option<String>,
+    /// Spoken language for this recording, e.g. `en` or `pt-BR`.
+    pub language: Option<String>,
+}
+
+impl Validate for ListenStartSearch {
+    fn validate(&self) -> crate::Result<()> {
+        if let Some(event_id) = &self.event_id {
+            validate_id("event_id", event_id)?;
+        }
+        if let Some(language) = &self.language {
+            validate_language(language)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ListenStopSearch {}
+
+impl Validate for ListenStopSearch {}
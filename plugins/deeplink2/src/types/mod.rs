@@ -1,15 +1,27 @@
+mod action;
 mod auth_callback;
 mod billing_refresh;
+mod import;
 mod integration_callback;
+mod listen;
+mod search;
+mod session;
 
+pub use action::*;
 pub use auth_callback::*;
 pub use billing_refresh::*;
+pub use import::*;
 pub use integration_callback::*;
+pub use listen::*;
+pub use search::*;
+pub use session::*;
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use specta::Type;
 use std::str::FromStr;
 
+const MAX_ID_LENGTH: usize = 256;
+
 #[derive(Debug, Clone, serde::Serialize, specta::Type, tauri_specta::Event)]
 pub struct DeepLinkEvent(pub DeepLink);
 
@@ -22,6 +34,18 @@ pub enum DeepLink {
     BillingRefresh(BillingRefreshSearch),
     #[serde(rename = "/integration/callback")]
     IntegrationCallback(IntegrationCallbackSearch),
+    #[serde(rename = "/listen/start")]
+    ListenStart(ListenStartSearch),
+    #[serde(rename = "/listen/stop")]
+    ListenStop(ListenStopSearch),
+    #[serde(rename = "/session/new")]
+    SessionNew(SessionNewSearch),
+    #[serde(rename = "/session/open")]
+    SessionOpen(SessionOpenSearch),
+    #[serde(rename = "/search")]
+    Search(SearchQuerySearch),
+    #[serde(rename = "/import")]
+    Import(ImportSearch),
 }
 
 impl DeepLink {
@@ -30,6 +54,27 @@ impl DeepLink {
             DeepLink::AuthCallback(_) => "/auth/callback",
             DeepLink::BillingRefresh(_) => "/billing/refresh",
             DeepLink::IntegrationCallback(_) => "/integration/callback",
+            DeepLink::ListenStart(_) => "/listen/start",
+            DeepLink::ListenStop(_) => "/listen/stop",
+            DeepLink::SessionNew(_) => "/session/new",
+            DeepLink::SessionOpen(_) => "/session/open",
+            DeepLink::Search(_) => "/search",
+            DeepLink::Import(_) => "/import",
+        }
+    }
+
+    /// The action this link triggers, or `None` for callbacks of flows the app started itself.
+    pub fn action(&self) -> Option<DeepLinkAction> {
+        match self {
+            DeepLink::AuthCallback(_)
+            | DeepLink::BillingRefresh(_)
+            | DeepLink::IntegrationCallback(_) => None,
+            DeepLink::ListenStart(_) => Some(DeepLinkAction::ListenStart),
+            DeepLink::ListenStop(_) => Some(DeepLinkAction::ListenStop),
+            DeepLink::SessionNew(_) => Some(DeepLinkAction::SessionNew),
+            DeepLink::SessionOpen(_) => Some(DeepLinkAction::SessionOpen),
+            DeepLink::Search(_) => Some(DeepLinkAction::Search),
+            DeepLink::Import(_) => Some(DeepLinkAction::Import),
         }
     }
 }
@@ -51,10 +96,147 @@ impl FromStr for DeepLink {
         let query = parsed.query().unwrap_or("");
 
         match full_path.as_str() {
-            "auth/callback" => Ok(DeepLink::AuthCallback(serde_qs::from_str(query)?)),
-            "billing/refresh" => Ok(DeepLink::BillingRefresh(serde_qs::from_str(query)?)),
-            "integration/callback" => Ok(DeepLink::IntegrationCallback(serde_qs::from_str(query)?)),
+            "auth/callback" => Ok(DeepLink::AuthCallback(parse_search(query)?)),
+            "billing/refresh" => Ok(DeepLink::BillingRefresh(parse_search(query)?)),
+            "integration/callback" => Ok(DeepLink::IntegrationCallback(parse_search(query)?)),
+            "listen/start" => Ok(DeepLink::ListenStart(parse_search(query)?)),
+            "listen/stop" => Ok(DeepLink::ListenStop(parse_search(query)?)),
+            "session/new" => Ok(DeepLink::SessionNew(parse_search(query)?)),
+            "session/open" => Ok(DeepLink::SessionOpen(parse_search(query)?)),
+            "search" => Ok(DeepLink::Search(parse_search(query)?)),
+            "import" => Ok(DeepLink::Import(parse_search(query)?)),
             _ => Err(crate::Error::UnknownPath(full_path)),
         }
     }
 }
+
+/// Checks a link's search params beyond what deserializing them already enforces.
+pub(crate) trait Validate {
+    fn validate(&self) -> crate::Result<()> {
+        Ok(())
+    }
+}
+
+fn parse_search<T: DeserializeOwned + Validate>(query: &str) -> crate::Result<T> {
+    let search: T = serde_qs::from_str(query)?;
+    search.validate()?;
+    Ok(search)
+}
+
+pub(crate) fn validate_id(name: &'static str, value: &str) -> crate::Result<()> {
+    if value.is_empty()
+        || value.len() > MAX_ID_LENGTH
+        || value.chars().any(|c| c.is_control() || c.is_whitespace())
+    {
+        return Err(crate::Error::InvalidParam {
+            name,
+            reason: "must be a non-empty identifier",
+        });
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_text(name: &'static str, value: &str, max_len: usize) -> crate::Result<()> {
+    if value.trim().is_empty() || value.chars().count() > max_len {
+        return Err(crate::Error::InvalidParam {
+            name,
+            reason: "must be non-empty and not too long",
+        });
+    }
+    if value.chars().any(|c| c.is_control()) {
+        return Err(crate::Error::InvalidParam {
+            name,
+            reason: "must not contain control characters",
+        });
+    }
+    Ok(())
+}
+
+/// Accepts BCP 47 style tags such as `en`, `yue` or `pt-BR`.
+pub(crate) fn validate_language(value: &str) -> crate::Result<()> {
+    let mut parts = value.split('-');
+    let primary = parts.next().unwrap_or("");
+    let valid = (2..=3).contains(&primary.len())
+        && primary.chars().all(|c| c.is_ascii_lowercase())
+        && parts.all(|part| {
+            (2..=8).contains(&part.len()) && part.chars().all(|c| c.is_ascii_alphanumeric())
+        });
+
+    if !valid {
+        return Err(crate::Error::InvalidParam {
+            name: "language",
+            reason: "must be a language code such as `en` or `pt-BR`",
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_actions() {
+        let link: DeepLink = "char://listen/start?event_id=evt_1&language=pt-BR"
+            .parse()
+            .unwrap();
+        assert_eq!(link.action(), Some(DeepLinkAction::ListenStart));
+        assert!(matches!(
+            link,
+            DeepLink::ListenStart(ListenStartSearch { event_id: Some(ref e), language: Some(ref l) })
+                if e == "evt_1" && l == "pt-BR"
+        ));
+
+        let link: DeepLink = "char://session/new?title=Weekly%20sync".parse().unwrap();
+        assert!(matches!(
+            link,
+            DeepLink::SessionNew(SessionNewSearch { title: Some(ref t), template: None })
+                if t == "Weekly sync"
+        ));
+
+        let link: DeepLink = "char://search?q=pricing+review".parse().unwrap();
+        assert!(
+            matches!(link, DeepLink::Search(SearchQuerySearch { ref q }) if q == "pricing review")
+        );
+
+        let link: DeepLink = "char://listen/stop".parse().unwrap();
+        assert_eq!(link.path(), "/listen/stop");
+
+        let link: DeepLink = "char://auth/callback?access_token=a&refresh_token=b"
+            .parse()
+            .unwrap();
+        assert_eq!(link.action(), None);
+    }
+
+    #[test]
+    fn test_rejects_invalid_params() {
+        for url in [
+            "char://listen/start?language=english",
+            "char://session/open",
+            "char://session/open?id=",
+            "char://search?q=%20%20",
+            "char://session/new?title=a%0Ab",
+            "char://import?path=notes/export.json",
+        ] {
+            assert!(url.parse::<DeepLink>().is_err(), "{url} should be rejected");
+        }
+
+        let link: DeepLink = "char://import?path=%2FUsers%2Fme%2Fvault".parse().unwrap();
+        assert!(
+            matches!(link, DeepLink::Import(ImportSearch { ref path }) if path == "/Users/me/vault")
+        );
+    }
+
+    #[test]
+    fn test_default_settings_require_opt_in() {
+        let settings: DeepLinkSettings = serde_json::from_str("{}").unwrap();
+        assert!(settings.allows(DeepLinkAction::Search));
+        assert!(!settings.allows(DeepLinkAction::ListenStart));
+        assert!(!settings.allows(DeepLinkAction::Import));
+
+        let settings: DeepLinkSettings =
+            serde_json::from_str(r#"{"allowed_actions":["listen_start"]}"#).unwrap();
+        assert!(settings.allows(DeepLinkAction::ListenStart));
+        assert!(!settings.allows(DeepLinkAction::Search));
+    }
+}
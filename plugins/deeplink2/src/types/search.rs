@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use super::{Validate, validate_text};
+
+const MAX_QUERY_LENGTH: usize = 500;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SearchQuerySearch {
+    pub q: String,
+}
+
+impl Validate for SearchQuerySearch {
+    fn validate(&self) -> crate::Result<()> {
+        validate_text("q", &self.q, MAX_QUERY_LENGTH)
+    }
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use super::{Validate, validate_id, validate_text};
+
+const MAX_TITLE_LENGTH: usize = 200;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SessionNewSearch {
+    pub title: Option<String>,
+    /// Template to enhance the note with.
+    pub template: Option<String>,
+}
+
+impl Validate for SessionNewSearch {
+    fn validate(&self) -> crate::Result<()> {
+        if let Some(title) = &self.title {
+            validate_text("title", title, MAX_TITLE_LENGTH)?;
+        }
+        if let Some(template) = &self.template {
+            validate_id("template", template)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SessionOpenSearch {
+    pub id: String,
+}
+
+impl Validate for SessionOpenSearch {
+    fn validate(&self) -> crate::Result<()> {
+        validate_id("id", &self.id)
+    }
+}
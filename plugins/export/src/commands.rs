@@ -1,5 +1,7 @@
 use std::path::PathBuf;
 
+use tauri_plugin_redaction::{PlaceholderMap, RedactionPluginExt, RedactionScope, Redactor};
+
 use crate::ExportPluginExt;
 
 #[tauri::command]
@@ -9,6 +11,20 @@ pub(crate) async fn export<R: tauri::Runtime>(
     path: PathBuf,
     input: crate::ExportInput,
 ) -> Result<(), String> {
+    let settings = app
+        .redaction()
+        .settings()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let input = if settings.applies_to(RedactionScope::Export) {
+        let redactor = Redactor::new(&settings.config).map_err(|e| e.to_string())?;
+        let mut placeholders = PlaceholderMap::new();
+        input.map_text(|text| redactor.redact(text, &mut placeholders))
+    } else {
+        input
+    };
+
     app.export()
         .export_pdf(&path, input)
         .map_err(|e| e.to_string())
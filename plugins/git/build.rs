@@ -14,6 +14,13 @@ const COMMANDS: &[&str] = &[
     "check_conflicts",
     "abort_merge",
     "get_current_branch",
+    "file_history",
+    "file_at",
+    "diff_file",
+    "diff_session",
+    "restore",
+    "start_auto_commit",
+    "stop_auto_commit",
 ];
 
 fn main() {
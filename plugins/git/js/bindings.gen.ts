@@ -125,6 +125,62 @@ async getCurrentBranch(path: string) : Promise<Result<string, string>> {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
+},
+async fileHistory(path: string, file: string, limit: number) : Promise<Result<FileVersion[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plugin:git|file_history", { path, file, limit }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async fileAt(path: string, commit: string, file: string) : Promise<Result<string | null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plugin:git|file_at", { path, commit, file }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async diffFile(path: string, file: string, from: string, to: string | null) : Promise<Result<FileDiff, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plugin:git|diff_file", { path, file, from, to }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async diffSession(path: string, sessionDir: string, from: string, to: string | null) : Promise<Result<FileDiff[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plugin:git|diff_session", { path, sessionDir, from, to }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async restore(path: string, file: string, commit: string) : Promise<Result<string, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plugin:git|restore", { path, file, commit }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async startAutoCommit(path: string, policy: AutoCommitPolicy | null) : Promise<Result<null, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plugin:git|start_auto_commit", { path, policy }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async stopAutoCommit(path: string) : Promise<Result<boolean, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plugin:git|stop_auto_commit", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
 }
 }
 
@@ -138,10 +194,17 @@ async getCurrentBranch(path: string) : Promise<Result<string, string>> {
 
 /** user-defined types **/
 
+export type AutoCommitPolicy = { debounce_secs: number; poll_interval_secs: number }
 export type CommitInfo = { id: string; message: string; author: string; timestamp: number }
 export type ConflictInfo = { files: string[] }
+export type DiffContentKind = "Markdown" | "Transcript" | "Text"
+export type DiffHunk = { old_start: number; old_lines: number; new_start: number; new_lines: number; lines: DiffLine[] }
+export type DiffLine = { kind: DiffLineKind; content: string }
+export type DiffLineKind = "Context" | "Added" | "Removed"
 export type FileChangeType = "Added" | "Modified" | "Deleted" | "Renamed" | "Copied"
+export type FileDiff = { path: string; from: string; to: string | null; kind: DiffContentKind; hunks: DiffHunk[]; additions: number; deletions: number }
 export type FileStatus = { path: string; status: FileChangeType }
+export type FileVersion = { commit: CommitInfo; change: FileChangeType }
 export type PullResult = { Success: { commits_pulled: number } } | "AlreadyUpToDate" | { Conflicts: { files: string[] } }
 export type PushResult = { Success: { commits_pushed: number } } | "AlreadyUpToDate" | { Rejected: { reason: string } }
 export type RemoteInfo = { name: string; url: string }
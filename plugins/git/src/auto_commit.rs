@@ -0,0 +1,296 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+
+use tauri::async_runtime::JoinHandle;
+
+use crate::operations::local;
+use crate::types::{AutoCommitPolicy, FileChangeType, FileStatus};
+
+#[derive(Default)]
+pub struct AutoCommitState {
+    pub(crate) tasks: Mutex<HashMap<PathBuf, JoinHandle<()>>>,
+}
+
+impl AutoCommitState {
+    pub fn start(&self, path: PathBuf, policy: AutoCommitPolicy) {
+        let handle = tauri::async_runtime::spawn(run(path.clone(), policy));
+        if let Some(previous) = self.tasks.lock().unwrap().insert(path, handle) {
+            previous.abort();
+        }
+    }
+
+    pub fn stop(&self, path: &Path) -> bool {
+        match self.tasks.lock().unwrap().remove(path) {
+            Some(handle) => {
+                handle.abort();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+type Snapshot = Vec<(String, Option<SystemTime>)>;
+
+async fn run(path: PathBuf, policy: AutoCommitPolicy) {
+    let poll_interval = Duration::from_secs(policy.poll_interval_secs.max(1) as u64);
+    let mut debounce = Debounce::new(Duration::from_secs(policy.debounce_secs as u64));
+
+    loop {
+        tokio::time::sleep(poll_interval).await;
+
+        let repo_path = path.clone();
+        let changes =
+            match tauri::async_runtime::spawn_blocking(move || local::pending_changes(&repo_path))
+                .await
+            {
+                Ok(Ok(changes)) => changes,
+                Ok(Err(e)) => {
+                    tracing::warn!("auto_commit_status_failed: {}", e);
+                    continue;
+                }
+                Err(e) => {
+                    tracing::warn!("auto_commit_task_failed: {}", e);
+                    continue;
+                }
+            };
+
+        if !debounce.settled(snapshot(&path, &changes), Instant::now()) {
+            continue;
+        }
+
+        let repo_path = path.clone();
+        match tauri::async_runtime::spawn_blocking(move || commit_changes(&repo_path, changes))
+            .await
+        {
+            Ok(Ok(commit_id)) => {
+                tracing::info!(%commit_id, "auto_commit_created");
+                debounce.reset();
+            }
+            Ok(Err(e)) => tracing::warn!("auto_commit_failed: {}", e),
+            Err(e) => tracing::warn!("auto_commit_task_failed: {}", e),
+        }
+    }
+}
+
+// Holds off committing until the pending changes have stopped moving for a whole window, so a
+// note being typed into is committed once rather than on every poll.
+struct Debounce {
+    window: Duration,
+    last_snapshot: Snapshot,
+    last_change: Instant,
+}
+
+impl Debounce {
+    fn new(window: Duration) -> Self {
+        Self {
+            window,
+            last_snapshot: Vec::new(),
+            last_change: Instant::now(),
+        }
+    }
+
+    fn settled(&mut self, snapshot: Snapshot, now: Instant) -> bool {
+        if snapshot.is_empty() {
+            self.last_snapshot = snapshot;
+            return false;
+        }
+        if snapshot != self.last_snapshot {
+            self.last_snapshot = snapshot;
+            self.last_change = now;
+            return false;
+        }
+        now.duration_since(self.last_change) >= self.window
+    }
+
+    fn reset(&mut self) {
+        self.last_snapshot.clear();
+    }
+}
+
+fn snapshot(repo_path: &Path, changes: &[FileStatus]) -> Snapshot {
+    let mut snapshot: Snapshot = changes
+        .iter()
+        .map(|change| {
+            let modified = std::fs::metadata(repo_path.join(&change.path))
+                .and_then(|m| m.modified())
+                .ok();
+            (change.path.clone(), modified)
+        })
+        .collect();
+    snapshot.sort();
+    snapshot
+}
+
+fn commit_changes(path: &Path, changes: Vec<FileStatus>) -> Result<String, crate::Error> {
+    let (deleted, updated): (Vec<_>, Vec<_>) = changes
+        .iter()
+        .partition(|change| matches!(change.status, FileChangeType::Deleted));
+
+    if !deleted.is_empty() {
+        local::reset(path, deleted.iter().map(|c| c.path.clone()).collect())?;
+    }
+    if !updated.is_empty() {
+        local::add(path, updated.iter().map(|c| c.path.clone()).collect())?;
+    }
+
+    local::commit(path, &generate_message(path, &changes))
+}
+
+// `Update "Weekly Sync" (2 files)`-style subject plus one line per file, so history stays
+// readable without anyone writing messages by hand.
+fn generate_message(repo_path: &Path, changes: &[FileStatus]) -> String {
+    let mut sessions: Vec<&str> = changes
+        .iter()
+        .filter_map(|change| session_dir(&change.path))
+        .collect();
+    sessions.sort();
+    sessions.dedup();
+
+    let files = if changes.len() == 1 {
+        "1 file".to_string()
+    } else {
+        format!("{} files", changes.len())
+    };
+    let subject = match sessions.as_slice() {
+        [] => format!("Update {}", files),
+        [session] => match session_title(repo_path, session) {
+            Some(title) => format!("Update \"{}\" ({})", title, files),
+            None => format!("Update session {} ({})", session_name(session), files),
+        },
+        _ => format!("Update {} sessions ({})", sessions.len(), files),
+    };
+
+    let mut lines: Vec<String> = changes
+        .iter()
+        .map(|change| {
+            let marker = match change.status {
+                FileChangeType::Added => "A",
+                FileChangeType::Modified => "M",
+                FileChangeType::Deleted => "D",
+                FileChangeType::Renamed => "R",
+                FileChangeType::Copied => "C",
+            };
+            format!("{} {}", marker, change.path)
+        })
+        .collect();
+    lines.sort();
+
+    format!("{}\n\n{}", subject, lines.join("\n"))
+}
+
+// `sessions/<folder>/<session id>/[attachments/]<file>` -> `sessions/<folder>/<session id>`.
+fn session_dir(path: &str) -> Option<&str> {
+    if !path.starts_with("sessions/") {
+        return None;
+    }
+    let (mut dir, _) = path.rsplit_once('/')?;
+    if let Some(parent) = dir.strip_suffix("/attachments") {
+        dir = parent;
+    }
+    (dir != "sessions").then_some(dir)
+}
+
+fn session_name(session_dir: &str) -> &str {
+    session_dir.rsplit('/').next().unwrap_or(session_dir)
+}
+
+fn session_title(repo_path: &Path, session_dir: &str) -> Option<String> {
+    let meta = std::fs::read_to_string(repo_path.join(session_dir).join("_meta.json")).ok()?;
+    let meta: serde_json::Value = serde_json::from_str(&meta).ok()?;
+    meta.get("title")
+        .and_then(|title| title.as_str())
+        .map(str::trim)
+        .filter(|title| !title.is_empty())
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::testing::{commit_all, init_repo, write};
+
+    fn at(path: &str, secs: u64) -> Snapshot {
+        vec![(
+            path.to_string(),
+            Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs)),
+        )]
+    }
+
+    #[test]
+    fn test_debounce_waits_for_quiet_window() {
+        let start = Instant::now();
+        let mut debounce = Debounce::new(Duration::from_secs(30));
+        let tick = |secs| start + Duration::from_secs(secs);
+
+        assert!(!debounce.settled(Vec::new(), tick(0)));
+        assert!(!debounce.settled(at("a.md", 1), tick(10)));
+        assert!(!debounce.settled(at("a.md", 1), tick(20)));
+        // Another edit restarts the window.
+        assert!(!debounce.settled(at("a.md", 2), tick(30)));
+        assert!(!debounce.settled(at("a.md", 2), tick(50)));
+        assert!(debounce.settled(at("a.md", 2), tick(60)));
+
+        // After a commit the same files count as new changes again.
+        debounce.reset();
+        assert!(!debounce.settled(at("a.md", 2), tick(70)));
+        assert!(debounce.settled(at("a.md", 2), tick(100)));
+    }
+
+    #[test]
+    fn test_debounce_forgets_changes_that_were_undone() {
+        let start = Instant::now();
+        let mut debounce = Debounce::new(Duration::from_secs(30));
+        let tick = |secs| start + Duration::from_secs(secs);
+
+        assert!(!debounce.settled(at("a.md", 1), tick(0)));
+        assert!(!debounce.settled(Vec::new(), tick(10)));
+        assert!(!debounce.settled(at("a.md", 1), tick(40)));
+        assert!(debounce.settled(at("a.md", 1), tick(70)));
+    }
+
+    #[test]
+    fn test_session_dir() {
+        let cases = [
+            ("sessions/work/abc/_memo.md", Some("sessions/work/abc")),
+            (
+                "sessions/work/abc/attachments/x.png",
+                Some("sessions/work/abc"),
+            ),
+            ("sessions/abc/transcript.json", Some("sessions/abc")),
+            ("sessions/readme.md", None),
+            ("humans/jane.md", None),
+        ];
+        for (path, expected) in cases {
+            assert_eq!(session_dir(path), expected, "{path}");
+        }
+    }
+
+    #[test]
+    fn test_commit_changes_uses_session_title() {
+        let dir = init_repo();
+        write(dir.path(), "sessions/abc/_memo.md", "first");
+        write(dir.path(), "sessions/abc/gone.md", "bye");
+        commit_all(dir.path(), "init");
+
+        write(
+            dir.path(),
+            "sessions/abc/_meta.json",
+            r#"{"title": "Weekly Sync"}"#,
+        );
+        write(dir.path(), "sessions/abc/_memo.md", "second draft");
+        std::fs::remove_file(dir.path().join("sessions/abc/gone.md")).unwrap();
+
+        let changes = local::pending_changes(dir.path()).unwrap();
+        commit_changes(dir.path(), changes).unwrap();
+
+        let log = local::log(dir.path(), 1).unwrap();
+        assert_eq!(
+            log[0].message,
+            "Update \"Weekly Sync\" (3 files)\n\nA sessions/abc/_meta.json\nD sessions/abc/gone.md\nM sessions/abc/_memo.md"
+        );
+        assert!(local::pending_changes(dir.path()).unwrap().is_empty());
+    }
+}
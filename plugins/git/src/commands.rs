@@ -1,7 +1,10 @@
 use std::path::PathBuf;
 
 use crate::GitPluginExt;
-use crate::types::{CommitInfo, ConflictInfo, PullResult, PushResult, RemoteInfo, StatusInfo};
+use crate::types::{
+    AutoCommitPolicy, CommitInfo, ConflictInfo, FileDiff, FileVersion, PullResult, PushResult,
+    RemoteInfo, StatusInfo,
+};
 
 #[tauri::command]
 #[specta::specta]
@@ -158,3 +161,91 @@ pub(crate) async fn get_current_branch<R: tauri::Runtime>(
         .get_current_branch(&path)
         .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn file_history<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    path: PathBuf,
+    file: String,
+    limit: u32,
+) -> Result<Vec<FileVersion>, String> {
+    app.git()
+        .file_history(&path, &file, limit)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn file_at<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    path: PathBuf,
+    commit: String,
+    file: String,
+) -> Result<Option<String>, String> {
+    app.git()
+        .file_at(&path, &commit, &file)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn diff_file<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    path: PathBuf,
+    file: String,
+    from: String,
+    to: Option<String>,
+) -> Result<FileDiff, String> {
+    app.git()
+        .diff_file(&path, &file, &from, to.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn diff_session<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    path: PathBuf,
+    session_dir: String,
+    from: String,
+    to: Option<String>,
+) -> Result<Vec<FileDiff>, String> {
+    app.git()
+        .diff_session(&path, &session_dir, &from, to.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn restore<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    path: PathBuf,
+    file: String,
+    commit: String,
+) -> Result<String, String> {
+    app.git()
+        .restore(&path, &file, &commit)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn start_auto_commit<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    path: PathBuf,
+    policy: Option<AutoCommitPolicy>,
+) -> Result<(), String> {
+    app.git()
+        .start_auto_commit(&path, policy.unwrap_or_default());
+    Ok(())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn stop_auto_commit<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    path: PathBuf,
+) -> Result<bool, String> {
+    Ok(app.git().stop_auto_commit(&path))
+}
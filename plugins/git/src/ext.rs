@@ -1,10 +1,16 @@
 use std::path::Path;
 
-use crate::operations::{local, merge, remote};
-use crate::types::{CommitInfo, ConflictInfo, PullResult, PushResult, RemoteInfo, StatusInfo};
+use tauri::Manager;
+
+use crate::auto_commit::AutoCommitState;
+use crate::operations::{history, local, merge, remote};
+use crate::types::{
+    AutoCommitPolicy, CommitInfo, ConflictInfo, FileDiff, FileVersion, PullResult, PushResult,
+    RemoteInfo, StatusInfo,
+};
 
 pub struct Git<'a, R: tauri::Runtime, M: tauri::Manager<R>> {
-    _manager: &'a M,
+    manager: &'a M,
     _runtime: std::marker::PhantomData<fn() -> R>,
 }
 
@@ -78,6 +84,58 @@ impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> Git<'a, R, M> {
     pub fn abort_merge(&self, path: &Path) -> Result<(), crate::Error> {
         merge::abort_merge(path)
     }
+
+    pub fn file_history(
+        &self,
+        path: &Path,
+        file: &str,
+        limit: u32,
+    ) -> Result<Vec<FileVersion>, crate::Error> {
+        history::file_history(path, file, limit)
+    }
+
+    pub fn file_at(
+        &self,
+        path: &Path,
+        commit: &str,
+        file: &str,
+    ) -> Result<Option<String>, crate::Error> {
+        history::file_at(path, commit, file)
+    }
+
+    pub fn diff_file(
+        &self,
+        path: &Path,
+        file: &str,
+        from: &str,
+        to: Option<&str>,
+    ) -> Result<FileDiff, crate::Error> {
+        history::diff_file(path, file, from, to)
+    }
+
+    pub fn diff_session(
+        &self,
+        path: &Path,
+        session_dir: &str,
+        from: &str,
+        to: Option<&str>,
+    ) -> Result<Vec<FileDiff>, crate::Error> {
+        history::diff_session(path, session_dir, from, to)
+    }
+
+    pub fn restore(&self, path: &Path, file: &str, commit: &str) -> Result<String, crate::Error> {
+        history::restore(path, file, commit)
+    }
+
+    pub fn start_auto_commit(&self, path: &Path, policy: AutoCommitPolicy) {
+        self.manager
+            .state::<AutoCommitState>()
+            .start(path.to_path_buf(), policy);
+    }
+
+    pub fn stop_auto_commit(&self, path: &Path) -> bool {
+        self.manager.state::<AutoCommitState>().stop(path)
+    }
 }
 
 pub trait GitPluginExt<R: tauri::Runtime> {
@@ -92,7 +150,7 @@ impl<R: tauri::Runtime, T: tauri::Manager<R>> GitPluginExt<R> for T {
         Self: Sized,
     {
         Git {
-            _manager: self,
+            manager: self,
             _runtime: std::marker::PhantomData,
         }
     }
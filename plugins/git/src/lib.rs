@@ -1,9 +1,13 @@
+use tauri::Manager;
+
+mod auto_commit;
 mod commands;
 mod error;
 mod ext;
 mod operations;
 pub mod types;
 
+pub use auto_commit::AutoCommitState;
 pub use error::{Error, Result};
 pub use ext::*;
 
@@ -28,6 +32,13 @@ fn make_specta_builder<R: tauri::Runtime>() -> tauri_specta::Builder<R> {
             commands::check_conflicts::<tauri::Wry>,
             commands::abort_merge::<tauri::Wry>,
             commands::get_current_branch::<tauri::Wry>,
+            commands::file_history::<tauri::Wry>,
+            commands::file_at::<tauri::Wry>,
+            commands::diff_file::<tauri::Wry>,
+            commands::diff_session::<tauri::Wry>,
+            commands::restore::<tauri::Wry>,
+            commands::start_auto_commit::<tauri::Wry>,
+            commands::stop_auto_commit::<tauri::Wry>,
         ])
         .error_handling(tauri_specta::ErrorHandlingMode::Result)
 }
@@ -37,6 +48,10 @@ pub fn init<R: tauri::Runtime>() -> tauri::plugin::TauriPlugin<R> {
 
     tauri::plugin::Builder::new(PLUGIN_NAME)
         .invoke_handler(specta_builder.invoke_handler())
+        .setup(|app, _api| {
+            app.manage(AutoCommitState::default());
+            Ok(())
+        })
         .build()
 }
 
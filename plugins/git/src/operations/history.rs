@@ -0,0 +1,760 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+use std::path::Path;
+
+use similar::{ChangeTag, TextDiff};
+
+use super::local;
+use crate::types::{
+    DiffContentKind, DiffHunk, DiffLine, DiffLineKind, FileChangeType, FileDiff, FileVersion,
+};
+
+const TRANSCRIPT_FILE: &str = "transcript.json";
+const DIFF_CONTEXT_LINES: usize = 3;
+
+/// Commits that changed `file`, newest first. Every parent is followed, so changes made on
+/// another device and merged in are listed too. Consecutive commits that produce the same content,
+/// like the same edit made on two devices, are listed once, at the newest of them.
+pub fn file_history(path: &Path, file: &str, limit: u32) -> Result<Vec<FileVersion>, crate::Error> {
+    let repo = gix::discover(path)?;
+    let file = normalize(file);
+    let mut versions = Vec::new();
+
+    let head = match repo.head_id() {
+        Ok(id) => id.detach(),
+        Err(_) => return Ok(versions),
+    };
+
+    let mut seen_commits = HashSet::new();
+    let mut last_listed = None;
+    // Newest first; commits made in the same second go in order of distance from HEAD, so a
+    // commit is still visited before its parents.
+    let mut pending = BinaryHeap::from([(commit_time(&repo, head)?, Reverse(0), head)]);
+
+    while let Some((_, Reverse(depth), oid)) = pending.pop() {
+        if versions.len() as u32 >= limit {
+            break;
+        }
+        if !seen_commits.insert(oid) {
+            continue;
+        }
+
+        let commit = find_commit(&repo, oid)?;
+        let commit_ref = commit
+            .decode()
+            .map_err(|e| crate::Error::Custom(e.to_string()))?;
+        let parents: Vec<gix::ObjectId> = commit_ref.parents().collect();
+
+        let here = entry_id_at(&repo, oid, &file)?;
+        let before = parents
+            .iter()
+            .map(|&parent| entry_id_at(&repo, parent, &file))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // A commit matching any parent took that parent's version rather than changing it.
+        let change = if !parents.is_empty() && before.contains(&here) {
+            None
+        } else {
+            match (before.iter().any(Option::is_some), here) {
+                (false, Some(_)) => Some(FileChangeType::Added),
+                (true, Some(_)) => Some(FileChangeType::Modified),
+                (true, None) => Some(FileChangeType::Deleted),
+                (false, None) => None,
+            }
+        };
+        let change = change.filter(|_| here.is_none() || here != last_listed);
+
+        if let Some(change) = change {
+            last_listed = here;
+            versions.push(FileVersion {
+                commit: local::commit_info(oid, &commit_ref),
+                change,
+            });
+        }
+
+        for parent in parents {
+            if !seen_commits.contains(&parent) {
+                pending.push((commit_time(&repo, parent)?, Reverse(depth + 1), parent));
+            }
+        }
+    }
+
+    Ok(versions)
+}
+
+pub fn file_at(path: &Path, commit: &str, file: &str) -> Result<Option<String>, crate::Error> {
+    let repo = gix::discover(path)?;
+    let commit_id = resolve_commit(&repo, commit)?;
+    read_blob_at(&repo, commit_id, &normalize(file))
+}
+
+pub fn diff_file(
+    path: &Path,
+    file: &str,
+    from: &str,
+    to: Option<&str>,
+) -> Result<FileDiff, crate::Error> {
+    let repo = gix::discover(path)?;
+    let file = normalize(file);
+
+    let from_id = resolve_commit(&repo, from)?;
+    let old = read_blob_at(&repo, from_id, &file)?;
+    let new = match to {
+        Some(to) => read_blob_at(&repo, resolve_commit(&repo, to)?, &file)?,
+        None => read_workdir_file(&repo, &file)?,
+    };
+
+    Ok(build_diff(
+        file,
+        from_id.to_string(),
+        to.map(str::to_string),
+        old.as_deref(),
+        new.as_deref(),
+    ))
+}
+
+pub fn diff_session(
+    path: &Path,
+    session_dir: &str,
+    from: &str,
+    to: Option<&str>,
+) -> Result<Vec<FileDiff>, crate::Error> {
+    let repo = gix::discover(path)?;
+    let session_dir = normalize(session_dir);
+
+    let from_id = resolve_commit(&repo, from)?;
+    let to_id = to.map(|to| resolve_commit(&repo, to)).transpose()?;
+
+    let mut files = list_session_files_at(&repo, from_id, &session_dir)?;
+    match to_id {
+        Some(to_id) => files.extend(list_session_files_at(&repo, to_id, &session_dir)?),
+        None => files.extend(list_session_files_in_workdir(&repo, &session_dir)?),
+    }
+    files.sort();
+    files.dedup();
+
+    let mut diffs = Vec::new();
+    for file in files {
+        let old = read_blob_at(&repo, from_id, &file)?;
+        let new = match to_id {
+            Some(to_id) => read_blob_at(&repo, to_id, &file)?,
+            None => read_workdir_file(&repo, &file)?,
+        };
+        if old == new {
+            continue;
+        }
+
+        diffs.push(build_diff(
+            file,
+            from_id.to_string(),
+            to_id.map(|id| id.to_string()),
+            old.as_deref(),
+            new.as_deref(),
+        ));
+    }
+
+    Ok(diffs)
+}
+
+pub fn restore(path: &Path, file: &str, commit: &str) -> Result<String, crate::Error> {
+    let repo = gix::discover(path)?;
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| crate::Error::Custom("No working directory".to_string()))?
+        .to_path_buf();
+    let file = normalize(file);
+    let commit_id = resolve_commit(&repo, commit)?;
+    let head = repo.head_id().ok().map(|id| id.detach());
+    let matches_head = match head {
+        Some(head) => entry_id_at(&repo, head, &file)? == entry_id_at(&repo, commit_id, &file)?,
+        None => false,
+    };
+
+    let restored = collect_blobs_at(&repo, commit_id, &file)?;
+    let tracked = tracked_paths_under(&repo, &file)?;
+
+    // Files that were tracked now but did not exist in the restored version are removed; untracked
+    // files (e.g. ignored audio) are left untouched.
+    let removed: Vec<String> = tracked
+        .into_iter()
+        .filter(|p| !restored.iter().any(|(restored, _)| restored == p))
+        .collect();
+    for removed_path in &removed {
+        let full_path = workdir.join(removed_path);
+        if full_path.exists() {
+            std::fs::remove_file(full_path)?;
+        }
+    }
+    if !removed.is_empty() {
+        local::reset(path, removed)?;
+    }
+
+    let mut added = Vec::new();
+    for (restored_path, data) in restored {
+        let full_path = workdir.join(&restored_path);
+        if let Some(parent) = full_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&full_path, data)?;
+        added.push(restored_path);
+    }
+    if !added.is_empty() {
+        local::add(path, added)?;
+    }
+
+    // Restoring what HEAD already has only discards local edits; there is nothing to commit.
+    if matches_head && let Some(head) = head {
+        return Ok(head.to_string());
+    }
+
+    let short_id: String = commit_id.to_string().chars().take(7).collect();
+    local::commit(path, &format!("Restore {} to {}", file, short_id))
+}
+
+fn normalize(path: &str) -> String {
+    path.replace('\\', "/").trim_matches('/').to_string()
+}
+
+fn resolve_commit(repo: &gix::Repository, rev: &str) -> Result<gix::ObjectId, crate::Error> {
+    let id = repo
+        .rev_parse_single(rev)
+        .map_err(|e| crate::Error::Custom(e.to_string()))?;
+    let commit = id
+        .object()
+        .map_err(|e| crate::Error::Custom(e.to_string()))?
+        .peel_to_commit()
+        .map_err(|e| crate::Error::Custom(e.to_string()))?;
+    Ok(commit.id)
+}
+
+fn find_commit(
+    repo: &gix::Repository,
+    oid: gix::ObjectId,
+) -> Result<gix::Commit<'_>, crate::Error> {
+    repo.find_object(oid)
+        .map_err(|e| crate::Error::Custom(e.to_string()))?
+        .try_into_commit()
+        .map_err(|e| crate::Error::Custom(e.to_string()))
+}
+
+fn commit_time(repo: &gix::Repository, commit: gix::ObjectId) -> Result<i64, crate::Error> {
+    let commit = find_commit(repo, commit)?;
+    let commit_ref = commit
+        .decode()
+        .map_err(|e| crate::Error::Custom(e.to_string()))?;
+    Ok(local::signature_seconds(&commit_ref.committer))
+}
+
+fn tree_at(repo: &gix::Repository, commit: gix::ObjectId) -> Result<gix::Tree<'_>, crate::Error> {
+    find_commit(repo, commit)?
+        .tree()
+        .map_err(|e| crate::Error::Custom(e.to_string()))
+}
+
+fn entry_id_at(
+    repo: &gix::Repository,
+    commit: gix::ObjectId,
+    file: &str,
+) -> Result<Option<gix::ObjectId>, crate::Error> {
+    let entry = tree_at(repo, commit)?
+        .lookup_entry_by_path(file)
+        .map_err(|e| crate::Error::Custom(e.to_string()))?;
+    Ok(entry.map(|entry| entry.object_id()))
+}
+
+fn read_blob_at(
+    repo: &gix::Repository,
+    commit: gix::ObjectId,
+    file: &str,
+) -> Result<Option<String>, crate::Error> {
+    let entry = tree_at(repo, commit)?
+        .lookup_entry_by_path(file)
+        .map_err(|e| crate::Error::Custom(e.to_string()))?;
+
+    let Some(entry) = entry.filter(|entry| entry.mode().is_blob()) else {
+        return Ok(None);
+    };
+
+    let blob = repo
+        .find_object(entry.object_id())
+        .map_err(|e| crate::Error::Custom(e.to_string()))?
+        .try_into_blob()
+        .map_err(|e| crate::Error::Custom(e.to_string()))?;
+    Ok(Some(String::from_utf8_lossy(&blob.data).to_string()))
+}
+
+fn read_workdir_file(repo: &gix::Repository, file: &str) -> Result<Option<String>, crate::Error> {
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| crate::Error::Custom("No working directory".to_string()))?;
+    let full_path = workdir.join(file);
+    if !full_path.is_file() {
+        return Ok(None);
+    }
+    let data = std::fs::read(full_path)?;
+    Ok(Some(String::from_utf8_lossy(&data).to_string()))
+}
+
+// Every blob at or below `file` in the given commit, keyed by repository-relative path.
+fn collect_blobs_at(
+    repo: &gix::Repository,
+    commit: gix::ObjectId,
+    file: &str,
+) -> Result<Vec<(String, Vec<u8>)>, crate::Error> {
+    let entry = tree_at(repo, commit)?
+        .lookup_entry_by_path(file)
+        .map_err(|e| crate::Error::Custom(e.to_string()))?
+        .ok_or_else(|| crate::Error::Custom(format!("{} does not exist in {}", file, commit)))?;
+
+    let mut blobs = Vec::new();
+    let mut pending = vec![(file.to_string(), entry.object_id(), entry.mode().is_tree())];
+
+    while let Some((entry_path, oid, is_tree)) = pending.pop() {
+        let object = repo
+            .find_object(oid)
+            .map_err(|e| crate::Error::Custom(e.to_string()))?;
+
+        if is_tree {
+            let tree = object
+                .try_into_tree()
+                .map_err(|e| crate::Error::Custom(e.to_string()))?;
+            for child in tree.iter() {
+                let child = child.map_err(|e| crate::Error::Custom(e.to_string()))?;
+                pending.push((
+                    format!("{}/{}", entry_path, child.inner.filename),
+                    child.inner.oid.into(),
+                    child.inner.mode.is_tree(),
+                ));
+            }
+        } else {
+            blobs.push((entry_path, object.detach().data));
+        }
+    }
+
+    Ok(blobs)
+}
+
+fn tracked_paths_under(repo: &gix::Repository, file: &str) -> Result<Vec<String>, crate::Error> {
+    let index = repo
+        .index_or_empty()
+        .map_err(|e| crate::Error::Custom(e.to_string()))?;
+    let dir_prefix = format!("{}/", file);
+
+    Ok(index
+        .entries()
+        .iter()
+        .map(|entry| String::from_utf8_lossy(entry.path(&index)).to_string())
+        .filter(|entry_path| entry_path == file || entry_path.starts_with(&dir_prefix))
+        .collect())
+}
+
+fn is_session_content(file: &str) -> bool {
+    file.ends_with(".md") || file.ends_with(TRANSCRIPT_FILE)
+}
+
+fn list_session_files_at(
+    repo: &gix::Repository,
+    commit: gix::ObjectId,
+    session_dir: &str,
+) -> Result<Vec<String>, crate::Error> {
+    let exists = tree_at(repo, commit)?
+        .lookup_entry_by_path(session_dir)
+        .map_err(|e| crate::Error::Custom(e.to_string()))?
+        .is_some();
+    if !exists {
+        return Ok(Vec::new());
+    }
+
+    Ok(collect_blobs_at(repo, commit, session_dir)?
+        .into_iter()
+        .map(|(file, _)| file)
+        .filter(|file| is_session_content(file))
+        .collect())
+}
+
+fn list_session_files_in_workdir(
+    repo: &gix::Repository,
+    session_dir: &str,
+) -> Result<Vec<String>, crate::Error> {
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| crate::Error::Custom("No working directory".to_string()))?;
+    let dir = workdir.join(session_dir);
+    if !dir.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    Ok(walkdir::WalkDir::new(&dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| {
+            e.path()
+                .strip_prefix(workdir)
+                .unwrap_or(e.path())
+                .to_string_lossy()
+                .replace('\\', "/")
+        })
+        .filter(|file| is_session_content(file))
+        .collect())
+}
+
+fn build_diff(
+    path: String,
+    from: String,
+    to: Option<String>,
+    old: Option<&str>,
+    new: Option<&str>,
+) -> FileDiff {
+    let kind = if path.ends_with(TRANSCRIPT_FILE) {
+        DiffContentKind::Transcript
+    } else if path.ends_with(".md") {
+        DiffContentKind::Markdown
+    } else {
+        DiffContentKind::Text
+    };
+
+    let render = |content: Option<&str>| match (content, &kind) {
+        (Some(content), DiffContentKind::Transcript) => {
+            transcript_to_lines(content).unwrap_or_else(|| content.to_string())
+        }
+        (Some(content), _) => content.to_string(),
+        (None, _) => String::new(),
+    };
+    let old_text = render(old);
+    let new_text = render(new);
+
+    let text_diff = TextDiff::from_lines(&old_text, &new_text);
+    let mut hunks = Vec::new();
+    let mut additions = 0;
+    let mut deletions = 0;
+
+    for group in text_diff.grouped_ops(DIFF_CONTEXT_LINES) {
+        let (Some(first), Some(last)) = (group.first(), group.last()) else {
+            continue;
+        };
+        let old_range = first.old_range().start..last.old_range().end;
+        let new_range = first.new_range().start..last.new_range().end;
+
+        let mut lines = Vec::new();
+        for op in &group {
+            for change in text_diff.iter_changes(op) {
+                let kind = match change.tag() {
+                    ChangeTag::Equal => DiffLineKind::Context,
+                    ChangeTag::Insert => {
+                        additions += 1;
+                        DiffLineKind::Added
+                    }
+                    ChangeTag::Delete => {
+                        deletions += 1;
+                        DiffLineKind::Removed
+                    }
+                };
+                lines.push(DiffLine {
+                    kind,
+                    content: change.value().trim_end_matches('\n').to_string(),
+                });
+            }
+        }
+
+        hunks.push(DiffHunk {
+            old_start: old_range.start as u32 + 1,
+            old_lines: old_range.len() as u32,
+            new_start: new_range.start as u32 + 1,
+            new_lines: new_range.len() as u32,
+            lines,
+        });
+    }
+
+    FileDiff {
+        path,
+        from,
+        to,
+        kind,
+        hunks,
+        additions,
+        deletions,
+    }
+}
+
+// Flattens `transcript.json` into one `[mm:ss] text` line per run of words on the same channel,
+// so edits show up as sentence changes instead of JSON noise.
+fn transcript_to_lines(content: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(content).ok()?;
+    let transcripts = value.get("transcripts")?.as_array()?;
+
+    let mut lines = Vec::new();
+    for transcript in transcripts {
+        let Some(words) = transcript.get("words").and_then(|w| w.as_array()) else {
+            continue;
+        };
+
+        let mut current: Option<(i64, i64, String)> = None;
+        for word in words {
+            let text = word.get("text").and_then(|t| t.as_str()).unwrap_or("");
+            let channel = word.get("channel").and_then(|c| c.as_i64()).unwrap_or(0);
+            let start_ms = word.get("start_ms").and_then(|s| s.as_i64()).unwrap_or(0);
+
+            match &mut current {
+                Some((current_channel, _, line)) if *current_channel == channel => {
+                    line.push_str(text);
+                }
+                _ => {
+                    if let Some(line) = current.take() {
+                        lines.push(format_transcript_line(line));
+                    }
+                    current = Some((channel, start_ms, text.to_string()));
+                }
+            }
+        }
+        if let Some(line) = current.take() {
+            lines.push(format_transcript_line(line));
+        }
+    }
+
+    Some(lines.join("\n"))
+}
+
+fn format_transcript_line((channel, start_ms, text): (i64, i64, String)) -> String {
+    let seconds = start_ms.max(0) / 1000;
+    format!(
+        "[{:02}:{:02}] ({}) {}",
+        seconds / 60,
+        seconds % 60,
+        channel,
+        text.trim()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::testing::{commit_all, commit_files, init_repo, read, write};
+
+    const NOTE: &str = "sessions/a/_memo.md";
+
+    fn ids(versions: &[FileVersion]) -> Vec<String> {
+        let mut ids: Vec<String> = versions.iter().map(|v| v.commit.id.clone()).collect();
+        ids.sort();
+        ids
+    }
+
+    fn sorted(commits: &[gix::ObjectId]) -> Vec<String> {
+        let mut ids: Vec<String> = commits.iter().map(|id| id.to_string()).collect();
+        ids.sort();
+        ids
+    }
+
+    // Commits the worktree as a merge of HEAD and `theirs`, as a pull would.
+    fn commit_merge(dir: &Path, theirs: gix::ObjectId) -> gix::ObjectId {
+        std::fs::write(dir.join(".git/MERGE_HEAD"), theirs.to_string()).unwrap();
+        commit_all(dir, "Merge")
+    }
+
+    #[test]
+    fn test_file_history_follows_merged_parents() {
+        let dir = init_repo();
+        write(dir.path(), NOTE, "one\n");
+        let first = commit_all(dir.path(), "first");
+        write(dir.path(), "sessions/b/_memo.md", "unrelated\n");
+        commit_all(dir.path(), "unrelated");
+
+        let theirs = commit_files(dir.path(), &[(NOTE, "remote\n")], &[first]);
+        write(dir.path(), NOTE, "local\n");
+        let ours = commit_all(dir.path(), "local edit");
+
+        // The merge keeps the remote version, so it isn't a change of its own.
+        write(dir.path(), NOTE, "remote\n");
+        commit_merge(dir.path(), theirs);
+
+        let history = file_history(dir.path(), NOTE, 10).unwrap();
+        assert_eq!(ids(&history), sorted(&[first, theirs, ours]));
+        let added = history
+            .iter()
+            .find(|v| v.commit.id == first.to_string())
+            .unwrap();
+        assert!(matches!(added.change, FileChangeType::Added));
+
+        let history = file_history(dir.path(), NOTE, 1).unwrap();
+        assert_eq!(history.len(), 1);
+    }
+
+    #[test]
+    fn test_file_history_lists_identical_content_once() {
+        let dir = init_repo();
+        write(dir.path(), NOTE, "one\n");
+        write(dir.path(), "sessions/a/_meta.json", "{}");
+        let first = commit_all(dir.path(), "first");
+
+        // Both devices made the same edit.
+        let theirs = commit_files(
+            dir.path(),
+            &[(NOTE, "two\n"), ("sessions/a/_meta.json", "{}")],
+            &[first],
+        );
+        write(dir.path(), NOTE, "two\n");
+        let ours = commit_all(dir.path(), "same edit");
+        commit_merge(dir.path(), theirs);
+
+        write(dir.path(), NOTE, "three\n");
+        let third = commit_all(dir.path(), "third");
+        std::fs::remove_file(dir.path().join(NOTE)).unwrap();
+        local::reset(dir.path(), vec![NOTE.to_string()]).unwrap();
+        let deleted = commit_all(dir.path(), "delete");
+
+        let history = file_history(dir.path(), NOTE, 10).unwrap();
+        assert_eq!(history.len(), 4);
+        assert_eq!(history[0].commit.id, deleted.to_string());
+        assert!(matches!(history[0].change, FileChangeType::Deleted));
+        assert_eq!(history[1].commit.id, third.to_string());
+
+        // Only one of the two commits that produced "two" is listed.
+        let listed = ids(&history);
+        assert!(listed.contains(&first.to_string()));
+        assert_eq!(
+            [ours, theirs]
+                .iter()
+                .filter(|id| listed.contains(&id.to_string()))
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_file_history_lists_reverted_content_again() {
+        let dir = init_repo();
+        write(dir.path(), NOTE, "a\n");
+        let first = commit_all(dir.path(), "first");
+        write(dir.path(), NOTE, "b\n");
+        let second = commit_all(dir.path(), "second");
+        write(dir.path(), NOTE, "a\n");
+        let reverted = commit_all(dir.path(), "revert");
+
+        let history = file_history(dir.path(), NOTE, 10).unwrap();
+        assert_eq!(ids(&history), sorted(&[first, second, reverted]));
+    }
+
+    #[test]
+    fn test_restore_to_head_version_makes_no_commit() {
+        let dir = init_repo();
+        write(dir.path(), NOTE, "v1\n");
+        let first = commit_all(dir.path(), "first");
+        write(dir.path(), NOTE, "edited\n");
+
+        let restored = restore(dir.path(), NOTE, &first.to_string()).unwrap();
+
+        assert_eq!(restored, first.to_string());
+        assert_eq!(read(dir.path(), NOTE), "v1\n");
+        assert_eq!(local::log(dir.path(), 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_restore_to_revision() {
+        let dir = init_repo();
+        write(dir.path(), NOTE, "v1\n");
+        write(dir.path(), "sessions/a/extra.md", "extra\n");
+        let first = commit_all(dir.path(), "first");
+
+        write(dir.path(), NOTE, "v2\n");
+        write(dir.path(), "sessions/a/new.md", "new\n");
+        std::fs::remove_file(dir.path().join("sessions/a/extra.md")).unwrap();
+        local::reset(dir.path(), vec!["sessions/a/extra.md".to_string()]).unwrap();
+        commit_all(dir.path(), "second");
+        write(dir.path(), "sessions/a/audio.wav", "untracked audio");
+
+        restore(dir.path(), "sessions/a", &first.to_string()).unwrap();
+
+        assert_eq!(read(dir.path(), NOTE), "v1\n");
+        assert_eq!(read(dir.path(), "sessions/a/extra.md"), "extra\n");
+        assert!(!dir.path().join("sessions/a/new.md").exists());
+        assert!(dir.path().join("sessions/a/audio.wav").exists());
+        assert!(local::pending_changes(dir.path()).unwrap().is_empty());
+
+        let short: String = first.to_string().chars().take(7).collect();
+        assert_eq!(
+            local::log(dir.path(), 1).unwrap()[0].message,
+            format!("Restore sessions/a to {}", short)
+        );
+        assert_eq!(
+            file_at(dir.path(), "HEAD", "sessions/a/new.md").unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_diff_file_against_worktree() {
+        let dir = init_repo();
+        write(dir.path(), NOTE, "a\nb\nc\n");
+        let first = commit_all(dir.path(), "first");
+        write(dir.path(), NOTE, "a\nB\nc\nd\n");
+
+        let diff = diff_file(dir.path(), NOTE, &first.to_string(), None).unwrap();
+        assert!(matches!(diff.kind, DiffContentKind::Markdown));
+        assert_eq!((diff.additions, diff.deletions), (2, 1));
+        assert_eq!(diff.hunks.len(), 1);
+
+        let lines: Vec<String> = diff.hunks[0]
+            .lines
+            .iter()
+            .map(|line| {
+                let marker = match line.kind {
+                    DiffLineKind::Context => ' ',
+                    DiffLineKind::Added => '+',
+                    DiffLineKind::Removed => '-',
+                };
+                format!("{marker}{}", line.content)
+            })
+            .collect();
+        assert_eq!(lines, [" a", "-b", "+B", " c", "+d"]);
+    }
+
+    #[test]
+    fn test_diff_session_renders_transcripts() {
+        let dir = init_repo();
+        let transcript = |text: &str| {
+            serde_json::json!({ "transcripts": [{ "words": [
+                { "text": "Hello", "channel": 0, "start_ms": 1000 },
+                { "text": text, "channel": 0, "start_ms": 1400 },
+                { "text": "Hi", "channel": 1, "start_ms": 62000 },
+            ]}]})
+            .to_string()
+        };
+        write(
+            dir.path(),
+            "sessions/a/transcript.json",
+            &transcript(" world"),
+        );
+        write(dir.path(), NOTE, "note\n");
+        write(dir.path(), "sessions/a/_meta.json", "{}");
+        let first = commit_all(dir.path(), "first");
+
+        write(
+            dir.path(),
+            "sessions/a/transcript.json",
+            &transcript(" there"),
+        );
+        write(dir.path(), "sessions/a/_meta.json", r#"{"title":"x"}"#);
+        let second = commit_all(dir.path(), "second");
+
+        let diffs = diff_session(
+            dir.path(),
+            "sessions/a",
+            &first.to_string(),
+            Some(&second.to_string()),
+        )
+        .unwrap();
+        assert_eq!(diffs.len(), 1);
+        assert!(matches!(diffs[0].kind, DiffContentKind::Transcript));
+
+        let changed: Vec<&str> = diffs[0].hunks[0]
+            .lines
+            .iter()
+            .filter(|line| !matches!(line.kind, DiffLineKind::Context))
+            .map(|line| line.content.as_str())
+            .collect();
+        assert_eq!(
+            changed,
+            ["[00:01] (0) Hello world", "[00:01] (0) Hello there"]
+        );
+    }
+}
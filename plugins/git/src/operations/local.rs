@@ -94,8 +94,11 @@ pub fn status(path: &Path) -> Result<StatusInfo, crate::Error> {
                 .unwrap_or(0);
 
             let index_mtime = entry.stat.mtime.secs as i64;
+            // Mtimes only have second precision here, so an edit made in the same second as the
+            // last commit is only caught by its size.
+            let stat_changed = mtime != index_mtime || metadata.len() != entry.stat.size as u64;
 
-            if mtime != index_mtime
+            if stat_changed
                 && let Ok(current_data) = std::fs::read(&full_path)
                 && let Ok(current_hash) = gix::objs::compute_hash(
                     repo.object_hash(),
@@ -116,6 +119,43 @@ pub fn status(path: &Path) -> Result<StatusInfo, crate::Error> {
     Ok(status_info)
 }
 
+// Unstaged and untracked changes, minus anything `.gitignore` excludes.
+pub fn pending_changes(path: &Path) -> Result<Vec<FileStatus>, crate::Error> {
+    let repo = gix::discover(path)?;
+    let info = status(path)?;
+
+    let index = repo
+        .index_or_empty()
+        .map_err(|e| crate::Error::Custom(e.to_string()))?;
+    let mut excludes = repo
+        .excludes(
+            &index,
+            None,
+            gix::worktree::stack::state::ignore::Source::WorktreeThenIdMappingIfNotSkipped,
+        )
+        .map_err(|e| crate::Error::Custom(e.to_string()))?;
+
+    let candidates = info
+        .unstaged
+        .into_iter()
+        .chain(info.untracked.into_iter().map(|path| FileStatus {
+            path,
+            status: FileChangeType::Added,
+        }));
+
+    let mut changes = Vec::new();
+    for change in candidates {
+        let is_excluded = excludes
+            .at_path(&change.path, Some(gix::index::entry::Mode::FILE))?
+            .is_excluded();
+        if !is_excluded {
+            changes.push(change);
+        }
+    }
+
+    Ok(changes)
+}
+
 pub fn add(path: &Path, patterns: Vec<String>) -> Result<(), crate::Error> {
     let repo = gix::discover(path)?;
     let index_path = repo.git_dir().join("index");
@@ -274,6 +314,16 @@ pub fn commit(path: &Path, message: &str) -> Result<String, crate::Error> {
             .index_or_empty()
             .map_err(|e| crate::Error::Custom(e.to_string()))?;
 
+        if index
+            .entries()
+            .iter()
+            .any(|e| e.stage() != gix::index::entry::Stage::Unconflicted)
+        {
+            return Err(crate::Error::Custom(
+                "Resolve and add the conflicted files before committing".to_string(),
+            ));
+        }
+
         let mut trees: std::collections::HashMap<Vec<u8>, gix::objs::Tree> =
             std::collections::HashMap::new();
 
@@ -312,9 +362,22 @@ pub fn commit(path: &Path, message: &str) -> Result<String, crate::Error> {
                                 oid: entry.id,
                             });
                     } else {
-                        trees
+                        // Link the subdirectory into its parent; the real tree id is filled in
+                        // once the subtree has been written below.
+                        let parent = trees
                             .entry(parent_path)
                             .or_insert_with(gix::objs::Tree::empty);
+                        if !parent
+                            .entries
+                            .iter()
+                            .any(|e| e.filename == parts[i].as_bytes())
+                        {
+                            parent.entries.push(gix::objs::tree::Entry {
+                                mode: gix::objs::tree::EntryKind::Tree.into(),
+                                filename: parts[i].as_bytes().into(),
+                                oid: gix::ObjectId::null(repo.object_hash()),
+                            });
+                        }
                     }
                 }
             }
@@ -358,16 +421,19 @@ pub fn commit(path: &Path, message: &str) -> Result<String, crate::Error> {
             .ok_or_else(|| crate::Error::Custom("Failed to create root tree".to_string()))?
     };
 
+    // A pending merge contributes its other side as the second parent.
     let parents: Vec<gix::ObjectId> = repo
         .head_id()
         .ok()
         .map(|id| id.detach())
         .into_iter()
+        .chain(super::merge::merge_head(&repo))
         .collect();
 
     let commit_id = repo
         .commit("HEAD", message, tree_id, parents)
         .map_err(|e| crate::Error::Custom(e.to_string()))?;
+    super::merge::clear_merge_state(&repo)?;
 
     Ok(commit_id.to_string())
 }
@@ -399,18 +465,7 @@ pub fn log(path: &Path, limit: u32) -> Result<Vec<CommitInfo>, crate::Error> {
             .decode()
             .map_err(|e| crate::Error::Custom(e.to_string()))?;
 
-        commits.push(CommitInfo {
-            id: oid.to_string(),
-            message: commit_ref.message.to_string(),
-            author: commit_ref.author.name.to_string(),
-            timestamp: commit_ref
-                .author
-                .time
-                .split_whitespace()
-                .next()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(0),
-        });
+        commits.push(commit_info(oid, &commit_ref));
 
         current = commit_ref.parents().next();
         count += 1;
@@ -419,6 +474,25 @@ pub fn log(path: &Path, limit: u32) -> Result<Vec<CommitInfo>, crate::Error> {
     Ok(commits)
 }
 
+pub(super) fn commit_info(id: gix::ObjectId, commit: &gix::objs::CommitRef<'_>) -> CommitInfo {
+    CommitInfo {
+        id: id.to_string(),
+        message: commit.message.to_string(),
+        author: commit.author.name.to_string(),
+        timestamp: signature_seconds(&commit.author),
+    }
+}
+
+// Seconds since the epoch; signatures store their time as `<seconds> <offset>`.
+pub(super) fn signature_seconds(signature: &gix::actor::SignatureRef<'_>) -> i64 {
+    signature
+        .time
+        .split_whitespace()
+        .next()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(0)
+}
+
 pub fn get_current_branch(path: &Path) -> Result<String, crate::Error> {
     let repo = gix::discover(path)?;
 
@@ -474,3 +548,37 @@ pub(super) fn create_stat_from_metadata(metadata: &std::fs::Metadata) -> gix::in
         size,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::testing::{commit_all, init_repo, write};
+
+    #[test]
+    fn test_commit_links_nested_directories() {
+        // `sessions` and `sessions/work` only contain directories, so they have no blob entries
+        // of their own to create their trees.
+        let dir = init_repo();
+        write(dir.path(), "sessions/work/abc/_memo.md", "memo\n");
+        write(dir.path(), "sessions/work/abc/attachments/a.png", "png");
+        let commit = commit_all(dir.path(), "nested");
+
+        let repo = gix::discover(dir.path()).unwrap();
+        let tree = repo
+            .find_object(commit)
+            .unwrap()
+            .try_into_commit()
+            .unwrap()
+            .tree()
+            .unwrap();
+        for path in [
+            "sessions/work/abc/_memo.md",
+            "sessions/work/abc/attachments/a.png",
+        ] {
+            assert!(tree.lookup_entry_by_path(path).unwrap().is_some(), "{path}");
+        }
+
+        assert!(pending_changes(dir.path()).unwrap().is_empty());
+        assert_eq!(log(dir.path(), 10).unwrap()[0].id, commit.to_string());
+    }
+}
@@ -1,7 +1,17 @@
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::path::Path;
 
+use super::resolve::{self, Merged};
 use crate::types::ConflictInfo;
 
+const MERGE_STATE_FILES: [&str; 3] = ["MERGE_HEAD", "MERGE_MSG", "MERGE_MODE"];
+
+pub(super) enum MergeOutcome {
+    // Worktree, index and `MERGE_HEAD` are ready for the merge commit.
+    Ready,
+    Conflicts(Vec<String>),
+}
+
 pub fn check_conflicts(path: &Path) -> Result<Option<ConflictInfo>, crate::Error> {
     let repo = gix::discover(path)?;
 
@@ -38,19 +48,7 @@ pub fn abort_merge(path: &Path) -> Result<(), crate::Error> {
     let repo = gix::discover(path)?;
     let git_dir = repo.git_dir();
 
-    let merge_head = git_dir.join("MERGE_HEAD");
-    let merge_msg = git_dir.join("MERGE_MSG");
-    let merge_mode = git_dir.join("MERGE_MODE");
-
-    if merge_head.exists() {
-        std::fs::remove_file(merge_head)?;
-    }
-    if merge_msg.exists() {
-        std::fs::remove_file(merge_msg)?;
-    }
-    if merge_mode.exists() {
-        std::fs::remove_file(merge_mode)?;
-    }
+    clear_merge_state(&repo)?;
 
     let head_commit = repo
         .head_id()
@@ -188,3 +186,628 @@ pub(super) fn populate_index_from_tree(
 
     Ok(())
 }
+
+pub(super) fn merge_head(repo: &gix::Repository) -> Option<gix::ObjectId> {
+    let content = std::fs::read_to_string(repo.git_dir().join("MERGE_HEAD")).ok()?;
+    gix::ObjectId::from_hex(content.trim().as_bytes()).ok()
+}
+
+pub(super) fn clear_merge_state(repo: &gix::Repository) -> Result<(), crate::Error> {
+    for name in MERGE_STATE_FILES {
+        let file = repo.git_dir().join(name);
+        if file.exists() {
+            std::fs::remove_file(file)?;
+        }
+    }
+    Ok(())
+}
+
+// Best common ancestor of both sides, as `git merge-base` picks it. A plain breadth-first search
+// would stop at the first shared commit, which after criss-cross merges is not a lowest one.
+pub(super) fn merge_base(
+    repo: &gix::Repository,
+    ours: gix::ObjectId,
+    theirs: gix::ObjectId,
+) -> Result<Option<gix::ObjectId>, crate::Error> {
+    match repo.merge_base(ours, theirs) {
+        Ok(id) => Ok(Some(id.detach())),
+        Err(gix::repository::merge_base::Error::NotFound { .. }) => Ok(None),
+        Err(e) => Err(crate::Error::Custom(e.to_string())),
+    }
+}
+
+// Commits reachable from `tip` that `known` doesn't have yet.
+pub(super) fn count_new_commits(
+    repo: &gix::Repository,
+    tip: gix::ObjectId,
+    known: gix::ObjectId,
+) -> Result<u32, crate::Error> {
+    let known = ancestors(repo, known)?;
+    let count = ancestors(repo, tip)?
+        .into_iter()
+        .filter(|id| !known.contains(id))
+        .count();
+    Ok(count as u32)
+}
+
+// Moves the worktree and index from `from` to `to`, touching only the files that differ.
+pub(super) fn fast_forward(
+    repo: &gix::Repository,
+    from: gix::ObjectId,
+    to: gix::ObjectId,
+) -> Result<(), crate::Error> {
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| crate::Error::Custom("No working directory".to_string()))?;
+
+    let from_files = tree_files(repo, from)?;
+    let to_files = tree_files(repo, to)?;
+    ensure_nothing_staged(repo, &from_files)?;
+
+    let changed: Vec<(&String, Option<gix::ObjectId>)> = from_files
+        .keys()
+        .chain(to_files.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|path| from_files.get(*path) != to_files.get(*path))
+        .map(|path| (path, to_files.get(path).copied()))
+        .collect();
+
+    for (path, _) in &changed {
+        ensure_unchanged(repo, workdir, path, from_files.get(*path).copied())?;
+    }
+    let mut written = BTreeSet::new();
+    for (path, id) in changed {
+        let data = id.map(|id| read_blob(repo, id)).transpose()?;
+        write_worktree_file(workdir, path, data.as_deref())?;
+        written.insert(path.as_str());
+    }
+
+    let mut state = index_for_files(repo, workdir, &to_files, &written)?;
+    state.sort_entries();
+    write_index(repo, state)
+}
+
+// Three-way merge of `theirs` into the checked-out `ours`. Files changed on one side take that
+// side; files changed on both are merged by `resolve::merge_file`. Anything that can't be merged
+// is left in the index as stages 1-3 with the best-effort content in the worktree, which is what
+// `check_conflicts` reports until the files are added again.
+pub(super) fn merge_trees(
+    repo: &gix::Repository,
+    base: Option<gix::ObjectId>,
+    ours: gix::ObjectId,
+    theirs: gix::ObjectId,
+    message: &str,
+) -> Result<MergeOutcome, crate::Error> {
+    let workdir = repo
+        .workdir()
+        .ok_or_else(|| crate::Error::Custom("No working directory".to_string()))?;
+
+    let base_files = match base {
+        Some(base) => tree_files(repo, base)?,
+        None => BTreeMap::new(),
+    };
+    let ours_files = tree_files(repo, ours)?;
+    let theirs_files = tree_files(repo, theirs)?;
+    ensure_nothing_staged(repo, &ours_files)?;
+
+    let paths: BTreeSet<&String> = base_files
+        .keys()
+        .chain(ours_files.keys())
+        .chain(theirs_files.keys())
+        .collect();
+
+    let mut merged: BTreeMap<String, gix::ObjectId> = BTreeMap::new();
+    let mut conflicts: Vec<(String, [Option<gix::ObjectId>; 3])> = Vec::new();
+    let mut writes: Vec<(String, Option<Vec<u8>>)> = Vec::new();
+
+    for path in paths {
+        let b = base_files.get(path).copied();
+        let o = ours_files.get(path).copied();
+        let t = theirs_files.get(path).copied();
+
+        if o == t || t == b {
+            merged.extend(o.map(|o| (path.clone(), o)));
+            continue;
+        }
+        if o == b {
+            merged.extend(t.map(|t| (path.clone(), t)));
+            writes.push((path.clone(), t.map(|t| read_blob(repo, t)).transpose()?));
+            continue;
+        }
+
+        match (o, t) {
+            (Some(o), Some(t)) => {
+                let base_data = b.map(|b| read_blob(repo, b)).transpose()?;
+                let ours_data = read_blob(repo, o)?;
+                let theirs_data = read_blob(repo, t)?;
+
+                match resolve::merge_file(path, base_data.as_deref(), &ours_data, &theirs_data) {
+                    Merged::Clean(data) => {
+                        let id = repo
+                            .write_blob(&data)
+                            .map_err(|e| crate::Error::Custom(e.to_string()))?;
+                        merged.insert(path.clone(), id.detach());
+                        writes.push((path.clone(), Some(data)));
+                    }
+                    Merged::Conflict(data) => {
+                        conflicts.push((path.clone(), [b, Some(o), Some(t)]));
+                        writes.push((path.clone(), Some(data)));
+                    }
+                }
+            }
+            // Edited on one side, deleted on the other: keep the edited file around.
+            (None, Some(t)) => {
+                conflicts.push((path.clone(), [b, None, Some(t)]));
+                writes.push((path.clone(), Some(read_blob(repo, t)?)));
+            }
+            (Some(o), None) => conflicts.push((path.clone(), [b, Some(o), None])),
+            (None, None) => {}
+        }
+    }
+
+    for (path, _) in &writes {
+        ensure_unchanged(repo, workdir, path, ours_files.get(path).copied())?;
+    }
+    for (path, data) in &writes {
+        write_worktree_file(workdir, path, data.as_deref())?;
+    }
+
+    let written = writes.iter().map(|(path, _)| path.as_str()).collect();
+    let mut state = index_for_files(repo, workdir, &merged, &written)?;
+    for (path, ids) in &conflicts {
+        let stages = [
+            gix::index::entry::Stage::Base,
+            gix::index::entry::Stage::Ours,
+            gix::index::entry::Stage::Theirs,
+        ];
+        for (stage, id) in stages.into_iter().zip(ids) {
+            if let Some(id) = id {
+                state.dangerously_push_entry(
+                    Default::default(),
+                    *id,
+                    gix::index::entry::Flags::from_stage(stage),
+                    gix::index::entry::Mode::FILE,
+                    path.as_bytes().into(),
+                );
+            }
+        }
+    }
+    state.sort_entries();
+    write_index(repo, state)?;
+
+    let git_dir = repo.git_dir();
+    std::fs::write(git_dir.join("MERGE_HEAD"), format!("{}\n", theirs))?;
+    std::fs::write(git_dir.join("MERGE_MSG"), format!("{}\n", message))?;
+
+    if conflicts.is_empty() {
+        Ok(MergeOutcome::Ready)
+    } else {
+        Ok(MergeOutcome::Conflicts(
+            conflicts.into_iter().map(|(path, _)| path).collect(),
+        ))
+    }
+}
+
+fn ancestors(
+    repo: &gix::Repository,
+    tip: gix::ObjectId,
+) -> Result<HashSet<gix::ObjectId>, crate::Error> {
+    let mut seen = HashSet::new();
+    let mut pending = vec![tip];
+    while let Some(id) = pending.pop() {
+        if seen.insert(id) {
+            pending.extend(parents(repo, id)?);
+        }
+    }
+    Ok(seen)
+}
+
+fn find_commit(repo: &gix::Repository, id: gix::ObjectId) -> Result<gix::Commit<'_>, crate::Error> {
+    repo.find_object(id)
+        .map_err(|e| crate::Error::Custom(e.to_string()))?
+        .try_into_commit()
+        .map_err(|e| crate::Error::Custom(e.to_string()))
+}
+
+fn parents(repo: &gix::Repository, id: gix::ObjectId) -> Result<Vec<gix::ObjectId>, crate::Error> {
+    Ok(find_commit(repo, id)?
+        .parent_ids()
+        .map(|id| id.detach())
+        .collect())
+}
+
+fn commit_tree(repo: &gix::Repository, id: gix::ObjectId) -> Result<gix::ObjectId, crate::Error> {
+    find_commit(repo, id)?
+        .tree_id()
+        .map(|id| id.detach())
+        .map_err(|e| crate::Error::Custom(e.to_string()))
+}
+
+// Every blob in the commit's tree, keyed by repository-relative path.
+fn tree_files(
+    repo: &gix::Repository,
+    commit: gix::ObjectId,
+) -> Result<BTreeMap<String, gix::ObjectId>, crate::Error> {
+    let mut files = BTreeMap::new();
+    let mut pending = vec![(String::new(), commit_tree(repo, commit)?)];
+
+    while let Some((prefix, tree_id)) = pending.pop() {
+        let tree = repo
+            .find_object(tree_id)
+            .map_err(|e| crate::Error::Custom(e.to_string()))?
+            .try_into_tree()
+            .map_err(|e| crate::Error::Custom(e.to_string()))?;
+
+        for entry in tree.iter() {
+            let entry = entry.map_err(|e| crate::Error::Custom(e.to_string()))?;
+            let entry_path = if prefix.is_empty() {
+                entry.inner.filename.to_string()
+            } else {
+                format!("{}/{}", prefix, entry.inner.filename)
+            };
+
+            if entry.inner.mode.is_tree() {
+                pending.push((entry_path, entry.inner.oid.into()));
+            } else {
+                files.insert(entry_path, entry.inner.oid.into());
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+fn read_blob(repo: &gix::Repository, id: gix::ObjectId) -> Result<Vec<u8>, crate::Error> {
+    Ok(repo
+        .find_object(id)
+        .map_err(|e| crate::Error::Custom(e.to_string()))?
+        .detach()
+        .data)
+}
+
+// The index is rebuilt from the merged tree, so anything staged but not yet committed would be
+// silently dropped. A repository without an index has nothing staged.
+fn ensure_nothing_staged(
+    repo: &gix::Repository,
+    committed: &BTreeMap<String, gix::ObjectId>,
+) -> Result<(), crate::Error> {
+    if !repo.git_dir().join("index").exists() {
+        return Ok(());
+    }
+
+    let index = repo
+        .index_or_empty()
+        .map_err(|e| crate::Error::Custom(e.to_string()))?;
+    let staged: BTreeMap<String, gix::ObjectId> = index
+        .entries()
+        .iter()
+        .map(|entry| {
+            (
+                String::from_utf8_lossy(entry.path(&index)).to_string(),
+                entry.id,
+            )
+        })
+        .collect();
+
+    if staged.len() == index.entries().len() && &staged == committed {
+        Ok(())
+    } else {
+        Err(crate::Error::Custom(
+            "Staged changes would be lost; commit or unstage them before pulling".to_string(),
+        ))
+    }
+}
+
+// Refuses to overwrite a worktree file whose content isn't what `expected` says is committed.
+fn ensure_unchanged(
+    repo: &gix::Repository,
+    workdir: &Path,
+    path: &str,
+    expected: Option<gix::ObjectId>,
+) -> Result<(), crate::Error> {
+    let full_path = workdir.join(path);
+    let current = if full_path.is_file() {
+        let data = std::fs::read(&full_path)?;
+        Some(
+            gix::objs::compute_hash(repo.object_hash(), gix::objs::Kind::Blob, &data)
+                .map_err(|e| crate::Error::Custom(e.to_string()))?,
+        )
+    } else {
+        None
+    };
+
+    if current == expected {
+        Ok(())
+    } else {
+        Err(crate::Error::Custom(format!(
+            "Local changes to {} would be overwritten; commit them before pulling",
+            path
+        )))
+    }
+}
+
+fn write_worktree_file(
+    workdir: &Path,
+    path: &str,
+    data: Option<&[u8]>,
+) -> Result<(), crate::Error> {
+    let full_path = workdir.join(path);
+    match data {
+        Some(data) => {
+            if let Some(parent) = full_path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&full_path, data)?;
+        }
+        None => {
+            if full_path.exists() {
+                std::fs::remove_file(&full_path)?;
+            }
+            // Drop directories the removal left empty, e.g. a deleted session folder.
+            let mut dir = full_path.parent();
+            while let Some(current) = dir.filter(|d| *d != workdir) {
+                if std::fs::remove_dir(current).is_err() {
+                    break;
+                }
+                dir = current.parent();
+            }
+        }
+    }
+    Ok(())
+}
+
+// Index entries for `files`. Only paths the caller just `written` are stat'ed from the worktree;
+// every other path keeps the stat it already had, so local edits to files the merge never touched
+// still show up as unstaged. Paths without a usable previous stat get a zeroed one, which makes
+// `status` compare their content.
+fn index_for_files(
+    repo: &gix::Repository,
+    workdir: &Path,
+    files: &BTreeMap<String, gix::ObjectId>,
+    written: &BTreeSet<&str>,
+) -> Result<gix::index::State, crate::Error> {
+    let index = repo
+        .index_or_empty()
+        .map_err(|e| crate::Error::Custom(e.to_string()))?;
+    let previous: BTreeMap<String, (gix::ObjectId, gix::index::entry::Stat)> = index
+        .entries()
+        .iter()
+        .filter(|entry| entry.stage() == gix::index::entry::Stage::Unconflicted)
+        .map(|entry| {
+            (
+                String::from_utf8_lossy(entry.path(&index)).to_string(),
+                (entry.id, entry.stat),
+            )
+        })
+        .collect();
+
+    let mut state = gix::index::State::new(repo.object_hash());
+    for (path, id) in files {
+        let stat = if written.contains(path.as_str()) {
+            std::fs::metadata(workdir.join(path))
+                .map(|metadata| super::local::create_stat_from_metadata(&metadata))
+                .unwrap_or_default()
+        } else {
+            match previous.get(path) {
+                Some((previous_id, stat)) if previous_id == id => *stat,
+                _ => Default::default(),
+            }
+        };
+        state.dangerously_push_entry(
+            stat,
+            *id,
+            gix::index::entry::Flags::empty(),
+            gix::index::entry::Mode::FILE,
+            path.as_bytes().into(),
+        );
+    }
+    Ok(state)
+}
+
+fn write_index(repo: &gix::Repository, state: gix::index::State) -> Result<(), crate::Error> {
+    let index_path = repo.git_dir().join("index");
+    let index = gix::index::File::from_state(state, index_path.clone());
+    let file = std::fs::File::create(&index_path)?;
+    index
+        .write_to(file, gix::index::write::Options::default())
+        .map_err(|e| crate::Error::Custom(e.to_string()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::operations::local;
+    use crate::operations::testing::{commit_all, commit_files, init_repo, read, write};
+
+    const NOTE: &str = "sessions/a/_memo.md";
+    const BASE_NOTE: &str = "---\ntitle: Standup\n---\n\nline one\nline two\nline three\n";
+
+    #[test]
+    fn test_merge_base_prefers_lowest_common_ancestor() {
+        let dir = init_repo();
+        let root = commit_files(dir.path(), &[("a.md", "a")], &[]);
+        let newer = commit_files(dir.path(), &[("a.md", "b")], &[root]);
+        let ours = commit_files(dir.path(), &[("a.md", "c")], &[newer]);
+        // `theirs` reaches `root` directly before it reaches `newer`.
+        let side = commit_files(dir.path(), &[("a.md", "d")], &[newer]);
+        let theirs = commit_files(dir.path(), &[("a.md", "e")], &[root, side]);
+
+        let repo = gix::discover(dir.path()).unwrap();
+        assert_eq!(merge_base(&repo, ours, theirs).unwrap(), Some(newer));
+
+        let unrelated = commit_files(dir.path(), &[("b.md", "x")], &[]);
+        assert_eq!(merge_base(&repo, ours, unrelated).unwrap(), None);
+    }
+
+    #[test]
+    fn test_fast_forward() {
+        let dir = init_repo();
+        write(dir.path(), NOTE, BASE_NOTE);
+        let base = commit_all(dir.path(), "base");
+        let theirs = commit_files(dir.path(), &[("b.md", "new")], &[base]);
+
+        let repo = gix::discover(dir.path()).unwrap();
+        fast_forward(&repo, base, theirs).unwrap();
+
+        assert!(!dir.path().join(NOTE).exists());
+        assert_eq!(read(dir.path(), "b.md"), "new");
+        assert!(local::status(dir.path()).unwrap().unstaged.is_empty());
+    }
+
+    #[test]
+    fn test_merges_divergent_commits() {
+        let dir = init_repo();
+        write(dir.path(), NOTE, BASE_NOTE);
+        write(
+            dir.path(),
+            "sessions/a/_meta.json",
+            r#"{"title":"Standup"}"#,
+        );
+        let base = commit_all(dir.path(), "base");
+
+        let theirs = commit_files(
+            dir.path(),
+            &[
+                (
+                    NOTE,
+                    "---\ntitle: Standup\n---\n\nline one\nline two\nline three, remote\n",
+                ),
+                (
+                    "sessions/a/_meta.json",
+                    r#"{"title":"Standup","tags":["eng"]}"#,
+                ),
+                ("sessions/b/_memo.md", "remote only\n"),
+            ],
+            &[base],
+        );
+
+        write(
+            dir.path(),
+            NOTE,
+            "---\ntitle: Standup\n---\n\nline one, local\nline two\nline three\n",
+        );
+        let ours = commit_all(dir.path(), "local edit");
+
+        let repo = gix::discover(dir.path()).unwrap();
+        let base = merge_base(&repo, ours, theirs).unwrap();
+        let outcome = merge_trees(&repo, base, ours, theirs, "Merge").unwrap();
+        assert!(matches!(outcome, MergeOutcome::Ready));
+
+        assert_eq!(
+            read(dir.path(), NOTE),
+            "---\ntitle: Standup\n---\n\nline one, local\nline two\nline three, remote\n"
+        );
+        assert!(read(dir.path(), "sessions/a/_meta.json").contains("eng"));
+        assert_eq!(read(dir.path(), "sessions/b/_memo.md"), "remote only\n");
+        assert!(check_conflicts(dir.path()).unwrap().is_none());
+
+        local::commit(dir.path(), "Merge").unwrap();
+        let repo = gix::discover(dir.path()).unwrap();
+        let head = find_commit(&repo, repo.head_id().unwrap().detach()).unwrap();
+        let parents: Vec<_> = head.parent_ids().map(|id| id.detach()).collect();
+        assert_eq!(parents, [ours, theirs]);
+        assert!(merge_head(&repo).is_none());
+    }
+
+    #[test]
+    fn test_conflicting_edits_can_be_aborted() {
+        let dir = init_repo();
+        write(dir.path(), NOTE, BASE_NOTE);
+        let base = commit_all(dir.path(), "base");
+
+        let theirs = commit_files(
+            dir.path(),
+            &[(
+                NOTE,
+                "---\ntitle: Standup\n---\n\nline one\nline two, remote\nline three\n",
+            )],
+            &[base],
+        );
+        let local_note = "---\ntitle: Standup\n---\n\nline one\nline two, local\nline three\n";
+        write(dir.path(), NOTE, local_note);
+        let ours = commit_all(dir.path(), "local edit");
+
+        let repo = gix::discover(dir.path()).unwrap();
+        let outcome = merge_trees(&repo, Some(base), ours, theirs, "Merge").unwrap();
+        assert!(matches!(outcome, MergeOutcome::Conflicts(ref files) if files == &[NOTE]));
+
+        let merged = read(dir.path(), NOTE);
+        assert!(merged.contains("<<<<<<< local\nline two, local\n=======\nline two, remote\n"));
+        assert_eq!(check_conflicts(dir.path()).unwrap().unwrap().files, [NOTE]);
+        assert!(local::commit(dir.path(), "Merge").is_err());
+
+        abort_merge(dir.path()).unwrap();
+        assert_eq!(read(dir.path(), NOTE), local_note);
+        assert!(check_conflicts(dir.path()).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_keeps_untouched_local_edits_unstaged() {
+        let dir = init_repo();
+        write(dir.path(), NOTE, BASE_NOTE);
+        write(dir.path(), "c.md", "committed");
+        let base = commit_all(dir.path(), "base");
+        let remote = commit_files(
+            dir.path(),
+            &[(NOTE, BASE_NOTE), ("c.md", "committed"), ("b.md", "remote")],
+            &[base],
+        );
+        write(dir.path(), "d.md", "local");
+        let ours = commit_all(dir.path(), "local");
+
+        write(dir.path(), "c.md", "edited locally, not committed");
+        let repo = gix::discover(dir.path()).unwrap();
+        let outcome = merge_trees(&repo, Some(base), ours, remote, "Merge").unwrap();
+        assert!(matches!(outcome, MergeOutcome::Ready));
+
+        let unstaged = local::status(dir.path()).unwrap().unstaged;
+        assert_eq!(
+            unstaged.iter().map(|f| f.path.as_str()).collect::<Vec<_>>(),
+            ["c.md"]
+        );
+        assert_eq!(read(dir.path(), "b.md"), "remote");
+    }
+
+    #[test]
+    fn test_fast_forward_keeps_untouched_local_edits_unstaged() {
+        let dir = init_repo();
+        write(dir.path(), NOTE, BASE_NOTE);
+        write(dir.path(), "c.md", "committed");
+        let base = commit_all(dir.path(), "base");
+        let theirs = commit_files(
+            dir.path(),
+            &[(NOTE, BASE_NOTE), ("c.md", "committed"), ("b.md", "new")],
+            &[base],
+        );
+
+        write(dir.path(), "c.md", "edited locally, not committed");
+        let repo = gix::discover(dir.path()).unwrap();
+        fast_forward(&repo, base, theirs).unwrap();
+
+        let unstaged = local::status(dir.path()).unwrap().unstaged;
+        assert_eq!(
+            unstaged.iter().map(|f| f.path.as_str()).collect::<Vec<_>>(),
+            ["c.md"]
+        );
+    }
+
+    #[test]
+    fn test_refuses_to_merge_over_staged_changes() {
+        let dir = init_repo();
+        write(dir.path(), NOTE, BASE_NOTE);
+        let base = commit_all(dir.path(), "base");
+        let theirs = commit_files(dir.path(), &[("b.md", "remote")], &[base]);
+        write(dir.path(), "c.md", "local");
+        let ours = commit_all(dir.path(), "local");
+
+        write(dir.path(), "staged.md", "not committed yet");
+        local::add(dir.path(), vec!["staged.md".to_string()]).unwrap();
+
+        let repo = gix::discover(dir.path()).unwrap();
+        assert!(merge_trees(&repo, Some(base), ours, theirs, "Merge").is_err());
+        assert!(!dir.path().join("b.md").exists());
+        assert!(merge_head(&repo).is_none());
+    }
+}
@@ -1,3 +1,8 @@
+pub mod history;
 pub mod local;
 pub mod merge;
 pub mod remote;
+mod resolve;
+
+#[cfg(test)]
+pub(crate) mod testing;
@@ -1,5 +1,6 @@
 use std::path::Path;
 
+use super::merge;
 use crate::types::{PullResult, PushResult, RemoteInfo};
 
 pub fn add_remote(path: &Path, name: &str, url: &str) -> Result<(), crate::Error> {
@@ -114,44 +115,41 @@ pub fn pull(path: &Path, remote_name: &str, branch: &str) -> Result<PullResult,
         return Ok(PullResult::AlreadyUpToDate);
     }
 
-    let head_ref = repo.git_dir().join("refs/heads").join(branch);
-    std::fs::write(&head_ref, format!("{}\n", remote_commit))?;
-
-    let remote_tree = repo
-        .find_object(remote_commit)
-        .map_err(|e| crate::Error::Custom(e.to_string()))?
-        .try_into_commit()
-        .map_err(|e| crate::Error::Custom(e.to_string()))?
-        .tree_id()
-        .map_err(|e| crate::Error::Custom(e.to_string()))?;
-
-    let workdir = repo
-        .workdir()
-        .ok_or_else(|| crate::Error::Custom("No working directory".to_string()))?;
-
-    let tree_obj = repo
-        .find_object(remote_tree)
-        .map_err(|e| crate::Error::Custom(e.to_string()))?
-        .try_into_tree()
-        .map_err(|e| crate::Error::Custom(e.to_string()))?;
-
-    let entries: Result<Vec<_>, _> = tree_obj.iter().collect();
-    let entries = entries.map_err(|e| crate::Error::Custom(e.to_string()))?;
+    if merge::merge_head(&repo).is_some() {
+        return Err(crate::Error::Custom(
+            "Finish or abort the pending merge before pulling".to_string(),
+        ));
+    }
 
-    for entry in entries {
-        super::merge::restore_tree_entry(&repo, workdir, entry.inner.into(), Vec::new())?;
+    let base = merge::merge_base(&repo, local_commit, remote_commit)?;
+    if base == Some(remote_commit) {
+        return Ok(PullResult::AlreadyUpToDate);
     }
+    let commits_pulled = merge::count_new_commits(&repo, remote_commit, local_commit)?;
+    let is_checked_out = super::local::get_current_branch(path)? == branch;
 
-    let mut new_state = gix::index::State::new(repo.object_hash());
-    super::merge::populate_index_from_tree(&repo, &mut new_state, remote_tree.into(), Vec::new())?;
+    if base == Some(local_commit) {
+        if is_checked_out {
+            merge::fast_forward(&repo, local_commit, remote_commit)?;
+        }
+        let head_ref = repo.git_dir().join("refs/heads").join(branch);
+        std::fs::write(&head_ref, format!("{}\n", remote_commit))?;
+        return Ok(PullResult::Success { commits_pulled });
+    }
 
-    let index_path = repo.git_dir().join("index");
-    let new_index = gix::index::File::from_state(new_state, index_path.clone());
-    let options = gix::index::write::Options::default();
-    let file = std::fs::File::create(&index_path)?;
-    new_index
-        .write_to(file, options)
-        .map_err(|e| crate::Error::Custom(e.to_string()))?;
+    if !is_checked_out {
+        return Err(crate::Error::Custom(format!(
+            "Cannot merge into {} because it is not checked out",
+            branch
+        )));
+    }
 
-    Ok(PullResult::Success { commits_pulled: 1 })
+    let message = format!("Merge {}/{} into {}", remote_name, branch, branch);
+    match merge::merge_trees(&repo, base, local_commit, remote_commit, &message)? {
+        merge::MergeOutcome::Ready => {
+            super::local::commit(path, &message)?;
+            Ok(PullResult::Success { commits_pulled })
+        }
+        merge::MergeOutcome::Conflicts(files) => Ok(PullResult::Conflicts { files }),
+    }
 }
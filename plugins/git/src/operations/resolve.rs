@@ -0,0 +1,528 @@
+use std::collections::HashMap;
+use std::ops::Range;
+use std::str::FromStr;
+
+use serde_json::{Map, Value};
+
+const TRANSCRIPT_FILE: &str = "transcript.json";
+const LOCAL_MARKER: &str = "<<<<<<< local";
+const SEPARATOR_MARKER: &str = "=======";
+const REMOTE_MARKER: &str = ">>>>>>> remote";
+
+type Frontmatter = Map<String, Value>;
+
+pub(super) enum Merged {
+    Clean(Vec<u8>),
+    // Content to leave in the working tree for the user to resolve.
+    Conflict(Vec<u8>),
+}
+
+// Three-way merge of one file that both sides changed. `base` is `None` when both sides added it.
+pub(super) fn merge_file(path: &str, base: Option<&[u8]>, ours: &[u8], theirs: &[u8]) -> Merged {
+    let (Ok(base), Ok(ours_text), Ok(theirs_text)) = (
+        std::str::from_utf8(base.unwrap_or_default()),
+        std::str::from_utf8(ours),
+        std::str::from_utf8(theirs),
+    ) else {
+        return Merged::Conflict(ours.to_vec());
+    };
+
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    let merged = if file_name == TRANSCRIPT_FILE {
+        merge_transcript(base, ours_text, theirs_text)
+    } else if file_name.ends_with(".json") {
+        merge_json(base, ours_text, theirs_text)
+    } else if file_name.ends_with(".md") {
+        merge_markdown(base, ours_text, theirs_text)
+    } else {
+        None
+    };
+
+    match merged {
+        Some(merged) => merged,
+        None => {
+            let (text, conflicted) = merge_lines(base, ours_text, theirs_text);
+            if conflicted {
+                Merged::Conflict(text.into_bytes())
+            } else {
+                Merged::Clean(text.into_bytes())
+            }
+        }
+    }
+}
+
+// Frontmatter is merged key by key and the body line by line. Notes without frontmatter fall
+// back to a plain line merge.
+fn merge_markdown(base: &str, ours: &str, theirs: &str) -> Option<Merged> {
+    let parse = |text: &str| hypr_frontmatter::Document::<Frontmatter>::from_str(text).ok();
+    let ours = parse(ours)?;
+    let theirs = parse(theirs)?;
+    let base = parse(base).unwrap_or_else(|| hypr_frontmatter::Document::new(Map::new(), base));
+
+    let (frontmatter, keys_conflicted) =
+        merge_maps(&base.frontmatter, &ours.frontmatter, &theirs.frontmatter);
+    let (content, body_conflicted) = merge_lines(&base.content, &ours.content, &theirs.content);
+
+    let rendered = hypr_frontmatter::Document::new(frontmatter, content)
+        .render()
+        .ok()?
+        .into_bytes();
+
+    if keys_conflicted || body_conflicted {
+        Some(Merged::Conflict(rendered))
+    } else {
+        Some(Merged::Clean(rendered))
+    }
+}
+
+// `_meta.json` and friends: top-level keys are merged independently. A key both sides changed
+// differently keeps the local value and marks the file conflicted, so the JSON stays parseable.
+fn merge_json(base: &str, ours: &str, theirs: &str) -> Option<Merged> {
+    let Value::Object(ours) = serde_json::from_str(ours).ok()? else {
+        return None;
+    };
+    let Value::Object(theirs) = serde_json::from_str(theirs).ok()? else {
+        return None;
+    };
+    let base = match serde_json::from_str(base) {
+        Ok(Value::Object(base)) => base,
+        _ => Map::new(),
+    };
+
+    let (merged, conflicted) = merge_maps(&base, &ours, &theirs);
+    let data = to_json(Value::Object(merged))?;
+
+    if conflicted {
+        Some(Merged::Conflict(data))
+    } else {
+        Some(Merged::Clean(data))
+    }
+}
+
+// Transcripts only ever grow or get corrected word by word, so both sides' words are kept:
+// entries are matched by id, deletions on one side are honoured, and when both sides edited the
+// same word the local edit wins. Never conflicts as long as every entry has an id.
+fn merge_transcript(base: &str, ours: &str, theirs: &str) -> Option<Merged> {
+    let transcripts = |text: &str| -> Option<Vec<Value>> {
+        serde_json::from_str::<Value>(text)
+            .ok()?
+            .get("transcripts")?
+            .as_array()
+            .cloned()
+    };
+
+    let ours_value: Value = serde_json::from_str(ours).ok()?;
+    let merged = merge_by_id(
+        &transcripts(base).unwrap_or_default(),
+        &transcripts(ours)?,
+        &transcripts(theirs)?,
+        merge_transcript_entry,
+    )?;
+
+    let mut result = ours_value.as_object()?.clone();
+    result.insert("transcripts".to_string(), Value::Array(merged));
+    Some(Merged::Clean(to_json(Value::Object(result))?))
+}
+
+fn merge_transcript_entry(base: Option<&Value>, ours: &Value, theirs: &Value) -> Option<Value> {
+    let list = |value: Option<&Value>, key: &str| -> Vec<Value> {
+        value
+            .and_then(|v| v.get(key))
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default()
+    };
+    let prefer_ours = |_: Option<&Value>, ours: &Value, _: &Value| Some(ours.clone());
+
+    let mut words = merge_by_id(
+        &list(base, "words"),
+        &list(Some(ours), "words"),
+        &list(Some(theirs), "words"),
+        prefer_ours,
+    )?;
+    words.sort_by_key(|word| word.get("start_ms").and_then(|s| s.as_i64()).unwrap_or(0));
+
+    let speaker_hints = merge_by_id(
+        &list(base, "speaker_hints"),
+        &list(Some(ours), "speaker_hints"),
+        &list(Some(theirs), "speaker_hints"),
+        prefer_ours,
+    )?;
+
+    let empty = Map::new();
+    let (mut entry, _) = merge_maps(
+        base.and_then(|b| b.as_object()).unwrap_or(&empty),
+        ours.as_object()?,
+        theirs.as_object()?,
+    );
+    entry.insert("words".to_string(), Value::Array(words));
+    entry.insert("speaker_hints".to_string(), Value::Array(speaker_hints));
+    Some(Value::Object(entry))
+}
+
+// Union of two lists of `{ "id": ... }` objects. Returns `None` if any entry lacks a string id.
+fn merge_by_id(
+    base: &[Value],
+    ours: &[Value],
+    theirs: &[Value],
+    both_changed: impl Fn(Option<&Value>, &Value, &Value) -> Option<Value>,
+) -> Option<Vec<Value>> {
+    let index = |list: &'_ [Value]| -> Option<HashMap<String, usize>> {
+        list.iter()
+            .enumerate()
+            .map(|(position, value)| Some((value.get("id")?.as_str()?.to_string(), position)))
+            .collect()
+    };
+    let (base_index, ours_index, theirs_index) = (index(base)?, index(ours)?, index(theirs)?);
+
+    // Keep local order, then append what only the remote has.
+    let mut keys: Vec<&String> = ours_index.keys().collect();
+    keys.sort_by_key(|key| ours_index[*key]);
+    let mut remote_only: Vec<&String> = theirs_index
+        .keys()
+        .filter(|key| !ours_index.contains_key(*key))
+        .collect();
+    remote_only.sort_by_key(|key| theirs_index[*key]);
+    keys.extend(remote_only);
+
+    let mut merged = Vec::new();
+    for key in keys {
+        let b = base_index.get(key).map(|&i| &base[i]);
+        let o = ours_index.get(key).map(|&i| &ours[i]);
+        let t = theirs_index.get(key).map(|&i| &theirs[i]);
+
+        let value = match (o, t) {
+            (Some(o), Some(t)) if o == t => Some(o.clone()),
+            (Some(o), Some(t)) if Some(o) == b => Some(t.clone()),
+            (Some(o), Some(t)) if Some(t) == b => Some(o.clone()),
+            (Some(o), Some(t)) => Some(both_changed(b, o, t)?),
+            // Deleted on one side and untouched on the other.
+            (Some(o), None) if Some(o) == b => None,
+            (None, Some(t)) if Some(t) == b => None,
+            (Some(o), None) => Some(o.clone()),
+            (None, Some(t)) => Some(t.clone()),
+            (None, None) => None,
+        };
+        merged.extend(value);
+    }
+
+    Some(merged)
+}
+
+// Returns the merged map and whether any key was changed differently on both sides; such keys
+// keep the local value.
+fn merge_maps(base: &Frontmatter, ours: &Frontmatter, theirs: &Frontmatter) -> (Frontmatter, bool) {
+    let mut merged = Map::new();
+    let mut conflicted = false;
+
+    let keys: Vec<&String> = ours
+        .keys()
+        .chain(theirs.keys().filter(|k| !ours.contains_key(*k)))
+        .chain(
+            base.keys()
+                .filter(|k| !ours.contains_key(*k) && !theirs.contains_key(*k)),
+        )
+        .collect();
+
+    for key in keys {
+        let (b, o, t) = (base.get(key), ours.get(key), theirs.get(key));
+        let value = if o == t || t == b {
+            o
+        } else if o == b {
+            t
+        } else {
+            conflicted = true;
+            o
+        };
+        if let Some(value) = value {
+            merged.insert(key.clone(), value.clone());
+        }
+    }
+
+    (merged, conflicted)
+}
+
+fn to_json(value: Value) -> Option<Vec<u8>> {
+    serde_json::to_string_pretty(&value)
+        .ok()
+        .map(String::into_bytes)
+}
+
+struct Hunk {
+    base: Range<usize>,
+    lines: Range<usize>,
+}
+
+fn hunks(base: &[&str], side: &[&str]) -> Vec<Hunk> {
+    similar::capture_diff_slices(similar::Algorithm::Myers, base, side)
+        .into_iter()
+        .filter_map(|op| {
+            let (tag, base, lines) = op.as_tag_tuple();
+            (tag != similar::DiffTag::Equal).then_some(Hunk { base, lines })
+        })
+        .collect()
+}
+
+// diff3-style line merge. Changes to disjoint regions of the base are combined; overlapping
+// changes that differ are written out between conflict markers.
+fn merge_lines(base: &str, ours: &str, theirs: &str) -> (String, bool) {
+    let base_lines: Vec<&str> = base.split_inclusive('\n').collect();
+    let ours_lines: Vec<&str> = ours.split_inclusive('\n').collect();
+    let theirs_lines: Vec<&str> = theirs.split_inclusive('\n').collect();
+
+    let ours_hunks = hunks(&base_lines, &ours_lines);
+    let theirs_hunks = hunks(&base_lines, &theirs_lines);
+
+    let mut output = String::new();
+    let mut conflicted = false;
+    let mut position = 0;
+    let (mut i, mut j) = (0, 0);
+
+    while i < ours_hunks.len() || j < theirs_hunks.len() {
+        let start = match (ours_hunks.get(i), theirs_hunks.get(j)) {
+            (Some(o), Some(t)) => o.base.start.min(t.base.start),
+            (Some(o), None) => o.base.start,
+            (None, Some(t)) => t.base.start,
+            (None, None) => unreachable!(),
+        };
+        let mut end = start;
+        let (ours_from, theirs_from) = (i, j);
+
+        // Grow the region until no hunk on either side touches it.
+        loop {
+            let overlaps = |hunk: &Hunk| {
+                hunk.base.start < end
+                    || (hunk.base.start == end && (hunk.base.is_empty() || start == end))
+            };
+            if let Some(hunk) = ours_hunks.get(i).filter(|h| overlaps(h)) {
+                end = end.max(hunk.base.end);
+                i += 1;
+            } else if let Some(hunk) = theirs_hunks.get(j).filter(|h| overlaps(h)) {
+                end = end.max(hunk.base.end);
+                j += 1;
+            } else {
+                break;
+            }
+        }
+
+        output.extend(base_lines[position..start].iter().copied());
+        position = end;
+
+        let ours_region = apply(
+            &base_lines,
+            &ours_lines,
+            &ours_hunks[ours_from..i],
+            start..end,
+        );
+        let theirs_region = apply(
+            &base_lines,
+            &theirs_lines,
+            &theirs_hunks[theirs_from..j],
+            start..end,
+        );
+
+        if ours_from == i {
+            output.push_str(&theirs_region);
+        } else if theirs_from == j || ours_region == theirs_region {
+            output.push_str(&ours_region);
+        } else {
+            conflicted = true;
+            push_marked(&mut output, LOCAL_MARKER, &ours_region);
+            push_marked(&mut output, SEPARATOR_MARKER, &theirs_region);
+            push_marked(&mut output, REMOTE_MARKER, "");
+        }
+    }
+
+    output.extend(base_lines[position..].iter().copied());
+    (output, conflicted)
+}
+
+// The region `range` of the base with one side's hunks applied.
+fn apply(base: &[&str], side: &[&str], hunks: &[Hunk], range: Range<usize>) -> String {
+    let mut output = String::new();
+    let mut cursor = range.start;
+    for hunk in hunks {
+        output.extend(base[cursor..hunk.base.start].iter().copied());
+        output.extend(side[hunk.lines.clone()].iter().copied());
+        cursor = hunk.base.end;
+    }
+    output.extend(base[cursor..range.end].iter().copied());
+    output
+}
+
+fn push_marked(output: &mut String, marker: &str, content: &str) {
+    output.push_str(marker);
+    output.push('\n');
+    output.push_str(content);
+    if !content.is_empty() && !content.ends_with('\n') {
+        output.push('\n');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn map(value: Value) -> Frontmatter {
+        value.as_object().unwrap().clone()
+    }
+
+    fn text(merged: Merged) -> (String, bool) {
+        match merged {
+            Merged::Clean(data) => (String::from_utf8(data).unwrap(), false),
+            Merged::Conflict(data) => (String::from_utf8(data).unwrap(), true),
+        }
+    }
+
+    #[test]
+    fn test_merge_lines_combines_edits_to_different_lines() {
+        let base = "one\ntwo\nthree\nfour\n";
+        let ours = "one, local\ntwo\nthree\nfour\n";
+        let theirs = "one\ntwo\nthree\nfour, remote\nfive\n";
+
+        assert_eq!(
+            merge_lines(base, ours, theirs),
+            (
+                "one, local\ntwo\nthree\nfour, remote\nfive\n".to_string(),
+                false
+            )
+        );
+    }
+
+    #[test]
+    fn test_merge_lines_marks_edits_to_the_same_line() {
+        let base = "one\ntwo\nthree\n";
+        let ours = "one\ntwo, local\nthree\n";
+        let theirs = "one\ntwo, remote\nthree\n";
+
+        let (merged, conflicted) = merge_lines(base, ours, theirs);
+        assert!(conflicted);
+        assert_eq!(
+            merged,
+            "one\n<<<<<<< local\ntwo, local\n=======\ntwo, remote\n>>>>>>> remote\nthree\n"
+        );
+
+        // Both sides making the same edit is not a conflict.
+        assert_eq!(merge_lines(base, ours, ours), (ours.to_string(), false));
+    }
+
+    #[test]
+    fn test_merge_transcript_by_word_id() {
+        let base = json!({ "transcripts": [{ "id": "t1", "words": [
+            { "id": "w1", "text": "hello", "start_ms": 0 },
+            { "id": "w2", "text": "wrld", "start_ms": 500 },
+        ], "speaker_hints": [] }] });
+        let ours = json!({ "transcripts": [{ "id": "t1", "words": [
+            { "id": "w1", "text": "Hello", "start_ms": 0 },
+            { "id": "w2", "text": "world", "start_ms": 500 },
+            { "id": "w3", "text": "again", "start_ms": 1000 },
+        ], "speaker_hints": [] }] });
+        let theirs = json!({ "transcripts": [{ "id": "t1", "words": [
+            { "id": "w0", "text": "oh", "start_ms": -200 },
+            { "id": "w1", "text": "hello", "start_ms": 0 },
+            { "id": "w2", "text": "World", "start_ms": 500 },
+        ], "speaker_hints": [{ "id": "h1", "word_id": "w0" }] }] });
+
+        let (merged, conflicted) = text(
+            merge_transcript(&base.to_string(), &ours.to_string(), &theirs.to_string()).unwrap(),
+        );
+        assert!(!conflicted);
+
+        let merged: Value = serde_json::from_str(&merged).unwrap();
+        let words: Vec<(&str, &str)> = merged["transcripts"][0]["words"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|w| (w["id"].as_str().unwrap(), w["text"].as_str().unwrap()))
+            .collect();
+        // w1: only local edited. w2: both edited, local wins. w0 and w3: one side added.
+        assert_eq!(
+            words,
+            [
+                ("w0", "oh"),
+                ("w1", "Hello"),
+                ("w2", "world"),
+                ("w3", "again")
+            ]
+        );
+        assert_eq!(merged["transcripts"][0]["speaker_hints"][0]["id"], "h1");
+    }
+
+    #[test]
+    fn test_merge_by_id_honours_deletions() {
+        let base = [json!({ "id": "a", "v": 1 }), json!({ "id": "b", "v": 1 })];
+        let ours = [json!({ "id": "b", "v": 1 })];
+        let theirs = [
+            json!({ "id": "a", "v": 1 }),
+            json!({ "id": "b", "v": 2 }),
+            json!({ "id": "c", "v": 1 }),
+        ];
+        let unreachable = |_: Option<&Value>, _: &Value, _: &Value| -> Option<Value> {
+            panic!("no entry was changed on both sides")
+        };
+
+        assert_eq!(
+            merge_by_id(&base, &ours, &theirs, unreachable).unwrap(),
+            [json!({ "id": "b", "v": 2 }), json!({ "id": "c", "v": 1 })]
+        );
+
+        let without_id = [json!({ "v": 1 })];
+        assert!(merge_by_id(&base, &without_id, &theirs, unreachable).is_none());
+    }
+
+    #[test]
+    fn test_merge_maps_key_changes_on_both_sides() {
+        let base = map(json!({ "title": "a", "kept": 1, "dropped_local": 1, "dropped_remote": 1 }));
+        let ours = map(json!({ "title": "a", "kept": 1, "dropped_remote": 1, "added_local": 1 }));
+        let theirs = map(json!({ "title": "b", "kept": 1, "dropped_local": 1, "added_remote": 1 }));
+
+        let (merged, conflicted) = merge_maps(&base, &ours, &theirs);
+        assert!(!conflicted);
+        assert_eq!(
+            Value::Object(merged),
+            json!({ "title": "b", "kept": 1, "added_local": 1, "added_remote": 1 })
+        );
+
+        // Removed locally but changed remotely: the removal wins and the file is flagged.
+        let theirs =
+            map(json!({ "title": "a", "kept": 1, "dropped_local": 2, "dropped_remote": 1 }));
+        let (merged, conflicted) = merge_maps(&base, &ours, &theirs);
+        assert!(conflicted);
+        assert!(!merged.contains_key("dropped_local"));
+    }
+
+    #[test]
+    fn test_merge_file_dispatches_by_type() {
+        let base = "---\ntitle: Standup\n---\n\nbody\n";
+        let ours = "---\ntitle: Standup\ntags:\n- eng\n---\n\nbody\n";
+        let theirs = "---\ntitle: Weekly\n---\n\nbody\nmore\n";
+        let (merged, conflicted) = text(merge_file(
+            "sessions/a/_memo.md",
+            Some(base.as_bytes()),
+            ours.as_bytes(),
+            theirs.as_bytes(),
+        ));
+        assert!(!conflicted);
+        assert!(merged.contains("title: Weekly"));
+        assert!(merged.contains("- eng"));
+        assert!(merged.ends_with("body\nmore\n"));
+
+        let (merged, conflicted) = text(merge_file(
+            "sessions/a/_meta.json",
+            Some(br#"{"a":1,"b":1}"#),
+            br#"{"a":2,"b":1}"#,
+            br#"{"a":3,"b":1}"#,
+        ));
+        assert!(conflicted);
+        assert_eq!(
+            serde_json::from_str::<Value>(&merged).unwrap(),
+            json!({ "a": 2, "b": 1 })
+        );
+
+        assert!(matches!(
+            merge_file("audio.bin", None, &[0xff, 0xfe], &[0xff]),
+            Merged::Conflict(data) if data == [0xff, 0xfe]
+        ));
+    }
+}
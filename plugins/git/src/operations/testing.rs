@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+pub(crate) fn init_repo() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    super::local::init(dir.path()).unwrap();
+
+    let config = dir.path().join(".git/config");
+    let mut content = std::fs::read_to_string(&config).unwrap();
+    content.push_str("[user]\n\tname = Test\n\temail = test@example.com\n");
+    std::fs::write(config, content).unwrap();
+
+    dir
+}
+
+pub(crate) fn write(dir: &Path, file: &str, content: &str) {
+    let path = dir.join(file);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+}
+
+pub(crate) fn read(dir: &Path, file: &str) -> String {
+    std::fs::read_to_string(dir.join(file)).unwrap()
+}
+
+// Stages the whole worktree and commits it on HEAD, the way auto-commit does.
+pub(crate) fn commit_all(dir: &Path, message: &str) -> gix::ObjectId {
+    super::local::add(dir, vec![".".to_string()]).unwrap();
+    let id = super::local::commit(dir, message).unwrap();
+    gix::ObjectId::from_hex(id.as_bytes()).unwrap()
+}
+
+// A commit holding exactly `files`, written without touching HEAD, the index or the worktree.
+// Stands in for commits made on another device.
+pub(crate) fn commit_files(
+    dir: &Path,
+    files: &[(&str, &str)],
+    parents: &[gix::ObjectId],
+) -> gix::ObjectId {
+    let repo = gix::discover(dir).unwrap();
+    let files = files
+        .iter()
+        .map(|(path, content)| (path.to_string(), content.as_bytes().to_vec()))
+        .collect();
+    let tree = write_tree(&repo, files);
+    let signature: gix::actor::Signature = repo.committer().unwrap().unwrap().into();
+
+    let commit = gix::objs::Commit {
+        message: "remote change".into(),
+        tree,
+        author: signature.clone(),
+        committer: signature,
+        encoding: None,
+        parents: parents.iter().copied().collect(),
+        extra_headers: Vec::new(),
+    };
+    repo.write_object(&commit).unwrap().detach()
+}
+
+fn write_tree(repo: &gix::Repository, files: BTreeMap<String, Vec<u8>>) -> gix::ObjectId {
+    let mut subtrees: BTreeMap<String, BTreeMap<String, Vec<u8>>> = BTreeMap::new();
+    let mut tree = gix::objs::Tree::empty();
+
+    for (path, content) in files {
+        match path.split_once('/') {
+            Some((dir, rest)) => {
+                subtrees
+                    .entry(dir.to_string())
+                    .or_default()
+                    .insert(rest.to_string(), content);
+            }
+            None => tree.entries.push(gix::objs::tree::Entry {
+                mode: gix::objs::tree::EntryKind::Blob.into(),
+                filename: path.as_str().into(),
+                oid: repo.write_blob(&content).unwrap().detach(),
+            }),
+        }
+    }
+    for (dir, files) in subtrees {
+        tree.entries.push(gix::objs::tree::Entry {
+            mode: gix::objs::tree::EntryKind::Tree.into(),
+            filename: dir.as_str().into(),
+            oid: write_tree(repo, files),
+        });
+    }
+
+    tree.entries.sort_by(|a, b| a.filename.cmp(&b.filename));
+    repo.write_object(&tree).unwrap().detach()
+}
@@ -65,3 +65,64 @@ pub enum PushResult {
 pub struct ConflictInfo {
     pub files: Vec<String>,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileVersion {
+    pub commit: CommitInfo,
+    pub change: FileChangeType,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub enum DiffContentKind {
+    Markdown,
+    Transcript,
+    Text,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub enum DiffLineKind {
+    Context,
+    Added,
+    Removed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DiffLine {
+    pub kind: DiffLineKind,
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DiffHunk {
+    pub old_start: u32,
+    pub old_lines: u32,
+    pub new_start: u32,
+    pub new_lines: u32,
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileDiff {
+    pub path: String,
+    pub from: String,
+    pub to: Option<String>,
+    pub kind: DiffContentKind,
+    pub hunks: Vec<DiffHunk>,
+    pub additions: u32,
+    pub deletions: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AutoCommitPolicy {
+    pub debounce_secs: u32,
+    pub poll_interval_secs: u32,
+}
+
+impl Default for AutoCommitPolicy {
+    fn default() -> Self {
+        Self {
+            debounce_secs: 30,
+            poll_interval_secs: 10,
+        }
+    }
+}
@@ -29,6 +29,22 @@ async runImportDry(source: ImportSourceKind) : Promise<Result<ImportStats, strin
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
+},
+async runImportFromPath(transform: TransformKind, path: string, userId: string) : Promise<Result<ImportDataResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plugin:importer|run_import_from_path", { transform, path, userId }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async runImportDryFromPath(transform: TransformKind, path: string) : Promise<Result<ImportStats, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plugin:importer|run_import_dry_from_path", { transform, path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
 }
 }
 
@@ -44,10 +60,10 @@ async runImportDry(source: ImportSourceKind) : Promise<Result<ImportStats, strin
 
 export type ImportDataResult = { stats: ImportStats; data: JsonValue }
 export type ImportSourceInfo = { kind: ImportSourceKind | null; transform: TransformKind; name: string; path: string; revealPath: string }
-export type ImportSourceKind = "granola" | "hyprnote_v0_stable" | "hyprnote_v0_nightly" | "as_is"
-export type ImportStats = { sessionsCount: number; transcriptsCount: number; humansCount: number; organizationsCount: number; participantsCount: number; templatesCount: number; enhancedNotesCount: number }
+export type ImportSourceKind = "granola" | "hyprnote_v0_stable" | "hyprnote_v0_nightly" | "meeting_transcripts" | "apple_notes" | "markdown_vault" | "as_is"
+export type ImportStats = { sessionsCount: number; transcriptsCount: number; humansCount: number; organizationsCount: number; participantsCount: number; templatesCount: number; enhancedNotesCount: number; attachmentsCount: number }
 export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
-export type TransformKind = "hyprnote_v0" | "granola" | "as_is"
+export type TransformKind = "hyprnote_v0" | "granola" | "meeting_transcript" | "apple_notes" | "markdown_vault" | "as_is"
 
 /** tauri-specta globals **/
 
@@ -1,5 +1,7 @@
 use crate::ext::ImporterPluginExt;
-use crate::types::{ImportDataResult, ImportSourceInfo, ImportSourceKind, ImportStats};
+use crate::types::{
+    ImportDataResult, ImportSourceInfo, ImportSourceKind, ImportStats, TransformKind,
+};
 
 #[tauri::command]
 #[specta::specta]
@@ -34,3 +36,30 @@ pub async fn run_import_dry<R: tauri::Runtime>(
         .await
         .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+#[specta::specta]
+pub async fn run_import_from_path<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    transform: TransformKind,
+    path: String,
+    user_id: String,
+) -> Result<ImportDataResult, String> {
+    app.importer()
+        .run_import_from_path(transform, path.into(), user_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn run_import_dry_from_path<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    transform: TransformKind,
+    path: String,
+) -> Result<ImportStats, String> {
+    app.importer()
+        .run_import_dry_from_path(transform, path.into())
+        .await
+        .map_err(|e| e.to_string())
+}
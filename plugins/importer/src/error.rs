@@ -12,6 +12,12 @@ pub enum Error {
     #[error("json error: {0}")]
     Json(#[from] serde_json::Error),
 
+    #[error("fs sync error: {0}")]
+    FsSync(#[from] hypr_fs_sync_core::Error),
+
+    #[error("sqlite error: {0}")]
+    Sqlite(#[from] hypr_db_core::libsql::Error),
+
     #[error("db parser error: {0}")]
     DbParser(#[from] hypr_db_parser::Error),
 
@@ -1,13 +1,17 @@
+use std::path::PathBuf;
+
 use crate::types::{
-    ImportDataResult, ImportSource, ImportSourceInfo, ImportSourceKind, ImportStats,
+    ImportDataResult, ImportSource, ImportSourceInfo, ImportSourceKind, ImportStats, TransformKind,
 };
 use hypr_importer_core::output::to_tinybase_json;
+use tauri_plugin_settings::SettingsPluginExt;
 
-pub struct Importer<R: tauri::Runtime> {
+pub struct Importer<'a, R: tauri::Runtime, M: tauri::Manager<R>> {
+    manager: &'a M,
     _runtime: std::marker::PhantomData<fn() -> R>,
 }
 
-impl<R: tauri::Runtime> Importer<R> {
+impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> Importer<'a, R, M> {
     pub fn list_available_sources(&self) -> Vec<ImportSourceInfo> {
         crate::sources::list_available_sources()
     }
@@ -30,7 +34,13 @@ impl<R: tauri::Runtime> Importer<R> {
             return Err(crate::Error::SourceNotAvailable(source.name.clone()));
         }
 
-        let data = crate::sources::import_all(source).await?;
+        let mut data = crate::sources::import_all(source).await?;
+        let has_folders = data.sessions.iter().any(|s| s.folder_id.is_some());
+        if !data.attachments.is_empty() || has_folders {
+            let vault_base = self.manager.settings().vault_base()?;
+            crate::vault::save_all(vault_base.into_std_path_buf(), &mut data).await?;
+        }
+
         let stats = ImportStats::from_data(&data);
         let tinybase_json = to_tinybase_json(&data, &user_id);
 
@@ -40,6 +50,16 @@ impl<R: tauri::Runtime> Importer<R> {
         })
     }
 
+    pub async fn run_import_from_path(
+        &self,
+        transform: TransformKind,
+        path: PathBuf,
+        user_id: String,
+    ) -> Result<ImportDataResult, crate::Error> {
+        let source = ImportSource::from_path(path, transform);
+        self.run_import_from_source(&source, user_id).await
+    }
+
     pub async fn run_import_dry(
         &self,
         source_kind: ImportSourceKind,
@@ -48,6 +68,15 @@ impl<R: tauri::Runtime> Importer<R> {
         self.run_import_dry_from_source(&source).await
     }
 
+    pub async fn run_import_dry_from_path(
+        &self,
+        transform: TransformKind,
+        path: PathBuf,
+    ) -> Result<ImportStats, crate::Error> {
+        let source = ImportSource::from_path(path, transform);
+        self.run_import_dry_from_source(&source).await
+    }
+
     pub async fn run_import_dry_from_source(
         &self,
         source: &ImportSource,
@@ -62,12 +91,18 @@ impl<R: tauri::Runtime> Importer<R> {
 }
 
 pub trait ImporterPluginExt<R: tauri::Runtime> {
-    fn importer(&self) -> Importer<R>;
+    fn importer(&self) -> Importer<'_, R, Self>
+    where
+        Self: tauri::Manager<R> + Sized;
 }
 
 impl<R: tauri::Runtime, T: tauri::Manager<R>> ImporterPluginExt<R> for T {
-    fn importer(&self) -> Importer<R> {
+    fn importer(&self) -> Importer<'_, R, Self>
+    where
+        Self: Sized,
+    {
         Importer {
+            manager: self,
             _runtime: std::marker::PhantomData,
         }
     }
@@ -5,6 +5,7 @@ mod error;
 mod ext;
 mod sources;
 mod types;
+mod vault;
 
 pub use error::*;
 pub use ext::*;
@@ -20,6 +21,8 @@ fn make_specta_builder<R: tauri::Runtime>() -> tauri_specta::Builder<R> {
             commands::list_available_sources::<Wry>,
             commands::run_import::<Wry>,
             commands::run_import_dry::<Wry>,
+            commands::run_import_from_path::<Wry>,
+            commands::run_import_dry_from_path::<Wry>,
         ])
         .error_handling(tauri_specta::ErrorHandlingMode::Result)
 }
@@ -0,0 +1,471 @@
+mod store;
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use hypr_apple_note::{EmbeddedObjectType, Table};
+
+use crate::types::{Attachment, AttachmentKind, Collection, Session};
+use store::{RawAttachment, RawFolder, RawNote, RawStore};
+
+pub const NOTE_STORE_FILENAME: &str = "NoteStore.sqlite";
+const OBJECT_REPLACEMENT_CHAR: char = '\u{fffc}';
+
+pub async fn import_all_from_path(path: &Path) -> Result<Collection, crate::Error> {
+    let db_path = if path.is_dir() {
+        path.join(NOTE_STORE_FILENAME)
+    } else {
+        path.to_path_buf()
+    };
+    let root = db_path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+    // Notes keeps the database open in WAL mode; read from a private copy so a running
+    // Notes.app is never locked or modified.
+    let snapshot_dir = std::env::temp_dir().join(format!(
+        "char-apple-notes-{}",
+        uuid::Uuid::new_v4().simple()
+    ));
+    std::fs::create_dir_all(&snapshot_dir)?;
+    let result = async {
+        let snapshot = snapshot_database(&db_path, &snapshot_dir)?;
+        store::read(&snapshot).await
+    }
+    .await;
+    let _ = std::fs::remove_dir_all(&snapshot_dir);
+
+    Ok(build_collection(&result?, &root))
+}
+
+fn snapshot_database(db_path: &Path, snapshot_dir: &Path) -> Result<PathBuf, crate::Error> {
+    let target = snapshot_dir.join(NOTE_STORE_FILENAME);
+    std::fs::copy(db_path, &target)?;
+
+    for suffix in ["-wal", "-shm"] {
+        let mut sidecar = db_path.as_os_str().to_owned();
+        sidecar.push(suffix);
+        let sidecar = PathBuf::from(sidecar);
+        if sidecar.exists() {
+            let mut target_sidecar = target.as_os_str().to_owned();
+            target_sidecar.push(suffix);
+            std::fs::copy(&sidecar, PathBuf::from(target_sidecar))?;
+        }
+    }
+
+    Ok(target)
+}
+
+fn build_collection(store: &RawStore, root: &Path) -> Collection {
+    let folder_paths = resolve_folder_paths(&store.folders);
+    let attachments_by_id: HashMap<&str, &RawAttachment> = store
+        .attachments
+        .iter()
+        .map(|a| (a.identifier.as_str(), a))
+        .collect();
+
+    let mut collection = Collection::default();
+
+    for raw in &store.notes {
+        let folder_id = match raw.folder_pk {
+            Some(pk) => match folder_paths.get(&pk) {
+                Some(FolderPath::Trash) => continue,
+                Some(FolderPath::Path(path)) => Some(path.clone()),
+                None => None,
+            },
+            None => None,
+        };
+
+        let proto = match hypr_apple_note::parse_note_store_proto(&raw.data) {
+            Ok(proto) => proto,
+            Err(e) => {
+                tracing::warn!(note = %raw.identifier, "apple_note_parse_failed: {}", e);
+                continue;
+            }
+        };
+        let note = &proto.document.note;
+
+        let session_id = uuid::Uuid::new_v5(
+            &uuid::Uuid::NAMESPACE_OID,
+            format!("apple-notes:{}", raw.identifier).as_bytes(),
+        )
+        .to_string();
+
+        let mut used_filenames: Vec<String> = Vec::new();
+        let mut rendered = Vec::new();
+        for object in hypr_apple_note::extract_embedded_objects(note) {
+            let attachment = attachments_by_id.get(object.uuid.as_str()).copied();
+            rendered.push(render_embedded(
+                &object.object_type,
+                attachment,
+                root,
+                &session_id,
+                &mut used_filenames,
+                &mut collection.attachments,
+            ));
+        }
+
+        let markdown = replace_object_markers(&hypr_apple_note::note_to_markdown(note), rendered);
+
+        collection.sessions.push(Session {
+            id: session_id,
+            user_id: String::new(),
+            created_at: core_time_to_rfc3339(raw.created_at.or(raw.modified_at)),
+            title: note_title(raw, note),
+            raw_md: Some(markdown.trim().to_string()),
+            enhanced_content: None,
+            folder_id,
+            event_id: None,
+        });
+    }
+
+    collection
+}
+
+enum FolderPath {
+    Trash,
+    Path(String),
+}
+
+fn resolve_folder_paths(folders: &[RawFolder]) -> HashMap<i64, FolderPath> {
+    let by_pk: HashMap<i64, &RawFolder> = folders.iter().map(|f| (f.pk, f)).collect();
+
+    folders
+        .iter()
+        .map(|folder| {
+            let mut segments = Vec::new();
+            let mut current = Some(folder);
+            let mut in_trash = false;
+
+            // Bounded walk: a corrupt parent cycle must not hang the import.
+            for _ in 0..=folders.len() {
+                let Some(f) = current else { break };
+                in_trash |= f.is_trash;
+                segments.push(sanitize_segment(&f.title));
+                current = f.parent_pk.and_then(|pk| by_pk.get(&pk).copied());
+            }
+
+            let path = if in_trash {
+                FolderPath::Trash
+            } else {
+                segments.reverse();
+                FolderPath::Path(segments.join("/"))
+            };
+            (folder.pk, path)
+        })
+        .collect()
+}
+
+fn sanitize_segment(title: &str) -> String {
+    let cleaned: String = title
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '\0' => '-',
+            c => c,
+        })
+        .collect();
+    let cleaned = cleaned.trim().trim_matches('.').to_string();
+    if cleaned.is_empty() {
+        "Untitled".to_string()
+    } else {
+        cleaned
+    }
+}
+
+fn note_title(raw: &RawNote, note: &hypr_apple_note::Note) -> String {
+    if let Some(title) = raw.title.as_ref().filter(|t| !t.trim().is_empty()) {
+        return title.trim().to_string();
+    }
+
+    hypr_apple_note::extract_plaintext(note)
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(|line| line.trim_matches(OBJECT_REPLACEMENT_CHAR).to_string())
+        .unwrap_or_default()
+}
+
+fn core_time_to_rfc3339(core_time: Option<f64>) -> String {
+    core_time
+        .and_then(|t| {
+            chrono::DateTime::from_timestamp(hypr_apple_note::core_time_to_unix(t as i64), 0)
+        })
+        .unwrap_or_else(chrono::Utc::now)
+        .to_rfc3339()
+}
+
+fn render_embedded(
+    object_type: &EmbeddedObjectType,
+    attachment: Option<&RawAttachment>,
+    root: &Path,
+    session_id: &str,
+    used_filenames: &mut Vec<String>,
+    out: &mut Vec<Attachment>,
+) -> String {
+    let Some(attachment) = attachment else {
+        return String::new();
+    };
+
+    match object_type {
+        EmbeddedObjectType::Table => attachment
+            .mergeable_data
+            .as_deref()
+            .and_then(|data| hypr_apple_note::parse_mergable_data_proto(data).ok())
+            .and_then(|proto| hypr_apple_note::parse_table(&proto))
+            .map(|table| table_to_markdown(&table))
+            .unwrap_or_default(),
+        EmbeddedObjectType::URL
+        | EmbeddedObjectType::Hashtag
+        | EmbeddedObjectType::Mention
+        | EmbeddedObjectType::Link
+        | EmbeddedObjectType::Gallery => String::new(),
+        _ => {
+            let Some(source) = find_media_file(root, attachment) else {
+                return String::new();
+            };
+
+            let filename = unique_filename(&source, used_filenames);
+            out.push(Attachment {
+                session_id: session_id.to_string(),
+                kind: AttachmentKind::File,
+                source_path: source.to_string_lossy().to_string(),
+                filename: filename.clone(),
+            });
+
+            let target = format!("attachments/{}", filename.replace(' ', "%20"));
+            match object_type {
+                EmbeddedObjectType::Image | EmbeddedObjectType::Drawing => {
+                    format!("\n![{}]({})\n", filename, target)
+                }
+                _ => format!("\n[{}]({})\n", filename, target),
+            }
+        }
+    }
+}
+
+fn replace_object_markers(markdown: &str, rendered: Vec<String>) -> String {
+    let mut rendered = rendered.into_iter();
+    let mut output = String::with_capacity(markdown.len());
+    for c in markdown.chars() {
+        if c == OBJECT_REPLACEMENT_CHAR {
+            output.push_str(&rendered.next().unwrap_or_default());
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
+
+fn table_to_markdown(table: &Table) -> String {
+    let columns = table.column_count();
+    if columns == 0 {
+        return String::new();
+    }
+
+    let escape = |cell: &str| cell.replace('|', "\\|").replace('\n', "<br>");
+    let mut lines = Vec::with_capacity(table.row_count() + 1);
+    for (idx, row) in table.rows.iter().enumerate() {
+        let cells: Vec<String> = (0..columns)
+            .map(|col| escape(row.get(col).map(String::as_str).unwrap_or("")))
+            .collect();
+        lines.push(format!("| {} |", cells.join(" | ")));
+        if idx == 0 {
+            lines.push(format!("|{}", " --- |".repeat(columns)));
+        }
+    }
+
+    format!("\n{}\n", lines.join("\n"))
+}
+
+// Media lives under `Accounts/<account>/Media/<media id>/[<generation>/]<filename>`; drawings
+// without media fall back to `Accounts/<account>/FallbackImages/<attachment id>.<ext>`.
+fn find_media_file(root: &Path, attachment: &RawAttachment) -> Option<PathBuf> {
+    let mut account_dirs: Vec<PathBuf> = std::fs::read_dir(root.join("Accounts"))
+        .map(|entries| entries.flatten().map(|e| e.path()).collect())
+        .unwrap_or_default();
+    account_dirs.push(root.to_path_buf());
+
+    for account in &account_dirs {
+        if let Some(media_id) = attachment.media_identifier.as_deref()
+            && let Some(found) = find_file_in(
+                &account.join("Media").join(media_id),
+                attachment.media_filename.as_deref(),
+            )
+        {
+            return Some(found);
+        }
+
+        for ext in ["jpeg", "jpg", "png"] {
+            let fallback = account
+                .join("FallbackImages")
+                .join(format!("{}.{}", attachment.identifier, ext));
+            if fallback.is_file() {
+                return Some(fallback);
+            }
+        }
+    }
+
+    None
+}
+
+fn find_file_in(dir: &Path, preferred_name: Option<&str>) -> Option<PathBuf> {
+    let mut stack = vec![dir.to_path_buf()];
+    let mut first_file = None;
+
+    while let Some(current) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                stack.push(path);
+            } else if path.is_file() {
+                let matches_name =
+                    preferred_name.is_some_and(|name| path.file_name().is_some_and(|n| n == name));
+                if matches_name {
+                    return Some(path);
+                }
+                first_file.get_or_insert(path);
+            }
+        }
+    }
+
+    first_file
+}
+
+fn unique_filename(source: &Path, used: &mut Vec<String>) -> String {
+    let name = source
+        .file_name()
+        .map(|n| sanitize_segment(&n.to_string_lossy()))
+        .unwrap_or_else(|| "attachment".to_string());
+    let stem = Path::new(&name)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| name.clone());
+    let extension = Path::new(&name)
+        .extension()
+        .map(|e| e.to_string_lossy().to_string());
+
+    let mut candidate = name.clone();
+    let mut counter = 1;
+    while used.contains(&candidate) {
+        candidate = match &extension {
+            Some(ext) => format!("{} {}.{}", stem, counter, ext),
+            None => format!("{} {}", stem, counter),
+        };
+        counter += 1;
+    }
+
+    used.push(candidate.clone());
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn folder(pk: i64, title: &str, parent_pk: Option<i64>, is_trash: bool) -> RawFolder {
+        RawFolder {
+            pk,
+            title: title.to_string(),
+            parent_pk,
+            is_trash,
+        }
+    }
+
+    #[test]
+    fn resolves_nested_folder_paths() {
+        let folders = vec![
+            folder(1, "Work", None, false),
+            folder(2, "Projects/2024", Some(1), false),
+            folder(3, "Recently Deleted", None, true),
+            folder(4, "Old", Some(3), false),
+        ];
+        let paths = resolve_folder_paths(&folders);
+
+        assert!(matches!(&paths[&2], FolderPath::Path(p) if p == "Work/Projects-2024"));
+        assert!(matches!(paths[&3], FolderPath::Trash));
+        assert!(matches!(paths[&4], FolderPath::Trash));
+    }
+
+    #[test]
+    fn renders_tables_as_markdown() {
+        let table = Table {
+            rows: vec![
+                vec!["Name".to_string(), "Role".to_string()],
+                vec!["Jane".to_string(), "PM | Eng".to_string()],
+            ],
+            direction: String::new(),
+        };
+
+        assert_eq!(
+            table_to_markdown(&table),
+            "\n| Name | Role |\n| --- | --- |\n| Jane | PM \\| Eng |\n"
+        );
+    }
+
+    #[test]
+    fn replaces_object_markers_in_order() {
+        let markdown = format!("a{}b{}c", OBJECT_REPLACEMENT_CHAR, OBJECT_REPLACEMENT_CHAR);
+        let output = replace_object_markers(&markdown, vec!["1".to_string()]);
+        assert_eq!(output, "a1bc");
+    }
+
+    #[test]
+    fn dedupes_attachment_filenames() {
+        let mut used = Vec::new();
+        assert_eq!(
+            unique_filename(Path::new("/x/image.png"), &mut used),
+            "image.png"
+        );
+        assert_eq!(
+            unique_filename(Path::new("/y/image.png"), &mut used),
+            "image 1.png"
+        );
+    }
+
+    #[tokio::test]
+    async fn imports_note_store_snapshot() {
+        let temp = tempfile::tempdir().unwrap();
+        let db_path = temp.path().join(NOTE_STORE_FILENAME);
+        let note_data = std::fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../../crates/apple-note/tests/data/simple_note_protobuf_gzipped.bin"
+        ))
+        .unwrap();
+
+        {
+            let db = hypr_db_core::libsql::Builder::new_local(&db_path)
+                .build()
+                .await
+                .unwrap();
+            let conn = db.connect().unwrap();
+            conn.execute_batch(
+                "CREATE TABLE ZICCLOUDSYNCINGOBJECT (Z_PK INTEGER PRIMARY KEY, ZIDENTIFIER TEXT, \
+                 ZTITLE1 TEXT, ZTITLE2 TEXT, ZCREATIONDATE3 REAL, ZFOLDER INTEGER, ZPARENT INTEGER, \
+                 ZFOLDERTYPE INTEGER, ZMARKEDFORDELETION INTEGER, ZTYPEUTI TEXT, ZNOTE INTEGER, \
+                 ZMEDIA INTEGER, ZFILENAME TEXT, ZMERGEABLEDATA1 BLOB);
+                 CREATE TABLE ZICNOTEDATA (Z_PK INTEGER PRIMARY KEY, ZNOTE INTEGER, ZDATA BLOB);
+                 INSERT INTO ZICCLOUDSYNCINGOBJECT (Z_PK, ZTITLE2) VALUES (1, 'Work');
+                 INSERT INTO ZICCLOUDSYNCINGOBJECT (Z_PK, ZIDENTIFIER, ZTITLE1, ZCREATIONDATE3, ZFOLDER)
+                   VALUES (2, 'NOTE-1', 'Standup', 700000000.0, 1);",
+            )
+            .await
+            .unwrap();
+            conn.execute(
+                "INSERT INTO ZICNOTEDATA (Z_PK, ZNOTE, ZDATA) VALUES (1, 2, ?1)",
+                [hypr_db_core::libsql::Value::Blob(note_data)],
+            )
+            .await
+            .unwrap();
+        }
+
+        let collection = import_all_from_path(temp.path()).await.unwrap();
+
+        assert_eq!(collection.sessions.len(), 1);
+        let session = &collection.sessions[0];
+        assert_eq!(session.title, "Standup");
+        assert_eq!(session.folder_id.as_deref(), Some("Work"));
+        assert!(session.created_at.starts_with("2023-03-08"));
+        assert!(!session.raw_md.as_deref().unwrap_or_default().is_empty());
+    }
+}
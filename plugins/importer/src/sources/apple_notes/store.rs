@@ -0,0 +1,241 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use hypr_db_core::libsql::{self, Connection, Value};
+
+const OBJECTS_TABLE: &str = "ZICCLOUDSYNCINGOBJECT";
+// Notes moves deleted folders here instead of dropping them.
+const FOLDER_TYPE_TRASH: i64 = 1;
+
+#[derive(Debug, Clone)]
+pub struct RawNote {
+    pub identifier: String,
+    pub title: Option<String>,
+    pub created_at: Option<f64>,
+    pub modified_at: Option<f64>,
+    pub folder_pk: Option<i64>,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RawFolder {
+    pub pk: i64,
+    pub title: String,
+    pub parent_pk: Option<i64>,
+    pub is_trash: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct RawAttachment {
+    pub identifier: String,
+    pub media_identifier: Option<String>,
+    pub media_filename: Option<String>,
+    pub mergeable_data: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Default)]
+pub struct RawStore {
+    pub notes: Vec<RawNote>,
+    pub folders: Vec<RawFolder>,
+    pub attachments: Vec<RawAttachment>,
+}
+
+pub async fn read(path: &Path) -> Result<RawStore, crate::Error> {
+    let db = libsql::Builder::new_local(path).build().await?;
+    let conn = db.connect()?;
+
+    let columns = table_columns(&conn, OBJECTS_TABLE).await?;
+    if columns.is_empty() {
+        return Err(crate::Error::InvalidData(format!(
+            "not an Apple Notes database: missing {}",
+            OBJECTS_TABLE
+        )));
+    }
+
+    Ok(RawStore {
+        notes: read_notes(&conn, &columns).await?,
+        folders: read_folders(&conn, &columns).await?,
+        attachments: read_attachments(&conn, &columns).await?,
+    })
+}
+
+async fn table_columns(conn: &Connection, table: &str) -> Result<HashSet<String>, crate::Error> {
+    let mut rows = conn
+        .query(&format!("PRAGMA table_info({})", table), ())
+        .await?;
+
+    let mut columns = HashSet::new();
+    while let Some(row) = rows.next().await? {
+        if let Value::Text(name) = row.get_value(1)? {
+            columns.insert(name);
+        }
+    }
+    Ok(columns)
+}
+
+// Column names drift between macOS releases (`ZTITLE1` vs `ZTITLE`, `ZCREATIONDATE3` vs
+// `ZCREATIONDATE1`), so every optional column is resolved against the live schema.
+fn pick(columns: &HashSet<String>, alias: &str, candidates: &[&str]) -> String {
+    candidates
+        .iter()
+        .find(|c| columns.contains(**c))
+        .map(|c| format!("{}.{}", alias, c))
+        .unwrap_or_else(|| "NULL".to_string())
+}
+
+async fn read_notes(
+    conn: &Connection,
+    columns: &HashSet<String>,
+) -> Result<Vec<RawNote>, crate::Error> {
+    let mut filters = vec!["d.ZDATA IS NOT NULL".to_string()];
+    if columns.contains("ZMARKEDFORDELETION") {
+        filters.push("COALESCE(n.ZMARKEDFORDELETION, 0) = 0".to_string());
+    }
+    if columns.contains("ZISPASSWORDPROTECTED") {
+        filters.push("COALESCE(n.ZISPASSWORDPROTECTED, 0) = 0".to_string());
+    }
+
+    let sql = format!(
+        "SELECT n.Z_PK, {identifier}, {title}, {created}, {modified}, {folder}, d.ZDATA \
+         FROM ZICNOTEDATA d JOIN {table} n ON n.Z_PK = d.ZNOTE \
+         WHERE {filters}",
+        identifier = pick(columns, "n", &["ZIDENTIFIER"]),
+        title = pick(columns, "n", &["ZTITLE1", "ZTITLE"]),
+        created = pick(
+            columns,
+            "n",
+            &["ZCREATIONDATE3", "ZCREATIONDATE1", "ZCREATIONDATE"]
+        ),
+        modified = pick(columns, "n", &["ZMODIFICATIONDATE1", "ZMODIFICATIONDATE"]),
+        folder = pick(columns, "n", &["ZFOLDER"]),
+        table = OBJECTS_TABLE,
+        filters = filters.join(" AND "),
+    );
+
+    let mut rows = conn.query(&sql, ()).await?;
+    let mut notes = Vec::new();
+    while let Some(row) = rows.next().await? {
+        let Some(data) = as_blob(row.get_value(6)?) else {
+            continue;
+        };
+        let pk = as_i64(row.get_value(0)?).unwrap_or_default();
+
+        notes.push(RawNote {
+            identifier: as_text(row.get_value(1)?).unwrap_or_else(|| pk.to_string()),
+            title: as_text(row.get_value(2)?),
+            created_at: as_f64(row.get_value(3)?),
+            modified_at: as_f64(row.get_value(4)?),
+            folder_pk: as_i64(row.get_value(5)?),
+            data,
+        });
+    }
+    Ok(notes)
+}
+
+async fn read_folders(
+    conn: &Connection,
+    columns: &HashSet<String>,
+) -> Result<Vec<RawFolder>, crate::Error> {
+    if !columns.contains("ZTITLE2") {
+        return Ok(Vec::new());
+    }
+
+    let sql = format!(
+        "SELECT f.Z_PK, f.ZTITLE2, {parent}, {folder_type} FROM {table} f \
+         WHERE f.ZTITLE2 IS NOT NULL",
+        parent = pick(columns, "f", &["ZPARENT"]),
+        folder_type = pick(columns, "f", &["ZFOLDERTYPE"]),
+        table = OBJECTS_TABLE,
+    );
+
+    let mut rows = conn.query(&sql, ()).await?;
+    let mut folders = Vec::new();
+    while let Some(row) = rows.next().await? {
+        let Some(title) = as_text(row.get_value(1)?) else {
+            continue;
+        };
+        folders.push(RawFolder {
+            pk: as_i64(row.get_value(0)?).unwrap_or_default(),
+            title,
+            parent_pk: as_i64(row.get_value(2)?),
+            is_trash: as_i64(row.get_value(3)?) == Some(FOLDER_TYPE_TRASH),
+        });
+    }
+    Ok(folders)
+}
+
+async fn read_attachments(
+    conn: &Connection,
+    columns: &HashSet<String>,
+) -> Result<Vec<RawAttachment>, crate::Error> {
+    if !columns.contains("ZTYPEUTI") {
+        return Ok(Vec::new());
+    }
+
+    let media_join = if columns.contains("ZMEDIA") {
+        format!("LEFT JOIN {} m ON m.Z_PK = a.ZMEDIA", OBJECTS_TABLE)
+    } else {
+        String::new()
+    };
+    let (media_identifier, media_filename) = if media_join.is_empty() {
+        ("NULL".to_string(), "NULL".to_string())
+    } else {
+        (
+            pick(columns, "m", &["ZIDENTIFIER"]),
+            pick(columns, "m", &["ZFILENAME"]),
+        )
+    };
+
+    let sql = format!(
+        "SELECT {identifier}, {media_identifier}, {media_filename}, {mergeable} \
+         FROM {table} a {media_join} WHERE a.ZTYPEUTI IS NOT NULL",
+        identifier = pick(columns, "a", &["ZIDENTIFIER"]),
+        mergeable = pick(columns, "a", &["ZMERGEABLEDATA1", "ZMERGEABLEDATA"]),
+        table = OBJECTS_TABLE,
+    );
+
+    let mut rows = conn.query(&sql, ()).await?;
+    let mut attachments = Vec::new();
+    while let Some(row) = rows.next().await? {
+        let Some(identifier) = as_text(row.get_value(0)?) else {
+            continue;
+        };
+        attachments.push(RawAttachment {
+            identifier,
+            media_identifier: as_text(row.get_value(1)?),
+            media_filename: as_text(row.get_value(2)?),
+            mergeable_data: as_blob(row.get_value(3)?),
+        });
+    }
+    Ok(attachments)
+}
+
+fn as_text(value: Value) -> Option<String> {
+    match value {
+        Value::Text(text) if !text.is_empty() => Some(text),
+        _ => None,
+    }
+}
+
+fn as_i64(value: Value) -> Option<i64> {
+    match value {
+        Value::Integer(n) => Some(n),
+        Value::Real(n) => Some(n as i64),
+        _ => None,
+    }
+}
+
+fn as_f64(value: Value) -> Option<f64> {
+    match value {
+        Value::Integer(n) => Some(n as f64),
+        Value::Real(n) => Some(n),
+        _ => None,
+    }
+}
+
+fn as_blob(value: Value) -> Option<Vec<u8>> {
+    match value {
+        Value::Blob(blob) if !blob.is_empty() => Some(blob),
+        _ => None,
+    }
+}
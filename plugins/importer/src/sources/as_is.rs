@@ -1,6 +1,6 @@
 use crate::types::{
-    Collection, EnhancedNote, Human, Organization, Session, SessionParticipant, Tag, TagMapping,
-    Template, Transcript,
+    Attachment, Collection, EnhancedNote, Human, Organization, Session, SessionParticipant, Tag,
+    TagMapping, Template, Transcript,
 };
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -25,6 +25,8 @@ pub struct AsIsData {
     pub tags: Vec<Tag>,
     #[serde(default)]
     pub tag_mappings: Vec<TagMapping>,
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
 }
 
 pub fn load_data(path: &Path) -> Result<Collection, crate::Error> {
@@ -43,5 +45,6 @@ pub fn load_data(path: &Path) -> Result<Collection, crate::Error> {
         enhanced_notes: data.enhanced_notes,
         tags: data.tags,
         tag_mappings: data.tag_mappings,
+        attachments: data.attachments,
     })
 }
@@ -0,0 +1,429 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::Deserialize;
+use serde_json::Value;
+
+// `[[Page]]`, `[[Page#Heading|Alias]]`, and embeds `![[image.png|300]]`.
+static WIKI_LINK_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(!?)\[\[([^\]|#]*)(#[^\]|]*)?(?:\|([^\]]*))?\]\]").unwrap());
+// `![alt](path "title")` and `![alt](<path with spaces>)`.
+static MARKDOWN_IMAGE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"!\[([^\]]*)\]\((?:<([^>]+)>|([^)\s]+))(?:\s+"[^"]*")?\)"#).unwrap()
+});
+// Logseq keeps page properties as leading `key:: value` lines instead of YAML.
+static LOGSEQ_PROPERTY_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*-?\s*([A-Za-z][\w-]*):: ?(.*)$").unwrap());
+
+// Vaults spell the same property differently, and Obsidian notes often carry several at once
+// (`date` and `created`), so the first non-null key of each list wins.
+const TITLE_KEYS: [&str; 1] = ["title"];
+const DATE_KEYS: [&str; 3] = ["date", "created", "created_at"];
+const TAG_KEYS: [&str; 2] = ["tags", "tag"];
+const ATTENDEE_KEYS: [&str; 3] = ["attendees", "participants", "people"];
+
+#[derive(Debug, Default)]
+pub struct Frontmatter {
+    pub title: Option<Value>,
+    pub date: Option<Value>,
+    pub tags: Option<Value>,
+    pub attendees: Option<Value>,
+}
+
+impl Frontmatter {
+    fn from_map(mut map: serde_json::Map<String, Value>) -> Self {
+        let mut take = |keys: &[&str]| {
+            keys.iter()
+                .filter_map(|key| map.remove(*key))
+                .find(|value| !value.is_null())
+        };
+
+        Self {
+            title: take(&TITLE_KEYS),
+            date: take(&DATE_KEYS),
+            tags: take(&TAG_KEYS),
+            attendees: take(&ATTENDEE_KEYS),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Frontmatter {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let map = Option::<serde_json::Map<String, Value>>::deserialize(deserializer)?;
+        Ok(Self::from_map(map.unwrap_or_default()))
+    }
+}
+
+pub struct ParsedNote {
+    pub frontmatter: Frontmatter,
+    pub body: String,
+}
+
+pub fn parse_note(text: &str) -> ParsedNote {
+    match hypr_frontmatter::Document::<Frontmatter>::from_str(text) {
+        Ok(doc) => ParsedNote {
+            frontmatter: doc.frontmatter,
+            body: doc.content,
+        },
+        Err(hypr_frontmatter::Error::YamlParse(e)) => {
+            tracing::warn!("markdown_frontmatter_parse_failed: {}", e);
+            ParsedNote {
+                frontmatter: Frontmatter::default(),
+                body: text.to_string(),
+            }
+        }
+        Err(_) => parse_logseq_properties(text),
+    }
+}
+
+fn parse_logseq_properties(text: &str) -> ParsedNote {
+    let mut properties = serde_json::Map::new();
+    let mut body_start = 0;
+
+    for line in text.split_inclusive('\n') {
+        let Some(caps) = LOGSEQ_PROPERTY_REGEX.captures(line.trim_end()) else {
+            break;
+        };
+        properties.insert(
+            caps[1].to_lowercase(),
+            Value::String(caps[2].trim().to_string()),
+        );
+        body_start += line.len();
+    }
+
+    ParsedNote {
+        frontmatter: Frontmatter::from_map(properties),
+        body: text[body_start..].to_string(),
+    }
+}
+
+// Accepts YAML lists as well as comma-separated strings; wiki-link brackets and `#` are dropped.
+pub fn value_to_list(value: Option<&Value>) -> Vec<String> {
+    let items: Vec<String> = match value {
+        Some(Value::Array(items)) => items.iter().filter_map(value_to_string).collect(),
+        Some(value) => value_to_string(value)
+            .map(|s| s.split(',').map(str::to_string).collect())
+            .unwrap_or_default(),
+        None => Vec::new(),
+    };
+
+    items
+        .into_iter()
+        .map(|item| {
+            item.trim()
+                .trim_start_matches("[[")
+                .trim_end_matches("]]")
+                .trim_start_matches('#')
+                .trim()
+                .to_string()
+        })
+        .filter(|item| !item.is_empty())
+        .collect()
+}
+
+pub fn value_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+pub fn parse_date(value: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    let value = value.trim().trim_start_matches("[[").trim_end_matches("]]");
+
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(value) {
+        return Some(dt.with_timezone(&chrono::Utc));
+    }
+
+    let local = |naive: chrono::NaiveDateTime| {
+        naive
+            .and_local_timezone(chrono::Local)
+            .earliest()
+            .map(|dt| dt.with_timezone(&chrono::Utc))
+    };
+
+    for format in ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M"] {
+        if let Ok(naive) = chrono::NaiveDateTime::parse_from_str(value, format) {
+            return local(naive);
+        }
+    }
+    for format in ["%Y-%m-%d", "%Y_%m_%d", "%Y.%m.%d"] {
+        if let Ok(date) = chrono::NaiveDate::parse_from_str(value, format) {
+            return local(date.and_hms_opt(0, 0, 0)?);
+        }
+    }
+
+    None
+}
+
+pub struct Embed {
+    pub source: PathBuf,
+    pub filename: String,
+}
+
+pub struct Resolver<'a> {
+    pub vault_root: &'a Path,
+    pub note_dir: &'a Path,
+    // Lowercased file name to path, for Obsidian's shortest-path embeds.
+    pub files_by_name: &'a HashMap<String, PathBuf>,
+    // Lowercased note name, and vault-relative path without extension, to its session id.
+    pub notes_by_name: &'a HashMap<String, String>,
+}
+
+impl Resolver<'_> {
+    fn resolve(&self, target: &str) -> Option<PathBuf> {
+        let target = target.trim().replace("%20", " ");
+        if target.is_empty() || target.contains("://") || target.starts_with("data:") {
+            return None;
+        }
+
+        let relative = Path::new(&target);
+        for base in [self.note_dir, self.vault_root] {
+            let candidate = base.join(relative);
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+        }
+
+        let name = relative.file_name()?.to_string_lossy().to_lowercase();
+        self.files_by_name.get(&name).cloned()
+    }
+
+    // `[[Note]]` and `[[folder/Note]]` resolve like Obsidian does: by path, then by name alone.
+    fn resolve_note(&self, target: &str) -> Option<&str> {
+        let target = target.trim().replace('\\', "/").to_lowercase();
+        let target = target.strip_suffix(".md").unwrap_or(&target);
+        let name = target.rsplit('/').next().unwrap_or(target);
+        self.notes_by_name
+            .get(target)
+            .or_else(|| self.notes_by_name.get(name))
+            .map(String::as_str)
+    }
+}
+
+// Rewrites wiki-links to imported notes as session mentions, other wiki-links to plain text and
+// local image embeds to `attachments/` links, collecting each embedded file once so it can be
+// copied next to the session.
+pub fn convert_body(body: &str, resolver: &Resolver<'_>, embeds: &mut Vec<Embed>) -> String {
+    let mut embed = |source: PathBuf| -> String {
+        if let Some(existing) = embeds.iter().find(|e| e.source == source) {
+            return existing.filename.clone();
+        }
+
+        let filename = unique_filename(&source, embeds);
+        embeds.push(Embed {
+            source,
+            filename: filename.clone(),
+        });
+        filename
+    };
+
+    let body = MARKDOWN_IMAGE_REGEX.replace_all(body, |caps: &regex::Captures<'_>| {
+        let target = caps
+            .get(2)
+            .or(caps.get(3))
+            .map(|m| m.as_str())
+            .unwrap_or("");
+        match resolver.resolve(target) {
+            Some(source) => attachment_markdown(&embed(source)),
+            None => caps[0].to_string(),
+        }
+    });
+
+    WIKI_LINK_REGEX
+        .replace_all(&body, |caps: &regex::Captures<'_>| {
+            let is_embed = !caps[1].is_empty();
+            let target = caps[2].trim();
+            let alias = caps.get(4).map(|m| m.as_str().trim());
+
+            if is_embed
+                && has_file_extension(target)
+                && let Some(source) = resolver.resolve(target)
+            {
+                return attachment_markdown(&embed(source));
+            }
+
+            if !target.is_empty()
+                && !has_file_extension(target)
+                && let Some(session_id) = resolver.resolve_note(target)
+            {
+                let label = alias.filter(|alias| !alias.is_empty()).unwrap_or(target);
+                return session_mention(session_id, label);
+            }
+
+            match alias {
+                Some(alias) if !alias.is_empty() && !is_embed => alias.to_string(),
+                _ if target.is_empty() => caps
+                    .get(3)
+                    .map(|m| m.as_str().trim_start_matches('#').to_string())
+                    .unwrap_or_default(),
+                _ => target.to_string(),
+            }
+        })
+        .into_owned()
+}
+
+fn has_file_extension(target: &str) -> bool {
+    Path::new(target)
+        .extension()
+        .is_some_and(|ext| !ext.eq_ignore_ascii_case("md"))
+}
+
+// The markdown form of the editor's session mention node.
+fn session_mention(session_id: &str, label: &str) -> String {
+    format!(
+        r#"<mention data-id="{}" data-type="session" data-label="{}"></mention>"#,
+        session_id,
+        label.replace('"', "'")
+    )
+}
+
+fn attachment_markdown(filename: &str) -> String {
+    let target = format!("attachments/{}", filename.replace(' ', "%20"));
+    if is_image(filename) {
+        format!("![{}]({})", filename, target)
+    } else {
+        format!("[{}]({})", filename, target)
+    }
+}
+
+fn is_image(filename: &str) -> bool {
+    const IMAGE_EXTENSIONS: [&str; 8] = ["png", "jpg", "jpeg", "gif", "webp", "svg", "bmp", "heic"];
+    Path::new(filename)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            IMAGE_EXTENSIONS
+                .iter()
+                .any(|candidate| ext.eq_ignore_ascii_case(candidate))
+        })
+}
+
+fn unique_filename(source: &Path, embeds: &[Embed]) -> String {
+    let name = source
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "attachment".to_string());
+    let stem = Path::new(&name)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| name.clone());
+    let extension = Path::new(&name)
+        .extension()
+        .map(|e| e.to_string_lossy().to_string());
+
+    let mut candidate = name.clone();
+    let mut counter = 1;
+    while embeds.iter().any(|e| e.filename == candidate) {
+        candidate = match &extension {
+            Some(ext) => format!("{} {}.{}", stem, counter, ext),
+            None => format!("{} {}", stem, counter),
+        };
+        counter += 1;
+    }
+    candidate
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_yaml_frontmatter() {
+        let note = parse_note(
+            "---\ntitle: Weekly Sync\ndate: 2024-03-05\ntags: [meeting, \"#team\"]\nattendees:\n  - \"[[Jane Doe]]\"\n  - John Smith\n---\n\nBody",
+        );
+
+        assert_eq!(
+            note.frontmatter.title.as_ref().and_then(value_to_string),
+            Some("Weekly Sync".to_string())
+        );
+        assert_eq!(
+            value_to_list(note.frontmatter.tags.as_ref()),
+            vec!["meeting", "team"]
+        );
+        assert_eq!(
+            value_to_list(note.frontmatter.attendees.as_ref()),
+            vec!["Jane Doe", "John Smith"]
+        );
+        assert_eq!(note.body, "Body");
+    }
+
+    #[test]
+    fn prefers_date_over_created_when_both_are_set() {
+        let note = parse_note(
+            "---\ncreated: 2024-01-01\ndate: 2024-03-05\ntag: standup\ntags:\n---\nBody",
+        );
+
+        assert_eq!(
+            note.frontmatter.date.as_ref().and_then(value_to_string),
+            Some("2024-03-05".to_string())
+        );
+        assert_eq!(
+            value_to_list(note.frontmatter.tags.as_ref()),
+            vec!["standup"]
+        );
+        assert_eq!(note.body, "Body");
+    }
+
+    #[test]
+    fn parses_logseq_properties() {
+        let note = parse_note("tags:: meeting, planning\ndate:: [[2024_03_05]]\n\n- First block");
+
+        assert_eq!(
+            value_to_list(note.frontmatter.tags.as_ref()),
+            vec!["meeting", "planning"]
+        );
+        let date = note.frontmatter.date.as_ref().and_then(value_to_string);
+        assert!(date.as_deref().and_then(parse_date).is_some());
+        assert_eq!(note.body, "\n- First block");
+    }
+
+    #[test]
+    fn keeps_body_without_frontmatter() {
+        let note = parse_note("# Heading\n\nText");
+        assert!(note.frontmatter.title.is_none());
+        assert_eq!(note.body, "# Heading\n\nText");
+    }
+
+    #[test]
+    fn converts_wiki_links_and_embeds() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(temp.path().join("assets")).unwrap();
+        std::fs::write(temp.path().join("assets/diagram one.png"), b"png").unwrap();
+
+        let mut files_by_name = HashMap::new();
+        files_by_name.insert(
+            "diagram one.png".to_string(),
+            temp.path().join("assets/diagram one.png"),
+        );
+        let notes_by_name = HashMap::from([
+            ("project plan".to_string(), "plan-id".to_string()),
+            ("projects/project plan".to_string(), "plan-id".to_string()),
+        ]);
+        let resolver = Resolver {
+            vault_root: temp.path(),
+            note_dir: temp.path(),
+            files_by_name: &files_by_name,
+            notes_by_name: &notes_by_name,
+        };
+
+        let mut embeds = Vec::new();
+        let output = convert_body(
+            "See [[Project Plan]], [[Projects/Project Plan#Goals|the goals]] and [[People/Jane|Jane]].\n![[diagram one.png|300]]\n![again](assets/diagram%20one.png)\n![remote](https://example.com/a.png)",
+            &resolver,
+            &mut embeds,
+        );
+
+        assert_eq!(
+            output,
+            "See <mention data-id=\"plan-id\" data-type=\"session\" data-label=\"Project Plan\"></mention>, <mention data-id=\"plan-id\" data-type=\"session\" data-label=\"the goals\"></mention> and Jane.\n![diagram one.png](attachments/diagram%20one.png)\n![diagram one.png](attachments/diagram%20one.png)\n![remote](https://example.com/a.png)"
+        );
+        assert_eq!(embeds.len(), 1);
+    }
+}
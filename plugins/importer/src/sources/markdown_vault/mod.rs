@@ -0,0 +1,335 @@
+mod markdown;
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::types::{
+    Attachment, AttachmentKind, Collection, Human, Session, SessionParticipant, Tag, TagMapping,
+};
+use markdown::{Embed, Resolver};
+
+const MARKDOWN_EXTENSIONS: [&str; 2] = ["md", "markdown"];
+// Folders that hold app config, backups or templates rather than notes; dot-folders are skipped too.
+const SKIPPED_DIRS: [&str; 3] = ["logseq", "node_modules", "_templates"];
+
+pub async fn import_all_from_path(path: &Path) -> Result<Collection, crate::Error> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || import_all_from_path_blocking(&path))
+        .await
+        .map_err(|e| crate::Error::InvalidData(e.to_string()))?
+}
+
+fn import_all_from_path_blocking(root: &Path) -> Result<Collection, crate::Error> {
+    let mut notes = Vec::new();
+    let mut files_by_name = HashMap::new();
+    collect_files(root, &mut notes, &mut files_by_name)?;
+    notes.sort();
+
+    let mut notes_by_name = HashMap::new();
+    for file in &notes {
+        let session_id = session_id_for(file);
+        if let Ok(relative) = file.strip_prefix(root) {
+            let relative = relative
+                .with_extension("")
+                .to_string_lossy()
+                .replace('\\', "/");
+            notes_by_name
+                .entry(relative.to_lowercase())
+                .or_insert_with(|| session_id.clone());
+        }
+        if let Some(stem) = file.file_stem() {
+            notes_by_name
+                .entry(stem.to_string_lossy().to_lowercase())
+                .or_insert(session_id);
+        }
+    }
+
+    let mut collection = Collection::default();
+    let mut tag_ids: HashMap<String, String> = HashMap::new();
+    let mut humans_by_key: HashMap<String, Human> = HashMap::new();
+
+    for file in notes {
+        let text = std::fs::read_to_string(&file)?;
+        let parsed = markdown::parse_note(&text);
+        let frontmatter = &parsed.frontmatter;
+
+        let session_id = session_id_for(&file);
+
+        let stem = file
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let title = frontmatter
+            .title
+            .as_ref()
+            .and_then(markdown::value_to_string)
+            .filter(|t| !t.trim().is_empty())
+            .unwrap_or_else(|| stem.clone());
+
+        let created_at = frontmatter
+            .date
+            .as_ref()
+            .and_then(markdown::value_to_string)
+            .and_then(|date| markdown::parse_date(&date))
+            // Daily notes (Obsidian `2024-03-05.md`, Logseq `journals/2024_03_05.md`).
+            .or_else(|| markdown::parse_date(&stem))
+            .or_else(|| {
+                std::fs::metadata(&file)
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .map(chrono::DateTime::<chrono::Utc>::from)
+            })
+            .unwrap_or_else(chrono::Utc::now)
+            .to_rfc3339();
+
+        let note_dir = file.parent().unwrap_or(root);
+        let resolver = Resolver {
+            vault_root: root,
+            note_dir,
+            files_by_name: &files_by_name,
+            notes_by_name: &notes_by_name,
+        };
+        let mut embeds: Vec<Embed> = Vec::new();
+        let body = markdown::convert_body(&parsed.body, &resolver, &mut embeds);
+
+        for embed in embeds {
+            collection.attachments.push(Attachment {
+                session_id: session_id.clone(),
+                kind: AttachmentKind::File,
+                source_path: embed.source.to_string_lossy().to_string(),
+                filename: embed.filename,
+            });
+        }
+
+        for tag_name in markdown::value_to_list(frontmatter.tags.as_ref()) {
+            let tag_id = tag_ids
+                .entry(tag_name.to_lowercase())
+                .or_insert_with(|| {
+                    let id = uuid::Uuid::new_v5(
+                        &uuid::Uuid::NAMESPACE_OID,
+                        tag_name.to_lowercase().as_bytes(),
+                    )
+                    .to_string();
+                    collection.tags.push(Tag {
+                        id: id.clone(),
+                        user_id: String::new(),
+                        name: tag_name.clone(),
+                    });
+                    id
+                })
+                .clone();
+
+            collection.tag_mappings.push(TagMapping {
+                id: format!("{}_{}", tag_id, session_id),
+                user_id: String::new(),
+                tag_id,
+                session_id: session_id.clone(),
+            });
+        }
+
+        let mut participant_ids = Vec::new();
+        for attendee in markdown::value_to_list(frontmatter.attendees.as_ref()) {
+            let (name, email) = split_attendee(&attendee);
+            let key = email.clone().unwrap_or_else(|| name.to_lowercase());
+            let human = humans_by_key.entry(key.clone()).or_insert_with(|| Human {
+                id: uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_OID, key.as_bytes()).to_string(),
+                user_id: String::new(),
+                created_at: created_at.clone(),
+                name: name.clone(),
+                email: email.clone(),
+                org_id: None,
+                job_title: None,
+                linkedin_username: None,
+            });
+
+            if !participant_ids.contains(&human.id) {
+                participant_ids.push(human.id.clone());
+                collection.participants.push(SessionParticipant {
+                    id: format!("{}_{}", session_id, human.id),
+                    user_id: String::new(),
+                    session_id: session_id.clone(),
+                    human_id: human.id.clone(),
+                    source: "imported".to_string(),
+                });
+            }
+        }
+
+        collection.sessions.push(Session {
+            id: session_id,
+            user_id: String::new(),
+            created_at,
+            title,
+            raw_md: Some(body.trim().to_string()),
+            enhanced_content: None,
+            folder_id: folder_id(root, note_dir),
+            event_id: None,
+        });
+    }
+
+    let mut humans: Vec<Human> = humans_by_key.into_values().collect();
+    humans.sort_by(|a, b| a.name.cmp(&b.name));
+    collection.humans = humans;
+
+    Ok(collection)
+}
+
+fn session_id_for(file: &Path) -> String {
+    uuid::Uuid::new_v5(
+        &uuid::Uuid::NAMESPACE_URL,
+        file.canonicalize()
+            .unwrap_or_else(|_| file.to_path_buf())
+            .to_string_lossy()
+            .as_bytes(),
+    )
+    .to_string()
+}
+
+fn collect_files(
+    dir: &Path,
+    notes: &mut Vec<PathBuf>,
+    files_by_name: &mut HashMap<String, PathBuf>,
+) -> Result<(), crate::Error> {
+    for entry in std::fs::read_dir(dir)?.flatten() {
+        let path = entry.path();
+        let Some(name) = path.file_name().map(|n| n.to_string_lossy().to_string()) else {
+            continue;
+        };
+        if name.starts_with('.') {
+            continue;
+        }
+
+        if path.is_dir() {
+            if !SKIPPED_DIRS.contains(&name.as_str()) {
+                collect_files(&path, notes, files_by_name)?;
+            }
+        } else if is_markdown(&path) {
+            notes.push(path);
+        } else {
+            files_by_name.entry(name.to_lowercase()).or_insert(path);
+        }
+    }
+    Ok(())
+}
+
+fn is_markdown(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            MARKDOWN_EXTENSIONS
+                .iter()
+                .any(|candidate| ext.eq_ignore_ascii_case(candidate))
+        })
+}
+
+fn folder_id(root: &Path, note_dir: &Path) -> Option<String> {
+    let relative = note_dir.strip_prefix(root).ok()?;
+    let segments: Vec<String> = relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+
+    if segments.is_empty() {
+        None
+    } else {
+        Some(segments.join("/"))
+    }
+}
+
+// `Jane Doe <jane@example.com>`, `jane@example.com` or a bare name.
+fn split_attendee(attendee: &str) -> (String, Option<String>) {
+    if let Some((name, rest)) = attendee.split_once('<')
+        && let Some(email) = rest.strip_suffix('>')
+        && email.contains('@')
+    {
+        let name = name.trim();
+        let name = if name.is_empty() { email } else { name };
+        return (name.to_string(), Some(email.trim().to_lowercase()));
+    }
+
+    if attendee.contains('@') && !attendee.contains(' ') {
+        return (attendee.to_string(), Some(attendee.to_lowercase()));
+    }
+
+    (attendee.to_string(), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn imports_obsidian_vault() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        std::fs::create_dir_all(root.join(".obsidian")).unwrap();
+        std::fs::write(root.join(".obsidian/app.json"), "{}").unwrap();
+        std::fs::create_dir_all(root.join("Meetings/2024")).unwrap();
+        std::fs::create_dir_all(root.join("Attachments")).unwrap();
+        std::fs::write(root.join("Attachments/whiteboard.png"), b"png").unwrap();
+        std::fs::write(
+            root.join("Meetings/2024/Weekly Sync.md"),
+            "---\ndate: 2024-03-05T10:00:00Z\ntags:\n  - meeting\nattendees:\n  - \"[[Jane Doe]]\"\n  - John Smith <john@example.com>\n---\n\nDiscussed [[Roadmap|the roadmap]].\n\n![[whiteboard.png]]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("Meetings/2024/1-1.md"),
+            "---\ntags: meeting, one-on-one\nattendees: [Jane Doe]\n---\nNotes, see [[Inbox]]",
+        )
+        .unwrap();
+        std::fs::write(root.join("Inbox.md"), "Loose note").unwrap();
+
+        let collection = import_all_from_path_blocking(root).unwrap();
+
+        assert_eq!(collection.sessions.len(), 3);
+        let sync = collection
+            .sessions
+            .iter()
+            .find(|s| s.title == "Weekly Sync")
+            .unwrap();
+        assert_eq!(sync.created_at, "2024-03-05T10:00:00+00:00");
+        assert_eq!(sync.folder_id.as_deref(), Some("Meetings/2024"));
+        assert_eq!(
+            sync.raw_md.as_deref(),
+            Some("Discussed the roadmap.\n\n![whiteboard.png](attachments/whiteboard.png)")
+        );
+
+        let inbox = collection
+            .sessions
+            .iter()
+            .find(|s| s.title == "Inbox")
+            .unwrap();
+        assert_eq!(inbox.folder_id, None);
+
+        let one_on_one = collection
+            .sessions
+            .iter()
+            .find(|s| s.title == "1-1")
+            .unwrap();
+        assert_eq!(
+            one_on_one.raw_md.clone().unwrap(),
+            format!(
+                r#"Notes, see <mention data-id="{}" data-type="session" data-label="Inbox"></mention>"#,
+                inbox.id
+            )
+        );
+
+        assert_eq!(collection.tags.len(), 2);
+        assert_eq!(collection.tag_mappings.len(), 3);
+        assert_eq!(collection.humans.len(), 2);
+        assert_eq!(collection.participants.len(), 3);
+        assert_eq!(collection.attachments.len(), 1);
+        assert_eq!(collection.attachments[0].session_id, sync.id);
+    }
+
+    #[test]
+    fn splits_attendee_email() {
+        assert_eq!(
+            split_attendee("John Smith <John@Example.com>"),
+            (
+                "John Smith".to_string(),
+                Some("john@example.com".to_string())
+            )
+        );
+        assert_eq!(split_attendee("Jane"), ("Jane".to_string(), None));
+    }
+}
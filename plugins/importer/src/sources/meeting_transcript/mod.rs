@@ -0,0 +1,424 @@
+mod parse;
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::types::{
+    Attachment, AttachmentKind, Collection, Human, Session, SessionParticipant, SpeakerHint,
+    Transcript, Word,
+};
+
+// Zoom names local recording folders `2024-03-05 10.00.00 Weekly Sync 81234567890`.
+static ZOOM_FOLDER_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(\d{4}-\d{2}-\d{2}) (\d{2})\.(\d{2})\.(\d{2}) (.+?)(?: \d{9,11})?$").unwrap()
+});
+
+const GENERIC_TRANSCRIPT_STEMS: [&str; 5] = [
+    "meeting_saved_closed_caption",
+    "closed_caption",
+    "transcript",
+    "captions",
+    "subtitles",
+];
+// Subfolders below the picked folder that are searched; Zoom keeps one folder per meeting.
+const MAX_SCAN_DEPTH: usize = 2;
+const AUDIO_EXTENSIONS: [&str; 7] = ["m4a", "mp3", "wav", "ogg", "aac", "flac", "opus"];
+const VIDEO_EXTENSIONS: [&str; 5] = ["mp4", "mov", "mkv", "webm", "m4v"];
+
+pub async fn import_all_from_path(path: &Path) -> Result<Collection, crate::Error> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || import_all_from_path_blocking(&path))
+        .await
+        .map_err(|e| crate::Error::InvalidData(e.to_string()))?
+}
+
+fn import_all_from_path_blocking(path: &Path) -> Result<Collection, crate::Error> {
+    let mut files = Vec::new();
+    if path.is_file() {
+        files.push(path.to_path_buf());
+    } else {
+        collect_transcript_files(path, 0, &mut files)?;
+    }
+    files.sort();
+
+    let mut collection = Collection::default();
+    let mut humans_by_name: HashMap<String, Human> = HashMap::new();
+
+    for file in files {
+        let cues = parse::parse_file(&file)?;
+        if cues.is_empty() {
+            continue;
+        }
+
+        let session = file_to_session(&file);
+        let transcript = cues_to_transcript(&session, &cues, &mut humans_by_name);
+
+        let mut speakers: Vec<String> = cues
+            .iter()
+            .filter_map(|c| c.speaker.as_ref())
+            .map(|s| s.to_lowercase())
+            .collect();
+        speakers.sort();
+        speakers.dedup();
+        for speaker in speakers {
+            let human_id = &humans_by_name[&speaker].id;
+            collection.participants.push(SessionParticipant {
+                id: format!("{}_{}", session.id, human_id),
+                user_id: String::new(),
+                session_id: session.id.clone(),
+                human_id: human_id.clone(),
+                source: "imported".to_string(),
+            });
+        }
+
+        if let Some(audio) = find_matching_media(&file) {
+            collection.attachments.push(Attachment {
+                session_id: session.id.clone(),
+                kind: AttachmentKind::Audio,
+                filename: audio
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_default(),
+                source_path: audio.to_string_lossy().to_string(),
+            });
+        }
+
+        collection.transcripts.push(transcript);
+        collection.sessions.push(session);
+    }
+
+    let mut humans: Vec<Human> = humans_by_name.into_values().collect();
+    humans.sort_by(|a, b| a.name.cmp(&b.name));
+    collection.humans = humans;
+
+    Ok(collection)
+}
+
+fn collect_transcript_files(
+    dir: &Path,
+    depth: usize,
+    out: &mut Vec<PathBuf>,
+) -> Result<(), crate::Error> {
+    for entry in std::fs::read_dir(dir)?.flatten() {
+        let path = entry.path();
+        let is_hidden = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| n.starts_with('.'));
+        if is_hidden {
+            continue;
+        }
+
+        if path.is_dir() {
+            if depth < MAX_SCAN_DEPTH {
+                collect_transcript_files(&path, depth + 1, out)?;
+            }
+        } else if is_scanned_transcript(&path) {
+            out.push(path);
+        }
+    }
+    Ok(())
+}
+
+// Subtitle files are always transcripts, but a folder full of `.txt` notes is not: plain text
+// is only picked up under the names meeting apps give their caption exports.
+fn is_scanned_transcript(path: &Path) -> bool {
+    if !parse::is_transcript_file(path) {
+        return false;
+    }
+    let is_text = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("txt"));
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    !is_text
+        || GENERIC_TRANSCRIPT_STEMS
+            .iter()
+            .any(|generic| stem.eq_ignore_ascii_case(generic))
+}
+
+fn file_to_session(file: &Path) -> Session {
+    let canonical = file.canonicalize().unwrap_or_else(|_| file.to_path_buf());
+    let id = uuid::Uuid::new_v5(
+        &uuid::Uuid::NAMESPACE_URL,
+        canonical.to_string_lossy().as_bytes(),
+    )
+    .to_string();
+
+    let stem = file
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let folder_name = file
+        .parent()
+        .and_then(|p| p.file_name())
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let zoom_folder = ZOOM_FOLDER_REGEX.captures(&folder_name);
+
+    let title = if GENERIC_TRANSCRIPT_STEMS
+        .iter()
+        .any(|generic| stem.eq_ignore_ascii_case(generic))
+    {
+        zoom_folder
+            .as_ref()
+            .map(|caps| caps[5].to_string())
+            .unwrap_or_else(|| folder_name.clone())
+    } else {
+        stem
+    };
+
+    let created_at = zoom_folder
+        .as_ref()
+        .and_then(|caps| {
+            chrono::NaiveDateTime::parse_from_str(
+                &format!("{} {}:{}:{}", &caps[1], &caps[2], &caps[3], &caps[4]),
+                "%Y-%m-%d %H:%M:%S",
+            )
+            .ok()
+        })
+        .and_then(|naive| naive.and_local_timezone(chrono::Local).single())
+        .map(|local| local.with_timezone(&chrono::Utc))
+        .or_else(|| {
+            std::fs::metadata(file)
+                .and_then(|m| m.modified())
+                .ok()
+                .map(chrono::DateTime::<chrono::Utc>::from)
+        })
+        .unwrap_or_else(chrono::Utc::now)
+        .to_rfc3339();
+
+    Session {
+        id,
+        user_id: String::new(),
+        created_at,
+        title,
+        raw_md: None,
+        enhanced_content: None,
+        folder_id: None,
+        event_id: None,
+    }
+}
+
+fn cues_to_transcript(
+    session: &Session,
+    cues: &[parse::Cue],
+    humans_by_name: &mut HashMap<String, Human>,
+) -> Transcript {
+    let mut words = Vec::new();
+    let mut speaker_hints = Vec::new();
+    let mut previous_speaker: Option<String> = None;
+
+    for cue in cues {
+        let timed_words = parse::split_words(cue);
+        let first_word_idx = words.len();
+
+        for timed in timed_words {
+            words.push(Word {
+                id: format!("{}-{}", session.id, words.len()),
+                text: timed.text,
+                start_ms: Some(timed.start_ms as f64),
+                end_ms: Some(timed.end_ms as f64),
+                channel: 0,
+                speaker: cue.speaker.clone(),
+            });
+        }
+
+        let Some(speaker) = cue.speaker.as_deref() else {
+            previous_speaker = None;
+            continue;
+        };
+        // Captions spell the same person differently ("Alice" vs "alice"), so speakers are
+        // keyed by their lowercase name everywhere.
+        let key = speaker.to_lowercase();
+        if words.len() == first_word_idx || previous_speaker.as_ref() == Some(&key) {
+            continue;
+        }
+        previous_speaker = Some(key.clone());
+
+        let human = humans_by_name.entry(key).or_insert_with_key(|key| Human {
+            id: uuid::Uuid::new_v5(&uuid::Uuid::NAMESPACE_OID, key.as_bytes()).to_string(),
+            user_id: String::new(),
+            created_at: session.created_at.clone(),
+            name: speaker.to_string(),
+            email: None,
+            org_id: None,
+            job_title: None,
+            linkedin_username: None,
+        });
+
+        speaker_hints.push(SpeakerHint {
+            word_id: words[first_word_idx].id.clone(),
+            hint_type: "user_speaker_assignment".to_string(),
+            value: serde_json::json!({ "human_id": human.id }).to_string(),
+        });
+    }
+
+    let start_ms = words.first().and_then(|w| w.start_ms);
+    let end_ms = words.last().and_then(|w| w.end_ms);
+
+    Transcript {
+        id: session.id.clone(),
+        user_id: String::new(),
+        created_at: session.created_at.clone(),
+        session_id: session.id.clone(),
+        title: session.title.clone(),
+        started_at: start_ms.unwrap_or(0.0),
+        ended_at: end_ms,
+        start_ms,
+        end_ms,
+        words,
+        speaker_hints,
+    }
+}
+
+// Prefers media sharing the transcript's file stem, then the only audio file in the folder,
+// then the only video file (Zoom keeps `audio*.m4a` next to the `.mp4`).
+fn find_matching_media(transcript: &Path) -> Option<PathBuf> {
+    let dir = transcript.parent()?;
+    let stem = transcript.file_stem()?.to_string_lossy().to_lowercase();
+
+    let mut audio = Vec::new();
+    let mut video = Vec::new();
+    for entry in std::fs::read_dir(dir).ok()?.flatten() {
+        let path = entry.path();
+        let Some(ext) = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase())
+        else {
+            continue;
+        };
+
+        let is_audio = AUDIO_EXTENSIONS.contains(&ext.as_str());
+        let is_video = VIDEO_EXTENSIONS.contains(&ext.as_str());
+        if !is_audio && !is_video {
+            continue;
+        }
+
+        let same_stem = path
+            .file_stem()
+            .is_some_and(|s| s.to_string_lossy().to_lowercase() == stem);
+        if same_stem {
+            return Some(path);
+        }
+
+        if is_audio {
+            audio.push(path);
+        } else {
+            video.push(path);
+        }
+    }
+
+    let transcripts_in_dir = std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .filter(|e| is_scanned_transcript(&e.path()))
+        .count();
+    if transcripts_in_dir > 1 {
+        return None;
+    }
+
+    audio.sort();
+    video.sort();
+    match (audio.len(), video.len()) {
+        (1, _) => audio.pop(),
+        (0, 1) => video.pop(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn imports_zoom_recording_folder() {
+        let temp = tempfile::tempdir().unwrap();
+        let meeting = temp
+            .path()
+            .join("2024-03-05 10.00.00 Weekly Sync 81234567890");
+        std::fs::create_dir_all(&meeting).unwrap();
+        std::fs::write(
+            meeting.join("meeting_saved_closed_caption.txt"),
+            "[Jane Doe] 10:02:15\nHello everyone\n[John Smith] 10:02:18\nHi Jane\n",
+        )
+        .unwrap();
+        std::fs::write(meeting.join("audio1234.m4a"), b"").unwrap();
+        std::fs::write(meeting.join("video1234.mp4"), b"").unwrap();
+
+        let collection = import_all_from_path_blocking(temp.path()).unwrap();
+
+        assert_eq!(collection.sessions.len(), 1);
+        assert_eq!(collection.sessions[0].title, "Weekly Sync");
+        assert_eq!(collection.humans.len(), 2);
+        assert_eq!(collection.participants.len(), 2);
+        assert_eq!(collection.transcripts[0].words.len(), 4);
+        assert_eq!(collection.transcripts[0].speaker_hints.len(), 2);
+        let exported = hypr_importer_core::output::to_tinybase_json(&collection, "user");
+        let hints = exported[0]["transcripts"][&collection.transcripts[0].id]["speaker_hints"]
+            .as_str()
+            .unwrap();
+        assert_eq!(
+            serde_json::from_str::<Vec<serde_json::Value>>(hints)
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(collection.attachments.len(), 1);
+        assert!(
+            collection.attachments[0]
+                .source_path
+                .ends_with("audio1234.m4a")
+        );
+    }
+
+    #[test]
+    fn merges_speakers_that_differ_only_in_case() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            temp.path().join("meeting_saved_closed_caption.txt"),
+            "[Alice] 10:02:15\nHello\n[alice] 10:02:18\nAgain\n[Bob] 10:02:20\nHi\n",
+        )
+        .unwrap();
+
+        let collection = import_all_from_path_blocking(temp.path()).unwrap();
+
+        assert_eq!(collection.humans.len(), 2);
+        assert_eq!(collection.participants.len(), 2);
+        assert_eq!(collection.transcripts[0].speaker_hints.len(), 2);
+    }
+
+    #[test]
+    fn skips_text_files_that_are_not_transcripts() {
+        let temp = tempfile::tempdir().unwrap();
+        std::fs::write(temp.path().join("notes.txt"), "Buy milk\nCall: Alice").unwrap();
+
+        let collection = import_all_from_path_blocking(temp.path()).unwrap();
+        assert!(collection.sessions.is_empty());
+    }
+
+    #[test]
+    fn scans_only_caption_named_text_files_near_the_top() {
+        let temp = tempfile::tempdir().unwrap();
+        let captions = "[Jane Doe] 10:02:15\nHello everyone\n";
+        std::fs::write(temp.path().join("standup notes.txt"), captions).unwrap();
+        std::fs::write(temp.path().join("closed_caption.txt"), captions).unwrap();
+
+        let nested = temp.path().join("a/b/c");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(nested.join("closed_caption.txt"), captions).unwrap();
+
+        let collection = import_all_from_path_blocking(temp.path()).unwrap();
+        assert_eq!(collection.sessions.len(), 1);
+
+        let picked = temp.path().join("standup notes.txt");
+        let collection = import_all_from_path_blocking(&picked).unwrap();
+        assert_eq!(collection.sessions.len(), 1);
+    }
+}
@@ -0,0 +1,354 @@
+use std::path::Path;
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+// Teams wraps every cue in a WebVTT voice span: `<v Jane Doe>Hello</v>`.
+static VOICE_TAG_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^<v(?:\.[^\s>]+)*\s+([^>]+)>").unwrap());
+static TAG_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"</?[^>]+>").unwrap());
+// Zoom and Meet prefix cue text with the speaker name: `Jane Doe: Hello`.
+static SPEAKER_PREFIX_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^([^:\n]{1,48}?):\s+(.+)$").unwrap());
+
+// Zoom `meeting_saved_closed_caption.txt`: `[Jane Doe] 10:02:15` followed by the text.
+static BRACKETED_SPEAKER_LINE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^\[([^\]]+)\]\s+(\d{1,2}:\d{2}(?::\d{2})?(?:[.,]\d+)?)\s*$").unwrap()
+});
+// Meet exports: a bare timestamp line, then `Speaker: text` lines.
+static TIMESTAMP_LINE_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\[?(\d{1,2}:\d{2}(?::\d{2})?(?:[.,]\d+)?)\]?\s*$").unwrap());
+// Inline form: `00:01:02 Jane Doe: text`.
+static INLINE_TIMESTAMP_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\[?(\d{1,2}:\d{2}(?::\d{2})?(?:[.,]\d+)?)\]?\s+(.+)$").unwrap());
+
+const FALLBACK_MS_PER_WORD: u64 = 400;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cue {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub speaker: Option<String>,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedWord {
+    pub text: String,
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+pub fn is_transcript_file(path: &Path) -> bool {
+    matches!(
+        extension(path).as_deref(),
+        Some("vtt") | Some("srt") | Some("txt")
+    )
+}
+
+pub fn parse_file(path: &Path) -> Result<Vec<Cue>, crate::Error> {
+    match extension(path).as_deref() {
+        Some("txt") => {
+            let content = std::fs::read_to_string(path)?;
+            Ok(parse_plain_text(&content))
+        }
+        _ => {
+            let subtitle = hypr_listener2_core::parse_subtitle_from_path(path)
+                .map_err(crate::Error::InvalidData)?;
+            Ok(subtitle
+                .tokens
+                .into_iter()
+                .filter_map(|token| {
+                    cue_from_subtitle(
+                        &token.text,
+                        token.start_time,
+                        token.end_time,
+                        token.speaker.as_deref(),
+                    )
+                })
+                .collect())
+        }
+    }
+}
+
+fn extension(path: &Path) -> Option<String> {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase())
+}
+
+fn cue_from_subtitle(
+    raw: &str,
+    start_ms: u64,
+    end_ms: u64,
+    identifier: Option<&str>,
+) -> Option<Cue> {
+    let raw = raw.trim();
+    let voice = VOICE_TAG_REGEX
+        .captures(raw)
+        .map(|caps| caps[1].trim().to_string());
+    let text = TAG_REGEX.replace_all(raw, "");
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let (speaker, text) = match voice {
+        Some(voice) => (Some(voice), text),
+        None => match split_speaker_prefix(&text) {
+            Some((speaker, rest)) => (Some(speaker), rest),
+            None => (
+                identifier.filter(|id| is_name_like(id)).map(String::from),
+                text,
+            ),
+        },
+    };
+
+    if text.is_empty() {
+        return None;
+    }
+
+    Some(Cue {
+        start_ms,
+        end_ms: end_ms.max(start_ms),
+        speaker,
+        text,
+    })
+}
+
+pub fn parse_plain_text(content: &str) -> Vec<Cue> {
+    let mut cues: Vec<Cue> = Vec::new();
+    let mut saw_timestamp = false;
+    let mut current_ms: Option<u64> = None;
+    let mut current_speaker: Option<String> = None;
+
+    for line in content.lines() {
+        let line = line.trim().trim_start_matches('\u{feff}');
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(caps) = BRACKETED_SPEAKER_LINE_REGEX.captures(line) {
+            saw_timestamp = true;
+            current_speaker = Some(caps[1].trim().to_string());
+            current_ms = parse_timestamp_ms(&caps[2]);
+            continue;
+        }
+
+        if let Some(caps) = TIMESTAMP_LINE_REGEX.captures(line) {
+            saw_timestamp = true;
+            current_ms = parse_timestamp_ms(&caps[1]);
+            continue;
+        }
+
+        if let Some(caps) = INLINE_TIMESTAMP_REGEX.captures(line)
+            && let Some(ms) = parse_timestamp_ms(&caps[1])
+        {
+            saw_timestamp = true;
+            let rest = caps[2].trim();
+            let (speaker, text) = match split_speaker_prefix(rest) {
+                Some((speaker, text)) => (Some(speaker), text),
+                None => (current_speaker.clone(), rest.to_string()),
+            };
+            current_speaker = speaker.clone();
+            push_cue(&mut cues, Some(ms), speaker, &text);
+            current_ms = None;
+            continue;
+        }
+
+        let (speaker, text) = match split_speaker_prefix(line) {
+            Some((speaker, text)) => {
+                current_speaker = Some(speaker.clone());
+                (Some(speaker), text)
+            }
+            None => (current_speaker.clone(), line.to_string()),
+        };
+        push_cue(&mut cues, current_ms.take(), speaker, &text);
+    }
+
+    if !saw_timestamp {
+        return Vec::new();
+    }
+
+    // Closed captions carry wall-clock times; rebase so the transcript starts at zero.
+    let base_ms = cues.iter().map(|c| c.start_ms).min().unwrap_or(0);
+    for cue in cues.iter_mut() {
+        cue.start_ms -= base_ms;
+    }
+
+    for idx in 0..cues.len() {
+        let next_start = cues.get(idx + 1).map(|next| next.start_ms);
+        cues[idx].end_ms = match next_start {
+            Some(next) if next > cues[idx].start_ms => next,
+            _ => estimated_end_ms(&cues[idx]),
+        };
+    }
+
+    cues
+}
+
+fn push_cue(cues: &mut Vec<Cue>, at: Option<u64>, speaker: Option<String>, text: &str) {
+    let text = text.trim();
+    if text.is_empty() {
+        return;
+    }
+
+    let start_ms = at.unwrap_or_else(|| cues.last().map(estimated_end_ms).unwrap_or(0));
+    cues.push(Cue {
+        start_ms,
+        end_ms: start_ms,
+        speaker,
+        text: text.to_string(),
+    });
+}
+
+fn estimated_end_ms(cue: &Cue) -> u64 {
+    cue.start_ms + FALLBACK_MS_PER_WORD * cue.text.split_whitespace().count() as u64
+}
+
+fn split_speaker_prefix(text: &str) -> Option<(String, String)> {
+    let caps = SPEAKER_PREFIX_REGEX.captures(text)?;
+    let speaker = caps[1].trim();
+    if !is_name_like(speaker) {
+        return None;
+    }
+    Some((speaker.to_string(), caps[2].trim().to_string()))
+}
+
+fn is_name_like(value: &str) -> bool {
+    let value = value.trim();
+    if value.is_empty() || value.contains("://") || value.contains('/') {
+        return false;
+    }
+    if !value.chars().any(|c| c.is_alphabetic()) {
+        return false;
+    }
+    // Teams cue identifiers look like `8a1f9c3e-...-4b2d/17-0`, never like a name.
+    let hex_or_dash = value
+        .chars()
+        .filter(|c| c.is_ascii_hexdigit() || *c == '-')
+        .count();
+    if value.len() >= 16 && hex_or_dash == value.len() {
+        return false;
+    }
+    value.split_whitespace().count() <= 6
+}
+
+fn parse_timestamp_ms(raw: &str) -> Option<u64> {
+    let (clock, fraction) = match raw.split_once(['.', ',']) {
+        Some((clock, fraction)) => (clock, Some(fraction)),
+        None => (raw, None),
+    };
+
+    let parts = clock
+        .split(':')
+        .map(|part| part.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let seconds = match parts.as_slice() {
+        [m, s] => m * 60 + s,
+        [h, m, s] => h * 3600 + m * 60 + s,
+        _ => return None,
+    };
+
+    let millis = match fraction {
+        Some(fraction) => format!("{:0<3}", fraction)[..3].parse::<u64>().ok()?,
+        None => 0,
+    };
+
+    Some(seconds * 1000 + millis)
+}
+
+// Spreads a cue's time span over its words proportionally to their length.
+pub fn split_words(cue: &Cue) -> Vec<TimedWord> {
+    let tokens: Vec<&str> = cue.text.split_whitespace().collect();
+    if tokens.is_empty() {
+        return Vec::new();
+    }
+
+    let total_chars: u64 = tokens.iter().map(|t| t.chars().count() as u64).sum();
+    let span = cue.end_ms.saturating_sub(cue.start_ms);
+
+    let mut cursor = 0u64;
+    tokens
+        .iter()
+        .map(|token| {
+            let start_ms = cue.start_ms + span * cursor / total_chars.max(1);
+            cursor += token.chars().count() as u64;
+            let end_ms = cue.start_ms + span * cursor / total_chars.max(1);
+            TimedWord {
+                text: format!(" {}", token),
+                start_ms,
+                end_ms,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_teams_voice_tags() {
+        let cue = cue_from_subtitle(
+            "<v Jane Doe>Hello there.</v>",
+            1_000,
+            2_000,
+            Some("8a1f9c3e-7d2b-4b2d-9a51-1e8c0f6d2a10/17-0"),
+        )
+        .unwrap();
+        assert_eq!(cue.speaker.as_deref(), Some("Jane Doe"));
+        assert_eq!(cue.text, "Hello there.");
+    }
+
+    #[test]
+    fn parses_zoom_speaker_prefix_and_ignores_numeric_identifiers() {
+        let cue = cue_from_subtitle("John Smith: Good morning", 0, 1_500, Some("12")).unwrap();
+        assert_eq!(cue.speaker.as_deref(), Some("John Smith"));
+        assert_eq!(cue.text, "Good morning");
+
+        let cue = cue_from_subtitle("No speaker here", 0, 1_500, Some("12")).unwrap();
+        assert_eq!(cue.speaker, None);
+    }
+
+    #[test]
+    fn parses_zoom_closed_caption_txt() {
+        let content = "[Jane Doe] 10:02:15\nHello everyone\n\n[John Smith] 10:02:18\nHi Jane\n";
+        let cues = parse_plain_text(content);
+
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].speaker.as_deref(), Some("Jane Doe"));
+        assert_eq!(cues[0].start_ms, 0);
+        assert_eq!(cues[0].end_ms, 3_000);
+        assert_eq!(cues[1].speaker.as_deref(), Some("John Smith"));
+        assert_eq!(cues[1].start_ms, 3_000);
+    }
+
+    #[test]
+    fn parses_meet_txt() {
+        let content = "00:00:00\nJane Doe: Let's start.\nJohn Smith: Sure.\n00:00:05\nJane Doe: Agenda first.\n";
+        let cues = parse_plain_text(content);
+
+        assert_eq!(cues.len(), 3);
+        assert_eq!(cues[1].speaker.as_deref(), Some("John Smith"));
+        assert_eq!(cues[2].start_ms, 5_000);
+    }
+
+    #[test]
+    fn rejects_txt_without_timestamps() {
+        assert!(parse_plain_text("Just some notes\nwith: colons").is_empty());
+    }
+
+    #[test]
+    fn splits_words_across_cue_span() {
+        let cue = Cue {
+            start_ms: 1_000,
+            end_ms: 2_000,
+            speaker: None,
+            text: "ab cd".to_string(),
+        };
+        let words = split_words(&cue);
+
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].text, " ab");
+        assert_eq!((words[0].start_ms, words[0].end_ms), (1_000, 1_500));
+        assert_eq!((words[1].start_ms, words[1].end_ms), (1_500, 2_000));
+    }
+}
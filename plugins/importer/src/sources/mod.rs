@@ -1,6 +1,9 @@
+mod apple_notes;
 mod as_is;
 mod granola;
 mod hyprnote;
+mod markdown_vault;
+mod meeting_transcript;
 
 pub use as_is::AsIsData;
 
@@ -10,6 +13,11 @@ pub async fn import_all(source: &ImportSource) -> Result<Collection, crate::Erro
     match source.transform {
         TransformKind::HyprnoteV0 => hyprnote::v0::import_all_from_path(&source.path).await,
         TransformKind::Granola => granola::import_all_from_path(&source.path).await,
+        TransformKind::MeetingTranscript => {
+            meeting_transcript::import_all_from_path(&source.path).await
+        }
+        TransformKind::AppleNotes => apple_notes::import_all_from_path(&source.path).await,
+        TransformKind::MarkdownVault => markdown_vault::import_all_from_path(&source.path).await,
         TransformKind::AsIs => as_is::load_data(&source.path),
     }
 }
@@ -18,6 +26,7 @@ pub fn all_sources() -> Vec<ImportSource> {
     [
         ImportSource::hyprnote_stable(),
         ImportSource::hyprnote_nightly(),
+        ImportSource::meeting_transcripts(),
     ]
     .into_iter()
     .flatten()
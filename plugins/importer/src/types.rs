@@ -2,8 +2,8 @@ use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 pub use hypr_importer_core::ir::{
-    Collection, EnhancedNote, Human, Organization, Session, SessionParticipant, Tag, TagMapping,
-    Template, TemplateSection, Transcript, Word,
+    Attachment, AttachmentKind, Collection, EnhancedNote, Human, Organization, Session,
+    SessionParticipant, SpeakerHint, Tag, TagMapping, Template, TemplateSection, Transcript, Word,
 };
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, specta::Type, PartialEq, Eq, Hash)]
@@ -11,6 +11,9 @@ pub use hypr_importer_core::ir::{
 pub enum TransformKind {
     HyprnoteV0,
     Granola,
+    MeetingTranscript,
+    AppleNotes,
+    MarkdownVault,
     AsIs,
 }
 
@@ -20,6 +23,9 @@ pub enum ImportSourceKind {
     Granola,
     HyprnoteV0Stable,
     HyprnoteV0Nightly,
+    MeetingTranscripts,
+    AppleNotes,
+    MarkdownVault,
     AsIs,
 }
 
@@ -75,6 +81,30 @@ impl ImportSource {
         })
     }
 
+    pub fn meeting_transcripts() -> Option<Self> {
+        let path = dirs::document_dir()?.join("Zoom");
+        Some(Self {
+            kind: Some(ImportSourceKind::MeetingTranscripts),
+            transform: TransformKind::MeetingTranscript,
+            path,
+            name: "Meeting Transcripts".to_string(),
+        })
+    }
+
+    pub fn apple_notes() -> Option<Self> {
+        let path = dirs::home_dir()?
+            .join("Library")
+            .join("Group Containers")
+            .join("group.com.apple.notes")
+            .join("NoteStore.sqlite");
+        Some(Self {
+            kind: Some(ImportSourceKind::AppleNotes),
+            transform: TransformKind::AppleNotes,
+            path,
+            name: "Apple Notes".to_string(),
+        })
+    }
+
     pub fn is_available(&self) -> bool {
         self.path.exists()
     }
@@ -113,6 +143,14 @@ impl From<ImportSourceKind> for ImportSource {
             ImportSourceKind::HyprnoteV0Stable => Self::hyprnote_stable().unwrap(),
             ImportSourceKind::HyprnoteV0Nightly => Self::hyprnote_nightly().unwrap(),
             ImportSourceKind::Granola => Self::granola().unwrap(),
+            ImportSourceKind::MeetingTranscripts => Self::meeting_transcripts().unwrap(),
+            ImportSourceKind::AppleNotes => Self::apple_notes().unwrap(),
+            ImportSourceKind::MarkdownVault => Self {
+                kind: Some(ImportSourceKind::MarkdownVault),
+                transform: TransformKind::MarkdownVault,
+                path: PathBuf::new(),
+                name: "Markdown Folder".to_string(),
+            },
             ImportSourceKind::AsIs => Self {
                 kind: Some(ImportSourceKind::AsIs),
                 transform: TransformKind::AsIs,
@@ -143,6 +181,7 @@ pub struct ImportStats {
     pub participants_count: usize,
     pub templates_count: usize,
     pub enhanced_notes_count: usize,
+    pub attachments_count: usize,
 }
 
 impl ImportStats {
@@ -155,6 +194,7 @@ impl ImportStats {
             participants_count: data.participants.len(),
             templates_count: data.templates.len(),
             enhanced_notes_count: data.enhanced_notes.len(),
+            attachments_count: data.attachments.len(),
         }
     }
 }
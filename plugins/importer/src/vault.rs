@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use hypr_fs_sync_core::FsSyncCore;
+
+use crate::types::{Attachment, AttachmentKind, Collection};
+
+// Creates the vault folders referenced by imported sessions and copies their attachments in.
+// Attachments renamed on save, e.g. because the session already has a file with that name, get
+// their `attachments/` links in the session's markdown pointed at the saved file.
+pub async fn save_all(vault_base: PathBuf, data: &mut Collection) -> Result<(), crate::Error> {
+    let attachments = data.attachments.clone();
+    let folders: Vec<(String, String)> = data
+        .sessions
+        .iter()
+        .filter_map(|s| {
+            s.folder_id
+                .as_ref()
+                .filter(|f| !f.is_empty())
+                .map(|f| (s.id.clone(), f.clone()))
+        })
+        .collect();
+
+    let renamed = tokio::task::spawn_blocking(move || {
+        let mut renamed: HashMap<String, HashMap<String, String>> = HashMap::new();
+        let core = FsSyncCore::new(vault_base.clone());
+        let sessions_dir = vault_base.join("sessions");
+
+        let mut folder_paths: Vec<&str> = folders.iter().map(|(_, f)| f.as_str()).collect();
+        folder_paths.sort();
+        folder_paths.dedup();
+        for folder in folder_paths {
+            if let Err(e) = core.create_folder(folder) {
+                tracing::warn!(folder = %folder, "import_folder_failed: {}", e);
+            }
+        }
+
+        for attachment in &attachments {
+            let folder = folders
+                .iter()
+                .find(|(id, _)| id == &attachment.session_id)
+                .map(|(_, folder)| folder.as_str());
+
+            match save_one(&core, &sessions_dir, folder, attachment) {
+                Ok(Some(saved))
+                    if !attachment.filename.is_empty() && saved != attachment.filename =>
+                {
+                    renamed
+                        .entry(attachment.session_id.clone())
+                        .or_default()
+                        .insert(attachment.filename.clone(), saved);
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!(
+                        session_id = %attachment.session_id,
+                        source_path = %attachment.source_path,
+                        "import_attachment_failed: {}",
+                        e
+                    );
+                }
+            }
+        }
+
+        renamed
+    })
+    .await
+    .map_err(|e| crate::Error::InvalidData(e.to_string()))?;
+
+    for session in &mut data.sessions {
+        if let (Some(renamed), Some(md)) = (renamed.get(&session.id), session.raw_md.as_mut()) {
+            *md = relink_attachments(md, renamed);
+        }
+    }
+
+    Ok(())
+}
+
+// Points every `](attachments/<name>)` link whose file was saved under another name at the saved
+// file. Done in one pass so a file renamed to a name another link already uses isn't relinked twice.
+fn relink_attachments(markdown: &str, renamed: &HashMap<String, String>) -> String {
+    const LINK_PREFIX: &str = "](attachments/";
+
+    let mut output = String::with_capacity(markdown.len());
+    let mut rest = markdown;
+    while let Some(start) = rest.find(LINK_PREFIX) {
+        let target_start = start + LINK_PREFIX.len();
+        output.push_str(&rest[..target_start]);
+        rest = &rest[target_start..];
+
+        let end = rest.find(')').unwrap_or(rest.len());
+        let target = &rest[..end];
+        match renamed.get(&target.replace("%20", " ")) {
+            Some(saved) => output.push_str(&saved.replace(' ', "%20")),
+            None => output.push_str(target),
+        }
+        rest = &rest[end..];
+    }
+    output.push_str(rest);
+    output
+}
+
+fn save_one(
+    core: &FsSyncCore,
+    sessions_dir: &Path,
+    folder: Option<&str>,
+    attachment: &Attachment,
+) -> Result<Option<String>, crate::Error> {
+    let existing = core.resolve_session_dir(&attachment.session_id);
+    let session_dir = match folder {
+        Some(folder) if !existing.exists() => {
+            sessions_dir.join(folder).join(&attachment.session_id)
+        }
+        _ => existing,
+    };
+    std::fs::create_dir_all(&session_dir)?;
+
+    let source_path = Path::new(&attachment.source_path);
+    match attachment.kind {
+        AttachmentKind::Audio => {
+            hypr_fs_sync_core::audio::import_to_session(&session_dir, source_path)
+                .map_err(|e| crate::Error::InvalidData(e.to_string()))?;
+            Ok(None)
+        }
+        AttachmentKind::File => {
+            let bytes = std::fs::read(source_path)?;
+            let filename = if attachment.filename.is_empty() {
+                source_path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| "attachment".to_string())
+            } else {
+                attachment.filename.clone()
+            };
+            let saved = core.attachment_save(&attachment.session_id, &bytes, &filename)?;
+            Ok(Some(saved.attachment_id))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relinks_renamed_attachments_once() {
+        let renamed = HashMap::from([
+            ("image.png".to_string(), "image 1.png".to_string()),
+            ("image 1.png".to_string(), "image 2.png".to_string()),
+        ]);
+        let markdown = "![image.png](attachments/image.png)\n\
+                        ![image.png](attachments/image%201.png)\n\
+                        [notes.pdf](attachments/notes.pdf)";
+
+        assert_eq!(
+            relink_attachments(markdown, &renamed),
+            "![image.png](attachments/image%201.png)\n\
+             ![image.png](attachments/image%202.png)\n\
+             [notes.pdf](attachments/notes.pdf)"
+        );
+    }
+}
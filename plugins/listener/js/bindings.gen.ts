@@ -110,11 +110,21 @@ sessionProgressEvent: "plugin:listener:session-progress-event"
 /** user-defined types **/
 
 export type DegradedError = { type: "authentication_failed"; provider: string } | { type: "upstream_unavailable"; message: string } | { type: "connection_timeout" } | { type: "stream_error"; message: string }
+/**
+ * What is known about a meeting before recording starts, used to bias transcription toward
+ * the people and terms likely to come up.
+ */
+export type MeetingContext = { title: string; description?: string | null; participants?: MeetingParticipant[] }
+export type MeetingParticipant = { name?: string | null; email?: string | null }
 export type Source = "microphone" | "speaker"
 export type SessionDataEvent = { type: "audio_amplitude"; session_id: string; mic: number; speaker: number } | { type: "speaker_activity"; session_id: string; source: Source; is_speaking: boolean } | { type: "mic_muted"; session_id: string; value: boolean } | { type: "stream_response"; session_id: string; response: StreamResponse }
 export type SessionErrorEvent = { type: "audio_error"; session_id: string; error: string; device: string | null; is_fatal: boolean } | { type: "connection_error"; session_id: string; error: string }
 export type SessionLifecycleEvent = { type: "inactive"; session_id: string; error: string | null } | { type: "active"; session_id: string; error?: DegradedError | null } | { type: "finalizing"; session_id: string }
-export type SessionParams = { session_id: string; languages: string[]; onboarding: boolean; record_enabled: boolean; model: string; base_url: string; api_key: string; keywords: string[] }
+export type SessionParams = { session_id: string; languages: string[]; onboarding: boolean; record_enabled: boolean; model: string; base_url: string; api_key: string; keywords: string[]; vocabulary?: Vocabulary; 
+/**
+ * Calendar context for the recording; its glossary is sent ahead of `keywords`.
+ */
+meeting?: MeetingContext | null }
 export type SessionProgressEvent = { type: "audio_initializing"; session_id: string } | { type: "audio_ready"; session_id: string; device: string | null } | { type: "connecting"; session_id: string } | { type: "connected"; session_id: string; adapter: string }
 export type State = "active" | "inactive" | "finalizing"
 export type StreamAlternatives = { transcript: string; words: StreamWord[]; confidence: number; languages?: string[] }
@@ -124,6 +134,26 @@ export type StreamMetadata = { request_id: string; model_info: StreamModelInfo;
 export type StreamModelInfo = { name: string; version: string; arch: string }
 export type StreamResponse = { type: "Results"; start: number; duration: number; is_final: boolean; speech_final: boolean; from_finalize: boolean; channel: StreamChannel; metadata: StreamMetadata; channel_index: number[] } | { type: "Metadata"; request_id: string; created: string; duration: number; channels: number } | { type: "SpeechStarted"; channel: number[]; timestamp: number } | { type: "UtteranceEnd"; channel: number[]; last_word_end: number } | { type: "Error"; error_code: number | null; error_message: string; provider: string }
 export type StreamWord = { word: string; start: number; end: number; confidence: number; speaker: number | null; punctuated_word: string | null; language: string | null }
+/**
+ * Terms and replacements for one transcription, merged from the enabled profiles.
+ */
+export type Vocabulary = { terms?: VocabularyTerm[]; replacements?: VocabularyReplacement[] }
+export type VocabularyReplacement = { from: string; to: string }
+export type VocabularyTerm = { 
+/**
+ * Canonical spelling, e.g. "Hyprnote".
+ */
+text: string; 
+/**
+ * Relative emphasis from 0.0 to 1.0. Adapters with a weighting knob scale it to their
+ * own range; the rest ignore it.
+ */
+boost?: number | null; 
+/**
+ * How the term tends to be misheard, e.g. "hyper note". Sent as pronunciation hints
+ * where supported, and always rewritten to `text` after transcription.
+ */
+sounds_like?: string[] }
 
 /** tauri-specta globals **/
 
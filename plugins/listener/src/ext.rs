@@ -3,7 +3,6 @@ use ractor::{ActorRef, call_t, registry};
 use hypr_listener_core::actors::{RootActor, RootMsg, SessionParams, SourceActor, SourceMsg};
 
 pub struct Listener<'a, R: tauri::Runtime, M: tauri::Manager<R>> {
-    #[allow(unused)]
     manager: &'a M,
     _runtime: std::marker::PhantomData<fn() -> R>,
 }
@@ -59,7 +58,13 @@ impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> Listener<'a, R, M> {
     }
 
     #[tracing::instrument(skip_all)]
-    pub async fn start_session(&self, params: SessionParams) {
+    pub async fn start_session(&self, mut params: SessionParams) {
+        use tauri_plugin_settings::SettingsPluginExt;
+
+        params.vocabulary = params
+            .vocabulary
+            .with_vault_settings(self.manager.settings().load().await);
+
         if let Some(cell) = registry::where_is(RootActor::name()) {
             let actor: ActorRef<RootMsg> = cell.into();
             let _ = ractor::call!(actor, RootMsg::StartSession, params);
@@ -38,6 +38,14 @@ async exportToVtt(sessionId: string, words: VttWord[]) : Promise<Result<string,
     else return { status: "error", error: e  as any };
 }
 },
+async assignSpeakers(params: SpeakerAssignmentParams) : Promise<Result<SpeakerAssignment[], string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plugin:listener2|assign_speakers", { params }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async isSupportedLanguagesBatch(provider: string, model: string | null, languages: string[]) : Promise<Result<boolean, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("plugin:listener2|is_supported_languages_batch", { provider, model, languages }) };
@@ -85,7 +93,7 @@ export type BatchAlternatives = { transcript: string; confidence: number; words?
 export type BatchChannel = { alternatives: BatchAlternatives[] }
 export type BatchErrorCode = "unknown" | "audio_metadata_join_failed" | "audio_metadata_read_failed" | "provider_request_failed" | "actor_spawn_failed" | "stream_start_cancelled" | "stream_stopped_without_completion_signal" | "stream_finished_without_status" | "stream_start_failed" | "stream_error" | "stream_timeout"
 export type BatchEvent = { type: "batchStarted"; session_id: string } | { type: "batchCompleted"; session_id: string } | { type: "batchResponse"; session_id: string; response: BatchResponse; mode: BatchRunMode } | { type: "batchProgress"; session_id: string; response: StreamResponse; percentage: number } | { type: "batchFailed"; session_id: string; code: BatchErrorCode; error: string }
-export type BatchParams = { session_id: string; provider: BatchProvider; file_path: string; model?: string | null; base_url: string; api_key: string; languages?: string[]; keywords?: string[] }
+export type BatchParams = { session_id: string; provider: BatchProvider; file_path: string; model?: string | null; base_url: string; api_key: string; languages?: string[]; keywords?: string[]; vocabulary?: Vocabulary }
 export type BatchProvider = "argmax" | "deepgram" | "soniox" | "assemblyai" | "fireworks" | "openai" | "gladia" | "elevenlabs" | "dashscope" | "mistral" | "hyprnote" | "am" | "cactus"
 export type BatchResponse = { metadata: JsonValue; results: BatchResults }
 export type BatchResults = { channels: BatchChannel[] }
@@ -94,7 +102,25 @@ export type BatchRunOutput = { session_id: string; mode: BatchRunMode; response:
 export type BatchWord = { word: string; start: number; end: number; confidence: number; speaker: number | null; punctuated_word: string | null }
 export type DenoiseEvent = { type: "denoiseStarted"; session_id: string } | { type: "denoiseProgress"; session_id: string; percentage: number } | { type: "denoiseCompleted"; session_id: string } | { type: "denoiseFailed"; session_id: string; error: string }
 export type DenoiseParams = { session_id: string; input_path: string; output_path: string }
+export type FinalizedWord = { id: string; text: string; start_ms: number; end_ms: number; channel: number; state: WordState }
 export type JsonValue = null | boolean | number | string | JsonValue[] | Partial<{ [key in string]: JsonValue }>
+export type SpeakerAssignment = { key: SpeakerKey; identity: SpeakerIdentity; overlap_ms: number }
+export type SpeakerAssignmentParams = { 
+/**
+ * Wall-clock start of the recording; word times are relative to it.
+ */
+started_at_ms: number; ended_at_ms: number; words: FinalizedWord[]; hints: SpeakerHint[]; 
+/**
+ * Participant names as the meeting shows them, mapped to human ids.
+ */
+human_ids: Partial<{ [key in string]: string }> }
+export type SpeakerHint = { word_id: string; speaker_index: number }
+export type SpeakerIdentity = { type: "unassigned"; value: { index: number } } | { type: "assigned"; value: { id: string; label: string } }
+/**
+ * What a name is assigned to: a diarized speaker, or a whole channel for words without a
+ * speaker hint.
+ */
+export type SpeakerKey = { type: "index"; value: number } | { type: "channel"; value: number }
 export type StreamAlternatives = { transcript: string; words: StreamWord[]; confidence: number; languages?: string[] }
 export type StreamChannel = { alternatives: StreamAlternatives[] }
 export type StreamExtra = { started_unix_millis: number }
@@ -104,7 +130,36 @@ export type StreamResponse = { type: "Results"; start: number; duration: number;
 export type StreamWord = { word: string; start: number; end: number; confidence: number; speaker: number | null; punctuated_word: string | null; language: string | null }
 export type Subtitle = { tokens: Token[] }
 export type Token = { text: string; start_time: number; end_time: number; speaker: string | null }
+/**
+ * Terms and replacements for one transcription, merged from the enabled profiles.
+ */
+export type Vocabulary = { terms?: VocabularyTerm[]; replacements?: VocabularyReplacement[] }
+export type VocabularyReplacement = { from: string; to: string }
+export type VocabularyTerm = { 
+/**
+ * Canonical spelling, e.g. "Hyprnote".
+ */
+text: string; 
+/**
+ * Relative emphasis from 0.0 to 1.0. Adapters with a weighting knob scale it to their
+ * own range; the rest ignore it.
+ */
+boost?: number | null; 
+/**
+ * How the term tends to be misheard, e.g. "hyper note". Sent as pronunciation hints
+ * where supported, and always rewritten to `text` after transcription.
+ */
+sounds_like?: string[] }
 export type VttWord = { text: string; start_ms: number; end_ms: number; speaker: string | null }
+/**
+ * Whether a finalized word is stable or awaiting correction.
+ *
+ * A word is `Pending` when it has been confirmed by the STT model but a
+ * correction source (cloud STT fallback, LLM postprocessor, etc.) is still
+ * processing it. The word has an ID and is persisted, but its text may be
+ * replaced when the correction resolves via `TranscriptDelta::replaced_ids`.
+ */
+export type WordState = "final" | "pending"
 
 /** tauri-specta globals **/
 
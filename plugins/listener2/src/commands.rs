@@ -35,6 +35,15 @@ pub async fn export_to_vtt<R: tauri::Runtime>(
     app.listener2().export_to_vtt(session_id, words)
 }
 
+#[tauri::command]
+#[specta::specta]
+pub async fn assign_speakers<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    params: core::SpeakerAssignmentParams,
+) -> Result<Vec<core::SpeakerAssignment>, String> {
+    app.listener2().assign_speakers(params)
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn run_denoise<R: tauri::Runtime>(
@@ -11,8 +11,14 @@ pub struct Listener2<'a, R: tauri::Runtime, M: tauri::Manager<R>> {
 impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> Listener2<'a, R, M> {
     pub async fn run_batch(
         &self,
-        params: core::BatchParams,
+        mut params: core::BatchParams,
     ) -> Result<core::BatchRunOutput, core::Error> {
+        use tauri_plugin_settings::SettingsPluginExt;
+
+        params.vocabulary = params
+            .vocabulary
+            .with_vault_settings(self.manager.settings().load().await);
+
         let state = self.manager.state::<crate::SharedState>();
         let guard = state.lock().await;
         let app = guard.app.clone();
@@ -57,6 +63,16 @@ impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> Listener2<'a, R, M> {
         core::export_words_to_vtt_file(words, &vtt_path)?;
         Ok(vtt_path.to_string())
     }
+
+    pub fn assign_speakers(
+        &self,
+        params: core::SpeakerAssignmentParams,
+    ) -> Result<Vec<core::SpeakerAssignment>, String> {
+        let Some(dir) = core::chrome_speaker_timeline_dir() else {
+            return Ok(Vec::new());
+        };
+        core::assign_speakers_from_timelines(dir, &params)
+    }
 }
 
 pub trait Listener2PluginExt<R: tauri::Runtime> {
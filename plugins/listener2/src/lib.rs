@@ -25,6 +25,7 @@ fn make_specta_builder<R: tauri::Runtime>() -> tauri_specta::Builder<R> {
             commands::run_denoise::<tauri::Wry>,
             commands::parse_subtitle::<tauri::Wry>,
             commands::export_to_vtt::<tauri::Wry>,
+            commands::assign_speakers::<tauri::Wry>,
             commands::is_supported_languages_batch::<tauri::Wry>,
             commands::suggest_providers_for_languages_batch::<tauri::Wry>,
             commands::list_documented_language_codes_batch::<tauri::Wry>,
@@ -33,6 +33,10 @@ impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> Misc<'a, R, M> {
     pub fn parse_meeting_link(&self, text: impl AsRef<str>) -> Option<String> {
         let text = text.as_ref();
 
+        if let Some(meeting) = hypr_calendar_interface::MeetingLink::parse(text) {
+            return Some(meeting.url);
+        }
+
         for regex in MEETING_REGEXES.iter() {
             if let Some(capture) = regex.find(text) {
                 return Some(capture.as_str().to_string());
@@ -69,9 +73,8 @@ impl<R: tauri::Runtime, T: tauri::Manager<R>> MiscPluginExt<R> for T {
 }
 
 lazy_static::lazy_static! {
+    // Platforms `MeetingLink` does not cover.
     pub static ref MEETING_REGEXES: Vec<regex::Regex> = vec![
-        regex::Regex::new(r"https://meet\.google\.com/[a-z0-9]{3,4}-[a-z0-9]{3,4}-[a-z0-9]{3,4}").unwrap(),
-        regex::Regex::new(r"https://[a-z0-9.-]+\.zoom\.us/j/\d+(\?pwd=[a-zA-Z0-9.]+)?").unwrap(),
         regex::Regex::new(r"https://app\.cal\.com/video/[a-zA-Z0-9]+").unwrap(),
     ];
 }
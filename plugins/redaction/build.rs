@@ -0,0 +1,5 @@
+const COMMANDS: &[&str] = &["redact", "restore"];
+
+fn main() {
+    tauri_plugin::Builder::new(COMMANDS).build();
+}
@@ -0,0 +1,118 @@
+// @ts-nocheck
+
+// This file was generated by [tauri-specta](https://github.com/oscartbeaumont/tauri-specta). Do not edit this file manually.
+
+/** user-defined commands **/
+
+
+export const commands = {
+async redact(scope: RedactionScope, texts: string[], placeholders: Placeholder[]) : Promise<Result<RedactOutput, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("plugin:redaction|redact", { scope, texts, placeholders }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+async restore(text: string, placeholders: Placeholder[]) : Promise<string> {
+    return await TAURI_INVOKE("plugin:redaction|restore", { text, placeholders });
+}
+}
+
+/** user-defined events **/
+
+
+
+/** user-defined constants **/
+
+
+
+/** user-defined types **/
+
+export type CustomPattern = { 
+/**
+ * Becomes the placeholder prefix, e.g. `matter number` yields `[MATTER_NUMBER_1]`.
+ */
+name: string; pattern: string }
+export type PiiKind = "email" | "phone" | "card" | "iban" | "government_id" | "custom" | "term"
+export type Placeholder = { placeholder: string; original: string; kind: PiiKind }
+export type RedactOutput = { 
+/**
+ * False when the workspace has redaction turned off for the scope; `texts` are unchanged.
+ */
+applied: boolean; texts: string[]; 
+/**
+ * Pass back on the next call of the same conversation to keep placeholders stable, and
+ * to `restore` for the model's answer.
+ */
+placeholders: Placeholder[] }
+export type RedactionConfig = { kinds?: PiiKind[]; patterns?: CustomPattern[]; 
+/**
+ * Matched case-insensitively on word boundaries, e.g. client or project names.
+ */
+terms?: string[] }
+export type RedactionScope = "enhance" | "chat" | "export"
+/**
+ * Workspace settings, stored under `redaction` in the vault's settings file.
+ */
+export type RedactionSettings = ({ enhance?: boolean; chat?: boolean; export?: boolean }) & RedactionConfig
+
+/** tauri-specta globals **/
+
+import {
+	invoke as TAURI_INVOKE,
+	Channel as TAURI_CHANNEL,
+} from "@tauri-apps/api/core";
+import * as TAURI_API_EVENT from "@tauri-apps/api/event";
+import { type WebviewWindow as __WebviewWindow__ } from "@tauri-apps/api/webviewWindow";
+
+type __EventObj__<T> = {
+	listen: (
+		cb: TAURI_API_EVENT.EventCallback<T>,
+	) => ReturnType<typeof TAURI_API_EVENT.listen<T>>;
+	once: (
+		cb: TAURI_API_EVENT.EventCallback<T>,
+	) => ReturnType<typeof TAURI_API_EVENT.once<T>>;
+	emit: null extends T
+		? (payload?: T) => ReturnType<typeof TAURI_API_EVENT.emit>
+		: (payload: T) => ReturnType<typeof TAURI_API_EVENT.emit>;
+};
+
+export type Result<T, E> =
+	| { status: "ok"; data: T }
+	| { status: "error"; error: E };
+
+function __makeEvents__<T extends Record<string, any>>(
+	mappings: Record<keyof T, string>,
+) {
+	return new Proxy(
+		{} as unknown as {
+			[K in keyof T]: __EventObj__<T[K]> & {
+				(handle: __WebviewWindow__): __EventObj__<T[K]>;
+			};
+		},
+		{
+			get: (_, event) => {
+				const name = mappings[event as keyof T];
+
+				return new Proxy((() => {}) as any, {
+					apply: (_, __, [window]: [__WebviewWindow__]) => ({
+						listen: (arg: any) => window.listen(name, arg),
+						once: (arg: any) => window.once(name, arg),
+						emit: (arg: any) => window.emit(name, arg),
+					}),
+					get: (_, command: keyof __EventObj__<any>) => {
+						switch (command) {
+							case "listen":
+								return (arg: any) => TAURI_API_EVENT.listen(name, arg);
+							case "once":
+								return (arg: any) => TAURI_API_EVENT.once(name, arg);
+							case "emit":
+								return (arg: any) => TAURI_API_EVENT.emit(name, arg);
+						}
+					},
+				});
+			},
+		},
+	);
+}
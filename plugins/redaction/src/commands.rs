@@ -0,0 +1,27 @@
+use hypr_redaction::{Placeholder, RedactionScope};
+
+use crate::{RedactOutput, RedactionPluginExt};
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) async fn redact<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    scope: RedactionScope,
+    texts: Vec<String>,
+    placeholders: Vec<Placeholder>,
+) -> Result<RedactOutput, String> {
+    app.redaction()
+        .redact(scope, texts, placeholders)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[specta::specta]
+pub(crate) fn restore<R: tauri::Runtime>(
+    app: tauri::AppHandle<R>,
+    text: String,
+    placeholders: Vec<Placeholder>,
+) -> String {
+    app.redaction().restore(&text, placeholders)
+}
@@ -0,0 +1,22 @@
+use serde::{Serialize, ser::Serializer};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Redaction(#[from] hypr_redaction::Error),
+    #[error(transparent)]
+    Settings(#[from] tauri_plugin_settings::Error),
+    #[error(transparent)]
+    Json(#[from] serde_json::Error),
+}
+
+impl Serialize for Error {
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.to_string().as_ref())
+    }
+}
@@ -0,0 +1,72 @@
+use hypr_redaction::{Placeholder, PlaceholderMap, RedactionScope, RedactionSettings, Redactor};
+use tauri_plugin_settings::SettingsPluginExt;
+
+use crate::RedactOutput;
+
+const SETTINGS_KEY: &str = "redaction";
+
+pub struct Redaction<'a, R: tauri::Runtime, M: tauri::Manager<R>> {
+    manager: &'a M,
+    _runtime: std::marker::PhantomData<fn() -> R>,
+}
+
+impl<'a, R: tauri::Runtime, M: tauri::Manager<R>> Redaction<'a, R, M> {
+    pub async fn settings(&self) -> crate::Result<RedactionSettings> {
+        let settings = self.manager.settings().load().await?;
+        match settings.get(SETTINGS_KEY) {
+            Some(value) => Ok(serde_json::from_value(value.clone())?),
+            None => Ok(RedactionSettings::default()),
+        }
+    }
+
+    pub async fn redact(
+        &self,
+        scope: RedactionScope,
+        texts: Vec<String>,
+        placeholders: Vec<Placeholder>,
+    ) -> crate::Result<RedactOutput> {
+        let settings = self.settings().await?;
+        if !settings.applies_to(scope) {
+            return Ok(RedactOutput {
+                applied: false,
+                texts,
+                placeholders,
+            });
+        }
+
+        let redactor = Redactor::new(&settings.config)?;
+        let mut map = PlaceholderMap::from(placeholders);
+        let texts = texts
+            .iter()
+            .map(|text| redactor.redact(text, &mut map))
+            .collect();
+
+        Ok(RedactOutput {
+            applied: true,
+            texts,
+            placeholders: map.into(),
+        })
+    }
+
+    pub fn restore(&self, text: &str, placeholders: Vec<Placeholder>) -> String {
+        PlaceholderMap::from(placeholders).restore(text)
+    }
+}
+
+pub trait RedactionPluginExt<R: tauri::Runtime> {
+    fn redaction(&self) -> Redaction<'_, R, Self>
+    where
+        Self: tauri::Manager<R> + Sized;
+}
+
+impl<R: tauri::Runtime, T: tauri::Manager<R>> RedactionPluginExt<R> for T {
+    fn redaction(&self) -> Redaction<'_, R, Self>
+    where
+        Self: Sized,
+    {
+        Redaction {
+            manager: self,
+            _runtime: std::marker::PhantomData,
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use hypr_redaction::Placeholder;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize, specta::Type)]
+pub struct RedactOutput {
+    /// False when the workspace has redaction turned off for the scope; `texts` are unchanged.
+    pub applied: bool,
+    pub texts: Vec<String>,
+    /// Pass back on the next call of the same conversation to keep placeholders stable, and
+    /// to `restore` for the model's answer.
+    pub placeholders: Vec<Placeholder>,
+}
@@ -84,7 +84,11 @@ export type Navigate = { path: string; search: Partial<{ [key in string]: JsonVa
 export type OpenTab = { tab: TabInput }
 export type PromptsState = { selectedTask: string | null }
 export type SearchState = { selectedTypes: string[] | null; initialQuery: string | null }
-export type SessionsState = { view: EditorView | null; autoStart: boolean | null }
+export type SessionsState = { view: EditorView | null; autoStart: boolean | null; 
+/**
+ * Overrides the configured spoken languages for the auto-started recording.
+ */
+autoStartLanguage?: string | null }
 export type TabInput = { type: "sessions"; id: string; state?: SessionsState | null } | { type: "contacts"; state?: ContactsState | null } | { type: "templates"; state?: TemplatesState | null } | { type: "prompts"; state?: PromptsState | null } | { type: "chat_shortcuts"; state?: ChatShortcutsState | null } | { type: "extensions"; state?: ExtensionsState | null } | { type: "humans"; id: string } | { type: "organizations"; id: string } | { type: "folders"; id: string | null } | { type: "empty" } | { type: "extension"; extensionId: string; state?: Partial<{ [key in string]: JsonValue }> | null } | { type: "calendar" } | { type: "changelog"; state: ChangelogState } | { type: "settings" } | { type: "ai"; state?: AiState | null } | { type: "search"; state?: SearchState | null } | { type: "chat_support"; state?: ChatState | null } | { type: "onboarding" } | { type: "edit"; requestId: string }
 export type TemplatesState = { showHomepage: boolean | null; isWebMode: boolean | null; selectedMineId: string | null; selectedWebIndex: number | null }
 export type VisibilityEvent = { window: AppWindow; visible: boolean }
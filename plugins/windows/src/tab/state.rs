@@ -16,6 +16,10 @@ crate::common_derives! {
     pub struct SessionsState {
         pub view: Option<EditorView>,
         pub auto_start: Option<bool>,
+        /// Overrides the configured spoken languages for the auto-started recording.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[specta(optional)]
+        pub auto_start_language: Option<String>,
     }
 }
 